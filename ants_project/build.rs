@@ -0,0 +1,28 @@
+// Relève le commit compilé pour les manifestes d'expérience (voir src/experiment.rs). Hors
+// d'un dépôt git, ou sans git installé, la variable n'est pas définie.
+use std::process::Command;
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout)
+        .ok()
+        .map(|out| out.trim().to_string())
+}
+
+fn main() {
+    if let Some(commit) = git(&["rev-parse", "HEAD"]) {
+        println!("cargo:rustc-env=ANTS_GIT_COMMIT={}", commit);
+    }
+    // Relancer quand HEAD change de branche ou que la branche courante avance
+    if let Some(dir) = git(&["rev-parse", "--git-dir"]) {
+        println!("cargo:rerun-if-changed={}/HEAD", dir);
+        if let Some(branch) = git(&["symbolic-ref", "-q", "HEAD"]) {
+            println!("cargo:rerun-if-changed={}/{}", dir, branch);
+        }
+    } else {
+        println!("cargo:rerun-if-changed=build.rs");
+    }
+}
@@ -0,0 +1,150 @@
+//! Optimisation par colonie de fourmis « classique » (ACO)
+//!
+//! Pas de Q-table ni de mise à jour de Bellman : chaque fourmi dépose une phéromone
+//! scalaire sur les cases qu'elle traverse et choisit sa case suivante avec une
+//! probabilité proportionnelle à phéromone^α · heuristique^β. Les deux pistes s'évaporent
+//! au même taux que les cartes du Q-Learning, pour comparer les deux approches sur les
+//! mêmes cartes.
+
+use crate::ant::AntsMode;
+use crate::coord::{Coord, GridShape};
+use crate::exploration::{target, MOVING_ACTIONS};
+use crate::grid::Grid;
+use crate::pheromone::Action;
+use serde::{Deserialize, Serialize};
+
+// Phéromone présente partout au départ, pour qu'une case jamais visitée reste tirable
+const INITIAL_SCENT: f32 = 0.1;
+// Attrait d'une source de nourriture pour une fourmi qui en cherche (les autres cases valent 1)
+const FOOD_HEURISTIC: f32 = 10.0;
+
+/// Pistes scalaires d'une colonie, une valeur par case
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScentTrails {
+    pub width: u32,
+    pub height: u32,
+    // Déposée par les fourmis chargées, suivie par celles qui cherchent de la nourriture
+    food: Vec<f32>,
+    // Déposée par les fourmis qui cherchent, suivie par celles qui rentrent au nid
+    nest: Vec<f32>,
+}
+
+impl ScentTrails {
+    pub fn new(width: u32, height: u32) -> Self {
+        let cells = GridShape::new(width, height).len();
+        ScentTrails {
+            width,
+            height,
+            food: vec![0.0; cells],
+            nest: vec![0.0; cells],
+        }
+    }
+
+    /// Piste vers la nourriture (`FINDING`) ou vers le nid (`RETURNING`) en (x, y)
+    pub fn get(&self, mode: AntsMode, x: u32, y: u32) -> f32 {
+        let Some(idx) = self.index(x, y) else {
+            return 0.0;
+        };
+        match mode {
+            AntsMode::FINDING => self.food[idx],
+            AntsMode::RETURNING => self.nest[idx],
+        }
+    }
+
+    // Rang de la case, None hors de la carte
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        GridShape::new(self.width, self.height)
+            .index(Coord::new(x, y))
+            .map(|index| index.as_usize())
+    }
+
+    /// Plus forte valeur de la piste suivie dans ce mode (échelle de l'affichage)
+    pub fn max_value(&self, mode: AntsMode) -> f32 {
+        let trail = match mode {
+            AntsMode::FINDING => &self.food,
+            AntsMode::RETURNING => &self.nest,
+        };
+        trail.iter().copied().fold(0.0, f32::max)
+    }
+
+    /// Une fourmi dans `mode` marque la case (x, y) : la fourmi chargée trace la piste vers
+    /// la nourriture, celle qui cherche trace la piste vers le nid
+    pub fn deposit(&mut self, mode: AntsMode, x: u32, y: u32, amount: f32) {
+        let Some(idx) = self.index(x, y) else {
+            return;
+        };
+        match mode {
+            AntsMode::FINDING => self.nest[idx] += amount,
+            AntsMode::RETURNING => self.food[idx] += amount,
+        }
+    }
+
+    // Cases dont une des deux pistes diffère de `old` (même taille) : (case, nourriture, nid)
+    pub(crate) fn changed_cells(&self, old: &ScentTrails) -> Vec<(usize, f32, f32)> {
+        (0..self.food.len())
+            .filter(|&cell| self.food[cell] != old.food[cell] || self.nest[cell] != old.nest[cell])
+            .map(|cell| (cell, self.food[cell], self.nest[cell]))
+            .collect()
+    }
+
+    // Réécrit une case relevée par `changed_cells`
+    pub(crate) fn set_cell(&mut self, (cell, food, nest): (usize, f32, f32)) {
+        self.food[cell] = food;
+        self.nest[cell] = nest;
+    }
+
+    pub fn evaporate(&mut self, rate: f32) {
+        for value in self.food.iter_mut().chain(self.nest.iter_mut()) {
+            *value *= 1.0 - rate;
+            if *value < 0.001 {
+                *value = 0.0;
+            }
+        }
+    }
+
+    /// Probabilité de chaque déplacement depuis (x, y) pour une fourmi dans `mode`.
+    /// L'heuristique attire vers les sources de nourriture en recherche, et vers `nest`
+    /// (distance de Manhattan) au retour.
+    #[allow(clippy::too_many_arguments)]
+    pub fn distribution(
+        &self,
+        grid: &Grid,
+        x: u32,
+        y: u32,
+        mode: AntsMode,
+        nest: Option<(u32, u32)>,
+        alpha: f32,
+        beta: f32,
+    ) -> Vec<(Action, f32)> {
+        let weights: Vec<(Action, f32)> = MOVING_ACTIONS
+            .iter()
+            .filter_map(|&action| {
+                let (nx, ny) = target(x, y, action);
+                if nx >= self.width || ny >= self.height || !grid.is_walkable(nx, ny) {
+                    return None;
+                }
+                // Un tunnel compte pour la case où il débouche
+                let (dx, dy) = grid.destination(nx, ny);
+                let heuristic = match (mode, nest) {
+                    (AntsMode::FINDING, _) if grid.has_food(dx, dy) => FOOD_HEURISTIC,
+                    (AntsMode::RETURNING, Some((hx, hy))) => {
+                        1.0 / (1.0 + dx.abs_diff(hx) as f32 + dy.abs_diff(hy) as f32)
+                    }
+                    _ => 1.0,
+                };
+                let scent = INITIAL_SCENT + self.get(mode, dx, dy);
+                Some((action, scent.powf(alpha) * heuristic.powf(beta)))
+            })
+            .collect();
+
+        let total: f32 = weights.iter().map(|(_, w)| w).sum();
+        // Fourmi enfermée par des murs : elle reste sur place
+        if weights.is_empty() || total <= 0.0 || !total.is_finite() {
+            return vec![(Action::Stay, 1.0)];
+        }
+        weights
+            .into_iter()
+            .map(|(action, w)| (action, w / total))
+            .collect()
+    }
+}
@@ -1,50 +1,224 @@
+use crate::cli_args::SimulationConfig;
+use crate::coord::Coord;
 use crate::grid::Grid;
-use crate::pheromone::Action;
+use crate::i18n::tr;
+use crate::pheromone::{Action, EligibilityTrace, NStepBuffer};
+use crate::stuck::StuckWatch;
+use crate::tile::{default_food_value, SUGAR_VALUE};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+/// Nombre de cases gardées dans le trajet récent de chaque fourmi
+pub const RECENT_PATH_LENGTH: usize = 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AntsType {
     EXPLORER,
     FIGHTER,
     PICKER,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum AntsMode {
     FINDING,
     RETURNING,
 }
 
-#[derive(Clone, Debug)]
+impl AntsType {
+    pub fn all() -> [AntsType; 3] {
+        [AntsType::EXPLORER, AntsType::PICKER, AntsType::FIGHTER]
+    }
+
+    // Rang du type dans les tableaux indexés par type (cartes par type de fourmi)
+    pub(crate) fn index(&self) -> usize {
+        match self {
+            AntsType::EXPLORER => 0,
+            AntsType::PICKER => 1,
+            AntsType::FIGHTER => 2,
+        }
+    }
+
+    /// Nom court, utilisé dans les noms de fichiers
+    pub fn name(&self) -> &'static str {
+        match self {
+            AntsType::EXPLORER => "explorer",
+            AntsType::FIGHTER => "fighter",
+            AntsType::PICKER => "picker",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            AntsType::EXPLORER => "Exploratrice",
+            AntsType::FIGHTER => "Combattante",
+            AntsType::PICKER => "Récolteuse",
+        }
+    }
+}
+
+impl AntsMode {
+    pub fn label(&self) -> &'static str {
+        match self {
+            AntsMode::FINDING => tr("ant_mode.finding"),
+            AntsMode::RETURNING => tr("ant_mode.returning"),
+        }
+    }
+}
+
+/// Compteurs d'activité d'une fourmi depuis le début de la partie
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AntStats {
+    /// Allers-retours terminés par une livraison au nid
+    pub trips: u32,
+    /// Cases parcourues
+    pub distance: u32,
+    /// Nourriture livrée au nid
+    pub food_delivered: u32,
+    /// Ticks passés sur la carte
+    pub ticks_alive: u32,
+    /// Cases parcourues depuis la dernière livraison (ou l'arrivée sur la carte)
+    #[serde(default)]
+    pub trip_cells: u32,
+    /// Cases parcourues pendant les allers-retours terminés
+    #[serde(default)]
+    pub trip_distance: u32,
+    /// Somme des plus courts allers-retours de la colonie, un par aller-retour terminé
+    #[serde(default)]
+    pub optimal_distance: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Ant {
+    // Identifiant unique dans la partie, donné par le manager quand la fourmi la rejoint
+    // (0 avant) et conservé par les snapshots, checkpoints et replays : l'index dans
+    // `ants()` change quand une fourmi meurt de vieillesse
+    #[serde(default)]
+    pub id: u32,
     pub ant_type: AntsType,
     pub maximal_charge: u32,
     pub current_charge: u32,
+    // Valeur par unité de la charge, celle de la source où elle a été ramassée
+    #[serde(default = "default_food_value")]
+    pub carried_value: u32,
     pub seconds_for_movement: u32,
     pub cooldown: u32,
     pub scope: u32,
     pub mode: AntsMode,
     pub position: Option<(u32, u32)>,
+    // Colonie d'appartenance (index du nid et des cartes de phéromones)
+    pub colony: usize,
+    // Action déjà choisie pour la case suivante (SARSA), avec la case et le mode visés
+    pub planned_action: Option<((u32, u32), AntsMode, Action)>,
+    // Réserve d'énergie, dépensée en se déplaçant et reconstituée au nid (si activée)
+    #[serde(default)]
+    pub energy: f32,
+    // Paires (case, action) récentes créditées par Q(λ)
+    #[serde(default)]
+    pub trace: EligibilityTrace,
+    // Derniers pas en attente de leur cible à n pas (`--n-step`)
+    #[serde(default)]
+    pub n_step: NStepBuffer,
+    // Dernières cases occupées, de la plus ancienne à la plus récente (affichage seulement,
+    // ni enregistré ni rejoué)
+    #[serde(skip)]
+    pub recent_path: VecDeque<(u32, u32)>,
+    #[serde(default)]
+    pub stats: AntStats,
+    // Points de vie, perdus au combat et rendus au nid (une ouvrière meurt au premier coup)
+    #[serde(default = "default_health")]
+    pub health: u32,
+    #[serde(default = "default_health")]
+    pub max_health: u32,
+    // Dégâts d'un coup porté à un prédateur (0 pour les ouvrières, qui ne se battent pas)
+    #[serde(default)]
+    pub attack: u32,
+    // Ticks écoulés depuis la naissance, au nid comme sur la carte
+    #[serde(default)]
+    pub age: u32,
+    // Surveillance des blocages, et ticks restants de la reprise qui suit un blocage
+    #[serde(default)]
+    pub stuck_watch: StuckWatch,
+    #[serde(default)]
+    pub recovery_ticks: u32,
+}
+
+// Points de vie des fourmis enregistrées avant l'apparition des combats
+fn default_health() -> u32 {
+    1
 }
 
 impl Ant {
-    pub fn new(ant_type: AntsType) -> Self {
+    pub fn new(ant_type: AntsType, config: &SimulationConfig) -> Self {
         // Valeur initiale de charge pour toutes les fourmis
         const DEFAULT_CHARGE: u32 = 0;
+        // Les caractéristiques de chaque type proviennent de la configuration
+        // Seules les combattantes encaissent plusieurs coups et en portent
+        let (health, attack) = match ant_type {
+            AntsType::FIGHTER => (config.fighter_health, config.fighter_attack),
+            AntsType::EXPLORER | AntsType::PICKER => (1, 0),
+        };
         let (max_charge, speed, scope) = match ant_type {
-            AntsType::EXPLORER => (10, 5, 1),
-            AntsType::FIGHTER => (10, 5, 1),
-            AntsType::PICKER => (100, 10, 0),
+            AntsType::EXPLORER => (
+                config.explorer_capacity_load,
+                config.explorer_speed,
+                config.explorer_scope,
+            ),
+            AntsType::FIGHTER => (
+                config.fighter_capacity_load,
+                config.fighter_speed,
+                config.fighter_scope,
+            ),
+            AntsType::PICKER => (
+                config.picker_capacity_load,
+                config.picker_speed,
+                config.picker_scope,
+            ),
         };
         Ant {
+            id: 0,
             ant_type,
             maximal_charge: max_charge,
             current_charge: DEFAULT_CHARGE,
+            carried_value: SUGAR_VALUE,
             seconds_for_movement: speed,
             scope,
             mode: AntsMode::FINDING,
             position: None,
             cooldown: 0,
+            colony: 0,
+            planned_action: None,
+            energy: config.ant_energy,
+            trace: EligibilityTrace::default(),
+            n_step: NStepBuffer::default(),
+            recent_path: VecDeque::with_capacity(RECENT_PATH_LENGTH),
+            stats: AntStats::default(),
+            health,
+            max_health: health,
+            attack,
+            age: 0,
+            stuck_watch: StuckWatch::default(),
+            recovery_ticks: 0,
+        }
+    }
+
+    /// Génère la colonie complète (explorateurs, récolteuses, combattantes) décrite par la configuration
+    pub fn colony_from_config(config: &SimulationConfig) -> Vec<Ant> {
+        let mut ants = Vec::new();
+        for _ in 0..config.num_explorers {
+            ants.push(Ant::new(AntsType::EXPLORER, config));
         }
+        for _ in 0..config.num_pickers {
+            ants.push(Ant::new(AntsType::PICKER, config));
+        }
+        for _ in 0..config.num_fighters {
+            ants.push(Ant::new(AntsType::FIGHTER, config));
+        }
+        ants
+    }
+
+    /// Case occupée, None hors de la carte
+    pub fn coord(&self) -> Option<Coord> {
+        self.position.map(Coord::from)
     }
 
     pub fn get_target_position(&self, action: Action) -> (u32, u32) {
@@ -60,13 +234,49 @@ impl Ant {
         }
     }
 
+    /// Énergie passée sous le seuil de faim : la fourmi suit la piste du nid pour aller manger
+    pub fn is_hungry(&self, config: &SimulationConfig) -> bool {
+        config.ant_energy > 0.0 && self.energy < config.hunger_threshold * config.ant_energy
+    }
+
+    /// Durée de vie de son type dépassée : la fourmi rentre mourir au nid
+    pub fn is_expired(&self, config: &SimulationConfig) -> bool {
+        let lifespan = config.lifespan[self.ant_type.index()];
+        lifespan > 0 && self.age >= lifespan
+    }
+
+    /// Nourriture que vaut la charge une fois déposée au nid
+    pub fn carried_food(&self) -> u32 {
+        self.current_charge.saturating_mul(self.carried_value)
+    }
+
     pub fn move_to(&mut self, x: u32, y: u32) {
         self.position = Some((x, y));
+        // Rester sur place n'allonge pas le trajet
+        if self.recent_path.back() == Some(&(x, y)) {
+            return;
+        }
+        if self.recent_path.len() == RECENT_PATH_LENGTH {
+            self.recent_path.pop_front();
+        }
+        self.recent_path.push_back((x, y));
+    }
+
+    /// Place la fourmi sur la carte en repartant d'un trajet vide, ses blessures soignées
+    pub fn spawn_at(&mut self, position: (u32, u32)) {
+        self.health = self.max_health;
+        self.stats.trip_cells = 0;
+        self.recent_path.clear();
+        self.trace.reset();
+        self.n_step.clear();
+        self.stuck_watch.reset();
+        self.recovery_ticks = 0;
+        self.move_to(position.0, position.1);
     }
 
     pub fn spawn_at_nest(&mut self, grid: &Grid) {
-        if let Some(nest_pos) = grid.get_nest_position() {
-            self.position = Some(nest_pos);
+        if let Some(nest_pos) = grid.get_colony_nest_position(self.colony) {
+            self.spawn_at(nest_pos);
         }
     }
 }
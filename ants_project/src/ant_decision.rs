@@ -0,0 +1,357 @@
+use crate::ant::{Ant, AntsMode, AntsType};
+use crate::baseline;
+use crate::cli_args::SimulationConfig;
+use crate::colony::Colony;
+use crate::exploration::{self, ExplorationParams};
+use crate::grid::Grid;
+use crate::patrol;
+use crate::pheromone::{Action, VisitCounts};
+use crate::policy::PolicyKind;
+use crate::q_learning_math::{LearningAlgorithm, QLearningMath};
+use crate::q_table::{StateFeatures, StateLayout};
+use crate::replay::Replay;
+use crate::reward::RewardModel;
+use crate::sensing;
+use crate::stuck::StuckRecovery;
+use rayon::prelude::*;
+
+// En dessous de ce nombre de fourmis à décider, le coût de rayon dépasse le gain
+const PARALLEL_MIN_ANTS: usize = 64;
+
+// Ce qu'une fourmi a décidé pendant la phase parallèle, appliqué ensuite séquentiellement
+pub(crate) struct AntDecision {
+    pub index: usize,
+    pub action: Action,
+    pub target: (u32, u32),
+    pub is_out: bool,
+    pub is_lethal: bool,
+    pub reward: f32,
+    pub delta: f32,
+    // Valeur de l'état suivant dans la cible de Bellman (0 si le pas est terminal)
+    pub next_value: f32,
+    // L'action n'est pas la meilleure connue : Q(λ) de Watkins coupe alors la trace
+    pub exploratory: bool,
+    // Double Q-Learning : la correction porte sur la seconde table
+    pub second_table: bool,
+    // Action déjà tirée pour la case suivante (SARSA)
+    pub next_planned: Option<((u32, u32), AntsMode, Action)>,
+    // État de départ et d'arrivée du pas, qui choisissent les couches de la Q-table
+    pub state: StateFeatures,
+    pub next_state: StateFeatures,
+}
+
+// Vue en lecture seule de l'état du tick, partagée entre les threads
+pub(crate) struct DecisionContext<'a> {
+    pub grid: &'a Grid,
+    pub ants: &'a [Ant],
+    pub colonies: &'a [Colony],
+    pub visits: &'a [(VisitCounts, VisitCounts)],
+    pub config: &'a SimulationConfig,
+    // Modèle de récompense actif (celui du code appelant ou `DefaultReward`)
+    pub reward: &'a dyn RewardModel,
+    // Stratégie d'exploration et calculs de Q-Learning de chaque colonie
+    pub learning: Vec<(ExplorationParams, QLearningMath)>,
+    pub playback: Option<&'a Replay>,
+    // Actions imposées par un agent externe, indexées comme `ants`
+    pub overrides: &'a [Option<Action>],
+    pub tick: u64,
+    // Positions des nids de chaque colonie (heuristique de l'ACO classique, cible de l'A*)
+    pub nests: Vec<Vec<(u32, u32)>>,
+    // Sources de nourriture restantes, relevées seulement pour l'A* omniscient
+    pub food: Vec<(u32, u32)>,
+    // Positions des prédateurs, que les combattantes interceptent
+    pub predators: Vec<(u32, u32)>,
+}
+
+impl DecisionContext<'_> {
+    // Décider pour chaque fourmi listée ; `rolls` contient deux tirages uniformes par fourmi
+    pub fn decide_all(&self, acting: &[usize], rolls: &[(f32, f32)]) -> Vec<AntDecision> {
+        if acting.len() >= PARALLEL_MIN_ANTS {
+            acting
+                .par_iter()
+                .zip(rolls.par_iter())
+                .map(|(&i, &roll)| self.decide(i, roll))
+                .collect()
+        } else {
+            acting
+                .iter()
+                .zip(rolls)
+                .map(|(&i, &roll)| self.decide(i, roll))
+                .collect()
+        }
+    }
+
+    fn decide(&self, i: usize, (action_roll, next_roll): (f32, f32)) -> AntDecision {
+        let ant = &self.ants[i];
+        let (x, y) = ant.position.unwrap_or((0, 0));
+        let (mode, colony) = (ant.mode, ant.colony);
+        let state = self.state_at(ant, (x, y), mode);
+        let map = self.colonies[colony].layer(ant.ant_type, state);
+        let q = self.colonies[colony].backend(ant.ant_type, state);
+        let math = &self.learning[colony].1;
+
+        let learns = self.config.updates_q_tables();
+
+        // Sélectionner la prochaine action selon la stratégie d'exploration (exploration vs exploitation)
+        // En SARSA, l'action a déjà été tirée au tick précédent et doit être respectée
+        let mut action = match (self.config.policy, ant.planned_action) {
+            // Une fourmi qui se dégage d'un blocage marche au hasard, quelle que soit sa politique
+            _ if self.recovering(ant, StuckRecovery::RandomWalk) => {
+                exploration::sample(&baseline::random_walk(self.grid, x, y), action_roll)
+            }
+            (PolicyKind::ClassicAco, _) => self.aco_action(x, y, ant, action_roll),
+            (PolicyKind::RandomWalk, _) => {
+                exploration::sample(&baseline::random_walk(self.grid, x, y), action_roll)
+            }
+            (PolicyKind::AStar, _) => self.astar_action(x, y, ant, action_roll),
+            (_, Some((pos, planned_mode, action))) if pos == (x, y) && planned_mode == mode => {
+                action
+            }
+            _ => self.pick_action(x, y, ant, action_roll),
+        };
+        // Une cible en vue attire la fourmi d'un pas glouton, quelle que soit sa politique
+        if self.senses(ant) {
+            let perception = sensing::sense(self.grid, (x, y), ant.scope, colony);
+            if let Some(step) = perception
+                .target(self.steering_mode(ant))
+                .and_then(|goal| sensing::step_toward(self.grid, (x, y), goal))
+            {
+                action = step;
+            }
+        }
+        // Des prédateurs rôdent : la combattante intercepte ou fait sa ronde
+        if let Some(step) = self.fighter_action(ant, (x, y)) {
+            action = step;
+        }
+        if let Some(&Some(forced)) = self.overrides.get(i) {
+            action = forced;
+        }
+        // En relecture l'action enregistrée fait foi
+        if let Some(recorded) = self.playback.and_then(|r| r.action_for(self.tick, i)) {
+            action = recorded;
+        }
+        // Double Q-Learning : une table tirée au sort est mise à jour (le second tirage ne
+        // sert qu'à SARSA, il est libre ici)
+        let double = self.config.learning_algorithm == LearningAlgorithm::DoubleQLearning;
+        let second_table = double && next_roll < 0.5;
+        let q_curr = if double {
+            map.table_values(second_table, x, y)[action.to_usize()]
+        } else {
+            q.get_q(self.grid, state, action)
+        };
+
+        let (nx, ny) = ant.get_target_position(action);
+        let is_out = nx >= self.grid.get_width() || ny >= self.grid.get_height();
+        // Entrer dans un tunnel mène directement à sa sortie : c'est elle qui est évaluée
+        let (nx, ny) = if is_out {
+            (nx, ny)
+        } else {
+            self.grid.destination(nx, ny)
+        };
+        let is_lethal = !is_out && self.grid.is_lethal(nx, ny);
+        let next_state = if is_out {
+            StateFeatures::new(nx, ny, mode)
+        } else {
+            self.state_at(ant, (nx, ny), mode)
+        };
+        let next_map = self.colonies[colony].layer(ant.ant_type, next_state);
+        let next_q = self.colonies[colony].backend(ant.ant_type, next_state);
+
+        // Calculer la récompense en fonction du type de case visée
+        let mut reward = self.reward.reward(ant, (nx, ny), self.grid, self.config);
+        // Bonus d'exploration : une action peu jouée depuis cette case rapporte davantage
+        if learns && self.config.count_bonus > 0.0 {
+            let visits = match mode {
+                AntsMode::FINDING => &self.visits[colony].0,
+                AntsMode::RETURNING => &self.visits[colony].1,
+            };
+            // Le pas en cours n'est compté qu'à l'application de la décision
+            let count = visits.count(x, y, action) + 1;
+            reward += self.config.count_bonus / (count as f32).sqrt();
+        }
+
+        // Calculer la valeur de l'état suivant pour la formule de Bellman selon l'algorithme choisi
+        let mut next_planned = None;
+        let next_value = if is_out || is_lethal || !learns {
+            0.0
+        } else {
+            match self.config.learning_algorithm {
+                LearningAlgorithm::QLearning if self.config.masked_max_q => {
+                    next_q.get_max_valid_q(self.grid, next_state)
+                }
+                LearningAlgorithm::QLearning => next_q.get_max_q(self.grid, next_state),
+                LearningAlgorithm::Sarsa => {
+                    let next_action = self.pick_action(nx, ny, ant, next_roll);
+                    next_planned = Some(((nx, ny), mode, next_action));
+                    next_q.get_q(self.grid, next_state, next_action)
+                }
+                LearningAlgorithm::ExpectedSarsa => {
+                    // Espérance de Q(s', a') sous la stratégie d'exploration courante
+                    let weighted: Vec<(f32, f32)> = self
+                        .distribution(nx, ny, ant)
+                        .iter()
+                        .map(|&(a, p)| (p, next_q.get_q(self.grid, next_state, a)))
+                        .collect();
+                    math.expected_value(&weighted)
+                }
+                LearningAlgorithm::DoubleQLearning => math.double_q_value(
+                    &next_map.table_values(second_table, nx, ny),
+                    &next_map.table_values(!second_table, nx, ny),
+                ),
+            }
+        };
+
+        AntDecision {
+            index: i,
+            action,
+            target: (nx, ny),
+            is_out,
+            is_lethal,
+            reward,
+            // Calculer la correction Delta : Alpha * (Reward + Gamma * NextValue - Current)
+            delta: if learns {
+                math.compute_delta(q_curr, reward, next_value)
+            } else {
+                0.0
+            },
+            next_value,
+            exploratory: q.get_q(self.grid, state, action)
+                < q.get_q(self.grid, state, q.get_best_action(self.grid, state)),
+            second_table,
+            next_planned,
+            state,
+            next_state,
+        }
+    }
+
+    // État de la fourmi en (x, y) dans ce mode, avec les traits que les Q-tables distinguent
+    fn state_at(&self, ant: &Ant, position: (u32, u32), mode: AntsMode) -> StateFeatures {
+        let layout = StateLayout::from_config(self.config);
+        StateFeatures::observe(self.grid, position, mode, ant.scope, layout)
+    }
+
+    // La fourmi suit encore la reprise `recovery` après un blocage
+    fn recovering(&self, ant: &Ant, recovery: StuckRecovery) -> bool {
+        ant.recovery_ticks > 0 && self.config.stuck_recovery == recovery
+    }
+
+    // La fourmi perçoit son voisinage : vision non nulle, hors politiques de référence
+    fn senses(&self, ant: &Ant) -> bool {
+        self.config.sensing && ant.scope > 0 && !self.config.policy.is_baseline()
+    }
+
+    // Mode dont la piste guide le choix d'action : une fourmi affamée ou en fin de vie qui
+    // cherche de la nourriture suit la piste du nid (elle apprend toujours dans son mode)
+    fn steering_mode(&self, ant: &Ant) -> AntsMode {
+        if ant.mode == AntsMode::FINDING
+            && (ant.is_hungry(self.config) || ant.is_expired(self.config))
+        {
+            AntsMode::RETURNING
+        } else {
+            ant.mode
+        }
+    }
+
+    // Probabilités des actions depuis (x, y) selon la stratégie d'exploration configurée,
+    // sans les pas vers un mur ni vers une zone mortelle que la fourmi voit
+    fn distribution(&self, x: u32, y: u32, ant: &Ant) -> Vec<(Action, f32)> {
+        let mode = self.steering_mode(ant);
+        let visits = match mode {
+            AntsMode::FINDING => &self.visits[ant.colony].0,
+            AntsMode::RETURNING => &self.visits[ant.colony].1,
+        };
+        let state = self.state_at(ant, (x, y), mode);
+        let q = self.colonies[ant.colony].backend(ant.ant_type, state);
+        let exploration = &self.learning[ant.colony].0;
+        // Une fourmi qui se dégage d'un blocage explore davantage, seule de sa colonie
+        let boosted;
+        let exploration = if self.recovering(ant, StuckRecovery::Explore) {
+            boosted = exploration.boosted(self.config.stuck_epsilon);
+            &boosted
+        } else {
+            exploration
+        };
+        let scope = if self.senses(ant) { ant.scope } else { 0 };
+        let fear = self.colonies[ant.colony].fear.as_ref();
+        exploration.distribution(q, visits, fear, self.grid, state, scope)
+    }
+
+    fn pick_action(&self, x: u32, y: u32, ant: &Ant, roll: f32) -> Action {
+        exploration::sample(&self.distribution(x, y, ant), roll)
+    }
+
+    // Nid de la colonie le plus proche de (x, y) (distance de Manhattan, le premier à égalité)
+    fn nearest_nest(&self, colony: usize, (x, y): (u32, u32)) -> Option<(u32, u32)> {
+        self.nests
+            .get(colony)?
+            .iter()
+            .copied()
+            .min_by_key(|&(nx, ny)| x.abs_diff(nx) + y.abs_diff(ny))
+    }
+
+    // Combattante quand les prédateurs sont activés : elle intercepte un prédateur proche,
+    // ou, sans charge, patrouille sur la piste de nourriture de sa colonie près du nid
+    fn fighter_action(&self, ant: &Ant, position: (u32, u32)) -> Option<Action> {
+        if ant.ant_type != AntsType::FIGHTER || self.config.max_predators == 0 {
+            return None;
+        }
+        let range = self.config.fighter_patrol_range;
+        if let Some(step) = patrol::intercept(self.grid, position, &self.predators, range) {
+            return Some(step);
+        }
+        if ant.mode != AntsMode::FINDING {
+            return None;
+        }
+        // La piste la plus fiable est celle des récolteuses (la commune sans cartes par type)
+        let trail = self.colonies[ant.colony].layer(
+            AntsType::PICKER,
+            StateFeatures::new(position.0, position.1, AntsMode::FINDING),
+        );
+        patrol::patrol(
+            self.grid,
+            trail,
+            position,
+            self.nearest_nest(ant.colony, position),
+            range,
+        )
+    }
+
+    // ACO classique : tirage proportionnel à phéromone^α · heuristique^β
+    fn aco_action(&self, x: u32, y: u32, ant: &Ant, roll: f32) -> Action {
+        let Some(trails) = &self.colonies[ant.colony].trails else {
+            return self.pick_action(x, y, ant, roll);
+        };
+        let distribution = trails.distribution(
+            self.grid,
+            x,
+            y,
+            self.steering_mode(ant),
+            self.nearest_nest(ant.colony, (x, y)),
+            self.config.aco_alpha,
+            self.config.aco_beta,
+        );
+        let distribution = if self.senses(ant) {
+            sensing::mask_hazards(self.grid, (x, y), distribution)
+        } else {
+            distribution
+        };
+        exploration::sample(&distribution, roll)
+    }
+
+    // A* omniscient vers la nourriture la plus proche ou vers le nid ; sans chemin sûr, la
+    // fourmi marche au hasard
+    fn astar_action(&self, x: u32, y: u32, ant: &Ant, roll: f32) -> Action {
+        let step = match self.steering_mode(ant) {
+            AntsMode::FINDING => baseline::astar_step(self.grid, (x, y), &self.food, |cx, cy| {
+                self.grid.has_food(cx, cy)
+            }),
+            AntsMode::RETURNING => {
+                let nests = self.nests.get(ant.colony).map_or(&[][..], Vec::as_slice);
+                baseline::astar_step(self.grid, (x, y), nests, |cx, cy| {
+                    self.grid.is_colony_nest(cx, cy, ant.colony)
+                })
+            }
+        };
+        step.unwrap_or_else(|| exploration::sample(&baseline::random_walk(self.grid, x, y), roll))
+    }
+}
@@ -1,352 +1,2598 @@
 use crate::ant::{Ant, AntsMode, AntsType};
+use crate::ant_decision::{AntDecision, DecisionContext};
 use crate::cli_args::SimulationConfig;
+use crate::colony::Colony;
+use crate::coord::GridIndex;
+use crate::error::AntsError;
+use crate::experience::{ExperienceBuffer, Transition};
+use crate::experiment::{manifest_path, ArtifactKind, Manifest};
+use crate::exploration::{target, ExplorationParams, ExplorationPolicy, MOVING_ACTIONS};
 use crate::grid::Grid;
-use crate::pheromone::{Action, PheromoneMap};
-use crate::tile::{Tile, TileType};
-use rand::Rng;
+use crate::grid_transaction::{GridTransaction, TransactionReport};
+use crate::hazard;
+use crate::i18n::{tr, trf};
+use crate::meta_controller::{ControllerDecision, MetaController};
+use crate::metrics::{
+    AntTypeStats, MetricsCollector, StatsHistory, StatsPoint, TickMetrics, TrafficMap,
+};
+use crate::observer::{
+    DeathCause, DeathEvent, FoodEvent, Observers, SimulationObserver, SpawnEvent,
+};
+use crate::pathfinding;
+use crate::pheromone::{Action, NStep, PheromoneMap, Resample, VisitCounts};
+use crate::policy::PolicyKind;
+use crate::predator::{self, Predator, PredatorStats};
+use crate::profiling::{PhaseClock, PhaseTimings};
+use crate::q_backend::QBackend;
+use crate::q_learning_math::{DecayConfig, LearningAlgorithm, QLearningMath};
+use crate::q_table::{QTable, StateFeatures, StateLayout};
+use crate::replay::Replay;
+use crate::replay_verify::StateHashes;
+use crate::reward::{load_fraction, DefaultReward, RewardModel};
+use crate::scenario::EventKind;
+use crate::scoring;
+use crate::spawn_policy::{choose_type, ColonyState, SpawnPolicy, SpawnReason, SpawnStats};
+use crate::stuck::StuckRecovery;
+use crate::tile::{Tile, TileType, SUGAR_VALUE};
+use crate::timeline::{StateRef, Timeline};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use tracing::{debug, info, warn};
+use web_time::SystemTime;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct GameStateSnapshot {
+    pub tick: usize,
     pub grid: Grid,
     pub ants: Vec<Ant>,
-    pub pheromones_food: PheromoneMap,
-    pub pheromones_nest: PheromoneMap,
+    pub colonies: Vec<Colony>,
+    pub predators: Vec<Predator>,
 }
 
+impl GameStateSnapshot {
+    /// Fourmi d'identifiant `id` dans cet état (voir `Ant::id`)
+    pub fn ant(&self, id: u32) -> Option<&Ant> {
+        self.ants.iter().find(|ant| ant.id == id)
+    }
+}
+
+// Résultat du passage d'une fourmi sur une source de nourriture ou sur son nid
+enum Interaction {
+    PickedUp(u32),
+    Delivered(u32),
+}
+
+/// Condition ayant mis fin à une partie
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EndReason {
+    /// Le nid de `colony` a stocké la nourriture demandée par `target_food`
+    TargetFood { colony: usize },
+    /// Plus aucune nourriture sur la carte
+    FoodExhausted,
+    /// Toutes les fourmis sont mortes
+    AllAntsDead,
+    /// Les réserves de `colony` ne paient plus son entretien et plus aucune de ses fourmis
+    /// n'est dehors pour en rapporter : la colonie a perdu
+    Famine { colony: usize },
+    /// Limite de ticks atteinte (signalée par le runner, jamais par le manager)
+    TickLimit,
+    /// Limite de temps de calcul dépassée : partie abandonnée (signalée par le runner)
+    Timeout,
+}
+
+impl EndReason {
+    /// Nom stable, indépendant de la langue de l'interface (bilan JSON)
+    pub fn name(&self) -> &'static str {
+        match self {
+            EndReason::TargetFood { .. } => "target_food",
+            EndReason::FoodExhausted => "food_exhausted",
+            EndReason::AllAntsDead => "all_ants_dead",
+            EndReason::Famine { .. } => "famine",
+            EndReason::TickLimit => "tick_limit",
+            EndReason::Timeout => "timeout",
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            EndReason::TargetFood { colony } => trf("end.target_food", &[&Colony::label(*colony)]),
+            EndReason::FoodExhausted => tr("end.food_exhausted").to_string(),
+            EndReason::AllAntsDead => tr("end.all_ants_dead").to_string(),
+            EndReason::Famine { colony } => trf("end.famine", &[&Colony::label(*colony)]),
+            EndReason::TickLimit => tr("end.tick_limit").to_string(),
+            EndReason::Timeout => tr("end.timeout").to_string(),
+        }
+    }
+}
+
+// Format de sauvegarde du "cerveau" d'une colonie (sa Q-table, plus celle de chaque type
+// de fourmi s'ils apprennent séparément)
+#[derive(Serialize, Deserialize)]
+struct BrainFile {
+    q_table: QTable,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    caste_tables: Vec<QTable>,
+}
+
+// Cerveau écrit avant les Q-tables à couches : une carte par mode
+#[derive(Deserialize)]
+struct LegacyBrainFile {
+    pheromones_food: PheromoneMap,
+    pheromones_nest: PheromoneMap,
+    #[serde(default)]
+    caste_maps: Vec<LegacyCasteMaps>,
+}
+
+#[derive(Deserialize)]
+struct LegacyCasteMaps {
+    pheromones_food: PheromoneMap,
+    pheromones_nest: PheromoneMap,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnyBrainFile {
+    Current(BrainFile),
+    Legacy(Box<LegacyBrainFile>),
+}
+
+impl From<AnyBrainFile> for BrainFile {
+    fn from(file: AnyBrainFile) -> Self {
+        match file {
+            AnyBrainFile::Current(brain) => brain,
+            AnyBrainFile::Legacy(legacy) => BrainFile {
+                q_table: QTable::from_mode_maps(legacy.pheromones_food, legacy.pheromones_nest),
+                caste_tables: legacy
+                    .caste_maps
+                    .into_iter()
+                    .map(|caste| {
+                        QTable::from_mode_maps(caste.pheromones_food, caste.pheromones_nest)
+                    })
+                    .collect(),
+            },
+        }
+    }
+}
+
+impl BrainFile {
+    fn of(colony: &Colony) -> Self {
+        BrainFile {
+            q_table: colony.q_table.clone(),
+            caste_tables: colony.caste_tables.clone(),
+        }
+    }
+
+    // Le même cerveau, toutes ses cartes adaptées à une grille `width` x `height`
+    fn resampled(&self, width: u32, height: u32, mode: Resample) -> Self {
+        BrainFile {
+            q_table: self.q_table.resampled(width, height, mode),
+            caste_tables: self
+                .caste_tables
+                .iter()
+                .map(|table| table.resampled(width, height, mode))
+                .collect(),
+        }
+    }
+}
+
+// Version du format des checkpoints, à incrémenter à chaque changement incompatible
+const CHECKPOINT_VERSION: u32 = 14;
+
+// Partie figée en cours de route : tout ce qu'il faut pour la reprendre au même tick avec
+// les mêmes tirages aléatoires que si elle n'avait jamais été interrompue
+#[derive(Serialize, Deserialize)]
+pub(crate) struct Checkpoint {
+    version: u32,
+    seed: u64,
+    // Configuration au format TOML : bincode ne sait pas relire les événements de scénario
+    config: String,
+    // Tick, carte, fourmis, colonies (cartes de phéromones comprises) et prédateurs
+    state: GameStateSnapshot,
+    rng: ChaCha12Rng,
+    visits: Vec<(VisitCounts, VisitCounts)>,
+    experience: ExperienceBuffer,
+    static_lethal: Vec<bool>,
+    ticks_until_predator: u32,
+    predator_stats: PredatorStats,
+    spawn_stats: SpawnStats,
+    starved_ants: u32,
+    stuck_ants: u32,
+    next_ant_id: u32,
+    stats: StatsHistory,
+    // Nourriture livrée, morts et naissances cumulées
+    totals: [u64; 3],
+    colony_learning: Vec<Option<ColonyLearning>>,
+    optimal_round_trips: Vec<Option<u32>>,
+    map_fingerprint: u64,
+    traffic: TrafficMap,
+    controller: MetaController,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct QLearningParams {
     pub alpha: f32,
     pub gamma: f32,
     pub epsilon: f32,
+    pub alpha_decay: DecayConfig,
+    pub epsilon_decay: DecayConfig,
+}
+
+// Réglages d'apprentissage propres à une colonie (tournoi) : valeurs de départ d'alpha et
+// d'epsilon, et paramètres courants après décroissance
+#[derive(Clone, Serialize, Deserialize)]
+struct ColonyLearning {
+    alpha: f32,
+    epsilon: f32,
+    params: QLearningParams,
+}
+
+impl QLearningParams {
+    pub fn from_config(config: &SimulationConfig) -> Self {
+        QLearningParams {
+            alpha: config.alpha,
+            gamma: config.gamma,
+            epsilon: config.epsilon,
+            alpha_decay: config.alpha_decay,
+            epsilon_decay: config.epsilon_decay,
+        }
+    }
+
+    // Faire décroître alpha et epsilon depuis leurs valeurs initiales
+    pub fn apply_decay(&mut self, initial_alpha: f32, initial_epsilon: f32, tick: u64) {
+        self.alpha = self.alpha_decay.value_at(initial_alpha, tick);
+        self.epsilon = self.epsilon_decay.value_at(initial_epsilon, tick);
+    }
+}
+
+pub struct AntsGameManager {
+    pub(crate) grid: Grid,
+    pub(crate) ants: Vec<Ant>,
+    // Une entrée par nid : cartes de phéromones, score et réserves propres à chaque colonie
+    pub(crate) colonies: Vec<Colony>,
+    pub(crate) rl_params: QLearningParams,
+    pub(crate) config: SimulationConfig,
+    // Snapshots triés par tick, espacés et limités selon la politique de la configuration
+    pub(crate) history: Timeline,
+    pub(crate) current_tick_index: usize,
+    pub(crate) spawn_stats: SpawnStats,
+    pub(crate) starved_ants: u32,
+    // Blocages détectés depuis le début de la partie (une fourmi peut compter plusieurs fois)
+    stuck_ants: u32,
+    // Identifiant de la prochaine fourmi née ; jamais rembobiné, pour qu'une fourmi née
+    // dans un futur abandonné ne prête pas son identifiant à une autre
+    next_ant_id: u32,
+    pub(crate) metrics: MetricsCollector,
+    // Courbes échantillonnées pour la GUI
+    stats: StatsHistory,
+    // Compteurs de visites par colonie et par carte (bonus d'exploration UCB)
+    visits: Vec<(VisitCounts, VisitCounts)>,
+    pub(crate) predators: Vec<Predator>,
+    pub(crate) predator_stats: PredatorStats,
+    ticks_until_predator: u32,
+    // Générateur unique de la simulation (graine configurable pour rejouer une partie)
+    rng: ChaCha12Rng,
+    seed: u64,
+    // Création de la partie (ou reprise du checkpoint), relevée dans les manifestes
+    started_at: SystemTime,
+    // Partie en cours d'enregistrement, ou partie relue dont les actions font foi
+    recording: Option<Replay>,
+    playback: Option<Replay>,
+    observers: Observers,
+    // Récompense reçue par chaque fourmi au dernier tick (0 si elle n'a pas agi)
+    last_rewards: Vec<f32>,
+    // Transitions récentes de toutes les fourmis, rejouées à chaque tick (experience replay)
+    experience: ExperienceBuffer,
+    // Règle de sortie du nid fournie par le code appelant, à la place de celle de la configuration
+    custom_spawn_policy: Option<Box<dyn SpawnPolicy>>,
+    // Récompenses maison fournies par le code appelant, à la place de `DefaultReward`
+    custom_reward_model: Option<Box<dyn RewardModel>>,
+    // Zones mortelles de la carte elle-même, que les zones scriptées ne doivent pas effacer
+    static_lethal: Vec<bool>,
+    // Alpha, gamma et epsilon propres à certaines colonies (None = ceux de la configuration)
+    colony_learning: Vec<Option<ColonyLearning>>,
+    // Plus court aller-retour nid-nourriture de chaque colonie sur la carte de départ
+    optimal_round_trips: Vec<Option<u32>>,
+    // Empreinte de la carte de départ, qui désigne son classement de meilleurs scores
+    map_fingerprint: u64,
+    // Passages des fourmis sur chaque case (calque « Trafic » de la GUI)
+    traffic: TrafficMap,
+    // Décisions du méta-contrôleur, qui corrige alpha et epsilon d'après les livraisons
+    controller: MetaController,
+    // Durées des phases des ticks, mesurées seulement une fois le profilage activé
+    timings: Option<PhaseTimings>,
 }
 
-pub struct AntsGameManager {
-    pub grid: Grid,
-    pub ants: Vec<Ant>,
-    pub pheromones_food: PheromoneMap,
-    pub pheromones_nest: PheromoneMap,
-    pub rl_params: QLearningParams,
-    pub config: SimulationConfig,
-    pub history: Vec<GameStateSnapshot>,
-    pub current_tick_index: usize,
-}
+impl AntsGameManager {
+    pub fn new(
+        width: u32,
+        height: u32,
+        tiles: Vec<Tile>,
+        ants: Vec<Ant>,
+        config: SimulationConfig,
+    ) -> Result<Self, AntsError> {
+        let grid = Grid::new_with_tiles(width, height, tiles)?;
+        let ants = Self::assign_colonies(ants, &grid);
+        let seed = Self::make_seed(&config);
+        Self::from_grid(grid, ants, config, seed)
+    }
+
+    /// Crée une partie sur une carte aléatoire avec la colonie décrite par la configuration
+    pub fn from_config(config: SimulationConfig) -> Result<Self, AntsError> {
+        let ants = Ant::colony_from_config(&config);
+        Self::new_game_mode_random(config.grid_width, config.grid_height, ants, config)
+    }
+
+    pub fn new_game_mode_random(
+        width: u32,
+        height: u32,
+        mut ants: Vec<Ant>,
+        config: SimulationConfig,
+    ) -> Result<Self, AntsError> {
+        if width == 0 || height == 0 {
+            return Err(AntsError::EmptyGrid { width, height });
+        }
+        let seed = Self::make_seed(&config);
+        // Une graine de carte fixe garde la même carte d'une partie à l'autre
+        let map_seed = config.map_gen.seed.unwrap_or(seed);
+        let grid = Grid::new_random_with_params(
+            width,
+            height,
+            &config.map_gen,
+            &mut StdRng::seed_from_u64(map_seed),
+        );
+
+        ants = Self::assign_colonies(ants, &grid);
+        Self::place_at_nests(&mut ants, &grid);
+
+        Self::from_grid(grid, ants, config, seed)
+    }
+
+    /// Crée une partie sur une carte existante (générée à l'avance ou chargée depuis l'éditeur) ;
+    /// chaque colonie reçoit sa copie de `ants`, placée à son nid
+    pub fn with_grid(
+        grid: Grid,
+        ants: Vec<Ant>,
+        config: SimulationConfig,
+    ) -> Result<Self, AntsError> {
+        let seed = Self::make_seed(&config);
+        let mut ants = Self::assign_colonies(ants, &grid);
+        Self::place_at_nests(&mut ants, &grid);
+        Self::from_grid(grid, ants, config, seed)
+    }
+
+    /// Reconstruit la partie enregistrée ; `game_step` rejoue ensuite ses actions tick par tick
+    pub fn from_replay(replay: Replay) -> Result<Self, AntsError> {
+        let state = replay.initial_state.clone();
+        // La timeline de relecture garde tous les ticks, quelle que soit la politique d'origine
+        let defaults = SimulationConfig::default();
+        let config = SimulationConfig {
+            seed: Some(replay.seed),
+            output_file: None,
+            save_brain: None,
+            record_file: None,
+            snapshot_interval: defaults.snapshot_interval,
+            max_snapshots: defaults.max_snapshots,
+            ..replay.config.clone()
+        };
+
+        let mut manager = Self::from_grid(state.grid, state.ants, config, replay.seed)?;
+        manager.colonies = state.colonies;
+        manager.predators = state.predators;
+        manager.playback = Some(replay);
+
+        // Le tick 0 de la timeline doit être l'état initial enregistré
+        manager.history.clear();
+        manager.save_snapshot();
+        Ok(manager)
+    }
+
+    // Sans graine fixée, on en tire une pour pouvoir quand même enregistrer la partie
+    fn make_seed(config: &SimulationConfig) -> u64 {
+        config.seed.unwrap_or_else(|| StdRng::from_entropy().gen())
+    }
+
+    // Chaque colonie de la carte reçoit sa propre copie de la population décrite ; les
+    // fourmis sont numérotées dans l'ordre, toutes colonies confondues
+    fn assign_colonies(ants: Vec<Ant>, grid: &Grid) -> Vec<Ant> {
+        (0..grid.colony_count())
+            .flat_map(|colony| {
+                ants.iter().cloned().map(move |mut ant| {
+                    ant.colony = colony;
+                    ant
+                })
+            })
+            .enumerate()
+            .map(|(id, mut ant)| {
+                ant.id = id as u32;
+                ant
+            })
+            .collect()
+    }
+
+    // Place chaque fourmi à un nid de sa colonie, réparties à tour de rôle entre ses nids
+    fn place_at_nests(ants: &mut [Ant], grid: &Grid) {
+        let nests: Vec<Vec<(u32, u32)>> = (0..grid.colony_count())
+            .map(|colony| grid.get_colony_nest_positions(colony))
+            .collect();
+        let mut placed = vec![0; nests.len()];
+        for ant in ants {
+            let Some(colony_nests) = nests.get(ant.colony).filter(|n| !n.is_empty()) else {
+                continue;
+            };
+            ant.spawn_at(colony_nests[placed[ant.colony] % colony_nests.len()]);
+            placed[ant.colony] += 1;
+        }
+    }
+
+    // Point de passage de tous les constructeurs : la configuration et la carte y sont vérifiées
+    fn from_grid(
+        grid: Grid,
+        ants: Vec<Ant>,
+        config: SimulationConfig,
+        seed: u64,
+    ) -> Result<Self, AntsError> {
+        config.validate()?;
+        if grid.get_nest_position().is_none() {
+            return Err(AntsError::MissingNest);
+        }
+
+        let width = grid.get_width();
+        let height = grid.get_height();
+        let colony_count = grid.colony_count();
+
+        // On crée l'état initial
+        let next_ant_id = ants.iter().map(|ant| ant.id + 1).max().unwrap_or(0);
+        let mut manager = AntsGameManager {
+            grid,
+            next_ant_id,
+            ants,
+            colonies: (0..colony_count)
+                .map(|id| {
+                    let mut colony = Colony::new(id, width, height);
+                    colony.set_state_layout(StateLayout::from_config(&config));
+                    colony.set_per_type_maps(config.per_type_q_tables);
+                    colony.set_scent_trails(config.policy == PolicyKind::ClassicAco);
+                    colony.set_fear_map(config.uses_fear());
+                    colony.set_double_tables(
+                        config.learning_algorithm == LearningAlgorithm::DoubleQLearning,
+                    );
+                    colony.set_q_bounds(config.q_bounds());
+                    colony
+                })
+                .collect(),
+            rl_params: QLearningParams::from_config(&config),
+            metrics: MetricsCollector::new(config.output_file.clone()),
+            stats: StatsHistory::default(),
+            visits: (0..colony_count)
+                .map(|_| {
+                    (
+                        VisitCounts::new(width, height),
+                        VisitCounts::new(width, height),
+                    )
+                })
+                .collect(),
+            predators: Vec::new(),
+            predator_stats: PredatorStats::default(),
+            ticks_until_predator: config.predator_spawn_interval,
+            experience: ExperienceBuffer::new(config.experience_buffer),
+            custom_spawn_policy: None,
+            custom_reward_model: None,
+            timings: None,
+            static_lethal: Vec::new(),
+            config,
+            history: Timeline::default(),
+            current_tick_index: 0,
+            spawn_stats: SpawnStats::default(),
+            starved_ants: 0,
+            stuck_ants: 0,
+            rng: ChaCha12Rng::seed_from_u64(seed),
+            seed,
+            started_at: SystemTime::now(),
+            recording: None,
+            playback: None,
+            observers: Observers::default(),
+            last_rewards: Vec::new(),
+            colony_learning: Vec::new(),
+            optimal_round_trips: Vec::new(),
+            map_fingerprint: 0,
+            traffic: TrafficMap::new(width, height),
+            controller: MetaController::default(),
+        };
+        manager.static_lethal = manager
+            .grid
+            .shape()
+            .coords()
+            .map(|coord| manager.grid.is_lethal(coord.x, coord.y))
+            .collect();
+        manager.set_q_backends();
+        manager.optimal_round_trips = (0..colony_count)
+            .map(|colony| {
+                let nests = manager.grid.get_colony_nest_positions(colony);
+                pathfinding::shortest_round_trip(&manager.grid, &nests)
+            })
+            .collect();
+        manager.map_fingerprint = scoring::map_fingerprint(&manager.grid);
+        manager.hold_back_over_capacity();
+        manager.update_hazards(0);
+
+        // Sauvegarder l'état initial (tick 0)
+        manager.save_snapshot();
+        Ok(manager)
+    }
+
+    // --- Accesseurs ---
+
+    pub fn grid(&self) -> &Grid {
+        &self.grid
+    }
+
+    pub fn ants(&self) -> &[Ant] {
+        &self.ants
+    }
+
+    /// Fourmi d'identifiant `id` (voir `Ant::id`), None si elle est morte de vieillesse ou
+    /// pas encore née
+    pub fn ant_by_id(&self, id: u32) -> Option<&Ant> {
+        self.ants.iter().find(|ant| ant.id == id)
+    }
+
+    /// Remplace la population (copiée pour chaque colonie) et place chaque fourmi à son nid ;
+    /// les identifiants repartent de 0
+    pub fn set_ants(&mut self, ants: Vec<Ant>) {
+        self.ants = Self::assign_colonies(ants, &self.grid);
+        self.next_ant_id = self.ants.len() as u32;
+        Self::place_at_nests(&mut self.ants, &self.grid);
+        self.hold_back_over_capacity();
+    }
+
+    pub fn colonies(&self) -> &[Colony] {
+        &self.colonies
+    }
+
+    /// Carte des pistes vers la nourriture de la première colonie (celle des exploratrices
+    /// si chaque type de fourmi a ses propres cartes)
+    pub fn pheromones_food(&self) -> &PheromoneMap {
+        self.colonies[0].maps(AntsType::EXPLORER).0
+    }
+
+    /// Carte des pistes vers le nid de la première colonie (celle des exploratrices si chaque
+    /// type de fourmi a ses propres cartes)
+    pub fn pheromones_nest(&self) -> &PheromoneMap {
+        self.colonies[0].maps(AntsType::EXPLORER).1
+    }
+
+    pub fn config(&self) -> &SimulationConfig {
+        &self.config
+    }
+
+    /// Remplace la configuration et resynchronise les paramètres Q-Learning
+    pub fn set_config(&mut self, config: SimulationConfig) {
+        self.rl_params = QLearningParams::from_config(&config);
+        if config.experience_buffer != self.config.experience_buffer {
+            self.experience = ExperienceBuffer::new(config.experience_buffer);
+        }
+        for colony in &mut self.colonies {
+            colony.set_state_layout(StateLayout::from_config(&config));
+            colony.set_per_type_maps(config.per_type_q_tables);
+            colony.set_scent_trails(config.policy == PolicyKind::ClassicAco);
+            colony.set_fear_map(config.uses_fear());
+            colony
+                .set_double_tables(config.learning_algorithm == LearningAlgorithm::DoubleQLearning);
+            colony.set_q_bounds(config.q_bounds());
+        }
+        self.config = config;
+        self.set_q_backends();
+    }
+
+    /// Fige la politique en cours de partie (actions gloutonnes, Q-tables ni mises à jour ni
+    /// évaporées) ou la libère. Comme `edit_tile`, refusé pour une partie enregistrée ou
+    /// rejouée : le replay ne garde que la configuration de départ.
+    pub fn set_frozen(&mut self, frozen: bool) -> Result<(), AntsError> {
+        if self.recording.is_some() || self.playback.is_some() {
+            return Err(AntsError::RecordedGame);
+        }
+        self.config.freeze = frozen;
+        Ok(())
+    }
+
+    pub fn rl_params(&self) -> &QLearningParams {
+        &self.rl_params
+    }
+
+    /// Le tick à jouer tombe la nuit du cycle jour/nuit
+    pub fn is_night(&self) -> bool {
+        self.config.is_night(self.current_tick_index)
+    }
+
+    /// Donne à une colonie l'alpha, le gamma, l'epsilon et les décroissances de `config` à la
+    /// place de ceux de la partie (tournoi entre deux réglages sur la même carte)
+    pub fn set_colony_learning(
+        &mut self,
+        colony: usize,
+        config: &SimulationConfig,
+    ) -> Result<(), String> {
+        if colony >= self.colonies.len() {
+            return Err(format!(
+                "Colonie {} absente de la carte ({} colonie(s))",
+                Colony::label(colony),
+                self.colonies.len()
+            ));
+        }
+        self.colony_learning.resize(self.colonies.len(), None);
+        self.colony_learning[colony] = Some(ColonyLearning {
+            alpha: config.alpha,
+            epsilon: config.epsilon,
+            params: QLearningParams::from_config(config),
+        });
+        Ok(())
+    }
+
+    /// Paramètres d'apprentissage courants d'une colonie
+    pub fn colony_params(&self, colony: usize) -> &QLearningParams {
+        match self.colony_learning.get(colony) {
+            Some(Some(learning)) => &learning.params,
+            _ => &self.rl_params,
+        }
+    }
+
+    pub fn history(&self) -> &Timeline {
+        &self.history
+    }
+
+    pub fn current_tick_index(&self) -> usize {
+        self.current_tick_index
+    }
+
+    pub fn spawn_stats(&self) -> &SpawnStats {
+        &self.spawn_stats
+    }
+
+    pub fn ticks_since_delivery(&self) -> u32 {
+        self.colonies[0].ticks_since_delivery
+    }
+
+    pub fn starved_ants(&self) -> u32 {
+        self.starved_ants
+    }
+
+    /// La colonie n'a pas pu payer son dernier entretien (`--food-upkeep`) : son nid ne fait
+    /// plus sortir de fourmis tant qu'une livraison ne l'a pas renfloué
+    pub fn in_famine(&self, colony: usize) -> bool {
+        self.colonies
+            .get(colony)
+            .is_some_and(|colony| colony.starving_ticks > 0)
+    }
+
+    /// Blocages détectés depuis le début de la partie (`--stuck-ticks`)
+    pub fn stuck_ants(&self) -> u32 {
+        self.stuck_ants
+    }
+
+    pub fn predators(&self) -> &[Predator] {
+        &self.predators
+    }
+
+    pub fn predator_stats(&self) -> &PredatorStats {
+        &self.predator_stats
+    }
+
+    /// Passages des fourmis sur chaque case depuis le début de la partie
+    pub fn traffic(&self) -> &TrafficMap {
+        &self.traffic
+    }
+
+    /// Décisions du méta-contrôleur (`--meta-controller`), de la plus ancienne à la plus récente
+    pub fn controller_decisions(&self) -> &[ControllerDecision] {
+        self.controller.decisions()
+    }
+
+    /// Plus court aller-retour entre un nid de `colony` et la nourriture la plus proche, sur
+    /// la carte de départ ; None si aucune nourriture n'était atteignable
+    pub fn optimal_round_trip(&self, colony: usize) -> Option<u32> {
+        self.optimal_round_trips.get(colony).copied().flatten()
+    }
+
+    /// Empreinte de la carte de départ (voir `scoring::map_fingerprint`), inchangée par les
+    /// modifications de la carte en cours de partie
+    pub fn map_fingerprint(&self) -> u64 {
+        self.map_fingerprint
+    }
+
+    /// Graine effective de la simulation (tirée au hasard si la configuration n'en fixe pas)
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Manifeste d'expérience des fichiers `artifacts` produits par cette partie
+    pub fn manifest(&self, kind: ArtifactKind, artifacts: Vec<String>) -> Manifest {
+        Manifest::new(kind, artifacts, &self.config, self.started_at)
+            .with_seed(self.seed)
+            .with_tick(self.current_tick_index as u64)
+    }
+
+    // Écrit le manifeste des fichiers `artifacts` dans `<base>.manifest.json`
+    fn write_manifest(
+        &self,
+        kind: ArtifactKind,
+        base: &str,
+        artifacts: Vec<String>,
+    ) -> Result<(), String> {
+        self.manifest(kind, artifacts).write(&manifest_path(base))
+    }
+
+    /// Commence à enregistrer les actions jouées, à appeler avant le premier tick
+    pub fn start_recording(&mut self) -> Result<(), String> {
+        if self.current_tick_index != 0 {
+            return Err("L'enregistrement doit commencer avant le premier tick".to_string());
+        }
+        let initial_state = self.capture_state();
+        self.recording = Some(Replay::new(self.seed, self.config.clone(), initial_state));
+        Ok(())
+    }
+
+    /// Écrit la partie enregistrée depuis `start_recording`, avec les empreintes de l'état
+    /// courant que `replay_verify` comparera
+    pub fn save_recording(&self, path: &str) -> Result<(), String> {
+        match &self.recording {
+            Some(replay) => {
+                let mut replay = replay.clone();
+                replay.final_state = Some(StateHashes::of(self));
+                replay.save(path)?;
+                self.write_manifest(ArtifactKind::Replay, path, vec![path.to_string()])
+            }
+            None => Err("Aucun enregistrement en cours".to_string()),
+        }
+    }
+
+    /// Fige la partie dans un fichier binaire que `load_checkpoint` reprend au même tick.
+    /// La timeline des snapshots et l'enregistrement en cours ne sont pas sauvegardés.
+    pub fn save_checkpoint(&self, path: &str) -> Result<(), String> {
+        let checkpoint = self.checkpoint()?;
+        // Écrit à côté puis renommé : une coupure pendant l'écriture garde l'ancien checkpoint
+        let partial = format!("{}.tmp", path);
+        let file = File::create(&partial)
+            .map_err(|e| format!("Impossible de créer {}: {}", partial, e))?;
+        bincode::serialize_into(BufWriter::new(file), &checkpoint)
+            .map_err(|e| format!("Impossible d'écrire le checkpoint {}: {}", partial, e))?;
+        fs::rename(&partial, path)
+            .map_err(|e| format!("Impossible d'écrire le checkpoint {}: {}", path, e))?;
+        self.write_manifest(ArtifactKind::Checkpoint, path, vec![path.to_string()])
+    }
+
+    // État de la partie sauvegardé par les checkpoints (et les sessions de la GUI)
+    pub(crate) fn checkpoint(&self) -> Result<Checkpoint, String> {
+        let config = toml::to_string(&self.config)
+            .map_err(|e| format!("Impossible de sérialiser la configuration: {}", e))?;
+        Ok(Checkpoint {
+            version: CHECKPOINT_VERSION,
+            seed: self.seed,
+            config,
+            state: self.capture_state(),
+            rng: self.rng.clone(),
+            visits: self.visits.clone(),
+            experience: self.experience.clone(),
+            static_lethal: self.static_lethal.clone(),
+            ticks_until_predator: self.ticks_until_predator,
+            predator_stats: self.predator_stats.clone(),
+            spawn_stats: self.spawn_stats.clone(),
+            starved_ants: self.starved_ants,
+            stuck_ants: self.stuck_ants,
+            next_ant_id: self.next_ant_id,
+            stats: self.stats.clone(),
+            totals: [
+                self.metrics.total_food_delivered,
+                self.metrics.total_deaths,
+                self.metrics.total_births,
+            ],
+            colony_learning: self.colony_learning.clone(),
+            optimal_round_trips: self.optimal_round_trips.clone(),
+            map_fingerprint: self.map_fingerprint,
+            traffic: self.traffic.clone(),
+            controller: self.controller.clone(),
+        })
+    }
+
+    /// Reprend une partie sauvegardée par `save_checkpoint`, avec sa configuration. Les
+    /// métriques par tick repartent dans un nouveau fichier à partir du tick du checkpoint.
+    pub fn load_checkpoint(path: &str) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("Impossible de lire {}: {}", path, e))?;
+        let checkpoint: Checkpoint = bincode::deserialize_from(BufReader::new(file))
+            .map_err(|e| format!("Checkpoint invalide {}: {}", path, e))?;
+        Self::from_checkpoint(checkpoint).map_err(|e| format!("Checkpoint {}: {}", path, e))
+    }
+
+    // Reprend la partie figée par `checkpoint`, la timeline repartant de son tick
+    pub(crate) fn from_checkpoint(checkpoint: Checkpoint) -> Result<Self, String> {
+        if checkpoint.version != CHECKPOINT_VERSION {
+            return Err(format!(
+                "version {}, version {} attendue",
+                checkpoint.version, CHECKPOINT_VERSION
+            ));
+        }
+        let config: SimulationConfig = toml::from_str(&checkpoint.config)
+            .map_err(|e| format!("configuration invalide: {}", e))?;
+
+        // Partie vide sur la carte sauvegardée, dont on remplace ensuite tout l'état
+        let state = checkpoint.state;
+        let mut manager = Self::from_grid(state.grid.clone(), Vec::new(), config, checkpoint.seed)
+            .map_err(|e| e.to_string())?;
+        manager.grid = state.grid;
+        manager.ants = state.ants;
+        manager.colonies = state.colonies;
+        manager.predators = state.predators;
+        manager.current_tick_index = state.tick;
+        manager.rng = checkpoint.rng;
+        manager.visits = checkpoint.visits;
+        manager.experience = checkpoint.experience;
+        manager.static_lethal = checkpoint.static_lethal;
+        manager.ticks_until_predator = checkpoint.ticks_until_predator;
+        manager.predator_stats = checkpoint.predator_stats;
+        manager.spawn_stats = checkpoint.spawn_stats;
+        manager.starved_ants = checkpoint.starved_ants;
+        manager.stuck_ants = checkpoint.stuck_ants;
+        manager.next_ant_id = checkpoint.next_ant_id;
+        manager.stats = checkpoint.stats;
+        manager.colony_learning = checkpoint.colony_learning;
+        manager.optimal_round_trips = checkpoint.optimal_round_trips;
+        manager.map_fingerprint = checkpoint.map_fingerprint;
+        manager.traffic = checkpoint.traffic;
+        manager.controller = checkpoint.controller;
+        let [food, deaths, births] = checkpoint.totals;
+        manager.metrics.total_food_delivered = food;
+        manager.metrics.total_deaths = deaths;
+        manager.metrics.total_births = births;
+
+        // La timeline repart du tick du checkpoint
+        manager.history.clear();
+        manager.save_snapshot();
+        Ok(manager)
+    }
+
+    pub fn is_replaying(&self) -> bool {
+        self.playback.is_some()
+    }
+
+    /// Vrai une fois tous les ticks enregistrés rejoués
+    pub fn replay_finished(&self) -> bool {
+        self.playback
+            .as_ref()
+            .is_some_and(|replay| self.current_tick_index as u64 >= replay.ticks)
+    }
+
+    /// Remplace la stratégie de sortie du nid de la configuration par une règle maison
+    pub fn set_spawn_policy(&mut self, policy: Box<dyn SpawnPolicy>) {
+        self.custom_spawn_policy = Some(policy);
+    }
+
+    /// Remplace les récompenses de la configuration par un modèle maison
+    pub fn set_reward_model(&mut self, model: Box<dyn RewardModel>) {
+        self.custom_reward_model = Some(model);
+    }
+
+    // Modèle de récompense actif
+    fn reward_model(&self) -> &dyn RewardModel {
+        match &self.custom_reward_model {
+            Some(model) => model.as_ref(),
+            None => &DefaultReward,
+        }
+    }
+
+    /// Enregistre un observateur qui recevra tous les événements de la simulation
+    pub fn add_observer(&mut self, observer: Box<dyn SimulationObserver>) {
+        self.observers.add(observer);
+    }
+
+    /// Appelé à la fin de chaque tick avec les métriques du tick
+    pub fn on_tick(&mut self, f: impl FnMut(&TickMetrics) + Send + 'static) {
+        self.observers.add_on_tick(f);
+    }
+
+    pub fn on_food_pickup(&mut self, f: impl FnMut(&FoodEvent) + Send + 'static) {
+        self.observers.add_on_food_pickup(f);
+    }
+
+    pub fn on_food_delivered(&mut self, f: impl FnMut(&FoodEvent) + Send + 'static) {
+        self.observers.add_on_food_delivered(f);
+    }
+
+    pub fn on_ant_death(&mut self, f: impl FnMut(&DeathEvent) + Send + 'static) {
+        self.observers.add_on_ant_death(f);
+    }
+
+    pub fn on_spawn(&mut self, f: impl FnMut(&SpawnEvent) + Send + 'static) {
+        self.observers.add_on_spawn(f);
+    }
+
+    /// Récompenses du dernier tick, indexées comme `ants()`
+    pub fn last_rewards(&self) -> &[f32] {
+        &self.last_rewards
+    }
+
+    pub fn metrics(&self) -> &MetricsCollector {
+        &self.metrics
+    }
+
+    /// Mesure désormais la durée de chaque phase des ticks (remet les compteurs à zéro)
+    pub fn enable_profiling(&mut self) {
+        self.timings = Some(PhaseTimings::default());
+    }
+
+    /// Durées cumulées des phases depuis `enable_profiling`
+    pub fn phase_timings(&self) -> Option<&PhaseTimings> {
+        self.timings.as_ref()
+    }
+
+    /// Courbes de la partie (nourriture au nid, fourmis actives, morts, Q moyen),
+    /// échantillonnées de plus en plus largement à mesure que la partie avance
+    pub fn stats_history(&self) -> &[StatsPoint] {
+        self.stats.points()
+    }
+
+    /// Écrit les métriques par tick encore en mémoire dans `config.output_file`
+    pub fn flush_metrics(&mut self) -> Result<(), String> {
+        self.metrics.flush()?;
+        match &self.config.output_file {
+            Some(path) => self.write_manifest(ArtifactKind::Metrics, path, vec![path.clone()]),
+            None => Ok(()),
+        }
+    }
+
+    /// Sauvegarde les deux cartes de phéromones (Q-tables) de la première colonie dans un fichier JSON
+    pub fn export_brain(&self, path: &str) -> Result<(), String> {
+        let json = self.brain_json()?;
+        fs::write(path, json).map_err(|e| format!("Impossible d'écrire {}: {}", path, e))?;
+        self.write_manifest(ArtifactKind::Brain, path, vec![path.to_string()])
+    }
+
+    /// Cerveau de la première colonie au format d'`export_brain`, sans passer par le disque
+    pub fn brain_json(&self) -> Result<String, String> {
+        serde_json::to_string(&BrainFile::of(&self.colonies[0]))
+            .map_err(|e| format!("Impossible de sérialiser le cerveau: {}", e))
+    }
+
+    /// Exporte les Q-tables de la première colonie en CSV et en carte de chaleur SVG
+    /// (voir `Colony::export_tables`) ; avec des cartes par type de fourmi, chaque type a les
+    /// siennes sous `<prefix>_<type>`. Renvoie les fichiers écrits.
+    pub fn dump_qtables(&self, prefix: &str) -> Result<Vec<String>, String> {
+        let colony = &self.colonies[0];
+        let written = if colony.has_per_type_maps() {
+            let mut written = Vec::new();
+            for ant_type in AntsType::all() {
+                let type_prefix = format!("{}_{}", prefix, ant_type.name());
+                written.extend(colony.export_tables(&type_prefix, ant_type, &self.grid)?);
+            }
+            written
+        } else {
+            colony.export_tables(prefix, AntsType::EXPLORER, &self.grid)?
+        };
+        self.write_manifest(ArtifactKind::QTables, prefix, written.clone())?;
+        Ok(written)
+    }
+
+    /// Exporte les cartes du type `ant_type` de la colonie `colony` sous `prefix` (voir
+    /// `Colony::export_tables`), avec leur manifeste. Renvoie les fichiers écrits.
+    pub fn export_tables(
+        &self,
+        colony: usize,
+        ant_type: AntsType,
+        prefix: &str,
+    ) -> Result<Vec<String>, String> {
+        let colony = self
+            .colonies
+            .get(colony)
+            .ok_or_else(|| format!("Colonie {} inconnue", colony))?;
+        let written = colony.export_tables(prefix, ant_type, &self.grid)?;
+        self.write_manifest(ArtifactKind::QTables, prefix, written.clone())?;
+        Ok(written)
+    }
+
+    /// Recharge des cartes de phéromones entraînées dans la première colonie ; un cerveau
+    /// appris sur une grille d'une autre taille y est adapté selon `config.brain_resample`
+    pub fn import_brain(&mut self, path: &str) -> Result<(), String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Impossible de lire {}: {}", path, e))?;
+        let mut brain: BrainFile = serde_json::from_str::<AnyBrainFile>(&content)
+            .map_err(|e| format!("Cerveau invalide {}: {}", path, e))?
+            .into();
+
+        let (width, height) = (self.grid.get_width(), self.grid.get_height());
+        let (from_width, from_height) = (brain.q_table.width(), brain.q_table.height());
+        if (from_width, from_height) != (width, height) {
+            warn!(
+                "Cerveau {} appris sur une grille {}x{}, redimensionné en {}x{} ({}) : \
+                 ses valeurs Q ne sont qu'une approximation pour cette carte",
+                path,
+                from_width,
+                from_height,
+                width,
+                height,
+                self.config.brain_resample.name()
+            );
+            brain = brain.resampled(width, height, self.config.brain_resample);
+        }
+        self.install_brain(brain, path)
+    }
+
+    /// Donne à la première colonie le cerveau de `colony`, entraînée sur une autre carte :
+    /// ses cartes de phéromones sont adaptées à la grille selon `mode`
+    pub fn adopt_brain(&mut self, colony: &Colony, mode: Resample) -> Result<(), String> {
+        let (width, height) = (self.grid.get_width(), self.grid.get_height());
+        let brain = BrainFile::of(colony).resampled(width, height, mode);
+        self.install_brain(
+            brain,
+            &format!("de la colonie {}", Colony::label(colony.id)),
+        )
+    }
+
+    /// Remplace les Q-tables de chaque colonie par `maps` : une liste par colonie, dans
+    /// l'ordre de `Colony::active_maps` (mise en commun des tables d'un ensemble)
+    pub(crate) fn replace_q_tables(&mut self, maps: &[Vec<PheromoneMap>]) {
+        for (colony, maps) in self.colonies.iter_mut().zip(maps) {
+            for (map, merged) in colony.active_maps_mut().into_iter().zip(maps) {
+                *map = merged.clone();
+            }
+        }
+        self.refresh_snapshot();
+    }
+
+    // Remplace le cerveau de la première colonie ; `source` nomme son origine dans les erreurs
+    fn install_brain(&mut self, brain: BrainFile, source: &str) -> Result<(), String> {
+        let (width, height) = (self.grid.get_width(), self.grid.get_height());
+        for table in std::iter::once(&brain.q_table).chain(&brain.caste_tables) {
+            table
+                .check(width, height)
+                .map_err(|e| format!("Cerveau {} invalide: {}", source, e))?;
+        }
+
+        let colony = &mut self.colonies[0];
+        if !brain.caste_tables.is_empty() && !colony.has_per_type_maps() {
+            return Err(format!(
+                "Le cerveau {} a des cartes par type de fourmi : activer --per-type-tables",
+                source
+            ));
+        }
+        let per_type = colony.has_per_type_maps();
+        colony.q_table = brain.q_table;
+        colony.caste_tables = brain.caste_tables;
+        // Un cerveau partagé sert de point de départ à chaque type de fourmi, et ses couches
+        // s'adaptent aux traits d'état de cette partie
+        colony.set_state_layout(StateLayout::from_config(&self.config));
+        colony.set_per_type_maps(per_type);
+        colony.set_double_tables(
+            self.config.learning_algorithm == LearningAlgorithm::DoubleQLearning,
+        );
+        colony.set_q_bounds(self.config.q_bounds());
+        self.set_q_backends();
+
+        // L'état courant de la timeline reflète maintenant le cerveau chargé
+        self.refresh_snapshot();
+        Ok(())
+    }
+
+    /// Remplace une case en cours de partie (case vide, mur, nourriture ou zone mortelle)
+    /// pour observer comment la politique apprise s'adapte : une transaction d'une seule
+    /// case (voir `apply_transaction`).
+    pub fn edit_tile(
+        &mut self,
+        position: (u32, u32),
+        tile_type: TileType,
+    ) -> Result<(), AntsError> {
+        self.apply_transaction(&GridTransaction::new().with_tile(position, tile_type))
+            .map(|_| ())
+    }
+
+    /// Applique d'un bloc les modifications de `transaction` et corrige les Q-tables des
+    /// cases devenues murs ou mortelles (voir [`crate::grid_transaction`]). Rien n'est
+    /// modifié si une des cases sort de la carte ou touche un nid : les nids portent l'état
+    /// des colonies et restent en place. Une partie enregistrée ou rejouée ne peut pas être
+    /// modifiée sans perdre son déterminisme.
+    pub fn apply_transaction(
+        &mut self,
+        transaction: &GridTransaction,
+    ) -> Result<TransactionReport, AntsError> {
+        if self.recording.is_some() || self.playback.is_some() {
+            return Err(AntsError::RecordedGame);
+        }
+        let (width, height) = (self.grid.get_width(), self.grid.get_height());
+        for ((x, y), tile_type) in transaction.changes() {
+            let (x, y) = (*x, *y);
+            let tile = self
+                .grid
+                .get_tile((x, y))
+                .ok_or(AntsError::TileOutOfBounds {
+                    x,
+                    y,
+                    width,
+                    height,
+                })?;
+            if matches!(tile_type, TileType::Nest { .. }) || tile.nest_colony().is_some() {
+                return Err(AntsError::ProtectedNest { x, y });
+            }
+        }
+        let report = self.apply_changes(transaction.changes());
+
+        // L'état courant de la timeline reflète maintenant la carte modifiée
+        self.refresh_snapshot();
+        Ok(report)
+    }
+
+    // Modifie les cases déjà vérifiées par l'appelant, puis corrige l'apprentissage et
+    // déplace ou tue les fourmis concernées
+    fn apply_changes(&mut self, changes: &[((u32, u32), TileType)]) -> TransactionReport {
+        let mut report = TransactionReport::default();
+        let mut walled = Vec::new();
+        let mut struck = Vec::new();
+        for (position, tile_type) in changes {
+            let position = *position;
+            let Some(tile) = self.grid.get_mut_tile(position) else {
+                continue;
+            };
+            if tile.tile_type == *tile_type {
+                continue;
+            }
+            let (was_wall, was_lethal) = (tile.tile_type == TileType::Wall, tile.is_lethal());
+            tile.tile_type = tile_type.clone();
+            let lethal = tile.is_lethal();
+            if let Some(cell) = self.cell_index(position) {
+                self.static_lethal[cell] = lethal;
+            }
+            report.changed += 1;
+            if *tile_type == TileType::Wall && !was_wall && !walled.contains(&position) {
+                walled.push(position);
+            }
+            if lethal && !was_lethal && !struck.contains(&position) {
+                struck.push(position);
+            }
+        }
+        // Une case modifiée plusieurs fois ne compte que pour son dernier type
+        walled.retain(|&cell| {
+            self.grid.get_tile(cell).map(|tile| &tile.tile_type) == Some(&TileType::Wall)
+        });
+        struck.retain(|&cell| {
+            self.grid
+                .get_tile(cell)
+                .is_some_and(|tile| tile.is_lethal())
+        });
+
+        for &cell in &walled {
+            self.forget_cell(cell);
+        }
+        for ant in &mut self.ants {
+            if ant
+                .position
+                .is_some_and(|position| walled.contains(&position))
+            {
+                Self::recall(&self.grid, ant);
+                report.recalled += 1;
+            }
+        }
+        report.walled = walled.len();
+        report.lethal = struck.len();
+        report.killed = self.strike(&struck);
+        report
+    }
+
+    fn capture_state(&self) -> GameStateSnapshot {
+        GameStateSnapshot {
+            tick: self.current_tick_index,
+            grid: self.grid.clone(),
+            ants: self.ants.clone(),
+            colonies: self.colonies.clone(),
+            predators: self.predators.clone(),
+        }
+    }
+
+    // Sauvegarder l'état du tick courant si la politique de snapshots le demande. Si on est
+    // revenu dans le passé et qu'on continue, le futur alternatif est supprimé.
+    fn save_snapshot(&mut self) {
+        let state = StateRef {
+            tick: self.current_tick_index,
+            grid: &self.grid,
+            ants: &self.ants,
+            colonies: &self.colonies,
+            predators: &self.predators,
+        };
+        self.history.record(state, &self.config);
+    }
+
+    // Le snapshot du tick courant, s'il existe, reflète maintenant l'état modifié
+    fn refresh_snapshot(&mut self) {
+        if self.history.last_tick() == Some(self.current_tick_index) {
+            self.save_snapshot();
+        }
+    }
+
+    /// Revenir au snapshot le plus proche du tick demandé (le plus ancien en cas d'égalité)
+    pub fn restore_snapshot(&mut self, tick: usize) {
+        if let Some(snapshot) = self.history.nearest(tick) {
+            self.grid = snapshot.grid;
+            self.ants = snapshot.ants;
+            self.colonies = snapshot.colonies;
+            self.predators = snapshot.predators;
+            self.current_tick_index = snapshot.tick;
+            // Les transitions mémorisées et les décisions du méta-contrôleur viennent d'un
+            // futur abandonné
+            self.experience.clear();
+            self.controller.rewind(snapshot.tick as u64);
+        }
+    }
+
+    pub fn game_step(&mut self) {
+        self.game_step_with_actions(&[]);
+    }
+
+    /// Avance d'un tick en imposant l'action de certaines fourmis (indexées comme `ants()`).
+    /// Les fourmis sans action imposée, ou absentes de `actions`, suivent la politique apprise.
+    pub fn game_step_with_actions(&mut self, actions: &[Option<Action>]) {
+        let tick = self.current_tick_index as u64;
+        let mut clock = PhaseClock::start(self.timings.is_some());
+
+        // Faire évoluer alpha et epsilon selon leurs calendriers de décroissance
+        self.rl_params
+            .apply_decay(self.config.alpha, self.config.epsilon, tick);
+        for learning in self.colony_learning.iter_mut().flatten() {
+            learning
+                .params
+                .apply_decay(learning.alpha, learning.epsilon, tick);
+        }
+        // Puis le méta-contrôleur les corrige d'après la nourriture livrée jusque-là
+        let delivered = self.colonies.iter().map(|colony| colony.score).sum();
+        let params = self.colony_params(0).clone();
+        if let Some(decision) = self
+            .controller
+            .update(&self.config, tick, delivered, &params)
+        {
+            info!(
+                kind = "controller",
+                tick,
+                "débit {:.3}/tick : epsilon x{:.2} ({:.3}), alpha x{:.2} ({:.3})",
+                decision.rate,
+                decision.epsilon_scale,
+                decision.epsilon,
+                decision.alpha_scale,
+                decision.alpha
+            );
+        }
+        self.controller.apply(&self.config, &mut self.rl_params);
+        for learning in self.colony_learning.iter_mut().flatten() {
+            self.controller.apply(&self.config, &mut learning.params);
+        }
+
+        self.update_hazards(self.current_tick_index + 1);
+        self.apply_events(self.current_tick_index + 1);
+
+        // Calculer la densité de fourmis sur chaque case pour éviter l'empilement excessif
+        let mut ant_density = self.compute_ant_density();
+
+        // Gérer le spawn intelligent des fourmis en sortant du nid
+        self.manage_smart_spawn(&ant_density);
+
+        for colony in &mut self.colonies {
+            colony.ticks_since_delivery = colony.ticks_since_delivery.saturating_add(1);
+        }
+
+        // Gérer le cooldown pour que les fourmis ne se déplacent pas à chaque tick ; la nuit,
+        // elles attendent plus longtemps
+        let slowdown = self.config.slowdown_at(self.current_tick_index);
+        let mut acting = Vec::new();
+        for (i, ant) in self.ants.iter_mut().enumerate() {
+            ant.age = ant.age.saturating_add(1);
+            // Ignorer les fourmis qui ne sont pas encore sur la carte
+            if ant.position.is_none() {
+                continue;
+            }
+            ant.stats.ticks_alive += 1;
+            if ant.cooldown > 0 {
+                ant.cooldown -= 1;
+                continue;
+            }
+            ant.cooldown = (ant.seconds_for_movement + 1) * slowdown - 1;
+            acting.push(i);
+        }
+
+        // Les tirages aléatoires sont faits à l'avance, dans l'ordre des fourmis,
+        // pour que la phase parallèle reste reproductible
+        let rolls: Vec<(f32, f32)> = acting
+            .iter()
+            .map(|_| (self.rng.gen::<f32>(), self.rng.gen::<f32>()))
+            .collect();
+        let setup = clock.lap();
+
+        // Phase 1 (parallèle, lecture seule) : choix de l'action, récompense et correction Q
+        let decisions = DecisionContext {
+            grid: &self.grid,
+            ants: &self.ants,
+            colonies: &self.colonies,
+            visits: &self.visits,
+            config: &self.config,
+            reward: self.reward_model(),
+            learning: (0..self.colonies.len())
+                .map(|colony| {
+                    let params = self.colony_params(colony);
+                    // La nuit, les fourmis explorent davantage
+                    let epsilon = self
+                        .config
+                        .epsilon_at(params.epsilon, self.current_tick_index);
+                    // Politique figée : toujours la meilleure action connue
+                    let (policy, epsilon) = if self.config.freeze {
+                        (ExplorationPolicy::EpsilonGreedy, 0.0)
+                    } else {
+                        (self.config.exploration_policy, epsilon)
+                    };
+                    (
+                        ExplorationParams {
+                            policy,
+                            epsilon,
+                            temperature: self.config.softmax_temperature,
+                            ucb_c: self.config.ucb_c,
+                            masked: !self.config.unmasked_exploration,
+                            fear_weight: self.config.fear_weight,
+                        },
+                        QLearningMath::new(params.alpha, params.gamma, epsilon),
+                    )
+                })
+                .collect(),
+            playback: self.playback.as_ref(),
+            overrides: actions,
+            tick,
+            nests: (0..self.colonies.len())
+                .map(|colony| self.grid.get_colony_nest_positions(colony))
+                .collect(),
+            food: if self.config.policy == PolicyKind::AStar {
+                self.grid.food_positions()
+            } else {
+                Vec::new()
+            },
+            predators: self.predators.iter().map(|p| p.position).collect(),
+        }
+        .decide_all(&acting, &rolls);
+        let decided = clock.lap();
+
+        // Phase 2 (séquentielle) : résolution des conflits de cases, mouvements et phéromones
+        self.last_rewards.clear();
+        self.last_rewards.resize(self.ants.len(), 0.0);
+        for decision in decisions {
+            self.last_rewards[decision.index] = decision.reward;
+            self.commit_decision(decision, &mut ant_density);
+        }
+        self.watch_stuck_ants();
+        let moved = clock.lap();
+
+        self.replay_experience();
+        self.step_predators();
+        self.apply_food_upkeep();
+        self.retire_old_ants();
+        self.breed();
+        self.grid
+            .regrow_food(self.config.food_regrowth(), self.current_tick_index + 1);
+        self.grid.decay_corpses(self.current_tick_index + 1);
+        let world = clock.lap();
+
+        // Appliquer l'évaporation (ralentie la nuit) et toutes les mises à jour de phéromones
+        // en attente ; une politique figée garde ses pistes telles quelles
+        let evaporation = self.config.evaporation_at(self.current_tick_index);
+        for colony in self.colonies.iter_mut().filter(|_| !self.config.freeze) {
+            for map in colony.active_maps_mut() {
+                map.apply_tick(evaporation);
+            }
+            for linear in colony.active_linear_mut() {
+                linear.apply_tick(evaporation);
+            }
+            if let Some(trails) = &mut colony.trails {
+                trails.evaporate(evaporation);
+            }
+        }
+        // L'alarme n'est pas apprise : elle se dissipe même quand la politique est figée
+        for fear in self.colonies.iter_mut().filter_map(|c| c.fear.as_mut()) {
+            fear.evaporate(self.config.fear_evaporation);
+        }
+        // Q(λ) : les traces des fourmis qui viennent d'agir s'atténuent de gamma * lambda
+        if self.config.lambda > 0.0 {
+            for &i in &acting {
+                let gamma = self.colony_params(self.ants[i].colony).gamma;
+                self.ants[i].trace.apply_tick(gamma * self.config.lambda);
+            }
+        }
+        let pheromones = clock.lap();
+        self.current_tick_index += 1;
+        self.save_snapshot();
+        if let Some(replay) = &mut self.recording {
+            replay.ticks = self.current_tick_index as u64;
+        }
+
+        let active_ants = self.ants.iter().filter(|a| a.position.is_some()).count() as u32;
+        let avg_q = self
+            .colonies
+            .iter()
+            .map(|c| {
+                let maps = c.active_maps();
+                maps.iter().map(|map| map.mean_abs_q()).sum::<f32>() / maps.len() as f32
+            })
+            .sum::<f32>()
+            / self.colonies.len() as f32;
+        let nest_food = self.grid.stored_food_total();
+        let mean_age = if self.ants.is_empty() {
+            0.0
+        } else {
+            self.ants.iter().map(|a| a.age as f32).sum::<f32>() / self.ants.len() as f32
+        };
+        self.metrics.end_tick(
+            self.current_tick_index,
+            active_ants,
+            nest_food,
+            avg_q,
+            mean_age,
+            AntTypeStats::by_type(&self.ants),
+        );
+        if self.stats.wants(self.current_tick_index) {
+            self.stats.record(StatsPoint {
+                tick: self.current_tick_index,
+                nest_food,
+                active_ants,
+                total_deaths: self.metrics.total_deaths,
+                avg_q_magnitude: avg_q,
+                population: self.ants.len() as u32,
+                mean_age,
+            });
+        }
+        if let Some(metrics) = self.metrics.last() {
+            self.observers.tick(metrics);
+        }
+        if let Some(timings) = &mut self.timings {
+            timings.ticks += 1;
+            timings.setup += setup;
+            timings.decisions += decided;
+            timings.movement += moved;
+            timings.world += world;
+            timings.pheromones += pheromones;
+            timings.bookkeeping += clock.lap();
+        }
+    }
+
+    // Rejouer `experience_batch` transitions tirées au hasard : leurs corrections, calculées
+    // sur les Q-values actuelles, s'ajoutent aux mises à jour en attente du tick
+    fn replay_experience(&mut self) {
+        if self.experience.is_empty() || !self.config.updates_q_tables() {
+            return;
+        }
+        let double = self.config.learning_algorithm == LearningAlgorithm::DoubleQLearning;
+        let batch = self
+            .experience
+            .sample(&mut self.rng, self.config.experience_batch);
+        for transition in batch {
+            let second = double && self.rng.gen::<bool>();
+            let params = self.colony_params(transition.colony);
+            let math = QLearningMath::new(params.alpha, params.gamma, params.epsilon);
+            let Some(colony) = self.colonies.get_mut(transition.colony) else {
+                continue;
+            };
+            let table = colony.table_mut(transition.ant_type);
+            let (x, y) = (transition.state.x, transition.state.y);
+            let map = table.layer(transition.state);
+            // Cible hors politique (max ou Double Q) : elle reste valable pour une action
+            // jouée il y a longtemps, quel que soit l'algorithme en ligne
+            let (q_curr, next_value) = if double {
+                let next_value = transition.next.map_or(0.0, |next| {
+                    let next_map = table.layer(next);
+                    math.double_q_value(
+                        &next_map.table_values(second, next.x, next.y),
+                        &next_map.table_values(!second, next.x, next.y),
+                    )
+                });
+                let q_curr = map.table_values(second, x, y)[transition.action.to_usize()];
+                (q_curr, next_value)
+            } else {
+                let next_value = transition.next.map_or(0.0, |next| {
+                    let next_q = table.backend(next);
+                    if self.config.masked_max_q {
+                        next_q.get_max_valid_q(&self.grid, next)
+                    } else {
+                        next_q.get_max_q(&self.grid, next)
+                    }
+                });
+                let q = table.backend(transition.state);
+                (
+                    q.get_q(&self.grid, transition.state, transition.action),
+                    next_value,
+                )
+            };
+            let delta = math.compute_delta(q_curr, transition.reward, next_value);
+            table.backend_mut(transition.state, second).queue_update(
+                &self.grid,
+                transition.state,
+                transition.action,
+                delta,
+            );
+        }
+    }
+
+    fn commit_decision(&mut self, decision: AntDecision, ant_density: &mut [u32]) {
+        let i = decision.index;
+        let (x, y) = match self.ants[i].position {
+            Some(pos) => pos,
+            None => return,
+        };
+        let (mode, colony, scope) = (self.ants[i].mode, self.ants[i].colony, self.ants[i].scope);
+        let (nx, ny) = decision.target;
+        let action = decision.action;
+
+        if let Some(replay) = &mut self.recording {
+            replay.record(self.current_tick_index as u64, i, action);
+        }
+        let (visits_food, visits_nest) = &mut self.visits[colony];
+        match mode {
+            AntsMode::FINDING => visits_food.record(x, y, action),
+            AntsMode::RETURNING => visits_nest.record(x, y, action),
+        }
+        let ant_type = self.ants[i].ant_type;
+        if self.config.updates_q_tables() && self.config.n_step > 1 {
+            self.learn_n_step(i, &decision);
+        } else if self.config.updates_q_tables() {
+            let table = self.colonies[colony].table_mut(ant_type);
+            if self.config.lambda > 0.0 {
+                // Q(λ) : la correction remonte le long de la trace de la fourmi
+                let layer = table.layer_index(decision.state);
+                let map = table
+                    .layer_mut(decision.state)
+                    .table_mut(decision.second_table);
+                let trace = &mut self.ants[i].trace;
+                if decision.exploratory {
+                    trace.reset();
+                }
+                trace.visit(map, layer, x, y, action);
+                map.queue_trace_update(trace, decision.delta);
+            } else {
+                table
+                    .backend_mut(decision.state, decision.second_table)
+                    .queue_update(&self.grid, decision.state, action, decision.delta);
+            }
+        }
+        if self.config.updates_q_tables() {
+            self.experience.push(Transition {
+                colony,
+                ant_type,
+                state: decision.state,
+                action,
+                reward: decision.reward,
+                next: (!decision.is_out && !decision.is_lethal).then_some(decision.next_state),
+            });
+        }
+        self.ants[i].planned_action = decision.next_planned;
+
+        // Vérifier si le mouvement est valide et autorisé
+        let mut move_allowed = !decision.is_out && self.grid.is_walkable(nx, ny);
+        if !decision.is_out {
+            // Vérifier que la case cible n'est pas saturée (limite configurable par case)
+            let target_density = self
+                .cell_index((nx, ny))
+                .map_or(0, |cell| ant_density[cell]);
+            if self.config.is_cell_full(target_density) {
+                move_allowed = false;
+            }
+        }
+
+        // Les fourmis avec vision détectent les zones mortelles et refusent d'avancer
+        if decision.is_lethal && scope > 0 {
+            move_allowed = false;
+        }
+
+        // Exécuter le mouvement si autorisé, ou tuer la fourmi si elle entre dans une zone mortelle
+        if !move_allowed {
+            return;
+        }
+        if decision.is_lethal {
+            // La fourmi meurt et disparait de la carte
+            if let Some(idx) = self.cell_index((x, y)) {
+                ant_density[idx] = ant_density[idx].saturating_sub(1);
+            }
+            self.sound_alarm(self.ants[i].colony, decision.target);
+            self.kill_ant(i, DeathCause::DeathZone);
+            return;
+        }
+
+        // Déplacer la fourmi et mettre à jour la densité
+        if let Some(old_idx) = self.cell_index((x, y)) {
+            ant_density[old_idx] = ant_density[old_idx].saturating_sub(1);
+        }
+        if let Some(new_idx) = self.cell_index((nx, ny)) {
+            ant_density[new_idx] += 1;
+        }
 
-impl AntsGameManager {
-    pub fn new(
-        width: u32,
-        height: u32,
-        tiles: Vec<Tile>,
-        ants: Vec<Ant>,
-        config: SimulationConfig,
-    ) -> Self {
-        let grid = Grid::new_with_tiles(width, height, tiles);
+        self.ants[i].move_to(nx, ny);
+        if (nx, ny) != (x, y) {
+            self.ants[i].stats.distance += 1;
+            self.ants[i].stats.trip_cells += 1;
+            self.traffic.record(nx, ny);
+        }
+        // La boue multiplie l'attente avant le prochain déplacement (vitesse + 1 ticks), en
+        // plus du ralentissement de la nuit
+        let slow_factor = self.grid.slow_factor(nx, ny);
+        if slow_factor > 1 {
+            let slowdown = self.config.slowdown_at(self.current_tick_index);
+            let ant = &mut self.ants[i];
+            ant.cooldown = (ant.seconds_for_movement + 1) * slow_factor * slowdown - 1;
+        }
 
-        // On crée l'état initial
-        let pheromones_food = PheromoneMap::new(width, height);
-        let pheromones_nest = PheromoneMap::new(width, height);
+        // Gérer les interactions: manger une nourriture, déposer au nid ou booster phéromones
+        let owner = &mut self.colonies[colony];
+        let interaction = Self::handle_interactions(
+            &mut self.grid,
+            &mut self.ants[i],
+            nx,
+            ny,
+            owner.table_mut(ant_type),
+            &self.config,
+        );
+        match interaction {
+            Some(Interaction::PickedUp(amount)) => {
+                debug!(
+                    kind = "pickup",
+                    tick = self.current_tick_index + 1,
+                    ant_id = self.ants[i].id,
+                    x = nx,
+                    y = ny,
+                    "ramasse {} unité(s)",
+                    amount
+                );
+                self.observers.food_pickup(FoodEvent {
+                    ant_index: i,
+                    ant_id: self.ants[i].id,
+                    colony,
+                    position: (nx, ny),
+                    amount,
+                })
+            }
+            Some(Interaction::Delivered(amount)) => {
+                debug!(
+                    kind = "delivery",
+                    tick = self.current_tick_index + 1,
+                    ant_id = self.ants[i].id,
+                    x = nx,
+                    y = ny,
+                    "livre {} unité(s) au nid {}",
+                    amount,
+                    Colony::label(colony)
+                );
+                owner.ticks_since_delivery = 0;
+                owner.score += amount as u64;
+                let optimal = self.optimal_round_trip(colony);
+                let stats = &mut self.ants[i].stats;
+                stats.trips += 1;
+                stats.food_delivered += amount;
+                // Sans aller-retour possible au départ (nourriture apparue depuis), le trajet
+                // compte comme optimal
+                stats.trip_distance += stats.trip_cells;
+                stats.optimal_distance += optimal.unwrap_or(stats.trip_cells);
+                stats.trip_cells = 0;
+                self.metrics.record_delivery(amount);
+                self.observers.food_delivered(FoodEvent {
+                    ant_index: i,
+                    ant_id: self.ants[i].id,
+                    colony,
+                    position: (nx, ny),
+                    amount,
+                });
+            }
+            None => {}
+        }
 
-        let mut manager = AntsGameManager {
-            grid: grid.clone(),
-            ants: ants.clone(),
-            pheromones_food: pheromones_food.clone(),
-            pheromones_nest: pheromones_nest.clone(),
-            rl_params: QLearningParams {
-                alpha: config.alpha,
-                gamma: config.gamma,
-                epsilon: config.epsilon,
-            },
-            config,
-            history: Vec::new(),
-            current_tick_index: 0,
+        // Après un ramassage ou un dépôt, la trace porte sur la carte de l'autre mode
+        if self.ants[i].mode != mode {
+            self.ants[i].trace.reset();
+            // Les pas à n pas en attente reçoivent leur cible, tronquée à ce pas
+            self.flush_n_step(i, decision.next_value, 0);
+        }
+
+        // ACO classique : la fourmi marque la case avec la piste de son mode (après un
+        // ramassage, c'est déjà la piste vers la nourriture)
+        let mode = self.ants[i].mode;
+        if let Some(trails) = self.colonies[colony]
+            .trails
+            .as_mut()
+            .filter(|_| !self.config.freeze)
+        {
+            trails.deposit(mode, nx, ny, self.config.aco_deposit);
+        }
+
+        self.spend_energy(i, colony, (nx, ny));
+    }
+
+    // Le déplacement coûte de l'énergie ; au nid, la fourmi mange les réserves de sa colonie
+    // pour refaire le plein, et meurt de faim si elle tombe à zéro ailleurs
+    fn spend_energy(&mut self, i: usize, colony: usize, (x, y): (u32, u32)) {
+        let max = self.config.ant_energy;
+        if max <= 0.0 {
+            return;
+        }
+        let ant = &mut self.ants[i];
+        ant.energy -= self.config.energy_drain;
+        if self.grid.is_colony_nest(x, y, colony) && ant.energy < max {
+            let wanted = ((max - ant.energy) / self.config.energy_per_food).ceil() as u32;
+            // La fourmi mange dans le nid où elle se trouve
+            let eaten = self
+                .grid
+                .get_mut_tile((x, y))
+                .map_or(0, |nest| nest.take_food_from_nest(wanted));
+            ant.energy = (ant.energy + eaten as f32 * self.config.energy_per_food).min(max);
+        }
+        if ant.energy <= 0.0 {
+            ant.current_charge = 0;
+            self.starved_ants += 1;
+            self.kill_ant(i, DeathCause::Starvation);
+        }
+    }
+
+    // Zones scriptées : les cases qu'elles quittent redeviennent vides, celles qu'elles
+    // atteignent deviennent mortelles. Les fourmis prises dedans meurent, et les Q-values
+    // des pas qui y mènent sont tirées vers la récompense de mort pour que la politique
+    // change de route au lieu d'y retourner.
+    fn update_hazards(&mut self, tick: usize) {
+        if self.config.hazards.is_empty() {
+            return;
+        }
+        let (width, height) = (self.grid.get_width(), self.grid.get_height());
+        let now = hazard::lethal_cells(&self.config.hazards, tick, width, height);
+        let before = match tick {
+            0 => Default::default(),
+            _ => hazard::lethal_cells(&self.config.hazards, tick - 1, width, height),
         };
 
-        // Sauvegarder l'état initial (tick 0)
-        manager.save_snapshot();
-        manager
+        for &(x, y) in before.difference(&now) {
+            if self.cell_index((x, y)).map(|cell| self.static_lethal[cell]) == Some(false) {
+                if let Some(tile) = self.grid.get_mut_tile((x, y)) {
+                    if tile.tile_type == TileType::DeathZone {
+                        tile.tile_type = TileType::Default;
+                    }
+                }
+            }
+        }
+
+        let mut struck = Vec::new();
+        for &(x, y) in &now {
+            let Some(tile) = self.grid.get_mut_tile((x, y)) else {
+                continue;
+            };
+            if tile.tile_type != TileType::Default {
+                continue;
+            }
+            tile.tile_type = TileType::DeathZone;
+            struck.push((x, y));
+        }
+
+        if !struck.is_empty() {
+            info!(
+                kind = "hazard",
+                tick,
+                "{} case(s) deviennent mortelles",
+                struck.len()
+            );
+        }
+        self.strike(&struck);
     }
 
-    pub fn new_game_mode_random(
-        width: u32,
-        height: u32,
-        mut ants: Vec<Ant>,
-        config: SimulationConfig,
-    ) -> Self {
-        let grid = Grid::new_random(width, height);
+    // Cases devenues mortelles en cours de partie : les fourmis qui s'y trouvent meurent.
+    // Renvoie le nombre de fourmis tuées.
+    fn strike(&mut self, cells: &[(u32, u32)]) -> usize {
+        for &cell in cells {
+            self.penalize_approaches(cell);
+        }
+        let mut killed = 0;
+        for i in 0..self.ants.len() {
+            if self.ants[i]
+                .position
+                .is_some_and(|pos| cells.contains(&pos))
+            {
+                if let Some(pos) = self.ants[i].position {
+                    self.sound_alarm(self.ants[i].colony, pos);
+                }
+                self.kill_ant(i, DeathCause::DeathZone);
+                killed += 1;
+            }
+        }
+        killed
+    }
 
-        for ant in &mut ants {
-            ant.spawn_at_nest(&grid);
+    // Chronologie du scénario : événements prévus pour ce tick. Une case déjà occupée par
+    // autre chose (mur, nid, ...) ignore l'événement.
+    fn apply_events(&mut self, tick: usize) {
+        let start = self
+            .config
+            .events
+            .partition_point(|event| event.tick < tick);
+        let events: Vec<EventKind> = self.config.events[start..]
+            .iter()
+            .take_while(|event| event.tick == tick)
+            .map(|event| event.kind)
+            .collect();
+        let mut changes = Vec::new();
+        for kind in events {
+            info!(kind = "scenario", tick, "{:?}", kind);
+            match kind {
+                EventKind::Food { x, y, amount } => {
+                    let Some(tile) = self.grid.get_mut_tile((x, y)) else {
+                        continue;
+                    };
+                    match &mut tile.tile_type {
+                        TileType::Default => {
+                            tile.tile_type = TileType::FoodSource {
+                                amount,
+                                value: SUGAR_VALUE,
+                            }
+                        }
+                        TileType::FoodSource {
+                            amount: current, ..
+                        } => *current += amount,
+                        _ => {}
+                    }
+                }
+                EventKind::Hazard { x, y, lethal } => {
+                    let Some(tile) = self.grid.get_tile((x, y)) else {
+                        continue;
+                    };
+                    let (from, to) = if lethal {
+                        (TileType::Default, TileType::DeathZone)
+                    } else {
+                        (TileType::DeathZone, TileType::Default)
+                    };
+                    if tile.tile_type == from {
+                        changes.push(((x, y), to));
+                    }
+                }
+                EventKind::PredatorWave { count } => {
+                    for _ in 0..count {
+                        let Some(pos) = Predator::find_spawn_position(&self.grid, &mut self.rng)
+                        else {
+                            break;
+                        };
+                        self.predators
+                            .push(Predator::new(pos, self.config.predator_health));
+                        self.predator_stats.predators_spawned += 1;
+                    }
+                }
+            }
         }
+        self.apply_changes(&changes);
+    }
 
-        let pheromones_food = PheromoneMap::new(width, height);
-        let pheromones_nest = PheromoneMap::new(width, height);
+    // Représentation des valeurs Q de chaque colonie selon la configuration
+    fn set_q_backends(&mut self) {
+        for colony in &mut self.colonies {
+            colony.set_q_backend(self.config.q_backend, &self.grid, self.config.linear_tile);
+        }
+    }
 
-        let mut manager = AntsGameManager {
-            grid: grid.clone(),
-            ants: ants.clone(),
-            pheromones_food: pheromones_food.clone(),
-            pheromones_nest: pheromones_nest.clone(),
-            rl_params: QLearningParams {
-                alpha: config.alpha,
-                gamma: config.gamma,
-                epsilon: config.epsilon,
-            },
-            config,
-            history: Vec::new(),
-            current_tick_index: 0,
+    // Q-Learning à n pas : le pas rejoint la mémoire de la fourmi, et le plus ancien reçoit
+    // sa correction dès que n pas sont connus. Une mort ou une sortie de carte termine
+    // l'épisode : tous les pas en attente sont corrigés sans valeur suivante.
+    fn learn_n_step(&mut self, i: usize, decision: &AntDecision) {
+        self.ants[i].n_step.push(NStep {
+            state: decision.state,
+            action: decision.action,
+            reward: decision.reward,
+            second_table: decision.second_table,
+        });
+        let keep = if decision.is_out || decision.is_lethal {
+            0
+        } else {
+            self.config.n_step as usize - 1
         };
+        self.flush_n_step(i, decision.next_value, keep);
+    }
 
-        // Sauvegarder l'état initial (tick 0)
-        manager.save_snapshot();
-        manager
+    // Corrige les plus anciens pas en attente de la fourmi jusqu'à n'en garder que `keep`,
+    // en complétant leur cible par `bootstrap`, la valeur de l'état qui suit le dernier pas
+    fn flush_n_step(&mut self, i: usize, bootstrap: f32, keep: usize) {
+        let params = self.colony_params(self.ants[i].colony);
+        let (alpha, gamma) = (params.alpha, params.gamma);
+        let ant = &mut self.ants[i];
+        let table = self.colonies[ant.colony].table_mut(ant.ant_type);
+        while ant.n_step.len() > keep {
+            let Some((step, target)) = ant.n_step.pop_target(gamma, bootstrap) else {
+                break;
+            };
+            let q = match table.linear() {
+                Some(linear) => linear.get_q(&self.grid, step.state, step.action),
+                None => table.layer(step.state).table_values(
+                    step.second_table,
+                    step.state.x,
+                    step.state.y,
+                )[step.action.to_usize()],
+            };
+            table
+                .backend_mut(step.state, step.second_table)
+                .queue_update(&self.grid, step.state, step.action, alpha * (target - q));
+        }
     }
 
-    fn save_snapshot(&mut self) {
-        // Si on est revenu dans le passé et qu'on a modifié quelque chose (ou qu'on continue),
-        // on supprime le futur alternatif.
-        if self.current_tick_index < self.history.len().saturating_sub(1) {
-            self.history.truncate(self.current_tick_index + 1);
+    // Case devenue un mur : ses valeurs et celles des pas qui y mènent depuis les cases
+    // voisines reviennent à la valeur initiale, dans toutes les cartes de toutes les colonies
+    fn forget_cell(&mut self, cell: (u32, u32)) {
+        if self.config.freeze {
+            return;
+        }
+        let mut entries: Vec<((u32, u32), Action)> =
+            Action::all().map(|action| (cell, action)).collect();
+        for action in MOVING_ACTIONS {
+            let from = target(cell.0, cell.1, action);
+            if let Some(step) = MOVING_ACTIONS
+                .into_iter()
+                .find(|&back| target(from.0, from.1, back) == cell)
+            {
+                entries.push((from, step));
+            }
+        }
+        for colony in &mut self.colonies {
+            for map in colony.active_maps_mut() {
+                let initial = map.bounds().initial;
+                let tables = if map.is_double() { 2 } else { 1 };
+                for second in (0..tables).map(|t| t == 1) {
+                    // Les cases hors de la carte sont ignorées par `queue_update`
+                    for &((x, y), action) in &entries {
+                        let q = map.table_values(second, x, y)[action.to_usize()];
+                        map.table_mut(second)
+                            .queue_update(x, y, action, initial - q);
+                    }
+                }
+            }
         }
+    }
 
-        self.history.push(GameStateSnapshot {
-            grid: self.grid.clone(),
-            ants: self.ants.clone(),
-            pheromones_food: self.pheromones_food.clone(),
-            pheromones_nest: self.pheromones_nest.clone(),
-        });
+    // Tire vers `reward_death` la valeur de chaque pas qui entre dans `cell`, dans toutes les
+    // cartes de toutes les colonies (et dans les deux tables en Double Q-learning)
+    fn penalize_approaches(&mut self, cell: (u32, u32)) {
+        if self.config.freeze {
+            return;
+        }
+        let alpha = self.rl_params.alpha;
+        let penalty = self.config.reward_death;
+        for action in MOVING_ACTIONS {
+            let from = target(cell.0, cell.1, action);
+            if !self.grid.is_walkable(from.0, from.1) {
+                continue;
+            }
+            // Pas inverse : de la case voisine vers la zone
+            let Some(step) = MOVING_ACTIONS
+                .into_iter()
+                .find(|&back| target(from.0, from.1, back) == cell)
+            else {
+                continue;
+            };
+            for colony in &mut self.colonies {
+                for map in colony.active_maps_mut() {
+                    let tables = if map.is_double() { 2 } else { 1 };
+                    for second in (0..tables).map(|t| t == 1) {
+                        let q = map.table_values(second, from.0, from.1)[step.to_usize()];
+                        map.table_mut(second).queue_update(
+                            from.0,
+                            from.1,
+                            step,
+                            alpha * (penalty - q),
+                        );
+                    }
+                }
+            }
+        }
+    }
 
-        // Mettre à jour l'index pour pointer sur le dernier élément
-        self.current_tick_index = self.history.len() - 1;
+    // Les fourmis restées trop longtemps près de leur case de référence sont bloquées : on
+    // les compte et elles suivent la reprise configurée
+    fn watch_stuck_ants(&mut self) {
+        let limit = self.config.stuck_ticks;
+        if limit == 0 {
+            return;
+        }
+        for i in 0..self.ants.len() {
+            let ant = &mut self.ants[i];
+            let Some(position) = ant.position else {
+                continue;
+            };
+            ant.recovery_ticks = ant.recovery_ticks.saturating_sub(1);
+            if !ant
+                .stuck_watch
+                .observe(position, self.config.stuck_radius, limit)
+            {
+                continue;
+            }
+            self.stuck_ants += 1;
+            debug!(
+                kind = "stuck",
+                tick = self.current_tick_index + 1,
+                ant_id = ant.id,
+                x = position.0,
+                y = position.1,
+                "bloquée depuis {} ticks ({})",
+                limit,
+                self.config.stuck_recovery.label()
+            );
+            match self.config.stuck_recovery {
+                StuckRecovery::Explore | StuckRecovery::RandomWalk => {
+                    ant.recovery_ticks = self.config.recovery_ticks;
+                    // L'action tirée d'avance (SARSA) est celle qui l'a bloquée
+                    ant.planned_action = None;
+                }
+                StuckRecovery::Recall => Self::recall(&self.grid, ant),
+            }
+        }
     }
 
-    pub fn restore_snapshot(&mut self, index: usize) {
-        if index < self.history.len() {
-            let snapshot = &self.history[index];
-            self.grid = snapshot.grid.clone();
-            self.ants = snapshot.ants.clone();
-            self.pheromones_food = snapshot.pheromones_food.clone();
-            self.pheromones_nest = snapshot.pheromones_nest.clone();
-            self.current_tick_index = index;
+    // Ramène une fourmi sur le nid de sa colonie le plus proche ; elle garde sa charge, son
+    // mode et son trajet en cours
+    fn recall(grid: &Grid, ant: &mut Ant) {
+        let Some(position) = ant.position else {
+            return;
+        };
+        let nest = grid
+            .get_colony_nest_positions(ant.colony)
+            .into_iter()
+            .min_by_key(|&(nx, ny)| nx.abs_diff(position.0) + ny.abs_diff(position.1));
+        if let Some(nest) = nest {
+            ant.recent_path.clear();
+            ant.trace.reset();
+            ant.n_step.clear();
+            ant.planned_action = None;
+            ant.move_to(nest.0, nest.1);
         }
     }
 
-    pub fn game_step(&mut self) {
-        // Synchroniser les paramètres Q-Learning depuis la configuration actuelle
-        self.rl_params.alpha = self.config.alpha;
-        self.rl_params.gamma = self.config.gamma;
-        self.rl_params.epsilon = self.config.epsilon;
+    // Une fourmi de la colonie vient de mourir d'un danger en `cell` : la colonie y dépose
+    // sa phéromone d'alarme, si elle tient une carte de peur
+    fn sound_alarm(&mut self, colony: usize, (x, y): (u32, u32)) {
+        if let Some(fear) = self.colonies.get_mut(colony).and_then(|c| c.fear.as_mut()) {
+            fear.alarm(x, y, self.config.fear_deposit);
+        }
+    }
 
-        let width = self.grid.get_width();
-        let height = self.grid.get_height();
+    // Retirer une fourmi de la carte en prévenant les métriques et les observateurs
+    fn kill_ant(&mut self, idx: usize, cause: DeathCause) {
+        let ant = &mut self.ants[idx];
+        let position = match ant.position.take() {
+            Some(pos) => pos,
+            None => return,
+        };
+        ant.trace.reset();
+        ant.n_step.clear();
+        // Le cadavre (et la charge transportée) reste sur place, sauf si un prédateur l'a dévoré
+        let carried = ant.current_charge;
+        if self.config.corpse_food > 0 && cause != DeathCause::Predator {
+            self.grid.drop_corpse(
+                position,
+                self.config.corpse_food + carried,
+                self.config.corpse_decay,
+            );
+        }
+        let ant = &self.ants[idx];
+        info!(
+            kind = "death",
+            tick = self.current_tick_index + 1,
+            ant_id = ant.id,
+            x = position.0,
+            y = position.1,
+            "{} de la colonie {} morte ({})",
+            ant.ant_type.label(),
+            Colony::label(ant.colony),
+            cause.label()
+        );
+        self.metrics.record_death();
+        if let Some(colony) = self.colonies.get_mut(ant.colony) {
+            colony.deaths += 1;
+        }
+        self.observers.ant_death(DeathEvent {
+            ant_index: idx,
+            ant_id: ant.id,
+            colony: ant.colony,
+            position,
+            cause,
+        });
+    }
 
-        // Calculer la densité de fourmis sur chaque case pour éviter l'empilement excessif
-        let mut ant_density = self.compute_ant_density();
+    fn step_predators(&mut self) {
+        // Faire apparaître un prédateur à intervalle régulier tant que le maximum n'est pas atteint
+        if self.config.max_predators > 0 {
+            self.ticks_until_predator = self.ticks_until_predator.saturating_sub(1);
+            if self.ticks_until_predator == 0 {
+                self.ticks_until_predator = self.config.predator_spawn_interval.max(1);
+                if (self.predators.len() as u32) < self.config.max_predators {
+                    if let Some(pos) = Predator::find_spawn_position(&self.grid, &mut self.rng) {
+                        info!(
+                            kind = "predator",
+                            tick = self.current_tick_index + 1,
+                            x = pos.0,
+                            y = pos.1,
+                            "un prédateur apparaît"
+                        );
+                        self.predators
+                            .push(Predator::new(pos, self.config.predator_health));
+                        self.predator_stats.predators_spawned += 1;
+                    }
+                }
+            }
+        }
 
-        // Gérer le spawn intelligent des fourmis en sortant du nid
-        self.manage_smart_spawn(&ant_density, width);
+        // Une colonie dont aucune combattante n'est sur la carte laisse ses nids sans défense
+        let undefended: Vec<bool> = (0..self.colonies.len())
+            .map(|colony| self.count_active(colony, AntsType::FIGHTER) == 0)
+            .collect();
+        let raided_nests: Vec<(u32, u32)> = if self.config.predator_raid > 0 {
+            self.grid
+                .get_nest_positions()
+                .into_iter()
+                .filter(|&pos| {
+                    let tile = self.grid.get_tile(pos);
+                    tile.and_then(Tile::nest_colony)
+                        .is_some_and(|colony| undefended[colony])
+                        && tile.and_then(Tile::stored_food).unwrap_or(0) > 0
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
 
-        let mut i = 0;
-        while i < self.ants.len() {
-            // Ignorer les fourmis qui ne sont pas encore sur la carte
-            if self.ants[i].position.is_none() {
-                i += 1;
+        // Chasse : chaque prédateur avance vers une ouvrière ou un nid sans défense, dévore
+        // l'ouvrière qu'il atteint et pille le nid qu'il touche
+        for p in 0..self.predators.len() {
+            if self.predators[p].cooldown > 0 {
+                self.predators[p].cooldown -= 1;
                 continue;
             }
+            self.predators[p].cooldown = self.config.predator_speed;
+            let mut targets: Vec<(u32, u32)> = self
+                .ants
+                .iter()
+                .filter(|a| a.ant_type != AntsType::FIGHTER)
+                .filter_map(|a| a.position)
+                .collect();
+            targets.extend_from_slice(&raided_nests);
+            self.predators[p].hunt(&self.grid, &targets, &mut self.rng);
 
-            // Gérer le cooldown pour que les fourmis ne se déplacent pas à chaque tick
-            if self.ants[i].cooldown > 0 {
-                self.ants[i].cooldown -= 1;
-                i += 1;
-                continue;
+            let pos = self.predators[p].position;
+            if let Some(prey) = self
+                .ants
+                .iter()
+                .position(|a| a.ant_type != AntsType::FIGHTER && a.position == Some(pos))
+            {
+                self.ants[prey].current_charge = 0;
+                self.predator_stats.ants_killed += 1;
+                self.sound_alarm(self.ants[prey].colony, pos);
+                self.kill_ant(prey, DeathCause::Predator);
             }
-            self.ants[i].cooldown = self.ants[i].seconds_for_movement;
+            self.raid_nest(p, &undefended);
+        }
 
-            let (x, y) = self.ants[i].position.unwrap();
-            let mode = self.ants[i].mode;
-            let scope = self.ants[i].scope;
+        self.resolve_combat();
+    }
 
-            // Sélectionner la prochaine action via la stratégie Epsilon-Greedy (exploration vs exploitation)
-            let (chosen_action, q_curr) = self.choose_action(x, y, mode);
-            let (nx, ny) = self.ants[i].get_target_position(chosen_action);
+    // Un prédateur sur un nid sans défense, ou à côté, y vole de la nourriture
+    fn raid_nest(&mut self, p: usize, undefended: &[bool]) {
+        let raid = self.config.predator_raid;
+        if raid == 0 {
+            return;
+        }
+        let predator = &self.predators[p];
+        let Some(nest) = self.grid.get_nest_positions().into_iter().find(|&pos| {
+            predator.is_adjacent_to(pos)
+                && self
+                    .grid
+                    .get_tile(pos)
+                    .and_then(Tile::nest_colony)
+                    .is_some_and(|colony| undefended[colony])
+        }) else {
+            return;
+        };
+        let Some(tile) = self.grid.get_mut_tile(nest) else {
+            return;
+        };
+        let colony = tile.nest_colony().unwrap_or(0);
+        let stolen = tile.take_food_from_nest(raid);
+        if stolen == 0 {
+            return;
+        }
+        self.predator_stats.food_raided += stolen;
+        info!(
+            kind = "predator",
+            tick = self.current_tick_index + 1,
+            x = nest.0,
+            y = nest.1,
+            "un prédateur pille {} unité(s) du nid {}",
+            stolen,
+            Colony::label(colony)
+        );
+    }
 
-            // Vérifier si le mouvement est valide et autorisé
-            let is_out = nx >= width || ny >= height;
-            let mut move_allowed = !is_out && self.grid.is_walkable(nx, ny);
-            let mut is_lethal = false;
+    // Les combattantes au contact d'un prédateur le frappent (chaque coup porte avec la
+    // probabilité configurée), puis un prédateur encore debout blesse la première d'entre
+    // elles ; une combattante revenue à son nid y est soignée
+    fn resolve_combat(&mut self) {
+        for ant in &mut self.ants {
+            if ant.ant_type == AntsType::FIGHTER
+                && ant
+                    .position
+                    .is_some_and(|(x, y)| self.grid.is_colony_nest(x, y, ant.colony))
+            {
+                ant.health = ant.max_health;
+            }
+        }
 
-            if !is_out {
-                is_lethal = self.grid.is_lethal(nx, ny);
-                // Vérifier que la case cible n'est pas saturée (max 10 fourmis par case)
-                let target_idx = (ny * width + nx) as usize;
-                if ant_density.get(target_idx).copied().unwrap_or(0) >= 10 {
-                    move_allowed = false;
+        let clashes = predator::clashes(&self.ants, &self.predators);
+        let mut slain = Vec::new();
+        let mut wounded = Vec::new();
+        for (p, predator) in self.predators.iter_mut().enumerate() {
+            let fighters: Vec<usize> = clashes
+                .iter()
+                .filter(|&&(_, target)| target == p)
+                .map(|&(ant, _)| ant)
+                .collect();
+            let Some(&first) = fighters.first() else {
+                continue;
+            };
+            for &f in &fighters {
+                if self.rng.gen::<f32>() < self.config.fighter_win_chance {
+                    predator.health = predator.health.saturating_sub(self.ants[f].attack);
                 }
             }
+            if predator.health == 0 {
+                slain.push((p, first));
+            } else {
+                wounded.push(first);
+            }
+        }
 
-            // Les fourmis avec vision détectent les zones mortelles et refusent d'avancer
-            if is_lethal && scope > 0 {
-                move_allowed = false;
+        for &f in &wounded {
+            let fighter = &mut self.ants[f];
+            if fighter.position.is_none() {
+                continue;
+            }
+            fighter.health = fighter.health.saturating_sub(self.config.predator_attack);
+            if fighter.health == 0 {
+                let (colony, position) = (fighter.colony, fighter.position);
+                self.predator_stats.fighters_lost += 1;
+                if let Some(position) = position {
+                    self.sound_alarm(colony, position);
+                }
+                self.kill_ant(f, DeathCause::Combat);
             }
+        }
 
-            // Calculer la récompense en fonction du type de case visée
-            let reward = self.calculate_reward(is_lethal, mode, nx, ny);
+        // Retirer les prédateurs tués en partant de la fin pour garder les indices valides
+        for &(p, f) in slain.iter().rev() {
+            let (x, y) = self.predators[p].position;
+            info!(
+                kind = "predator",
+                tick = self.current_tick_index + 1,
+                ant_id = self.ants[f].id,
+                x,
+                y,
+                "prédateur tué par une combattante"
+            );
+            self.predators.remove(p);
+            self.predator_stats.predators_killed += 1;
+        }
+    }
 
-            let map = match mode {
-                AntsMode::FINDING => &self.pheromones_food,
-                _ => &self.pheromones_nest,
-            };
+    fn apply_food_upkeep(&mut self) {
+        if self.config.food_upkeep_per_ant <= 0.0 {
+            return;
+        }
 
-            // Calculer la valeur Q maximale de l'état suivant pour la formule de Bellman
-            let max_next_q = if is_out || is_lethal {
-                0.0
-            } else {
-                map.get_max_q(nx, ny, &self.grid)
-            };
+        // Le nid prélève l'entretien de tout l'intervalle d'un coup
+        let interval = self.config.upkeep_interval.max(1);
+        if !(self.current_tick_index + 1).is_multiple_of(interval as usize) {
+            return;
+        }
 
-            // Calculer la correction Delta selon la formule Q-Learning: Alpha * (Reward + Gamma * MaxNext - Current)
-            let delta =
-                self.rl_params.alpha * (reward + self.rl_params.gamma * max_next_q - q_curr);
-
-            match mode {
-                AntsMode::FINDING => self
-                    .pheromones_food
-                    .queue_update(x, y, chosen_action, delta),
-                AntsMode::RETURNING => {
-                    self.pheromones_nest
-                        .queue_update(x, y, chosen_action, delta)
-                }
-            };
+        for c in 0..self.colonies.len() {
+            // Chaque fourmi active consomme une fraction d'unité de nourriture par tick
+            let active = self
+                .ants
+                .iter()
+                .filter(|a| a.colony == c && a.position.is_some())
+                .count();
+            let colony = &mut self.colonies[c];
+            colony.upkeep_debt += active as f32 * self.config.food_upkeep_per_ant * interval as f32;
+            let due = colony.upkeep_debt.floor() as u32;
+            if due == 0 {
+                continue;
+            }
+            colony.upkeep_debt -= due as f32;
 
-            // Exécuter le mouvement si autorisé, ou tuer la fourmi si elle entre dans une zone mortelle
-            if move_allowed {
-                if is_lethal {
-                    // La fourmi meurt et disparait de la carte
-                    let idx = (y * width + x) as usize;
-                    if idx < ant_density.len() {
-                        ant_density[idx] = ant_density[idx].saturating_sub(1);
-                    }
-                    self.ants[i].position = None;
-                } else {
-                    // Déplacer la fourmi et mettre à jour la densité
-                    let old_idx = (y * width + x) as usize;
-                    let new_idx = (ny * width + nx) as usize;
-                    if old_idx < ant_density.len() {
-                        ant_density[old_idx] = ant_density[old_idx].saturating_sub(1);
-                    }
-                    if new_idx < ant_density.len() {
-                        ant_density[new_idx] += 1;
-                    }
+            // Une colonie sans nid n'a aucune réserve pour payer
+            let paid = self.grid.take_food_from_colony_nest(c, due).unwrap_or(0);
+            if paid >= due {
+                colony.starving_ticks = 0;
+                continue;
+            }
 
-                    self.ants[i].move_to(nx, ny);
-
-                    // Gérer les interactions: manger une nourriture, déposer au nid ou booster phéromones
-                    Self::handle_interactions(
-                        &mut self.grid,
-                        &mut self.ants[i],
-                        nx,
-                        ny,
-                        &mut self.pheromones_food,
-                        &mut self.pheromones_nest,
-                        &self.config,
-                    );
+            // Réserves épuisées : la colonie est en famine (voir `in_famine`), une fourmi
+            // meurt tous les `starvation_ticks` ticks de famine
+            let period = self.config.starvation_ticks.max(1);
+            let before = colony.starving_ticks;
+            colony.starving_ticks += interval;
+            let deaths = colony.starving_ticks / period - before / period;
+            for _ in 0..deaths {
+                if let Some(idx) = self
+                    .ants
+                    .iter()
+                    .rposition(|a| a.colony == c && a.position.is_some())
+                {
+                    self.starved_ants += 1;
+                    self.kill_ant(idx, DeathCause::Starvation);
                 }
             }
-            i += 1;
         }
+    }
 
-        // Appliquer l'évaporation et tous les mises à jour de phéromones en attente
-        self.pheromones_food
-            .apply_tick(self.config.pheromone_evaporation);
-        self.pheromones_nest
-            .apply_tick(self.config.pheromone_evaporation);
-        self.save_snapshot();
+    // Fin de vie : une fourmi qui a dépassé la durée de vie de son type meurt dès qu'elle est
+    // au nid et quitte la colonie ; seules les naissances la remplacent. Sur la carte, elle
+    // rentre d'abord (voir `Ant::is_expired`).
+    fn retire_old_ants(&mut self) {
+        if self.config.lifespan.iter().all(|&lifespan| lifespan == 0) {
+            return;
+        }
+        // De la fin vers le début pour que les indices restant à voir ne bougent pas
+        for i in (0..self.ants.len()).rev() {
+            let ant = &self.ants[i];
+            if !ant.is_expired(&self.config) {
+                continue;
+            }
+            match ant.position {
+                Some((x, y)) if !self.grid.is_colony_nest(x, y, ant.colony) => continue,
+                Some(_) => self.kill_ant(i, DeathCause::OldAge),
+                // Déjà au nid : jamais sortie, ou morte et en attente de ressortir
+                None => info!(
+                    kind = "death",
+                    tick = self.current_tick_index + 1,
+                    ant_id = ant.id,
+                    "{} de la colonie {} morte au nid ({})",
+                    ant.ant_type.label(),
+                    Colony::label(ant.colony),
+                    DeathCause::OldAge.label()
+                ),
+            }
+            self.ants.remove(i);
+        }
+    }
+
+    // Naissances : à intervalle régulier, chaque nid assez riche paie `birth_cost` unités pour
+    // une nouvelle fourmi, du type le plus en retard sur `birth_ratio` parmi ceux qui ont
+    // encore de la place au nid. Elle attend au nid d'être déployée.
+    fn breed(&mut self) {
+        let cost = self.config.birth_cost;
+        if cost == 0
+            || !(self.current_tick_index + 1).is_multiple_of(self.config.birth_interval as usize)
+        {
+            return;
+        }
+        for colony in 0..self.colonies.len() {
+            if self.grid.colony_stored_food(colony) < cost {
+                continue;
+            }
+            let population = |ant_type: AntsType| {
+                self.ants
+                    .iter()
+                    .filter(|a| a.colony == colony && a.ant_type == ant_type)
+                    .count() as u32
+            };
+            // Retard sur la proportion visée : effectif / poids, le plus petit l'emporte
+            let newborn = AntsType::all()
+                .into_iter()
+                .filter_map(|ant_type| {
+                    let weight = self.config.birth_ratio[ant_type.index()];
+                    let count = population(ant_type);
+                    (weight > 0 && count < self.type_cap(colony, ant_type))
+                        .then_some((ant_type, count as f32 / weight as f32))
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(ant_type, _)| ant_type);
+            let Some(ant_type) = newborn else {
+                continue;
+            };
+            if self.grid.take_food_from_colony_nest(colony, cost).is_err() {
+                continue;
+            }
+            let mut ant = Ant::new(ant_type, &self.config);
+            ant.id = self.next_ant_id;
+            ant.colony = colony;
+            self.next_ant_id += 1;
+            self.ants.push(ant);
+            self.metrics.record_birth();
+            info!(
+                kind = "birth",
+                tick = self.current_tick_index + 1,
+                ant_id = self.next_ant_id - 1,
+                "naissance : {} dans la colonie {}",
+                ant_type.label(),
+                Colony::label(colony)
+            );
+        }
     }
 
-    fn compute_ant_density(&self) -> Vec<u8> {
-        let width = self.grid.get_width();
-        let height = self.grid.get_height();
-        let mut density = vec![0u8; (width * height) as usize];
+    // Rang de la case dans les tableaux par case (densité, zones mortelles fixes), None hors
+    // de la carte
+    fn cell_index(&self, cell: (u32, u32)) -> Option<usize> {
+        self.grid
+            .shape()
+            .index(cell.into())
+            .map(GridIndex::as_usize)
+    }
+
+    fn compute_ant_density(&self) -> Vec<u32> {
+        let shape = self.grid.shape();
+        let mut density = vec![0u32; shape.len()];
 
         for ant in &self.ants {
-            if let Some((ax, ay)) = ant.position {
-                let idx = (ay * width + ax) as usize;
-                if idx < density.len() {
-                    density[idx] += 1;
-                }
+            if let Some(idx) = ant.coord().and_then(|coord| shape.index(coord)) {
+                density[idx.as_usize()] += 1;
             }
         }
         density
     }
 
-    fn manage_smart_spawn(&mut self, ant_density: &[u8], width: u32) {
+    fn manage_smart_spawn(&mut self, ant_density: &[u32]) {
+        // Chaque nid fait sortir ses propres fourmis, jusqu'à `spawn_rate_per_tick` par tick
+        for colony in 0..self.colonies.len() {
+            for deployed in 0..self.config.spawn_rate_per_tick {
+                if !self.spawn_for_colony(colony, ant_density, deployed) {
+                    break;
+                }
+            }
+        }
+    }
+
+    // Déploie au plus une fourmi ; `deployed` compte celles déjà sorties du nid ce tick.
+    // Renvoie false quand le nid ne peut plus rien faire sortir.
+    fn spawn_for_colony(&mut self, colony: usize, ant_density: &[u32], deployed: u32) -> bool {
         // Récupérer la limite du nombre de fourmis actives depuis la configuration
         let max_active_ants = self.config.nest_capacity as usize;
-        const MIN_EXPLORERS_ACTIVE: usize = 3;
 
-        let active_explorers = self
+        let active_explorers = self.count_active(colony, AntsType::EXPLORER);
+        let active_pickers = self.count_active(colony, AntsType::PICKER);
+        let active_total = self
             .ants
             .iter()
-            .filter(|a| a.position.is_some() && a.ant_type == AntsType::EXPLORER)
+            .filter(|a| a.colony == colony && a.position.is_some())
             .count();
-        let active_total = self.ants.iter().filter(|a| a.position.is_some()).count();
 
-        // Arrêter le spawn si le nid est saturé ou si la limite globale de fourmis actives est atteinte
-        if active_total >= max_active_ants {
-            return;
+        // Arrêter le spawn si le nid est saturé ou si la limite de fourmis actives de la colonie est atteinte,
+        // ou si la colonie en famine n'a plus de quoi nourrir de nouvelles sorties
+        if active_total >= max_active_ants || self.in_famine(colony) {
+            return false;
         }
 
-        let nest_pos = match self.grid.get_nest_position() {
-            Some(pos) => pos,
-            None => return,
+        // Avec plusieurs nids, la fourmi sort du moins encombré ; à égalité, les nids se
+        // relaient d'un tick à l'autre
+        let crowd = |&cell: &(u32, u32)| self.cell_index(cell).map_or(0, |i| ant_density[i]);
+        let mut nests = self.grid.get_colony_nest_positions(colony);
+        if nests.is_empty() {
+            return false;
+        }
+        let turn = self.current_tick_index % nests.len();
+        nests.rotate_left(turn);
+        let Some(nest_pos) = nests.into_iter().min_by_key(crowd) else {
+            return false;
         };
 
-        let nest_idx = (nest_pos.1 * width + nest_pos.0) as usize;
-        if ant_density.get(nest_idx).copied().unwrap_or(0) >= 10 {
-            return;
+        let at_nest = crowd(&nest_pos) + deployed;
+        if self.config.is_cell_full(at_nest) {
+            return false;
         }
 
-        // Privilégier la sortie des explorateurs si leur nombre est inférieur au minimum
-        let target_type = if active_explorers < MIN_EXPLORERS_ACTIVE {
-            Some(AntsType::EXPLORER)
-        } else {
-            None
+        // Demander à la stratégie de spawn quel type de fourmi déployer
+        let state = ColonyState {
+            tick: self.current_tick_index,
+            active_explorers: active_explorers as u32,
+            active_pickers: active_pickers as u32,
+            active_fighters: self.count_active(colony, AntsType::FIGHTER) as u32,
+            food_trail_strength: AntsType::all()
+                .iter()
+                .map(|&ant_type| self.colonies[colony].maps(ant_type).0.max_value())
+                .fold(0.0, f32::max),
+            ticks_since_delivery: self.colonies[colony].ticks_since_delivery,
         };
+        let policy = match &self.custom_spawn_policy {
+            Some(policy) => policy.as_ref(),
+            None => self.config.spawn_policy.policy(),
+        };
+        let (target_type, reason) = choose_type(policy, &state, &self.config);
 
+        // Types dont le nid a encore des places libres
+        let has_room = AntsType::all().map(|ant_type| {
+            (self.count_active(colony, ant_type) as u32) < self.type_cap(colony, ant_type)
+        });
         let ant_index_to_spawn = self.ants.iter().position(|a| {
-            a.position.is_none() && (target_type.is_none() || a.ant_type == target_type.unwrap())
+            a.colony == colony
+                && a.position.is_none()
+                && has_room[a.ant_type.index()]
+                && (target_type.is_none() || a.ant_type == target_type.unwrap())
         });
 
+        // Si pas de fourmi du type prioritaire disponible, déployer n'importe quelle autre fourmi inactive
+        let (idx, reason) = match ant_index_to_spawn {
+            Some(idx) => (idx, reason),
+            None if target_type.is_some() => {
+                let Some(idx) = self.ants.iter().position(|a| {
+                    a.colony == colony && a.position.is_none() && has_room[a.ant_type.index()]
+                }) else {
+                    return false;
+                };
+                (idx, SpawnReason::Any)
+            }
+            None => return false,
+        };
+
         // Déployer la fourmi trouvée en la plaçant au nid
-        if let Some(idx) = ant_index_to_spawn {
-            self.ants[idx].position = Some(nest_pos);
-            self.ants[idx].mode = AntsMode::FINDING;
-            self.ants[idx].current_charge = 0;
-            self.ants[idx].cooldown = 2;
-        } else if target_type.is_some() {
-            // Si pas d'explorateur disponible, déployer n'importe quelle autre fourmi inactive
-            if let Some(idx) = self.ants.iter().position(|a| a.position.is_none()) {
-                self.ants[idx].position = Some(nest_pos);
-                self.ants[idx].mode = AntsMode::FINDING;
+        self.ants[idx].spawn_at(nest_pos);
+        self.ants[idx].mode = AntsMode::FINDING;
+        self.ants[idx].energy = self.config.ant_energy;
+        self.ants[idx].current_charge = 0;
+        self.ants[idx].cooldown = self.config.spawn_cooldown;
+        self.spawn_stats.record(self.ants[idx].ant_type, reason);
+        self.notify_spawn(idx);
+        true
+    }
+
+    // Au départ, les fourmis au-delà des places du nid y restent jusqu'à ce qu'une place se libère
+    fn hold_back_over_capacity(&mut self) {
+        let mut deployed = vec![[0u32; 3]; self.colonies.len()];
+        let mut total = vec![0u32; self.colonies.len()];
+        for i in 0..self.ants.len() {
+            let (colony, ant_type) = (self.ants[i].colony, self.ants[i].ant_type);
+            if self.ants[i].position.is_none() || colony >= self.colonies.len() {
+                continue;
+            }
+            let count = &mut deployed[colony][ant_type.index()];
+            if *count >= self.type_cap(colony, ant_type)
+                || total[colony] >= self.config.nest_capacity
+            {
+                self.ants[i].position = None;
+            } else {
+                *count += 1;
+                total[colony] += 1;
             }
         }
     }
 
-    fn choose_action(&self, x: u32, y: u32, mode: AntsMode) -> (Action, f32) {
-        let mut rng = rand::thread_rng();
-        let map = match mode {
-            AntsMode::FINDING => &self.pheromones_food,
-            AntsMode::RETURNING => &self.pheromones_nest,
-        };
+    // Places du nid pour ce type : celles de la configuration si elles sont fixées, sinon
+    // celles de la case du nid
+    fn type_cap(&self, colony: usize, ant_type: AntsType) -> u32 {
+        if let Some(caps) = self.config.nest_type_caps {
+            return caps[ant_type.index()];
+        }
+        self.grid
+            .colony_nest_capacity(colony, ant_type)
+            .unwrap_or(u32::MAX)
+    }
 
-        if rng.gen::<f32>() < self.rl_params.epsilon {
-            let action = match rng.gen_range(0..4) {
-                0 => Action::Up,
-                1 => Action::Down,
-                2 => Action::Left,
-                _ => Action::Right,
-            };
-            (action, map.get_q(x, y, action))
-        } else {
-            let best = map.get_best_action(x, y, &self.grid);
-            (best, map.get_q(x, y, best))
+    fn count_active(&self, colony: usize, ant_type: AntsType) -> usize {
+        self.ants
+            .iter()
+            .filter(|a| a.colony == colony && a.position.is_some() && a.ant_type == ant_type)
+            .count()
+    }
+
+    fn notify_spawn(&mut self, idx: usize) {
+        self.metrics.record_spawn();
+        let ant = &self.ants[idx];
+        if let Some(position) = ant.position {
+            debug!(
+                kind = "spawn",
+                tick = self.current_tick_index + 1,
+                ant_id = ant.id,
+                x = position.0,
+                y = position.1,
+                "{} sort du nid {}",
+                ant.ant_type.label(),
+                Colony::label(ant.colony)
+            );
+            self.observers.spawn(SpawnEvent {
+                ant_index: idx,
+                ant_id: ant.id,
+                colony: ant.colony,
+                ant_type: ant.ant_type,
+                position,
+            });
         }
     }
 
@@ -355,53 +2601,135 @@ impl AntsGameManager {
         ant: &mut Ant,
         nx: u32,
         ny: u32,
-        phero_food: &mut PheromoneMap,
-        phero_nest: &mut PheromoneMap,
+        table: &mut QTable,
         config: &SimulationConfig,
-    ) {
+    ) -> Option<Interaction> {
         // Calculer le boost immédiat basé sur la récompense configurée pour trouver de la nourriture
         let immediate_boost = config.reward_food * 0.5;
+        // État de la fourmi sur la case, relevé avant que la nourriture n'y soit prise
+        let state = StateFeatures::observe(grid, (nx, ny), ant.mode, ant.scope, table.layout());
 
         match ant.mode {
             AntsMode::FINDING => {
                 if grid.has_food(nx, ny) {
                     if let Some(tile) = grid.get_mut_tile((nx, ny)) {
-                        if let TileType::FoodSource { amount } = &mut tile.tile_type {
-                            if *amount > 0 {
-                                *amount = amount.saturating_sub(1);
-                                ant.current_charge = ant.maximal_charge;
+                        if let TileType::FoodSource { amount, value } = &mut tile.tile_type {
+                            // La fourmi prend ce que sa charge peut encore contenir, au plus ce
+                            // qui reste, et repart au nid pleine ou la source épuisée
+                            let taken = (*amount)
+                                .min(ant.maximal_charge.saturating_sub(ant.current_charge));
+                            if taken > 0 {
+                                let fraction = load_fraction(ant, taken, config);
+                                *amount -= taken;
+                                ant.current_charge += taken;
+                                ant.carried_value = *value;
                                 ant.mode = AntsMode::RETURNING;
-                                phero_food.queue_update(nx, ny, Action::Stay, immediate_boost);
+                                if !config.freeze {
+                                    table.layer_mut(state).queue_update(
+                                        nx,
+                                        ny,
+                                        Action::Stay,
+                                        immediate_boost * *value as f32 * fraction,
+                                    );
+                                }
+                                return Some(Interaction::PickedUp(taken));
                             }
                         }
                     }
                 }
             }
             AntsMode::RETURNING => {
-                if grid.is_nest(nx, ny) {
-                    grid.add_food_to_nest(ant.current_charge);
+                // Une fourmi ne dépose sa charge que dans un nid de sa propre colonie, celui
+                // qu'elle vient d'atteindre
+                if let Some(nest) = grid
+                    .get_mut_tile((nx, ny))
+                    .filter(|tile| tile.nest_colony() == Some(ant.colony))
+                {
+                    // Le nid stocke la valeur de la charge, pas son nombre d'unités
+                    let delivered = ant.carried_food();
+                    nest.add_food_to_nest(delivered);
                     ant.current_charge = 0;
                     ant.mode = AntsMode::FINDING;
-                    phero_nest.queue_update(nx, ny, Action::Stay, immediate_boost);
+                    if !config.freeze {
+                        table.layer_mut(state).queue_update(
+                            nx,
+                            ny,
+                            Action::Stay,
+                            immediate_boost * ant.carried_value as f32,
+                        );
+                    }
+                    return Some(Interaction::Delivered(delivered));
                 }
             }
         }
+        None
     }
 
-    pub fn calculate_reward(&self, is_lethal: bool, mode: AntsMode, nx: u32, ny: u32) -> f32 {
-        if is_lethal {
-            return self.config.reward_death;
-        }
+    /// Récompense que recevrait `ant` en entrant dans la case `target`, selon le modèle actif
+    pub fn calculate_reward(&self, ant: &Ant, target: (u32, u32)) -> f32 {
+        self.reward_model()
+            .reward(ant, target, &self.grid, &self.config)
+    }
 
-        match mode {
-            AntsMode::FINDING if self.grid.has_food(nx, ny) => self.config.reward_food,
-            AntsMode::RETURNING if self.grid.is_nest(nx, ny) => self.config.reward_nest,
-            _ => self.config.reward_default,
+    pub fn is_game_finished(&self) -> bool {
+        self.end_reason().is_some()
+    }
+
+    /// Condition qui a terminé la partie, None tant qu'elle continue
+    pub fn end_reason(&self) -> Option<EndReason> {
+        // Un nid qui atteint l'objectif de nourriture l'emporte sur les autres conditions
+        if let Some(target) = self.config.target_food {
+            // Les réserves d'une colonie à plusieurs nids s'additionnent
+            let reached = (0..self.colonies.len())
+                .find(|&colony| self.grid.colony_stored_food(colony) >= target);
+            if let Some(colony) = reached {
+                return Some(EndReason::TargetFood { colony });
+            }
         }
+        // Une source qui repousse n'est jamais épuisée pour de bon
+        if !self.grid.is_food_remaining()
+            && !self.grid.has_regrowing_food(self.config.food_regrowth())
+        {
+            return Some(EndReason::FoodExhausted);
+        }
+        // Une colonie affamée qui n'a plus personne dehors ne peut plus se refaire
+        let starved = (0..self.colonies.len()).find(|&colony| {
+            self.in_famine(colony)
+                && !self
+                    .ants
+                    .iter()
+                    .any(|ant| ant.colony == colony && ant.position.is_some())
+        });
+        if let Some(colony) = starved {
+            return Some(EndReason::Famine { colony });
+        }
+        if self.ants.iter().all(|ant| ant.position.is_none()) {
+            return Some(EndReason::AllAntsDead);
+        }
+        None
     }
 
-    pub fn is_game_finished(&self) -> bool {
-        // La simulation s'arrête quand toutes les fourmis sont mortes ou qu'aucune nourriture n'est disponible sur la carte
-        self.ants.iter().all(|ant| ant.position.is_none()) || !self.grid.is_food_remaining()
+    /// Colonie gagnante une fois la partie terminée, s'il y a plusieurs colonies : la première
+    /// à atteindre l'objectif de nourriture, sinon le meilleur score s'il n'y a pas d'égalité
+    pub fn winner(&self) -> Option<usize> {
+        if self.colonies.len() < 2 {
+            return None;
+        }
+        let starved = match self.end_reason()? {
+            EndReason::TargetFood { colony } => return Some(colony),
+            EndReason::Famine { colony } => Some(colony),
+            EndReason::FoodExhausted
+            | EndReason::AllAntsDead
+            | EndReason::TickLimit
+            | EndReason::Timeout => None,
+        };
+        // Une colonie morte de faim a perdu : seules les autres se disputent la victoire
+        let contenders = || self.colonies.iter().filter(|c| Some(c.id) != starved);
+        let best = contenders().map(|c| c.score).max()?;
+        let mut leaders = contenders().filter(|c| c.score == best);
+        match (leaders.next(), leaders.next()) {
+            (Some(leader), None) => Some(leader.id),
+            _ => None,
+        }
     }
 }
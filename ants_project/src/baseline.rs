@@ -0,0 +1,93 @@
+//! Politiques de référence sans apprentissage
+//!
+//! La marche aléatoire donne une borne basse : chaque pas est tiré uniformément parmi les
+//! cases praticables voisines. L'A* omniscient donne une borne haute : la fourmi connaît la
+//! carte et suit le plus court chemin vers la nourriture la plus proche (ou vers son nid),
+//! en évitant les zones mortelles et en tenant compte de la boue et des tunnels.
+
+use crate::exploration::{target, MOVING_ACTIONS};
+use crate::grid::Grid;
+use crate::pheromone::Action;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Déplacement tiré uniformément parmi les cases praticables autour de (x, y)
+pub fn random_walk(grid: &Grid, x: u32, y: u32) -> Vec<(Action, f32)> {
+    let moves: Vec<Action> = MOVING_ACTIONS
+        .iter()
+        .copied()
+        .filter(|&action| {
+            let (nx, ny) = target(x, y, action);
+            grid.is_walkable(nx, ny)
+        })
+        .collect();
+    // Fourmi enfermée par des murs : elle reste sur place
+    if moves.is_empty() {
+        return vec![(Action::Stay, 1.0)];
+    }
+    let p = 1.0 / moves.len() as f32;
+    moves.into_iter().map(|action| (action, p)).collect()
+}
+
+/// Premier pas du plus court chemin de (x, y) vers une case satisfaisant `is_goal`.
+/// L'heuristique est la distance de Manhattan à la plus proche des `goals` ; `None` si aucun
+/// but n'est atteignable sans traverser de zone mortelle.
+pub fn astar_step(
+    grid: &Grid,
+    (x, y): (u32, u32),
+    goals: &[(u32, u32)],
+    is_goal: impl Fn(u32, u32) -> bool,
+) -> Option<Action> {
+    if goals.is_empty() {
+        return None;
+    }
+    let heuristic = |cx: u32, cy: u32| {
+        goals
+            .iter()
+            .map(|&(gx, gy)| cx.abs_diff(gx) + cy.abs_diff(gy))
+            .min()
+            .unwrap_or(0)
+    };
+
+    // Coût connu de chaque case et premier pas qui y mène depuis le départ
+    let mut best: HashMap<(u32, u32), (u32, Action)> = HashMap::new();
+    let mut open = BinaryHeap::new();
+    best.insert((x, y), (0, Action::Stay));
+    open.push(Reverse((heuristic(x, y), 0, (x, y))));
+
+    while let Some(Reverse((_, cost, (cx, cy)))) = open.pop() {
+        if best.get(&(cx, cy)).is_some_and(|&(known, _)| known < cost) {
+            continue;
+        }
+        let first = best[&(cx, cy)].1;
+        if (cx, cy) != (x, y) && is_goal(cx, cy) {
+            return Some(first);
+        }
+        for action in MOVING_ACTIONS {
+            let (nx, ny) = target(cx, cy, action);
+            if !grid.is_walkable(nx, ny) {
+                continue;
+            }
+            // Un tunnel mène directement à sa sortie, la boue coûte plus cher à traverser
+            let (dx, dy) = grid.destination(nx, ny);
+            if grid.is_lethal(dx, dy) {
+                continue;
+            }
+            let next_cost = cost + grid.slow_factor(nx, ny);
+            if best
+                .get(&(dx, dy))
+                .is_some_and(|&(known, _)| known <= next_cost)
+            {
+                continue;
+            }
+            let step = if (cx, cy) == (x, y) { action } else { first };
+            best.insert((dx, dy), (next_cost, step));
+            open.push(Reverse((
+                next_cost + heuristic(dx, dy),
+                next_cost,
+                (dx, dy),
+            )));
+        }
+    }
+    None
+}
@@ -0,0 +1,197 @@
+//! Mode batch : N parties indépendantes, puis statistiques sur leurs résultats
+//!
+//! `--runs N` joue la même configuration N fois en parallèle (rayon). Avec une graine fixée,
+//! la partie `r` utilise la graine `seed + r`, ce qui rend le batch entier reproductible ;
+//! sans graine, chaque partie tire la sienne.
+//!
+//! `--threads N` limite le nombre de parties jouées en même temps, et `--run-timeout SECS`
+//! abandonne une partie trop longue : elle reste dans les résultats, terminée par
+//! `EndReason::Timeout` (DNF). Les parties parallèles ne gardent jamais de timeline de
+//! rembobinage, dont la mémoire serait multipliée par le nombre de parties.
+
+use crate::ants_game_manager::{AntsGameManager, EndReason};
+use crate::cli_args::SimulationConfig;
+use crate::optimizer::SampleStats;
+use crate::runner::{SimulationRunner, SimulationSummary};
+use rayon::prelude::*;
+use std::fs;
+use std::time::Duration;
+
+/// Résultat d'une partie du batch
+#[derive(Clone, Debug)]
+pub struct RunRecord {
+    pub run: u32,
+    pub seed: u64,
+    pub summary: SimulationSummary,
+}
+
+/// Résultats de toutes les parties, dans l'ordre des numéros de partie
+#[derive(Clone, Debug)]
+pub struct BatchReport {
+    pub runs: Vec<RunRecord>,
+}
+
+impl BatchReport {
+    pub fn ticks(&self) -> SampleStats {
+        self.stats(|summary| summary.ticks as f64)
+    }
+
+    pub fn food_delivered(&self) -> SampleStats {
+        self.stats(|summary| summary.food_delivered as f64)
+    }
+
+    pub fn deaths(&self) -> SampleStats {
+        self.stats(|summary| summary.deaths as f64)
+    }
+
+    /// Parties abandonnées par `--run-timeout`
+    pub fn timeouts(&self) -> usize {
+        self.runs
+            .iter()
+            .filter(|run| run.summary.end_reason == Some(EndReason::Timeout))
+            .count()
+    }
+
+    /// Longueur moyenne des allers-retours, sur les parties qui en ont terminé au moins un
+    pub fn mean_trip_length(&self) -> Option<SampleStats> {
+        self.optional_stats(|summary| summary.activity.mean_trip_length())
+    }
+
+    /// Cases parcourues en trop par aller-retour, sur les parties qui en ont terminé un
+    pub fn trip_regret(&self) -> Option<SampleStats> {
+        self.optional_stats(|summary| summary.activity.trip_regret())
+    }
+
+    fn stats(&self, value: impl Fn(&SimulationSummary) -> f64) -> SampleStats {
+        let samples: Vec<f64> = self.runs.iter().map(|run| value(&run.summary)).collect();
+        SampleStats::from_samples(&samples)
+    }
+
+    // None si aucune partie n'a de valeur
+    fn optional_stats(
+        &self,
+        value: impl Fn(&SimulationSummary) -> Option<f64>,
+    ) -> Option<SampleStats> {
+        let samples: Vec<f64> = self
+            .runs
+            .iter()
+            .filter_map(|run| value(&run.summary))
+            .collect();
+        (!samples.is_empty()).then(|| SampleStats::from_samples(&samples))
+    }
+
+    /// Tableau des statistiques, une ligne par mesure
+    pub fn print(&self) {
+        println!("Résultats sur {} parties :", self.runs.len());
+        println!(
+            "{:<12} {:>12} {:>12} {:>12} {:>12} {:>12}",
+            "", "moyenne", "médiane", "écart-type", "min", "max"
+        );
+        let trips = [
+            ("trajet", self.mean_trip_length()),
+            ("regret", self.trip_regret()),
+        ];
+        for (label, stats) in [
+            ("ticks", Some(self.ticks())),
+            ("nourriture", Some(self.food_delivered())),
+            ("morts", Some(self.deaths())),
+        ]
+        .into_iter()
+        .chain(trips)
+        {
+            // Aucune partie n'a terminé d'aller-retour
+            let Some(stats) = stats else {
+                continue;
+            };
+            println!(
+                "{:<12} {:>12.1} {:>12.1} {:>12.1} {:>12.0} {:>12.0}",
+                label, stats.mean, stats.median, stats.stddev, stats.min, stats.max
+            );
+        }
+        let timeouts = self.timeouts();
+        if timeouts > 0 {
+            println!(
+                "{} partie(s) abandonnée(s) par --run-timeout (DNF), comptée(s) là où elles se sont arrêtées",
+                timeouts
+            );
+        }
+    }
+
+    /// Une ligne par partie : graine, durée, nourriture livrée, morts, condition d'arrêt et
+    /// allers-retours (vides si la partie n'en a terminé aucun)
+    pub fn save_csv(&self, path: &str) -> Result<(), String> {
+        let mut content = String::from(
+            "run,seed,ticks,food_delivered,deaths,end_reason,mean_trip_length,optimal_round_trip,trip_regret\n",
+        );
+        let optional = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+        for record in &self.runs {
+            let summary = &record.summary;
+            content.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{}\n",
+                record.run,
+                record.seed,
+                summary.ticks,
+                summary.food_delivered,
+                summary.deaths,
+                summary
+                    .end_reason
+                    .map(|reason| reason.label())
+                    .unwrap_or_default(),
+                optional(summary.activity.mean_trip_length()),
+                optional(summary.optimal_round_trip.map(f64::from)),
+                optional(summary.activity.trip_regret())
+            ));
+        }
+        fs::write(path, content).map_err(|e| format!("Impossible d'écrire {}: {}", path, e))
+    }
+}
+
+/// Limite les calculs parallèles (rayon) à `threads` threads, 0 gardant un thread par coeur.
+/// À appeler avant le premier calcul parallèle.
+pub fn limit_threads(threads: usize) -> Result<(), String> {
+    if threads == 0 {
+        return Ok(());
+    }
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .map_err(|e| format!("Impossible de limiter les threads: {}", e))
+}
+
+/// Configuration d'une partie parallèle : sa graine, sans fichier de métriques ni timeline
+pub(crate) fn member_config(config: &SimulationConfig, member: u32) -> SimulationConfig {
+    SimulationConfig {
+        seed: config.seed.map(|seed| seed.wrapping_add(member as u64)),
+        output_file: None,
+        snapshot_interval: 0,
+        ..config.clone()
+    }
+}
+
+/// Partie prête à être jouée, abandonnée après `run_timeout` secondes de calcul
+pub(crate) fn member_runner(manager: AntsGameManager) -> SimulationRunner {
+    let timeout = manager.config().run_timeout;
+    let runner = SimulationRunner::new(manager);
+    match timeout {
+        0 => runner,
+        secs => runner.with_time_limit(Duration::from_secs(secs)),
+    }
+}
+
+/// Joue `runs` parties de `config` en parallèle. `build` crée la partie à partir de la
+/// configuration de chaque répétition (graine propre, sans fichier de métriques).
+pub fn run_batch<F>(config: &SimulationConfig, runs: u32, build: F) -> Result<BatchReport, String>
+where
+    F: Fn(SimulationConfig) -> Result<AntsGameManager, String> + Sync,
+{
+    let runs = (0..runs)
+        .into_par_iter()
+        .map(|run| {
+            let manager = build(member_config(config, run))?;
+            let seed = manager.seed();
+            let summary = member_runner(manager).run_to_completion();
+            Ok(RunRecord { run, seed, summary })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    Ok(BatchReport { runs })
+}
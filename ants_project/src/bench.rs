@@ -0,0 +1,98 @@
+//! Benchmark du simulateur : débit en ticks par seconde sur une charge fixe
+//!
+//! `--bench` joue toujours la même partie (carte aléatoire de graine fixe, colonie fixe,
+//! timeline et sorties désactivées) pour obtenir un chiffre comparable d'une version du
+//! code à l'autre. Les réglages d'apprentissage de la ligne de commande s'appliquent, ce
+//! qui permet de mesurer le coût d'une option (`--algorithm`, `--per-type-tables`...).
+
+use crate::ants_game_manager::AntsGameManager;
+use crate::cli_args::SimulationConfig;
+use crate::profiling::PhaseTimings;
+use std::time::{Duration, Instant};
+
+// Charge de travail du benchmark, fixe pour que les mesures restent comparables
+const BENCH_SEED: u64 = 42;
+pub(crate) const BENCH_GRID_SIZE: u32 = 100;
+const BENCH_EXPLORERS: u32 = 20;
+const BENCH_PICKERS: u32 = 40;
+const BENCH_FIGHTERS: u32 = 10;
+
+/// Mesures d'un benchmark
+#[derive(Clone, Debug)]
+pub struct BenchReport {
+    pub ticks: u64,
+    pub elapsed: Duration,
+    pub timings: PhaseTimings,
+}
+
+impl BenchReport {
+    pub fn ticks_per_second(&self) -> f64 {
+        self.ticks as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Débit global puis temps de chaque phase, par tick et en part du total
+    pub fn print(&self) {
+        println!(
+            "Benchmark : {} ticks, grille {}x{}, {} fourmis, graine {}",
+            self.ticks,
+            BENCH_GRID_SIZE,
+            BENCH_GRID_SIZE,
+            BENCH_EXPLORERS + BENCH_PICKERS + BENCH_FIGHTERS,
+            BENCH_SEED
+        );
+        println!(
+            "Débit : {:.1} ticks/s ({:.3} s)",
+            self.ticks_per_second(),
+            self.elapsed.as_secs_f64()
+        );
+        let total = self.timings.total().as_secs_f64().max(f64::EPSILON);
+        let ticks = self.ticks.max(1) as f64;
+        println!("{:<20} {:>12} {:>8}", "phase", "µs/tick", "part");
+        for (label, duration) in self.timings.phases() {
+            let seconds = duration.as_secs_f64();
+            println!(
+                "{:<20} {:>12.2} {:>7.1}%",
+                label,
+                seconds * 1e6 / ticks,
+                seconds / total * 100.0
+            );
+        }
+    }
+}
+
+/// Joue `config.bench_ticks` ticks de la charge fixe avec les réglages d'apprentissage de `config`
+pub fn run_bench(config: &SimulationConfig) -> Result<BenchReport, String> {
+    let mut manager = AntsGameManager::from_config(workload(config, BENCH_GRID_SIZE))
+        .map_err(|e| format!("Erreur de création de la partie: {}", e))?;
+    manager.enable_profiling();
+
+    let start = Instant::now();
+    for _ in 0..config.bench_ticks {
+        manager.game_step();
+    }
+    let elapsed = start.elapsed();
+
+    Ok(BenchReport {
+        ticks: config.bench_ticks,
+        elapsed,
+        timings: manager.phase_timings().cloned().unwrap_or_default(),
+    })
+}
+
+// Charge fixe sur une grille `size` x `size`, jouée `config.bench_ticks` ticks
+pub(crate) fn workload(config: &SimulationConfig, size: u32) -> SimulationConfig {
+    SimulationConfig {
+        grid_width: size,
+        grid_height: size,
+        num_explorers: BENCH_EXPLORERS,
+        num_pickers: BENCH_PICKERS,
+        num_fighters: BENCH_FIGHTERS,
+        seed: Some(BENCH_SEED),
+        max_ticks: config.bench_ticks,
+        target_food: None,
+        snapshot_interval: 0,
+        output_file: None,
+        load_brain: None,
+        ..config.clone()
+    }
+}
@@ -0,0 +1,150 @@
+//! Couche fixe du plateau de la GUI : fond, murs, zones mortelles et boue
+//!
+//! Ces cases ne changent presque jamais (mode Dieu, événements) alors que les redessiner une
+//! à une à chaque image coûte cher sur les grandes cartes. Elles sont peintes une fois dans
+//! une texture, régénérée seulement quand la couleur d'une case change (changement de
+//! thème compris).
+
+use crate::coord::{Coord, GridShape};
+use crate::grid::Grid;
+use crate::theme;
+use crate::tile::TileType;
+use eframe::egui;
+
+// Côté maximal de la texture, en pixels
+const MAX_TEXTURE_SIDE: u32 = 2048;
+// Pixels par case au plus : au-delà l'image n'est pas plus nette
+const MAX_CELL_PIXELS: u32 = 8;
+// Pixels par case à partir desquels un liseré du fond sépare les cases voisines
+const BORDER_MIN_CELL_PIXELS: u32 = 4;
+
+// Couleur d'une case dans la texture ; le fond pour les cases dessinées par-dessus à chaque image
+fn tile_color(tile_type: &TileType) -> egui::Color32 {
+    let palette = theme::palette();
+    match tile_type {
+        TileType::Wall => palette.wall,
+        TileType::DeathZone => palette.death_zone,
+        TileType::Mud { .. } => palette.mud,
+        _ => palette.background,
+    }
+}
+
+/// Texture des cases fixes d'un plateau, avec les couleurs qui ont servi à la peindre
+#[derive(Default)]
+pub struct StaticLayer {
+    size: (u32, u32),
+    colors: Vec<egui::Color32>,
+    texture: Option<egui::TextureHandle>,
+    // Tunnels (entrée, sortie) relevés à la dernière image
+    tunnels: Vec<((u32, u32), (u32, u32))>,
+}
+
+impl StaticLayer {
+    /// Dessine la couche fixe de `grid`, dont la case (0, 0) commence en `origin`, puis les
+    /// tunnels ; la texture n'est régénérée que si une case a changé depuis l'image précédente
+    pub fn draw(
+        &mut self,
+        ctx: &egui::Context,
+        painter: &egui::Painter,
+        grid: &Grid,
+        origin: egui::Pos2,
+        size: f32,
+    ) {
+        let (width, height) = (grid.get_width(), grid.get_height());
+        if width == 0 || height == 0 {
+            return;
+        }
+        let mut stale = self.texture.is_none() || self.size != (width, height);
+        self.size = (width, height);
+        self.colors
+            .resize(grid.shape().len(), egui::Color32::TRANSPARENT);
+        self.tunnels.clear();
+        for y in 0..height {
+            for x in 0..width {
+                let Some(tile) = grid.get_tile((x, y)) else {
+                    continue;
+                };
+                if let TileType::Tunnel { exit } = tile.tile_type {
+                    self.tunnels.push(((x, y), exit));
+                }
+                let color = tile_color(&tile.tile_type);
+                let cached = &mut self.colors[grid.shape().index_of(Coord::new(x, y)).as_usize()];
+                if *cached != color {
+                    *cached = color;
+                    stale = true;
+                }
+            }
+        }
+        if stale {
+            self.upload(ctx);
+        }
+
+        if let Some(texture) = &self.texture {
+            painter.image(
+                texture.id(),
+                egui::Rect::from_min_size(
+                    origin,
+                    egui::vec2(width as f32 * size, height as f32 * size),
+                ),
+                egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                egui::Color32::WHITE,
+            );
+        }
+
+        // Entrée violette reliée à sa sortie par une flèche discrète
+        let center = |(x, y): (u32, u32)| {
+            origin + egui::vec2((x as f32 + 0.5) * size, (y as f32 + 0.5) * size)
+        };
+        let tunnel = theme::palette().tunnel;
+        for &(entrance, exit) in &self.tunnels {
+            painter.circle_filled(center(entrance), size * 0.35, tunnel);
+            painter.arrow(
+                center(entrance),
+                center(exit) - center(entrance),
+                egui::Stroke::new(1.0, tunnel.gamma_multiply(0.5)),
+            );
+        }
+    }
+
+    /// Texture de la dernière image, reprise en petit par la minicarte
+    pub fn texture_id(&self) -> Option<egui::TextureId> {
+        self.texture.as_ref().map(|texture| texture.id())
+    }
+
+    // Peint les couleurs des cases dans la texture, agrandie au plus de MAX_CELL_PIXELS
+    // pixels par case sans dépasser MAX_TEXTURE_SIDE
+    fn upload(&mut self, ctx: &egui::Context) {
+        let (width, height) = self.size;
+        let cell_pixels = (MAX_TEXTURE_SIDE / width.max(height)).clamp(1, MAX_CELL_PIXELS);
+        let border = usize::from(cell_pixels >= BORDER_MIN_CELL_PIXELS);
+        let cell_pixels = cell_pixels as usize;
+        let image_width = width as usize * cell_pixels;
+        let image_height = height as usize * cell_pixels;
+
+        let background = theme::palette().background;
+        let mut pixels = vec![background; image_width * image_height];
+        for (cell, &color) in self.colors.iter().enumerate() {
+            if color == background {
+                continue;
+            }
+            let Coord { x, y } = GridShape::new(width, height).coord_at(cell);
+            let (x, y) = (x as usize * cell_pixels, y as usize * cell_pixels);
+            for row in y + border..y + cell_pixels - border {
+                let start = row * image_width + x;
+                pixels[start + border..start + cell_pixels - border].fill(color);
+            }
+        }
+
+        let image = egui::ColorImage::new([image_width, image_height], pixels);
+        match &mut self.texture {
+            Some(texture) => texture.set(image, egui::TextureOptions::NEAREST),
+            None => {
+                self.texture = Some(ctx.load_texture(
+                    "board_static_layer",
+                    image,
+                    egui::TextureOptions::NEAREST,
+                ))
+            }
+        }
+    }
+}
@@ -0,0 +1,149 @@
+//! Zoom et déplacement des plateaux dessinés par la GUI (partie et éditeur de carte)
+//!
+//! Sans zoom la grille tient entière dans sa zone ; sur les grandes cartes les cases ne
+//! font alors que quelques pixels, la molette permet de s'en rapprocher et le glisser de
+//! se déplacer.
+
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+// Taille maximale d'une case à l'écran, quel que soit le zoom
+const MAX_ZOOMED_CELL: f32 = 120.0;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Camera {
+    // Facteur appliqué à la taille qui fait tenir toute la grille (1.0 = vue ajustée)
+    zoom: f32,
+    // Décalage de la grille par rapport à sa position centrée, en pixels
+    pan: egui::Vec2,
+    // Case à amener au centre de la zone à la prochaine image
+    #[serde(skip)]
+    focus: Option<(u32, u32)>,
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Camera {
+            zoom: 1.0,
+            pan: egui::Vec2::ZERO,
+            focus: None,
+        }
+    }
+}
+
+/// Position de la grille à l'écran pour l'image en cours
+#[derive(Clone, Copy, Debug)]
+pub struct BoardLayout {
+    /// Coin haut gauche de la case (0, 0)
+    pub origin: egui::Pos2,
+    pub cell_size: f32,
+    width: u32,
+    height: u32,
+}
+
+impl BoardLayout {
+    /// Case située sous un point de l'écran
+    pub fn cell_at(&self, pos: egui::Pos2) -> Option<(u32, u32)> {
+        let rel = (pos - self.origin) / self.cell_size;
+        if rel.x < 0.0 || rel.y < 0.0 {
+            return None;
+        }
+        let (x, y) = (rel.x as u32, rel.y as u32);
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    /// Partie de la grille visible dans `rect`, en cases (cadre de la minicarte)
+    pub fn visible_cells(&self, rect: egui::Rect) -> egui::Rect {
+        let grid = egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(self.width as f32, self.height as f32),
+        );
+        egui::Rect::from_min_max(
+            ((rect.min - self.origin) / self.cell_size).to_pos2(),
+            ((rect.max - self.origin) / self.cell_size).to_pos2(),
+        )
+        .intersect(grid)
+    }
+}
+
+impl Camera {
+    /// Revient à la vue ajustée : toute la grille, centrée
+    pub fn reset(&mut self) {
+        *self = Camera::default();
+    }
+
+    /// Centre la vue sur une case à la prochaine image (suivi d'une fourmi)
+    pub fn focus(&mut self, cell: (u32, u32)) {
+        self.focus = Some(cell);
+    }
+
+    pub fn is_reset(&self) -> bool {
+        self.zoom == 1.0 && self.pan == egui::Vec2::ZERO
+    }
+
+    /// Applique la molette (zoom autour du pointeur) et le glisser (déplacement) reçus
+    /// par la zone `response`, puis place une grille `width` x `height` dedans.
+    /// Le bouton droit et celui du milieu déplacent toujours la vue, le bouton principal
+    /// seulement si `pan_with_primary` (l'éditeur s'en sert pour peindre).
+    pub fn update(
+        &mut self,
+        ui: &egui::Ui,
+        response: &egui::Response,
+        (width, height): (u32, u32),
+        max_cell: f32,
+        pan_with_primary: bool,
+    ) -> BoardLayout {
+        let rect = response.rect;
+        let grid = egui::vec2(width as f32, height as f32);
+        let fit = (rect.width() / grid.x)
+            .min(rect.height() / grid.y)
+            .min(max_cell);
+        let max_zoom = (MAX_ZOOMED_CELL / fit).max(1.0);
+
+        if response.hovered() {
+            let (scroll, pinch) = ui.input(|i| (i.smooth_scroll_delta.y, i.zoom_delta()));
+            let factor = pinch * (scroll / 200.0).exp();
+            if factor != 1.0 {
+                let old_cell = fit * self.zoom;
+                self.zoom = (self.zoom * factor).clamp(1.0, max_zoom);
+                let new_cell = fit * self.zoom;
+
+                // Garder sous le pointeur la même case avant et après le zoom
+                if let Some(pointer) = response.hover_pos() {
+                    let old_origin = self.origin(rect, grid, old_cell);
+                    let cells = (pointer - old_origin) / old_cell;
+                    let new_origin = pointer - cells * new_cell;
+                    self.pan += new_origin - self.origin(rect, grid, new_cell);
+                }
+            }
+        }
+
+        if response.dragged_by(egui::PointerButton::Secondary)
+            || response.dragged_by(egui::PointerButton::Middle)
+            || (pan_with_primary && response.dragged_by(egui::PointerButton::Primary))
+        {
+            self.pan += response.drag_delta();
+        }
+
+        let cell_size = fit * self.zoom;
+        if let Some((x, y)) = self.focus.take() {
+            let cell_center = egui::vec2(x as f32 + 0.5, y as f32 + 0.5) * cell_size;
+            self.pan = grid * cell_size / 2.0 - cell_center;
+        }
+
+        // La grille ne peut pas sortir entièrement de la zone
+        let limit = (grid * cell_size + rect.size()) / 2.0;
+        self.pan = self.pan.clamp(-limit, limit);
+
+        BoardLayout {
+            origin: self.origin(rect, grid, cell_size),
+            cell_size,
+            width,
+            height,
+        }
+    }
+
+    fn origin(&self, rect: egui::Rect, grid: egui::Vec2, cell_size: f32) -> egui::Pos2 {
+        rect.center() - grid * cell_size / 2.0 + self.pan
+    }
+}
@@ -1,41 +1,231 @@
 /// Module de gestion des paramètres en ligne de commande
+use crate::error::AntsError;
+use crate::event_log::LogLevel;
+use crate::exploration::ExplorationPolicy;
+use crate::grid::MapGenParams;
+use crate::hazard::HazardScript;
+use crate::meta_controller::ControllerMode;
+use crate::observation::ObservationFormat;
+use crate::pheromone::{QBounds, QMerge, Resample};
+use crate::policy::PolicyKind;
+use crate::q_backend::QBackendKind;
+use crate::q_learning_math::{DecayConfig, DecaySchedule, LearningAlgorithm};
+use crate::runner::TickRate;
+use crate::scenario::{Scenario, ScenarioEvent};
+use crate::spawn_policy::SpawnPolicyKind;
+use crate::stuck::StuckRecovery;
+use crate::tile::FoodRegrowth;
+use serde::{Deserialize, Serialize};
 use std::env;
+use std::fmt;
+use std::fs;
 use std::process;
+use std::str::FromStr;
 
+// Noms acceptés par --epsilon-schedule et --alpha-schedule
+const SCHEDULE_NAMES: &str = "constant, linear, exponential, step";
 
-#[derive(Clone, Debug)]
+/// Multiplicateur maximal du mode turbo de la GUI
+pub const MAX_TICKS_PER_FRAME: u32 = 1000;
+
+/// Ticks joués par `--bench` sans `--bench-ticks`
+pub const DEFAULT_BENCH_TICKS: u64 = 2000;
+
+// Les champs absents d'un fichier --config gardent leur valeur par défaut
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(default)]
 pub struct SimulationConfig {
     // --- Paramètres de grille ---
     pub grid_width: u32,
     pub grid_height: u32,
+    pub map_gen: MapGenParams, // Proportions et graine des cartes aléatoires
 
     // --- Paramètres de fourmis ---
     pub num_explorers: u32,
     pub num_fighters: u32,
     pub num_pickers: u32,
 
+    // --- Caractéristiques par type de fourmi ---
+    pub explorer_capacity_load: u32, // Charge maximale transportée
+    pub explorer_speed: u32,         // Ticks d'attente entre deux mouvements
+    pub explorer_scope: u32,         // Rayon de vision en cases (0 = aveugle)
+    pub picker_capacity_load: u32,
+    pub picker_speed: u32,
+    pub picker_scope: u32,
+    pub fighter_capacity_load: u32,
+    pub fighter_speed: u32,
+    pub fighter_scope: u32,
+    pub sensing: bool, // La vision masque les dangers et attire vers les cibles visibles
+
     // --- Paramètres Q-Learning ---
-    pub alpha: f32,   // Facteur d'apprentissage (0.0-1.0)
-    pub gamma: f32,   // Facteur d'actualisation (0.0-1.0)
-    pub epsilon: f32, // Facteur ε-greedy (0.0-1.0)
+    pub alpha: f32,                            // Facteur d'apprentissage (0.0-1.0)
+    pub gamma: f32,                            // Facteur d'actualisation (0.0-1.0)
+    pub lambda: f32,                           // Q(λ) : persistance des traces (0 = un pas)
+    pub n_step: u32,                           // Pas cumulés dans la cible de Bellman (1 = un pas)
+    pub epsilon: f32,                          // Facteur ε-greedy (0.0-1.0)
+    pub learning_algorithm: LearningAlgorithm, // Q-Learning, SARSA, Expected SARSA ou Double Q
+    pub q_backend: QBackendKind,               // Q-table par case ou approximation linéaire
+    pub linear_tile: u32,                      // Côté des pavés de position de l'approximation
+    pub exploration_policy: ExplorationPolicy, // Epsilon-greedy, Softmax ou UCB
+    pub softmax_temperature: f32, // Température du Softmax (plus haute = plus d'exploration)
+    pub ucb_c: f32,               // Poids du bonus d'exploration UCB
+    pub count_bonus: f32, // Bonus de récompense c/√N(s,a) des actions peu jouées (0 = désactivé)
+    pub unmasked_exploration: bool, // L'exploration epsilon-greedy peut viser les murs (ancien tirage)
+    pub masked_max_q: bool,         // Le max de la cible de Bellman ignore les pas vers les murs
+    pub freeze: bool, // Politique figée : actions gloutonnes, ni mise à jour ni évaporation
+    pub epsilon_decay: DecayConfig, // Décroissance d'epsilon au fil des ticks
+    pub alpha_decay: DecayConfig, // Décroissance d'alpha au fil des ticks
+    pub meta_controller: ControllerMode, // Correction d'epsilon et d'alpha en cours de partie selon les livraisons
+    pub controller_period: u32,          // Ticks entre deux décisions du méta-contrôleur
+    pub controller_step: f32, // Facteur appliqué à epsilon et alpha quand les livraisons stagnent (stall)
+    pub controller_max_epsilon: f32, // Plafond de l'epsilon relevé par le méta-contrôleur
+    pub per_type_q_tables: bool, // Cartes de phéromones propres à chaque type de fourmi
+    pub near_food_state: bool, // L'état Q distingue la nourriture en vue (deux couches de plus)
+    pub experience_buffer: usize, // Transitions mémorisées pour l'experience replay (0 = désactivé)
+    pub experience_batch: usize, // Transitions rejouées à chaque tick
+    pub q_initial: f32,       // Valeur Q de départ (> 0 : exploration optimiste)
+    pub q_clamp: f32,         // Amplitude maximale des valeurs Q (0 = sans borne)
+    pub q_display_scale: f32, // Valeur Q affichée à pleine intensité (GUI, TUI, images)
+
+    // --- Politique des fourmis ---
+    pub policy: PolicyKind, // Q-Learning, ACO classique ou référence sans apprentissage
+    pub aco_alpha: f32,     // ACO : exposant de la phéromone
+    pub aco_beta: f32,      // ACO : exposant de l'heuristique
+    pub aco_deposit: f32,   // ACO : phéromone déposée à chaque pas
 
     // --- Paramètres de simulation ---
-    pub max_ticks: u64,        // Limite de temps (1 milliard par défaut)
-    pub simulation_speed: u64, // Vitesse en ms (pour GUI)
+    pub max_ticks: u64,               // Limite de temps (1 milliard par défaut)
+    pub target_food: Option<u32>, // Nourriture stockée au nid qui termine la partie (None = aucun objectif)
+    pub seed: Option<u64>,        // Graine aléatoire (None = différente à chaque lancement)
+    pub tick_rate: TickRate,      // Rythme des ticks en GUI (et en CLI avec realtime)
+    pub realtime: bool,           // Mode CLI : jouer au rythme de tick_rate plutôt qu'au plus vite
+    pub ticks_per_frame: u32,     // Ticks calculés à chaque pas de la GUI (mode turbo)
+    pub snapshot_interval: u32,   // Ticks entre deux snapshots de la timeline (0 = désactivée)
+    pub max_snapshots: Option<usize>, // Snapshots conservés au maximum (None = illimité)
+    pub keyframe_interval: u32, // Snapshots entre deux états complets de la timeline (les autres ne gardent que les changements)
 
     // --- Paramètres de récompenses ---
-    pub reward_food: f32,    // Nourriture trouvée
-    pub reward_nest: f32,    // Retour au nid
-    pub reward_death: f32,   // Zone mortelle
-    pub reward_default: f32, // Case normale
+    pub reward_food: f32,          // Nourriture trouvée
+    pub reward_nest: f32,          // Retour au nid
+    pub reward_death: f32,         // Zone mortelle
+    pub reward_default: f32,       // Case normale
+    pub reward_load_scaling: bool, // reward_food proportionnelle à la part de la charge remplie
+    pub reward_per_unit: f32,      // Récompense de chaque unité livrée au nid
+
+    // --- Formule des meilleurs scores ---
+    pub score_food: f32,  // Points par unité de nourriture livrée
+    pub score_death: f32, // Points retirés par fourmi morte
+    pub score_tick: f32,  // Points retirés par tick joué
 
     // --- Paramètres de nid ---
-    pub nest_capacity: u32,         // Capacité d'accueil du nid
+    pub nest_capacity: u32, // Fourmis déployées max par colonie, tous types confondus
+    pub nest_type_caps: Option<[u32; 3]>, // Places par type, remplace celles des nids (rang de `AntsType::index`)
+    pub max_ants_per_cell: Option<u32>,   // Fourmis max par case (None = illimité)
+    pub spawn_cooldown: u32,              // Ticks d'attente d'une fourmi qui sort du nid
+    pub spawn_rate_per_tick: u32,         // Fourmis déployées max par nid et par tick
+    pub min_explorers_active: u32,        // Explorateurs prioritaires à la sortie du nid
+    pub min_pickers_active: u32,          // Récolteuses prioritaires à la sortie du nid
+    pub spawn_policy: SpawnPolicyKind,    // Stratégie de choix du type de fourmi déployée
+    pub spawn_trail_threshold: f32,       // Intensité de piste déclenchant l'envoi de récolteuses
+    pub spawn_stall_ticks: u32,           // Ticks sans livraison déclenchant l'envoi d'explorateurs
+    pub spawn_ratio: [u32; 3],            // Proportions visées par la stratégie proportionnelle
+    pub spawn_burst_ticks: u32,           // Durée de la vague d'explorateurs de départ
+    pub food_upkeep_per_ant: f32, // Nourriture consommée par fourmi active et par tick (0 = désactivé)
+    pub upkeep_interval: u32,     // Ticks entre deux prélèvements de l'entretien du nid
+    pub starvation_ticks: u32,    // Ticks de famine avant qu'une fourmi meure de faim
+    pub ant_energy: f32,          // Énergie d'une fourmi rassasiée (0 = désactivé)
+    pub energy_drain: f32,        // Énergie dépensée à chaque déplacement
+    pub energy_per_food: f32,     // Énergie rendue par unité de nourriture mangée au nid
+    pub hunger_threshold: f32,    // Part de l'énergie sous laquelle la fourmi rentre manger
+
+    // --- Fourmis bloquées ---
+    pub stuck_ticks: u32, // Ticks sans s'éloigner de sa case de référence avant d'être bloquée (0 = désactivé)
+    pub stuck_radius: u32, // Distance à la case de référence en deçà de laquelle la fourmi n'a pas bougé
+    pub stuck_recovery: StuckRecovery, // Reprise d'une fourmi bloquée : exploration, marche au hasard ou rappel
+    pub recovery_ticks: u32,           // Durée de l'exploration renforcée ou de la marche au hasard
+    pub stuck_epsilon: f32,            // Epsilon minimal d'une fourmi qui explore pour se dégager
+
+    // --- Naissances (le nid transforme sa nourriture en fourmis) ---
+    pub birth_cost: u32,     // Nourriture consommée par naissance (0 = désactivé)
+    pub birth_interval: u32, // Ticks entre deux naissances
+    pub birth_ratio: [u32; 3], // Proportions des naissances par type (rang de `AntsType::index`)
+    pub lifespan: [u32; 3], // Durée de vie en ticks par type, 0 = illimitée (rang de `AntsType::index`)
+
+    // --- Repousse de la nourriture (sources sans réglage propre) ---
+    pub food_regrowth_amount: u32, // Unités ajoutées à chaque repousse (0 = désactivé)
+    pub food_regrowth_interval: u32, // Ticks entre deux repousses
+    pub food_regrowth_cap: u32,    // Quantité maximale atteinte par repousse
+
+    // --- Cadavres ---
+    pub corpse_food: u32, // Unités laissées par une fourmi morte, en plus de sa charge (0 = désactivé)
+    pub corpse_decay: u32, // Ticks pour qu'un cadavre perde une unité
+
+    // --- Zones mortelles scriptées (fichier --config uniquement) ---
+    pub hazards: Vec<HazardScript>,
+    // Chronologie d'événements (scénario), triée par tick
+    pub events: Vec<ScenarioEvent>,
+
+    // --- Prédateurs ---
+    pub max_predators: u32, // Prédateurs simultanés maximum (0 = désactivé)
+    pub predator_spawn_interval: u32, // Ticks entre deux apparitions
+    pub predator_speed: u32, // Ticks d'attente entre deux mouvements
+    pub fighter_win_chance: f32, // Probabilité qu'un coup de combattante porte
+    pub fighter_health: u32, // Points de vie d'une combattante, rendus au nid
+    pub fighter_attack: u32, // Dégâts d'un coup de combattante
+    pub predator_health: u32, // Points de vie d'un prédateur
+    pub predator_attack: u32, // Dégâts infligés par tick à une combattante au contact
+    pub predator_raid: u32, // Nourriture volée par mouvement dans un nid sans combattante (0 = pas de pillage)
+    pub fighter_patrol_range: u32, // Rayon de patrouille autour du nid et de détection des prédateurs
     pub pheromone_evaporation: f32, // Taux d'évaporation (0.01 = 1%)
+    pub fear_weight: f32, // Poids de la peur retranchée des valeurs Q au choix de l'action (0 = désactivée)
+    pub fear_deposit: f32, // Peur déposée là où une fourmi meurt (zone mortelle, prédateur, combat)
+    pub fear_evaporation: f32, // Taux d'évaporation de la carte de peur
+
+    // --- Cycle jour/nuit (la nuit occupe la seconde moitié de chaque période) ---
+    pub day_night_period: u32, // Ticks d'un jour et d'une nuit (0 = désactivé)
+    pub night_evaporation_factor: f32, // Facteur appliqué à l'évaporation la nuit
+    pub night_epsilon_boost: f32, // Ajouté à epsilon la nuit (plafonné à 1)
+    pub night_slowdown: u32,   // Facteur d'attente entre deux mouvements la nuit
 
     // --- Mode d'exécution ---
-    pub use_gui: bool,               // Utiliser l'interface graphique
+    pub use_gui: bool,                    // Utiliser l'interface graphique
+    pub use_tui: bool,                    // Afficher la simulation dans le terminal (mode CLI)
+    pub watch_interval: u32, // Ticks entre deux rendus ASCII en mode CLI (0 = désactivé)
+    pub log_level: LogLevel, // Verbosité du journal d'événements
     pub output_file: Option<String>, // Fichier de résultats
+    pub summary_file: Option<String>, // Bilan JSON de la partie CLI (`-` pour la sortie standard)
+    pub report_file: Option<String>, // Rapport HTML autonome écrit en fin de partie CLI
+    pub report_gif: bool,    // Joindre au rapport l'animation de la partie
+    pub leaderboard_file: Option<String>, // Classement JSON des meilleurs scores (inscription des parties CLI)
+    pub metrics_port: Option<u16>,        // Port HTTP des métriques Prometheus en mode CLI
+    pub serve_ws: Option<u16>,            // Port WebSocket de diffusion de l'état en mode CLI
+    pub ws_interval: u32,                 // Ticks entre deux messages WebSocket
+    pub observation: ObservationFormat, // Observation encodée renvoyée par AntsEnv (aucune, traits, carte)
+    pub render_frames: Option<String>,  // Dossier des images PNG (ou fichier .gif) rendues en CLI
+    pub frame_interval: u32,            // Ticks entre deux images rendues
+    pub runs: u32,                      // Parties indépendantes jouées en mode CLI (batch si > 1)
+    pub runs_output: Option<String>,    // Résultats du batch, une ligne par partie (CSV)
+    pub ensemble: u32, // Parties jouées en parallèle qui mettent leurs Q-tables en commun (CLI, 1 = aucune)
+    pub ensemble_sync: u32, // Ticks entre deux mises en commun des Q-tables de l'ensemble
+    pub ensemble_merge: QMerge, // Mise en commun des Q-tables de l'ensemble : moyenne ou vote
+    pub run_timeout: u64, // Temps de calcul maximal d'une partie du batch ou de l'ensemble, en secondes (0 = aucun)
+    pub threads: usize,   // Threads des calculs parallèles (0 = un par coeur)
+    pub bench_ticks: u64, // Ticks joués par le benchmark sur une charge fixe (0 = pas de benchmark)
+    pub map_file: Option<String>, // Carte sauvegardée depuis l'éditeur (JSON)
+    pub save_brain: Option<String>, // Fichier où sauvegarder les Q-tables en fin de simulation
+    pub dump_qtable: Option<String>, // Préfixe des Q-tables exportées en CSV/SVG en fin de simulation
+    pub load_brain: Option<String>,  // Q-tables entraînées à recharger au démarrage
+    pub brain_resample: Resample, // Adaptation d'un cerveau appris sur une grille d'une autre taille
+    pub record_file: Option<String>, // Replay de la partie écrit en fin de simulation (CLI)
+    pub replay_file: Option<String>, // Replay à rejouer au lieu d'une nouvelle partie
+    pub checkpoint_every: u32,    // Ticks entre deux checkpoints en mode CLI (0 = aucun)
+    pub checkpoint_file: String,  // Fichier réécrit à chaque checkpoint
+    #[serde(skip)]
+    pub resume_from: Option<String>, // Checkpoint dont la partie reprend
+    #[serde(skip)]
+    pub scenario_file: Option<String>, // Scénario dont la carte remplace la carte aléatoire
+    #[serde(skip)]
+    pub dump_config: Option<String>, // Fichier où écrire la configuration effective (TOML)
 }
 
 impl Default for SimulationConfig {
@@ -43,44 +233,451 @@ impl Default for SimulationConfig {
         SimulationConfig {
             grid_width: 20,
             grid_height: 20,
+            map_gen: MapGenParams::default(),
 
             num_explorers: 2,
             num_fighters: 1,
             num_pickers: 3,
 
+            explorer_capacity_load: 10,
+            explorer_speed: 5,
+            explorer_scope: 1,
+            picker_capacity_load: 100,
+            picker_speed: 10,
+            picker_scope: 0,
+            fighter_capacity_load: 10,
+            fighter_speed: 5,
+            fighter_scope: 1,
+            sensing: true,
+
             alpha: 0.1,
             gamma: 0.99,
+            lambda: 0.0,
+            n_step: 1,
             epsilon: 0.05,
+            learning_algorithm: LearningAlgorithm::QLearning,
+            q_backend: QBackendKind::Tabular,
+            linear_tile: 4,
+            exploration_policy: ExplorationPolicy::EpsilonGreedy,
+            softmax_temperature: 50.0,
+            ucb_c: 100.0,
+            count_bonus: 0.0,
+            unmasked_exploration: false,
+            masked_max_q: false,
+            freeze: false,
+            epsilon_decay: DecayConfig::default(),
+            alpha_decay: DecayConfig::default(),
+            meta_controller: ControllerMode::Off,
+            controller_period: 500,
+            controller_step: 1.5,
+            controller_max_epsilon: 0.5,
+            per_type_q_tables: false,
+            near_food_state: false,
+            experience_buffer: 0,
+            experience_batch: 32,
+            q_initial: 0.0,
+            q_clamp: 0.0,
+            q_display_scale: 50.0,
+
+            policy: PolicyKind::QLearning,
+            aco_alpha: 1.0,
+            aco_beta: 2.0,
+            aco_deposit: 1.0,
 
             max_ticks: 1_000_000_000,
-            simulation_speed: 100,
+            target_food: None,
+            seed: None,
+            tick_rate: TickRate::default(),
+            realtime: false,
+            ticks_per_frame: 1,
+            snapshot_interval: 1,
+            max_snapshots: None,
+            keyframe_interval: 256,
 
             reward_food: 1000.0,
             reward_nest: 1000.0,
             reward_death: -100.0,
             reward_default: -1.0,
+            reward_load_scaling: false,
+            reward_per_unit: 0.0,
+
+            score_food: 1.0,
+            score_death: 2.0,
+            score_tick: 0.01,
 
             nest_capacity: 100,
+            nest_type_caps: None,
+            max_ants_per_cell: Some(10),
+            spawn_cooldown: 2,
+            spawn_rate_per_tick: 1,
+            min_explorers_active: 3,
+            min_pickers_active: 0,
+            spawn_policy: SpawnPolicyKind::Priority,
+            spawn_trail_threshold: 50.0,
+            spawn_stall_ticks: 200,
+            spawn_ratio: [1, 2, 1],
+            spawn_burst_ticks: 200,
+            food_upkeep_per_ant: 0.0,
+            upkeep_interval: 1,
+            starvation_ticks: 50,
+            ant_energy: 0.0,
+            energy_drain: 1.0,
+            energy_per_food: 50.0,
+            hunger_threshold: 0.3,
+
+            stuck_ticks: 0,
+            stuck_radius: 2,
+            stuck_recovery: StuckRecovery::Explore,
+            recovery_ticks: 20,
+            stuck_epsilon: 0.5,
+
+            birth_cost: 0,
+            birth_interval: 100,
+            birth_ratio: [1, 2, 1],
+            lifespan: [0, 0, 0],
+            food_regrowth_amount: 0,
+            food_regrowth_interval: 100,
+            food_regrowth_cap: 1000,
+
+            corpse_food: 0,
+            corpse_decay: 50,
+
+            hazards: Vec::new(),
+            events: Vec::new(),
+
+            max_predators: 0,
+            predator_spawn_interval: 200,
+            predator_speed: 3,
+            fighter_win_chance: 0.7,
+            fighter_health: 12,
+            fighter_attack: 4,
+            predator_health: 10,
+            predator_attack: 3,
+            predator_raid: 5,
+            fighter_patrol_range: 6,
             pheromone_evaporation: 0.01,
+            fear_weight: 0.0,
+            fear_deposit: 100.0,
+            fear_evaporation: 0.02,
+
+            day_night_period: 0,
+            night_evaporation_factor: 0.25,
+            night_epsilon_boost: 0.2,
+            night_slowdown: 2,
 
             use_gui: true,
+            use_tui: false,
+            watch_interval: 0,
+            log_level: LogLevel::Debug,
             output_file: None,
+            summary_file: None,
+            report_file: None,
+            report_gif: false,
+            leaderboard_file: None,
+            metrics_port: None,
+            serve_ws: None,
+            ws_interval: 10,
+            observation: ObservationFormat::None,
+            render_frames: None,
+            frame_interval: 10,
+            runs: 1,
+            runs_output: None,
+            ensemble: 1,
+            ensemble_sync: 100,
+            ensemble_merge: QMerge::default(),
+            run_timeout: 0,
+            threads: 0,
+            bench_ticks: 0,
+            map_file: None,
+            save_brain: None,
+            dump_qtable: None,
+            load_brain: None,
+            brain_resample: Resample::default(),
+            record_file: None,
+            replay_file: None,
+            checkpoint_every: 0,
+            checkpoint_file: "checkpoint.bin".to_string(),
+            resume_from: None,
+            scenario_file: None,
+            dump_config: None,
         }
     }
 }
 
-impl SimulationConfig {
+/// Erreur de lecture des options de la ligne de commande ou du fichier --config
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConfigError {
+    /// Option inconnue pour cette sous-commande
+    UnknownArgument(String),
+    /// Sous-commande inconnue
+    UnknownCommand(String),
+    /// Option donnée sans sa valeur
+    MissingValue(String),
+    /// Valeur illisible pour l'option `flag`
+    InvalidValue {
+        flag: String,
+        value: String,
+        expected: &'static str,
+    },
+    /// `ants replay` sans fichier à rejouer
+    MissingReplayFile,
+    /// Fichier --config illisible ou invalide
+    File(String),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::UnknownArgument(arg) => write!(f, "Argument inconnu: {}", arg),
+            ConfigError::UnknownCommand(command) => write!(f, "Commande inconnue: {}", command),
+            ConfigError::MissingValue(flag) => write!(f, "{} attend une valeur", flag),
+            ConfigError::InvalidValue {
+                flag,
+                value,
+                expected,
+            } => write!(
+                f,
+                "Valeur invalide pour {}: {} (attendu: {})",
+                flag, value, expected
+            ),
+            ConfigError::MissingReplayFile => write!(f, "ants replay attend le fichier à rejouer"),
+            ConfigError::File(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+// Valeur de l'option args[*i] ; avance `i` sur la valeur consommée
+pub(crate) fn next_arg<'a>(args: &'a [String], i: &mut usize) -> Result<&'a str, ConfigError> {
+    let flag = &args[*i];
+    *i += 1;
+    match args.get(*i) {
+        // Une option à la place de la valeur signifie que celle-ci a été oubliée
+        Some(value) if !value.starts_with("--") => Ok(value),
+        _ => Err(ConfigError::MissingValue(flag.clone())),
+    }
+}
+
+pub(crate) fn next_parsed<T: FromStr>(
+    args: &[String],
+    i: &mut usize,
+    expected: &'static str,
+) -> Result<T, ConfigError> {
+    let flag = args[*i].clone();
+    let value = next_arg(args, i)?;
+    value.parse().map_err(|_| ConfigError::InvalidValue {
+        flag,
+        value: value.to_string(),
+        expected,
+    })
+}
+
+pub(crate) fn next_int<T: FromStr>(args: &[String], i: &mut usize) -> Result<T, ConfigError> {
+    next_parsed(args, i, "un entier positif")
+}
+
+fn next_float(args: &[String], i: &mut usize) -> Result<f32, ConfigError> {
+    next_parsed(args, i, "un nombre")
+}
+
+// Limite numérique, ou `unlimited` pour aucune limite
+fn next_limit<T: FromStr>(args: &[String], i: &mut usize) -> Result<Option<T>, ConfigError> {
+    if args.get(*i + 1).is_some_and(|value| value == "unlimited") {
+        *i += 1;
+        return Ok(None);
+    }
+    next_parsed(args, i, "un entier positif ou unlimited").map(Some)
+}
+
+// Valeur choisie parmi des noms (`expected` les liste dans le message d'erreur)
+// Une valeur par type, "explorateurs:récolteuses:combattantes", par exemple 1:2:1
+fn parse_per_type(value: &str) -> Option<[u32; 3]> {
+    let parts: Vec<u32> = value
+        .split(':')
+        .map(|part| part.trim().parse().ok())
+        .collect::<Option<_>>()?;
+    parts.try_into().ok()
+}
+
+fn next_named<T>(
+    args: &[String],
+    i: &mut usize,
+    from_name: fn(&str) -> Option<T>,
+    expected: &'static str,
+) -> Result<T, ConfigError> {
+    let flag = args[*i].clone();
+    let value = next_arg(args, i)?;
+    from_name(value).ok_or_else(|| ConfigError::InvalidValue {
+        flag,
+        value: value.to_string(),
+        expected,
+    })
+}
+
+/// Sous-commande de l'exécutable `ants`, chacune avec ses propres options
+pub enum Command {
+    /// Nouvelle simulation (sous-commande par défaut)
+    Simulate(SimulationConfig),
+    /// Relecture d'une partie enregistrée, désignée par `replay_file`
+    Replay(SimulationConfig),
+    /// Éditeur de carte ouvert sur `map_file` (carte vierge si le fichier n'existe pas)
+    Edit(SimulationConfig),
+    /// Options transmises telles quelles au méta-optimiseur
+    Optimize(Vec<String>),
+    /// Options transmises telles quelles au tournoi entre deux réglages
+    Tournament(Vec<String>),
+    /// Scénarios et options transmis tels quels à l'entraînement par curriculum
+    Curriculum(Vec<String>),
+    /// Replays à rejouer pour vérifier leur état final
+    VerifyReplay(Vec<String>),
+    /// Options transmises telles quelles à l'évaluation d'un cerveau sur plusieurs cartes
+    Evaluate(Vec<String>),
+    /// Options transmises telles quelles au serveur REST
+    Serve(Vec<String>),
+    /// Options transmises telles quelles à la comparaison des stockages de Q-tables
+    #[cfg(feature = "perf-compare")]
+    PerfCompare(Vec<String>),
+}
+
+impl Command {
     /// Parse les arguments de la ligne de commande
-    pub fn from_args() -> Self {
+    pub fn from_args() -> Result<Self, ConfigError> {
+        let args: Vec<String> = env::args().skip(1).collect();
+        Self::parse(&args)
+    }
+
+    /// Parse les arguments qui suivent le nom de l'exécutable
+    pub fn parse(args: &[String]) -> Result<Self, ConfigError> {
+        match args.first().map(String::as_str) {
+            // Sans sous-commande, les options sont celles de `simulate`
+            None => Ok(Command::Simulate(SimulationConfig::default())),
+            Some(arg) if arg.starts_with('-') => {
+                SimulationConfig::parse_args(args).map(Command::Simulate)
+            }
+            Some("simulate") => SimulationConfig::parse_args(&args[1..]).map(Command::Simulate),
+            Some("replay") => SimulationConfig::parse_replay_args(&args[1..]).map(Command::Replay),
+            Some("edit") => SimulationConfig::parse_edit_args(&args[1..]).map(Command::Edit),
+            Some("optimize") => Ok(Command::Optimize(args[1..].to_vec())),
+            Some("tournament") => Ok(Command::Tournament(args[1..].to_vec())),
+            Some("curriculum") => Ok(Command::Curriculum(args[1..].to_vec())),
+            Some("verify-replay") => Ok(Command::VerifyReplay(args[1..].to_vec())),
+            Some("evaluate") => Ok(Command::Evaluate(args[1..].to_vec())),
+            Some("serve") => Ok(Command::Serve(args[1..].to_vec())),
+            #[cfg(feature = "perf-compare")]
+            Some("perf-compare") => Ok(Command::PerfCompare(args[1..].to_vec())),
+            Some("help") => {
+                SimulationConfig::print_help();
+                process::exit(0);
+            }
+            Some(other) => Err(ConfigError::UnknownCommand(other.to_string())),
+        }
+    }
+}
+
+impl SimulationConfig {
+    /// Repousse appliquée aux sources de nourriture sans réglage propre (None = désactivée)
+    pub fn food_regrowth(&self) -> Option<FoodRegrowth> {
+        (self.food_regrowth_amount > 0).then_some(FoodRegrowth {
+            amount: self.food_regrowth_amount,
+            interval: self.food_regrowth_interval,
+            cap: self.food_regrowth_cap,
+        })
+    }
+
+    /// Nuit au tick `tick` : seconde moitié de chaque période du cycle jour/nuit, jamais
+    /// quand le cycle est désactivé
+    pub fn is_night(&self, tick: usize) -> bool {
+        let period = self.day_night_period as usize;
+        period > 0 && tick % period >= period / 2
+    }
+
+    /// Taux d'évaporation en vigueur au tick `tick`
+    pub fn evaporation_at(&self, tick: usize) -> f32 {
+        if self.is_night(tick) {
+            self.pheromone_evaporation * self.night_evaporation_factor
+        } else {
+            self.pheromone_evaporation
+        }
+    }
+
+    /// Valeur de départ et borne des valeurs Q de toutes les cartes
+    pub fn q_bounds(&self) -> QBounds {
+        QBounds {
+            initial: self.q_initial,
+            clamp: self.q_clamp,
+        }
+    }
+
+    /// Les fourmis mettent à jour leurs Q-tables : politique apprenante et cerveau non figé
+    pub fn updates_q_tables(&self) -> bool {
+        self.policy.learns() && !self.freeze
+    }
+
+    /// Les colonies tiennent une carte de peur (phéromone d'alarme)
+    pub fn uses_fear(&self) -> bool {
+        self.fear_weight > 0.0
+    }
+
+    /// Epsilon en vigueur au tick `tick` pour une colonie dont l'epsilon de jour est `epsilon`
+    pub fn epsilon_at(&self, epsilon: f32, tick: usize) -> f32 {
+        if self.is_night(tick) {
+            (epsilon + self.night_epsilon_boost).min(1.0)
+        } else {
+            epsilon
+        }
+    }
+
+    /// Facteur d'attente entre deux mouvements au tick `tick` (1 le jour)
+    pub fn slowdown_at(&self, tick: usize) -> u32 {
+        if self.is_night(tick) {
+            self.night_slowdown.max(1)
+        } else {
+            1
+        }
+    }
+
+    /// Parse les options de `ants simulate` (arguments qui suivent la sous-commande)
+    pub fn parse_args(args: &[String]) -> Result<Self, ConfigError> {
         let mut config = SimulationConfig::default();
-        let args: Vec<String> = env::args().collect();
 
-        if args.len() < 2 {
+        if args.is_empty() {
             // Pas d'argument = GUI par défaut
-            return config;
+            return Ok(config);
         }
 
-        let mut i = 1;
+        // Sans réglage explicite, la timeline est désactivée en CLI (inutile et coûteuse en mémoire)
+        let mut snapshot_interval_set = false;
+        // De même, le journal ne montre que les erreurs en CLI et tout le détail en GUI
+        let mut log_level_set = false;
+
+        // Le fichier --config sert de base, quelle que soit sa position : les options
+        // de la ligne de commande l'emportent toujours sur ses valeurs
+        let scenario = args.iter().position(|arg| arg == "--scenario");
+        if let Some(mut pos) = args.iter().position(|arg| arg == "--config") {
+            if scenario.is_some() {
+                return Err(ConfigError::File(
+                    "--config et --scenario ne se combinent pas : le scénario porte sa configuration"
+                        .to_string(),
+                ));
+            }
+            let path = next_arg(args, &mut pos)?;
+            let (loaded, keys) = Self::load_with_keys(path).map_err(ConfigError::File)?;
+            config = loaded;
+            snapshot_interval_set = keys.iter().any(|key| key == "snapshot_interval");
+            log_level_set = keys.iter().any(|key| key == "log_level");
+        }
+        // Un scénario sert de base de la même façon, carte et chronologie comprises
+        if let Some(mut pos) = scenario {
+            let path = next_arg(args, &mut pos)?;
+            config = Scenario::load(path).map_err(ConfigError::File)?.config;
+            config.scenario_file = Some(path.to_string());
+            snapshot_interval_set =
+                config.snapshot_interval != SimulationConfig::default().snapshot_interval;
+            log_level_set = config.log_level != SimulationConfig::default().log_level;
+        }
+
+        let mut i = 0;
         while i < args.len() {
             let arg = &args[i];
 
@@ -88,121 +685,771 @@ impl SimulationConfig {
                 // --- Mode ---
                 "--gui" => config.use_gui = true,
                 "--cli" => config.use_gui = false,
-
-                // --- Grille ---
-                "--width" => {
-                    i += 1;
-                    if i < args.len() {
-                        config.grid_width = args[i].parse().unwrap_or(20);
-                    }
+                "--tui" => {
+                    config.use_gui = false;
+                    config.use_tui = true;
                 }
-                "--height" => {
-                    i += 1;
-                    if i < args.len() {
-                        config.grid_height = args[i].parse().unwrap_or(20);
+                "--watch" => config.watch_interval = next_int(args, &mut i)?,
+                "--tick-rate" => {
+                    config.tick_rate = next_named(
+                        args,
+                        &mut i,
+                        TickRate::from_name,
+                        "unlimited, <N>tps ou <N>ms avec N > 0",
+                    )?
+                }
+                // Ancienne forme de --tick-rate <N>ms, où 0 voulait dire au plus vite
+                "--speed" => {
+                    config.tick_rate = match next_int(args, &mut i)? {
+                        0 => TickRate::Unlimited,
+                        ms => TickRate::MsPerTick(ms),
                     }
                 }
+                "--realtime" => config.realtime = true,
+                "--ticks-per-frame" => config.ticks_per_frame = next_int(args, &mut i)?,
+
+                // --- Grille ---
+                "--width" => config.grid_width = next_int(args, &mut i)?,
+                "--height" => config.grid_height = next_int(args, &mut i)?,
+                "--wall-density" => config.map_gen.wall_density = next_float(args, &mut i)?,
+                "--death-density" => config.map_gen.death_density = next_float(args, &mut i)?,
+                "--food-sources" => config.map_gen.food_sources = next_int(args, &mut i)?,
+                "--food-min" => config.map_gen.food_min = next_int(args, &mut i)?,
+                "--food-max" => config.map_gen.food_max = next_int(args, &mut i)?,
+                "--protein-share" => config.map_gen.protein_share = next_float(args, &mut i)?,
+                "--map-seed" => config.map_gen.seed = Some(next_int(args, &mut i)?),
 
                 // --- Fourmis ---
-                "--explorers" => {
-                    i += 1;
-                    if i < args.len() {
-                        config.num_explorers = args[i].parse().unwrap_or(2);
-                    }
+                "--explorers" => config.num_explorers = next_int(args, &mut i)?,
+                "--fighters" => config.num_fighters = next_int(args, &mut i)?,
+                "--pickers" => config.num_pickers = next_int(args, &mut i)?,
+
+                // --- Caractéristiques par type ---
+                "--explorer-capacity" => config.explorer_capacity_load = next_int(args, &mut i)?,
+                "--explorer-speed" => config.explorer_speed = next_int(args, &mut i)?,
+                "--explorer-scope" => config.explorer_scope = next_int(args, &mut i)?,
+                "--picker-capacity" => config.picker_capacity_load = next_int(args, &mut i)?,
+                "--picker-speed" => config.picker_speed = next_int(args, &mut i)?,
+                "--picker-scope" => config.picker_scope = next_int(args, &mut i)?,
+                "--fighter-capacity" => config.fighter_capacity_load = next_int(args, &mut i)?,
+                "--fighter-speed" => config.fighter_speed = next_int(args, &mut i)?,
+                "--fighter-scope" => config.fighter_scope = next_int(args, &mut i)?,
+                "--no-sensing" => config.sensing = false,
+
+                // --- Nid et encombrement ---
+                "--nest-capacity" => config.nest_capacity = next_int(args, &mut i)?,
+                "--nest-type-caps" => {
+                    config.nest_type_caps =
+                        Some(next_named(args, &mut i, parse_per_type, "E:P:F (entiers)")?)
                 }
-                "--fighters" => {
-                    i += 1;
-                    if i < args.len() {
-                        config.num_fighters = args[i].parse().unwrap_or(1);
-                    }
+                "--max-ants-per-cell" => config.max_ants_per_cell = next_limit(args, &mut i)?,
+                "--spawn-cooldown" => config.spawn_cooldown = next_int(args, &mut i)?,
+                "--spawn-rate" => config.spawn_rate_per_tick = next_int(args, &mut i)?,
+
+                "--min-explorers" => config.min_explorers_active = next_int(args, &mut i)?,
+                "--min-pickers" => config.min_pickers_active = next_int(args, &mut i)?,
+                "--spawn-policy" => {
+                    config.spawn_policy = next_named(
+                        args,
+                        &mut i,
+                        SpawnPolicyKind::from_name,
+                        "priority, proportional, burst, demand",
+                    )?
                 }
-                "--pickers" => {
-                    i += 1;
-                    if i < args.len() {
-                        config.num_pickers = args[i].parse().unwrap_or(3);
-                    }
+                "--spawn-trail-threshold" => {
+                    config.spawn_trail_threshold = next_float(args, &mut i)?
+                }
+                "--spawn-stall-ticks" => config.spawn_stall_ticks = next_int(args, &mut i)?,
+                "--spawn-ratio" => {
+                    config.spawn_ratio =
+                        next_named(args, &mut i, parse_per_type, "E:P:F (entiers)")?
+                }
+                "--spawn-burst-ticks" => config.spawn_burst_ticks = next_int(args, &mut i)?,
+
+                // --- Entretien de la colonie ---
+                "--food-upkeep" => config.food_upkeep_per_ant = next_float(args, &mut i)?,
+                "--upkeep-interval" => config.upkeep_interval = next_int(args, &mut i)?,
+                "--starvation-ticks" => config.starvation_ticks = next_int(args, &mut i)?,
+                "--ant-energy" => config.ant_energy = next_float(args, &mut i)?,
+                "--energy-drain" => config.energy_drain = next_float(args, &mut i)?,
+                "--energy-per-food" => config.energy_per_food = next_float(args, &mut i)?,
+                "--hunger-threshold" => config.hunger_threshold = next_float(args, &mut i)?,
+
+                // --- Fourmis bloquées ---
+                "--stuck-ticks" => config.stuck_ticks = next_int(args, &mut i)?,
+                "--stuck-radius" => config.stuck_radius = next_int(args, &mut i)?,
+                "--stuck-recovery" => {
+                    config.stuck_recovery = next_named(
+                        args,
+                        &mut i,
+                        StuckRecovery::from_name,
+                        "explore, random, recall",
+                    )?
+                }
+                "--recovery-ticks" => config.recovery_ticks = next_int(args, &mut i)?,
+                "--stuck-epsilon" => config.stuck_epsilon = next_float(args, &mut i)?,
+
+                // --- Naissances ---
+                "--birth-cost" => config.birth_cost = next_int(args, &mut i)?,
+                "--birth-interval" => config.birth_interval = next_int(args, &mut i)?,
+                "--birth-ratio" => {
+                    config.birth_ratio =
+                        next_named(args, &mut i, parse_per_type, "E:P:F (entiers)")?
+                }
+                "--lifespan" => {
+                    config.lifespan = next_named(args, &mut i, parse_per_type, "E:P:F (entiers)")?
+                }
+
+                // --- Repousse de la nourriture ---
+                "--food-regrowth" => config.food_regrowth_amount = next_int(args, &mut i)?,
+                "--regrowth-interval" => config.food_regrowth_interval = next_int(args, &mut i)?,
+                "--regrowth-cap" => config.food_regrowth_cap = next_int(args, &mut i)?,
+
+                // --- Cadavres ---
+                "--corpse-food" => config.corpse_food = next_int(args, &mut i)?,
+                "--corpse-decay" => config.corpse_decay = next_int(args, &mut i)?,
+
+                // --- Prédateurs ---
+                "--predators" => config.max_predators = next_int(args, &mut i)?,
+                "--predator-interval" => config.predator_spawn_interval = next_int(args, &mut i)?,
+                "--predator-speed" => config.predator_speed = next_int(args, &mut i)?,
+                "--fighter-win-chance" => config.fighter_win_chance = next_float(args, &mut i)?,
+                "--fighter-health" => config.fighter_health = next_int(args, &mut i)?,
+                "--fighter-attack" => config.fighter_attack = next_int(args, &mut i)?,
+                "--predator-health" => config.predator_health = next_int(args, &mut i)?,
+                "--predator-attack" => config.predator_attack = next_int(args, &mut i)?,
+                "--predator-raid" => config.predator_raid = next_int(args, &mut i)?,
+                "--patrol-range" => config.fighter_patrol_range = next_int(args, &mut i)?,
+
+                // --- Phéromones et récompenses ---
+                "--evaporation" => config.pheromone_evaporation = next_float(args, &mut i)?,
+                "--fear-weight" => config.fear_weight = next_float(args, &mut i)?,
+                "--fear-deposit" => config.fear_deposit = next_float(args, &mut i)?,
+                "--fear-evaporation" => config.fear_evaporation = next_float(args, &mut i)?,
+                "--day-night" => config.day_night_period = next_int(args, &mut i)?,
+                "--night-evaporation" => {
+                    config.night_evaporation_factor = next_float(args, &mut i)?
+                }
+                "--night-epsilon" => config.night_epsilon_boost = next_float(args, &mut i)?,
+                "--night-slowdown" => config.night_slowdown = next_int(args, &mut i)?,
+                "--reward-food" => config.reward_food = next_float(args, &mut i)?,
+                "--reward-nest" => config.reward_nest = next_float(args, &mut i)?,
+                "--reward-death" => config.reward_death = next_float(args, &mut i)?,
+                "--reward-default" => config.reward_default = next_float(args, &mut i)?,
+                "--reward-load-scaling" => config.reward_load_scaling = true,
+                "--reward-per-unit" => config.reward_per_unit = next_float(args, &mut i)?,
+
+                // --- Score ---
+                "--score-food" => config.score_food = next_float(args, &mut i)?,
+                "--score-death" => config.score_death = next_float(args, &mut i)?,
+                "--score-tick" => config.score_tick = next_float(args, &mut i)?,
+                "--leaderboard" => {
+                    config.leaderboard_file = Some(next_arg(args, &mut i)?.to_string())
                 }
 
                 // --- Q-Learning ---
-                "--alpha" => {
-                    i += 1;
-                    if i < args.len() {
-                        config.alpha = args[i].parse().unwrap_or(0.1);
+                "--alpha" => config.alpha = next_float(args, &mut i)?,
+                "--gamma" => config.gamma = next_float(args, &mut i)?,
+                "--lambda" => config.lambda = next_float(args, &mut i)?,
+                "--n-step" => config.n_step = next_int(args, &mut i)?,
+                "--epsilon" => config.epsilon = next_float(args, &mut i)?,
+                "--epsilon-decay" => {
+                    config.epsilon_decay.rate = next_float(args, &mut i)?;
+                    // Un taux seul implique une décroissance exponentielle
+                    if config.epsilon_decay.schedule == DecaySchedule::Constant {
+                        config.epsilon_decay.schedule = DecaySchedule::Exponential;
                     }
                 }
-                "--gamma" => {
-                    i += 1;
-                    if i < args.len() {
-                        config.gamma = args[i].parse().unwrap_or(0.9);
-                    }
+                "--epsilon-min" => config.epsilon_decay.min = next_float(args, &mut i)?,
+                "--epsilon-schedule" => {
+                    config.epsilon_decay.schedule =
+                        next_named(args, &mut i, DecaySchedule::from_name, SCHEDULE_NAMES)?
                 }
-                "--epsilon" => {
-                    i += 1;
-                    if i < args.len() {
-                        config.epsilon = args[i].parse().unwrap_or(0.05);
+                "--epsilon-step" => config.epsilon_decay.step_every = next_int(args, &mut i)?,
+                "--alpha-decay" => {
+                    config.alpha_decay.rate = next_float(args, &mut i)?;
+                    // Un taux seul implique une décroissance exponentielle
+                    if config.alpha_decay.schedule == DecaySchedule::Constant {
+                        config.alpha_decay.schedule = DecaySchedule::Exponential;
                     }
                 }
+                "--alpha-min" => config.alpha_decay.min = next_float(args, &mut i)?,
+                "--alpha-schedule" => {
+                    config.alpha_decay.schedule =
+                        next_named(args, &mut i, DecaySchedule::from_name, SCHEDULE_NAMES)?
+                }
+                "--alpha-step" => config.alpha_decay.step_every = next_int(args, &mut i)?,
+                "--meta-controller" => {
+                    config.meta_controller = next_named(
+                        args,
+                        &mut i,
+                        ControllerMode::from_name,
+                        "off, stall, bandit",
+                    )?
+                }
+                "--controller-period" => config.controller_period = next_int(args, &mut i)?,
+                "--controller-step" => config.controller_step = next_float(args, &mut i)?,
+                "--controller-max-epsilon" => {
+                    config.controller_max_epsilon = next_float(args, &mut i)?
+                }
+                "--exploration" => {
+                    config.exploration_policy = next_named(
+                        args,
+                        &mut i,
+                        ExplorationPolicy::from_name,
+                        "epsilon-greedy, softmax, ucb",
+                    )?
+                }
+                "--temperature" => config.softmax_temperature = next_float(args, &mut i)?,
+                "--ucb-c" => config.ucb_c = next_float(args, &mut i)?,
+                "--count-bonus" => config.count_bonus = next_float(args, &mut i)?,
+                "--unmasked-exploration" => config.unmasked_exploration = true,
+                "--masked-max-q" => config.masked_max_q = true,
+                "--freeze" => config.freeze = true,
+                "--per-type-tables" => config.per_type_q_tables = true,
+                "--near-food-state" => config.near_food_state = true,
+                "--experience-buffer" => config.experience_buffer = next_int(args, &mut i)?,
+                "--experience-batch" => config.experience_batch = next_int(args, &mut i)?,
+                "--q-init" => config.q_initial = next_float(args, &mut i)?,
+                "--q-clamp" => config.q_clamp = next_float(args, &mut i)?,
+                "--q-scale" => config.q_display_scale = next_float(args, &mut i)?,
+                "--policy" => {
+                    config.policy = next_named(
+                        args,
+                        &mut i,
+                        PolicyKind::from_name,
+                        "qlearning, aco, random, astar",
+                    )?
+                }
+                "--aco-alpha" => config.aco_alpha = next_float(args, &mut i)?,
+                "--aco-beta" => config.aco_beta = next_float(args, &mut i)?,
+                "--aco-deposit" => config.aco_deposit = next_float(args, &mut i)?,
+                "--algorithm" => {
+                    config.learning_algorithm = next_named(
+                        args,
+                        &mut i,
+                        LearningAlgorithm::from_name,
+                        "qlearning, sarsa, expected-sarsa, double-qlearning",
+                    )?
+                }
+                "--q-backend" => {
+                    config.q_backend =
+                        next_named(args, &mut i, QBackendKind::from_name, "tabular, linear")?
+                }
+                "--linear-tile" => config.linear_tile = next_int(args, &mut i)?,
 
                 // --- Limite ---
-                "--max-ticks" => {
-                    i += 1;
-                    if i < args.len() {
-                        config.max_ticks = args[i].parse().unwrap_or(1_000_000_000);
-                    }
+                "--max-ticks" => config.max_ticks = next_int(args, &mut i)?,
+                "--target-food" => config.target_food = Some(next_int(args, &mut i)?),
+
+                // Graine pour rejouer exactement la même simulation
+                "--seed" => config.seed = Some(next_int(args, &mut i)?),
+
+                // Politique de snapshots de la timeline
+                "--snapshot-interval" => {
+                    config.snapshot_interval = next_int(args, &mut i)?;
+                    snapshot_interval_set = true;
+                }
+                "--max-snapshots" => config.max_snapshots = next_limit(args, &mut i)?,
+                "--keyframe-interval" => config.keyframe_interval = next_int(args, &mut i)?,
+                "--log-level" => {
+                    config.log_level = next_named(
+                        args,
+                        &mut i,
+                        LogLevel::from_name,
+                        "error, warn, info, debug ou trace",
+                    )?;
+                    log_level_set = true;
                 }
 
                 // Configurer le fichier de sortie pour les résultats de simulation
-                "--output" => {
-                    i += 1;
-                    if i < args.len() {
-                        config.output_file = Some(args[i].clone());
+                "--output" => config.output_file = Some(next_arg(args, &mut i)?.to_string()),
+                "--summary" => config.summary_file = Some(next_arg(args, &mut i)?.to_string()),
+                "--report" => config.report_file = Some(next_arg(args, &mut i)?.to_string()),
+                "--report-gif" => config.report_gif = true,
+                "--metrics-port" => config.metrics_port = Some(next_int(args, &mut i)?),
+                "--serve-ws" => config.serve_ws = Some(next_int(args, &mut i)?),
+                "--ws-interval" => config.ws_interval = next_int(args, &mut i)?,
+                "--observation" => {
+                    config.observation = next_named(
+                        args,
+                        &mut i,
+                        ObservationFormat::from_name,
+                        "none, features, grid",
+                    )?
+                }
+                "--render-frames" => {
+                    config.render_frames = Some(next_arg(args, &mut i)?.to_string())
+                }
+                "--frame-interval" => config.frame_interval = next_int(args, &mut i)?,
+                "--runs" => config.runs = next_int(args, &mut i)?,
+                "--runs-output" => config.runs_output = Some(next_arg(args, &mut i)?.to_string()),
+                "--ensemble" => config.ensemble = next_int(args, &mut i)?,
+                "--ensemble-sync" => config.ensemble_sync = next_int(args, &mut i)?,
+                "--run-timeout" => config.run_timeout = next_int(args, &mut i)?,
+                "--threads" => config.threads = next_int(args, &mut i)?,
+                "--ensemble-merge" => {
+                    config.ensemble_merge =
+                        next_named(args, &mut i, QMerge::from_name, "mean ou vote")?
+                }
+                "--bench" => {
+                    config.use_gui = false;
+                    if config.bench_ticks == 0 {
+                        config.bench_ticks = DEFAULT_BENCH_TICKS;
                     }
                 }
+                "--bench-ticks" => {
+                    config.use_gui = false;
+                    config.bench_ticks = next_int(args, &mut i)?;
+                }
+
+                // Charger une carte sauvegardée depuis l'éditeur
+                "--map" => config.map_file = Some(next_arg(args, &mut i)?.to_string()),
+
+                // Persistance des Q-tables
+                "--save-brain" => config.save_brain = Some(next_arg(args, &mut i)?.to_string()),
+                "--load-brain" => config.load_brain = Some(next_arg(args, &mut i)?.to_string()),
+                "--brain-resample" => {
+                    config.brain_resample =
+                        next_named(args, &mut i, Resample::from_name, "scale, bilinear ou crop")?
+                }
+                "--dump-qtable" => config.dump_qtable = Some(next_arg(args, &mut i)?.to_string()),
+
+                // Enregistrement d'une partie
+                "--record" => config.record_file = Some(next_arg(args, &mut i)?.to_string()),
+
+                // Checkpoints d'une longue partie et reprise
+                "--checkpoint-every" => config.checkpoint_every = next_int(args, &mut i)?,
+                "--checkpoint-file" => config.checkpoint_file = next_arg(args, &mut i)?.to_string(),
+                "--resume-from" => config.resume_from = Some(next_arg(args, &mut i)?.to_string()),
+
+                // Fichier de configuration, déjà chargé avant la boucle
+                "--config" | "--scenario" => {
+                    next_arg(args, &mut i)?;
+                }
+                "--dump-config" => config.dump_config = Some(next_arg(args, &mut i)?.to_string()),
 
                 "--help" => {
                     Self::print_help();
                     process::exit(0);
                 }
 
-                _ => {
-                    eprintln!("Argument inconnu: {}", arg);
-                }
+                _ => return Err(ConfigError::UnknownArgument(arg.clone())),
             }
 
             i += 1;
         }
 
-        config
+        if !config.use_gui && !snapshot_interval_set {
+            config.snapshot_interval = 0;
+        }
+        if !config.use_gui && !log_level_set {
+            config.log_level = LogLevel::Warn;
+        }
+
+        Ok(config)
+    }
+
+    /// Charge une configuration TOML ; les champs absents gardent leur valeur par défaut
+    pub fn load(path: &str) -> Result<Self, String> {
+        Self::load_with_keys(path).map(|(config, _)| config)
+    }
+
+    // Configuration et noms des champs présents dans le fichier
+    fn load_with_keys(path: &str) -> Result<(Self, Vec<String>), String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Impossible de lire {}: {}", path, e))?;
+        let table: toml::Table = content
+            .parse()
+            .map_err(|e| format!("Configuration invalide {}: {}", path, e))?;
+        let keys = table.keys().cloned().collect();
+        let config = table
+            .try_into()
+            .map_err(|e| format!("Configuration invalide {}: {}", path, e))?;
+        Ok((config, keys))
+    }
+
+    /// Écrit la configuration complète au format TOML, rechargeable avec --config
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let content = toml::to_string_pretty(self)
+            .map_err(|e| format!("Impossible de sérialiser la configuration: {}", e))?;
+        fs::write(path, content).map_err(|e| format!("Impossible d'écrire {}: {}", path, e))
+    }
+
+    /// Parse les options de `ants replay <FICHIER>` : seuls le mode d'affichage et la durée
+    /// s'appliquent, les autres paramètres sont ceux de la partie enregistrée
+    pub fn parse_replay_args(args: &[String]) -> Result<Self, ConfigError> {
+        let mut options = Vec::new();
+        let mut file = None;
+        let mut i = 0;
+        while i < args.len() {
+            match args[i].as_str() {
+                "--gui" | "--cli" | "--tui" | "--realtime" | "--report-gif" => {
+                    options.push(args[i].clone())
+                }
+                "--watch" | "--max-ticks" | "--log-level" | "--tick-rate" | "--summary"
+                | "--report" | "--frame-interval" => {
+                    options.extend(args[i..args.len().min(i + 2)].iter().cloned());
+                    i += 1;
+                }
+                "--help" => {
+                    Self::print_replay_help();
+                    process::exit(0);
+                }
+                arg if !arg.starts_with('-') && file.is_none() => file = Some(arg.to_string()),
+                arg => return Err(ConfigError::UnknownArgument(arg.to_string())),
+            }
+            i += 1;
+        }
+        if file.is_none() {
+            return Err(ConfigError::MissingReplayFile);
+        }
+
+        let mut config = Self::parse_args(&options)?;
+        config.replay_file = file;
+        Ok(config)
+    }
+
+    /// Parse les options de `ants edit` : fichier de la carte et dimensions d'une carte vierge
+    pub fn parse_edit_args(args: &[String]) -> Result<Self, ConfigError> {
+        let mut options = Vec::new();
+        let mut i = 0;
+        while i < args.len() {
+            match args[i].as_str() {
+                "--map" | "--width" | "--height" => {
+                    options.extend(args[i..args.len().min(i + 2)].iter().cloned());
+                    i += 1;
+                }
+                "--help" => {
+                    Self::print_edit_help();
+                    process::exit(0);
+                }
+                arg => return Err(ConfigError::UnknownArgument(arg.to_string())),
+            }
+            i += 1;
+        }
+        Self::parse_args(&options)
     }
 
     pub fn print_help() {
-        println!("Usage: ants_project [OPTIONS]");
+        println!("Usage: ants [COMMANDE] [OPTIONS]");
         println!();
-        println!("OPTIONS:");
+        println!("COMMANDES:");
+        println!("  simulate               Lancer une simulation (défaut sans commande)");
+        println!("  replay <FILE>          Rejouer un replay enregistré (.ants)");
+        println!("  edit --map <FILE>      Dessiner une carte dans l'éditeur");
+        println!("  optimize               Chercher les meilleurs hyperparamètres");
+        println!("  tournament             Opposer deux réglages sur une carte à deux nids");
+        println!(
+            "  curriculum             Entraîner une colonie sur des cartes de plus en plus dures"
+        );
+        println!("  verify-replay <FILE>.. Rejouer des replays et vérifier leur état final");
+        println!("  evaluate               Mesurer un cerveau entraîné sur un dossier de cartes");
+        println!("  serve --port <N>       Piloter des simulations par une API REST (HTTP)");
+        if cfg!(feature = "perf-compare") {
+            println!("  perf-compare           Comparer les débits des stockages de Q-tables");
+        }
+        println!("  help                   Afficher cette aide");
+        println!();
+        println!("Chaque commande accepte --help pour détailler ses options.");
+        println!();
+        println!("OPTIONS DE SIMULATE:");
+        println!(
+            "  --config <FILE>        Configuration TOML, les options suivantes la complètent"
+        );
+        println!(
+            "  --dump-config <FILE>   Écrire la configuration effective (TOML) avant de lancer"
+        );
+        println!("  --scenario <FILE>      Scénario (configuration, carte, événements) à la place de --config");
         println!("  --gui                  Utiliser l'interface graphique (défaut)");
         println!("  --cli                  Mode ligne de commande");
+        println!("  --tui                  Affichage dans le terminal (pause: espace, quitter: q)");
+        println!(
+            "  --watch <N>            Redessiner la grille en ASCII tous les N ticks (mode CLI)"
+        );
+        println!(
+            "  --log-level <L>        Journal d'événements : error, warn, info, debug, trace (défaut: debug en GUI, warn en CLI)"
+        );
+        println!("  --tick-rate <RATE>     Rythme des ticks en GUI : unlimited, <N>tps (ticks par seconde) ou <N>ms (délai entre deux ticks) (défaut: 100ms)");
+        println!("  --speed <MS>           Ancienne forme de --tick-rate <MS>ms, 0 = unlimited");
+        println!("  --realtime             Mode CLI : jouer les ticks au rythme de --tick-rate plutôt qu'au plus vite");
+        println!(
+            "  --ticks-per-frame <N>  Ticks calculés à chaque pas en GUI, 1 à 1000 (défaut: 1)"
+        );
         println!("  --width <N>            Largeur de la grille (défaut: 20)");
         println!("  --height <N>           Hauteur de la grille (défaut: 20)");
+        println!(
+            "  --wall-density <F>     Part maximale de murs d'une carte aléatoire (défaut: 0.25)"
+        );
+        println!("  --death-density <F>    Part maximale des cases restantes en zones mortelles (défaut: 0.1)");
+        println!("  --food-sources <N>     Nombre maximal de sources de nourriture (défaut: 3)");
+        println!("  --food-min <N>         Quantité minimale d'une source (défaut: 100)");
+        println!("  --food-max <N>         Quantité maximale d'une source (défaut: 9999)");
+        println!("  --protein-share <F>    Part des sources en protéine (défaut: 0.25)");
+        println!(
+            "  --map-seed <N>         Graine de la carte aléatoire (défaut: celle de la partie)"
+        );
         println!("  --explorers <N>        Nombre d'explorateurs (défaut: 2)");
         println!("  --fighters <N>         Nombre de combattantes (défaut: 1)");
         println!("  --pickers <N>          Nombre de récolteuses (défaut: 3)");
+        println!("  --explorer-capacity <N> Unités de nourriture portées max par les explorateurs (défaut: 10)");
+        println!("  --explorer-speed <N>   Ticks entre deux mouvements d'explorateur (défaut: 5)");
+        println!("  --explorer-scope <N>   Vision des explorateurs (défaut: 1)");
+        println!("  --picker-capacity <N>  Unités de nourriture portées max par les récolteuses (défaut: 100)");
+        println!("  --picker-speed <N>     Ticks entre deux mouvements de récolteuse (défaut: 10)");
+        println!("  --picker-scope <N>     Vision des récolteuses (défaut: 0)");
+        println!("  --fighter-capacity <N> Unités de nourriture portées max par les combattantes (défaut: 10)");
+        println!("  --fighter-speed <N>    Ticks entre deux mouvements de combattante (défaut: 5)");
+        println!("  --fighter-scope <N>    Vision des combattantes (défaut: 1)");
+        println!("  --no-sensing           La vision ne sert qu'à refuser d'entrer dans une zone mortelle");
+        println!("  --nest-capacity <N>    Fourmis déployées max par colonie (défaut: 100)");
+        println!(
+            "  --nest-type-caps <E:P:F> Fourmis déployées max par type, au lieu des places du nid"
+        );
+        println!("  --max-ants-per-cell <N|unlimited> Fourmis max par case (défaut: 10)");
+        println!(
+            "  --spawn-cooldown <N>   Ticks d'attente d'une fourmi qui sort du nid (défaut: 2)"
+        );
+        println!("  --spawn-rate <N>       Fourmis déployées max par nid et par tick (défaut: 1)");
+        println!("  --min-explorers <N>    Explorateurs actifs minimum (défaut: 3)");
+        println!("  --min-pickers <N>      Récolteuses actives minimum (défaut: 0)");
+        println!(
+            "  --spawn-policy <P>     Stratégie de spawn: priority, proportional, burst, demand (défaut: priority)"
+        );
+        println!(
+            "  --spawn-trail-threshold <F> Piste déclenchant l'envoi de récolteuses (défaut: 50)"
+        );
+        println!("  --spawn-stall-ticks <N> Ticks sans livraison avant d'envoyer des explorateurs (défaut: 200)");
+        println!(
+            "  --spawn-ratio <E:P:F>  Proportions de la stratégie proportional (défaut: 1:2:1)"
+        );
+        println!("  --spawn-burst-ticks <N> Durée de la vague d'explorateurs de la stratégie burst (défaut: 200)");
+        println!(
+            "  --food-upkeep <F>      Nourriture consommée par fourmi et par tick (défaut: 0)"
+        );
+        println!(
+            "  --upkeep-interval <N>  Ticks entre deux prélèvements de l'entretien (défaut: 1)"
+        );
+        println!("  --starvation-ticks <N> Ticks de famine avant une mort de faim (défaut: 50)");
+        println!(
+            "  --ant-energy <F>       Énergie d'une fourmi, morte à 0, 0 = désactivé (défaut: 0)"
+        );
+        println!("  --energy-drain <F>     Énergie dépensée par déplacement (défaut: 1)");
+        println!("  --energy-per-food <F>  Énergie rendue par unité mangée au nid (défaut: 50)");
+        println!("  --hunger-threshold <F> Part de l'énergie sous laquelle la fourmi suit la piste du nid (défaut: 0.3)");
+        println!("  --stuck-ticks <N>      Ticks sans s'éloigner de --stuck-radius cases avant qu'une fourmi soit bloquée, 0 = désactivé (défaut: 0)");
+        println!("  --stuck-radius <N>     Distance en deçà de laquelle une fourmi n'a pas bougé (défaut: 2)");
+        println!("  --stuck-recovery <R>   Reprise d'une fourmi bloquée: explore, random, recall (défaut: explore)");
+        println!("  --recovery-ticks <N>   Durée de la reprise explore ou random (défaut: 20)");
+        println!("  --stuck-epsilon <F>    Epsilon minimal de la reprise explore (défaut: 0.5)");
+        println!("  --birth-cost <N>       Nourriture du nid consommée par naissance, 0 = désactivé (défaut: 0)");
+        println!("  --birth-interval <N>   Ticks entre deux naissances (défaut: 100)");
+        println!("  --birth-ratio <E:P:F>  Proportions explorateurs:récolteuses:combattantes (défaut: 1:2:1)");
+        println!("  --lifespan <E:P:F>     Durée de vie en ticks par type, 0 = illimitée (défaut: 0:0:0)");
+        println!(
+            "  --food-regrowth <K>    Unités rendues à chaque source par repousse (défaut: 0)"
+        );
+        println!("  --regrowth-interval <N> Ticks entre deux repousses (défaut: 100)");
+        println!(
+            "  --regrowth-cap <N>     Quantité maximale d'une source par repousse (défaut: 1000)"
+        );
+        println!("  --corpse-food <N>      Unités laissées par une fourmi morte, 0 = pas de cadavre (défaut: 0)");
+        println!("  --corpse-decay <N>     Ticks pour qu'un cadavre perde une unité (défaut: 50)");
+        println!("  --predators <N>        Prédateurs simultanés maximum (défaut: 0)");
+        println!("  --predator-interval <N> Ticks entre deux apparitions (défaut: 200)");
+        println!("  --predator-speed <N>   Ticks entre deux mouvements de prédateur (défaut: 3)");
+        println!("  --fighter-win-chance <F> Chance qu'un coup de combattante porte (défaut: 0.7)");
+        println!("  --fighter-health <N>   Points de vie d'une combattante (défaut: 12)");
+        println!("  --fighter-attack <N>   Dégâts d'un coup de combattante (défaut: 4)");
+        println!("  --predator-health <N>  Points de vie d'un prédateur (défaut: 10)");
+        println!("  --predator-attack <N>  Dégâts d'un prédateur par tick de combat (défaut: 3)");
+        println!("  --predator-raid <N>    Nourriture pillée par mouvement dans un nid sans combattante, 0 = jamais (défaut: 5)");
+        println!("  --patrol-range <N>     Rayon de patrouille des combattantes autour du nid (défaut: 6)");
+        println!("  --evaporation <F>      Taux d'évaporation des phéromones (défaut: 0.01)");
+        println!("  --fear-weight <F>      Poids de la phéromone d'alarme retranchée des valeurs Q, 0 = désactivée (défaut: 0)");
+        println!(
+            "  --fear-deposit <F>     Alarme déposée là où une fourmi meurt tuée (défaut: 100)"
+        );
+        println!(
+            "  --fear-evaporation <F> Taux d'évaporation de la phéromone d'alarme (défaut: 0.02)"
+        );
+        println!("  --day-night <N>        Période du cycle jour/nuit en ticks, 0 = pas de nuit (défaut: 0)");
+        println!("  --night-evaporation <F> Facteur d'évaporation la nuit (défaut: 0.25)");
+        println!("  --night-epsilon <F>    Ajouté à epsilon la nuit (défaut: 0.2)");
+        println!(
+            "  --night-slowdown <N>   Attente entre deux mouvements multipliée la nuit (défaut: 2)"
+        );
+        println!("  --reward-food <F>      Récompense pour la nourriture trouvée (défaut: 1000)");
+        println!("  --reward-nest <F>      Récompense pour le retour au nid (défaut: 1000)");
+        println!("  --reward-death <F>     Récompense d'une zone mortelle (défaut: -100)");
+        println!("  --reward-default <F>   Récompense d'une case normale (défaut: -1)");
+        println!("  --reward-load-scaling  Multiplier la récompense de la nourriture par la part de la charge remplie");
+        println!("  --reward-per-unit <F>  Récompense de chaque unité livrée au nid, en plus de --reward-nest (défaut: 0)");
         println!("  --alpha <F>            Facteur d'apprentissage (défaut: 0.1)");
         println!("  --gamma <F>            Facteur d'actualisation (défaut: 0.9)");
+        println!("  --lambda <F>           Traces d'éligibilité Q(λ), 0 = mise à jour à un pas (défaut: 0)");
+        println!("  --n-step <N>           Cible de Bellman sur N pas par fourmi, exclusif avec --lambda (défaut: 1)");
         println!("  --epsilon <F>          Facteur ε-greedy (défaut: 0.05)");
+        println!(
+            "  --epsilon-decay <F>    Taux de décroissance d'epsilon (exponentielle par défaut)"
+        );
+        println!("  --epsilon-min <F>      Valeur plancher d'epsilon (défaut: 0)");
+        println!("  --epsilon-schedule <S> constant, linear, exponential, step");
+        println!("  --epsilon-step <N>     Taille d'un palier en ticks (défaut: 1000)");
+        println!(
+            "  --alpha-decay <F>      Taux de décroissance d'alpha (exponentielle par défaut)"
+        );
+        println!("  --alpha-min <F>        Valeur plancher d'alpha (défaut: 0)");
+        println!("  --alpha-schedule <S>   constant, linear, exponential, step");
+        println!("  --alpha-step <N>       Taille d'un palier en ticks (défaut: 1000)");
+        println!("  --meta-controller <M>  Correction d'epsilon et d'alpha selon les livraisons: off, stall, bandit (défaut: off)");
+        println!(
+            "  --controller-period <N> Ticks entre deux décisions du méta-contrôleur (défaut: 500)"
+        );
+        println!("  --controller-step <F>  Facteur d'epsilon et d'alpha quand les livraisons stagnent, mode stall (défaut: 1.5)");
+        println!("  --controller-max-epsilon <F> Plafond de l'epsilon relevé par le méta-contrôleur (défaut: 0.5)");
+        println!("  --exploration <P>      epsilon-greedy, softmax, ucb (défaut: epsilon-greedy)");
+        println!("  --temperature <F>      Température du Softmax (défaut: 50)");
+        println!("  --ucb-c <F>            Poids du bonus UCB (défaut: 100)");
+        println!("  --count-bonus <F>      Ajoute c/√N(s,a) à la récompense des actions peu jouées (défaut: 0, désactivé)");
+        println!("  --unmasked-exploration L'exploration epsilon-greedy tire parmi les 4 directions, murs compris");
+        println!("  --masked-max-q         Le max de la cible de Bellman ignore les déplacements vers les murs");
+        println!("  --freeze               Politique figée : actions gloutonnes, Q-tables ni mises à jour ni évaporées");
+        println!("  --algorithm <A>        qlearning, sarsa, expected-sarsa, double-qlearning (défaut: qlearning)");
+        println!("  --q-backend <B>        tabular (une valeur par case) ou linear (traits de case) (défaut: tabular)");
+        println!("  --linear-tile <N>      Côté en cases des pavés de position de --q-backend linear (défaut: 4)");
+        println!("  --per-type-tables      Une paire de cartes par type de fourmi au lieu d'une par colonie");
+        println!("  --near-food-state      L'état Q distingue la nourriture en vue (couches *_near_food)");
+        println!("  --experience-buffer <N> Transitions gardées pour l'experience replay, 0 = désactivé (défaut: 0)");
+        println!("  --experience-batch <N> Transitions rejouées à chaque tick (défaut: 32)");
+        println!("  --q-init <F>           Valeur Q de départ, > 0 pour une exploration optimiste (défaut: 0)");
+        println!(
+            "  --q-clamp <F>          Amplitude maximale des valeurs Q, 0 = sans borne (défaut: 0)"
+        );
+        println!("  --q-scale <F>          Valeur Q affichée à pleine intensité (défaut: 50)");
+        println!("  --policy <P>           qlearning, aco, random, astar (défaut: qlearning)");
+        println!("  --aco-alpha <F>        ACO : exposant de la phéromone (défaut: 1)");
+        println!("  --aco-beta <F>         ACO : exposant de l'heuristique (défaut: 2)");
+        println!("  --aco-deposit <F>      ACO : phéromone déposée à chaque pas (défaut: 1)");
         println!("  --max-ticks <N>        Limite de temps en ticks (défaut: 1000000000)");
-        println!("  --output <FILE>        Fichier de résultats");
+        println!("  --target-food <N>      Terminer quand un nid a stocké N unités de nourriture");
+        println!("  --seed <N>             Graine aléatoire pour une simulation reproductible");
+        println!("  --snapshot-interval <N> Ticks entre deux snapshots (0 = aucun, défaut: 1 en GUI, 0 en CLI)");
+        println!(
+            "  --max-snapshots <N|unlimited> Snapshots conservés au maximum (défaut: unlimited)"
+        );
+        println!("  --keyframe-interval <N> Snapshots entre deux états complets de la timeline (défaut: 256)");
+        println!("  --output <FILE>        Métriques par tick (.csv ou .json/.ndjson)");
+        println!("  --summary <FILE>       Bilan JSON de la partie CLI (ticks, fin, nourriture, morts, types de fourmis, graine, configuration) ; - pour la sortie standard");
+        println!("  --report <FILE>        Rapport HTML autonome de la partie CLI (bilan, courbes, Q-tables finales, configuration)");
+        println!("  --report-gif           Joindre au rapport l'animation de la partie, une image tous les --frame-interval ticks");
+        println!("  --leaderboard <FILE>   Inscrire la partie CLI au classement JSON des meilleurs scores de sa carte (GUI : remplace meilleurs_scores.json)");
+        println!(
+            "  --score-food <F>       Score : points par unité de nourriture livrée (défaut: 1)"
+        );
+        println!("  --score-death <F>      Score : points retirés par fourmi morte (défaut: 2)");
+        println!("  --score-tick <F>       Score : points retirés par tick joué (défaut: 0.01)");
+        println!("  --metrics-port <PORT>  Servir les métriques Prometheus sur http://0.0.0.0:PORT/metrics (CLI)");
+        println!("  --serve-ws <PORT>      Diffuser l'état de la partie par WebSocket sur ws://0.0.0.0:PORT (CLI)");
+        println!("  --ws-interval <N>      Ticks entre deux messages WebSocket (défaut: 10)");
+        println!("  --observation <F>      Observation encodée de l'environnement externe : none, features ou grid (défaut: none)");
+        println!("  --render-frames <DIR>  Dessiner le plateau en PNG dans DIR, ou en animation si le chemin finit par .gif (CLI)");
+        println!("  --frame-interval <N>   Ticks entre deux images rendues (défaut: 10)");
+        println!("  --runs <N>             Jouer N parties en parallèle et afficher leurs statistiques (CLI)");
+        println!(
+            "  --runs-output <FILE>   Résultats de chaque partie du batch ou de l'ensemble (CSV)"
+        );
+        println!("  --ensemble <K>         Jouer K parties en parallèle qui mettent régulièrement leurs Q-tables en commun (CLI)");
+        println!("  --ensemble-sync <N>    Ticks entre deux mises en commun des Q-tables de l'ensemble (défaut: 100)");
+        println!("  --ensemble-merge <M>   Mise en commun des Q-tables : mean (moyenne) ou vote (meilleure action majoritaire) (défaut: mean)");
+        println!("  --run-timeout <SECS>   Abandonner (DNF) une partie du batch ou de l'ensemble après SECS secondes de calcul");
+        println!("  --threads <N>          Threads des calculs parallèles (défaut: un par coeur)");
+        println!("  --bench                Mesurer le débit (ticks/s) et le temps de chaque phase sur une charge fixe");
+        println!("  --bench-ticks <N>      Ticks joués par le benchmark (défaut: 2000)");
+        println!("  --map <FILE>           Carte JSON sauvegardée depuis l'éditeur (en GUI, ouvre directement le plateau)");
+        println!("  --save-brain <FILE>    Sauvegarder les Q-tables en fin de simulation");
+        println!("  --load-brain <FILE>    Recharger des Q-tables entraînées au démarrage");
+        println!("  --brain-resample <MODE> Adapter un cerveau appris sur une autre taille de grille : scale, bilinear ou crop (défaut: scale)");
+        println!("  --dump-qtable <PREFIX> Exporter les Q-tables en CSV et en carte de chaleur SVG en fin de simulation (CLI)");
+        println!("  --record <FILE>        Enregistrer la partie CLI dans un replay (.ants)");
+        println!("  --checkpoint-every <N> Sauvegarder la partie CLI tous les N ticks (0 = jamais, défaut: 0)");
+        println!("  --checkpoint-file <FILE> Fichier des checkpoints (défaut: checkpoint.bin)");
+        println!("  --resume-from <FILE>   Reprendre la partie d'un checkpoint (CLI)");
         println!("  --help                 Afficher cette aide");
         println!();
         println!("EXEMPLES:");
-        println!("  ants_project --gui --width 30 --height 30");
-        println!("  ants_project --cli --alpha 0.2 --gamma 0.8 --output results.txt");
+        println!("  ants simulate --gui --width 30 --height 30");
+        println!("  ants simulate --cli --alpha 0.2 --gamma 0.8 --output results.txt");
+        println!("  ants replay partie.ants --tui");
+        println!("  ants edit --map carte.json");
+        println!("  ants optimize --strategy bayes --samples 100");
+    }
+
+    pub fn print_replay_help() {
+        println!("Usage: ants replay <FILE> [OPTIONS]");
+        println!();
+        println!("OPTIONS:");
+        println!("  --gui                  Timeline dans l'interface graphique (défaut)");
+        println!("  --cli                  Rejouer sans affichage");
+        println!("  --tui                  Affichage dans le terminal (pause: espace, quitter: q)");
+        println!(
+            "  --watch <N>            Redessiner la grille en ASCII tous les N ticks (mode CLI)"
+        );
+        println!("  --max-ticks <N>        Limite de temps en ticks (défaut: 1000000000)");
+        println!(
+            "  --realtime             Rejouer sans affichage au rythme de --tick-rate (mode CLI)"
+        );
+        println!("  --tick-rate <RATE>     Rythme de la relecture : unlimited, <N>tps ou <N>ms (défaut: 100ms)");
+        println!("  --summary <FILE>       Bilan JSON de la relecture (mode CLI) ; - pour la sortie standard");
+        println!("  --report <FILE>        Rapport HTML autonome de la relecture (mode CLI)");
+        println!("  --report-gif           Joindre au rapport l'animation de la relecture");
+        println!("  --frame-interval <N>   Ticks entre deux images de l'animation (défaut: 10)");
+        println!("  --log-level <L>        Journal d'événements : error, warn, info, debug, trace");
+        println!("  --help                 Afficher cette aide");
     }
 
-    pub fn validate(&self) -> Result<(), String> {
+    pub fn print_edit_help() {
+        println!("Usage: ants edit [OPTIONS]");
+        println!();
+        println!("OPTIONS:");
+        println!("  --map <FILE>           Carte JSON à ouvrir, créée à la sauvegarde si absente");
+        println!("  --width <N>            Largeur d'une nouvelle carte (défaut: 20)");
+        println!("  --height <N>           Hauteur d'une nouvelle carte (défaut: 20)");
+        println!("  --help                 Afficher cette aide");
+    }
+
+    /// Indique si une case contenant `count` fourmis est saturée
+    pub fn is_cell_full(&self, count: u32) -> bool {
+        self.max_ants_per_cell.is_some_and(|max| count >= max)
+    }
+
+    pub fn validate(&self) -> Result<(), AntsError> {
+        self.check().map_err(AntsError::InvalidConfig)
+    }
+
+    fn check(&self) -> Result<(), String> {
         if self.grid_width == 0 || self.grid_height == 0 {
             return Err("La grille doit avoir des dimensions > 0".to_string());
         }
 
+        for (name, share) in [
+            ("wall_density", self.map_gen.wall_density),
+            ("death_density", self.map_gen.death_density),
+            ("protein_share", self.map_gen.protein_share),
+        ] {
+            if !(0.0..=1.0).contains(&share) {
+                return Err(format!("{} doit être entre 0.0 et 1.0", name));
+            }
+        }
+
+        if self.map_gen.food_sources == 0 {
+            return Err("food_sources doit être > 0".to_string());
+        }
+
+        if self.map_gen.food_min == 0 || self.map_gen.food_min > self.map_gen.food_max {
+            return Err("Il faut 0 < food_min <= food_max".to_string());
+        }
+
         if self.alpha < 0.0 || self.alpha > 1.0 {
             return Err("alpha doit être entre 0.0 et 1.0".to_string());
         }
@@ -211,14 +1458,413 @@ impl SimulationConfig {
             return Err("gamma doit être entre 0.0 et 1.0".to_string());
         }
 
+        if self.lambda < 0.0 || self.lambda > 1.0 {
+            return Err("lambda doit être entre 0.0 et 1.0".to_string());
+        }
+
+        if self.n_step == 0 {
+            return Err("n-step doit être au moins 1".to_string());
+        }
+
+        if self.n_step > 1 && self.lambda > 0.0 {
+            return Err("--n-step et --lambda sont exclusifs".to_string());
+        }
+
+        if self.linear_tile == 0 {
+            return Err("linear-tile doit être au moins 1".to_string());
+        }
+
+        // Traces et seconde table sont indexées par case : propres à la Q-table
+        if self.q_backend == QBackendKind::Linear {
+            if self.lambda > 0.0 {
+                return Err("--lambda demande --q-backend tabular".to_string());
+            }
+            if self.learning_algorithm == LearningAlgorithm::DoubleQLearning {
+                return Err("double-qlearning demande --q-backend tabular".to_string());
+            }
+        }
+
         if self.epsilon < 0.0 || self.epsilon > 1.0 {
             return Err("epsilon doit être entre 0.0 et 1.0".to_string());
         }
 
+        if !(1..=MAX_TICKS_PER_FRAME).contains(&self.ticks_per_frame) {
+            return Err(format!(
+                "ticks_per_frame doit être entre 1 et {}",
+                MAX_TICKS_PER_FRAME
+            ));
+        }
+
+        if self.target_food == Some(0) {
+            return Err("target_food doit être > 0".to_string());
+        }
+
+        if self.max_snapshots == Some(0) {
+            return Err("max_snapshots doit être > 0 (ou unlimited)".to_string());
+        }
+
+        if self.keyframe_interval == 0 {
+            return Err("keyframe_interval doit être > 0".to_string());
+        }
+
+        if self.explorer_capacity_load == 0
+            || self.picker_capacity_load == 0
+            || self.fighter_capacity_load == 0
+        {
+            return Err("la charge max de chaque type doit être > 0".to_string());
+        }
+
+        if self.spawn_ratio.iter().all(|&w| w == 0) {
+            return Err("spawn_ratio doit contenir au moins une proportion > 0".to_string());
+        }
+
+        if self.spawn_rate_per_tick == 0 {
+            return Err("spawn_rate_per_tick doit être > 0".to_string());
+        }
+
+        if self.max_ants_per_cell == Some(0) {
+            return Err("max_ants_per_cell doit être > 0 (ou unlimited)".to_string());
+        }
+
+        if self.food_upkeep_per_ant < 0.0 {
+            return Err("food_upkeep_per_ant doit être >= 0.0".to_string());
+        }
+        if self.upkeep_interval == 0 {
+            return Err("upkeep_interval doit être > 0".to_string());
+        }
+
+        if self.ant_energy < 0.0 || self.energy_drain < 0.0 {
+            return Err("ant_energy et energy_drain doivent être >= 0.0".to_string());
+        }
+
+        if self
+            .nest_type_caps
+            .is_some_and(|caps| caps.iter().all(|&cap| cap == 0))
+        {
+            return Err("nest_type_caps doit laisser au moins une place".to_string());
+        }
+
+        if !self.events.is_sorted_by_key(|event| event.tick) {
+            return Err("events doit être trié par tick".to_string());
+        }
+
+        for hazard in &self.hazards {
+            hazard.validate()?;
+        }
+
+        if self.corpse_decay == 0 {
+            return Err("corpse_decay doit être > 0".to_string());
+        }
+
+        if self.birth_interval == 0 {
+            return Err("birth_interval doit être > 0".to_string());
+        }
+
+        if self.birth_cost > 0 && self.birth_ratio.iter().all(|&w| w == 0) {
+            return Err("birth_ratio doit contenir au moins une proportion > 0".to_string());
+        }
+
+        if self.energy_per_food <= 0.0 {
+            return Err("energy_per_food doit être > 0.0".to_string());
+        }
+
+        if self.hunger_threshold < 0.0 || self.hunger_threshold > 1.0 {
+            return Err("hunger_threshold doit être entre 0.0 et 1.0".to_string());
+        }
+
+        if self.food_regrowth_interval == 0 {
+            return Err("food_regrowth_interval doit être > 0".to_string());
+        }
+
+        if self.fighter_win_chance < 0.0 || self.fighter_win_chance > 1.0 {
+            return Err("fighter_win_chance doit être entre 0.0 et 1.0".to_string());
+        }
+
+        if self.fighter_health == 0 || self.predator_health == 0 {
+            return Err("fighter_health et predator_health doivent être > 0".to_string());
+        }
+
+        if self.day_night_period == 1 {
+            return Err("day_night_period doit être 0 (désactivé) ou >= 2".to_string());
+        }
+
+        if self.night_evaporation_factor < 0.0 {
+            return Err("night_evaporation_factor doit être >= 0.0".to_string());
+        }
+
+        if !(0.0..=1.0).contains(&self.night_epsilon_boost) {
+            return Err("night_epsilon_boost doit être entre 0.0 et 1.0".to_string());
+        }
+
+        if self.night_slowdown == 0 {
+            return Err("night_slowdown doit être > 0".to_string());
+        }
+
+        if self.count_bonus < 0.0 {
+            return Err("count_bonus doit être >= 0.0".to_string());
+        }
+        if self.softmax_temperature <= 0.0 {
+            return Err("softmax_temperature doit être > 0.0".to_string());
+        }
+
+        if self.aco_alpha < 0.0 || self.aco_beta < 0.0 {
+            return Err("aco_alpha et aco_beta doivent être >= 0.0".to_string());
+        }
+
+        if self.experience_buffer > 0 && self.experience_batch == 0 {
+            return Err(
+                "experience_batch doit être > 0 quand l'experience replay est actif".to_string(),
+            );
+        }
+
+        if !self.q_initial.is_finite() {
+            return Err("q_initial doit être un nombre fini".to_string());
+        }
+
+        if self.q_clamp < 0.0 {
+            return Err("q_clamp doit être >= 0.0".to_string());
+        }
+
+        if self.q_clamp > 0.0 && self.q_initial.abs() > self.q_clamp {
+            return Err("q_initial doit rester dans la borne q_clamp".to_string());
+        }
+
+        if self.q_display_scale <= 0.0 {
+            return Err("q_display_scale doit être > 0.0".to_string());
+        }
+
+        if self.aco_deposit <= 0.0 {
+            return Err("aco_deposit doit être > 0.0".to_string());
+        }
+
         if self.pheromone_evaporation < 0.0 || self.pheromone_evaporation > 1.0 {
             return Err("pheromone_evaporation doit être entre 0.0 et 1.0".to_string());
         }
 
+        if self.controller_period == 0 {
+            return Err("controller_period doit être > 0".to_string());
+        }
+
+        if self.controller_step <= 1.0 {
+            return Err("controller_step doit être > 1.0".to_string());
+        }
+
+        if !(0.0..=1.0).contains(&self.controller_max_epsilon) {
+            return Err("controller_max_epsilon doit être entre 0.0 et 1.0".to_string());
+        }
+
+        if !(0.0..=1.0).contains(&self.stuck_epsilon) {
+            return Err("stuck_epsilon doit être entre 0.0 et 1.0".to_string());
+        }
+
+        if self.stuck_ticks > 0 && self.recovery_ticks == 0 {
+            return Err(
+                "recovery_ticks doit être > 0 quand la détection des blocages est active"
+                    .to_string(),
+            );
+        }
+
+        if self.fear_weight < 0.0 || self.fear_deposit < 0.0 {
+            return Err("fear_weight et fear_deposit doivent être >= 0.0".to_string());
+        }
+
+        if !(0.0..=1.0).contains(&self.fear_evaporation) {
+            return Err("fear_evaporation doit être entre 0.0 et 1.0".to_string());
+        }
+
+        if self.use_tui && self.use_gui {
+            return Err("--tui et --gui ne peuvent pas être combinés".to_string());
+        }
+
+        if !self.tick_rate.is_valid() {
+            return Err(format!(
+                "tick_rate {} invalide : utiliser unlimited pour ne pas limiter le rythme",
+                self.tick_rate
+            ));
+        }
+
+        // La GUI suit déjà tick_rate, le mode terminal a ses propres vitesses
+        if self.realtime && (self.use_gui || self.use_tui) {
+            return Err(
+                "--realtime n'est disponible qu'en mode CLI (--cli), sans --tui".to_string(),
+            );
+        }
+
+        if self.realtime && (self.runs > 1 || self.ensemble > 1 || self.bench_ticks > 0) {
+            return Err(
+                "--realtime ne se combine pas avec --runs, --ensemble ni --bench".to_string(),
+            );
+        }
+
+        if self.watch_interval > 0 && (self.use_gui || self.use_tui) {
+            return Err("--watch n'est disponible qu'en mode CLI (--cli), sans --tui".to_string());
+        }
+
+        if self.record_file.is_some() && self.use_gui {
+            return Err("--record n'est disponible qu'en mode CLI (--cli)".to_string());
+        }
+
+        if self.record_file.is_some() && self.replay_file.is_some() {
+            return Err("--record et --replay ne peuvent pas être combinés".to_string());
+        }
+
+        if self.runs == 0 {
+            return Err("runs doit être > 0".to_string());
+        }
+
+        if self.ensemble == 0 {
+            return Err("ensemble doit être > 0".to_string());
+        }
+
+        if self.ensemble_sync == 0 {
+            return Err("ensemble_sync doit être > 0".to_string());
+        }
+
+        if self.run_timeout > 0 && self.runs <= 1 && self.ensemble <= 1 {
+            return Err("--run-timeout ne concerne que --runs et --ensemble".to_string());
+        }
+
+        // Le benchmark joue sa propre partie, sans affichage
+        if self.bench_ticks > 0
+            && (self.use_gui
+                || self.use_tui
+                || self.runs > 1
+                || self.replay_file.is_some()
+                || self.resume_from.is_some())
+        {
+            return Err(
+                "--bench n'est disponible qu'en mode CLI, sans --tui, --runs, --replay ni \
+                 --resume-from"
+                    .to_string(),
+            );
+        }
+
+        if self.metrics_port.is_some() && self.use_gui {
+            return Err("--metrics-port n'est disponible qu'en mode CLI (--cli)".to_string());
+        }
+
+        if self.serve_ws.is_some() && self.use_gui {
+            return Err("--serve-ws n'est disponible qu'en mode CLI (--cli)".to_string());
+        }
+
+        if self.ws_interval == 0 {
+            return Err("ws_interval doit être > 0".to_string());
+        }
+
+        if self.render_frames.is_some() && self.use_gui {
+            return Err("--render-frames n'est disponible qu'en mode CLI (--cli)".to_string());
+        }
+
+        if self.frame_interval == 0 {
+            return Err("frame_interval doit être > 0".to_string());
+        }
+
+        if self.checkpoint_every > 0 && self.checkpoint_file.is_empty() {
+            return Err("--checkpoint-every demande un --checkpoint-file".to_string());
+        }
+
+        // Le checkpoint porte déjà sa carte et sa configuration
+        if self.resume_from.is_some()
+            && (self.use_gui
+                || self.record_file.is_some()
+                || self.replay_file.is_some()
+                || self.scenario_file.is_some()
+                || self.map_file.is_some())
+        {
+            return Err(
+                "--resume-from n'est disponible qu'en mode CLI, sans --record, --replay, \
+                 --scenario ni --map"
+                    .to_string(),
+            );
+        }
+
+        // Le bilan JSON est celui d'une partie CLI ; le mode terminal occupe la sortie standard
+        if self.summary_file.is_some() && (self.use_gui || self.bench_ticks > 0) {
+            return Err("--summary n'est disponible qu'en mode CLI, sans --bench".to_string());
+        }
+        if self.summary_file.as_deref() == Some("-") && (self.use_tui || self.watch_interval > 0) {
+            return Err(
+                "--summary - ne se combine pas avec --tui ni --watch : donner un fichier"
+                    .to_string(),
+            );
+        }
+
+        // Le rapport HTML est celui d'une partie CLI
+        if self.report_file.is_some() && (self.use_gui || self.bench_ticks > 0) {
+            return Err("--report n'est disponible qu'en mode CLI, sans --bench".to_string());
+        }
+        if self.report_gif && self.report_file.is_none() {
+            return Err("--report-gif demande un --report".to_string());
+        }
+
+        // Le classement reçoit une partie à la fois
+        if self.leaderboard_file.is_some()
+            && (self.runs > 1 || self.ensemble > 1 || self.bench_ticks > 0)
+        {
+            return Err(
+                "--leaderboard ne se combine pas avec --runs, --ensemble ni --bench".to_string(),
+            );
+        }
+        for (name, value) in [
+            ("--score-food", self.score_food),
+            ("--score-death", self.score_death),
+            ("--score-tick", self.score_tick),
+        ] {
+            if !value.is_finite() {
+                return Err(format!("{} doit être un nombre fini", name));
+            }
+        }
+
+        // Ces fichiers décrivent une seule partie
+        if self.runs > 1
+            && (self.output_file.is_some()
+                || self.summary_file.is_some()
+                || self.report_file.is_some()
+                || self.record_file.is_some()
+                || self.save_brain.is_some()
+                || self.dump_qtable.is_some()
+                || self.replay_file.is_some()
+                || self.checkpoint_every > 0
+                || self.resume_from.is_some()
+                || self.metrics_port.is_some()
+                || self.serve_ws.is_some()
+                || self.render_frames.is_some())
+        {
+            return Err(
+                "--runs ne se combine pas avec --output, --summary, --report, --record, --save-brain, \
+                 --dump-qtable, --replay, --checkpoint-every, --resume-from, --metrics-port, \
+                 --serve-ws ou --render-frames (voir --runs-output)"
+                    .to_string(),
+            );
+        }
+
+        // Les parties de l'ensemble ne sont ni affichées ni enregistrées une à une ; seul le
+        // cerveau commun est sauvegardé
+        if self.ensemble > 1 && (self.use_gui || self.use_tui) {
+            return Err("--ensemble n'est disponible qu'en mode CLI, sans --tui".to_string());
+        }
+        if self.ensemble > 1
+            && (self.runs > 1
+                || self.bench_ticks > 0
+                || self.output_file.is_some()
+                || self.summary_file.is_some()
+                || self.report_file.is_some()
+                || self.record_file.is_some()
+                || self.replay_file.is_some()
+                || self.checkpoint_every > 0
+                || self.resume_from.is_some()
+                || self.metrics_port.is_some()
+                || self.serve_ws.is_some()
+                || self.render_frames.is_some())
+        {
+            return Err(
+                "--ensemble ne se combine pas avec --runs, --bench, --output, --summary, \
+                 --report, --record, --replay, --checkpoint-every, --resume-from, --metrics-port, \
+                 --serve-ws ou --render-frames (voir --runs-output)"
+                    .to_string(),
+            );
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,238 @@
+use crate::aco::ScentTrails;
+use crate::ant::{AntsMode, AntsType};
+use crate::fear::FearMap;
+use crate::grid::Grid;
+use crate::linear_q::LinearQ;
+use crate::pheromone::{PheromoneMap, QBounds};
+use crate::q_backend::{QBackend, QBackendKind};
+use crate::q_table::{QTable, StateFeatures, StateLayout};
+use serde::{Deserialize, Serialize};
+
+// État propre à une colonie : son cerveau (une Q-table, une couche de phéromones par mode)
+// et son score
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Colony {
+    pub id: usize,
+    pub q_table: QTable,
+    // Tables propres à chaque type de fourmi (rang de `AntsType::index`), vide quand toutes
+    // les fourmis partagent la table ci-dessus
+    #[serde(default)]
+    pub caste_tables: Vec<QTable>,
+    // Pistes scalaires de l'ACO classique, None quand les fourmis apprennent par Q-Learning
+    #[serde(default)]
+    pub trails: Option<ScentTrails>,
+    // Phéromone d'alarme déposée là où ses fourmis meurent, None quand la peur est désactivée
+    #[serde(default)]
+    pub fear: Option<FearMap>,
+    // Nourriture totale rapportée au nid
+    pub score: u64,
+    // Fourmis de la colonie mortes depuis le début de la partie
+    #[serde(default)]
+    pub deaths: u64,
+    pub ticks_since_delivery: u32,
+    pub(crate) upkeep_debt: f32,
+    pub(crate) starving_ticks: u32,
+}
+
+impl Colony {
+    pub fn new(id: usize, width: u32, height: u32) -> Self {
+        Colony {
+            id,
+            q_table: QTable::new(width, height, StateLayout::default()),
+            caste_tables: Vec::new(),
+            trails: None,
+            fear: None,
+            score: 0,
+            deaths: 0,
+            ticks_since_delivery: 0,
+            upkeep_debt: 0.0,
+            starving_ticks: 0,
+        }
+    }
+
+    // Nom affiché de la colonie : A, B, C...
+    pub fn label(id: usize) -> char {
+        (b'A' + (id % 26) as u8) as char
+    }
+
+    /// Donne (ou retire) à chaque type de fourmi sa propre table ; les nouvelles tables
+    /// partent de ce que la colonie a appris en commun
+    pub fn set_per_type_maps(&mut self, per_type: bool) {
+        if !per_type {
+            self.caste_tables.clear();
+        } else if self.caste_tables.is_empty() {
+            self.caste_tables = AntsType::all()
+                .iter()
+                .map(|_| self.q_table.clone())
+                .collect();
+        }
+    }
+
+    /// Change les traits d'état distingués par toutes les tables de la colonie
+    pub fn set_state_layout(&mut self, layout: StateLayout) {
+        for table in std::iter::once(&mut self.q_table).chain(&mut self.caste_tables) {
+            table.set_layout(layout);
+        }
+    }
+
+    /// Crée (ou retire) les pistes de l'ACO classique ; des pistes existantes sont gardées
+    pub fn set_scent_trails(&mut self, enabled: bool) {
+        if !enabled {
+            self.trails = None;
+        } else if self.trails.is_none() {
+            let (width, height) = (self.q_table.width(), self.q_table.height());
+            self.trails = Some(ScentTrails::new(width, height));
+        }
+    }
+
+    /// Crée (ou retire) la carte de peur ; une carte existante est gardée
+    pub fn set_fear_map(&mut self, enabled: bool) {
+        if !enabled {
+            self.fear = None;
+        } else if self.fear.is_none() {
+            let (width, height) = (self.q_table.width(), self.q_table.height());
+            self.fear = Some(FearMap::new(width, height));
+        }
+    }
+
+    /// Ajoute (ou retire) la seconde table du Double Q-Learning à chaque carte de la colonie
+    pub fn set_double_tables(&mut self, enabled: bool) {
+        for table in std::iter::once(&mut self.q_table).chain(&mut self.caste_tables) {
+            for map in table.layers_mut() {
+                map.set_double(enabled);
+            }
+        }
+    }
+
+    /// Change la valeur de départ et la borne des valeurs Q de chaque carte de la colonie
+    pub fn set_q_bounds(&mut self, bounds: QBounds) {
+        for table in std::iter::once(&mut self.q_table).chain(&mut self.caste_tables) {
+            for map in table.layers_mut() {
+                map.set_bounds(bounds);
+            }
+            if let Some(linear) = table.linear_mut() {
+                linear.set_bounds(bounds);
+            }
+        }
+    }
+
+    /// Donne à chaque table de la colonie une approximation linéaire à pavés de `tile` cases
+    /// (ou la retire) ; une approximation déjà construite pour ce pavage est gardée
+    pub fn set_q_backend(&mut self, kind: QBackendKind, grid: &Grid, tile: u32) {
+        let id = self.id;
+        for table in std::iter::once(&mut self.q_table).chain(&mut self.caste_tables) {
+            let linear = match kind {
+                QBackendKind::Tabular => None,
+                QBackendKind::Linear => match table.linear() {
+                    Some(linear) if linear.fits(grid, tile) => Some(linear.clone()),
+                    _ => {
+                        let bounds = table.layers().first().map(|map| map.bounds());
+                        Some(LinearQ::new(grid, id, tile, bounds.unwrap_or_default()))
+                    }
+                },
+            };
+            table.set_linear(linear);
+        }
+    }
+
+    pub fn has_per_type_maps(&self) -> bool {
+        !self.caste_tables.is_empty()
+    }
+
+    /// Q-table utilisée par un type de fourmi
+    pub fn table(&self, ant_type: AntsType) -> &QTable {
+        self.caste_tables
+            .get(ant_type.index())
+            .unwrap_or(&self.q_table)
+    }
+
+    pub fn table_mut(&mut self, ant_type: AntsType) -> &mut QTable {
+        match self.caste_tables.get_mut(ant_type.index()) {
+            Some(table) => table,
+            None => &mut self.q_table,
+        }
+    }
+
+    /// Cartes (nourriture, nid) d'un type de fourmi, sans trait d'état optionnel
+    pub fn maps(&self, ant_type: AntsType) -> (&PheromoneMap, &PheromoneMap) {
+        let table = self.table(ant_type);
+        (
+            table.mode_map(AntsMode::FINDING),
+            table.mode_map(AntsMode::RETURNING),
+        )
+    }
+
+    /// Carte d'un type de fourmi dans ce mode, sans trait d'état optionnel (affichage)
+    pub fn map(&self, ant_type: AntsType, mode: AntsMode) -> &PheromoneMap {
+        self.table(ant_type).mode_map(mode)
+    }
+
+    /// Carte consultée par une fourmi de ce type dans cet état
+    pub fn layer(&self, ant_type: AntsType, state: StateFeatures) -> &PheromoneMap {
+        self.table(ant_type).layer(state)
+    }
+
+    pub fn layer_mut(&mut self, ant_type: AntsType, state: StateFeatures) -> &mut PheromoneMap {
+        self.table_mut(ant_type).layer_mut(state)
+    }
+
+    /// Valeurs Q consultées par une fourmi de ce type dans cet état (carte ou approximation)
+    pub fn backend(&self, ant_type: AntsType, state: StateFeatures) -> &dyn QBackend {
+        self.table(ant_type).backend(state)
+    }
+
+    /// Cartes réellement mises à jour par les fourmis (les partagées, ou celles de chaque type)
+    pub fn active_maps(&self) -> Vec<&PheromoneMap> {
+        if self.caste_tables.is_empty() {
+            return self.q_table.layers().iter().collect();
+        }
+        self.caste_tables
+            .iter()
+            .flat_map(|table| table.layers())
+            .collect()
+    }
+
+    /// Exporte chaque couche de la table d'un type de fourmi en CSV et en SVG :
+    /// `<prefix>_food.csv`, `<prefix>_food.svg`, `<prefix>_nest.csv`, `<prefix>_nest.svg`,
+    /// plus `<prefix>_food_near_food.*` et `<prefix>_nest_near_food.*` si la table distingue la
+    /// nourriture en vue. Renvoie les fichiers écrits.
+    pub fn export_tables(
+        &self,
+        prefix: &str,
+        ant_type: AntsType,
+        grid: &Grid,
+    ) -> Result<Vec<String>, String> {
+        let table = self.table(ant_type);
+        let mut written = Vec::new();
+        for (layer, map) in table.layers().iter().enumerate() {
+            let name = table.layer_name(layer);
+            let csv = format!("{}_{}.csv", prefix, name);
+            map.export_csv(&csv)?;
+            let svg = format!("{}_{}.svg", prefix, name);
+            map.export_svg(&svg, grid)?;
+            written.extend([csv, svg]);
+        }
+        Ok(written)
+    }
+
+    /// Approximations linéaires réellement mises à jour, comme `active_maps_mut`
+    pub fn active_linear_mut(&mut self) -> Vec<&mut LinearQ> {
+        if self.caste_tables.is_empty() {
+            return self.q_table.linear_mut().into_iter().collect();
+        }
+        self.caste_tables
+            .iter_mut()
+            .filter_map(|table| table.linear_mut())
+            .collect()
+    }
+
+    pub fn active_maps_mut(&mut self) -> Vec<&mut PheromoneMap> {
+        if self.caste_tables.is_empty() {
+            return self.q_table.layers_mut().iter_mut().collect();
+        }
+        self.caste_tables
+            .iter_mut()
+            .flat_map(|table| table.layers_mut())
+            .collect()
+    }
+}
@@ -0,0 +1,118 @@
+//! Coordonnées des cases et rang d'une case dans les tableaux de la carte
+//!
+//! Toutes les données par case (tuiles, Q-values, peur, trafic, distances...) sont rangées
+//! ligne après ligne : la case (x, y) a le rang `y * width + x`. [`GridShape`] est le seul
+//! endroit où une position devient un rang et inversement, pour qu'aucun tableau ne soit
+//! indexé avec x et y intervertis sur une carte qui n'est pas carrée.
+
+use crate::pheromone::Action;
+use serde::{Deserialize, Serialize};
+
+/// Position d'une case : colonne `x` (vers la droite), rangée `y` (vers le bas)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Coord {
+    pub x: u32,
+    pub y: u32,
+}
+
+impl Coord {
+    pub const fn new(x: u32, y: u32) -> Self {
+        Coord { x, y }
+    }
+
+    /// Case voisine dans la direction de `action` ; un pas au-delà du bord haut ou gauche
+    /// sort de la carte (coordonnée démesurée, rejetée par `GridShape::contains`)
+    pub fn step(self, action: Action) -> Coord {
+        let Coord { x, y } = self;
+        match action {
+            Action::Up => Coord::new(x, y.wrapping_sub(1)),
+            Action::Down => Coord::new(x, y + 1),
+            Action::Left => Coord::new(x.wrapping_sub(1), y),
+            Action::Right => Coord::new(x + 1, y),
+            Action::Stay => self,
+        }
+    }
+
+    /// Distance de Manhattan
+    pub fn manhattan(self, other: Coord) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+impl From<(u32, u32)> for Coord {
+    fn from((x, y): (u32, u32)) -> Self {
+        Coord::new(x, y)
+    }
+}
+
+impl From<Coord> for (u32, u32) {
+    fn from(coord: Coord) -> Self {
+        (coord.x, coord.y)
+    }
+}
+
+/// Rang d'une case dans un tableau rangé ligne après ligne, obtenu par `GridShape::index` ;
+/// `as_usize` le donne pour indexer le tableau
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GridIndex(usize);
+
+impl GridIndex {
+    pub fn as_usize(self) -> usize {
+        self.0
+    }
+}
+
+/// Dimensions d'une carte, et correspondance entre positions et rangs
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GridShape {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl GridShape {
+    pub const fn new(width: u32, height: u32) -> Self {
+        GridShape { width, height }
+    }
+
+    /// Nombre de cases
+    pub fn len(self) -> usize {
+        self.width as usize * self.height as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn contains(self, coord: Coord) -> bool {
+        coord.x < self.width && coord.y < self.height
+    }
+
+    /// Rang de la case, None hors de la carte
+    pub fn index(self, coord: Coord) -> Option<GridIndex> {
+        self.contains(coord)
+            .then(|| GridIndex(coord.y as usize * self.width as usize + coord.x as usize))
+    }
+
+    /// Rang d'une case que l'appelant sait sur la carte
+    pub fn index_of(self, coord: Coord) -> GridIndex {
+        debug_assert!(self.contains(coord), "{:?} hors de {:?}", coord, self);
+        GridIndex(coord.y as usize * self.width as usize + coord.x as usize)
+    }
+
+    /// Position de la case de rang `index`
+    pub fn coord(self, index: GridIndex) -> Coord {
+        self.coord_at(index.0)
+    }
+
+    /// Position de la case rangée en `rank` dans un tableau de `len()` cases (rang obtenu en
+    /// parcourant le tableau)
+    pub fn coord_at(self, rank: usize) -> Coord {
+        let width = self.width.max(1) as usize;
+        Coord::new((rank % width) as u32, (rank / width) as u32)
+    }
+
+    /// Toutes les cases, dans l'ordre de leurs rangs
+    pub fn coords(self) -> impl Iterator<Item = Coord> {
+        (0..self.height).flat_map(move |y| (0..self.width).map(move |x| Coord::new(x, y)))
+    }
+}
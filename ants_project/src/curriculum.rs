@@ -0,0 +1,250 @@
+//! Curriculum : entraîner une colonie sur une suite de cartes de difficulté croissante
+//!
+//! `ants curriculum facile.scenario moyen.scenario difficile.scenario` joue les scénarios
+//! dans l'ordre. Le cerveau de la colonie A (ses cartes de phéromones) passe d'une étape à
+//! la suivante, adapté à la taille de la nouvelle carte selon le `transfer` de l'étape
+//! (voir [`Resample`](crate::pheromone::Resample)). Une étape est réussie quand la section
+//! `[goal]` de son scénario est atteinte avant sa limite de ticks ; une étape sans `[goal]`
+//! se joue jusqu'au bout et compte comme réussie. Après `--attempts` échecs, le curriculum
+//! s'arrête.
+
+use crate::ant::Ant;
+use crate::ants_game_manager::AntsGameManager;
+use crate::cli_args::SimulationConfig;
+use crate::runner::SimulationRunner;
+use crate::scenario::{Scenario, StageGoal};
+use std::fs;
+
+struct CurriculumArgs {
+    stages: Vec<String>,
+    // Essais d'une étape avant d'abandonner, chacun reprenant le cerveau du précédent
+    attempts: u32,
+    // Cerveau obtenu à la fin du curriculum (format de --save-brain)
+    save_brain: Option<String>,
+    // Résultat de chaque essai (CSV)
+    output: Option<String>,
+}
+
+/// Résultat d'un essai d'une étape
+#[derive(Clone, Debug)]
+pub struct StageResult {
+    pub stage: usize,
+    pub attempt: u32,
+    pub ticks: u64,
+    // Nourriture livrée au nid A
+    pub food: u64,
+    pub deaths: u64,
+    pub passed: bool,
+}
+
+// Point d'entrée de `ants curriculum` : `args` ne contient que les options de la sous-commande
+pub fn run(args: &[String]) -> Result<(), String> {
+    let Some(args) = parse_args(args)? else {
+        return Ok(());
+    };
+    // Toutes les étapes sont lues avant de commencer : une faute de frappe dans la dernière
+    // ne doit pas se découvrir après une heure d'entraînement
+    let scenarios = args
+        .stages
+        .iter()
+        .map(|path| {
+            let scenario = Scenario::load(path)?;
+            scenario
+                .config
+                .validate()
+                .map_err(|e| format!("Configuration invalide {}: {}", path, e))?;
+            Ok(scenario)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut results = Vec::new();
+    let mut trained: Option<AntsGameManager> = None;
+    'stages: for (stage, (path, scenario)) in args.stages.iter().zip(&scenarios).enumerate() {
+        println!("Étape {} : {}", stage + 1, path);
+        for attempt in 0..args.attempts {
+            let mut manager = build_stage(scenario, attempt)?;
+            match &trained {
+                Some(previous) => {
+                    let transfer = scenario.goal.unwrap_or_default().transfer;
+                    manager.adopt_brain(&previous.colonies()[0], transfer)?;
+                }
+                None => {
+                    if let Some(brain) = &scenario.config.load_brain {
+                        manager
+                            .import_brain(brain)
+                            .map_err(|e| format!("Erreur de chargement du cerveau: {}", e))?;
+                    }
+                }
+            }
+            let (result, manager) = play_stage(manager, scenario.goal, stage, attempt);
+            println!(
+                "  essai {} : {} en {} ticks, {} livrée(s), {} mort(s)",
+                attempt + 1,
+                if result.passed { "réussi" } else { "échoué" },
+                result.ticks,
+                result.food,
+                result.deaths
+            );
+            trained = Some(manager);
+            results.push(result);
+            if results.last().is_some_and(|r| r.passed) {
+                continue 'stages;
+            }
+        }
+        println!(
+            "Curriculum interrompu : étape {} échouée {} fois",
+            stage + 1,
+            args.attempts
+        );
+        break;
+    }
+
+    let passed = results.iter().filter(|r| r.passed).count();
+    println!("{}/{} étape(s) réussie(s)", passed, args.stages.len());
+    if let Some(path) = &args.output {
+        save_csv(&results, &args.stages, path)?;
+    }
+    if let (Some(path), Some(manager)) = (&args.save_brain, &trained) {
+        manager.export_brain(path)?;
+    }
+    Ok(())
+}
+
+// Nouvelle partie sur la carte du scénario (sinon aléatoire) ; chaque essai change de graine
+fn build_stage(scenario: &Scenario, attempt: u32) -> Result<AntsGameManager, String> {
+    let config = SimulationConfig {
+        seed: scenario
+            .config
+            .seed
+            .map(|seed| seed.wrapping_add(attempt as u64)),
+        use_gui: false,
+        output_file: None,
+        ..scenario.config.clone()
+    };
+    let created = match scenario.map_editor().transpose()? {
+        Some(editor) => AntsGameManager::new(
+            editor.width,
+            editor.height,
+            editor.to_tiles(),
+            vec![],
+            config.clone(),
+        )
+        .map(|mut manager| {
+            manager.set_ants(Ant::colony_from_config(&config));
+            manager
+        }),
+        None => AntsGameManager::from_config(config),
+    };
+    created.map_err(|e| format!("Erreur de création de la partie: {}", e))
+}
+
+// Joue l'étape jusqu'à son objectif, trop de morts, ou la fin de la partie
+fn play_stage(
+    manager: AntsGameManager,
+    goal: Option<StageGoal>,
+    stage: usize,
+    attempt: u32,
+) -> (StageResult, AntsGameManager) {
+    let mut runner = SimulationRunner::new(manager);
+    let passed = loop {
+        let food = runner.manager().colonies()[0].score;
+        let deaths = runner.manager().metrics().total_deaths;
+        match goal {
+            Some(goal) if food >= goal.food => break true,
+            Some(goal) if goal.max_deaths.is_some_and(|max| deaths > max) => break false,
+            _ if runner.is_done() => break goal.is_none(),
+            _ => {
+                runner.run_ticks(1);
+            }
+        }
+    };
+    let manager = runner.manager();
+    let result = StageResult {
+        stage,
+        attempt,
+        ticks: runner.tick_count(),
+        food: manager.colonies()[0].score,
+        deaths: manager.metrics().total_deaths,
+        passed,
+    };
+    (result, runner.into_manager())
+}
+
+// Une ligne par essai, dans l'ordre où ils ont été joués
+fn save_csv(results: &[StageResult], stages: &[String], path: &str) -> Result<(), String> {
+    let mut content = String::from("stage,scenario,attempt,ticks,food,deaths,passed\n");
+    for result in results {
+        content.push_str(&format!(
+            "{},{},{},{},{},{},{}\n",
+            result.stage + 1,
+            stages[result.stage],
+            result.attempt + 1,
+            result.ticks,
+            result.food,
+            result.deaths,
+            result.passed
+        ));
+    }
+    fs::write(path, content).map_err(|e| format!("Impossible d'écrire {}: {}", path, e))
+}
+
+fn parse_args(args: &[String]) -> Result<Option<CurriculumArgs>, String> {
+    let mut parsed = CurriculumArgs {
+        stages: Vec::new(),
+        attempts: 1,
+        save_brain: None,
+        output: None,
+    };
+    let mut i = 0;
+    while i < args.len() {
+        let flag = args[i].as_str();
+        if flag == "--help" || flag == "-h" {
+            print_help();
+            return Ok(None);
+        }
+        if !flag.starts_with("--") {
+            parsed.stages.push(flag.to_string());
+            i += 1;
+            continue;
+        }
+        let value = args
+            .get(i + 1)
+            .ok_or_else(|| format!("{} attend une valeur", flag))?;
+        match flag {
+            "--attempts" => {
+                parsed.attempts = value
+                    .parse()
+                    .ok()
+                    .filter(|&attempts| attempts > 0)
+                    .ok_or_else(|| {
+                        format!("Valeur invalide pour {}: {} (entier > 0)", flag, value)
+                    })?
+            }
+            "--save-brain" => parsed.save_brain = Some(value.clone()),
+            "--output" => parsed.output = Some(value.clone()),
+            other => return Err(format!("Argument inconnu: {}", other)),
+        }
+        i += 2;
+    }
+    if parsed.stages.is_empty() {
+        return Err("ants curriculum demande au moins un scénario (voir --help)".to_string());
+    }
+    Ok(Some(parsed))
+}
+
+fn print_help() {
+    println!("Usage: ants curriculum <SCENARIO>... [OPTIONS]");
+    println!();
+    println!("Entraîne la colonie A sur chaque scénario dans l'ordre, en gardant son cerveau");
+    println!("d'une carte à l'autre. La section [goal] d'un scénario fixe sa réussite :");
+    println!("  food = N               Nourriture à livrer avant max_ticks");
+    println!("  max_deaths = N         Morts tolérées (optionnel)");
+    println!(
+        "  transfer = \"scale\"     Adaptation du cerveau à la carte : scale, bilinear ou crop"
+    );
+    println!();
+    println!("OPTIONS:");
+    println!("  --attempts <N>         Essais par étape avant d'abandonner (défaut: 1)");
+    println!("  --save-brain <FILE>    Cerveau obtenu à la fin (réutilisable avec --load-brain)");
+    println!("  --output <FILE>        Résultat de chaque essai (CSV)");
+}
@@ -0,0 +1,107 @@
+//! Ensembles de colonies indépendantes sur une même carte
+//!
+//! `--ensemble K` joue K parties en parallèle (rayon) sur des copies de la même carte, chacune
+//! avec ses propres Q-tables et sa propre graine (`seed + k` avec une graine fixée). Tous les
+//! `--ensemble-sync` ticks, les Q-tables des K parties sont mises en commun (moyenne ou vote,
+//! voir `QMerge`) et chaque partie repart de la table commune : un moyen peu coûteux de
+//! stabiliser l'apprentissage tabulaire. Les pistes ACO restent propres à chaque partie.
+
+use crate::ant::Ant;
+use crate::ants_game_manager::AntsGameManager;
+use crate::batch::{self, BatchReport, RunRecord};
+use crate::cli_args::SimulationConfig;
+use crate::pheromone::{PheromoneMap, QMerge};
+use crate::runner::SimulationRunner;
+use rayon::prelude::*;
+
+/// Fin d'un ensemble : résultat de chaque partie et Q-tables communes
+pub struct EnsembleOutcome {
+    pub report: BatchReport,
+    /// Mises en commun des Q-tables effectuées
+    pub syncs: u64,
+    /// Première partie de l'ensemble, qui porte les Q-tables de la dernière mise en commun
+    pub consensus: AntsGameManager,
+}
+
+/// Joue `config.ensemble` parties en parallèle en mettant leurs Q-tables en commun tous les
+/// `config.ensemble_sync` ticks. `build` crée la première partie (sans fichier de
+/// métriques) ; les suivantes reprennent sa carte et ses Q-tables de départ avec leur propre
+/// graine.
+pub fn run_ensemble<F>(config: &SimulationConfig, build: F) -> Result<EnsembleOutcome, String>
+where
+    F: FnOnce(SimulationConfig) -> Result<AntsGameManager, String>,
+{
+    let member_config = |member: u32| batch::member_config(config, member);
+    let first = build(member_config(0))?;
+    let grid = first.grid().clone();
+    let tables: Vec<Vec<PheromoneMap>> = first
+        .colonies()
+        .iter()
+        .map(|colony| colony.active_maps().into_iter().cloned().collect())
+        .collect();
+    let others = (1..config.ensemble)
+        .into_par_iter()
+        .map(|member| {
+            let config = member_config(member);
+            let ants = Ant::colony_from_config(&config);
+            let mut manager = AntsGameManager::with_grid(grid.clone(), ants, config)
+                .map_err(|e| format!("Erreur de création de la partie: {}", e))?;
+            manager.replace_q_tables(&tables);
+            Ok(batch::member_runner(manager))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    let mut members: Vec<SimulationRunner> = std::iter::once(batch::member_runner(first))
+        .chain(others)
+        .collect();
+
+    let mut syncs = 0;
+    loop {
+        members.par_iter_mut().for_each(|member| {
+            member.run_ticks(config.ensemble_sync as u64);
+        });
+        // La dernière mise en commun donne les Q-tables de fin de l'ensemble
+        synchronize(&mut members, config.ensemble_merge);
+        syncs += 1;
+        if members.iter().all(SimulationRunner::is_done) {
+            break;
+        }
+    }
+
+    let runs = members
+        .iter()
+        .enumerate()
+        .map(|(member, runner)| RunRecord {
+            run: member as u32,
+            seed: runner.manager().seed(),
+            summary: runner.summary(),
+        })
+        .collect();
+    Ok(EnsembleOutcome {
+        report: BatchReport { runs },
+        syncs,
+        consensus: members.swap_remove(0).into_manager(),
+    })
+}
+
+// Fusionne les Q-tables de chaque colonie sur toutes les parties et les redonne à chacune
+fn synchronize(members: &mut [SimulationRunner], merge: QMerge) {
+    let colonies = members[0].manager().colonies().len();
+    let merged: Vec<Vec<PheromoneMap>> = (0..colonies)
+        .map(|colony| {
+            let tables: Vec<Vec<&PheromoneMap>> = members
+                .iter()
+                .map(|member| member.manager().colonies()[colony].active_maps())
+                .collect();
+            (0..tables[0].len())
+                .into_par_iter()
+                .map(|layer| {
+                    let maps: Vec<&PheromoneMap> = tables.iter().map(|maps| maps[layer]).collect();
+                    PheromoneMap::merged(&maps, merge)
+                })
+                .collect()
+        })
+        .collect();
+    members
+        .par_iter_mut()
+        .for_each(|member| member.manager_mut().replace_q_tables(&merged));
+}
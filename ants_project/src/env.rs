@@ -0,0 +1,119 @@
+use crate::ant::{AntsMode, AntsType};
+use crate::ants_game_manager::AntsGameManager;
+use crate::cli_args::SimulationConfig;
+use crate::error::AntsError;
+use crate::exploration;
+use crate::grid::Grid;
+use crate::observation::{EncodedObservation, ObservationBuilder};
+use crate::pheromone::Action;
+
+// État d'une fourmi tel que vu par un agent externe
+#[derive(Clone, Debug, PartialEq)]
+pub struct AntObservation {
+    // None si la fourmi n'est pas (ou plus) sur la carte
+    pub position: Option<(u32, u32)>,
+    pub colony: usize,
+    pub ant_type: AntsType,
+    pub mode: AntsMode,
+    pub carrying: u32,
+    // Vrai si la fourmi jouera au prochain step (sur la carte et sans cooldown)
+    pub ready: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Observation {
+    pub tick: u64,
+    pub ants: Vec<AntObservation>,
+    // Nourriture livrée par chaque colonie depuis le début de la partie
+    pub scores: Vec<u64>,
+    // Vecteurs par fourmi ou tenseur de la carte selon `config.observation` (None sans format)
+    pub encoded: Option<EncodedObservation>,
+}
+
+/// Environnement façon Gym : un agent externe choisit les actions des fourmis,
+/// le Q-learning intégré complète pour celles qu'il laisse à None
+pub struct AntsEnv {
+    config: SimulationConfig,
+    manager: AntsGameManager,
+}
+
+impl AntsEnv {
+    pub fn new(config: SimulationConfig) -> Result<Self, AntsError> {
+        let manager = AntsGameManager::from_config(config.clone())?;
+        Ok(AntsEnv { config, manager })
+    }
+
+    /// Actions possibles pour chaque fourmi
+    pub fn action_space() -> Vec<Action> {
+        Action::all().collect()
+    }
+
+    /// Actions que la fourmi `ant` peut jouer depuis sa case : les déplacements valides
+    /// (voir `exploration::valid_actions`, avec sa vision si elle est activée) et Stay.
+    /// Vide si la fourmi n'est pas sur la carte.
+    pub fn valid_actions(&self, ant: usize) -> Vec<Action> {
+        let Some(ant) = self.manager.ants().get(ant) else {
+            return Vec::new();
+        };
+        let Some((x, y)) = ant.position else {
+            return Vec::new();
+        };
+        let scope = if self.config.sensing { ant.scope } else { 0 };
+        let mut actions = exploration::valid_actions(x, y, self.grid(), scope);
+        actions.push(Action::Stay);
+        actions
+    }
+
+    /// Recommence une partie ; avec `config.seed` à None, la carte change à chaque appel
+    pub fn reset(&mut self) -> Result<Observation, AntsError> {
+        self.manager = AntsGameManager::from_config(self.config.clone())?;
+        Ok(self.observation())
+    }
+
+    /// Joue un tick. `actions[i]` pilote la fourmi i ; les récompenses sont indexées de même
+    /// (0 pour les fourmis qui n'ont pas agi pendant ce tick).
+    pub fn step(&mut self, actions: &[Option<Action>]) -> (Observation, Vec<f32>, bool) {
+        self.manager.game_step_with_actions(actions);
+        let rewards = self.manager.last_rewards().to_vec();
+        (self.observation(), rewards, self.is_done())
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.manager.is_game_finished()
+            || self.manager.current_tick_index() as u64 >= self.config.max_ticks
+    }
+
+    pub fn observation(&self) -> Observation {
+        Observation {
+            tick: self.manager.current_tick_index() as u64,
+            ants: self
+                .manager
+                .ants()
+                .iter()
+                .map(|ant| AntObservation {
+                    position: ant.position,
+                    colony: ant.colony,
+                    ant_type: ant.ant_type,
+                    mode: ant.mode,
+                    carrying: ant.current_charge,
+                    ready: ant.position.is_some() && ant.cooldown == 0,
+                })
+                .collect(),
+            scores: self.manager.colonies().iter().map(|c| c.score).collect(),
+            encoded: ObservationBuilder::new(self.config.observation).build(&self.manager),
+        }
+    }
+
+    /// Carte courante (murs, nourriture, zones mortelles, nids)
+    pub fn grid(&self) -> &Grid {
+        self.manager.grid()
+    }
+
+    pub fn manager(&self) -> &AntsGameManager {
+        &self.manager
+    }
+
+    pub fn manager_mut(&mut self) -> &mut AntsGameManager {
+        &mut self.manager
+    }
+}
@@ -0,0 +1,73 @@
+//! Erreurs de la bibliothèque
+//!
+//! Les constructeurs de partie et les opérations sur la grille renvoient [`AntsError`]
+//! plutôt que de paniquer, pour que l'application qui embarque le simulateur (GUI,
+//! méta-optimiseur, bindings) puisse signaler le problème et continuer.
+
+use std::fmt;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AntsError {
+    /// Paramètre de configuration hors de son domaine
+    InvalidConfig(String),
+    /// Grille sans aucune case (largeur ou hauteur nulle)
+    EmptyGrid { width: u32, height: u32 },
+    /// Case placée en dehors de la grille
+    TileOutOfBounds {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
+    /// La carte ne contient aucun nid
+    MissingNest,
+    /// La carte ne contient pas de nid pour cette colonie
+    MissingColonyNest(usize),
+    /// Les nids ne peuvent être ni ajoutés ni modifiés en cours de partie
+    ProtectedNest { x: u32, y: u32 },
+    /// Une partie enregistrée ou rejouée ne peut pas changer de carte
+    RecordedGame,
+}
+
+impl fmt::Display for AntsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AntsError::InvalidConfig(message) => write!(f, "{}", message),
+            AntsError::EmptyGrid { width, height } => {
+                write!(f, "Grille {}x{} sans aucune case", width, height)
+            }
+            AntsError::TileOutOfBounds {
+                x,
+                y,
+                width,
+                height,
+            } => write!(
+                f,
+                "Case ({}, {}) hors de la grille {}x{}",
+                x, y, width, height
+            ),
+            AntsError::MissingNest => write!(f, "La carte ne contient aucun nid"),
+            AntsError::MissingColonyNest(colony) => {
+                write!(f, "Aucun nid pour la colonie {}", colony)
+            }
+            AntsError::ProtectedNest { x, y } => write!(
+                f,
+                "Case ({}, {}) : les nids ne peuvent être ni ajoutés ni modifiés en cours de partie",
+                x, y
+            ),
+            AntsError::RecordedGame => write!(
+                f,
+                "Une partie enregistrée ou rejouée ne peut pas être modifiée"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AntsError {}
+
+// Les fonctions qui signalent leurs erreurs par message peuvent propager une AntsError avec `?`
+impl From<AntsError> for String {
+    fn from(error: AntsError) -> Self {
+        error.to_string()
+    }
+}
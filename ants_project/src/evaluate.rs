@@ -0,0 +1,277 @@
+//! Évaluation d'un cerveau entraîné sur une série de cartes
+//!
+//! `ants evaluate --brain brain.json --maps cartes/ --runs-per-map 5` joue le cerveau sur
+//! chaque carte JSON du dossier, sans exploration (epsilon = 0) et sans apprentissage
+//! (`freeze`) : on mesure ce que la politique apprise sait faire sur des cartes
+//! qu'elle n'a pas forcément vues, et non la vitesse à laquelle elle apprend. Le cerveau est
+//! installé dans la colonie A et adapté à la taille de chaque carte (`brain_resample`).
+
+use crate::ant::Ant;
+use crate::ants_game_manager::AntsGameManager;
+use crate::batch::{self, BatchReport};
+use crate::cli_args::SimulationConfig;
+use crate::exploration::ExplorationPolicy;
+use crate::map_editor::MapEditor;
+use crate::q_learning_math::DecayConfig;
+use std::fs;
+use std::path::Path;
+
+// Durée maximale d'une partie sans --max-ticks
+const DEFAULT_MAX_TICKS: u64 = 5000;
+
+struct EvaluateArgs {
+    brain: String,
+    maps: String,
+    runs_per_map: u32,
+    // Population, récompenses, ... (défaut : configuration par défaut)
+    config: Option<String>,
+    max_ticks: u64,
+    // Graine de la première partie de chaque carte, les suivantes utilisent seed + 1, ...
+    seed: u64,
+    // Scores de chaque carte (CSV)
+    output: Option<String>,
+}
+
+/// Résultats des parties jouées sur une carte
+#[derive(Clone, Debug)]
+pub struct MapScore {
+    pub map: String,
+    pub report: BatchReport,
+}
+
+// Point d'entrée de `ants evaluate` : `args` ne contient que les options de la sous-commande
+pub fn run(args: &[String]) -> Result<(), String> {
+    let Some(args) = parse_args(args)? else {
+        return Ok(());
+    };
+    let base = match &args.config {
+        Some(path) => SimulationConfig::load(path)?,
+        None => SimulationConfig::default(),
+    };
+    let config = evaluation_config(base, &args);
+    config
+        .validate()
+        .map_err(|e| format!("Configuration invalide: {}", e))?;
+    let maps = list_maps(&args.maps)?;
+
+    println!(
+        "Évaluation de {} sur {} carte(s), {} partie(s) par carte",
+        args.brain,
+        maps.len(),
+        args.runs_per_map
+    );
+    let scores = maps
+        .iter()
+        .map(|path| {
+            let map = MapEditor::load_from_file(path)
+                .map_err(|e| format!("Erreur de chargement de la carte {}: {}", path, e))?;
+            let report = batch::run_batch(&config, args.runs_per_map, |config| {
+                build_game(&map, config, &args.brain)
+            })
+            .map_err(|e| format!("{}: {}", path, e))?;
+            Ok(MapScore {
+                map: map_name(path),
+                report,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    print_report(&scores);
+    if let Some(path) = &args.output {
+        save_csv(&scores, path)?;
+    }
+    Ok(())
+}
+
+// Politique gloutonne et Q-tables figées, quelle que soit la configuration de départ
+fn evaluation_config(base: SimulationConfig, args: &EvaluateArgs) -> SimulationConfig {
+    SimulationConfig {
+        seed: Some(args.seed),
+        max_ticks: args.max_ticks,
+        epsilon: 0.0,
+        night_epsilon_boost: 0.0,
+        epsilon_decay: DecayConfig::default(),
+        exploration_policy: ExplorationPolicy::EpsilonGreedy,
+        freeze: true,
+        experience_buffer: 0,
+        use_gui: false,
+        output_file: None,
+        ..base
+    }
+}
+
+fn build_game(
+    map: &MapEditor,
+    config: SimulationConfig,
+    brain: &str,
+) -> Result<AntsGameManager, String> {
+    let mut manager = AntsGameManager::new(
+        map.width,
+        map.height,
+        map.to_tiles(),
+        vec![],
+        config.clone(),
+    )
+    .map_err(|e| format!("Erreur de création de la partie: {}", e))?;
+    manager.set_ants(Ant::colony_from_config(&config));
+    manager
+        .import_brain(brain)
+        .map_err(|e| format!("Erreur de chargement du cerveau: {}", e))?;
+    Ok(manager)
+}
+
+// Cartes JSON du dossier, dans l'ordre alphabétique
+fn list_maps(dir: &str) -> Result<Vec<String>, String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("Impossible de lire {}: {}", dir, e))?;
+    let mut maps: Vec<String> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "json"))
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+    if maps.is_empty() {
+        return Err(format!("Aucune carte JSON dans {}", dir));
+    }
+    maps.sort();
+    Ok(maps)
+}
+
+fn map_name(path: &str) -> String {
+    Path::new(path).file_stem().map_or_else(
+        || path.to_string(),
+        |stem| stem.to_string_lossy().into_owned(),
+    )
+}
+
+fn print_report(scores: &[MapScore]) {
+    println!(
+        "{:<20} {:>12} {:>10} {:>10} {:>10} {:>10}",
+        "carte", "nourriture", "±", "ticks", "morts", "regret"
+    );
+    for score in scores {
+        let report = &score.report;
+        let food = report.food_delivered();
+        println!(
+            "{:<20} {:>12.1} {:>10.1} {:>10.1} {:>10.1} {:>10}",
+            score.map,
+            food.mean,
+            food.stddev,
+            report.ticks().mean,
+            report.deaths().mean,
+            report
+                .trip_regret()
+                .map_or("-".to_string(), |regret| format!("{:.1}", regret.mean))
+        );
+    }
+    let mean_food = scores
+        .iter()
+        .map(|score| score.report.food_delivered().mean)
+        .sum::<f64>()
+        / scores.len().max(1) as f64;
+    println!(
+        "Nourriture livrée en moyenne sur les {} carte(s) : {:.1}",
+        scores.len(),
+        mean_food
+    );
+}
+
+// Une ligne par carte ; le regret est vide si aucune partie n'a terminé d'aller-retour
+fn save_csv(scores: &[MapScore], path: &str) -> Result<(), String> {
+    let mut content = String::from(
+        "map,runs,food_mean,food_stddev,food_min,food_max,ticks_mean,deaths_mean,trip_regret_mean\n",
+    );
+    for score in scores {
+        let report = &score.report;
+        let food = report.food_delivered();
+        content.push_str(&format!(
+            "{},{},{},{},{},{},{},{},{}\n",
+            score.map,
+            report.runs.len(),
+            food.mean,
+            food.stddev,
+            food.min,
+            food.max,
+            report.ticks().mean,
+            report.deaths().mean,
+            report
+                .trip_regret()
+                .map(|regret| regret.mean.to_string())
+                .unwrap_or_default()
+        ));
+    }
+    fs::write(path, content).map_err(|e| format!("Impossible d'écrire {}: {}", path, e))
+}
+
+fn parse_args(args: &[String]) -> Result<Option<EvaluateArgs>, String> {
+    let mut brain = None;
+    let mut maps = None;
+    let mut parsed = EvaluateArgs {
+        brain: String::new(),
+        maps: String::new(),
+        runs_per_map: 5,
+        config: None,
+        max_ticks: DEFAULT_MAX_TICKS,
+        seed: 0,
+        output: None,
+    };
+    let mut i = 0;
+    while i < args.len() {
+        let flag = args[i].as_str();
+        if flag == "--help" || flag == "-h" {
+            print_help();
+            return Ok(None);
+        }
+        let value = args
+            .get(i + 1)
+            .ok_or_else(|| format!("{} attend une valeur", flag))?;
+        let number =
+            |expected: &str| format!("Valeur invalide pour {}: {} ({})", flag, value, expected);
+        match flag {
+            "--brain" => brain = Some(value.clone()),
+            "--maps" => maps = Some(value.clone()),
+            "--runs-per-map" => {
+                parsed.runs_per_map = value
+                    .parse()
+                    .ok()
+                    .filter(|&runs| runs > 0)
+                    .ok_or_else(|| number("entier > 0"))?
+            }
+            "--config" => parsed.config = Some(value.clone()),
+            "--max-ticks" => {
+                parsed.max_ticks = value
+                    .parse()
+                    .ok()
+                    .filter(|&ticks| ticks > 0)
+                    .ok_or_else(|| number("entier > 0"))?
+            }
+            "--seed" => parsed.seed = value.parse().map_err(|_| number("entier"))?,
+            "--output" => parsed.output = Some(value.clone()),
+            other => return Err(format!("Argument inconnu: {}", other)),
+        }
+        i += 2;
+    }
+    let missing = |flag: &str| format!("ants evaluate demande {} (voir --help)", flag);
+    parsed.brain = brain.ok_or_else(|| missing("--brain"))?;
+    parsed.maps = maps.ok_or_else(|| missing("--maps"))?;
+    Ok(Some(parsed))
+}
+
+fn print_help() {
+    println!("Usage: ants evaluate --brain <FILE> --maps <DIR> [OPTIONS]");
+    println!();
+    println!("Joue un cerveau entraîné (format de --save-brain) sur chaque carte JSON d'un");
+    println!("dossier, sans exploration ni apprentissage, et affiche le score de chaque carte.");
+    println!();
+    println!("OPTIONS:");
+    println!("  --brain <FILE>         Cerveau à évaluer, installé dans la colonie A");
+    println!("  --maps <DIR>           Dossier des cartes JSON de l'éditeur");
+    println!("  --runs-per-map <N>     Parties jouées sur chaque carte (défaut: 5)");
+    println!(
+        "  --config <FILE>        Configuration TOML de la partie (population, récompenses...)"
+    );
+    println!(
+        "  --max-ticks <N>        Durée maximale d'une partie (défaut: {})",
+        DEFAULT_MAX_TICKS
+    );
+    println!("  --seed <N>             Graine de la première partie de chaque carte, puis N+1... (défaut: 0)");
+    println!("  --output <FILE>        Scores de chaque carte (CSV)");
+}
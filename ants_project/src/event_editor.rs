@@ -0,0 +1,560 @@
+//! Éditeur de la chronologie d'un scénario
+//!
+//! Troisième écran d'édition de la GUI, après les dimensions et la carte : les événements
+//! scriptés (nourriture qui apparaît, case qui devient mortelle ou redevient sûre, vague de
+//! prédateurs) se posent d'un clic sur une frise graduée en ticks, se déplacent en les
+//! faisant glisser et se règlent un par un. Sur une carte dessinée, un clic sur l'aperçu
+//! choisit la case visée. La chronologie s'enregistre dans un fichier `.scenario` avec la
+//! configuration et la carte en cours : un scénario dynamique se prépare sans écrire de TOML.
+
+use crate::cli_args::SimulationConfig;
+use crate::i18n::{tr, trf};
+use crate::map_editor::MapEditor;
+use crate::scenario::{EventKind, Scenario, ScenarioEvent};
+use eframe::egui;
+use std::collections::HashMap;
+
+// Hauteur de la frise, en points
+const TIMELINE_HEIGHT: f32 = 64.0;
+// Distance (en points) sous laquelle un clic attrape un événement de la frise
+const GRAB_DISTANCE: f32 = 7.0;
+// Côté maximal de l'aperçu de la carte, en points
+const PREVIEW_SIZE: f32 = 320.0;
+// Durée de la frise quand la partie n'a pas de limite de ticks à l'échelle humaine
+const DEFAULT_HORIZON: usize = 5000;
+// Réglages des événements posés sur la frise
+const DEFAULT_FOOD_AMOUNT: u32 = 200;
+const DEFAULT_WAVE_SIZE: u32 = 3;
+
+/// Type de l'événement posé par un clic sur la frise
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventType {
+    Food,
+    HazardOn,
+    HazardOff,
+    PredatorWave,
+}
+
+impl EventType {
+    pub fn all() -> [EventType; 4] {
+        [
+            EventType::Food,
+            EventType::HazardOn,
+            EventType::HazardOff,
+            EventType::PredatorWave,
+        ]
+    }
+
+    pub fn of(kind: &EventKind) -> Self {
+        match kind {
+            EventKind::Food { .. } => EventType::Food,
+            EventKind::Hazard { lethal: true, .. } => EventType::HazardOn,
+            EventKind::Hazard { lethal: false, .. } => EventType::HazardOff,
+            EventKind::PredatorWave { .. } => EventType::PredatorWave,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            EventType::Food => tr("events.food"),
+            EventType::HazardOn => tr("events.hazard_on"),
+            EventType::HazardOff => tr("events.hazard_off"),
+            EventType::PredatorWave => tr("events.predator_wave"),
+        }
+    }
+
+    fn color(&self) -> egui::Color32 {
+        match self {
+            EventType::Food => egui::Color32::from_rgb(80, 200, 80),
+            EventType::HazardOn => egui::Color32::from_rgb(220, 60, 60),
+            EventType::HazardOff => egui::Color32::from_rgb(230, 160, 50),
+            EventType::PredatorWave => egui::Color32::from_rgb(170, 90, 230),
+        }
+    }
+}
+
+/// État de l'écran : chronologie en cours d'édition et réglages du prochain événement
+pub struct EventEditor {
+    /// Événements dans l'ordre de création (voir `sorted_events` pour l'ordre des ticks)
+    pub events: Vec<ScenarioEvent>,
+    /// Dernier tick couvert par la frise
+    pub horizon: usize,
+    // Taille de la carte, qui borne les coordonnées des événements
+    width: u32,
+    height: u32,
+    pub placing: EventType,
+    /// Case visée par les prochains événements (dernier clic sur l'aperçu)
+    pub cell: (u32, u32),
+    /// Événement réglé dans le panneau de détail (indice dans `events`)
+    pub selected: Option<usize>,
+    // Événement déplacé à la souris sur la frise
+    dragging: Option<usize>,
+    // Chemin utilisé par les boutons Sauvegarder / Charger
+    pub file_path: String,
+    pub file_status: Option<String>,
+}
+
+impl EventEditor {
+    /// Éditeur ouvert sur `events`, pour une carte `width` x `height` jouée `max_ticks` ticks
+    pub fn new(events: Vec<ScenarioEvent>, width: u32, height: u32, max_ticks: u64) -> Self {
+        let mut editor = EventEditor {
+            events: Vec::new(),
+            horizon: (max_ticks as usize).clamp(1, DEFAULT_HORIZON),
+            width: width.max(1),
+            height: height.max(1),
+            placing: EventType::Food,
+            cell: (0, 0),
+            selected: None,
+            dragging: None,
+            file_path: "chronologie.scenario".to_string(),
+            file_status: None,
+        };
+        editor.set_events(events);
+        editor
+    }
+
+    // Remplace la chronologie et allonge la frise pour la montrer en entier
+    fn set_events(&mut self, events: Vec<ScenarioEvent>) {
+        let last = events.iter().map(|event| event.tick).max().unwrap_or(0);
+        self.horizon = self.horizon.max(last + last / 10);
+        self.events = events;
+        self.selected = None;
+        self.dragging = None;
+    }
+
+    /// Pose un événement du type `placing` au tick `tick`, sur la case `cell`, et le sélectionne
+    pub fn add(&mut self, tick: usize) {
+        let (x, y) = self.cell;
+        let kind = match self.placing {
+            EventType::Food => EventKind::Food {
+                x,
+                y,
+                amount: DEFAULT_FOOD_AMOUNT,
+            },
+            EventType::HazardOn => EventKind::Hazard { x, y, lethal: true },
+            EventType::HazardOff => EventKind::Hazard {
+                x,
+                y,
+                lethal: false,
+            },
+            EventType::PredatorWave => EventKind::PredatorWave {
+                count: DEFAULT_WAVE_SIZE,
+            },
+        };
+        self.events.push(ScenarioEvent { tick, kind });
+        self.selected = Some(self.events.len() - 1);
+    }
+
+    pub fn remove(&mut self, index: usize) {
+        self.events.remove(index);
+        self.selected = None;
+        self.dragging = None;
+    }
+
+    /// Chronologie triée par tick, comme l'attend la configuration ; deux événements d'un
+    /// même tick gardent leur ordre de création
+    pub fn sorted_events(&self) -> Vec<ScenarioEvent> {
+        self.order().into_iter().map(|i| self.events[i]).collect()
+    }
+
+    // Indices des événements dans l'ordre des ticks
+    fn order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.events.len()).collect();
+        order.sort_by_key(|&i| self.events[i].tick);
+        order
+    }
+
+    /// Scénario complet : `config` (ses propres événements exceptés), la carte dessinée s'il
+    /// y en a une et la chronologie de l'éditeur
+    pub fn to_scenario(&self, config: &SimulationConfig, map: Option<&MapEditor>) -> Scenario {
+        Scenario {
+            config: SimulationConfig {
+                events: Vec::new(),
+                ..config.clone()
+            },
+            map: map.map(MapEditor::to_map_file),
+            events: self.sorted_events(),
+            goal: None,
+        }
+    }
+
+    /// Reprend la chronologie d'un scénario ; sa configuration et sa carte sont ignorées
+    pub fn load_scenario(&mut self, scenario: Scenario) -> usize {
+        self.set_events(scenario.config.events);
+        self.events.len()
+    }
+}
+
+// Case visée par un événement, None pour une vague de prédateurs
+fn target(kind: &EventKind) -> Option<(u32, u32)> {
+    match *kind {
+        EventKind::Food { x, y, .. } | EventKind::Hazard { x, y, .. } => Some((x, y)),
+        EventKind::PredatorWave { .. } => None,
+    }
+}
+
+fn describe(kind: &EventKind) -> String {
+    match *kind {
+        EventKind::Food { x, y, amount } => trf("events.food_at", &[&amount, &x, &y]),
+        EventKind::Hazard { x, y, lethal } => trf(
+            if lethal {
+                "events.hazard_on_at"
+            } else {
+                "events.hazard_off_at"
+            },
+            &[&x, &y],
+        ),
+        EventKind::PredatorWave { count } => trf("events.wave_of", &[&count]),
+    }
+}
+
+/// Écran complet : type posé par un clic, frise, détail de l'événement choisi, liste et
+/// aperçu de la carte `map` (None pour une carte aléatoire), puis fichier du scénario
+pub fn show_event_editor(
+    ui: &mut egui::Ui,
+    editor: &mut EventEditor,
+    map: Option<&MapEditor>,
+    config: &SimulationConfig,
+) {
+    ui.horizontal_wrapped(|ui| {
+        ui.label(tr("events.placing"));
+        for event_type in EventType::all() {
+            ui.selectable_value(
+                &mut editor.placing,
+                event_type,
+                egui::RichText::new(event_type.label()).color(event_type.color()),
+            );
+        }
+        ui.separator();
+        ui.label(tr("events.horizon"));
+        ui.add(egui::DragValue::new(&mut editor.horizon).range(10..=usize::MAX));
+    });
+    show_timeline(ui, editor);
+    ui.label(trf("events.count", &[&editor.events.len()]));
+    ui.separator();
+
+    ui.columns(2, |columns| {
+        show_selected(&mut columns[0], editor);
+        columns[0].separator();
+        show_list(&mut columns[0], editor);
+        match map {
+            Some(map) => show_map_preview(&mut columns[1], editor, map),
+            None => {
+                columns[1].label(tr("events.random_map"));
+            }
+        }
+    });
+    ui.separator();
+
+    show_file_bar(ui, editor, map, config);
+    if let Some(status) = &editor.file_status {
+        ui.label(status);
+    }
+}
+
+// Frise graduée en ticks : un clic pose un événement ou sélectionne celui qu'il touche,
+// un glisser déplace l'événement attrapé
+fn show_timeline(ui: &mut egui::Ui, editor: &mut EventEditor) {
+    let (response, painter) = ui.allocate_painter(
+        egui::vec2(ui.available_width(), TIMELINE_HEIGHT),
+        egui::Sense::click_and_drag(),
+    );
+    let rect = response.rect;
+    // Marge pour que les événements des deux extrémités restent entiers
+    let bar = rect.shrink2(egui::vec2(2.0 * GRAB_DISTANCE, 0.0));
+    let axis_y = bar.bottom() - 16.0;
+    let horizon = editor.horizon.max(1);
+    let x_of = |tick: usize| bar.left() + bar.width() * tick.min(horizon) as f32 / horizon as f32;
+    let tick_at = |x: f32| ((x - bar.left()) / bar.width()).clamp(0.0, 1.0) * horizon as f32;
+
+    painter.rect_filled(rect, 4.0, egui::Color32::from_gray(30));
+    let axis = egui::Stroke::new(1.0, egui::Color32::GRAY);
+    painter.hline(bar.x_range(), axis_y, axis);
+    for step in 0..=5 {
+        let tick = horizon * step / 5;
+        let x = x_of(tick);
+        painter.vline(x, axis_y - 3.0..=axis_y + 3.0, axis);
+        painter.text(
+            egui::pos2(x, axis_y + 3.0),
+            egui::Align2::CENTER_TOP,
+            tick.to_string(),
+            egui::FontId::proportional(10.0),
+            egui::Color32::GRAY,
+        );
+    }
+
+    // Les événements d'un même tick s'empilent au-dessus de l'axe
+    let mut stacked: HashMap<usize, usize> = HashMap::new();
+    let markers: Vec<(usize, egui::Pos2)> = editor
+        .order()
+        .into_iter()
+        .map(|i| {
+            let tick = editor.events[i].tick;
+            let level = stacked.entry(tick).or_default();
+            let center = egui::pos2(x_of(tick), axis_y - 9.0 - *level as f32 * 11.0);
+            *level += 1;
+            (i, center)
+        })
+        .collect();
+    for &(i, center) in &markers {
+        let color = EventType::of(&editor.events[i].kind).color();
+        painter.circle_filled(center, 5.0, color);
+        if editor.selected == Some(i) {
+            painter.circle_stroke(center, 7.0, egui::Stroke::new(2.0, egui::Color32::WHITE));
+        }
+    }
+
+    let grabbed = |pos: egui::Pos2| {
+        markers
+            .iter()
+            .map(|&(i, center)| (i, center.distance(pos)))
+            .filter(|&(_, distance)| distance <= GRAB_DISTANCE)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(i, _)| i)
+    };
+    if response.drag_started() {
+        editor.dragging = response.interact_pointer_pos().and_then(grabbed);
+        if editor.dragging.is_some() {
+            editor.selected = editor.dragging;
+        }
+    }
+    if let (Some(i), Some(pos)) = (editor.dragging, response.interact_pointer_pos()) {
+        if response.dragged() {
+            editor.events[i].tick = tick_at(pos.x).round() as usize;
+        }
+    }
+    if response.drag_stopped() {
+        editor.dragging = None;
+    }
+    if response.clicked() {
+        if let Some(pos) = response.interact_pointer_pos() {
+            match grabbed(pos) {
+                Some(i) => editor.selected = Some(i),
+                None => editor.add(tick_at(pos.x).round() as usize),
+            }
+        }
+    }
+
+    if let Some(pos) = response.hover_pos() {
+        painter.vline(
+            pos.x,
+            rect.y_range(),
+            egui::Stroke::new(1.0, egui::Color32::from_white_alpha(40)),
+        );
+        painter.text(
+            egui::pos2(pos.x + 4.0, rect.top() + 2.0),
+            egui::Align2::LEFT_TOP,
+            trf("events.tick", &[&(tick_at(pos.x).round() as usize)]),
+            egui::FontId::proportional(11.0),
+            egui::Color32::LIGHT_GRAY,
+        );
+    }
+    response.on_hover_text(tr("events.timeline_hint"));
+}
+
+// Réglages de l'événement sélectionné
+fn show_selected(ui: &mut egui::Ui, editor: &mut EventEditor) {
+    let Some(index) = editor.selected.filter(|&i| i < editor.events.len()) else {
+        ui.label(tr("events.none_selected"));
+        return;
+    };
+    let (max_x, max_y) = (editor.width - 1, editor.height - 1);
+    let event = &mut editor.events[index];
+    let event_type = EventType::of(&event.kind);
+    ui.label(
+        egui::RichText::new(event_type.label())
+            .strong()
+            .color(event_type.color()),
+    );
+    ui.horizontal(|ui| {
+        ui.label(tr("events.at_tick"));
+        ui.add(egui::DragValue::new(&mut event.tick));
+    });
+    match &mut event.kind {
+        EventKind::Food { x, y, amount } => {
+            show_cell(ui, x, y, max_x, max_y);
+            ui.horizontal(|ui| {
+                ui.label(tr("events.amount"));
+                ui.add(egui::DragValue::new(amount).range(1..=1_000_000));
+            });
+        }
+        EventKind::Hazard { x, y, lethal } => {
+            show_cell(ui, x, y, max_x, max_y);
+            ui.checkbox(lethal, tr("events.lethal"));
+        }
+        EventKind::PredatorWave { count } => {
+            ui.horizontal(|ui| {
+                ui.label(tr("events.wave_size"));
+                ui.add(egui::DragValue::new(count).range(1..=100));
+            });
+        }
+    }
+    if let Some(cell) = target(&event.kind) {
+        editor.cell = cell;
+    }
+    if ui.button(tr("events.delete")).clicked() {
+        editor.remove(index);
+    }
+}
+
+fn show_cell(ui: &mut egui::Ui, x: &mut u32, y: &mut u32, max_x: u32, max_y: u32) {
+    ui.horizontal(|ui| {
+        ui.label(tr("events.cell"));
+        ui.add(egui::DragValue::new(x).range(0..=max_x).prefix("x "));
+        ui.add(egui::DragValue::new(y).range(0..=max_y).prefix("y "));
+    });
+}
+
+// Événements dans l'ordre des ticks ; un clic sélectionne
+fn show_list(ui: &mut egui::Ui, editor: &mut EventEditor) {
+    egui::ScrollArea::vertical()
+        .id_salt("scenario_events")
+        .max_height(220.0)
+        .show(ui, |ui| {
+            for i in editor.order() {
+                let event = editor.events[i];
+                let text =
+                    egui::RichText::new(format!("{:>7}  {}", event.tick, describe(&event.kind)))
+                        .color(EventType::of(&event.kind).color())
+                        .monospace();
+                if ui
+                    .selectable_label(editor.selected == Some(i), text)
+                    .clicked()
+                {
+                    editor.selected = Some(i);
+                }
+            }
+        });
+}
+
+// Carte dessinée et cases visées par les événements ; un clic choisit la case des prochains
+// événements et déplace l'événement sélectionné
+fn show_map_preview(ui: &mut egui::Ui, editor: &mut EventEditor, map: &MapEditor) {
+    let cell_size = (PREVIEW_SIZE / map.width.max(map.height).max(1) as f32).clamp(2.0, 24.0);
+    let (response, painter) = ui.allocate_painter(
+        egui::vec2(map.width as f32 * cell_size, map.height as f32 * cell_size),
+        egui::Sense::click(),
+    );
+    let origin = response.rect.min;
+    let cell_rect = |(x, y): (u32, u32)| {
+        egui::Rect::from_min_size(
+            origin + egui::vec2(x as f32 * cell_size, y as f32 * cell_size),
+            egui::Vec2::splat(cell_size),
+        )
+    };
+    for y in 0..map.height {
+        for x in 0..map.width {
+            painter.rect_filled(
+                cell_rect((x, y)),
+                0.0,
+                map.tiles[y as usize][x as usize].color(),
+            );
+        }
+    }
+    for (i, event) in editor.events.iter().enumerate() {
+        let Some(cell) = target(&event.kind) else {
+            continue;
+        };
+        let center = cell_rect(cell).center();
+        painter.circle_filled(
+            center,
+            (cell_size * 0.35).max(1.5),
+            EventType::of(&event.kind).color(),
+        );
+        if editor.selected == Some(i) {
+            painter.circle_stroke(
+                center,
+                (cell_size * 0.5).max(3.0),
+                egui::Stroke::new(2.0, egui::Color32::WHITE),
+            );
+        }
+    }
+    painter.rect_stroke(
+        cell_rect(editor.cell),
+        0.0,
+        egui::Stroke::new(1.5, egui::Color32::WHITE),
+        egui::StrokeKind::Inside,
+    );
+
+    if response.clicked() {
+        if let Some(pos) = response.interact_pointer_pos() {
+            let offset = (pos - origin) / cell_size;
+            let cell = (
+                (offset.x.max(0.0) as u32).min(map.width.saturating_sub(1)),
+                (offset.y.max(0.0) as u32).min(map.height.saturating_sub(1)),
+            );
+            editor.cell = cell;
+            if let Some(event) = editor.selected.and_then(|i| editor.events.get_mut(i)) {
+                if let EventKind::Food { x, y, .. } | EventKind::Hazard { x, y, .. } =
+                    &mut event.kind
+                {
+                    (*x, *y) = cell;
+                }
+            }
+        }
+    }
+    response.on_hover_text(tr("events.preview_hint"));
+}
+
+// Sauvegarde et chargement du scénario dans un fichier
+#[cfg(not(target_arch = "wasm32"))]
+fn show_file_bar(
+    ui: &mut egui::Ui,
+    editor: &mut EventEditor,
+    map: Option<&MapEditor>,
+    config: &SimulationConfig,
+) {
+    ui.horizontal(|ui| {
+        ui.label(tr("editor.file"));
+        ui.text_edit_singleline(&mut editor.file_path);
+        if ui.button(tr("editor.save")).clicked() {
+            let scenario = editor.to_scenario(config, map);
+            editor.file_status = Some(match scenario.save(&editor.file_path) {
+                Ok(()) => trf("events.saved", &[&editor.file_path]),
+                Err(e) => e,
+            });
+        }
+        if ui.button(tr("editor.load")).clicked() {
+            editor.file_status = Some(match Scenario::load(&editor.file_path) {
+                Ok(scenario) => {
+                    let count = editor.load_scenario(scenario);
+                    trf("events.loaded", &[&count, &editor.file_path])
+                }
+                Err(e) => e,
+            });
+        }
+    });
+}
+
+// Pas de système de fichiers dans le navigateur : le scénario passe par le presse-papiers,
+// et le champ texte reçoit le TOML collé
+#[cfg(target_arch = "wasm32")]
+fn show_file_bar(
+    ui: &mut egui::Ui,
+    editor: &mut EventEditor,
+    map: Option<&MapEditor>,
+    config: &SimulationConfig,
+) {
+    ui.horizontal(|ui| {
+        ui.label(tr("events.toml"));
+        ui.text_edit_singleline(&mut editor.file_path);
+        if ui.button(tr("editor.copy")).clicked() {
+            editor.file_status = Some(match editor.to_scenario(config, map).to_toml() {
+                Ok(toml) => {
+                    ui.ctx().copy_text(toml);
+                    tr("events.copied").to_string()
+                }
+                Err(e) => e,
+            });
+        }
+        if ui.button(tr("editor.paste")).clicked() {
+            editor.file_status = Some(match Scenario::from_toml(&editor.file_path) {
+                Ok(scenario) => {
+                    let count = editor.load_scenario(scenario);
+                    trf("events.pasted", &[&count])
+                }
+                Err(e) => e,
+            });
+        }
+    });
+}
@@ -0,0 +1,333 @@
+//! Journal d'événements structuré
+//!
+//! La simulation émet ses événements avec `tracing` : chacun porte un `kind` (naissance,
+//! mort, livraison, ...), le `tick`, l'`ant_id` (`Ant::id`) et la case `x`/`y`
+//! quand ils ont un sens, plus un message lisible. `EventLog` est un subscriber minimal qui
+//! garde les derniers événements en mémoire pour le panneau Journal de la GUI et peut les
+//! recopier sur la sortie d'erreur en mode CLI. La verbosité se règle avec `--log-level`.
+
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex, OnceLock};
+use tracing::field::{Field, Visit};
+use tracing::level_filters::LevelFilter;
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+// Événements gardés en mémoire au maximum
+const LOG_CAPACITY: usize = 5000;
+
+/// Verbosité du journal, de la plus discrète à la plus bavarde
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum LogLevel {
+    // Échecs d'écriture de fichiers
+    Error,
+    // Saisies refusées
+    Warn,
+    // Morts, naissances, prédateurs, zones et événements de scénario, checkpoints
+    Info,
+    // Sorties du nid, ramassages et livraisons de nourriture
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub fn all() -> impl Iterator<Item = LogLevel> {
+        [
+            LogLevel::Error,
+            LogLevel::Warn,
+            LogLevel::Info,
+            LogLevel::Debug,
+            LogLevel::Trace,
+        ]
+        .iter()
+        .copied()
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "error" => Some(LogLevel::Error),
+            "warn" => Some(LogLevel::Warn),
+            "info" => Some(LogLevel::Info),
+            "debug" => Some(LogLevel::Debug),
+            "trace" => Some(LogLevel::Trace),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LogLevel::Error => "ERREUR",
+            LogLevel::Warn => "ALERTE",
+            LogLevel::Info => "INFO",
+            LogLevel::Debug => "DEBUG",
+            LogLevel::Trace => "TRACE",
+        }
+    }
+
+    fn from_tracing(level: &Level) -> Self {
+        match *level {
+            Level::ERROR => LogLevel::Error,
+            Level::WARN => LogLevel::Warn,
+            Level::INFO => LogLevel::Info,
+            Level::DEBUG => LogLevel::Debug,
+            Level::TRACE => LogLevel::Trace,
+        }
+    }
+
+    fn filter(&self) -> LevelFilter {
+        match self {
+            LogLevel::Error => LevelFilter::ERROR,
+            LogLevel::Warn => LevelFilter::WARN,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Trace => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// Nature d'un événement, d'après son champ `kind`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LogKind {
+    Spawn,
+    Pickup,
+    Delivery,
+    Death,
+    Birth,
+    Predator,
+    Hazard,
+    Scenario,
+    Checkpoint,
+    // Tout le reste : erreurs d'entrée/sortie, fin de partie, ...
+    System,
+}
+
+impl LogKind {
+    pub fn all() -> impl Iterator<Item = LogKind> {
+        [
+            LogKind::Spawn,
+            LogKind::Pickup,
+            LogKind::Delivery,
+            LogKind::Death,
+            LogKind::Birth,
+            LogKind::Predator,
+            LogKind::Hazard,
+            LogKind::Scenario,
+            LogKind::Checkpoint,
+            LogKind::System,
+        ]
+        .iter()
+        .copied()
+    }
+
+    /// Valeur du champ `kind` dans les macros de `tracing`
+    pub fn name(&self) -> &'static str {
+        match self {
+            LogKind::Spawn => "spawn",
+            LogKind::Pickup => "pickup",
+            LogKind::Delivery => "delivery",
+            LogKind::Death => "death",
+            LogKind::Birth => "birth",
+            LogKind::Predator => "predator",
+            LogKind::Hazard => "hazard",
+            LogKind::Scenario => "scenario",
+            LogKind::Checkpoint => "checkpoint",
+            LogKind::System => "system",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        LogKind::all().find(|kind| kind.name() == name)
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LogKind::Spawn => tr("log_kind.spawn"),
+            LogKind::Pickup => tr("log_kind.pickup"),
+            LogKind::Delivery => tr("log_kind.delivery"),
+            LogKind::Death => tr("log_kind.death"),
+            LogKind::Birth => tr("log_kind.birth"),
+            LogKind::Predator => tr("log_kind.predator"),
+            LogKind::Hazard => tr("log_kind.hazard"),
+            LogKind::Scenario => tr("log_kind.scenario"),
+            LogKind::Checkpoint => tr("log_kind.checkpoint"),
+            LogKind::System => tr("log_kind.system"),
+        }
+    }
+}
+
+/// Événement reçu par le journal
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    pub level: LogLevel,
+    pub kind: LogKind,
+    pub tick: Option<u64>,
+    pub ant_id: Option<u64>,
+    pub position: Option<(u32, u32)>,
+    pub message: String,
+}
+
+impl fmt::Display for LogEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.level.label(), self.kind.name())?;
+        if let Some(tick) = self.tick {
+            write!(f, " tick={}", tick)?;
+        }
+        if let Some(ant) = self.ant_id {
+            write!(f, " ant={}", ant)?;
+        }
+        if let Some((x, y)) = self.position {
+            write!(f, " ({}, {})", x, y)?;
+        }
+        write!(f, " {}", self.message)
+    }
+}
+
+// Relève les champs connus d'un événement `tracing`
+#[derive(Default)]
+struct EntryFields {
+    kind: Option<LogKind>,
+    tick: Option<u64>,
+    ant_id: Option<u64>,
+    x: Option<u64>,
+    y: Option<u64>,
+    message: String,
+}
+
+impl Visit for EntryFields {
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        match field.name() {
+            "tick" => self.tick = Some(value),
+            "ant_id" => self.ant_id = Some(value),
+            "x" => self.x = Some(value),
+            "y" => self.y = Some(value),
+            _ => self.record_debug(field, &value),
+        }
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        match field.name() {
+            "kind" => self.kind = LogKind::from_name(value),
+            _ => self.record_debug(field, &value),
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message = format!("{:?}", value);
+        } else {
+            // Champ libre : ajouté à la fin du message
+            self.message
+                .push_str(&format!(" {}={:?}", field.name(), value));
+        }
+    }
+}
+
+/// Subscriber `tracing` qui garde les derniers événements de la simulation ; les copies
+/// partagent le même tampon
+#[derive(Clone)]
+pub struct EventLog {
+    entries: Arc<Mutex<VecDeque<LogEntry>>>,
+    level: LogLevel,
+    // Recopier chaque événement sur la sortie d'erreur (mode CLI)
+    echo: bool,
+}
+
+impl EventLog {
+    pub fn new(level: LogLevel, echo: bool) -> Self {
+        EventLog {
+            entries: Arc::new(Mutex::new(VecDeque::new())),
+            level,
+            echo,
+        }
+    }
+
+    pub fn level(&self) -> LogLevel {
+        self.level
+    }
+
+    /// Copie des événements gardés, du plus ancien au plus récent
+    pub fn entries(&self) -> Vec<LogEntry> {
+        self.lock().iter().cloned().collect()
+    }
+
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, VecDeque<LogEntry>> {
+        // Un thread mort en cours d'écriture ne rend pas le journal illisible
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn push(&self, entry: LogEntry) {
+        if self.echo {
+            eprintln!("{}", entry);
+        }
+        let mut entries = self.lock();
+        if entries.len() >= LOG_CAPACITY {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+}
+
+impl Subscriber for EventLog {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        // Seuls les événements de la simulation : les dépendances ont leurs propres journaux
+        metadata.target().starts_with(env!("CARGO_CRATE_NAME"))
+            && *metadata.level() <= self.level.filter()
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(self.level.filter())
+    }
+
+    // Le journal n'utilise pas de spans
+    fn new_span(&self, _span: &Attributes<'_>) -> Id {
+        Id::from_u64(1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = EntryFields::default();
+        event.record(&mut fields);
+        self.push(LogEntry {
+            level: LogLevel::from_tracing(event.metadata().level()),
+            kind: fields.kind.unwrap_or(LogKind::System),
+            tick: fields.tick,
+            ant_id: fields.ant_id,
+            position: fields.x.zip(fields.y).map(|(x, y)| (x as u32, y as u32)),
+            message: fields.message,
+        });
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+static GLOBAL: OnceLock<EventLog> = OnceLock::new();
+
+/// Installe le journal pour tout le processus (threads de simulation compris). Seul le
+/// premier appel compte : les suivants renvoient le journal déjà installé.
+pub fn install(level: LogLevel, echo: bool) -> EventLog {
+    GLOBAL
+        .get_or_init(|| {
+            let log = EventLog::new(level, echo);
+            // Échoue seulement si un autre subscriber global est déjà en place
+            let _ = tracing::subscriber::set_global_default(log.clone());
+            log
+        })
+        .clone()
+}
+
+/// Journal installé par `install`, s'il y en a un
+pub fn global() -> Option<EventLog> {
+    GLOBAL.get().cloned()
+}
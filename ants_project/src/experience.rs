@@ -0,0 +1,78 @@
+//! Mémoire de transitions (experience replay) pour l'apprentissage tabulaire
+//!
+//! Chaque pas d'une fourmi laisse une transition (s, a, r, s') dans un tampon circulaire
+//! commun à toutes les colonies. À chaque tick, quelques transitions tirées au hasard sont
+//! rejouées dans les cartes de phéromones, ce qui lisse l'apprentissage quand peu de
+//! fourmis sont actives.
+
+use crate::ant::AntsType;
+use crate::pheromone::Action;
+use crate::q_table::StateFeatures;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+/// Un pas d'une fourmi, rattaché à la table qu'elle utilisait
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct Transition {
+    pub colony: usize,
+    pub ant_type: AntsType,
+    pub state: StateFeatures,
+    pub action: Action,
+    pub reward: f32,
+    // État d'arrivée, None si le pas était terminal (sortie de carte ou case mortelle)
+    pub next: Option<StateFeatures>,
+}
+
+/// Tampon circulaire : une fois plein, chaque nouvelle transition remplace la plus ancienne
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ExperienceBuffer {
+    capacity: usize,
+    transitions: Vec<Transition>,
+    // Prochaine case écrasée une fois le tampon plein
+    oldest: usize,
+}
+
+impl ExperienceBuffer {
+    pub fn new(capacity: usize) -> Self {
+        ExperienceBuffer {
+            capacity,
+            transitions: Vec::with_capacity(capacity),
+            oldest: 0,
+        }
+    }
+
+    pub fn push(&mut self, transition: Transition) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.transitions.len() < self.capacity {
+            self.transitions.push(transition);
+        } else {
+            self.transitions[self.oldest] = transition;
+            self.oldest = (self.oldest + 1) % self.capacity;
+        }
+    }
+
+    /// Tire `count` transitions uniformément, avec remise
+    pub fn sample<R: Rng>(&self, rng: &mut R, count: usize) -> Vec<Transition> {
+        if self.transitions.is_empty() {
+            return Vec::new();
+        }
+        (0..count)
+            .map(|_| self.transitions[rng.gen_range(0..self.transitions.len())])
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.transitions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.transitions.clear();
+        self.oldest = 0;
+    }
+}
@@ -0,0 +1,141 @@
+//! Manifestes d'expérience
+//!
+//! Chaque fichier produit par une partie (métriques, cerveau, replay, checkpoint, Q-tables,
+//! courbes de la GUI, résultats de l'optimiseur) est accompagné de `<fichier>.manifest.json` : la configuration
+//! effective complète, la graine, la version du crate et le commit compilés, la ligne de
+//! commande et les dates de départ et d'écriture. Un résultat retrouvé des semaines plus
+//! tard peut ainsi être reproduit sans deviner les options de la partie.
+
+use crate::cli_args::SimulationConfig;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use web_time::{SystemTime, UNIX_EPOCH};
+
+// Version du format, à incrémenter à chaque changement incompatible
+const MANIFEST_VERSION: u32 = 1;
+
+/// Nature des fichiers décrits par un manifeste
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArtifactKind {
+    /// Métriques par tick (`--output`)
+    Metrics,
+    /// Cerveau exporté (`--save-brain`)
+    Brain,
+    /// Partie enregistrée (`--record`)
+    Replay,
+    /// Checkpoint (`--checkpoint-file`)
+    Checkpoint,
+    /// Q-tables en CSV et SVG (`--dump-qtable`, export de la GUI)
+    QTables,
+    /// Courbe du panneau de statistiques de la GUI, en CSV et en PNG
+    Chart,
+    /// Résultats d'un balayage de l'optimiseur
+    OptimizerResults,
+}
+
+/// Description de l'expérience dont proviennent un ou plusieurs fichiers
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Manifest {
+    pub manifest_version: u32,
+    pub kind: ArtifactKind,
+    /// Fichiers décrits, tels qu'ils ont été écrits
+    pub artifacts: Vec<String>,
+    pub crate_version: String,
+    /// Commit compilé, absent si le binaire n'a pas été construit depuis un dépôt git
+    pub git_commit: Option<String>,
+    /// Graine de la partie (None pour un balayage, dont chaque simulation a la sienne)
+    pub seed: Option<u64>,
+    /// Tick atteint à l'écriture
+    pub tick: Option<u64>,
+    /// Arguments du programme, exécutable compris
+    pub command: Vec<String>,
+    /// Dates UTC au format RFC 3339
+    pub started_at: String,
+    pub written_at: String,
+    pub config: SimulationConfig,
+}
+
+impl Manifest {
+    pub fn new(
+        kind: ArtifactKind,
+        artifacts: Vec<String>,
+        config: &SimulationConfig,
+        started_at: SystemTime,
+    ) -> Self {
+        Manifest {
+            manifest_version: MANIFEST_VERSION,
+            kind,
+            artifacts,
+            crate_version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: option_env!("ANTS_GIT_COMMIT").map(str::to_string),
+            seed: None,
+            tick: None,
+            command: std::env::args().collect(),
+            started_at: rfc3339(started_at),
+            written_at: rfc3339(SystemTime::now()),
+            config: config.clone(),
+        }
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn with_tick(mut self, tick: u64) -> Self {
+        self.tick = Some(tick);
+        self
+    }
+
+    /// Écrit le manifeste en JSON dans `path` (voir `manifest_path`)
+    pub fn write(&self, path: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Impossible de sérialiser le manifeste: {}", e))?;
+        fs::write(path, json + "\n").map_err(|e| format!("Impossible d'écrire {}: {}", path, e))
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Impossible de lire {}: {}", path, e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Manifeste invalide {}: {}", path, e))
+    }
+}
+
+/// Fichier du manifeste qui accompagne `artifact` (ou les fichiers d'un même préfixe)
+pub fn manifest_path(artifact: &str) -> String {
+    format!("{}.manifest.json", artifact)
+}
+
+// Date UTC au format RFC 3339, à la seconde près
+pub(crate) fn rfc3339(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let (days, rest) = (secs / 86_400, secs % 86_400);
+    let (year, month, day) = civil_from_days(days);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        rest / 3600,
+        rest % 3600 / 60,
+        rest % 60
+    )
+}
+
+// Date du calendrier grégorien `days` jours après le 1er janvier 1970 (algorithme de
+// Howard Hinnant, en ères de 400 ans commençant un 1er mars)
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
@@ -0,0 +1,238 @@
+use crate::coord::Coord;
+use crate::fear::FearMap;
+use crate::grid::Grid;
+use crate::pheromone::{Action, VisitCounts};
+use crate::q_backend::QBackend;
+use crate::q_table::StateFeatures;
+use crate::sensing;
+use serde::{Deserialize, Serialize};
+
+pub(crate) const MOVING_ACTIONS: [Action; 4] =
+    [Action::Up, Action::Down, Action::Left, Action::Right];
+
+// Stratégie de sélection d'action (compromis exploration / exploitation)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExplorationPolicy {
+    // Action aléatoire avec probabilité epsilon, sinon la meilleure
+    EpsilonGreedy,
+    // Probabilité proportionnelle à exp(Q / température)
+    Softmax,
+    // Meilleure valeur Q + bonus pour les actions peu essayées (Upper Confidence Bound)
+    Ucb,
+}
+
+impl ExplorationPolicy {
+    pub fn all() -> impl Iterator<Item = ExplorationPolicy> {
+        [
+            ExplorationPolicy::EpsilonGreedy,
+            ExplorationPolicy::Softmax,
+            ExplorationPolicy::Ucb,
+        ]
+        .iter()
+        .copied()
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "epsilon-greedy" => Some(ExplorationPolicy::EpsilonGreedy),
+            "softmax" => Some(ExplorationPolicy::Softmax),
+            "ucb" => Some(ExplorationPolicy::Ucb),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExplorationPolicy::EpsilonGreedy => "Epsilon-Greedy",
+            ExplorationPolicy::Softmax => "Softmax (Boltzmann)",
+            ExplorationPolicy::Ucb => "UCB",
+        }
+    }
+}
+
+// Paramètres nécessaires au calcul de la distribution d'actions
+#[derive(Clone, Copy)]
+pub struct ExplorationParams {
+    pub policy: ExplorationPolicy,
+    pub epsilon: f32,
+    pub temperature: f32,
+    pub ucb_c: f32,
+    // Faux : l'exploration d'epsilon-greedy tire parmi les 4 directions, murs compris
+    pub masked: bool,
+    // Poids de la peur de la case visée, retranchée des valeurs Q (0 = ignorée)
+    pub fear_weight: f32,
+}
+
+impl ExplorationParams {
+    // Mêmes réglages en epsilon-greedy avec au moins `epsilon` d'exploration (reprise d'une
+    // fourmi bloquée)
+    pub fn boosted(&self, epsilon: f32) -> ExplorationParams {
+        ExplorationParams {
+            policy: ExplorationPolicy::EpsilonGreedy,
+            epsilon: self.epsilon.max(epsilon),
+            ..*self
+        }
+    }
+
+    // Probabilité de chaque action depuis l'état `state` selon la stratégie
+    // Utilisée à la fois pour tirer l'action et pour l'espérance d'Expected SARSA ;
+    // seules les actions de `valid_actions` (vision `scope`) y ont une probabilité non nulle.
+    // Les actions sont comparées sur leur valeur Q moins la peur pondérée de leur case.
+    pub fn distribution(
+        &self,
+        q: &dyn QBackend,
+        visits: &VisitCounts,
+        fear: Option<&FearMap>,
+        grid: &Grid,
+        state: StateFeatures,
+        scope: u32,
+    ) -> Vec<(Action, f32)> {
+        let (x, y) = (state.x, state.y);
+        let walkable = valid_actions(x, y, grid, scope);
+        let unmasked = self.policy == ExplorationPolicy::EpsilonGreedy && !self.masked;
+        let value = |a: Action| q.get_q(grid, state, a) - self.fear_penalty(fear, grid, x, y, a);
+
+        // Fourmi enfermée par des murs : elle reste sur place
+        if walkable.is_empty() && !unmasked {
+            return vec![(Action::Stay, 1.0)];
+        }
+
+        match self.policy {
+            ExplorationPolicy::EpsilonGreedy => {
+                // Sans masque, l'exploration tire parmi les 4 directions, murs compris (la
+                // fourmi se cogne), et seule la vision retire ensuite les zones mortelles
+                let explored: &[Action] = if unmasked { &MOVING_ACTIONS } else { &walkable };
+                let mut dist: Vec<(Action, f32)> = explored
+                    .iter()
+                    .map(|&a| (a, self.epsilon / explored.len() as f32))
+                    .collect();
+                let best = if walkable.is_empty() {
+                    vec![Action::Stay]
+                } else {
+                    ties(&walkable, value)
+                };
+                let share = (1.0 - self.epsilon) / best.len() as f32;
+                for action in best {
+                    match dist.iter_mut().find(|(a, _)| *a == action) {
+                        Some((_, p)) => *p += share,
+                        None => dist.push((action, share)),
+                    }
+                }
+                if unmasked && scope > 0 {
+                    sensing::mask_hazards(grid, (x, y), dist)
+                } else {
+                    dist
+                }
+            }
+            ExplorationPolicy::Softmax => {
+                let temperature = self.temperature.max(1e-3);
+                let max_q = walkable
+                    .iter()
+                    .map(|&a| value(a))
+                    .fold(f32::NEG_INFINITY, f32::max);
+                // Soustraire le max évite les débordements de exp()
+                let weights: Vec<f32> = walkable
+                    .iter()
+                    .map(|&a| ((value(a) - max_q) / temperature).exp())
+                    .collect();
+                let total: f32 = weights.iter().sum();
+                walkable
+                    .iter()
+                    .zip(weights)
+                    .map(|(&a, w)| (a, w / total))
+                    .collect()
+            }
+            ExplorationPolicy::Ucb => {
+                let state_visits = visits.state_count(x, y) as f32;
+                let best = ties(&walkable, |a| {
+                    let n = visits.count(x, y, a) as f32;
+                    value(a) + self.ucb_c * ((state_visits + 1.0).ln() / (n + 1.0)).sqrt()
+                });
+                let share = 1.0 / best.len() as f32;
+                best.into_iter().map(|a| (a, share)).collect()
+            }
+        }
+    }
+
+    // Peur pondérée de la case où mène `action` depuis (x, y) (un tunnel compte pour sa sortie)
+    fn fear_penalty(
+        &self,
+        fear: Option<&FearMap>,
+        grid: &Grid,
+        x: u32,
+        y: u32,
+        action: Action,
+    ) -> f32 {
+        let Some(fear) = fear.filter(|_| self.fear_weight > 0.0) else {
+            return 0.0;
+        };
+        let (nx, ny) = target(x, y, action);
+        if nx >= grid.get_width() || ny >= grid.get_height() {
+            return 0.0;
+        }
+        let (dx, dy) = grid.destination(nx, ny);
+        self.fear_weight * fear.get(dx, dy)
+    }
+}
+
+/// Déplacements valides depuis (x, y) : vers une case de la carte qui n'est pas un mur.
+/// Avec une vision `scope` non nulle, ceux qui mènent dans une zone mortelle (un tunnel
+/// compte pour sa sortie) sont aussi écartés, sauf s'ils le sont tous. Vide si la case est
+/// emmurée : la fourmi reste alors sur place.
+pub fn valid_actions(x: u32, y: u32, grid: &Grid, scope: u32) -> Vec<Action> {
+    let walkable: Vec<Action> = MOVING_ACTIONS
+        .iter()
+        .copied()
+        .filter(|&a| {
+            let (nx, ny) = target(x, y, a);
+            nx < grid.get_width() && ny < grid.get_height() && grid.is_walkable(nx, ny)
+        })
+        .collect();
+    if scope == 0 {
+        return walkable;
+    }
+    let safe: Vec<Action> = walkable
+        .iter()
+        .copied()
+        .filter(|&a| {
+            let (nx, ny) = target(x, y, a);
+            let (dx, dy) = grid.destination(nx, ny);
+            !grid.is_lethal(dx, dy)
+        })
+        .collect();
+    if safe.is_empty() {
+        walkable
+    } else {
+        safe
+    }
+}
+
+// Tirer une action dans une distribution à partir d'un nombre uniforme dans [0, 1)
+pub fn sample(distribution: &[(Action, f32)], roll: f32) -> Action {
+    let mut cumulative = 0.0;
+    for &(action, p) in distribution {
+        cumulative += p;
+        if roll < cumulative {
+            return action;
+        }
+    }
+    // Erreurs d'arrondi : on retombe sur la dernière action possible
+    distribution.last().map_or(Action::Stay, |&(a, _)| a)
+}
+
+// Toutes les actions à égalité pour la meilleure valeur (évite de toujours favoriser Up)
+fn ties(actions: &[Action], value: impl Fn(Action) -> f32) -> Vec<Action> {
+    let best = actions
+        .iter()
+        .map(|&a| value(a))
+        .fold(f32::NEG_INFINITY, f32::max);
+    actions
+        .iter()
+        .copied()
+        .filter(|&a| value(a) >= best)
+        .collect()
+}
+
+pub(crate) fn target(x: u32, y: u32, action: Action) -> (u32, u32) {
+    Coord::new(x, y).step(action).into()
+}
@@ -0,0 +1,96 @@
+//! Phéromone d'alarme (carte de « peur »)
+//!
+//! Une colonie marque l'endroit où l'une de ses fourmis meurt dans une zone mortelle ou
+//! sous les coups d'un prédateur. La peur d'une case est retranchée, pondérée, de la valeur
+//! Q des pas qui y mènent au moment de choisir l'action : les autres fourmis évitent le
+//! danger bien avant que les récompenses négatives ne se soient propagées dans la Q-table.
+//! La carte s'évapore à son propre taux et n'entre jamais dans la mise à jour de Bellman.
+
+use crate::coord::{Coord, GridShape};
+use serde::{Deserialize, Serialize};
+
+// Part du dépôt reçue par chacune des quatre cases voisines de l'incident
+const SPREAD: f32 = 0.5;
+// En dessous, la peur s'est dissipée (les cases restent nulles et les snapshots légers)
+const FADED: f32 = 0.001;
+
+/// Intensité de la peur sur chaque case, propre à une colonie
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct FearMap {
+    pub width: u32,
+    pub height: u32,
+    values: Vec<f32>,
+}
+
+impl FearMap {
+    pub fn new(width: u32, height: u32) -> Self {
+        FearMap {
+            width,
+            height,
+            values: vec![0.0; GridShape::new(width, height).len()],
+        }
+    }
+
+    pub fn get(&self, x: u32, y: u32) -> f32 {
+        self.index(x, y).map_or(0.0, |i| self.values[i])
+    }
+
+    // Rang de la case, None hors de la carte
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        GridShape::new(self.width, self.height)
+            .index(Coord::new(x, y))
+            .map(|index| index.as_usize())
+    }
+
+    /// Plus forte peur de la carte (échelle de l'affichage)
+    pub fn max_value(&self) -> f32 {
+        self.values.iter().copied().fold(0.0, f32::max)
+    }
+
+    /// Sonne l'alarme en (x, y) : `amount` sur la case, la moitié sur ses quatre voisines
+    pub fn alarm(&mut self, x: u32, y: u32, amount: f32) {
+        self.add(x, y, amount);
+        let neighbours = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        for (nx, ny) in neighbours {
+            self.add(nx, ny, amount * SPREAD);
+        }
+    }
+
+    fn add(&mut self, x: u32, y: u32, amount: f32) {
+        if let Some(i) = self.index(x, y) {
+            self.values[i] += amount;
+        }
+    }
+
+    pub fn evaporate(&mut self, rate: f32) {
+        for value in &mut self.values {
+            *value *= 1.0 - rate;
+            if *value < FADED {
+                *value = 0.0;
+            }
+        }
+    }
+
+    // Cases dont la peur diffère de `old` (même taille) : (case, peur)
+    pub(crate) fn changed_cells(&self, old: &FearMap) -> Vec<(usize, f32)> {
+        self.values
+            .iter()
+            .zip(&old.values)
+            .enumerate()
+            .filter(|(_, (new, old))| new != old)
+            .map(|(cell, (&new, _))| (cell, new))
+            .collect()
+    }
+
+    // Réécrit une case relevée par `changed_cells`
+    pub(crate) fn set_cell(&mut self, (cell, value): (usize, f32)) {
+        if let Some(slot) = self.values.get_mut(cell) {
+            *slot = value;
+        }
+    }
+}
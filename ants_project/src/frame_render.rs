@@ -0,0 +1,704 @@
+//! Rendu headless du plateau en images
+//!
+//! `--render-frames <CHEMIN>` dessine le plateau tous les `--frame-interval` ticks, sans
+//! egui : une image PNG par capture (`frame_000120.png`, ...) si le chemin est un dossier,
+//! ou une seule animation si le chemin se termine par `.gif`. Le dessin reprend celui de
+//! la GUI (cases, phéromones et peur de la colonie A, nids, nourriture, fourmis, prédateurs), sans
+//! les textes ni les calques optionnels.
+//!
+//! Le même canevas dessine les cartes de chaleur de `ants optimize --plot` ([`Heatmap`]) et
+//! les courbes exportées du panneau de statistiques de la GUI ([`render_chart`]).
+
+use crate::aco::ScentTrails;
+use crate::ant::{Ant, AntsMode, AntsType};
+use crate::ants_game_manager::AntsGameManager;
+use crate::fear::FearMap;
+use crate::grid::Grid;
+use crate::pheromone::PheromoneMap;
+use crate::predator::{self, Predator};
+use crate::tile::TileType;
+use image::codecs::gif::{GifEncoder, Repeat};
+use image::{Delay, Frame, Rgb, RgbImage, RgbaImage};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Côté d'une case en pixels
+pub const CELL_SIZE: u32 = 16;
+
+// Durée d'affichage d'une capture dans le GIF
+const GIF_FRAME_DELAY_MS: u32 = 100;
+
+// Qualité de la quantification des couleurs du GIF (1 = meilleure, 30 = plus rapide)
+const GIF_SPEED: i32 = 10;
+
+// Mêmes teintes que `Interface::draw_board`
+const BACKGROUND: Rgb<u8> = Rgb([30, 30, 30]);
+const WALL: Rgb<u8> = Rgb([160, 160, 160]);
+const DEATH_ZONE: Rgb<u8> = Rgb([50, 0, 0]);
+const MUD: Rgb<u8> = Rgb([80, 55, 30]);
+const TUNNEL: Rgb<u8> = Rgb([170, 90, 220]);
+const FOOD_TRAIL: Rgb<u8> = Rgb([139, 69, 19]);
+const NEST_TRAIL: Rgb<u8> = Rgb([255, 105, 180]);
+const FEAR: Rgb<u8> = Rgb([148, 0, 211]);
+const FOOD: Rgb<u8> = Rgb([0, 255, 0]);
+const CORPSE: Rgb<u8> = Rgb([120, 80, 50]);
+const WHITE: Rgb<u8> = Rgb([255, 255, 255]);
+const BLACK: Rgb<u8> = Rgb([0, 0, 0]);
+const YELLOW: Rgb<u8> = Rgb([255, 255, 0]);
+const RED: Rgb<u8> = Rgb([255, 0, 0]);
+const CLASH: Rgb<u8> = Rgb([255, 140, 0]);
+
+enum Output {
+    // Un PNG par capture dans ce dossier
+    Directory(PathBuf),
+    // Toutes les captures dans une seule animation (fichier ou mémoire)
+    Gif(Box<GifEncoder<Box<dyn Write + Send>>>),
+}
+
+/// Animation GIF écrite en mémoire par [`FrameRenderer::in_memory`], complète une fois le
+/// renderer libéré
+#[derive(Clone, Default)]
+pub struct GifBuffer(Arc<Mutex<Vec<u8>>>);
+
+impl GifBuffer {
+    /// Octets du fichier GIF écrits jusqu'ici
+    pub fn bytes(&self) -> Vec<u8> {
+        self.0.lock().map(|bytes| bytes.clone()).unwrap_or_default()
+    }
+}
+
+impl Write for GifBuffer {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0
+            .lock()
+            .map_err(|_| io::Error::other("tampon GIF empoisonné"))?
+            .extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Capture le plateau à intervalle régulier ; le GIF est terminé quand le renderer est libéré
+pub struct FrameRenderer {
+    output: Output,
+    interval: usize,
+    frames: usize,
+    // Après une erreur d'écriture, les captures suivantes sont abandonnées
+    failed: bool,
+}
+
+impl FrameRenderer {
+    /// Prépare la sortie : `path` est un dossier (créé au besoin) ou un fichier `.gif`
+    pub fn new(path: &str, interval: u32) -> Result<Self, String> {
+        let output = if path.to_lowercase().ends_with(".gif") {
+            let file =
+                File::create(path).map_err(|e| format!("Impossible de créer {}: {}", path, e))?;
+            Self::gif_output(Box::new(BufWriter::new(file)))
+                .map_err(|e| format!("Impossible d'écrire {}: {}", path, e))?
+        } else {
+            fs::create_dir_all(path)
+                .map_err(|e| format!("Impossible de créer le dossier {}: {}", path, e))?;
+            Output::Directory(PathBuf::from(path))
+        };
+        Ok(FrameRenderer {
+            output,
+            interval: interval.max(1) as usize,
+            frames: 0,
+            failed: false,
+        })
+    }
+
+    /// Animation gardée en mémoire plutôt qu'écrite sur le disque (rapport HTML)
+    pub fn in_memory(interval: u32) -> Result<(Self, GifBuffer), String> {
+        let buffer = GifBuffer::default();
+        let output = Self::gif_output(Box::new(buffer.clone()))
+            .map_err(|e| format!("Impossible de préparer l'animation: {}", e))?;
+        let renderer = FrameRenderer {
+            output,
+            interval: interval.max(1) as usize,
+            frames: 0,
+            failed: false,
+        };
+        Ok((renderer, buffer))
+    }
+
+    // Encodeur GIF rejouant l'animation en boucle
+    fn gif_output(writer: Box<dyn Write + Send>) -> image::ImageResult<Output> {
+        let mut encoder = GifEncoder::new_with_speed(writer, GIF_SPEED);
+        encoder.set_repeat(Repeat::Infinite)?;
+        Ok(Output::Gif(Box::new(encoder)))
+    }
+
+    /// Nombre d'images écrites jusqu'ici
+    pub fn frames(&self) -> usize {
+        self.frames
+    }
+
+    /// Dessine et écrit le plateau si le tick courant tombe sur l'intervalle
+    pub fn capture(&mut self, manager: &AntsGameManager) {
+        let tick = manager.current_tick_index();
+        if self.failed || !tick.is_multiple_of(self.interval) {
+            return;
+        }
+        let image = render(manager);
+        let result = match &mut self.output {
+            Output::Directory(dir) => {
+                let path = dir.join(format!("frame_{:06}.png", tick));
+                image
+                    .save(&path)
+                    .map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))
+            }
+            Output::Gif(encoder) => {
+                let rgba = RgbaImage::from_fn(image.width(), image.height(), |x, y| {
+                    let Rgb([r, g, b]) = *image.get_pixel(x, y);
+                    image::Rgba([r, g, b, 255])
+                });
+                let delay = Delay::from_numer_denom_ms(GIF_FRAME_DELAY_MS, 1);
+                encoder
+                    .encode_frame(Frame::from_parts(rgba, 0, 0, delay))
+                    .map_err(|e| format!("Impossible d'écrire l'animation: {}", e))
+            }
+        };
+        match result {
+            Ok(()) => self.frames += 1,
+            Err(e) => {
+                tracing::error!(tick = tick as u64, "{}", e);
+                self.failed = true;
+            }
+        }
+    }
+}
+
+/// Image du plateau tel que le montre la GUI, `CELL_SIZE` pixels par case
+pub fn render(manager: &AntsGameManager) -> RgbImage {
+    let grid = manager.grid();
+    let mut canvas = Canvas {
+        image: RgbImage::from_pixel(
+            grid.get_width() * CELL_SIZE,
+            grid.get_height() * CELL_SIZE,
+            BACKGROUND,
+        ),
+    };
+
+    canvas.draw_grid_base(grid);
+    // Colonie et type de fourmi affichés par défaut dans la GUI
+    let colony = &manager.colonies()[0];
+    match &colony.trails {
+        Some(trails) => {
+            canvas.draw_scent(trails, AntsMode::FINDING, FOOD_TRAIL);
+            canvas.draw_scent(trails, AntsMode::RETURNING, NEST_TRAIL);
+        }
+        None => {
+            // Valeur Q affichée à pleine intensité, comme dans la GUI
+            let scale = manager.config().q_display_scale;
+            let (food, nest) = colony.maps(AntsType::EXPLORER);
+            canvas.draw_pheromones(food, grid, scale, FOOD_TRAIL);
+            canvas.draw_pheromones(nest, grid, scale, NEST_TRAIL);
+        }
+    }
+    if let Some(fear) = &colony.fear {
+        canvas.draw_fear(fear, manager.config().fear_deposit);
+    }
+    canvas.draw_grid_objects(grid);
+    canvas.draw_ants(manager.ants());
+    canvas.draw_predators(manager.predators(), manager.ants());
+    canvas.image
+}
+
+fn colony_color(colony: usize) -> Rgb<u8> {
+    match colony {
+        0 => Rgb([255, 215, 0]),
+        1 => Rgb([255, 140, 0]),
+        _ => Rgb([100, 149, 237]),
+    }
+}
+
+// Centre d'une case en pixels
+fn center((x, y): (u32, u32)) -> (f32, f32) {
+    let size = CELL_SIZE as f32;
+    ((x as f32 + 0.5) * size, (y as f32 + 0.5) * size)
+}
+
+// Primitives de dessin : chaque forme colore les pixels dont le centre tombe dedans
+struct Canvas {
+    image: RgbImage,
+}
+
+impl Canvas {
+    fn blend(&mut self, x: i64, y: i64, color: Rgb<u8>, alpha: f32) {
+        if x < 0 || y < 0 || x >= self.image.width() as i64 || y >= self.image.height() as i64 {
+            return;
+        }
+        let pixel = self.image.get_pixel_mut(x as u32, y as u32);
+        for (channel, value) in pixel.0.iter_mut().zip(color.0) {
+            *channel = (*channel as f32 * (1.0 - alpha) + value as f32 * alpha).round() as u8;
+        }
+    }
+
+    // Remplit les pixels de la boîte englobante pour lesquels `inside(dx, dy)` est vrai,
+    // (dx, dy) étant la position du pixel par rapport à `(cx, cy)`
+    fn fill(
+        &mut self,
+        (cx, cy): (f32, f32),
+        radius: f32,
+        color: Rgb<u8>,
+        alpha: f32,
+        inside: impl Fn(f32, f32) -> bool,
+    ) {
+        let (x0, x1) = ((cx - radius - 1.0).floor(), (cx + radius + 1.0).ceil());
+        let (y0, y1) = ((cy - radius - 1.0).floor(), (cy + radius + 1.0).ceil());
+        for y in y0 as i64..y1 as i64 {
+            for x in x0 as i64..x1 as i64 {
+                if inside(x as f32 + 0.5 - cx, y as f32 + 0.5 - cy) {
+                    self.blend(x, y, color, alpha);
+                }
+            }
+        }
+    }
+
+    fn rect(&mut self, center: (f32, f32), half: f32, color: Rgb<u8>, alpha: f32) {
+        self.fill(center, half, color, alpha, |dx, dy| {
+            dx.abs() <= half && dy.abs() <= half
+        });
+    }
+
+    fn disc(&mut self, center: (f32, f32), radius: f32, color: Rgb<u8>) {
+        self.fill(center, radius, color, 1.0, |dx, dy| {
+            dx * dx + dy * dy <= radius * radius
+        });
+    }
+
+    fn ring(&mut self, center: (f32, f32), radius: f32, width: f32, color: Rgb<u8>) {
+        self.fill(center, radius + width, color, 1.0, |dx, dy| {
+            ((dx * dx + dy * dy).sqrt() - radius).abs() <= width / 2.0
+        });
+    }
+
+    fn line(&mut self, from: (f32, f32), to: (f32, f32), color: Rgb<u8>, alpha: f32) {
+        let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs()).ceil() as usize;
+        for step in 0..=steps {
+            let t = step as f32 / steps.max(1) as f32;
+            let x = from.0 + (to.0 - from.0) * t;
+            let y = from.1 + (to.1 - from.1) * t;
+            self.blend(x as i64, y as i64, color, alpha);
+        }
+    }
+
+    fn draw_grid_base(&mut self, grid: &Grid) {
+        // Cases réduites d'un pixel de chaque côté, comme dans la GUI
+        let half = CELL_SIZE as f32 / 2.0 - 1.0;
+        for y in 0..grid.get_height() {
+            for x in 0..grid.get_width() {
+                let Some(tile) = grid.get_tile((x, y)) else {
+                    continue;
+                };
+                let c = center((x, y));
+                match tile.tile_type {
+                    TileType::Wall => self.rect(c, half, WALL, 1.0),
+                    TileType::DeathZone => self.rect(c, half, DEATH_ZONE, 1.0),
+                    TileType::Mud { .. } => self.rect(c, half, MUD, 1.0),
+                    TileType::Tunnel { exit } => {
+                        self.disc(c, CELL_SIZE as f32 * 0.35, TUNNEL);
+                        self.line(c, center(exit), TUNNEL, 0.5);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    // Piste de l'ACO classique, plus opaque là où elle est forte
+    fn draw_scent(&mut self, trails: &ScentTrails, mode: AntsMode, color: Rgb<u8>) {
+        let max = trails.max_value(mode);
+        if max <= 0.0 {
+            return;
+        }
+        for y in 0..trails.height {
+            for x in 0..trails.width {
+                let ratio = trails.get(mode, x, y) / max;
+                if ratio >= 0.01 {
+                    self.rect(center((x, y)), CELL_SIZE as f32 / 2.0, color, alpha(ratio));
+                }
+            }
+        }
+    }
+
+    // Phéromone d'alarme, à pleine intensité là où vient d'avoir lieu un dépôt `scale`
+    fn draw_fear(&mut self, fear: &FearMap, scale: f32) {
+        for y in 0..fear.height {
+            for x in 0..fear.width {
+                let ratio = (fear.get(x, y) / scale.max(f32::EPSILON)).clamp(0.0, 1.0);
+                if ratio >= 0.01 {
+                    self.rect(center((x, y)), CELL_SIZE as f32 / 2.0, FEAR, alpha(ratio));
+                }
+            }
+        }
+    }
+
+    fn draw_pheromones(&mut self, map: &PheromoneMap, grid: &Grid, scale: f32, color: Rgb<u8>) {
+        for y in 0..map.height {
+            for x in 0..map.width {
+                if !grid.is_walkable(x, y) {
+                    continue;
+                }
+                let max_q = map.get_max_q(x, y, grid).max(0.0);
+                if max_q > 0.1 {
+                    let ratio = (max_q / scale).clamp(0.0, 1.0);
+                    self.rect(center((x, y)), CELL_SIZE as f32 / 2.0, color, alpha(ratio));
+                }
+            }
+        }
+    }
+
+    fn draw_grid_objects(&mut self, grid: &Grid) {
+        let size = CELL_SIZE as f32;
+        for y in 0..grid.get_height() {
+            for x in 0..grid.get_width() {
+                let Some(tile) = grid.get_tile((x, y)) else {
+                    continue;
+                };
+                let c = center((x, y));
+                match tile.tile_type {
+                    TileType::Nest { colony, .. } => {
+                        self.rect(c, size * 0.25, colony_color(colony), 1.0);
+                    }
+                    TileType::FoodSource { .. } => {
+                        let fill = if tile.is_corpse() { CORPSE } else { FOOD };
+                        self.disc(c, size * 0.35, fill);
+                        self.ring(c, size * 0.35, 2.0, WHITE);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    fn draw_ants(&mut self, ants: &[Ant]) {
+        let size = CELL_SIZE as f32;
+        for ant in ants {
+            let Some(position) = ant.position else {
+                continue;
+            };
+            let c = center(position);
+            let color = match ant.mode {
+                AntsMode::FINDING => WHITE,
+                AntsMode::RETURNING => YELLOW,
+            };
+            self.disc(c, size * 0.25, color);
+            // Le contour indique la colonie (noir pour la colonie A)
+            let outline = if ant.colony == 0 {
+                BLACK
+            } else {
+                colony_color(ant.colony)
+            };
+            self.ring(c, size * 0.25, 1.5, outline);
+            if ant.current_charge > 0 {
+                self.disc((c.0 + size * 0.15, c.1 - size * 0.15), size * 0.1, FOOD);
+            }
+        }
+    }
+
+    // Losanges rouges cerclés de noir, reliés par un trait orange aux combattantes qui les
+    // affrontent
+    fn draw_predators(&mut self, predators: &[Predator], ants: &[Ant]) {
+        for (ant, predator) in predator::clashes(ants, predators) {
+            if let Some(position) = ants[ant].position {
+                let to = center(predators[predator].position);
+                self.line(center(position), to, CLASH, 1.0);
+            }
+        }
+        let r = CELL_SIZE as f32 * 0.4;
+        for predator in predators {
+            let c = center(predator.position);
+            self.fill(c, r, BLACK, 1.0, |dx, dy| dx.abs() + dy.abs() <= r + 0.5);
+            self.fill(c, r, RED, 1.0, |dx, dy| dx.abs() + dy.abs() <= r - 0.5);
+        }
+    }
+
+    // Texte centré sur `center` ; les caractères sans glyphe restent vides
+    fn text_centered(&mut self, center: (f32, f32), text: &str, color: Rgb<u8>) {
+        let left = (center.0 - text_width(text) as f32 / 2.0).round() as i64;
+        let top = (center.1 - (5 * GLYPH_SCALE) as f32 / 2.0).round() as i64;
+        for (index, ch) in text.chars().enumerate() {
+            let Some((_, rows)) = GLYPHS.iter().find(|(glyph, _)| *glyph == ch) else {
+                continue;
+            };
+            let x0 = left + (index as u32 * GLYPH_ADVANCE) as i64;
+            for (row, bits) in rows.iter().enumerate() {
+                for column in 0..3 {
+                    if bits & (0b100 >> column) == 0 {
+                        continue;
+                    }
+                    for dy in 0..GLYPH_SCALE as i64 {
+                        for dx in 0..GLYPH_SCALE as i64 {
+                            let x = x0 + column * GLYPH_SCALE as i64 + dx;
+                            let y = top + row as i64 * GLYPH_SCALE as i64 + dy;
+                            self.blend(x, y, color, 1.0);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Opacité d'une phéromone : racine du ratio pour faire ressortir les faibles valeurs
+fn alpha(ratio: f32) -> f32 {
+    (ratio.sqrt() * 200.0).floor() / 255.0
+}
+
+/// Côté d'une case de carte de chaleur en pixels
+pub const HEATMAP_CELL_SIZE: u32 = 56;
+
+// Marges de la carte de chaleur : graduations à gauche et en bas, légende à droite
+const HEATMAP_MARGIN: u32 = 8;
+const HEATMAP_AXIS_LEFT: u32 = 64;
+const HEATMAP_AXIS_BOTTOM: u32 = 24;
+const HEATMAP_LEGEND_WIDTH: u32 = 16;
+const HEATMAP_LEGEND_LABELS: u32 = 64;
+const HEATMAP_EMPTY: Rgb<u8> = Rgb([60, 60, 60]);
+
+// Chiffres 3x5 (un bit par pixel, ligne par ligne) : les cartes de chaleur n'affichent que
+// des nombres
+const GLYPHS: [(char, [u8; 5]); 12] = [
+    ('0', [0b111, 0b101, 0b101, 0b101, 0b111]),
+    ('1', [0b010, 0b110, 0b010, 0b010, 0b111]),
+    ('2', [0b111, 0b001, 0b111, 0b100, 0b111]),
+    ('3', [0b111, 0b001, 0b111, 0b001, 0b111]),
+    ('4', [0b101, 0b101, 0b111, 0b001, 0b001]),
+    ('5', [0b111, 0b100, 0b111, 0b001, 0b111]),
+    ('6', [0b111, 0b100, 0b111, 0b101, 0b111]),
+    ('7', [0b111, 0b001, 0b001, 0b001, 0b001]),
+    ('8', [0b111, 0b101, 0b111, 0b101, 0b111]),
+    ('9', [0b111, 0b101, 0b111, 0b001, 0b111]),
+    ('.', [0b000, 0b000, 0b000, 0b000, 0b010]),
+    ('-', [0b000, 0b000, 0b111, 0b000, 0b000]),
+];
+// Pixels par point de glyphe, et avance d'un caractère au suivant
+const GLYPH_SCALE: u32 = 2;
+const GLYPH_ADVANCE: u32 = 4 * GLYPH_SCALE;
+
+/// Grille de résultats à dessiner par [`render_heatmap`] : une case par couple (colonne,
+/// ligne), vide si la combinaison n'a pas été évaluée
+pub struct Heatmap {
+    // Valeurs du paramètre en abscisse, de gauche à droite
+    pub columns: Vec<f32>,
+    // Valeurs du paramètre en ordonnée, de bas en haut
+    pub rows: Vec<f32>,
+    // Ligne par ligne depuis la plus basse : valeur affichée et score (plus petit = meilleur)
+    pub cells: Vec<Option<(f64, f64)>>,
+}
+
+/// Carte de chaleur du bleu (pire score) au jaune (meilleur), mêmes teintes que le panneau
+/// de balayage de la GUI, avec les valeurs dans les cases et la légende à droite
+pub fn render_heatmap(heatmap: &Heatmap) -> RgbImage {
+    let size = HEATMAP_CELL_SIZE;
+    let (columns, rows) = (heatmap.columns.len() as u32, heatmap.rows.len() as u32);
+    let plot_left = HEATMAP_AXIS_LEFT;
+    let plot_bottom = HEATMAP_MARGIN + rows * size;
+    let legend_left = plot_left + columns * size + 2 * HEATMAP_MARGIN;
+    let mut canvas = Canvas {
+        image: RgbImage::from_pixel(
+            legend_left + HEATMAP_LEGEND_WIDTH + HEATMAP_LEGEND_LABELS,
+            plot_bottom + HEATMAP_AXIS_BOTTOM,
+            BACKGROUND,
+        ),
+    };
+
+    let scores = heatmap.cells.iter().flatten().map(|&(_, score)| score);
+    let best = scores.clone().fold(f64::INFINITY, f64::min);
+    let worst = scores.fold(f64::NEG_INFINITY, f64::max);
+    let ratio = |score: f64| {
+        if worst > best {
+            ((worst - score) / (worst - best)) as f32
+        } else {
+            1.0
+        }
+    };
+
+    let half = size as f32 / 2.0 - 1.0;
+    for (index, cell) in heatmap.cells.iter().enumerate() {
+        let (column, row) = (index as u32 % columns.max(1), index as u32 / columns.max(1));
+        let left = plot_left + column * size;
+        let top = plot_bottom - (row + 1) * size;
+        let c = (
+            left as f32 + size as f32 / 2.0,
+            top as f32 + size as f32 / 2.0,
+        );
+        match cell {
+            Some((value, score)) => {
+                let ratio = ratio(*score);
+                canvas.rect(c, half, heat_color(ratio), 1.0);
+                let ink = if ratio > 0.5 { BLACK } else { WHITE };
+                canvas.text_centered(c, &format_number(*value), ink);
+            }
+            None => canvas.rect(c, half, HEATMAP_EMPTY, 1.0),
+        }
+    }
+
+    // Graduations : valeur de chaque colonne sous la grille, de chaque ligne à sa gauche
+    for (column, value) in heatmap.columns.iter().enumerate() {
+        let x = plot_left as f32 + (column as f32 + 0.5) * size as f32;
+        let y = (plot_bottom + HEATMAP_AXIS_BOTTOM / 2) as f32;
+        canvas.text_centered((x, y), &format_number(*value as f64), WHITE);
+    }
+    for (row, value) in heatmap.rows.iter().enumerate() {
+        let label = format_number(*value as f64);
+        let y = plot_bottom as f32 - (row as f32 + 0.5) * size as f32;
+        let x = plot_left as f32 - HEATMAP_MARGIN as f32 - text_width(&label) as f32 / 2.0;
+        canvas.text_centered((x, y), &label, WHITE);
+    }
+
+    // Légende : dégradé du pire (en bas) au meilleur (en haut), avec les valeurs extrêmes
+    let legend_height = rows * size;
+    for y in 0..legend_height {
+        let color = heat_color(1.0 - y as f32 / legend_height.max(1) as f32);
+        for x in 0..HEATMAP_LEGEND_WIDTH {
+            canvas.blend(
+                (legend_left + x) as i64,
+                (HEATMAP_MARGIN + y) as i64,
+                color,
+                1.0,
+            );
+        }
+    }
+    let extreme = |target: f64| {
+        heatmap
+            .cells
+            .iter()
+            .flatten()
+            .find(|&&(_, score)| score == target)
+            .map(|&(value, _)| format_number(value))
+    };
+    let label_x = legend_left + HEATMAP_LEGEND_WIDTH + HEATMAP_MARGIN;
+    if let Some(label) = extreme(best) {
+        let x = label_x as f32 + text_width(&label) as f32 / 2.0;
+        canvas.text_centered(
+            (x, (HEATMAP_MARGIN + 5 * GLYPH_SCALE) as f32),
+            &label,
+            WHITE,
+        );
+    }
+    if let Some(label) = extreme(worst) {
+        let x = label_x as f32 + text_width(&label) as f32 / 2.0;
+        let y = (plot_bottom - 5 * GLYPH_SCALE) as f32;
+        canvas.text_centered((x, y), &label, WHITE);
+    }
+    canvas.image
+}
+
+// Dimensions d'une courbe exportée, et marges autour du tracé : graduations à gauche et en bas
+const CHART_WIDTH: u32 = 640;
+const CHART_HEIGHT: u32 = 360;
+const CHART_AXIS_LEFT: u32 = 72;
+const CHART_AXIS_BOTTOM: u32 = 28;
+const CHART_MARGIN: u32 = 16;
+// Graduations de chaque axe, extrémités comprises
+const CHART_TICKS: u32 = 5;
+const CHART_GRID: Rgb<u8> = Rgb([90, 90, 90]);
+// Couleur de chaque partie : principale, puis seconde partie du mode comparaison
+const CHART_COLORS: [Rgb<u8>; 2] = [Rgb([255, 215, 0]), Rgb([100, 149, 237])];
+
+/// Courbes (tick, valeur) de une ou deux parties sur les mêmes axes, comme les graphiques du
+/// panneau de statistiques : l'axe des valeurs inclut toujours 0
+pub fn render_chart(series: &[Vec<[f64; 2]>]) -> RgbImage {
+    let mut canvas = Canvas {
+        image: RgbImage::from_pixel(CHART_WIDTH, CHART_HEIGHT, BACKGROUND),
+    };
+    let points = || series.iter().flatten();
+    let (mut x_min, mut x_max) = points()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+            (lo.min(p[0]), hi.max(p[0]))
+        });
+    let (y_min, mut y_max) = points().fold((0.0_f64, 0.0_f64), |(lo, hi), p| {
+        (lo.min(p[1]), hi.max(p[1]))
+    });
+    if x_min > x_max {
+        (x_min, x_max) = (0.0, 1.0);
+    }
+    if x_max == x_min {
+        x_max = x_min + 1.0;
+    }
+    if y_max == y_min {
+        y_max = y_min + 1.0;
+    }
+
+    let (left, right) = (CHART_AXIS_LEFT as f32, (CHART_WIDTH - CHART_MARGIN) as f32);
+    let (top, bottom) = (
+        CHART_MARGIN as f32,
+        (CHART_HEIGHT - CHART_AXIS_BOTTOM) as f32,
+    );
+    let to_pixel = |[x, y]: [f64; 2]| {
+        (
+            left + ((x - x_min) / (x_max - x_min)) as f32 * (right - left),
+            bottom - ((y - y_min) / (y_max - y_min)) as f32 * (bottom - top),
+        )
+    };
+
+    // Quadrillage et graduations
+    for step in 0..CHART_TICKS {
+        let ratio = step as f64 / (CHART_TICKS - 1) as f64;
+        let x_value = x_min + ratio * (x_max - x_min);
+        let (x, _) = to_pixel([x_value, y_min]);
+        canvas.line((x, top), (x, bottom), CHART_GRID, 0.5);
+        let label_y = bottom + CHART_AXIS_BOTTOM as f32 / 2.0;
+        canvas.text_centered((x, label_y), &format_number(x_value.round()), WHITE);
+
+        let y_value = y_min + ratio * (y_max - y_min);
+        let (_, y) = to_pixel([x_min, y_value]);
+        canvas.line((left, y), (right, y), CHART_GRID, 0.5);
+        let label = format_number(y_value);
+        let label_x = left - CHART_MARGIN as f32 / 2.0 - text_width(&label) as f32 / 2.0;
+        canvas.text_centered((label_x, y), &label, WHITE);
+    }
+    canvas.line((left, top), (left, bottom), WHITE, 1.0);
+    canvas.line((left, bottom), (right, bottom), WHITE, 1.0);
+
+    // Traits de deux pixels d'épaisseur
+    for (points, color) in series.iter().zip(CHART_COLORS.iter().cycle()) {
+        for pair in points.windows(2) {
+            let (from, to) = (to_pixel(pair[0]), to_pixel(pair[1]));
+            canvas.line(from, to, *color, 1.0);
+            canvas.line((from.0, from.1 + 1.0), (to.0, to.1 + 1.0), *color, 1.0);
+        }
+        if let [point] = points.as_slice() {
+            canvas.disc(to_pixel(*point), 2.0, *color);
+        }
+    }
+    canvas.image
+}
+
+fn text_width(text: &str) -> u32 {
+    (text.chars().count() as u32 * GLYPH_ADVANCE).saturating_sub(GLYPH_SCALE)
+}
+
+// Rampe bleu → jaune de la GUI, `ratio` = 1 pour le meilleur score
+fn heat_color(ratio: f32) -> Rgb<u8> {
+    Rgb([
+        (60.0 + ratio * 195.0) as u8,
+        (110.0 + ratio * 120.0) as u8,
+        (255.0 - ratio * 255.0) as u8,
+    ])
+}
+
+// Au plus 4 décimales significatives pour les petites valeurs, aucune au-delà de 100
+fn format_number(value: f64) -> String {
+    let decimals = if value.abs() >= 100.0 {
+        0
+    } else if value.abs() >= 1.0 {
+        2
+    } else {
+        4
+    };
+    let text = format!("{:.*}", decimals, value);
+    if text.contains('.') {
+        text.trim_end_matches('0').trim_end_matches('.').to_string()
+    } else {
+        text
+    }
+}
@@ -1,12 +1,145 @@
-use crate::tile::Tile;
-use crate::tile::TileType;
+use crate::ant::{Ant, AntsType};
+use crate::coord::{Coord, GridShape};
+use crate::error::AntsError;
+use crate::pheromone::PheromoneMap;
+use crate::tile::{FoodRegrowth, Tile, TileType, PROTEIN_VALUE, SUGAR_VALUE};
 use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+// Numéro de version unique à toute la partie : deux grilles (ou deux états d'une même grille)
+// n'ont jamais le même, sauf une copie non modifiée
+fn next_revision() -> u64 {
+    static NEXT: AtomicU64 = AtomicU64::new(1);
+    NEXT.fetch_add(1, Ordering::Relaxed)
+}
+
+/// Proportions des cartes aléatoires ; les valeurs par défaut donnent les cartes historiques
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MapGenParams {
+    pub wall_density: f32,  // Part maximale des cases occupées par des murs
+    pub death_density: f32, // Part maximale des cases restantes en zones mortelles
+    pub food_sources: u32,  // Nombre maximal de sources de nourriture (au moins une)
+    pub food_min: u32,      // Quantité minimale d'une source
+    pub food_max: u32,      // Quantité maximale d'une source
+    pub protein_share: f32, // Probabilité qu'une source soit de la protéine
+    pub seed: Option<u64>,  // Graine de la carte, à défaut celle de la partie
+}
+
+impl Default for MapGenParams {
+    fn default() -> Self {
+        MapGenParams {
+            wall_density: 0.25,
+            death_density: 0.1,
+            food_sources: 3,
+            food_min: 100,
+            food_max: 9999,
+            protein_share: 0.25,
+            seed: None,
+        }
+    }
+}
+
+// Nombre de cases correspondant à une part de `count` (arrondi inférieur)
+fn share_of(count: u32, density: f32) -> u32 {
+    (count as f64 * density as f64).floor() as u32
+}
 
-#[derive(Clone)]
+/// Version du format sérialisé des grilles (checkpoints, replays, sessions), à incrémenter
+/// à chaque changement de `Grid`, `Tile` ou `TileType` en ajoutant sa migration
+pub const GRID_FORMAT_VERSION: u32 = 1;
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "GridFile", into = "GridFile")]
 pub struct Grid {
     tiles: Vec<Tile>,
     width: u32,
     height: u32,
+    // Change à chaque accès en écriture à une case (invalide les caches de chemins)
+    revision: u64,
+}
+
+// Forme sérialisée : version du format, dimensions, puis les cases ligne après ligne (la case
+// (x, y) au rang `GridShape::index_of((x, y))`, soit y * width + x)
+#[derive(Serialize, Deserialize)]
+struct GridFile {
+    // Absente des grilles écrites avant le versionnage (format 0)
+    #[serde(default)]
+    version: u32,
+    width: u32,
+    height: u32,
+    tiles: Vec<Tile>,
+}
+
+impl GridFile {
+    // Amène une grille d'un format antérieur au format courant, une version après l'autre
+    fn migrate(mut self) -> Result<Self, String> {
+        if self.version > GRID_FORMAT_VERSION {
+            return Err(format!(
+                "grille au format {}, plus récent que le format {} de cette version",
+                self.version, GRID_FORMAT_VERSION
+            ));
+        }
+        while self.version < GRID_FORMAT_VERSION {
+            match self.version {
+                // Format 0 : même disposition, les positions sont vérifiées au chargement
+                0 => {}
+                version => return Err(format!("format de grille {} inconnu", version)),
+            }
+            self.version += 1;
+        }
+        Ok(self)
+    }
+}
+
+impl From<Grid> for GridFile {
+    fn from(grid: Grid) -> Self {
+        GridFile {
+            version: GRID_FORMAT_VERSION,
+            width: grid.width,
+            height: grid.height,
+            tiles: grid.tiles,
+        }
+    }
+}
+
+impl TryFrom<GridFile> for Grid {
+    type Error = String;
+
+    fn try_from(file: GridFile) -> Result<Self, String> {
+        let file = file.migrate()?;
+        let shape = GridShape::new(file.width, file.height);
+        let expected = shape.len();
+        if file.tiles.len() != expected {
+            return Err(format!(
+                "{} cases pour une grille {}x{} ({} attendues)",
+                file.tiles.len(),
+                file.width,
+                file.height,
+                expected
+            ));
+        }
+        // Une case rangée ailleurs qu'à sa position (largeur et hauteur inversées, par
+        // exemple) fausserait toutes les recherches par coordonnées
+        let misplaced = file
+            .tiles
+            .iter()
+            .enumerate()
+            .find(|(i, tile)| Coord::from(tile.position) != shape.coord_at(*i));
+        if let Some((i, tile)) = misplaced {
+            return Err(format!(
+                "case ({}, {}) rangée au rang {} d'une grille {}x{}",
+                tile.position.0, tile.position.1, i, file.width, file.height
+            ));
+        }
+        Ok(Grid {
+            tiles: file.tiles,
+            width: file.width,
+            height: file.height,
+            revision: next_revision(),
+        })
+    }
 }
 
 impl Grid {
@@ -22,10 +155,27 @@ impl Grid {
             tiles,
             width,
             height,
+            revision: next_revision(),
         }
     }
 
     pub fn new_random(width: u32, height: u32) -> Self {
+        Self::new_random_with_rng(width, height, &mut rand::thread_rng())
+    }
+
+    // Génération reproductible : toute l'aléa provient du générateur fourni
+    pub fn new_random_with_rng<R: Rng>(width: u32, height: u32, rng: &mut R) -> Self {
+        Self::new_random_with_params(width, height, &MapGenParams::default(), rng)
+    }
+
+    /// Carte aléatoire aux proportions données : le nombre de murs, de zones mortelles et
+    /// de sources est tiré entre zéro (une pour les sources) et le maximum fixé par `params`
+    pub fn new_random_with_params<R: Rng>(
+        width: u32,
+        height: u32,
+        params: &MapGenParams,
+        rng: &mut R,
+    ) -> Self {
         // Créer la grille initiale avec toutes les cases en par défaut
         let mut tiles = Vec::with_capacity((width * height) as usize);
         for y in 0..height {
@@ -34,20 +184,19 @@ impl Grid {
             }
         }
 
-        let mut rng = rand::thread_rng();
         let total = width * height;
 
         // Générer des quantités aléatoires raisonnables pour chaque type d'obstacle
-        let food_tiles_number = rng.gen_range(1..4);
+        let food_tiles_number = rng.gen_range(1..=params.food_sources);
         let remaining_after_food = total.saturating_sub(food_tiles_number);
         let wall_tiles_number = if remaining_after_food > 0 {
-            rng.gen_range(0..=(total / 4))
+            rng.gen_range(0..=share_of(total, params.wall_density))
         } else {
             0
         };
         let remaining_after_walls = remaining_after_food.saturating_sub(wall_tiles_number);
         let death_tiles_number = if remaining_after_walls > 0 {
-            rng.gen_range(0..=(remaining_after_walls / 10))
+            rng.gen_range(0..=share_of(remaining_after_walls, params.death_density))
         } else {
             0
         };
@@ -67,28 +216,38 @@ impl Grid {
             nest_x,
             nest_y,
             TileType::Nest {
+                colony: 0,
                 stored_food: 0,
-                explorer_capacity: rng.gen_range(0..10),
-                picker_capacity: rng.gen_range(0..10),
-                fighter_capacity: rng.gen_range(0..10),
+                explorer_capacity: rng.gen_range(1..=10),
+                picker_capacity: rng.gen_range(1..=10),
+                fighter_capacity: rng.gen_range(1..=10),
             },
             None,
         );
-        let nest_idx = (nest_y * width + nest_x) as usize;
+        let nest_idx = GridShape::new(width, height)
+            .index_of(Coord::new(nest_x, nest_y))
+            .as_usize();
         tiles[nest_idx] = nest_tile;
 
         // Placer les tuiles de nourriture avec des quantités aléatoires
         Self::place_items(
+            rng,
+            params,
             &mut tiles,
             width,
             height,
             food_tiles_number,
             nest_idx,
-            TileType::FoodSource { amount: 0 },
+            TileType::FoodSource {
+                amount: 0,
+                value: SUGAR_VALUE,
+            },
         );
 
         // Placer les murs qui bloquent la circulation
         Self::place_items(
+            rng,
+            params,
             &mut tiles,
             width,
             height,
@@ -99,6 +258,8 @@ impl Grid {
 
         // Placer les zones mortelles qui tuent les fourmis
         Self::place_items(
+            rng,
+            params,
             &mut tiles,
             width,
             height,
@@ -111,18 +272,21 @@ impl Grid {
             tiles,
             width,
             height,
+            revision: next_revision(),
         }
     }
 
-    fn place_items(
-        tiles: &mut Vec<Tile>, // La grille qu'on modifie
+    #[allow(clippy::too_many_arguments)]
+    fn place_items<R: Rng>(
+        rng: &mut R,
+        params: &MapGenParams,
+        tiles: &mut [Tile], // La grille qu'on modifie
         width: u32,
         height: u32,
         count: u32,           // Nombre d'éléments à placer
         forbidden_idx: usize, // L'index du nid pour ne pas y placer d'objets
         item_type: TileType,  // Le type d'élément à placer (mur, zone mortelle, nourriture, etc)
     ) {
-        let mut rng = rand::thread_rng();
         let mut placed = 0;
 
         // Limite de tentatives pour éviter une boucle infinie si la grille est pleine
@@ -134,7 +298,9 @@ impl Grid {
             // Choisir une position aléatoire sur la carte
             let x = rng.gen_range(0..width);
             let y = rng.gen_range(0..height);
-            let idx = (y * width + x) as usize;
+            let idx = GridShape::new(width, height)
+                .index_of(Coord::new(x, y))
+                .as_usize();
 
             // Vérifier que la position n'est pas le nid
             if idx == forbidden_idx {
@@ -145,9 +311,15 @@ impl Grid {
             if matches!(tiles[idx].tile_type, TileType::Default) {
                 // Créer la vraie tuile selon le type demandé
                 let final_type = match item_type {
-                    // Pour la nourriture, générer un montant aléatoire
+                    // Pour la nourriture, générer un montant aléatoire ; une part des sources
+                    // est de la protéine, qui vaut plus par unité
                     TileType::FoodSource { .. } => TileType::FoodSource {
-                        amount: rng.gen_range(100..10000),
+                        amount: rng.gen_range(params.food_min..=params.food_max),
+                        value: if rng.gen_bool(params.protein_share as f64) {
+                            PROTEIN_VALUE
+                        } else {
+                            SUGAR_VALUE
+                        },
                     },
                     // Pour les murs ou la zone de mort, on copie juste le type tel quel
                     TileType::Wall => TileType::Wall,
@@ -165,23 +337,109 @@ impl Grid {
 
     pub fn print_grid(&self) {
         println!("Grid {}x{}:", self.width, self.height);
+        print!("{}", self.render_ascii(&[], None, false));
+    }
+
+    // Rendu texte de la grille, une ligne par rangée, avec les fourmis par-dessus les cases
+    // (e/p/f selon le type, en majuscule si elles transportent de la nourriture).
+    // Avec `ansi`, les fourmis prennent la couleur de leur colonie et le fond de chaque case
+    // reflète l'intensité de la piste `pheromones` (carte, valeur Q de pleine intensité).
+    pub fn render_ascii(
+        &self,
+        ants: &[Ant],
+        pheromones: Option<(&PheromoneMap, f32)>,
+        ansi: bool,
+    ) -> String {
+        let shape = self.shape();
+        let mut overlay: Vec<Option<&Ant>> = vec![None; self.tiles.len()];
+        for ant in ants {
+            if let Some(index) = ant.coord().and_then(|coord| shape.index(coord)) {
+                overlay[index.as_usize()] = Some(ant);
+            }
+        }
+
+        let mut out = String::new();
+        let mut last_style = String::new();
         for y in 0..self.height {
             for x in 0..self.width {
-                let tile = &self.tiles[(y * self.width + x) as usize];
-                let ch = match &tile.tile_type {
-                    TileType::Default => '.',
-                    TileType::Wall => 'W',
-                    TileType::DeathZone => 'X',
-                    TileType::FoodSource { .. } => 'F',
-                    TileType::Nest { .. } => 'N',
+                let idx = shape.index_of(Coord::new(x, y)).as_usize();
+                let ch = match overlay[idx] {
+                    Some(ant) => {
+                        let ch = match ant.ant_type {
+                            AntsType::EXPLORER => 'e',
+                            AntsType::PICKER => 'p',
+                            AntsType::FIGHTER => 'f',
+                        };
+                        if ant.current_charge > 0 {
+                            ch.to_ascii_uppercase()
+                        } else {
+                            ch
+                        }
+                    }
+                    None => match &self.tiles[idx].tile_type {
+                        TileType::Default => '.',
+                        TileType::Wall => 'W',
+                        TileType::DeathZone => 'X',
+                        TileType::FoodSource { .. } => 'F',
+                        TileType::Nest { .. } => 'N',
+                        TileType::Mud { .. } => '~',
+                        TileType::Tunnel { .. } => 'O',
+                    },
+                };
+                if !ansi {
+                    out.push(ch);
+                    continue;
+                }
+
+                // Fond : du gris foncé vers le brun de la piste selon sa force
+                let intensity = pheromones.map_or(0.0, |(map, scale)| {
+                    (map.get_max_q(x, y, self).max(0.0) / scale)
+                        .clamp(0.0, 1.0)
+                        .sqrt()
+                });
+                let mix = |c: f32| (30.0 + (c - 30.0) * intensity * 0.8) as u8;
+                let fg = match (overlay[idx], &self.tiles[idx].tile_type) {
+                    (Some(ant), _) => match ant.colony {
+                        0 => "1;38;2;255;215;0",
+                        1 => "1;38;2;255;140;0",
+                        _ => "1;38;2;100;149;237",
+                    },
+                    (None, TileType::Wall) => "37",
+                    (None, TileType::DeathZone) => "31",
+                    (None, TileType::FoodSource { .. }) => "32",
+                    (None, TileType::Nest { .. }) => "1;33",
+                    (None, TileType::Default) => "90",
+                    (None, TileType::Mud { .. }) => "33",
+                    (None, TileType::Tunnel { .. }) => "1;35",
                 };
-                print!("{}", ch);
+                // Les codes ne sont réémis que quand le style change, pour alléger le flux (SSH)
+                let style = format!(
+                    "\x1b[0;48;2;{};{};{};{}m",
+                    mix(139.0),
+                    mix(69.0),
+                    mix(19.0),
+                    fg
+                );
+                if style != last_style {
+                    out.push_str(&style);
+                    last_style = style;
+                }
+                out.push(ch);
             }
-            println!();
+            if ansi {
+                out.push_str("\x1b[0m");
+                last_style.clear();
+            }
+            out.push('\n');
         }
+        out
     }
 
-    pub fn new_with_tiles(width: u32, height: u32, tiles: Vec<Tile>) -> Self {
+    pub fn new_with_tiles(width: u32, height: u32, tiles: Vec<Tile>) -> Result<Self, AntsError> {
+        if width == 0 || height == 0 {
+            return Err(AntsError::EmptyGrid { width, height });
+        }
+
         // Créer la grille de base avec toutes les cases en défaut
         let mut grid_tiles = Vec::new();
         for y in 0..height {
@@ -193,18 +451,24 @@ impl Grid {
         // Placer les tuiles spécifiées à leurs positions respectives
         for tile in tiles {
             let (x, y) = tile.position;
-            if x < width && y < height {
-                let index = (y * width + x) as usize;
-                grid_tiles[index] = tile;
+            if x >= width || y >= height {
+                return Err(AntsError::TileOutOfBounds {
+                    x,
+                    y,
+                    width,
+                    height,
+                });
             }
-            // Ignorer silencieusement les tuiles hors limites
+            let index = GridShape::new(width, height).index_of(Coord::new(x, y));
+            grid_tiles[index.as_usize()] = tile;
         }
 
-        Grid {
+        Ok(Grid {
             tiles: grid_tiles,
             width,
             height,
-        }
+            revision: next_revision(),
+        })
     }
 
     pub fn get_width(&self) -> u32 {
@@ -215,22 +479,28 @@ impl Grid {
         self.height
     }
 
+    /// Dimensions de la grille, pour ranger des données par case dans le même ordre
+    pub fn shape(&self) -> GridShape {
+        GridShape::new(self.width, self.height)
+    }
+
     pub fn get_tile(&self, pos: (u32, u32)) -> Option<&Tile> {
-        let (x, y) = pos;
-        if x < self.width && y < self.height {
-            Some(&self.tiles[(y * self.width + x) as usize])
-        } else {
-            None
-        }
+        let index = self.shape().index(pos.into())?;
+        Some(&self.tiles[index.as_usize()])
+    }
+
+    /// Version de la grille : elle change dès qu'une case peut avoir été modifiée par
+    /// `get_mut_tile`. La repousse de la nourriture et la décomposition des cadavres, qui
+    /// ne changent ni les murs ni les dangers, gardent la même version.
+    pub fn revision(&self) -> u64 {
+        self.revision
     }
 
+    /// Accès en écriture à une case ; la grille change de version (voir `revision`)
     pub fn get_mut_tile(&mut self, pos: (u32, u32)) -> Option<&mut Tile> {
-        let (x, y) = pos;
-        if x < self.width && y < self.height {
-            Some(&mut self.tiles[(y * self.width + x) as usize])
-        } else {
-            None
-        }
+        let index = self.shape().index(pos.into())?;
+        self.revision = next_revision();
+        Some(&mut self.tiles[index.as_usize()])
     }
 
     pub fn get_nest_position(&self) -> Option<(u32, u32)> {
@@ -242,6 +512,66 @@ impl Grid {
         None
     }
 
+    pub fn get_colony_nest_position(&self, colony: usize) -> Option<(u32, u32)> {
+        self.tiles
+            .iter()
+            .find(|tile| tile.nest_colony() == Some(colony))
+            .map(|tile| tile.position)
+    }
+
+    /// Positions de tous les nids, toutes colonies confondues, dans l'ordre de la grille
+    pub fn get_nest_positions(&self) -> Vec<(u32, u32)> {
+        self.tiles
+            .iter()
+            .filter(|tile| tile.is_nest())
+            .map(|tile| tile.position)
+            .collect()
+    }
+
+    /// Positions des nids d'une colonie (plusieurs en mode multi-nids), dans l'ordre de la grille
+    pub fn get_colony_nest_positions(&self, colony: usize) -> Vec<(u32, u32)> {
+        self.tiles
+            .iter()
+            .filter(|tile| tile.nest_colony() == Some(colony))
+            .map(|tile| tile.position)
+            .collect()
+    }
+
+    /// Nourriture stockée dans l'ensemble des nids d'une colonie
+    pub fn colony_stored_food(&self, colony: usize) -> u32 {
+        self.tiles
+            .iter()
+            .filter(|tile| tile.nest_colony() == Some(colony))
+            .filter_map(|tile| tile.stored_food())
+            .sum()
+    }
+
+    /// Places de ce type sur l'ensemble des nids d'une colonie, None si elle n'a pas de nid
+    pub fn colony_nest_capacity(&self, colony: usize, ant_type: AntsType) -> Option<u32> {
+        self.tiles
+            .iter()
+            .filter(|tile| tile.nest_colony() == Some(colony))
+            .filter_map(|tile| tile.nest_capacity(ant_type))
+            .reduce(u32::saturating_add)
+    }
+
+    // Nourriture stockée dans l'ensemble des nids
+    pub fn stored_food_total(&self) -> u32 {
+        self.tiles
+            .iter()
+            .filter_map(|tile| tile.stored_food())
+            .sum()
+    }
+
+    // Nombre de colonies décrites par la carte (au moins une, même sans nid)
+    pub fn colony_count(&self) -> usize {
+        self.tiles
+            .iter()
+            .filter_map(|tile| tile.nest_colony())
+            .max()
+            .map_or(1, |max| max + 1)
+    }
+
     pub fn get_nest(&self) -> Option<&Tile> {
         let pos = self.get_nest_position()?;
         self.get_tile(pos)
@@ -253,13 +583,52 @@ impl Grid {
     }
 
     pub fn get_food_from_nest(&mut self) -> Option<u32> {
-        self.get_nest()?.food_amount()
+        self.get_nest()?.stored_food()
     }
 
-    pub fn add_food_to_nest(&mut self, amount: u32) {
+    pub fn add_food_to_nest(&mut self, amount: u32) -> Result<(), AntsError> {
         self.get_mut_nest()
-            .expect("Nest must exist")
+            .ok_or(AntsError::MissingNest)?
             .add_food_to_nest(amount);
+        Ok(())
+    }
+
+    pub fn take_food_from_nest(&mut self, amount: u32) -> Result<u32, AntsError> {
+        self.get_mut_nest()
+            .map(|nest| nest.take_food_from_nest(amount))
+            .ok_or(AntsError::MissingNest)
+    }
+
+    // Nid de la colonie, modifiable
+    fn colony_nest_mut(&mut self, colony: usize) -> Result<&mut Tile, AntsError> {
+        self.get_colony_nest_position(colony)
+            .and_then(|pos| self.get_mut_tile(pos))
+            .ok_or(AntsError::MissingColonyNest(colony))
+    }
+
+    pub fn add_food_to_colony_nest(&mut self, colony: usize, amount: u32) -> Result<(), AntsError> {
+        self.colony_nest_mut(colony)?.add_food_to_nest(amount);
+        Ok(())
+    }
+
+    /// Retire jusqu'à `amount` unités des réserves de la colonie, nid après nid dans l'ordre
+    /// de la grille ; renvoie la quantité réellement retirée
+    pub fn take_food_from_colony_nest(
+        &mut self,
+        colony: usize,
+        amount: u32,
+    ) -> Result<u32, AntsError> {
+        self.colony_nest_mut(colony)?;
+        let mut taken = 0;
+        for tile in &mut self.tiles {
+            if taken == amount {
+                break;
+            }
+            if tile.nest_colony() == Some(colony) {
+                taken += tile.take_food_from_nest(amount - taken);
+            }
+        }
+        Ok(taken)
     }
 
     pub fn get_walls_positions(&self) -> Vec<(u32, u32)> {
@@ -272,30 +641,132 @@ impl Grid {
         walls
     }
 
+    /// Cases contenant encore de la nourriture
+    pub fn food_positions(&self) -> Vec<(u32, u32)> {
+        self.tiles
+            .iter()
+            .filter(|tile| tile.has_food())
+            .map(|tile| tile.position)
+            .collect()
+    }
+
     pub fn is_walkable(&self, x: u32, y: u32) -> bool {
         // Si la tuile existe, on demande à la tuile. Sinon (hors map), c'est false.
+        // Un tunnel dont la sortie est hors de la carte ou murée est bouché.
+        self.get_tile((x, y))
+            .is_some_and(|tile| match tile.tunnel_exit() {
+                Some(exit) => self.get_tile(exit).is_some_and(|exit| exit.is_walkable()),
+                None => tile.is_walkable(),
+            })
+    }
+
+    /// Case où arrive une fourmi qui entre en (x, y) : la sortie pour un tunnel, la case
+    /// elle-même sinon (les tunnels ne s'enchaînent pas)
+    pub fn destination(&self, x: u32, y: u32) -> (u32, u32) {
+        self.get_tile((x, y))
+            .and_then(|tile| tile.tunnel_exit())
+            .filter(|&(ex, ey)| ex < self.width && ey < self.height)
+            .unwrap_or((x, y))
+    }
+
+    /// Multiplicateur du temps de déplacement d'une fourmi qui entre en (x, y)
+    pub fn slow_factor(&self, x: u32, y: u32) -> u32 {
         self.get_tile((x, y))
-            .map_or(false, |tile| tile.is_walkable())
+            .and_then(|tile| tile.slow_factor())
+            .unwrap_or(1)
     }
 
     pub fn is_lethal(&self, x: u32, y: u32) -> bool {
         // Si la tuile existe, on demande à la tuile. Sinon (hors map), c'est false.
-        self.get_tile((x, y)).map_or(false, |tile| tile.is_lethal())
+        self.get_tile((x, y)).is_some_and(|tile| tile.is_lethal())
     }
 
     pub fn has_food(&self, x: u32, y: u32) -> bool {
         // Si la tuile existe, on demande à la tuile. Sinon (hors map), c'est false.
-        self.get_tile((x, y)).map_or(false, |tile| tile.has_food())
+        self.get_tile((x, y)).is_some_and(|tile| tile.has_food())
     }
 
     pub fn is_nest(&self, x: u32, y: u32) -> bool {
         // Si la tuile existe, on demande à la tuile. Sinon (hors map), c'est false.
-        self.get_tile((x, y)).map_or(false, |tile| tile.is_nest())
+        self.get_tile((x, y)).is_some_and(|tile| tile.is_nest())
+    }
+
+    pub fn is_colony_nest(&self, x: u32, y: u32, colony: usize) -> bool {
+        self.get_tile((x, y))
+            .is_some_and(|tile| tile.nest_colony() == Some(colony))
+    }
+
+    /// Fait repousser les sources de nourriture dont c'est le tour au tick `tick` ;
+    /// `default` s'applique aux sources sans repousse propre
+    pub fn regrow_food(&mut self, default: Option<FoodRegrowth>, tick: usize) {
+        for tile in &mut self.tiles {
+            let Some(rule) = tile.food_regrowth(default) else {
+                continue;
+            };
+            if !tick.is_multiple_of(rule.interval as usize) {
+                continue;
+            }
+            if let TileType::FoodSource { amount, .. } = &mut tile.tile_type {
+                if *amount < rule.cap {
+                    *amount = (*amount + rule.amount).min(rule.cap);
+                }
+            }
+        }
+    }
+
+    /// Dépose un cadavre de `amount` unités en (x, y) : sur une case vide il devient une
+    /// source temporaire, sur un autre cadavre il s'y ajoute ; ailleurs il est perdu
+    pub fn drop_corpse(&mut self, (x, y): (u32, u32), amount: u32, decay: u32) -> bool {
+        let Some(tile) = self.get_mut_tile((x, y)) else {
+            return false;
+        };
+        match &mut tile.tile_type {
+            TileType::Default => {
+                tile.tile_type = TileType::FoodSource {
+                    amount,
+                    value: SUGAR_VALUE,
+                };
+                tile.decay = Some(decay.max(1));
+            }
+            TileType::FoodSource {
+                amount: current, ..
+            } if tile.decay.is_some() => {
+                *current += amount;
+            }
+            _ => return false,
+        }
+        true
+    }
+
+    /// Fait pourrir les cadavres au tick `tick` ; un cadavre vide redevient une case vide
+    pub fn decay_corpses(&mut self, tick: usize) {
+        for tile in &mut self.tiles {
+            let Some(decay) = tile.decay else {
+                continue;
+            };
+            if let TileType::FoodSource { amount, .. } = &mut tile.tile_type {
+                if tick.is_multiple_of(decay as usize) {
+                    *amount = amount.saturating_sub(1);
+                }
+                if *amount > 0 {
+                    continue;
+                }
+            }
+            tile.tile_type = TileType::Default;
+            tile.decay = None;
+        }
+    }
+
+    /// Au moins une source de nourriture repousse : la carte ne sera jamais épuisée
+    pub fn has_regrowing_food(&self, default: Option<FoodRegrowth>) -> bool {
+        self.tiles
+            .iter()
+            .any(|tile| tile.food_regrowth(default).is_some_and(|rule| rule.cap > 0))
     }
 
     pub fn is_food_remaining(&self) -> bool {
         for tile in &self.tiles {
-            if let TileType::FoodSource { amount } = tile.tile_type {
+            if let TileType::FoodSource { amount, .. } = tile.tile_type {
                 if amount > 0 {
                     return true;
                 }
@@ -304,3 +775,88 @@ impl Grid {
         false
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Grille 7x3 dont les coins et une case intérieure se distinguent, pour qu'une largeur
+    // et une hauteur inversées ne passent pas inaperçues
+    fn asymmetric_grid() -> Grid {
+        let mut grid = Grid::new(7, 3);
+        for (position, tile_type) in [
+            ((0, 0), TileType::Wall),
+            ((6, 0), TileType::DeathZone),
+            ((6, 2), TileType::Wall),
+            ((2, 1), TileType::DeathZone),
+        ] {
+            if let Some(tile) = grid.get_mut_tile(position) {
+                tile.tile_type = tile_type;
+            }
+        }
+        grid
+    }
+
+    fn tiles(grid: &Grid) -> Vec<Tile> {
+        (0..grid.get_height())
+            .flat_map(|y| (0..grid.get_width()).map(move |x| (x, y)))
+            .filter_map(|position| grid.get_tile(position).cloned())
+            .collect()
+    }
+
+    fn assert_same(loaded: &Grid, grid: &Grid) {
+        assert_eq!(loaded.get_width(), 7);
+        assert_eq!(loaded.get_height(), 3);
+        assert_eq!(tiles(loaded), tiles(grid));
+        assert_eq!(
+            loaded.get_tile((6, 0)).map(|tile| &tile.tile_type),
+            Some(&TileType::DeathZone)
+        );
+    }
+
+    #[test]
+    fn asymmetric_grid_round_trips_through_json() {
+        let grid = asymmetric_grid();
+        let json = serde_json::to_string(&grid).unwrap();
+        let loaded: Grid = serde_json::from_str(&json).unwrap();
+        assert_same(&loaded, &grid);
+    }
+
+    #[test]
+    fn asymmetric_grid_round_trips_through_bincode() {
+        let grid = asymmetric_grid();
+        let bytes = bincode::serialize(&grid).unwrap();
+        let loaded: Grid = bincode::deserialize(&bytes).unwrap();
+        assert_same(&loaded, &grid);
+    }
+
+    // Forme sérialisée de la grille 7x3, modifiée par `change`, puis relue
+    fn load_altered(change: impl FnOnce(&mut serde_json::Value)) -> Result<Grid, String> {
+        let mut value = serde_json::to_value(asymmetric_grid()).unwrap();
+        change(&mut value);
+        serde_json::from_value(value).map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn swapped_dimensions_are_rejected() {
+        let loaded = load_altered(|value| {
+            value["width"] = 3.into();
+            value["height"] = 7.into();
+        });
+        assert!(loaded.is_err_and(|e| e.contains("rangée")));
+    }
+
+    #[test]
+    fn wrong_tile_count_is_rejected() {
+        let loaded = load_altered(|value| {
+            value["tiles"].as_array_mut().unwrap().pop();
+        });
+        assert!(loaded.is_err_and(|e| e.contains("attendues")));
+    }
+
+    #[test]
+    fn newer_format_is_rejected() {
+        let loaded = load_altered(|value| value["version"] = (GRID_FORMAT_VERSION + 1).into());
+        assert!(loaded.is_err_and(|e| e.contains("plus récent")));
+    }
+}
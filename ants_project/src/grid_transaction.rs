@@ -0,0 +1,65 @@
+//! Transactions sur la carte : modifier des cases en cours de partie sans tromper l'apprentissage
+//!
+//! L'édition en direct (mode Dieu, `AntsGameManager::edit_tile`), les événements de scénario
+//! et le code appelant modifient les cases par une [`GridTransaction`], appliquée par
+//! `AntsGameManager::apply_transaction`. Toutes les cases sont vérifiées avant d'en
+//! modifier une seule, puis les Q-tables de toutes les colonies sont corrigées :
+//!
+//! - une case devenue mur revient à la valeur initiale des Q-tables, comme les pas qui y
+//!   menaient depuis ses voisines : la politique apprise ne compte plus sur ce passage, et
+//!   la case repart de zéro si elle est un jour rouverte ;
+//! - une case devenue mortelle pénalise les pas qui y mènent (`reward_death`), tue les
+//!   fourmis présentes et y dépose la peur de leur colonie, comme les zones des aléas.
+//!
+//! Une case rouverte (mur ou zone mortelle retirés) n'est pas corrigée : les fourmis la
+//! redécouvrent en explorant. Les fourmis murées sont rappelées à leur nid, et le snapshot
+//! du tick courant reflète la carte modifiée, de sorte qu'un rembobinage la retrouve.
+
+use crate::tile::TileType;
+
+/// Modifications de cases à appliquer d'un bloc
+#[derive(Clone, Debug, Default)]
+pub struct GridTransaction {
+    changes: Vec<((u32, u32), TileType)>,
+}
+
+impl GridTransaction {
+    pub fn new() -> Self {
+        GridTransaction::default()
+    }
+
+    /// Remplace la case `position` par `tile_type` ; pour une case modifiée plusieurs fois,
+    /// la dernière modification l'emporte
+    pub fn set(&mut self, position: (u32, u32), tile_type: TileType) {
+        self.changes.push((position, tile_type));
+    }
+
+    /// Comme `set`, pour enchaîner les modifications
+    pub fn with_tile(mut self, position: (u32, u32), tile_type: TileType) -> Self {
+        self.set(position, tile_type);
+        self
+    }
+
+    pub fn changes(&self) -> &[((u32, u32), TileType)] {
+        &self.changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+}
+
+/// Bilan d'une transaction appliquée
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TransactionReport {
+    /// Cases dont le type a changé
+    pub changed: usize,
+    /// Cases devenues murs, dont les Q-values sont revenues à la valeur initiale
+    pub walled: usize,
+    /// Cases devenues mortelles, dont les accès ont été pénalisés
+    pub lethal: usize,
+    /// Fourmis tuées par une case devenue mortelle
+    pub killed: usize,
+    /// Fourmis rappelées à leur nid depuis une case devenue mur
+    pub recalled: usize,
+}
@@ -0,0 +1,77 @@
+//! Zones mortelles scriptées
+//!
+//! Une zone scriptée couvre un groupe de cases qui peut clignoter (mortelle pendant
+//! `on_ticks` ticks au début de chaque période de `period` ticks) et dériver à travers la
+//! carte (décalage de `drift` tous les `drift_interval` ticks, en revenant par le bord
+//! opposé). Les zones se déclarent dans le fichier de configuration, en `[[hazards]]`.
+//! Elles ne recouvrent que des cases vides : murs, nids et nourriture restent en place.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+/// Groupe de cases mortelles animé au fil des ticks
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct HazardScript {
+    // Cases couvertes au tick 0
+    pub cells: Vec<(u32, u32)>,
+    // Durée d'un cycle de clignotement (0 = toujours active)
+    pub period: u32,
+    // Ticks actifs au début de chaque cycle
+    pub on_ticks: u32,
+    // Décalage appliqué à chaque déplacement
+    pub drift: (i32, i32),
+    // Ticks entre deux déplacements (0 = immobile)
+    pub drift_interval: u32,
+}
+
+impl HazardScript {
+    pub fn is_active(&self, tick: usize) -> bool {
+        self.period == 0 || tick % (self.period as usize) < self.on_ticks as usize
+    }
+
+    /// Cases mortelles au tick `tick` sur une carte `width` x `height`
+    pub fn cells_at(&self, tick: usize, width: u32, height: u32) -> Vec<(u32, u32)> {
+        if !self.is_active(tick) || width == 0 || height == 0 {
+            return Vec::new();
+        }
+        let moves = match self.drift_interval {
+            0 => 0,
+            interval => (tick / interval as usize) as i64,
+        };
+        let (dx, dy) = (self.drift.0 as i64 * moves, self.drift.1 as i64 * moves);
+        self.cells
+            .iter()
+            .filter(|&&(x, y)| x < width && y < height)
+            .map(|&(x, y)| {
+                (
+                    (x as i64 + dx).rem_euclid(width as i64) as u32,
+                    (y as i64 + dy).rem_euclid(height as i64) as u32,
+                )
+            })
+            .collect()
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.period > 0 && (self.on_ticks == 0 || self.on_ticks > self.period) {
+            return Err("hazards: on_ticks doit être compris entre 1 et period".to_string());
+        }
+        if self.drift != (0, 0) && self.drift_interval == 0 {
+            return Err("hazards: drift demande un drift_interval > 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Réunion des cases mortelles de toutes les zones au tick `tick`
+pub fn lethal_cells(
+    scripts: &[HazardScript],
+    tick: usize,
+    width: u32,
+    height: u32,
+) -> HashSet<(u32, u32)> {
+    scripts
+        .iter()
+        .flat_map(|script| script.cells_at(tick, width, height))
+        .collect()
+}
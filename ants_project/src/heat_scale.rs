@@ -0,0 +1,76 @@
+//! Échelle de couleur des pistes de phéromones dans la GUI
+//!
+//! Avec de fortes récompenses, les valeurs Q dépassent vite une échelle fixe et toute la
+//! carte sature. L'échelle automatique suit la plage des valeurs de chaque carte : elle
+//! s'élargit dès qu'une valeur en sort et se resserre lentement quand les pistes
+//! s'évaporent, pour que les couleurs ne clignotent pas d'une image à l'autre.
+
+use crate::grid::Grid;
+use crate::pheromone::PheromoneMap;
+
+// Part de l'écart comblée à chaque image quand la plage se resserre
+const SHRINK_RATE: f32 = 0.05;
+
+/// Plage des valeurs Q converties en intensité : `min` est transparent, `max` plein
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HeatScale {
+    pub min: f32,
+    pub max: f32,
+}
+
+impl Default for HeatScale {
+    fn default() -> Self {
+        HeatScale { min: 0.0, max: 0.0 }
+    }
+}
+
+impl HeatScale {
+    /// Échelle fixe de 0 à `max` (réglage `q_display_scale`)
+    pub fn fixed(max: f32) -> Self {
+        HeatScale { min: 0.0, max }
+    }
+
+    /// Suit la plage (plus faible, plus forte) valeur de l'image ; sans valeur, la plage
+    /// se resserre vers zéro
+    pub fn track(&mut self, range: Option<(f32, f32)>) {
+        let (low, high) = range.unwrap_or((0.0, 0.0));
+        self.min = if low < self.min {
+            low
+        } else {
+            self.min + (low - self.min) * SHRINK_RATE
+        };
+        self.max = if high > self.max {
+            high
+        } else {
+            self.max + (high - self.max) * SHRINK_RATE
+        };
+    }
+
+    /// Intensité dans [0, 1] de la valeur `q` : racine de la position dans la plage (pour
+    /// faire ressortir les faibles valeurs), ou son logarithme avec `log`
+    pub fn intensity(&self, q: f32, log: bool) -> f32 {
+        let span = self.max - self.min;
+        if span <= f32::EPSILON {
+            return 0.0;
+        }
+        let offset = (q - self.min).clamp(0.0, span);
+        if log {
+            offset.ln_1p() / span.ln_1p()
+        } else {
+            (offset / span).sqrt()
+        }
+    }
+}
+
+/// Plus faible et plus forte valeur Q maximale (négatifs ramenés à 0) des cases praticables
+/// où la carte a appris quelque chose ; None si elle est vide
+pub fn value_range(map: &PheromoneMap, grid: &Grid) -> Option<(f32, f32)> {
+    map.active_cells()
+        .into_iter()
+        .filter(|&(x, y)| grid.is_walkable(x, y))
+        .map(|(x, y)| map.get_max_q(x, y, grid).max(0.0))
+        .fold(None, |range, q| match range {
+            None => Some((q, q)),
+            Some((low, high)) => Some((f32::min(low, q), f32::max(high, q))),
+        })
+}
@@ -0,0 +1,333 @@
+//! Rapport HTML autonome d'une partie CLI
+//!
+//! `--report <FICHIER>` écrit en fin de partie une seule page HTML, sans feuille de style ni
+//! script externes, à partager telle quelle : le bilan de `--summary`, les courbes
+//! d'apprentissage du panneau de statistiques (SVG), les cartes de chaleur finales des
+//! Q-tables de la première colonie (celles de `--dump-qtable`) et la configuration jouée en
+//! TOML. Avec `--report-gif`, l'animation de la partie (une image tous les
+//! `--frame-interval` ticks) est encodée dans la page.
+
+use crate::ant::AntsType;
+use crate::ants_game_manager::AntsGameManager;
+use crate::i18n::tr;
+use crate::meta_controller::ControllerDecision;
+use crate::metrics::{AntTypeStats, StatsSeries};
+use crate::runner::RunReport;
+use std::fmt::Write;
+use std::fs;
+
+// Dimensions d'une courbe, en pixels
+const CHART_WIDTH: f64 = 360.0;
+const CHART_HEIGHT: f64 = 200.0;
+// Marges autour du tracé : graduations à gauche et en bas, titre en haut
+const CHART_LEFT: f64 = 56.0;
+const CHART_RIGHT: f64 = 12.0;
+const CHART_TOP: f64 = 28.0;
+const CHART_BOTTOM: f64 = 24.0;
+
+// Teintes des courbes, comme le thème par défaut de la GUI
+const CURVE: &str = "#ffaa00";
+const EPSILON_CURVE: &str = "#3c6eff";
+const ALPHA_CURVE: &str = "#ff69b4";
+
+const STYLE: &str = "body{background:#1e1e1e;color:#ddd;font-family:sans-serif;margin:2em}\
+h1,h2{color:#fff}table{border-collapse:collapse;margin-bottom:1em}\
+td,th{border:1px solid #555;padding:4px 10px;text-align:right}th{background:#2c2c2c}\
+td:first-child{text-align:left}.grid{display:flex;flex-wrap:wrap;gap:16px}\
+figure{margin:0}figcaption{text-align:center}figure svg{max-width:480px;height:auto}\
+pre{background:#2c2c2c;padding:1em;overflow:auto}";
+
+/// Écrit le rapport de la partie dans `path` ; `animation` est un GIF de la partie
+pub fn write_report(
+    path: &str,
+    report: &RunReport,
+    manager: &AntsGameManager,
+    animation: Option<&[u8]>,
+) -> Result<(), String> {
+    let html = render_report(report, manager, animation)?;
+    fs::write(path, html).map_err(|e| format!("Impossible d'écrire {}: {}", path, e))
+}
+
+/// Page HTML du rapport, sans passer par le disque
+pub fn render_report(
+    report: &RunReport,
+    manager: &AntsGameManager,
+    animation: Option<&[u8]>,
+) -> Result<String, String> {
+    let config = toml::to_string_pretty(report.config)
+        .map_err(|e| format!("Impossible de sérialiser la configuration: {}", e))?;
+
+    let mut html = String::new();
+    let _ = writeln!(
+        html,
+        "<!DOCTYPE html>\n<html lang=\"fr\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <title>Simulation de fourmis (graine {})</title>\n<style>{}</style>\n</head>\n<body>",
+        report.seed, STYLE
+    );
+    let _ = writeln!(
+        html,
+        "<h1>Simulation de fourmis</h1>\n<p>Graine {}, {} ticks{}</p>",
+        report.seed,
+        report.ticks,
+        report
+            .end_reason
+            .map_or_else(String::new, |reason| format!(", fin : {}", reason))
+    );
+
+    html.push_str("<h2>Bilan</h2>\n");
+    summary_table(&mut html, report);
+    type_table(&mut html, report);
+
+    html.push_str("<h2>Courbes d'apprentissage</h2>\n<div class=\"grid\">\n");
+    let points = manager.stats_history();
+    for series in StatsSeries::all() {
+        let curve: Vec<[f64; 2]> = points
+            .iter()
+            .map(|point| [point.tick as f64, series.value(point)])
+            .collect();
+        html.push_str(&chart_svg(tr(series.key()), &[("", CURVE, curve)]));
+    }
+    if !report.controller.is_empty() {
+        let curve = |value: fn(&ControllerDecision) -> f32| {
+            report
+                .controller
+                .iter()
+                .map(|decision| [decision.tick as f64, value(decision) as f64])
+                .collect::<Vec<_>>()
+        };
+        html.push_str(&chart_svg(
+            tr("controller.title"),
+            &[
+                ("epsilon", EPSILON_CURVE, curve(|decision| decision.epsilon)),
+                ("alpha", ALPHA_CURVE, curve(|decision| decision.alpha)),
+            ],
+        ));
+    }
+    html.push_str("</div>\n");
+
+    html.push_str("<h2>Q-tables finales (colonie A)</h2>\n<div class=\"grid\">\n");
+    let colony = &manager.colonies()[0];
+    let types: Vec<AntsType> = if colony.has_per_type_maps() {
+        AntsType::all().to_vec()
+    } else {
+        vec![AntsType::EXPLORER]
+    };
+    for ant_type in types {
+        let table = colony.table(ant_type);
+        for (layer, map) in table.layers().iter().enumerate() {
+            let mut name = table.layer_name(layer);
+            if colony.has_per_type_maps() {
+                name = format!("{} {}", ant_type.name(), name);
+            }
+            let _ = writeln!(
+                html,
+                "<figure>{}<figcaption>{}</figcaption></figure>",
+                map.to_svg(manager.grid()),
+                escape(&name)
+            );
+        }
+    }
+    html.push_str("</div>\n");
+
+    if let Some(gif) = animation {
+        let _ = writeln!(
+            html,
+            "<h2>Animation</h2>\n<img alt=\"Animation de la partie\" src=\"data:image/gif;base64,{}\">",
+            base64(gif)
+        );
+    }
+
+    let _ = writeln!(
+        html,
+        "<h2>Configuration</h2>\n<pre>{}</pre>\n</body>\n</html>",
+        escape(&config)
+    );
+    Ok(html)
+}
+
+// Compteurs de la partie entière
+fn summary_table(html: &mut String, report: &RunReport) {
+    let optional =
+        |value: Option<f64>| value.map_or_else(|| "-".to_string(), |v| format!("{:.1}", v));
+    let rows = [
+        ("Ticks joués", report.ticks.to_string()),
+        (
+            "Colonie gagnante",
+            report
+                .winner
+                .map_or_else(|| "-".to_string(), |winner| winner.to_string()),
+        ),
+        ("Nourriture livrée", report.food_delivered.to_string()),
+        ("Morts", report.deaths.to_string()),
+        ("Blocages détectés", report.stuck_ants.to_string()),
+        (
+            "Aller-retour moyen (cases)",
+            optional(report.mean_trip_length),
+        ),
+        (
+            "Plus court aller-retour (cases)",
+            report
+                .optimal_round_trip
+                .map_or_else(|| "-".to_string(), |cells| cells.to_string()),
+        ),
+        ("Regret par aller-retour", optional(report.trip_regret)),
+    ];
+    html.push_str("<table>\n");
+    for (label, value) in rows {
+        let _ = writeln!(html, "<tr><td>{}</td><td>{}</td></tr>", label, value);
+    }
+    html.push_str("</table>\n");
+}
+
+// Compteurs de chaque type de fourmi
+fn type_table(html: &mut String, report: &RunReport) {
+    html.push_str(
+        "<table>\n<tr><th>Type</th><th>Fourmis</th><th>Allers-retours</th>\
+         <th>Distance</th><th>Nourriture livrée</th><th>Nourriture / 1000 ticks</th></tr>\n",
+    );
+    let rows: [(AntsType, &AntTypeStats); 3] = [
+        (AntsType::EXPLORER, &report.explorers),
+        (AntsType::PICKER, &report.pickers),
+        (AntsType::FIGHTER, &report.fighters),
+    ];
+    for (ant_type, stats) in rows {
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{:.1}</td></tr>",
+            ant_type.name(),
+            stats.ants,
+            stats.trips,
+            stats.distance,
+            stats.food_delivered,
+            stats.food_per_kilotick()
+        );
+    }
+    html.push_str("</table>\n");
+}
+
+// Courbes (légende, couleur, points) sur des axes communs, avec les bornes en graduation
+fn chart_svg(title: &str, series: &[(&str, &str, Vec<[f64; 2]>)]) -> String {
+    let points = || series.iter().flat_map(|(_, _, points)| points);
+    let (x_min, x_max) = points().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+        (lo.min(p[0]), hi.max(p[0]))
+    });
+    let (y_min, y_max) = points().fold((0.0_f64, 0.0_f64), |(lo, hi), p| {
+        (lo.min(p[1]), hi.max(p[1]))
+    });
+    let (x_min, x_max) = if x_min < x_max {
+        (x_min, x_max)
+    } else if x_min.is_finite() {
+        (x_min, x_min + 1.0)
+    } else {
+        (0.0, 1.0)
+    };
+    let y_max = if y_max > y_min { y_max } else { y_min + 1.0 };
+
+    let plot_width = CHART_WIDTH - CHART_LEFT - CHART_RIGHT;
+    let plot_height = CHART_HEIGHT - CHART_TOP - CHART_BOTTOM;
+    let to_x = |x: f64| CHART_LEFT + (x - x_min) / (x_max - x_min) * plot_width;
+    let to_y = |y: f64| CHART_TOP + (1.0 - (y - y_min) / (y_max - y_min)) * plot_height;
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r##"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}" font-size="11" fill="#ddd">"##,
+        w = CHART_WIDTH,
+        h = CHART_HEIGHT
+    );
+    let _ = writeln!(
+        svg,
+        r##"<rect width="100%" height="100%" fill="#2c2c2c"/><text x="{}" y="18" font-size="13">{}</text>"##,
+        CHART_LEFT,
+        escape(title)
+    );
+    let bottom = CHART_TOP + plot_height;
+    let _ = writeln!(
+        svg,
+        r##"<path d="M{l},{t} V{b} H{r}" stroke="#888" fill="none"/>"##,
+        l = CHART_LEFT,
+        t = CHART_TOP,
+        b = bottom,
+        r = CHART_LEFT + plot_width
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{x}" y="{t}" text-anchor="end" dominant-baseline="hanging">{}</text><text x="{x}" y="{b}" text-anchor="end">{}</text>"#,
+        axis_label(y_max),
+        axis_label(y_min),
+        x = CHART_LEFT - 4.0,
+        t = CHART_TOP,
+        b = bottom
+    );
+    let _ = writeln!(
+        svg,
+        r#"<text x="{}" y="{y}">{}</text><text x="{}" y="{y}" text-anchor="end">{}</text>"#,
+        CHART_LEFT,
+        axis_label(x_min),
+        CHART_LEFT + plot_width,
+        axis_label(x_max),
+        y = CHART_HEIGHT - 6.0
+    );
+    let mut legend_x = CHART_WIDTH - CHART_RIGHT;
+    for (label, color, points) in series.iter().rev() {
+        let path: Vec<String> = points
+            .iter()
+            .map(|p| format!("{:.1},{:.1}", to_x(p[0]), to_y(p[1])))
+            .collect();
+        let _ = writeln!(
+            svg,
+            r#"<polyline points="{}" stroke="{}" stroke-width="1.5" fill="none"/>"#,
+            path.join(" "),
+            color
+        );
+        if !label.is_empty() {
+            let _ = writeln!(
+                svg,
+                r#"<text x="{}" y="18" text-anchor="end" fill="{}">{}</text>"#,
+                legend_x,
+                color,
+                escape(label)
+            );
+            legend_x -= 8.0 * label.len() as f64 + 8.0;
+        }
+    }
+    svg.push_str("</svg>\n");
+    svg
+}
+
+// Graduation lisible : entière au-delà de 100, deux décimales en dessous
+fn axis_label(value: f64) -> String {
+    if value.abs() >= 100.0 || value.fract() == 0.0 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+// Texte inséré tel quel dans la page
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+// Encodage base64 standard (avec remplissage) des images intégrées à la page
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let triple = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &byte)| acc | (byte as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(triple >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
@@ -0,0 +1,698 @@
+//! Traduction de l'interface graphique (français / anglais)
+//!
+//! Les textes de l'interface et de l'éditeur de carte sont rangés dans [`TEXTS`], indexés par
+//! un identifiant (`"game.start"`). [`tr`] renvoie le texte dans la langue courante et [`trf`]
+//! y remplace les `{}` dans l'ordre. La langue est globale : elle se change dans le menu
+//! « Réglages » de l'interface, qui la garde d'une session à l'autre. Le mode CLI ne la
+//! change jamais et reste en français, comme le journal d'événements.
+
+use crate::ant::AntsType;
+use crate::event_log::LogLevel;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+/// Langue de l'interface
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    #[default]
+    French,
+    English,
+}
+
+impl Language {
+    pub fn all() -> [Language; 2] {
+        [Language::French, Language::English]
+    }
+
+    /// Nom de la langue dans cette langue, pour le menu
+    pub fn label(&self) -> &'static str {
+        match self {
+            Language::French => "Français",
+            Language::English => "English",
+        }
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Langue courante de l'interface
+pub fn language() -> Language {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Language::English,
+        _ => Language::French,
+    }
+}
+
+pub fn set_language(language: Language) {
+    CURRENT.store(language as u8, Ordering::Relaxed);
+}
+
+/// Texte de `key` dans la langue courante ; une clé absente de la table est renvoyée telle
+/// quelle pour rester visible à l'écran
+pub fn tr(key: &'static str) -> &'static str {
+    static TABLE: OnceLock<HashMap<&'static str, [&'static str; 2]>> = OnceLock::new();
+    let table = TABLE.get_or_init(|| TEXTS.iter().map(|&(key, fr, en)| (key, [fr, en])).collect());
+    table
+        .get(key)
+        .map_or(key, |texts| texts[language() as usize])
+}
+
+/// Texte de `key` dont chaque `{}` est remplacé, dans l'ordre, par un élément de `args`
+pub fn trf(key: &'static str, args: &[&dyn Display]) -> String {
+    let mut parts = tr(key).split("{}");
+    let mut text = parts.next().unwrap_or_default().to_string();
+    for (part, arg) in parts.zip(args.iter().map(Some).chain(std::iter::repeat(None))) {
+        if let Some(arg) = arg {
+            text.push_str(&arg.to_string());
+        }
+        text.push_str(part);
+    }
+    text
+}
+
+/// Nom d'un type de fourmi à l'écran (`AntsType::label` reste en français pour le journal)
+pub fn ant_type(ant_type: AntsType) -> &'static str {
+    tr(match ant_type {
+        AntsType::EXPLORER => "ant_type.explorer",
+        AntsType::PICKER => "ant_type.picker",
+        AntsType::FIGHTER => "ant_type.fighter",
+    })
+}
+
+/// Niveau du journal à l'écran (`LogLevel::label` reste celui des lignes du journal)
+pub fn log_level(level: LogLevel) -> &'static str {
+    tr(match level {
+        LogLevel::Error => "log_level.error",
+        LogLevel::Warn => "log_level.warn",
+        LogLevel::Info => "log_level.info",
+        LogLevel::Debug => "log_level.debug",
+        LogLevel::Trace => "log_level.trace",
+    })
+}
+
+// (identifiant, français, anglais), regroupés par écran
+#[rustfmt::skip]
+const TEXTS: &[(&str, &str, &str)] = &[
+    // Réglages
+    ("settings.menu", "⚙ Réglages", "⚙ Settings"),
+    ("settings.language", "Langue :", "Language:"),
+    ("settings.theme", "Thème :", "Theme:"),
+    ("theme.default", "Par défaut", "Default"),
+    ("theme.colorblind", "Daltonisme", "Colorblind-safe"),
+    ("theme.high_contrast", "Contraste élevé", "High contrast"),
+    ("theme.grayscale", "Niveaux de gris (impression)", "Grayscale (print)"),
+    ("session.menu", "💾 Session", "💾 Session"),
+    ("session.file", "Fichier de session :", "Session file:"),
+    ("session.save", "Enregistrer", "Save"),
+    ("session.save_hint", "Enregistre la partie, sa timeline et les réglages de l'interface pour reprendre la démonstration plus tard", "Saves the game, its timeline and the interface settings to resume the demo later"),
+    ("session.open", "Ouvrir", "Open"),
+    ("session.saved", "Session enregistrée dans {}", "Session saved to {}"),
+
+    // Raccourcis clavier du plateau
+    ("shortcuts.button", "⌨ Raccourcis", "⌨ Shortcuts"),
+    ("shortcuts.hover", "Touche ? ou F1", "Key ? or F1"),
+    ("shortcuts.title", "Raccourcis clavier", "Keyboard shortcuts"),
+    ("shortcuts.key_space", "Espace", "Space"),
+    ("shortcuts.key_right", "→", "→"),
+    ("shortcuts.key_plus_minus", "+ / -", "+ / -"),
+    ("shortcuts.key_f", "F", "F"),
+    ("shortcuts.key_r", "R", "R"),
+    ("shortcuts.key_help", "? / F1", "? / F1"),
+    ("shortcuts.start_pause", "Lancer, mettre en pause ou reprendre", "Start, pause or resume"),
+    ("shortcuts.step", "Avancer d'un tick (en pause)", "Step one tick (while paused)"),
+    ("shortcuts.speed", "Accélérer / ralentir la simulation", "Speed up / slow down the simulation"),
+    ("shortcuts.pheromones", "Afficher / cacher les phéromones", "Show / hide pheromones"),
+    ("shortcuts.reset", "Revenir au début de la timeline", "Rewind to the start of the timeline"),
+    ("shortcuts.help", "Afficher cette aide", "Show this help"),
+
+    // Presets
+    ("presets.title", "Presets", "Presets"),
+    ("presets.none", "(aucun)", "(none)"),
+    ("presets.load", "Charger", "Load"),
+    ("presets.delete", "Supprimer", "Delete"),
+    ("presets.locked", "Chargement possible avant le lancement seulement", "Can only be loaded before the start"),
+    ("presets.name", "Nom du preset", "Preset name"),
+    ("presets.save", "Enregistrer", "Save"),
+    ("sweep.title", "Méta-optimiseur", "Meta-optimizer"),
+    ("sweep.min", "min", "min"),
+    ("sweep.max", "max", "max"),
+    ("sweep.steps", "valeurs", "values"),
+    ("sweep.repeats", "Répétitions :", "Repeats:"),
+    ("sweep.ticks", "Ticks max :", "Max ticks:"),
+    ("sweep.start", "Lancer le balayage", "Start the sweep"),
+    ("sweep.stop", "Arrêter", "Stop"),
+    ("sweep.simulations", "{} simulation(s) sur cette carte", "{} simulation(s) on this map"),
+    ("sweep.best", "Meilleur : alpha={} gamma={} epsilon={} ({})", "Best: alpha={} gamma={} epsilon={} ({})"),
+    ("sweep.apply", "Appliquer la meilleure configuration", "Apply the best configuration"),
+    ("sweep.apply_locked", "Les paramètres ne changent plus une fois la partie lancée", "Parameters are locked once the game has started"),
+    ("preset.fast_learner", "Apprentissage rapide", "Fast learner"),
+    ("preset.cautious_explorer", "Exploratrice prudente", "Cautious explorer"),
+
+    // Textes communs
+    ("common.back", "Retour", "Back"),
+    ("common.fit_view", "Ajuster à la vue", "Fit to view"),
+    ("common.unavailable_replay", "Indisponible pendant une relecture", "Unavailable during a replay"),
+    ("common.ratios", "Proportions :", "Ratios:"),
+    ("common.units", "unités", "units"),
+
+    // Écrans de préparation
+    ("dimensions.title", "Définir les dimensions de la grille", "Set the grid size"),
+    ("dimensions.width", "Largeur :", "Width:"),
+    ("dimensions.height", "Hauteur :", "Height:"),
+    ("dimensions.continue", "Continuer", "Continue"),
+    ("tutorial.button", "Tutoriel", "Tutorial"),
+    ("tutorial.button_hint", "Une partie guidée sur de petites cartes : le plateau, les couches de phéromones et les réglages du Q-learning", "A guided game on tiny maps: the board, the pheromone layers and the Q-learning settings"),
+    ("tutorial.progress", "Étape {} / {}", "Step {} / {}"),
+    ("tutorial.previous", "Précédent", "Previous"),
+    ("tutorial.next", "Suivant", "Next"),
+    ("tutorial.finish", "Terminer", "Finish"),
+    ("tutorial.quit", "Quitter le tutoriel", "Leave the tutorial"),
+    ("tutorial.waiting_start", "En attente du lancement de la partie...", "Waiting for the game to start..."),
+    ("tutorial.waiting_tick", "Tick {} / {}...", "Tick {} / {}..."),
+    ("tutorial.welcome.title", "Bienvenue", "Welcome"),
+    ("tutorial.welcome.text", "Sur ce petit plateau, le nid est en haut à gauche et la nourriture en bas à droite, derrière un mur. Les fourmis ne connaissent pas le chemin : elles l'apprennent par Q-learning. Chaque pas leur rapporte une récompense (un petit coût par déplacement, un gros bonus en trouvant la nourriture ou en la rapportant au nid), et chaque colonie retient dans une Q-table la valeur de chaque action depuis chaque case.", "On this tiny board, the nest is in the top-left corner and the food in the bottom-right one, behind a wall. The ants do not know the way: they learn it with Q-learning. Every step earns them a reward (a small cost per move, a big bonus for finding food or bringing it back to the nest), and each colony keeps the value of every action from every tile in a Q-table."),
+    ("tutorial.start.title", "Lancer la partie", "Start the game"),
+    ("tutorial.start.text", "Cliquez sur « LANCER LA SIMULATION » en haut du panneau de gauche, ou appuyez sur Espace.", "Click \"START SIMULATION\" at the top of the left panel, or press Space."),
+    ("tutorial.explore.title", "Exploration", "Exploration"),
+    ("tutorial.explore.text", "Au début, les fourmis errent : la Q-table est vide, et epsilon fixe la part des pas tirés au hasard pour découvrir la carte. Laissez passer quelques centaines de ticks ; la vitesse se règle dans le panneau de gauche ou avec + et -.", "At first the ants wander: the Q-table is empty, and epsilon sets the share of random steps taken to discover the map. Let a few hundred ticks go by; the speed is set in the left panel or with + and -."),
+    ("tutorial.food_trail.title", "Piste vers la nourriture", "Food trail"),
+    ("tutorial.food_trail.text", "Seule la piste vers la nourriture est affichée : pour chaque case, la plus grande valeur de la Q-table des fourmis qui cherchent. Plus la case est colorée, plus la colonie l'estime proche de la nourriture. La récompense remonte peu à peu depuis la source vers le nid.", "Only the food trail is shown: for every tile, the highest value in the Q-table of the ants looking for food. The more colored the tile, the closer to food the colony believes it is. The reward slowly flows back from the source towards the nest."),
+    ("tutorial.nest_trail.title", "Piste du retour", "Return trail"),
+    ("tutorial.nest_trail.text", "Voici l'autre couche : la piste du retour au nid, apprise par les fourmis chargées. Chaque mode a sa propre Q-table. Les deux couches se règlent dans « Visualisation », et la touche F les affiche ou les cache ensemble.", "Here is the other layer: the trail back to the nest, learned by the loaded ants. Each mode has its own Q-table. Both layers are set in \"Display\", and the F key shows or hides them together."),
+    ("tutorial.policy.title", "Politique apprise", "Learned policy"),
+    ("tutorial.policy.text", "Les flèches montrent la politique : l'action de plus grande valeur depuis chaque case. Une fois l'apprentissage avancé, elles dessinent le chemin le plus court autour du mur. Ce calque se choisit dans « Visualisation > Politique apprise ».", "The arrows show the policy: the highest-valued action from each tile. Once learning has progressed, they draw the shortest path around the wall. This overlay is picked in \"Display > Learned policy\"."),
+    ("tutorial.sliders.title", "Réglages du Q-learning", "Q-learning settings"),
+    ("tutorial.sliders.text", "Le panneau « Cerveau (Q-Learning) » règle l'apprentissage avant le lancement d'une partie : alpha est la vitesse à laquelle une Q-value suit chaque nouvelle expérience, gamma le poids des récompenses futures (la portée de la piste), epsilon la part d'exploration. « Récompenses » règle les bonus et les coûts. Pendant la partie, les valeurs courantes d'alpha et d'epsilon y sont affichées.", "The \"Brain (Q-Learning)\" panel sets up learning before a game starts: alpha is how fast a Q-value follows each new experience, gamma the weight of future rewards (how far the trail reaches), epsilon the share of exploration. \"Rewards\" sets the bonuses and costs. During the game, the current alpha and epsilon are shown there."),
+    ("tutorial.danger.title", "Zones mortelles", "Death zones"),
+    ("tutorial.danger.text", "Nouveau plateau : une bande de zones mortelles (rouge sombre) sépare le nid de la nourriture, avec deux passages. Une fourmi qui y entre meurt et la colonie retient une forte pénalité. Lancez la partie.", "New board: a band of death zones (dark red) separates the nest from the food, with two gaps. An ant that steps in dies and the colony remembers a heavy penalty. Start the game."),
+    ("tutorial.hazard.title", "Un monde qui change", "A changing world"),
+    ("tutorial.hazard.text", "Au tick 600, un événement du scénario rend mortel le passage du milieu. Regardez la colonie y perdre quelques fourmis, puis la piste se reporter sur le passage du haut.", "At tick 600, a scenario event makes the middle gap deadly. Watch the colony lose a few ants there, then the trail move to the top gap."),
+    ("tutorial.done.title", "À vous de jouer", "Your turn"),
+    ("tutorial.done.text", "Vous avez vu l'essentiel. Continuez sur ce plateau (le mode Dieu permet d'y poser murs et nourriture en pleine partie), ou quittez la partie pour créer la vôtre : carte aléatoire, éditeur de carte et chronologie d'événements.", "You have seen the essentials. Keep playing on this board (God mode lets you place walls and food mid-game), or quit the game to build your own: random map, map editor and event timeline."),
+    ("map_type.title", "Sélection du type de map", "Choose the map type"),
+    ("map_type.random", "Map Aléatoire", "Random Map"),
+    ("map_type.custom", "Map Personnalisée", "Custom Map"),
+    ("map_type.back", "Retour aux Dimensions", "Back to Dimensions"),
+    ("map_type.events", "🕒 Événements du Scénario", "🕒 Scenario Events"),
+    ("map_type.events_hint", "Programmer de la nourriture, des zones mortelles ou des vagues de prédateurs à des ticks donnés", "Schedule food, death zones or predator waves at given ticks"),
+    ("map_gen.title", "Map Aléatoire avancée", "Advanced Random Map"),
+    ("map_gen.walls", "Part maximale de murs", "Maximum wall share"),
+    ("map_gen.death_zones", "Part maximale de zones mortelles", "Maximum death zone share"),
+    ("map_gen.food_sources", "Sources de nourriture (max)", "Food sources (max)"),
+    ("map_gen.food_min", "Quantité minimale par source", "Minimum amount per source"),
+    ("map_gen.food_max", "Quantité maximale par source", "Maximum amount per source"),
+    ("map_gen.protein", "Part de protéine", "Protein share"),
+    ("map_gen.seed", "Graine fixe", "Fixed seed"),
+    ("map_gen.seed_hint", "La même graine redonne la même carte ; sans elle, la carte suit la graine de la partie", "The same seed gives the same map again; without it, the map follows the game seed"),
+    ("map_gen.reset", "Valeurs par défaut", "Defaults"),
+    ("setup.title", "Configuration de la Colonie", "Colony Setup"),
+    ("setup.explorers", "Explorateurs", "Explorers"),
+    ("setup.pickers", "Récolteuses", "Pickers"),
+    ("setup.fighters", "Combattantes", "Fighters"),
+    ("setup.sensing", "Vision active", "Sensing"),
+    ("setup.sensing_hint", "Les fourmis évitent les zones mortelles qu'elles voient et marchent droit vers la nourriture (ou leur nid) en vue", "Ants avoid the death zones they see and walk straight to the food (or their nest) in sight"),
+    ("setup.total", "Total: {} fourmis", "Total: {} ants"),
+    ("setup.to_board", "Vers le Plateau de Jeu", "To the Game Board"),
+    ("profile.title", "Caractéristiques", "Characteristics"),
+    ("profile.capacity", "Charge maximale :", "Maximum load:"),
+    ("profile.speed", "Attente entre mouvements (ticks) :", "Wait between moves (ticks):"),
+    ("profile.scope", "Vision (rayon en cases) :", "Vision (radius in cells):"),
+    ("editor_screen.title", "Éditeur de Carte", "Map Editor"),
+    ("editor_screen.confirm", "✓ Confirmer la Carte", "✓ Confirm Map"),
+    ("editor_screen.back", "← Retour au Choix", "← Back to Map Choice"),
+    ("editor_screen.events", "🕒 Événements...", "🕒 Events..."),
+    ("events_screen.title", "Chronologie du Scénario", "Scenario Timeline"),
+    ("events_screen.confirm", "✓ Valider la Chronologie", "✓ Confirm Timeline"),
+    ("events_screen.back", "← Retour sans Modifier", "← Back Without Changes"),
+    ("events.placing", "Clic sur la frise :", "Click on the timeline:"),
+    ("events.food", "Nourriture", "Food"),
+    ("events.hazard_on", "Zone mortelle", "Death zone"),
+    ("events.hazard_off", "Fin de zone mortelle", "Death zone removed"),
+    ("events.predator_wave", "Vague de prédateurs", "Predator wave"),
+    ("events.horizon", "Durée de la frise (ticks) :", "Timeline length (ticks):"),
+    ("events.count", "{} événement(s)", "{} event(s)"),
+    ("events.tick", "tick {}", "tick {}"),
+    ("events.timeline_hint", "Clic : poser un événement ou sélectionner celui touché. Glisser : déplacer un événement", "Click: add an event or select the one under the pointer. Drag: move an event"),
+    ("events.none_selected", "Aucun événement sélectionné", "No event selected"),
+    ("events.at_tick", "Tick :", "Tick:"),
+    ("events.cell", "Case :", "Cell:"),
+    ("events.amount", "Quantité :", "Amount:"),
+    ("events.lethal", "Case mortelle (décocher pour la rendre sûre)", "Lethal cell (untick to make it safe)"),
+    ("events.wave_size", "Prédateurs :", "Predators:"),
+    ("events.delete", "🗑 Supprimer", "🗑 Delete"),
+    ("events.food_at", "+{} nourriture en ({}, {})", "+{} food at ({}, {})"),
+    ("events.hazard_on_at", "zone mortelle en ({}, {})", "death zone at ({}, {})"),
+    ("events.hazard_off_at", "fin de zone mortelle en ({}, {})", "death zone removed at ({}, {})"),
+    ("events.wave_of", "vague de {} prédateur(s)", "wave of {} predator(s)"),
+    ("events.random_map", "Carte aléatoire : choisissez les cases visées par leurs coordonnées", "Random map: set the target cells by their coordinates"),
+    ("events.preview_hint", "Clic : case des prochains événements (et de l'événement sélectionné)", "Click: cell of the next events (and of the selected event)"),
+    ("events.saved", "Scénario sauvegardé dans {}", "Scenario saved to {}"),
+    ("events.loaded", "{} événement(s) chargé(s) depuis {}", "{} event(s) loaded from {}"),
+    ("events.toml", "TOML :", "TOML:"),
+    ("events.copied", "Scénario copié dans le presse-papiers", "Scenario copied to the clipboard"),
+    ("events.pasted", "{} événement(s) chargé(s)", "{} event(s) loaded"),
+
+    // Panneau de contrôle de la partie
+    ("game.title", "Configuration & Contrôle", "Settings & Controls"),
+    ("game.state", "État de la Simulation", "Simulation State"),
+    ("game.ready", "Prêt à démarrer. Réglez les paramètres ci-dessous.", "Ready to start. Adjust the parameters below."),
+    ("game.start", "LANCER LA SIMULATION", "START SIMULATION"),
+    ("game.pause", "PAUSE", "PAUSE"),
+    ("game.resume", "REPRENDRE", "RESUME"),
+    ("game.step_1", "Avancer 1 tick", "Step 1 tick"),
+    ("game.step_100", "Avancer 100 ticks", "Step 100 ticks"),
+    ("game.locked", "Paramètres verrouillés", "Parameters locked"),
+    ("game.over", "Partie terminée : {}", "Game over: {}"),
+    ("game.replay_finished", "Fin de la partie enregistrée", "End of the recorded game"),
+    ("game.replaying", "Relecture d'une partie enregistrée", "Replaying a recorded game"),
+    ("game.tick_rate", "Rythme des ticks :", "Tick rate:"),
+    ("game.rate_unlimited", "Au plus vite", "Unlimited"),
+    ("game.rate_tps", "Ticks/s", "Ticks/s"),
+    ("game.rate_ms", "ms/tick", "ms/tick"),
+    ("game.turbo", "Ticks par pas (turbo) :", "Ticks per step (turbo):"),
+    ("game.freeze", "Politique figée", "Frozen policy"),
+    ("game.freeze_hint", "Les fourmis jouent la meilleure action connue sans modifier les Q-tables : ni apprentissage, ni évaporation", "Ants play the best known action without changing the Q-tables: no learning, no evaporation"),
+    ("game.fast_forward", "Avance rapide", "Fast forward"),
+    ("game.fast_forward_left", "Avance rapide : {} ticks restants", "Fast forward: {} ticks left"),
+    ("game.quit", "Quitter / Reset", "Quit / Reset"),
+    ("stats.title", "Statistiques", "Statistics"),
+    ("stats.colony_food", "Colonie {} : {} nourriture", "Colony {}: {} food"),
+    ("stats.winner", "🏆 Colonie {} gagnante", "🏆 Colony {} wins"),
+    ("preview.start", "▶ Prévisualiser {} ticks", "▶ Preview {} ticks"),
+    ("preview.start_hint", "Joue en fond une courte partie sur cette carte, avec les réglages par défaut, puis superpose à la grille les passages des fourmis ou les pistes apprises : de quoi voir si la disposition s'apprend avant de lancer la partie.", "Plays a short game on this map in the background, with the default settings, then overlays the ants' traffic or the learned trails on the grid, to see whether the layout can be learned before launching the game."),
+    ("preview.stop", "⏹ Arrêter", "⏹ Stop"),
+    ("preview.show", "Aperçu :", "Preview:"),
+    ("preview.traffic", "Trafic", "Traffic"),
+    ("preview.food_trail", "Piste nourriture", "Food trail"),
+    ("preview.nest_trail", "Piste nid", "Nest trail"),
+    ("preview.summary", "{} nourriture livrée, {} morts en {} ticks", "{} food delivered, {} deaths in {} ticks"),
+    ("preview.stale", "(carte modifiée depuis l'aperçu)", "(map changed since the preview)"),
+    ("scores.open", "🏆 Meilleurs scores", "🏆 Best scores"),
+    ("scores.open_hint", "Parties terminées sur cette carte, classées par score. La fenêtre s'ouvre d'elle-même à la fin de chaque partie.", "Finished games on this map, ranked by score. The window opens by itself at the end of each game."),
+    ("scores.title", "Meilleurs scores", "Best scores"),
+    ("scores.formula", "Score :", "Score:"),
+    ("scores.food_weight", "nourriture ×", "food ×"),
+    ("scores.death_weight", "− morts ×", "− deaths ×"),
+    ("scores.tick_weight", "− ticks ×", "− ticks ×"),
+    ("scores.formula_hint", "Changer la formule reclasse toutes les parties déjà inscrites.", "Changing the formula re-ranks every game already recorded."),
+    ("scores.this_run", "Cette partie : {} points, rang {} sur {}", "This game: {} points, rank {} of {}"),
+    ("scores.empty", "Aucune partie terminée sur cette carte pour l'instant.", "No finished game on this map yet."),
+    ("scores.runs", "Parties", "Games"),
+    ("scores.colonies", "Colonies", "Colonies"),
+    ("scores.rank", "#", "#"),
+    ("scores.colony", "Colonie", "Colony"),
+    ("scores.score", "Score", "Score"),
+    ("scores.food", "Nourriture", "Food"),
+    ("scores.deaths", "Morts", "Deaths"),
+    ("scores.ticks", "Ticks", "Ticks"),
+    ("scores.seed", "Graine", "Seed"),
+    ("scores.date", "Date", "Date"),
+    ("scores.file", "Classement enregistré dans {}", "Leaderboard saved in {}"),
+    ("stats.spawns", "Déploiements : {} explo. / {} récolt. / {} comb.", "Deployed: {} explorers / {} pickers / {} fighters"),
+    ("stats.on_demand", "Décisions à la demande : {} piste / {} stagnation", "On-demand decisions: {} trail / {} stall"),
+    ("stats.starved", "Mortes de faim : {}", "Starved: {}"),
+    ("stats.stuck", "Blocages détectés : {}", "Stuck ants detected: {}"),
+    ("stats.predators", "Prédateurs : {} actifs / {} tués", "Predators: {} active / {} killed"),
+    ("stats.raided", "Nourriture pillée par les prédateurs : {}", "Food raided by predators: {}"),
+    ("stats.losses", "Pertes : {} ouvrières dévorées / {} combattantes", "Losses: {} workers eaten / {} fighters"),
+    ("timeline.title", "Timeline (retour en arrière)", "Timeline (rewind)"),
+    ("timeline.tick", "Tick : {} / {}", "Tick: {} / {}"),
+    ("timeline.speed", "snapshots/s", "snapshots/s"),
+    ("timeline.time", "Temps", "Time"),
+    ("timeline.stop", "⏸ Arrêter", "⏸ Stop"),
+    ("timeline.play", "▶ Relire", "▶ Play back"),
+    ("timeline.loop", "Boucle", "Loop"),
+    ("policy.title", "Politique", "Policy"),
+    ("policy.aco_hint", "Phéromone^α · heuristique^β, sans Q-table :", "Pheromone^α · heuristic^β, no Q-table:"),
+    ("policy.aco_deposit", "Dépôt", "Deposit"),
+    ("brain.title", "Cerveau (Q-Learning)", "Brain (Q-Learning)"),
+    ("brain.alpha_now", "Alpha courant : {}", "Current alpha: {}"),
+    ("brain.epsilon_now", "Epsilon courant : {}", "Current epsilon: {}"),
+    ("brain.alpha", "Alpha (Apprentissage) :", "Alpha (learning rate):"),
+    ("brain.gamma", "Gamma (Vision) :", "Gamma (foresight):"),
+    ("brain.lambda", "Lambda (Traces d'éligibilité) :", "Lambda (eligibility traces):"),
+    ("brain.lambda_hint", "Q(λ) de Watkins : chaque correction remonte aussi vers les cases récemment parcourues. 0 = mise à jour à un pas", "Watkins's Q(λ): each correction also flows back to recently visited cells. 0 = one-step update"),
+    ("brain.n_step", "Retours à n pas :", "N-step returns:"),
+    ("brain.n_step_hint", "Chaque fourmi cumule les récompenses de ses n derniers pas avant de corriger la Q-value du plus ancien. 1 = mise à jour à un pas, exclusif avec lambda", "Each ant sums the rewards of its last n steps before correcting the oldest Q-value. 1 = one-step update, exclusive with lambda"),
+    ("brain.epsilon", "Epsilon (Exploration) :", "Epsilon (exploration):"),
+    ("brain.epsilon_decay", "Décroissance d'epsilon :", "Epsilon decay:"),
+    ("brain.alpha_decay", "Décroissance d'alpha :", "Alpha decay:"),
+    ("brain.action_selection", "Sélection d'action :", "Action selection:"),
+    ("brain.temperature", "Température", "Temperature"),
+    ("brain.ucb_bonus", "Bonus C", "C bonus"),
+    ("brain.unmasked_exploration", "Explorer vers les murs (ancien tirage)", "Explore into walls (legacy sampling)"),
+    ("brain.masked_max_q", "Max de Bellman sans les murs", "Bellman max without walls"),
+    ("brain.masked_max_q_hint", "La valeur de l'état suivant ne compte que les déplacements possibles ; décoché, les pas vers un mur comptent aussi (cerveaux entraînés avant ce réglage)", "The next state's value only counts possible moves; unticked, steps into a wall count too (brains trained before this setting)"),
+    ("brain.algorithm", "Algorithme :", "Algorithm:"),
+    ("brain.q_backend", "Valeurs Q :", "Q-values:"),
+    ("brain.q_backend_hint", "Q-table : une valeur par case et par action. Linéaire : quelques poids par trait de case (position grossière, nature, distance au nid), partagés sur toute la carte ; les cartes affichées ne sont alors plus mises à jour", "Q-table: one value per cell and action. Linear: a few weights per cell feature (coarse position, tile type, distance to the nest), shared across the map; the displayed maps are then no longer updated"),
+    ("brain.linear_tile", "pavé (cases)", "tile (cells)"),
+    ("backend.tabular", "Q-table", "Q-table"),
+    ("backend.linear", "Approximation linéaire", "Linear approximation"),
+    ("brain.per_type", "Cartes par type de fourmi", "Maps per ant type"),
+    ("brain.per_type_hint", "Exploratrices, récolteuses et combattantes apprennent chacune leurs propres cartes", "Explorers, pickers and fighters each learn their own maps"),
+    ("brain.near_food", "État : nourriture en vue", "State: food in sight"),
+    ("brain.near_food_hint", "Une fourmi qui voit de la nourriture apprend dans des cartes à part", "An ant that sees food learns in separate maps"),
+    ("brain.experience_replay", "Rejeu d'expérience :", "Experience replay:"),
+    ("brain.replay_memory", "Mémoire ", "Memory "),
+    ("brain.replay_memory_hint", "Transitions gardées, 0 = désactivé", "Transitions kept, 0 = disabled"),
+    ("brain.replay_batch", "Rejouées/tick ", "Replayed/tick "),
+    ("brain.q_values", "Valeurs Q :", "Q-values:"),
+    ("brain.q_initial", "Départ ", "Initial "),
+    ("brain.q_initial_hint", "Valeur des paires jamais visitées ; positive, elle pousse à explorer l'inconnu (initialisation optimiste)", "Value of never-visited pairs; a positive one drives ants toward the unknown (optimistic initialization)"),
+    ("brain.q_clamp", "Borne ", "Bound "),
+    ("brain.q_clamp_hint", "Amplitude maximale des valeurs Q, 0 = sans borne", "Maximum Q-value magnitude, 0 = unbounded"),
+    ("brain.q_scale", "Échelle d'affichage", "Display scale"),
+    ("decay.linear_rate", "Retrait/tick", "Decrease/tick"),
+    ("decay.factor", "Facteur", "Factor"),
+    ("decay.step_every", "Palier", "Step"),
+    ("decay.min", "Plancher", "Floor"),
+    ("decay.constant", "Constante", "Constant"),
+    ("decay.linear", "Linéaire", "Linear"),
+    ("decay.exponential", "Exponentielle", "Exponential"),
+    ("decay.step", "Par paliers", "Steps"),
+    ("rewards.title", "Récompenses", "Rewards"),
+    ("rewards.food", "Nourriture (+):", "Food (+):"),
+    ("rewards.nest", "Retour Nid (+):", "Back at the nest (+):"),
+    ("rewards.step", "Coût Déplacement (-):", "Move cost (-):"),
+    ("rewards.death", "Mort (-):", "Death (-):"),
+    ("rewards.load_scaling", "Nourriture selon la charge remplie", "Food reward scaled by load"),
+    ("rewards.load_scaling_hint", "La récompense de la nourriture est multipliée par la part de sa charge que la fourmi remplit : un aller-retour pour une unité rapporte moins qu'une charge pleine", "The food reward is multiplied by the share of its capacity the ant fills: a trip for one unit pays less than a full load"),
+    ("rewards.per_unit", "Par unité livrée (+):", "Per unit delivered (+):"),
+    ("colony.title", "Colonie", "Colony"),
+    ("colony.max_per_cell", "Fourmis max par case :", "Max ants per cell:"),
+    ("colony.unlimited", "Illimité", "Unlimited"),
+    ("colony.spawns", "Sorties du nid :", "Leaving the nest:"),
+    ("colony.spawn_rate", "fourmis par tick", "ants per tick"),
+    ("colony.spawn_cooldown", "ticks d'attente au nid", "ticks waiting at the nest"),
+    ("colony.min_explorers", "Explorateurs actifs minimum :", "Minimum active explorers:"),
+    ("colony.min_pickers", "Récolteuses actives minimum :", "Minimum active pickers:"),
+    ("colony.upkeep", "Consommation par fourmi et par tick :", "Food eaten per ant and per tick:"),
+    ("colony.upkeep_interval", "Ticks entre deux prélèvements :", "Ticks between two upkeep payments:"),
+    ("colony.starvation", "Ticks de famine avant décès :", "Starving ticks before death:"),
+    ("colony.energy", "Énergie des fourmis (0 = désactivée) :", "Ant energy (0 = disabled):"),
+    ("colony.energy_hint", "Chaque déplacement coûte de l'énergie, la fourmi mange les réserves du nid pour refaire le plein et meurt à zéro", "Every move costs energy, the ant eats from the nest's stores to refill and dies at zero"),
+    ("colony.energy_drain", "Coût d'un pas", "Cost of a step"),
+    ("colony.energy_per_food", "Énergie par unité mangée", "Energy per unit eaten"),
+    ("colony.hunger", "Seuil de faim", "Hunger threshold"),
+    ("colony.nest_capacity", "Fourmis déployées max par colonie :", "Max deployed ants per colony:"),
+    ("colony.type_caps", "Places par type imposées", "Fixed slots per type"),
+    ("colony.type_caps_hint", "Sinon, les places de chaque nid (éditeur de carte)", "Otherwise, each nest's own slots (map editor)"),
+    ("colony.birth_cost", "Coût d'une naissance (0 = désactivées) :", "Cost of a birth (0 = disabled):"),
+    ("colony.birth_hint", "Le nid transforme ses réserves en nouvelles fourmis, dans la limite de ses capacités par type", "The nest turns its stores into new ants, within its capacity for each type"),
+    ("colony.birth_interval", "ticks entre deux naissances", "ticks between births"),
+    ("colony.lifespan", "Durée de vie en ticks (0 = illimitée) :", "Lifespan in ticks (0 = unlimited):"),
+    ("colony.lifespan_hint", "Une fourmi trop vieille rentre mourir au nid ; seules les naissances la remplacent", "An ant past its lifespan goes home to die; only births replace it"),
+    ("colony.regrowth", "Repousse de la nourriture (0 = désactivée) :", "Food regrowth (0 = disabled):"),
+    ("colony.regrowth_interval", "ticks entre deux repousses", "ticks between regrowths"),
+    ("colony.regrowth_cap", "plafond par source", "cap per source"),
+    ("colony.corpses", "Cadavres (0 = désactivés) :", "Corpses (0 = disabled):"),
+    ("colony.corpse_food", "unités par fourmi morte", "units per dead ant"),
+    ("colony.corpse_hint", "Une fourmi morte (hors prédateur) laisse une source temporaire, charge transportée comprise", "A dead ant (unless eaten by a predator) leaves a temporary food source, including its load"),
+    ("colony.corpse_decay", "ticks par unité perdue", "ticks per unit lost"),
+    ("colony.target", "Objectif de nourriture au nid", "Food target at the nest"),
+    ("colony.spawn_policy", "Stratégie de sortie du nid :", "Nest exit strategy:"),
+    ("colony.burst_ticks", "Durée de la vague d'explorateurs (ticks) :", "Explorer wave duration (ticks):"),
+    ("colony.trail_threshold", "Seuil de piste (récolteuses) :", "Trail threshold (pickers):"),
+    ("colony.stall_ticks", "Ticks sans livraison (explorateurs) :", "Ticks without delivery (explorers):"),
+    ("spawn_policy.priority", "Priorité (minimums)", "Priority (minimums)"),
+    ("spawn_policy.proportional", "Proportions par type", "Ratios per type"),
+    ("spawn_policy.burst", "Explorateurs puis récolteuses", "Explorers then pickers"),
+    ("spawn_policy.demand", "À la demande", "On demand"),
+    ("controller.title", "Méta-contrôleur", "Meta-controller"),
+    ("controller.off", "Désactivé", "Off"),
+    ("controller.stall", "Relance si stagnation", "Boost on stall"),
+    ("controller.bandit", "Bandit (UCB1)", "Bandit (UCB1)"),
+    ("controller.hint", "Corrige epsilon et alpha en cours de partie d'après la nourriture livrée aux nids, par-dessus leurs décroissances", "Adjusts epsilon and alpha during the game from the food delivered to the nests, on top of their decay schedules"),
+    ("controller.period", "Ticks entre deux décisions :", "Ticks between decisions:"),
+    ("controller.step", "Facteur appliqué en cas de stagnation :", "Factor applied on stall:"),
+    ("controller.max_epsilon", "Epsilon maximal :", "Maximum epsilon:"),
+    ("controller.rate", "Livraisons/tick", "Deliveries/tick"),
+    ("stuck.title", "Fourmis bloquées", "Stuck ants"),
+    ("stuck.ticks", "Ticks sur place avant blocage (0 = désactivé) :", "Ticks in place before stuck (0 = off):"),
+    ("stuck.radius", "Rayon considéré comme sur place :", "Radius counted as in place:"),
+    ("stuck.recovery", "Reprise :", "Recovery:"),
+    ("stuck.recovery_ticks", "Durée de la reprise (ticks) :", "Recovery length (ticks):"),
+    ("stuck.epsilon", "Epsilon minimal pendant la reprise :", "Minimum epsilon while recovering:"),
+    ("stuck.explore", "Exploration renforcée", "Epsilon boost"),
+    ("stuck.random", "Marche au hasard", "Random walk"),
+    ("stuck.recall", "Rappel au nid", "Recall to nest"),
+    ("fear.title", "Phéromone d'alarme", "Alarm pheromone"),
+    ("fear.weight", "Poids de la peur (0 = désactivée) :", "Fear weight (0 = off):"),
+    ("fear.deposit", "Alarme déposée par une mort :", "Alarm laid by a death:"),
+    ("fear.evaporation", "Évaporation de l'alarme :", "Alarm evaporation:"),
+    ("night.title", "Cycle jour/nuit", "Day/night cycle"),
+    ("night.now", "🌙 C'est la nuit", "🌙 It is night"),
+    ("night.period", "Période en ticks (0 = toujours jour) :", "Period in ticks (0 = always day):"),
+    ("night.evaporation", "Facteur d'évaporation la nuit :", "Night evaporation factor:"),
+    ("night.epsilon", "Epsilon ajouté la nuit :", "Epsilon added at night:"),
+    ("night.slowdown", "Ralentissement la nuit : x", "Night slowdown: x"),
+    ("predators.title", "Prédateurs", "Predators"),
+    ("predators.max", "Nombre maximum (0 = aucun) :", "Maximum number (0 = none):"),
+    ("predators.interval", "Ticks entre deux apparitions :", "Ticks between spawns:"),
+    ("predators.fighter_win", "Chance qu'un coup de combattante porte :", "Chance that a fighter's blow lands:"),
+    ("predators.fighter_health", "Points de vie des combattantes :", "Fighter health:"),
+    ("predators.fighter_attack", "Dégâts d'un coup de combattante :", "Fighter attack:"),
+    ("predators.health", "Points de vie des prédateurs :", "Predator health:"),
+    ("predators.attack", "Dégâts des prédateurs par tick de combat :", "Predator attack per combat tick:"),
+    ("predators.raid", "Pillage d'un nid sans combattante (par mouvement) :", "Raid on a nest without fighters (per move):"),
+    ("predators.patrol_range", "Rayon de patrouille des combattantes :", "Fighter patrol range:"),
+    ("view.title", "Visualisation", "Display"),
+    ("view.food_trails", "Pistes Nourriture", "Food trails"),
+    ("view.nest_trails", "Pistes Retour", "Return trails"),
+    ("view.fear", "Peur", "Fear"),
+    ("view.fear_hint", "Phéromone d'alarme déposée là où des fourmis de la colonie sont mortes dans une zone mortelle ou sous les coups d'un prédateur", "Alarm pheromone laid where the colony's ants died in a death zone or to a predator"),
+    ("view.ant_paths", "Trajets des fourmis", "Ant paths"),
+    ("view.minimap", "Minicarte", "Minimap"),
+    ("view.minimap_hint", "Quand le plateau est zoomé, vue d'ensemble dans le coin avec le cadre de la partie visible ; un clic ou un glisser y recentre la vue", "When the board is zoomed in, an overview in the corner framing the visible part; click or drag on it to recentre the view"),
+    ("view.heat_auto", "Échelle auto", "Auto scale"),
+    ("view.heat_auto_hint", "L'intensité des pistes suit la plage des valeurs Q de chaque carte au lieu de l'échelle d'affichage fixe", "Trail intensity follows each map's Q-value range instead of the fixed display scale"),
+    ("view.heat_log", "Logarithmique", "Logarithmic"),
+    ("view.heat_food", "Nourriture", "Food"),
+    ("view.heat_nest", "Nid", "Nest"),
+    ("view.traffic", "Trafic", "Traffic"),
+    ("view.traffic_hint", "Nombre de passages des fourmis sur chaque case depuis le début de la partie", "How many times ants stepped on each tile since the start of the game"),
+    ("view.heat_traffic", "Passages", "Visits"),
+    ("view.policy", "Politique apprise", "Learned policy"),
+    ("view.shown_colony", "Colonie affichée", "Displayed colony"),
+    ("view.shown_maps", "Cartes affichées", "Displayed maps"),
+    ("view.export_brain", "Exporter le cerveau", "Export brain"),
+    ("overlay.hidden", "Masquée", "Hidden"),
+    ("overlay.food", "Vers la nourriture", "Toward food"),
+    ("overlay.nest", "Vers le nid", "Toward the nest"),
+    ("god.title", "Mode Dieu", "God Mode"),
+    ("god.edit", "Modifier la carte (clic sur une case)", "Edit the map (click a cell)"),
+    ("god.tool", "Outil :", "Tool:"),
+    ("god.inspect", "Inspecter", "Inspect"),
+    ("god.food", "Nourriture", "Food"),
+    ("god.erase", "Effacer", "Erase"),
+    ("god.food_amount", "Quantité :", "Amount:"),
+    ("compare.title", "Comparaison", "Comparison"),
+    ("compare.enable", "Comparer avec une seconde simulation sur la même carte", "Compare with a second simulation on the same map"),
+    ("compare.shared", "Mêmes paramètres", "Same parameters"),
+    ("compare.alpha", "Alpha", "Alpha"),
+    ("compare.gamma", "Gamma", "Gamma"),
+    ("compare.epsilon", "Epsilon", "Epsilon"),
+    ("compare.rival_tick", "Simulation 2 — tick {}", "Simulation 2 — tick {}"),
+    ("compare.note", "Les autres paramètres, la carte et la graine sont ceux de la simulation 1.", "The other parameters, the map and the seed are those of simulation 1."),
+    ("board.nest", "Nid {}\n{}", "Nest {}\n{}"),
+
+    // Panneaux du bas : courbes, statistiques par type, journal
+    ("plots.title", "Statistiques (courbes)", "Statistics (charts)"),
+    ("plots.nest_food", "Nourriture au nid", "Food at the nest"),
+    ("plots.active_ants", "Fourmis actives", "Active ants"),
+    ("plots.population", "Population", "Population"),
+    ("plots.mean_age", "Âge moyen", "Mean age"),
+    ("plots.deaths", "Morts cumulées", "Total deaths"),
+    ("plots.mean_q", "|Q| moyen", "Mean |Q|"),
+    ("plots.export", "Exporter", "Export"),
+    ("plots.export_hint", "Écrit la courbe dans courbe_<nom>.csv et courbe_<nom>.png (dossier courant)", "Writes the chart to courbe_<name>.csv and courbe_<name>.png (working directory)"),
+    ("type_stats.title", "Statistiques par type de fourmi", "Statistics by ant type"),
+    ("type_stats.type", "Type", "Type"),
+    ("type_stats.ants", "Fourmis", "Ants"),
+    ("type_stats.trips", "Allers-retours", "Round trips"),
+    ("type_stats.distance", "Cases parcourues", "Cells walked"),
+    ("type_stats.food", "Nourriture livrée", "Food delivered"),
+    ("type_stats.ticks", "Ticks sur la carte", "Ticks on the map"),
+    ("type_stats.food_rate", "Livrée / 1000 ticks", "Delivered / 1000 ticks"),
+    ("type_stats.distance_per_trip", "Cases / aller-retour", "Cells / round trip"),
+    ("log.title", "Journal d'événements", "Event log"),
+    ("log.inactive", "Journal inactif (aucun journal installé par le programme)", "Log inactive (no log installed by the program)"),
+    ("log.clear", "Effacer", "Clear"),
+    ("log.level", "Niveau : {}", "Level: {}"),
+    ("log_level.error", "erreur", "error"),
+    ("log_level.warn", "alerte", "warning"),
+    ("log_level.info", "info", "info"),
+    ("log_level.debug", "debug", "debug"),
+    ("log_level.trace", "trace", "trace"),
+    ("log_kind.spawn", "Sorties du nid", "Nest exits"),
+    ("log_kind.pickup", "Ramassages", "Pickups"),
+    ("log_kind.delivery", "Livraisons", "Deliveries"),
+    ("log_kind.death", "Morts", "Deaths"),
+    ("log_kind.birth", "Naissances", "Births"),
+    ("log_kind.predator", "Prédateurs", "Predators"),
+    ("log_kind.hazard", "Zones mortelles", "Death zones"),
+    ("log_kind.scenario", "Scénario", "Scenario"),
+    ("log_kind.checkpoint", "Checkpoints", "Checkpoints"),
+    ("log_kind.system", "Système", "System"),
+
+    // Inspecteurs (case en mode Dieu, fourmi sélectionnée)
+    ("tile.title", "Case", "Cell"),
+    ("tile.position", "Position : ({}, {})", "Position: ({}, {})"),
+    ("tile.empty", "Case vide", "Empty cell"),
+    ("tile.wall", "Mur", "Wall"),
+    ("tile.death_zone", "Zone mortelle", "Death zone"),
+    ("tile.mud", "Boue", "Mud"),
+    ("tile.mud_slow", "Boue : déplacements {} fois plus lents", "Mud: moves {} times slower"),
+    ("tile.tunnel_to", "Tunnel vers ({}, {})", "Tunnel to ({}, {})"),
+    ("tile.corpse", "Cadavre de fourmi : {} restante(s)", "Ant corpse: {} left"),
+    ("tile.food", "Source de nourriture : {} restante(s)", "Food source: {} left"),
+    ("tile.food_value", "Valeur par unité : {}", "Value per unit: {}"),
+    ("tile.nest", "Nid de la colonie {}", "Nest of colony {}"),
+    ("tile.stored_food", "Nourriture stockée : {}", "Stored food: {}"),
+    ("tile.slots", "Places : {} explo. / {} récolt. / {} comb.", "Slots: {} explorers / {} pickers / {} fighters"),
+    ("tile.ants_here", "Fourmis sur la case : {}", "Ants on the cell: {}"),
+    ("tile.nest_locked", "Les nids ne peuvent pas être modifiés.", "Nests cannot be edited."),
+    ("tile.convert", "Convertir en :", "Convert to:"),
+    ("tile.drop_food", "Déposer de la nourriture", "Drop food"),
+    ("ant.title", "Fourmi", "Ant"),
+    ("ant.number", "Numéro", "Number"),
+    ("ant.type", "Type", "Type"),
+    ("ant.colony", "Colonie", "Colony"),
+    ("ant.mode", "Mode", "Mode"),
+    ("ant.load", "Charge", "Load"),
+    ("ant.energy", "Énergie", "Energy"),
+    ("ant.hungry", " (affamée)", " (hungry)"),
+    ("ant.age", "Âge", "Age"),
+    ("ant.cooldown", "Attente", "Cooldown"),
+    ("ant.trips", "Allers-retours", "Round trips"),
+    ("ant.trips_value", "{} ({} livrée(s))", "{} ({} delivered)"),
+    ("ant.walked", "Parcouru", "Walked"),
+    ("ant.walked_value", "{} cases en {} ticks", "{} cells in {} ticks"),
+    ("ant.trace", "Trace Q(λ)", "Q(λ) trace"),
+    ("ant.trace_value", "{} paires", "{} pairs"),
+    ("ant.position", "Position", "Position"),
+    ("ant.off_map", "Hors de la carte", "Off the map"),
+    ("ant.vision", "Vision", "Vision"),
+    ("ant.blind", "Aveugle", "Blind"),
+    ("ant.one_cell", "1 case", "1 cell"),
+    ("ant.cells", "{} cases", "{} cells"),
+    ("ant.in_sight", "En vue", "In sight"),
+    ("ant.seen_food", "nourriture ({}, {})", "food ({}, {})"),
+    ("ant.seen_nest", "nid ({}, {})", "nest ({}, {})"),
+    ("ant.seen_hazards", "{} zone(s) mortelle(s)", "{} death zone(s)"),
+    ("ant.seen_nothing", "rien", "nothing"),
+    ("ant.follow", "Suivre la fourmi", "Follow the ant"),
+    ("ant.q_food", "Valeurs Q (carte nourriture)", "Q-values (food map)"),
+    ("ant.q_nest", "Valeurs Q (carte nid)", "Q-values (nest map)"),
+    ("ant.trail", "Trajet récent ({} cases)", "Recent path ({} cells)"),
+    ("action.up", "↑ Haut", "↑ Up"),
+    ("action.down", "↓ Bas", "↓ Down"),
+    ("action.left", "← Gauche", "← Left"),
+    ("action.right", "→ Droite", "→ Right"),
+    ("action.stay", "• Rester", "• Stay"),
+    ("ant_type.explorer", "Exploratrice", "Explorer"),
+    ("ant_type.picker", "Récolteuse", "Picker"),
+    ("ant_type.fighter", "Combattante", "Fighter"),
+    ("ant_mode.finding", "Cherche de la nourriture", "Looking for food"),
+    ("ant_mode.returning", "Rentre au nid", "Heading back to the nest"),
+    ("end.target_food", "objectif de nourriture atteint (colonie {})", "food target reached (colony {})"),
+    ("end.food_exhausted", "plus de nourriture sur la carte", "no food left on the map"),
+    ("end.all_ants_dead", "toutes les fourmis sont mortes", "all the ants are dead"),
+    ("end.famine", "famine : la colonie {} ne peut plus nourrir ses fourmis", "famine: colony {} can no longer feed its ants"),
+    ("end.tick_limit", "limite de ticks atteinte", "tick limit reached"),
+    ("end.timeout", "temps de calcul dépassé (DNF)", "wall-clock timeout (DNF)"),
+    ("policy_kind.aco", "ACO classique", "Classic ACO"),
+    ("policy_kind.random", "Marche aléatoire", "Random walk"),
+    ("policy_kind.astar", "A* omniscient", "Omniscient A*"),
+
+    // Éditeur de carte
+    ("editor.tile_default", "Vide", "Empty"),
+    ("editor.tile_nest_a", "Nid A", "Nest A"),
+    ("editor.tile_nest_b", "Nid B", "Nest B"),
+    ("editor.tile_food", "Nourriture", "Food"),
+    ("editor.tile_danger", "Danger", "Danger"),
+    ("editor.tile_tunnel", "Tunnel", "Tunnel"),
+    ("editor.pencil", "✏ Pinceau", "✏ Pencil"),
+    ("editor.rectangle", "▭ Rectangle", "▭ Rectangle"),
+    ("editor.line", "╱ Ligne", "╱ Line"),
+    ("editor.fill", "🪣 Remplissage", "🪣 Fill"),
+    ("editor.nest_no_food", "❌ Le nid {} n'atteint aucune nourriture", "❌ Nest {} cannot reach any food"),
+    ("editor.food_unreachable", "❌ La nourriture en ({}, {}) est inaccessible", "❌ The food at ({}, {}) cannot be reached"),
+    ("editor.place_nest", "❌ Placez 1 NID (case jaune)", "❌ Place 1 NEST (yellow cell)"),
+    ("editor.too_many_nests", "❌ Trop de NIDS ({}/1)", "❌ Too many NESTS ({}/1)"),
+    ("editor.too_many_nests_b", "❌ Trop de NIDS B ({}/1)", "❌ Too many B NESTS ({}/1)"),
+    ("editor.place_food", "❌ Placez de la NOURRITURE (case verte)", "❌ Place some FOOD (green cell)"),
+    ("editor.tunnel_no_exit", "❌ Le tunnel en ({}, {}) n'a pas de sortie", "❌ The tunnel at ({}, {}) has no exit"),
+    ("editor.tunnel_into_wall", "❌ Le tunnel en ({}, {}) débouche sur un mur", "❌ The tunnel at ({}, {}) leads into a wall"),
+    ("editor.file", "Fichier :", "File:"),
+    ("editor.save", "💾 Sauvegarder", "💾 Save"),
+    ("editor.saved", "Carte sauvegardée dans {}", "Map saved to {}"),
+    ("editor.load", "📂 Charger", "📂 Load"),
+    ("editor.loaded_from", "Carte chargée depuis {}", "Map loaded from {}"),
+    ("editor.json", "JSON :", "JSON:"),
+    ("editor.copy", "📋 Copier", "📋 Copy"),
+    ("editor.copied", "Carte copiée dans le presse-papiers", "Map copied to the clipboard"),
+    ("editor.paste", "📥 Coller", "📥 Paste"),
+    ("editor.loaded", "Carte chargée", "Map loaded"),
+    ("editor.invalid", "Carte invalide: {}", "Invalid map: {}"),
+    ("editor.maze", "Labyrinthe", "Maze"),
+    ("editor.caves", "Grottes", "Caves"),
+    ("editor.noise", "Bruit de Perlin", "Perlin noise"),
+    ("editor.maze_density", "Murs conservés", "Walls kept"),
+    ("editor.caves_density", "Remplissage initial", "Initial fill"),
+    ("editor.noise_density", "Part de murs", "Share of walls"),
+    ("editor.seed", "Graine :", "Seed:"),
+    ("editor.random_seed", "Graine au hasard", "Random seed"),
+    ("editor.generate_note", "Les nids, la nourriture et les zones mortelles sont conservés.", "Nests, food and death zones are kept."),
+    ("editor.generate", "Générer", "Generate"),
+    ("editor.generate_menu", "🎲 Générer", "🎲 Generate"),
+    ("editor.width", "Largeur : ", "Width: "),
+    ("editor.height", "Hauteur : ", "Height: "),
+    ("editor.anchor", "Ancrage :", "Anchor:"),
+    ("editor.apply", "Appliquer", "Apply"),
+    ("editor.size_menu", "📐 Taille", "📐 Size"),
+    ("editor.cell_hint", "Clic droit sur une case de nourriture, un nid, de la boue ou un tunnel pour la régler", "Right-click a food, nest, mud or tunnel cell to adjust it"),
+    ("editor.cell", "Case ({}, {}) : {}", "Cell ({}, {}): {}"),
+    ("editor.food_amount", "Nourriture : ", "Food: "),
+    ("editor.food_value", "Valeur : x", "Value: x"),
+    ("editor.food_value_hint", "Valeur de chaque unité ramassée (1 = sucre, 5 = protéines) : la récompense et les réserves du nid en sont multipliées", "Value of each unit picked up (1 = sugar, 5 = protein): the reward and the nest stores are multiplied by it"),
+    ("editor.own_regrowth", "Repousse propre", "Own regrowth"),
+    ("editor.units", " unités", " units"),
+    ("editor.every", "tous les ", "every "),
+    ("editor.up_to", "jusqu'à ", "up to "),
+    ("editor.explorers", "Exploratrices : ", "Explorers: "),
+    ("editor.pickers", "Récolteuses : ", "Pickers: "),
+    ("editor.fighters", "Combattantes : ", "Fighters: "),
+    ("editor.slowdown", "Ralentissement : x", "Slowdown: x"),
+    ("editor.exit", "Sortie : ({}, {})", "Exit: ({}, {})"),
+    ("editor.no_exit", "Sortie : aucune", "Exit: none"),
+    ("editor.pick_exit", "🎯 Choisir la sortie", "🎯 Pick the exit"),
+    ("editor.pick_exit_hint", "Puis cliquer sur la case où ressortent les fourmis", "Then click the cell where the ants come out"),
+    ("editor.no_settings", "(aucun réglage pour ce type de case)", "(no settings for this cell type)"),
+    ("editor.defaults", "Valeurs par défaut", "Defaults"),
+    ("editor.tools", "Outils :", "Tools:"),
+    ("editor.clear", "🗑️ Tout effacer", "🗑️ Clear all"),
+    ("editor.fill_empty", "⬜ Remplir vide", "⬜ Fill empty"),
+    ("editor.undo", "↶ Annuler", "↶ Undo"),
+    ("editor.redo", "↷ Rétablir", "↷ Redo"),
+    ("editor.rotate", "Tourner la carte d'un quart de tour", "Rotate the map a quarter turn"),
+    ("editor.mirror_horizontal", "Retourner la carte (gauche / droite)", "Flip the map (left / right)"),
+    ("editor.mirror_vertical", "Retourner la carte (haut / bas)", "Flip the map (top / bottom)"),
+    ("editor.show_unreachable", "Montrer les zones inaccessibles depuis les nids", "Show areas the nests cannot reach"),
+    ("editor.multi_nest", "Plusieurs nids par colonie", "Several nests per colony"),
+    ("editor.multi_nest_hint", "Chaque fourmi livre au nid de sa colonie qu'elle atteint, et les réserves de tous les nids d'une colonie s'additionnent", "Ants deliver to whichever nest of their colony they reach, and the stores of all nests of a colony add up"),
+    ("editor.tool", "Outil :", "Tool:"),
+    ("editor.brush_size", "Taille", "Size"),
+    ("editor.symmetry", "Symétrie :", "Symmetry:"),
+    ("editor.symmetry_horizontal", "↔ Gauche/droite", "↔ Left/right"),
+    ("editor.symmetry_vertical", "↕ Haut/bas", "↕ Top/bottom"),
+    ("editor.symmetry_rotational", "⟲ Demi-tour", "⟲ Half turn"),
+    ("editor.launch", "🚀 LANCER LA PARTIE", "🚀 START THE GAME"),
+    ("editor.nests", "Nids: A {}/1, B {}/1", "Nests: A {}/1, B {}/1"),
+    ("editor.nests_multi", "Nids: A {}, B {}", "Nests: A {}, B {}"),
+];
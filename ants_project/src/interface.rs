@@ -1,44 +1,390 @@
+use crate::aco::ScentTrails;
 use crate::ant::{Ant, AntsMode, AntsType};
 use crate::ants_game_manager::AntsGameManager;
-use crate::cli_args::SimulationConfig;
+use crate::board_layer::StaticLayer;
+use crate::camera::{BoardLayout, Camera};
+use crate::cli_args::{SimulationConfig, MAX_TICKS_PER_FRAME};
+use crate::colony::Colony;
+use crate::event_editor::EventEditor;
+use crate::event_log::{self, EventLog, LogKind};
+use crate::exploration::ExplorationPolicy;
+use crate::fear::FearMap;
+use crate::grid::MapGenParams;
+use crate::heat_scale::{self, HeatScale};
+use crate::i18n::{self, tr, trf, Language};
 use crate::map_editor::MapEditor;
-use crate::pheromone::PheromoneMap;
+use crate::meta_controller::{ControllerDecision, ControllerMode};
+use crate::metrics::{AntTypeStats, StatsSeries, TrafficMap};
+use crate::pheromone::{Action, PheromoneMap};
+use crate::policy::PolicyKind;
+use crate::predator::{self, Predator};
+use crate::presets::Preset;
+use crate::q_backend::QBackendKind;
+use crate::q_learning_math::{DecayConfig, DecaySchedule, LearningAlgorithm};
+use crate::runner::TickRate;
+use crate::scoring::{
+    self, Leaderboard, RunResult, RunScores, ScoreEntry, ScoreRule, DEFAULT_LEADERBOARD_FILE,
+};
+use crate::sensing;
+use crate::session::{self, DEFAULT_SESSION_FILE};
+use crate::sim_worker::{GameView, HistoryPlayback, SimulationWorker, WorkerCommand};
+use crate::spawn_policy::SpawnPolicyKind;
+use crate::stuck::StuckRecovery;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::sweep_panel::SweepPanel;
+use crate::theme::{self, RenderTheme};
+use crate::tile::{TileType, DEFAULT_MUD_SLOW_FACTOR, SUGAR_VALUE};
+use crate::tutorial::{self, Highlight, Tutorial};
 use eframe::egui;
-use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::ops::Range;
+use std::slice;
 
-#[derive(PartialEq)]
+// Clé de la langue de l'interface dans le stockage persistant d'eframe
+const LANGUAGE_KEY: &str = "language";
+const THEME_KEY: &str = "render_theme";
+
+// Délai maximal entre deux ticks proposé par le curseur de vitesse (ms)
+const MAX_TICK_DELAY: u64 = 100;
+
+// Rythme maximal proposé par le curseur de vitesse en ticks par seconde ; au-delà, autant
+// ne plus limiter
+const MAX_TICKS_PER_SECOND: u32 = 1000;
+
+// Côté maximal de la minicarte et marge avec le bord du plateau, en pixels
+const MINIMAP_SIDE: f32 = 180.0;
+const MINIMAP_MARGIN: f32 = 8.0;
+
+// Parties montrées par classement dans la fenêtre des meilleurs scores
+const SCORES_SHOWN: usize = 10;
+
+// Raccourcis du plateau (touche, action), listés dans la fenêtre d'aide
+const SHORTCUTS: [(&str, &str); 6] = [
+    ("shortcuts.key_space", "shortcuts.start_pause"),
+    ("shortcuts.key_right", "shortcuts.step"),
+    ("shortcuts.key_plus_minus", "shortcuts.speed"),
+    ("shortcuts.key_f", "shortcuts.pheromones"),
+    ("shortcuts.key_r", "shortcuts.reset"),
+    ("shortcuts.key_help", "shortcuts.help"),
+];
+
+#[derive(Clone, Copy, PartialEq)]
 enum AppState {
     DimensionInput,
     MapTypeSelection,
     MapEditor,
+    EventEditor,
     AntTypeSelection,
     Game,
 }
 
+// Carte dont on dessine la politique apprise (flèche vers la meilleure action)
+#[derive(Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum PolicyOverlay {
+    Hidden,
+    Food,
+    Nest,
+}
+
+// Outil du mode Dieu : inspecter la case cliquée, ou y poser directement un élément
+#[derive(Clone, Copy, PartialEq)]
+enum GodTool {
+    Inspect,
+    Food,
+    Wall,
+    DeathZone,
+    Erase,
+}
+
+impl GodTool {
+    const ALL: [GodTool; 5] = [
+        GodTool::Inspect,
+        GodTool::Food,
+        GodTool::Wall,
+        GodTool::DeathZone,
+        GodTool::Erase,
+    ];
+
+    fn label(self) -> &'static str {
+        tr(match self {
+            GodTool::Inspect => "god.inspect",
+            GodTool::Food => "god.food",
+            GodTool::Wall => "tile.wall",
+            GodTool::DeathZone => "tile.death_zone",
+            GodTool::Erase => "god.erase",
+        })
+    }
+
+    // Case posée par un clic, None pour l'inspection
+    fn tile(self, food_amount: u32) -> Option<TileType> {
+        match self {
+            GodTool::Inspect => None,
+            GodTool::Food => Some(TileType::FoodSource {
+                amount: food_amount,
+                value: SUGAR_VALUE,
+            }),
+            GodTool::Wall => Some(TileType::Wall),
+            GodTool::DeathZone => Some(TileType::DeathZone),
+            GodTool::Erase => Some(TileType::Default),
+        }
+    }
+}
+
+// Colonnes et lignes de la grille qui recoupent la zone de dessin : les autres ne sont pas dessinées
+fn visible_cells(
+    painter: &egui::Painter,
+    (off_x, off_y): (f32, f32),
+    size: f32,
+    (width, height): (u32, u32),
+) -> (Range<u32>, Range<u32>) {
+    let clip = painter.clip_rect();
+    let span = |min: f32, max: f32, offset: f32, count: u32| {
+        let first = ((min - offset) / size).floor().clamp(0.0, count as f32) as u32;
+        let last = ((max - offset) / size).ceil().clamp(0.0, count as f32) as u32;
+        first..last
+    };
+    (
+        span(clip.min.x, clip.max.x, off_x, width),
+        span(clip.min.y, clip.max.y, off_y, height),
+    )
+}
+
+// Rythme exprimé en ticks par seconde, dans les bornes du curseur
+fn as_ticks_per_second(rate: TickRate) -> u32 {
+    match rate {
+        TickRate::Unlimited => MAX_TICKS_PER_SECOND,
+        TickRate::TicksPerSecond(tps) => tps,
+        TickRate::MsPerTick(ms) => (1000 / ms.max(1)) as u32,
+    }
+    .clamp(1, MAX_TICKS_PER_SECOND)
+}
+
+// Rythme exprimé en délai entre deux ticks, dans les bornes du curseur
+fn as_ms_per_tick(rate: TickRate) -> u64 {
+    match rate {
+        TickRate::Unlimited => 1,
+        TickRate::TicksPerSecond(tps) => 1000 / tps.max(1) as u64,
+        TickRate::MsPerTick(ms) => ms,
+    }
+    .clamp(1, MAX_TICK_DELAY)
+}
+
+// Le pas à pas n'a de sens que sur une partie en pause qui peut encore avancer
+fn can_step(view: &GameView) -> bool {
+    !view.running && view.end_reason.is_none() && !view.replay_finished
+}
+
+// Classement de la fenêtre des meilleurs scores, les résultats de `current` (la partie qui
+// vient de se terminer) en doré ; une colonne de plus pour les classements de colonies
+fn score_table(
+    ui: &mut egui::Ui,
+    id: &str,
+    entries: &[&ScoreEntry],
+    rule: &ScoreRule,
+    current: &[RunResult],
+) {
+    let per_colony = entries.iter().any(|entry| entry.result.colony.is_some());
+    egui::Grid::new(id).striped(true).show(ui, |ui| {
+        let mut headers = vec![tr("scores.rank")];
+        if per_colony {
+            headers.push(tr("scores.colony"));
+        }
+        headers.extend([
+            tr("scores.score"),
+            tr("scores.food"),
+            tr("scores.deaths"),
+            tr("scores.ticks"),
+            "α",
+            "γ",
+            "ε",
+            tr("scores.seed"),
+            tr("scores.date"),
+        ]);
+        for header in headers {
+            ui.strong(header);
+        }
+        ui.end_row();
+
+        for (rank, entry) in entries.iter().take(SCORES_SHOWN).enumerate() {
+            let result = &entry.result;
+            let mut cells = vec![(rank + 1).to_string()];
+            if per_colony {
+                cells.push(
+                    result
+                        .colony
+                        .map_or_else(String::new, |colony| Colony::label(colony).to_string()),
+                );
+            }
+            cells.extend([
+                format!("{:.1}", rule.score(result)),
+                result.food.to_string(),
+                result.deaths.to_string(),
+                result.ticks.to_string(),
+                format!("{:.2}", result.alpha),
+                format!("{:.2}", result.gamma),
+                format!("{:.2}", result.epsilon),
+                result.seed.to_string(),
+                entry
+                    .recorded_at
+                    .get(..10)
+                    .unwrap_or(&entry.recorded_at)
+                    .to_string(),
+            ]);
+            let highlight = current.contains(result);
+            for cell in cells {
+                if highlight {
+                    ui.colored_label(egui::Color32::GOLD, cell);
+                } else {
+                    ui.label(cell);
+                }
+            }
+            ui.end_row();
+        }
+    });
+}
+
+impl PolicyOverlay {
+    fn label(&self) -> &'static str {
+        match self {
+            PolicyOverlay::Hidden => tr("overlay.hidden"),
+            PolicyOverlay::Food => tr("overlay.food"),
+            PolicyOverlay::Nest => tr("overlay.nest"),
+        }
+    }
+}
+
 pub struct Interface {
     state: AppState,
     config: SimulationConfig,
     width_input: String,
     height_input: String,
+    // Partie en préparation, confiée au worker à l'ouverture du plateau
     ants_game_manager: Option<AntsGameManager>,
+    // Partie en cours, calculée hors du thread d'affichage
+    worker: Option<SimulationWorker>,
     nb_explorers: usize,
     nb_pickers: usize,
     nb_fighters: usize,
-    is_running: bool,
     simulation_started: bool,
-    last_update: Instant,
-
-    // Paramètres Q-Learning
-    alpha_input: String,
-    gamma_input: String,
-    epsilon_input: String,
+    // Nombre de ticks du bouton d'avance rapide
+    fast_forward_ticks: u64,
+    // Réglages de la relecture de la timeline
+    history_playback: HistoryPlayback,
 
     // Éditeur de carte
     map_editor: Option<MapEditor>,
+    // Éditeur de la chronologie du scénario, et écran où il ramène une fois fermé
+    event_editor: Option<EventEditor>,
+    event_editor_back: AppState,
+    // Dernière erreur de création de la partie, affichée sous le bouton de lancement
+    launch_error: Option<String>,
 
     // Options d'affichage
     show_pheromones_food: bool,
     show_pheromones_nest: bool,
+    // Phéromone d'alarme de la colonie affichée, en violet
+    show_fear: bool,
+    // Échelle des pistes suivant leurs valeurs (sinon fixe, `q_display_scale`), logarithmique
+    heat_auto: bool,
+    heat_log: bool,
+    // Plage suivie des pistes (nourriture, nid) de chaque plateau
+    heat_ranges: [[HeatScale; 2]; 2],
+    // Calque des passages des fourmis sur chaque case
+    show_traffic: bool,
+    // Dessiner le trajet récent de chaque fourmi
+    show_ant_paths: bool,
+    policy_overlay: PolicyOverlay,
+    // Colonie dont on affiche les pistes en mode multi-colonies
+    shown_colony: usize,
+    // Type de fourmi dont on affiche les cartes quand chaque type a les siennes
+    shown_caste: AntsType,
+    // Zoom et déplacement du plateau de jeu
+    board_camera: Camera,
+    // Cases fixes de chaque plateau (partie principale, seconde partie en comparaison)
+    static_layers: [StaticLayer; 2],
+    // Fourmi ouverte dans l'inspecteur (`Ant::id`, stable quand d'autres meurent ou naissent)
+    selected_ant: Option<u32>,
+    // La vue se recentre sur la fourmi inspectée à chaque image
+    follow_selected_ant: bool,
+    // Minicarte de toute la grille dans le coin du plateau zoomé
+    show_minimap: bool,
+    // Mode Dieu : un clic sélectionne une case à modifier au lieu d'une fourmi, ou la
+    // modifie directement avec l'outil choisi dans la barre d'outils
+    god_mode: bool,
+    god_tool: GodTool,
+    selected_tile: Option<(u32, u32)>,
+    // Quantité de nourriture déposée par le mode Dieu
+    god_food_amount: u32,
+
+    // Comparaison : une seconde partie sur la même carte, jouée tick pour tick
+    comparison: bool,
+    // La seconde partie reprend exactement les réglages de la première
+    comparison_shared: bool,
+    // Réglages d'apprentissage propres à la seconde partie
+    rival_config: SimulationConfig,
+
+    // Journal d'événements installé par le programme, et types d'événements affichés
+    event_log: Option<EventLog>,
+    shown_log_kinds: HashSet<LogKind>,
+
+    // Fenêtre d'aide des raccourcis clavier du plateau
+    show_shortcuts: bool,
+
+    // Presets disponibles (relus après chaque enregistrement), choix courant et nom à donner
+    presets: Vec<Preset>,
+    selected_preset: Option<Preset>,
+    preset_name: String,
+    preset_error: Option<String>,
+
+    // Balayage alpha/gamma/epsilon lancé depuis le panneau Méta-optimiseur
+    #[cfg(not(target_arch = "wasm32"))]
+    sweep_panel: SweepPanel,
+    // Fichier de session du menu Session, et dernière erreur d'ouverture
+    session_path: String,
+    session_error: Option<String>,
+
+    // Tutoriel en cours, affiché par-dessus l'écran courant, et dernière erreur de
+    // chargement de ses scénarios
+    tutorial: Option<Tutorial>,
+    tutorial_error: Option<String>,
+
+    // Meilleurs scores : classement relu du disque, dernière partie inscrite (pour ne
+    // l'inscrire qu'une fois), fenêtre ouverte et dernière erreur de lecture ou d'écriture
+    leaderboard: Option<Leaderboard>,
+    recorded_scores: Option<RunScores>,
+    show_scores: bool,
+    leaderboard_error: Option<String>,
+}
+
+/// Réglages de l'interface enregistrés avec la partie dans une session (voir `session`)
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct InterfaceState {
+    // Configurations au format TOML : bincode ne sait pas relire les événements de scénario
+    config: String,
+    rival_config: String,
+    nb_explorers: usize,
+    nb_pickers: usize,
+    nb_fighters: usize,
+    simulation_started: bool,
+    fast_forward_ticks: u64,
+    history_playback: HistoryPlayback,
+    show_pheromones_food: bool,
+    show_pheromones_nest: bool,
+    show_fear: bool,
+    heat_auto: bool,
+    heat_log: bool,
+    show_traffic: bool,
+    show_ant_paths: bool,
+    policy_overlay: PolicyOverlay,
+    shown_colony: usize,
+    shown_caste: AntsType,
+    board_camera: Camera,
+    selected_ant: Option<u32>,
+    follow_selected_ant: bool,
+    show_minimap: bool,
+    comparison: bool,
+    comparison_shared: bool,
 }
 
 impl Interface {
@@ -52,70 +398,349 @@ impl Interface {
             width_input: config.grid_width.to_string(),
             height_input: config.grid_height.to_string(),
             ants_game_manager: None,
+            worker: None,
 
             nb_explorers: config.num_explorers as usize,
             nb_pickers: config.num_pickers as usize,
             nb_fighters: config.num_fighters as usize,
-            is_running: false,
-            last_update: Instant::now(),
-
-            alpha_input: config.alpha.to_string(),
-            gamma_input: config.gamma.to_string(),
-            epsilon_input: config.epsilon.to_string(),
 
             map_editor: None,
+            event_editor: None,
+            event_editor_back: AppState::MapTypeSelection,
+            launch_error: None,
 
             show_pheromones_food: true,
             show_pheromones_nest: true,
+            show_fear: true,
+            heat_auto: true,
+            heat_log: false,
+            show_traffic: false,
+            heat_ranges: Default::default(),
+            show_ant_paths: false,
+            policy_overlay: PolicyOverlay::Hidden,
+            shown_colony: 0,
+            shown_caste: AntsType::EXPLORER,
+            board_camera: Camera::default(),
+            static_layers: Default::default(),
+            selected_ant: None,
+            follow_selected_ant: false,
+            show_minimap: true,
+            god_mode: false,
+            god_tool: GodTool::Inspect,
+            selected_tile: None,
+            god_food_amount: 50,
+            comparison: false,
+            comparison_shared: false,
+            rival_config: config.clone(),
+            event_log: event_log::global(),
+            shown_log_kinds: LogKind::all().collect(),
+            show_shortcuts: false,
+            presets: Preset::all(),
+            selected_preset: None,
+            preset_name: String::new(),
+            preset_error: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            sweep_panel: SweepPanel::default(),
+            session_path: DEFAULT_SESSION_FILE.to_string(),
+            session_error: None,
+            tutorial: None,
+            tutorial_error: None,
+            leaderboard: None,
+            recorded_scores: None,
+            show_scores: false,
+            leaderboard_error: None,
             simulation_started: false,
+            fast_forward_ticks: 1000,
+            history_playback: HistoryPlayback {
+                snapshots_per_second: 10,
+                looping: false,
+            },
             config,
         }
     }
 }
 
-impl eframe::App for Interface {
-    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        // Gestion de la boucle de jeu automatique
-        if self.state == AppState::Game && self.is_running {
-            if self.last_update.elapsed() >= Duration::from_millis(self.config.simulation_speed) {
-                if let Some(manager) = &mut self.ants_game_manager {
-                    manager.game_step();
-                }
-                self.last_update = Instant::now();
-            }
-            ctx.request_repaint();
+impl Interface {
+    /// Ouvre directement le plateau sur une partie enregistrée, prête à être rejouée
+    pub fn new_with_replay(manager: AntsGameManager) -> Self {
+        let mut interface = Self::new_with_config(manager.config().clone());
+        interface.ants_game_manager = Some(manager);
+        interface.state = AppState::Game;
+        // Les paramètres de la partie enregistrée ne doivent pas être modifiés
+        interface.simulation_started = true;
+        interface
+    }
+
+    /// Ouvre directement le plateau sur une partie préparée hors de l'assistant (carte passée
+    /// en ligne de commande) ; les paramètres restent modifiables jusqu'au lancement
+    pub fn new_with_game(manager: AntsGameManager) -> Self {
+        let grid = manager.grid();
+        let mut interface = Self::new_with_config(SimulationConfig {
+            grid_width: grid.get_width(),
+            grid_height: grid.get_height(),
+            ..manager.config().clone()
+        });
+        interface.ants_game_manager = Some(manager);
+        interface.state = AppState::Game;
+        interface
+    }
+
+    /// Ouvre directement l'éditeur de carte, la partie se lance ensuite comme depuis le menu
+    pub fn new_with_editor(editor: crate::map_editor::MapEditor, config: SimulationConfig) -> Self {
+        let mut interface = Self::new_with_config(SimulationConfig {
+            grid_width: editor.width,
+            grid_height: editor.height,
+            ..config
+        });
+        interface.map_editor = Some(editor);
+        interface.state = AppState::MapEditor;
+        interface
+    }
+}
+
+impl Interface {
+    /// Reprend les réglages gardés par eframe d'une session à l'autre (la langue)
+    pub fn with_storage(self, storage: Option<&dyn eframe::Storage>) -> Self {
+        if let Some(language) =
+            storage.and_then(|storage| eframe::get_value::<Language>(storage, LANGUAGE_KEY))
+        {
+            i18n::set_language(language);
         }
+        if let Some(render_theme) =
+            storage.and_then(|storage| eframe::get_value::<RenderTheme>(storage, THEME_KEY))
+        {
+            theme::set_theme(render_theme);
+        }
+        self
+    }
+}
+
+impl Default for Interface {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
+impl eframe::App for Interface {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.show_settings_bar(ctx);
         match self.state {
             AppState::DimensionInput => self.show_dimension_input(ctx),
             AppState::MapTypeSelection => self.show_map_type_selection(ctx),
             AppState::MapEditor => self.show_map_editor_screen(ctx),
+            AppState::EventEditor => self.show_event_editor_screen(ctx),
             AppState::AntTypeSelection => self.show_ant_type_selection(ctx),
             AppState::Game => self.show_game(ctx),
         }
+        self.show_tutorial(ctx);
+    }
+
+    // La langue et le thème choisis sont retrouvés au prochain lancement (voir `with_storage`)
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, LANGUAGE_KEY, &i18n::language());
+        eframe::set_value(storage, THEME_KEY, &theme::theme());
     }
 }
 
 impl Interface {
+    // Barre du haut, présente sur tous les écrans
+    fn show_settings_bar(&mut self, ctx: &egui::Context) {
+        egui::TopBottomPanel::top("settings_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.menu_button(tr("settings.menu"), |ui| {
+                    ui.label(tr("settings.language"));
+                    let mut language = i18n::language();
+                    for option in Language::all() {
+                        ui.radio_value(&mut language, option, option.label());
+                    }
+                    if language != i18n::language() {
+                        i18n::set_language(language);
+                    }
+                    ui.separator();
+                    ui.label(tr("settings.theme"));
+                    let mut render_theme = theme::theme();
+                    for option in RenderTheme::all() {
+                        ui.radio_value(&mut render_theme, option, tr(option.label_key()));
+                    }
+                    if render_theme != theme::theme() {
+                        theme::set_theme(render_theme);
+                    }
+                });
+                ui.menu_button(tr("session.menu"), |ui| self.show_session_menu(ui));
+                if self.state == AppState::Game {
+                    ui.toggle_value(&mut self.show_shortcuts, tr("shortcuts.button"))
+                        .on_hover_text(tr("shortcuts.hover"));
+                }
+            });
+        });
+    }
+
+    // Enregistrer la partie en cours avec les réglages de l'interface, ou rouvrir une session
+    fn show_session_menu(&mut self, ui: &mut egui::Ui) {
+        ui.label(tr("session.file"));
+        ui.text_edit_singleline(&mut self.session_path);
+        let path = self.session_path.trim().to_string();
+        ui.horizontal(|ui| {
+            let can_save = self.worker.is_some() && !path.is_empty();
+            if ui
+                .add_enabled(can_save, egui::Button::new(tr("session.save")))
+                .on_hover_text(tr("session.save_hint"))
+                .clicked()
+            {
+                match self.session_state() {
+                    Ok(state) => {
+                        if let Some(worker) = &mut self.worker {
+                            worker.send(WorkerCommand::SaveSession(path.clone(), Box::new(state)));
+                        }
+                        self.session_error = None;
+                    }
+                    Err(e) => self.session_error = Some(e),
+                }
+            }
+            if ui
+                .add_enabled(!path.is_empty(), egui::Button::new(tr("session.open")))
+                .clicked()
+            {
+                self.open_session(ui.ctx(), &path);
+            }
+        });
+        let saved = self
+            .worker
+            .as_mut()
+            .and_then(|worker| worker.view().session_saved.clone());
+        match saved {
+            Some(Ok(file)) => {
+                ui.colored_label(egui::Color32::GREEN, trf("session.saved", &[&file]));
+            }
+            Some(Err(e)) => {
+                ui.colored_label(egui::Color32::RED, e);
+            }
+            None => {}
+        }
+        if let Some(error) = &self.session_error {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+    }
+
+    // Réglages de l'interface à enregistrer avec la partie
+    fn session_state(&self) -> Result<InterfaceState, String> {
+        let to_toml = |config: &SimulationConfig| {
+            toml::to_string(config)
+                .map_err(|e| format!("Impossible de sérialiser la configuration: {}", e))
+        };
+        Ok(InterfaceState {
+            config: to_toml(&self.config)?,
+            rival_config: to_toml(&self.rival_config)?,
+            nb_explorers: self.nb_explorers,
+            nb_pickers: self.nb_pickers,
+            nb_fighters: self.nb_fighters,
+            simulation_started: self.simulation_started,
+            fast_forward_ticks: self.fast_forward_ticks,
+            history_playback: self.history_playback,
+            show_pheromones_food: self.show_pheromones_food,
+            show_pheromones_nest: self.show_pheromones_nest,
+            show_fear: self.show_fear,
+            heat_auto: self.heat_auto,
+            heat_log: self.heat_log,
+            show_traffic: self.show_traffic,
+            show_ant_paths: self.show_ant_paths,
+            policy_overlay: self.policy_overlay,
+            shown_colony: self.shown_colony,
+            shown_caste: self.shown_caste,
+            board_camera: self.board_camera.clone(),
+            selected_ant: self.selected_ant,
+            follow_selected_ant: self.follow_selected_ant,
+            show_minimap: self.show_minimap,
+            comparison: self.comparison,
+            comparison_shared: self.comparison_shared,
+        })
+    }
+
+    // Remplace la partie en cours par celle de la session, en pause, avec ses réglages
+    fn open_session(&mut self, ctx: &egui::Context, path: &str) {
+        let opened = session::load(path).and_then(|session| {
+            let parse = |config: &str| {
+                toml::from_str::<SimulationConfig>(config)
+                    .map_err(|e| format!("Session invalide {}: {}", path, e))
+            };
+            let state = session.interface;
+            Ok((
+                session.manager,
+                session.rival,
+                parse(&state.config)?,
+                parse(&state.rival_config)?,
+                state,
+            ))
+        });
+        let (manager, rival, config, rival_config, state) = match opened {
+            Ok(opened) => opened,
+            Err(e) => {
+                self.session_error = Some(e);
+                return;
+            }
+        };
+        let mut worker = SimulationWorker::spawn(manager, ctx.clone());
+        if let Some(rival) = rival {
+            worker.send(WorkerCommand::ResumeRival(Box::new(rival)));
+        }
+        worker.send(WorkerCommand::SetTickRate(config.tick_rate));
+        worker.send(WorkerCommand::SetTicksPerFrame(config.ticks_per_frame));
+        worker.send(WorkerCommand::Track(state.selected_ant));
+        self.worker = Some(worker);
+        self.ants_game_manager = None;
+        self.map_editor = None;
+        self.state = AppState::Game;
+        self.session_error = None;
+        self.selected_tile = None;
+
+        self.width_input = config.grid_width.to_string();
+        self.height_input = config.grid_height.to_string();
+        self.config = config;
+        self.rival_config = rival_config;
+        self.nb_explorers = state.nb_explorers;
+        self.nb_pickers = state.nb_pickers;
+        self.nb_fighters = state.nb_fighters;
+        self.simulation_started = state.simulation_started;
+        self.fast_forward_ticks = state.fast_forward_ticks;
+        self.history_playback = state.history_playback;
+        self.show_pheromones_food = state.show_pheromones_food;
+        self.show_pheromones_nest = state.show_pheromones_nest;
+        self.show_fear = state.show_fear;
+        self.heat_auto = state.heat_auto;
+        self.heat_log = state.heat_log;
+        self.heat_ranges = Default::default();
+        self.show_traffic = state.show_traffic;
+        self.show_ant_paths = state.show_ant_paths;
+        self.policy_overlay = state.policy_overlay;
+        self.shown_colony = state.shown_colony;
+        self.shown_caste = state.shown_caste;
+        self.board_camera = state.board_camera;
+        self.selected_ant = state.selected_ant;
+        self.follow_selected_ant = state.follow_selected_ant;
+        self.show_minimap = state.show_minimap;
+        self.comparison = state.comparison;
+        self.comparison_shared = state.comparison_shared;
+    }
+
     fn show_dimension_input(&mut self, ctx: &egui::Context) {
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.vertical_centered(|ui| {
                 ui.add_space(50.0);
-                ui.heading("Définir les dimensions de la grille");
+                ui.heading(tr("dimensions.title"));
                 ui.add_space(30.0);
 
-                ui.label("Largeur :");
+                ui.label(tr("dimensions.width"));
                 ui.text_edit_singleline(&mut self.width_input);
 
                 ui.add_space(20.0);
 
-                ui.label("Hauteur :");
+                ui.label(tr("dimensions.height"));
                 ui.text_edit_singleline(&mut self.height_input);
 
                 ui.add_space(30.0);
 
                 if ui
-                    .button(egui::RichText::new("Continuer").size(18.0))
+                    .button(egui::RichText::new(tr("dimensions.continue")).size(18.0))
                     .clicked()
                 {
                     if let (Ok(width), Ok(height)) = (
@@ -125,25 +750,208 @@ impl Interface {
                         if width > 0 && height > 0 {
                             self.state = AppState::MapTypeSelection;
                         } else {
-                            eprintln!("Les dimensions doivent être supérieures à 0");
+                            tracing::warn!("Les dimensions doivent être supérieures à 0");
                         }
                     } else {
-                        eprintln!("Veuillez entrer des nombres valides");
+                        tracing::warn!("Veuillez entrer des nombres valides");
                     }
                 }
+
+                ui.add_space(40.0);
+                ui.separator();
+                ui.add_space(10.0);
+                if ui
+                    .button(egui::RichText::new(tr("tutorial.button")).size(18.0))
+                    .on_hover_text(tr("tutorial.button_hint"))
+                    .clicked()
+                {
+                    self.tutorial = Some(Tutorial::new());
+                    self.enter_tutorial_step();
+                }
             });
         });
     }
 
+    // Prépare l'étape courante du tutoriel : son scénario, puis le calque mis en avant
+    fn enter_tutorial_step(&mut self) {
+        let Some(step) = self.tutorial.as_ref().map(Tutorial::step) else {
+            return;
+        };
+        if let Some(index) = step.scenario {
+            if let Err(e) = self.load_tutorial_scenario(index) {
+                self.tutorial_error = Some(e);
+                return;
+            }
+        }
+        self.tutorial_error = None;
+        match step.highlight {
+            Highlight::None => {}
+            Highlight::FoodTrail => {
+                self.show_pheromones_food = true;
+                self.show_pheromones_nest = false;
+                self.policy_overlay = PolicyOverlay::Hidden;
+            }
+            Highlight::NestTrail => {
+                self.show_pheromones_food = false;
+                self.show_pheromones_nest = true;
+                self.policy_overlay = PolicyOverlay::Hidden;
+            }
+            Highlight::Policy => {
+                self.show_pheromones_food = true;
+                self.show_pheromones_nest = true;
+                self.policy_overlay = PolicyOverlay::Food;
+            }
+        }
+    }
+
+    // Remplace la partie par le scénario `index` du tutoriel, prête à être lancée
+    fn load_tutorial_scenario(&mut self, index: usize) -> Result<(), String> {
+        let scenario = tutorial::scenario(index)?;
+        let editor = scenario
+            .map_editor()
+            .ok_or_else(|| "Le scénario du tutoriel n'a pas de carte".to_string())??;
+        let config = SimulationConfig {
+            grid_width: editor.width,
+            grid_height: editor.height,
+            ..scenario.config
+        };
+        let mut manager = AntsGameManager::new(
+            editor.width,
+            editor.height,
+            editor.to_tiles(),
+            vec![],
+            config.clone(),
+        )
+        .map_err(|e| e.to_string())?;
+        manager.set_ants(Ant::colony_from_config(&config));
+
+        // Détruire la poignée arrête le thread de l'ancienne partie
+        self.worker = None;
+        self.ants_game_manager = Some(manager);
+        self.map_editor = None;
+        self.state = AppState::Game;
+        self.simulation_started = false;
+        self.god_mode = false;
+        self.comparison = false;
+        self.width_input = config.grid_width.to_string();
+        self.height_input = config.grid_height.to_string();
+        self.nb_explorers = config.num_explorers as usize;
+        self.nb_pickers = config.num_pickers as usize;
+        self.nb_fighters = config.num_fighters as usize;
+        self.rival_config = config.clone();
+        self.config = config;
+        Ok(())
+    }
+
+    // Fenêtre du tutoriel, dans le coin du plateau ; l'étape avance d'elle-même quand la
+    // partie remplit sa condition
+    fn show_tutorial(&mut self, ctx: &egui::Context) {
+        let Some(tutorial) = &self.tutorial else {
+            return;
+        };
+        let tick = self
+            .worker
+            .as_mut()
+            .map_or(0, |worker| worker.view().state.tick);
+        if tutorial.ready(self.simulation_started, tick) {
+            if let Some(tutorial) = &mut self.tutorial {
+                tutorial.forward();
+            }
+            self.enter_tutorial_step();
+        }
+        let Some(tutorial) = self.tutorial.clone() else {
+            return;
+        };
+        let step = tutorial.step();
+
+        enum Move {
+            Previous,
+            Next,
+            Quit,
+        }
+        let mut action = None;
+        egui::Window::new(tr(step.title))
+            .id(egui::Id::new("tutorial_window"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, [-16.0, -16.0])
+            .collapsible(false)
+            .resizable(false)
+            .default_width(320.0)
+            .show(ctx, |ui| {
+                ui.label(tr(step.text));
+                ui.add_space(6.0);
+                match step.advance {
+                    tutorial::Advance::Next => {}
+                    tutorial::Advance::Started => {
+                        ui.label(egui::RichText::new(tr("tutorial.waiting_start")).italics());
+                    }
+                    tutorial::Advance::Tick(target) => {
+                        ui.label(
+                            egui::RichText::new(trf("tutorial.waiting_tick", &[&tick, &target]))
+                                .italics(),
+                        );
+                    }
+                }
+                if let Some(error) = &self.tutorial_error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(trf(
+                        "tutorial.progress",
+                        &[&(tutorial.index() + 1), &tutorial::STEPS.len()],
+                    ));
+                    if ui
+                        .add_enabled(
+                            tutorial.index() > 0,
+                            egui::Button::new(tr("tutorial.previous")),
+                        )
+                        .clicked()
+                    {
+                        action = Some(Move::Previous);
+                    }
+                    let next = if tutorial.is_last() {
+                        tr("tutorial.finish")
+                    } else {
+                        tr("tutorial.next")
+                    };
+                    if ui.button(next).clicked() {
+                        action = Some(Move::Next);
+                    }
+                    if !tutorial.is_last() && ui.button(tr("tutorial.quit")).clicked() {
+                        action = Some(Move::Quit);
+                    }
+                });
+            });
+
+        match action {
+            Some(Move::Previous) => {
+                if let Some(tutorial) = &mut self.tutorial {
+                    tutorial.back();
+                }
+                self.enter_tutorial_step();
+            }
+            Some(Move::Next) => {
+                let moved = self.tutorial.as_mut().is_some_and(Tutorial::forward);
+                if moved {
+                    self.enter_tutorial_step();
+                } else {
+                    self.tutorial = None;
+                }
+            }
+            Some(Move::Quit) => self.tutorial = None,
+            None => {}
+        }
+    }
+
     fn show_map_type_selection(&mut self, ctx: &egui::Context) {
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.vertical_centered(|ui| {
                 ui.add_space(50.0);
-                ui.heading("Sélection du type de map");
+                ui.heading(tr("map_type.title"));
                 ui.add_space(30.0);
 
                 if ui
-                    .button(egui::RichText::new("Map Aléatoire").size(20.0))
+                    .button(egui::RichText::new(tr("map_type.random")).size(20.0))
                     .clicked()
                 {
                     let width = self.width_input.parse::<u32>().unwrap_or(20);
@@ -158,11 +966,12 @@ impl Interface {
 
                     self.state = AppState::AntTypeSelection;
                 }
+                Self::show_map_gen_settings(ui, &mut self.config.map_gen);
 
                 ui.add_space(20.0);
 
                 if ui
-                    .button(egui::RichText::new("Map Personnalisée").size(20.0))
+                    .button(egui::RichText::new(tr("map_type.custom")).size(20.0))
                     .clicked()
                 {
                     let width = self.width_input.parse::<u32>().unwrap_or(20);
@@ -176,118 +985,224 @@ impl Interface {
                     self.state = AppState::MapEditor;
                 }
 
+                ui.add_space(20.0);
+
+                if ui
+                    .button(tr("map_type.events"))
+                    .on_hover_text(tr("map_type.events_hint"))
+                    .clicked()
+                {
+                    self.config.grid_width = self.width_input.parse::<u32>().unwrap_or(20);
+                    self.config.grid_height = self.height_input.parse::<u32>().unwrap_or(20);
+                    self.open_event_editor(AppState::MapTypeSelection);
+                }
+
                 ui.add_space(20.0);
                 ui.separator();
 
-                if ui.button("Retour aux Dimensions").clicked() {
+                if ui.button(tr("map_type.back")).clicked() {
                     self.state = AppState::DimensionInput;
                 }
             });
         });
     }
 
+    // Proportions de la carte aléatoire, repliées par défaut
+    fn show_map_gen_settings(ui: &mut egui::Ui, params: &mut MapGenParams) {
+        egui::CollapsingHeader::new(tr("map_gen.title")).show(ui, |ui| {
+            ui.add(
+                egui::Slider::new(&mut params.wall_density, 0.0..=1.0).text(tr("map_gen.walls")),
+            );
+            ui.add(
+                egui::Slider::new(&mut params.death_density, 0.0..=1.0)
+                    .text(tr("map_gen.death_zones")),
+            );
+            ui.add(
+                egui::Slider::new(&mut params.food_sources, 1..=20)
+                    .text(tr("map_gen.food_sources")),
+            );
+            ui.add(
+                egui::Slider::new(&mut params.food_min, 1..=params.food_max)
+                    .text(tr("map_gen.food_min")),
+            );
+            ui.add(
+                egui::Slider::new(&mut params.food_max, params.food_min..=20000)
+                    .text(tr("map_gen.food_max")),
+            );
+            ui.add(
+                egui::Slider::new(&mut params.protein_share, 0.0..=1.0).text(tr("map_gen.protein")),
+            );
+            ui.horizontal(|ui| {
+                let mut fixed = params.seed.is_some();
+                if ui.checkbox(&mut fixed, tr("map_gen.seed")).changed() {
+                    params.seed = fixed.then_some(0);
+                }
+                if let Some(seed) = &mut params.seed {
+                    ui.add(egui::DragValue::new(seed));
+                }
+            })
+            .response
+            .on_hover_text(tr("map_gen.seed_hint"));
+            if ui.button(tr("map_gen.reset")).clicked() {
+                *params = MapGenParams::default();
+            }
+        });
+    }
+
     fn show_ant_type_selection(&mut self, ctx: &egui::Context) {
         egui::CentralPanel::default().show(ctx, |ui| {
             ui.vertical_centered(|ui| {
                 ui.add_space(50.0);
-                ui.heading("Configuration de la Colonie");
+                ui.heading(tr("setup.title"));
                 ui.add_space(30.0);
 
                 // Sliders pour le nombre de fourmis
                 ui.group(|ui| {
-                    ui.heading("Explorateurs");
+                    ui.heading(tr("setup.explorers"));
                     ui.add(egui::Slider::new(&mut self.nb_explorers, 0..=50));
+                    Self::show_ant_profile_sliders(
+                        ui,
+                        "explorer_profile",
+                        &mut self.config.explorer_capacity_load,
+                        &mut self.config.explorer_speed,
+                        &mut self.config.explorer_scope,
+                    );
                 });
                 ui.add_space(10.0);
 
                 ui.group(|ui| {
-                    ui.heading("Récolteuses");
+                    ui.heading(tr("setup.pickers"));
                     ui.add(egui::Slider::new(&mut self.nb_pickers, 0..=50));
+                    Self::show_ant_profile_sliders(
+                        ui,
+                        "picker_profile",
+                        &mut self.config.picker_capacity_load,
+                        &mut self.config.picker_speed,
+                        &mut self.config.picker_scope,
+                    );
                 });
                 ui.add_space(10.0);
 
                 ui.group(|ui| {
-                    ui.heading("Combattantes");
+                    ui.heading(tr("setup.fighters"));
                     ui.add(egui::Slider::new(&mut self.nb_fighters, 0..=50));
+                    Self::show_ant_profile_sliders(
+                        ui,
+                        "fighter_profile",
+                        &mut self.config.fighter_capacity_load,
+                        &mut self.config.fighter_speed,
+                        &mut self.config.fighter_scope,
+                    );
                 });
+                ui.add_space(10.0);
+
+                ui.checkbox(&mut self.config.sensing, tr("setup.sensing"))
+                    .on_hover_text(tr("setup.sensing_hint"));
 
                 ui.add_space(30.0);
 
                 let total = self.nb_explorers + self.nb_pickers + self.nb_fighters;
-                ui.label(format!("Total: {} fourmis", total));
+                ui.label(trf("setup.total", &[&total]));
 
                 ui.add_space(20.0);
 
-                if ui.button("Vers le Plateau de Jeu").clicked() {
+                if ui.button(tr("setup.to_board")).clicked() {
                     // 1. Mise à jour de la configuration avec les sliders actuels
                     self.config.num_explorers = self.nb_explorers as u32;
                     self.config.num_pickers = self.nb_pickers as u32;
                     self.config.num_fighters = self.nb_fighters as u32;
 
                     // 2. Génération des fourmis
-                    let ants = self.generate_ants();
+                    let ants = Ant::colony_from_config(&self.config);
 
                     // 3. Gestion du Manager (Création ou Mise à jour)
-                    let mut manager =
-                        if let Some(mut existing_manager) = self.ants_game_manager.take() {
-                            // CAS 1 : Map Éditeur (Le manager existe déjà avec la grille)
-                            existing_manager.ants = ants;
-
-                            for ant in &mut existing_manager.ants {
-                                ant.spawn_at_nest(&existing_manager.grid);
-                            }
-
-                            existing_manager.config = self.config.clone();
-                            existing_manager
-                        } else {
-                            AntsGameManager::new_game_mode_random(
-                                self.config.grid_width,
-                                self.config.grid_height,
-                                ants,
-                                self.config.clone(),
-                            )
-                        };
+                    let manager = if let Some(mut existing_manager) = self.ants_game_manager.take()
+                    {
+                        // CAS 1 : Map Éditeur (Le manager existe déjà avec la grille)
+                        existing_manager.set_ants(ants);
+                        Ok(existing_manager)
+                    } else {
+                        AntsGameManager::new_game_mode_random(
+                            self.config.grid_width,
+                            self.config.grid_height,
+                            ants,
+                            self.config.clone(),
+                        )
+                    };
 
-                    // 4. Synchronisation initiale des paramètres Q-Learning
-                    manager.rl_params.alpha = self.config.alpha;
-                    manager.rl_params.gamma = self.config.gamma;
-                    manager.rl_params.epsilon = self.config.epsilon;
+                    match manager {
+                        Ok(mut manager) => {
+                            // 4. Synchronisation initiale des paramètres Q-Learning
+                            manager.set_config(self.config.clone());
 
-                    self.ants_game_manager = Some(manager);
+                            self.ants_game_manager = Some(manager);
+                            self.launch_error = None;
 
-                    // 5. Transition vers le jeu
-                    self.state = AppState::Game;
-                    self.is_running = false; // Pause au démarrage
-                    self.simulation_started = false; // Paramètres déverrouillés
+                            // 5. Transition vers le jeu
+                            self.state = AppState::Game;
+                            self.simulation_started = false; // Paramètres déverrouillés
+                        }
+                        Err(e) => self.launch_error = Some(e.to_string()),
+                    }
+                }
+                if let Some(error) = &self.launch_error {
+                    ui.colored_label(egui::Color32::RED, error);
                 }
 
                 ui.add_space(10.0);
-                if ui.button("Retour").clicked() {
+                if ui.button(tr("common.back")).clicked() {
                     self.state = AppState::MapTypeSelection;
                 }
             });
         });
     }
 
-    fn generate_ants(&self) -> Vec<Ant> {
-        let mut ants = Vec::new();
-
-        // Générer les Explorateurs
-        for _ in 0..self.nb_explorers {
-            ants.push(Ant::new(AntsType::EXPLORER));
-        }
-
-        // Générer les Pickers
-        for _ in 0..self.nb_pickers {
-            ants.push(Ant::new(AntsType::PICKER));
+    // Contrôles d'un calendrier de décroissance (forme, taux, plancher, palier)
+    fn show_decay_controls(ui: &mut egui::Ui, id: &str, decay: &mut DecayConfig) {
+        egui::ComboBox::from_id_salt(id)
+            .selected_text(decay.schedule.label())
+            .show_ui(ui, |ui| {
+                for schedule in DecaySchedule::all() {
+                    ui.selectable_value(&mut decay.schedule, schedule, schedule.label());
+                }
+            });
+        match decay.schedule {
+            DecaySchedule::Constant => return,
+            DecaySchedule::Linear => {
+                ui.add(
+                    egui::Slider::new(&mut decay.rate, 0.0..=0.001).text(tr("decay.linear_rate")),
+                );
+            }
+            DecaySchedule::Exponential | DecaySchedule::Step => {
+                ui.add(egui::Slider::new(&mut decay.rate, 0.9..=1.0).text(tr("decay.factor")));
+            }
         }
-
-        // Générer les Fighters
-        for _ in 0..self.nb_fighters {
-            ants.push(Ant::new(AntsType::FIGHTER));
+        if decay.schedule == DecaySchedule::Step {
+            ui.add(
+                egui::Slider::new(&mut decay.step_every, 1..=10000).text(tr("decay.step_every")),
+            );
         }
+        ui.add(egui::Slider::new(&mut decay.min, 0.0..=1.0).text(tr("decay.min")));
+    }
 
-        ants
+    // Sliders des caractéristiques d'un type de fourmi (charge, vitesse, vision)
+    fn show_ant_profile_sliders(
+        ui: &mut egui::Ui,
+        id: &str,
+        capacity_load: &mut u32,
+        speed: &mut u32,
+        scope: &mut u32,
+    ) {
+        egui::CollapsingHeader::new(tr("profile.title"))
+            .id_salt(id)
+            .show(ui, |ui| {
+                ui.label(tr("profile.capacity"));
+                ui.add(egui::Slider::new(capacity_load, 1..=500));
+                ui.label(tr("profile.speed"));
+                ui.add(egui::Slider::new(speed, 0..=50));
+                ui.label(tr("profile.scope"));
+                ui.add(egui::Slider::new(scope, 0..=5));
+            });
     }
 
     fn show_map_editor_screen(&mut self, ctx: &egui::Context) {
@@ -302,9 +1217,10 @@ impl Interface {
         }
 
         let mut action = EditorAction::None;
+        let mut open_events = false;
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            ui.heading("Éditeur de Carte");
+            ui.heading(tr("editor_screen.title"));
             ui.separator();
 
             if let Some(editor) = &mut self.map_editor {
@@ -314,13 +1230,21 @@ impl Interface {
                 ui.separator();
                 ui.add_space(10.0);
                 ui.horizontal(|ui| {
-                    if ui.button("✓ Confirmer la Carte").clicked() {
+                    if ui.button(tr("editor_screen.confirm")).clicked() {
                         manual_launch = true;
                     }
 
-                    if ui.button("← Retour au Choix").clicked() {
+                    if ui.button(tr("editor_screen.back")).clicked() {
                         action = EditorAction::GoBack;
                     }
+
+                    if ui
+                        .button(tr("editor_screen.events"))
+                        .on_hover_text(tr("map_type.events_hint"))
+                        .clicked()
+                    {
+                        open_events = true;
+                    }
                 });
 
                 if auto_launch || manual_launch {
@@ -340,11 +1264,22 @@ impl Interface {
                 height,
                 tiles,
             } => {
-                let game_manager =
-                    AntsGameManager::new(width, height, tiles, vec![], self.config.clone());
-                self.ants_game_manager = Some(game_manager);
-                self.map_editor = None;
-                self.state = AppState::AntTypeSelection;
+                // La carte a pu être redimensionnée ou tournée dans l'éditeur
+                self.config.grid_width = width;
+                self.config.grid_height = height;
+                match AntsGameManager::new(width, height, tiles, vec![], self.config.clone()) {
+                    Ok(game_manager) => {
+                        self.ants_game_manager = Some(game_manager);
+                        self.map_editor = None;
+                        self.state = AppState::AntTypeSelection;
+                    }
+                    // La carte reste ouverte dans l'éditeur pour être corrigée
+                    Err(e) => {
+                        if let Some(editor) = &mut self.map_editor {
+                            editor.file_status = Some(e.to_string());
+                        }
+                    }
+                }
             }
             EditorAction::GoBack => {
                 self.state = AppState::MapTypeSelection;
@@ -352,97 +1287,573 @@ impl Interface {
             }
             EditorAction::None => {}
         }
+        if open_events {
+            self.open_event_editor(AppState::MapEditor);
+        }
     }
 
-    fn show_game(&mut self, ctx: &egui::Context) {
-        egui::SidePanel::left("controls_panel")
-            .resizable(true)
-            .default_width(280.0)
-            .show(ctx, |ui| {
-                egui::ScrollArea::vertical().show(ui, |ui| {
-                    ui.vertical_centered(|ui| {
-                        ui.heading("Configuration & Contrôle");
-                    });
-                    ui.separator();
+    // Ouvre la chronologie du scénario sur les événements de la configuration ; `back` est
+    // l'écran retrouvé en la fermant
+    fn open_event_editor(&mut self, back: AppState) {
+        let (width, height) = match &self.map_editor {
+            Some(editor) => (editor.width, editor.height),
+            None => (self.config.grid_width, self.config.grid_height),
+        };
+        self.event_editor = Some(EventEditor::new(
+            self.config.events.clone(),
+            width,
+            height,
+            self.config.max_ticks,
+        ));
+        self.event_editor_back = back;
+        self.state = AppState::EventEditor;
+    }
 
-                    // Afficher les contrôles de lancement et pause de la simulation
-                    ui.group(|ui| {
-                        ui.heading("État de la Simulation");
-                        ui.add_space(5.0);
+    // Valider reprend la chronologie dans la configuration de la partie
+    fn show_event_editor_screen(&mut self, ctx: &egui::Context) {
+        let mut closed = None;
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading(tr("events_screen.title"));
+            ui.separator();
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                if let Some(editor) = &mut self.event_editor {
+                    crate::event_editor::show_event_editor(
+                        ui,
+                        editor,
+                        self.map_editor.as_ref(),
+                        &self.config,
+                    );
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui.button(tr("events_screen.confirm")).clicked() {
+                        closed = Some(true);
+                    }
+                    if ui.button(tr("events_screen.back")).clicked() {
+                        closed = Some(false);
+                    }
+                });
+            });
+        });
 
-                        if !self.simulation_started {
-                            // Avant le lancement, montrer le bouton de démarrage en vert
-                            ui.label("Prêt à démarrer. Réglez les paramètres ci-dessous.");
-                            if ui
-                                .button(
-                                    egui::RichText::new("LANCER LA SIMULATION")
-                                        .size(20.0)
-                                        .color(egui::Color32::GREEN),
-                                )
-                                .clicked()
-                            {
-                                self.simulation_started = true;
-                                self.is_running = true;
-                            }
-                        } else {
-                            // Une fois lancée, afficher seulement les boutons pause/reprise
-                            let btn_text = if self.is_running {
-                                "PAUSE"
+        if let Some(confirmed) = closed {
+            if let Some(editor) = self.event_editor.take() {
+                if confirmed {
+                    self.config.events = editor.sorted_events();
+                }
+            }
+            self.state = self.event_editor_back;
+        }
+    }
+
+    // Premier lancement de la partie : le worker reçoit les réglages choisis sur le plateau
+    fn launch(&mut self, view: &GameView, commands: &mut Vec<WorkerCommand>) {
+        self.simulation_started = true;
+        commands.push(WorkerCommand::SetConfig(Box::new(self.config.clone())));
+        if self.comparison && !view.replaying {
+            commands.push(WorkerCommand::SetRival(Some(Box::new(
+                self.rival_settings(),
+            ))));
+        }
+        commands.push(WorkerCommand::Start);
+    }
+
+    // Raccourcis du plateau, ignorés tant qu'un champ de texte a le clavier
+    fn handle_shortcuts(
+        &mut self,
+        ctx: &egui::Context,
+        view: &GameView,
+        commands: &mut Vec<WorkerCommand>,
+    ) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        let pressed = |key| ctx.input(|i| i.key_pressed(key));
+
+        if pressed(egui::Key::Questionmark) || pressed(egui::Key::F1) {
+            self.show_shortcuts = !self.show_shortcuts;
+        }
+        if pressed(egui::Key::Space) {
+            if !self.simulation_started {
+                self.launch(view, commands);
+            } else if view.running {
+                commands.push(WorkerCommand::Pause);
+            } else {
+                commands.push(WorkerCommand::Start);
+            }
+        }
+        if pressed(egui::Key::ArrowRight) && self.simulation_started && can_step(view) {
+            commands.push(WorkerCommand::Step(1));
+        }
+
+        // Accélérer double le nombre de ticks par seconde, ralentir le divise par deux
+        let rate = self.config.tick_rate;
+        if pressed(egui::Key::Plus) || pressed(egui::Key::Equals) {
+            self.config.tick_rate = rate.faster(MAX_TICKS_PER_SECOND);
+        }
+        if pressed(egui::Key::Minus) {
+            self.config.tick_rate = rate.slower(MAX_TICK_DELAY);
+        }
+        if self.config.tick_rate != rate {
+            commands.push(WorkerCommand::SetTickRate(self.config.tick_rate));
+        }
+
+        // Les deux couches de phéromones s'affichent ou se cachent ensemble
+        if pressed(egui::Key::F) {
+            let shown = self.show_pheromones_food || self.show_pheromones_nest;
+            self.show_pheromones_food = !shown;
+            self.show_pheromones_nest = !shown;
+        }
+        // Revenir au premier snapshot, partie en pause
+        if let Some((first_tick, _)) = view.timeline.filter(|_| pressed(egui::Key::R)) {
+            commands.push(WorkerCommand::Restore(first_tick));
+        }
+    }
+
+    fn show_shortcuts_window(&mut self, ctx: &egui::Context) {
+        egui::Window::new(tr("shortcuts.title"))
+            .id(egui::Id::new("shortcuts_window"))
+            .open(&mut self.show_shortcuts)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("shortcuts_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (key, action) in SHORTCUTS {
+                            ui.monospace(tr(key));
+                            ui.label(tr(action));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    fn leaderboard_path(&self) -> String {
+        self.config
+            .leaderboard_file
+            .clone()
+            .unwrap_or_else(|| DEFAULT_LEADERBOARD_FILE.to_string())
+    }
+
+    // Inscrit la partie terminée aux meilleurs scores de sa carte et ouvre leur fenêtre. Une
+    // partie relue depuis un replay n'est pas inscrite ; une partie rembobinée puis terminée
+    // autrement l'est à nouveau.
+    fn record_scores(&mut self, view: &GameView) {
+        let Some(scores) = view
+            .scores
+            .as_ref()
+            .filter(|_| !view.replaying && !view.replay_finished)
+        else {
+            return;
+        };
+        if self.recorded_scores.as_ref() == Some(scores) {
+            return;
+        }
+        self.recorded_scores = Some(scores.clone());
+        let path = self.leaderboard_path();
+        let rule = ScoreRule::from_config(&self.config);
+        let recorded = Leaderboard::load(&path).and_then(|mut leaderboard| {
+            leaderboard.record(scores, &rule);
+            leaderboard.save(&path).map(|()| leaderboard)
+        });
+        match recorded {
+            Ok(leaderboard) => {
+                self.leaderboard = Some(leaderboard);
+                self.leaderboard_error = None;
+            }
+            Err(e) => self.leaderboard_error = Some(e),
+        }
+        self.show_scores = true;
+    }
+
+    // Fenêtre « Meilleurs scores » de la carte en cours : formule, parties et colonies classées
+    fn show_scores_window(&mut self, ctx: &egui::Context, view: &GameView) {
+        if !self.show_scores {
+            return;
+        }
+        let path = self.leaderboard_path();
+        if self.leaderboard.is_none() && self.leaderboard_error.is_none() {
+            match Leaderboard::load(&path) {
+                Ok(leaderboard) => self.leaderboard = Some(leaderboard),
+                Err(e) => self.leaderboard_error = Some(e),
+            }
+        }
+        let mut open = self.show_scores;
+        let config = &mut self.config;
+        let map = self
+            .leaderboard
+            .as_ref()
+            .and_then(|leaderboard| leaderboard.map(view.map));
+        let error = &self.leaderboard_error;
+        egui::Window::new(tr("scores.title"))
+            .id(egui::Id::new("scores_window"))
+            .open(&mut open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.label(tr("scores.formula"));
+                ui.horizontal(|ui| {
+                    for (weight, key) in [
+                        (&mut config.score_food, "scores.food_weight"),
+                        (&mut config.score_death, "scores.death_weight"),
+                        (&mut config.score_tick, "scores.tick_weight"),
+                    ] {
+                        ui.label(tr(key));
+                        ui.add(egui::DragValue::new(weight).speed(0.01));
+                    }
+                });
+                ui.label(egui::RichText::new(tr("scores.formula_hint")).small());
+                let rule = ScoreRule::from_config(config);
+                ui.separator();
+
+                let current = view.scores.as_ref();
+                if let Some((scores, map)) = current.zip(map) {
+                    ui.label(
+                        egui::RichText::new(trf(
+                            "scores.this_run",
+                            &[
+                                &format!("{:.1}", rule.score(&scores.run)),
+                                &scoring::rank(&map.runs, &scores.run, &rule),
+                                &map.runs.len(),
+                            ],
+                        ))
+                        .strong(),
+                    );
+                }
+                if let Some(error) = error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                let Some(map) = map.filter(|map| !map.runs.is_empty()) else {
+                    ui.label(tr("scores.empty"));
+                    return;
+                };
+                ui.heading(tr("scores.runs"));
+                score_table(
+                    ui,
+                    "scores_runs",
+                    &map.ranked_runs(&rule),
+                    &rule,
+                    current.map_or(&[], |scores| slice::from_ref(&scores.run)),
+                );
+                if !map.colonies.is_empty() {
+                    ui.add_space(5.0);
+                    ui.heading(tr("scores.colonies"));
+                    score_table(
+                        ui,
+                        "scores_colonies",
+                        &map.ranked_colonies(&rule),
+                        &rule,
+                        current.map_or(&[], |scores| &scores.colonies),
+                    );
+                }
+                ui.add_space(5.0);
+                ui.label(egui::RichText::new(trf("scores.file", &[&path])).small());
+            });
+        self.show_scores = open;
+    }
+
+    fn show_game(&mut self, ctx: &egui::Context) {
+        // La partie préparée passe au worker dès l'ouverture du plateau
+        if let Some(manager) = self.ants_game_manager.take() {
+            self.worker = Some(SimulationWorker::spawn(manager, ctx.clone()));
+            self.board_camera.reset();
+            self.selected_ant = None;
+            self.selected_tile = None;
+        }
+        let Some(view) = self.worker.as_mut().map(|worker| worker.view()) else {
+            return;
+        };
+        // Ordres du panneau, transmis au worker une fois l'interface dessinée
+        let mut commands = Vec::new();
+        self.handle_shortcuts(ctx, &view, &mut commands);
+        self.show_shortcuts_window(ctx);
+        self.record_scores(&view);
+        self.show_scores_window(ctx, &view);
+        let previous_selection = self.selected_ant;
+
+        egui::SidePanel::left("controls_panel")
+            .resizable(true)
+            .default_width(280.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.heading(tr("game.title"));
+                    });
+                    ui.separator();
+
+                    // Afficher les contrôles de lancement et pause de la simulation
+                    ui.group(|ui| {
+                        ui.heading(tr("game.state"));
+                        ui.add_space(5.0);
+
+                        if !self.simulation_started {
+                            // Avant le lancement, montrer le bouton de démarrage en vert
+                            ui.label(tr("game.ready"));
+                            if ui
+                                .button(
+                                    egui::RichText::new(tr("game.start"))
+                                        .size(20.0)
+                                        .color(egui::Color32::GREEN),
+                                )
+                                .clicked()
+                            {
+                                self.launch(&view, &mut commands);
+                            }
+                        } else {
+                            // Une fois lancée, afficher seulement les boutons pause/reprise
+                            let btn_text = if view.running {
+                                tr("game.pause")
                             } else {
-                                "REPRENDRE"
+                                tr("game.resume")
                             };
                             if ui
                                 .button(egui::RichText::new(btn_text).size(20.0))
                                 .clicked()
                             {
-                                self.is_running = !self.is_running;
+                                commands.push(if view.running {
+                                    WorkerCommand::Pause
+                                } else {
+                                    WorkerCommand::Start
+                                });
                             }
+                            // Pas à pas pour suivre les mises à jour de Bellman une par une
+                            ui.add_enabled_ui(can_step(&view), |ui| {
+                                ui.horizontal(|ui| {
+                                    if ui.button(tr("game.step_1")).clicked() {
+                                        commands.push(WorkerCommand::Step(1));
+                                    }
+                                    if ui.button(tr("game.step_100")).clicked() {
+                                        commands.push(WorkerCommand::Step(100));
+                                    }
+                                });
+                            });
                             ui.label(
-                                egui::RichText::new("Paramètres verrouillés")
+                                egui::RichText::new(tr("game.locked"))
                                     .color(egui::Color32::RED)
                                     .small(),
                             );
+                            if let Some(reason) = &view.end_reason {
+                                ui.label(trf("game.over", &[&reason.label()]));
+                            } else if view.replay_finished {
+                                ui.label(tr("game.replay_finished"));
+                            } else if view.replaying {
+                                ui.label(tr("game.replaying"));
+                            }
                         }
 
                         ui.add_space(10.0);
-                        // La vitesse d'affichage reste modifiable même en jeu
-                        ui.label("Vitesse (Calculs/Image) :");
-                        ui.add(
-                            egui::Slider::new(&mut self.config.simulation_speed, 1..=100)
-                                .logarithmic(true),
-                        );
+                        // La vitesse reste modifiable même en jeu
+                        ui.label(tr("game.tick_rate"));
+                        let rate = self.config.tick_rate;
+                        ui.horizontal(|ui| {
+                            // Changer d'unité garde à peu près le même rythme
+                            for (choice, key) in [
+                                (TickRate::Unlimited, "game.rate_unlimited"),
+                                (
+                                    TickRate::TicksPerSecond(as_ticks_per_second(rate)),
+                                    "game.rate_tps",
+                                ),
+                                (TickRate::MsPerTick(as_ms_per_tick(rate)), "game.rate_ms"),
+                            ] {
+                                let selected = std::mem::discriminant(&rate)
+                                    == std::mem::discriminant(&choice);
+                                if ui.selectable_label(selected, tr(key)).clicked() && !selected {
+                                    self.config.tick_rate = choice;
+                                }
+                            }
+                        });
+                        match &mut self.config.tick_rate {
+                            TickRate::Unlimited => {}
+                            TickRate::TicksPerSecond(tps) => {
+                                ui.add(
+                                    egui::Slider::new(tps, 1..=MAX_TICKS_PER_SECOND)
+                                        .logarithmic(true)
+                                        .suffix(" ticks/s"),
+                                );
+                            }
+                            TickRate::MsPerTick(ms) => {
+                                ui.add(
+                                    egui::Slider::new(ms, 1..=MAX_TICK_DELAY)
+                                        .logarithmic(true)
+                                        .suffix(" ms"),
+                                );
+                            }
+                        }
+                        if self.config.tick_rate != rate {
+                            commands.push(WorkerCommand::SetTickRate(self.config.tick_rate));
+                        }
+
+                        // Mode turbo : plusieurs ticks à chaque pas pour accélérer l'entraînement
+                        ui.label(tr("game.turbo"));
+                        if ui
+                            .add(
+                                egui::Slider::new(
+                                    &mut self.config.ticks_per_frame,
+                                    1..=MAX_TICKS_PER_FRAME,
+                                )
+                                .logarithmic(true)
+                                .suffix("x"),
+                            )
+                            .changed()
+                        {
+                            commands
+                                .push(WorkerCommand::SetTicksPerFrame(self.config.ticks_per_frame));
+                        }
+
+                        // Figer la politique, même en cours de partie, pour la mesurer telle
+                        // quelle avant de reprendre l'entraînement
+                        let mut frozen = if self.simulation_started {
+                            view.frozen
+                        } else {
+                            self.config.freeze
+                        };
+                        ui.add_enabled_ui(!view.replaying, |ui| {
+                            if ui
+                                .checkbox(&mut frozen, tr("game.freeze"))
+                                .on_hover_text(tr("game.freeze_hint"))
+                                .changed()
+                            {
+                                self.config.freeze = frozen;
+                                if self.simulation_started {
+                                    commands.push(WorkerCommand::SetFrozen(frozen));
+                                }
+                            }
+                        });
+
+                        ui.add_enabled_ui(self.simulation_started, |ui| {
+                            ui.horizontal(|ui| {
+                                if ui.button(tr("game.fast_forward")).clicked() {
+                                    commands
+                                        .push(WorkerCommand::FastForward(self.fast_forward_ticks));
+                                }
+                                ui.add(
+                                    egui::DragValue::new(&mut self.fast_forward_ticks)
+                                        .range(1..=1_000_000)
+                                        .suffix(" ticks"),
+                                );
+                            });
+                        });
+                        if view.fast_forward > 0 {
+                            ui.label(trf("game.fast_forward_left", &[&view.fast_forward]));
+                        }
                     });
 
-                    if let Some(manager) = &mut self.ants_game_manager {
-                        if !manager.history.is_empty() {
-                            ui.add_space(10.0);
-                            ui.separator();
-                            ui.heading("Timeline (Rewind)");
+                    ui.add_space(10.0);
+                    ui.group(|ui| {
+                        ui.heading(tr("stats.title"));
+                        let colonies = &view.state.colonies;
+                        if colonies.len() > 1 {
+                            for colony in colonies {
+                                ui.label(trf(
+                                    "stats.colony_food",
+                                    &[&Colony::label(colony.id), &colony.score],
+                                ));
+                            }
+                            if let Some(winner) = view.winner {
+                                ui.colored_label(
+                                    egui::Color32::GOLD,
+                                    trf("stats.winner", &[&Colony::label(winner)]),
+                                );
+                            }
+                        }
+                        let stats = &view.spawn_stats;
+                        ui.label(trf(
+                            "stats.spawns",
+                            &[
+                                &stats.explorers_spawned,
+                                &stats.pickers_spawned,
+                                &stats.fighters_spawned,
+                            ],
+                        ));
+                        ui.label(trf(
+                            "stats.on_demand",
+                            &[&stats.trail_driven, &stats.stall_driven],
+                        ));
+                        ui.label(trf("stats.starved", &[&view.starved_ants]));
+                        if self.config.stuck_ticks > 0 {
+                            ui.label(trf("stats.stuck", &[&view.stuck_ants]));
+                        }
+                        let combat = &view.predator_stats;
+                        ui.label(trf(
+                            "stats.predators",
+                            &[&view.state.predators.len(), &combat.predators_killed],
+                        ));
+                        ui.label(trf(
+                            "stats.losses",
+                            &[&combat.ants_killed, &combat.fighters_lost],
+                        ));
+                        ui.label(trf("stats.raided", &[&combat.food_raided]));
+                        if ui
+                            .button(tr("scores.open"))
+                            .on_hover_text(tr("scores.open_hint"))
+                            .clicked()
+                        {
+                            self.show_scores = true;
+                        }
+                    });
+
+                    // Les snapshots peuvent être espacés et les plus anciens oubliés
+                    if let Some((min_tick, max_tick)) = view.timeline {
+                        ui.add_space(10.0);
+                        ui.separator();
+                        ui.heading(tr("timeline.title"));
 
-                            let max_tick = manager.history.len() - 1;
-                            let mut current = manager.current_tick_index;
+                        let mut current = view.state.tick;
+                        let step = self.config.snapshot_interval.max(1) as usize;
 
-                            ui.label(format!("Tick: {} / {}", current, max_tick));
+                        ui.label(trf("timeline.tick", &[&current, &max_tick]));
 
-                            let slider =
-                                ui.add(egui::Slider::new(&mut current, 0..=max_tick).text("Temps"));
+                        let slider = ui.add(
+                            egui::Slider::new(&mut current, min_tick..=max_tick)
+                                .text(tr("timeline.time")),
+                        );
+
+                        // Si on bouge le slider, le worker restaure le snapshot et se met en pause
+                        if slider.changed() {
+                            commands.push(WorkerCommand::Restore(current));
+                        }
 
-                            // Si on bouge le slider, on met à jour et on pause
-                            if slider.changed() {
-                                self.is_running = false;
-                                manager.restore_snapshot(current);
+                        ui.horizontal(|ui| {
+                            if ui.button(format!("<< -{}", step)).clicked() && current > min_tick {
+                                commands.push(WorkerCommand::Restore(current.saturating_sub(step)));
+                            }
+                            if ui.button(format!("+{} >>", step)).clicked() && current < max_tick {
+                                commands.push(WorkerCommand::Restore(current + step));
                             }
+                        });
 
-                            ui.horizontal(|ui| {
-                                if ui.button("<< -1").clicked() && current > 0 {
-                                    self.is_running = false;
-                                    manager.restore_snapshot(current - 1);
-                                }
-                                if ui.button("+1 >>").clicked() && current < max_tick {
-                                    self.is_running = false;
-                                    manager.restore_snapshot(current + 1);
-                                }
-                            });
+                        // Relire la timeline comme un film, sans toucher à la simulation
+                        let playing = view.history_playback.is_some();
+                        let mut changed = false;
+                        ui.horizontal(|ui| {
+                            let label = if playing {
+                                tr("timeline.stop")
+                            } else {
+                                tr("timeline.play")
+                            };
+                            if ui.button(label).clicked() {
+                                commands.push(if playing {
+                                    WorkerCommand::StopHistory
+                                } else {
+                                    WorkerCommand::PlayHistory(self.history_playback)
+                                });
+                            }
+                            changed |= ui
+                                .checkbox(&mut self.history_playback.looping, tr("timeline.loop"))
+                                .changed();
+                        });
+                        changed |= ui
+                            .add(
+                                egui::Slider::new(
+                                    &mut self.history_playback.snapshots_per_second,
+                                    1..=120,
+                                )
+                                .logarithmic(true)
+                                .text(tr("timeline.speed")),
+                            )
+                            .changed();
+                        if changed && playing {
+                            commands.push(WorkerCommand::PlayHistory(self.history_playback));
                         }
                     }
 
@@ -451,180 +1862,1951 @@ impl Interface {
                     // Déterminer si les sliders peuvent être activés
                     let params_enabled = !self.simulation_started;
 
-                    // Afficher les paramètres d'apprentissage du Q-Learning
-                    ui.collapsing("Cerveau (Q-Learning)", |ui| {
+                    // Afficher les paramètres d'apprentissage du Q-Learning une fois la partie lancée
+                    let live_params = self
+                        .simulation_started
+                        .then_some((view.alpha, view.epsilon));
+                    ui.collapsing(tr("presets.title"), |ui| {
+                        self.show_presets(ui, params_enabled, &mut commands);
+                    });
+                    #[cfg(not(target_arch = "wasm32"))]
+                    ui.collapsing(tr("sweep.title"), |ui| {
+                        self.sweep_panel.show(
+                            ui,
+                            &view.state.grid,
+                            &mut self.config,
+                            params_enabled,
+                        );
+                    });
+                    ui.collapsing(tr("policy.title"), |ui| {
+                        ui.add_enabled_ui(params_enabled, |ui| {
+                            egui::ComboBox::from_id_salt("policy")
+                                .selected_text(self.config.policy.label())
+                                .show_ui(ui, |ui| {
+                                    for policy in PolicyKind::all() {
+                                        ui.selectable_value(
+                                            &mut self.config.policy,
+                                            policy,
+                                            policy.label(),
+                                        );
+                                    }
+                                });
+                            if self.config.policy == PolicyKind::ClassicAco {
+                                ui.label(tr("policy.aco_hint"));
+                                ui.add(
+                                    egui::Slider::new(&mut self.config.aco_alpha, 0.0..=5.0)
+                                        .text("α"),
+                                );
+                                ui.add(
+                                    egui::Slider::new(&mut self.config.aco_beta, 0.0..=5.0)
+                                        .text("β"),
+                                );
+                                ui.add(
+                                    egui::Slider::new(&mut self.config.aco_deposit, 0.1..=100.0)
+                                        .logarithmic(true)
+                                        .text(tr("policy.aco_deposit")),
+                                );
+                            }
+                        });
+                    });
+
+                    ui.add_space(10.0);
+
+                    ui.collapsing(tr("brain.title"), |ui| {
+                        if let Some((alpha, epsilon)) = live_params {
+                            ui.label(trf("brain.alpha_now", &[&format!("{:.4}", alpha)]));
+                            ui.label(trf("brain.epsilon_now", &[&format!("{:.4}", epsilon)]));
+                            ui.separator();
+                        }
                         ui.add_enabled_ui(params_enabled, |ui| {
-                            ui.label("Alpha (Apprentissage) :");
+                            ui.label(tr("brain.alpha"));
                             ui.add(egui::Slider::new(&mut self.config.alpha, 0.0..=1.0));
 
                             ui.separator();
-                            ui.label("Gamma (Vision) :");
+                            ui.label(tr("brain.gamma"));
                             ui.add(egui::Slider::new(&mut self.config.gamma, 0.0..=1.0));
 
                             ui.separator();
-                            ui.label("Epsilon (Exploration) :");
+                            // Traces et retours à n pas s'excluent : chacun n'est réglable
+                            // que quand l'autre est au repos
+                            ui.label(tr("brain.lambda"));
+                            ui.add_enabled(
+                                self.config.n_step == 1
+                                    && self.config.q_backend == QBackendKind::Tabular,
+                                egui::Slider::new(&mut self.config.lambda, 0.0..=1.0),
+                            )
+                            .on_hover_text(tr("brain.lambda_hint"));
+                            ui.label(tr("brain.n_step"));
+                            ui.add_enabled(
+                                self.config.lambda == 0.0,
+                                egui::Slider::new(&mut self.config.n_step, 1..=20),
+                            )
+                            .on_hover_text(tr("brain.n_step_hint"));
+
+                            ui.separator();
+                            ui.label(tr("brain.epsilon"));
                             ui.add(egui::Slider::new(&mut self.config.epsilon, 0.0..=1.0));
+
+                            ui.separator();
+                            ui.label(tr("brain.epsilon_decay"));
+                            Self::show_decay_controls(
+                                ui,
+                                "epsilon_decay",
+                                &mut self.config.epsilon_decay,
+                            );
+                            ui.label(tr("brain.alpha_decay"));
+                            Self::show_decay_controls(
+                                ui,
+                                "alpha_decay",
+                                &mut self.config.alpha_decay,
+                            );
+
+                            ui.separator();
+                            ui.label(tr("brain.action_selection"));
+                            egui::ComboBox::from_id_salt("exploration_policy")
+                                .selected_text(self.config.exploration_policy.label())
+                                .show_ui(ui, |ui| {
+                                    for policy in ExplorationPolicy::all() {
+                                        ui.selectable_value(
+                                            &mut self.config.exploration_policy,
+                                            policy,
+                                            policy.label(),
+                                        );
+                                    }
+                                });
+                            match self.config.exploration_policy {
+                                ExplorationPolicy::EpsilonGreedy => {
+                                    ui.checkbox(
+                                        &mut self.config.unmasked_exploration,
+                                        tr("brain.unmasked_exploration"),
+                                    );
+                                }
+                                ExplorationPolicy::Softmax => {
+                                    ui.add(
+                                        egui::Slider::new(
+                                            &mut self.config.softmax_temperature,
+                                            0.1..=1000.0,
+                                        )
+                                        .logarithmic(true)
+                                        .text(tr("brain.temperature")),
+                                    );
+                                }
+                                ExplorationPolicy::Ucb => {
+                                    ui.add(
+                                        egui::Slider::new(&mut self.config.ucb_c, 0.0..=1000.0)
+                                            .text(tr("brain.ucb_bonus")),
+                                    );
+                                }
+                            }
+
+                            ui.separator();
+                            ui.label(tr("brain.algorithm"));
+                            egui::ComboBox::from_id_salt("learning_algorithm")
+                                .selected_text(self.config.learning_algorithm.label())
+                                .show_ui(ui, |ui| {
+                                    // La seconde table du Double Q-Learning est propre à la
+                                    // Q-table
+                                    let linear = self.config.q_backend == QBackendKind::Linear;
+                                    for algorithm in LearningAlgorithm::all().filter(|&a| {
+                                        !linear || a != LearningAlgorithm::DoubleQLearning
+                                    }) {
+                                        ui.selectable_value(
+                                            &mut self.config.learning_algorithm,
+                                            algorithm,
+                                            algorithm.label(),
+                                        );
+                                    }
+                                });
+                            ui.label(tr("brain.q_backend"));
+                            ui.add_enabled_ui(
+                                self.config.lambda == 0.0
+                                    && self.config.learning_algorithm
+                                        != LearningAlgorithm::DoubleQLearning,
+                                |ui| {
+                                    egui::ComboBox::from_id_salt("q_backend")
+                                        .selected_text(self.config.q_backend.label())
+                                        .show_ui(ui, |ui| {
+                                            for kind in QBackendKind::all() {
+                                                ui.selectable_value(
+                                                    &mut self.config.q_backend,
+                                                    kind,
+                                                    kind.label(),
+                                                );
+                                            }
+                                        });
+                                },
+                            )
+                            .response
+                            .on_hover_text(tr("brain.q_backend_hint"));
+                            if self.config.q_backend == QBackendKind::Linear {
+                                ui.add(
+                                    egui::Slider::new(&mut self.config.linear_tile, 1..=32)
+                                        .text(tr("brain.linear_tile")),
+                                );
+                            }
+                            ui.checkbox(&mut self.config.masked_max_q, tr("brain.masked_max_q"))
+                                .on_hover_text(tr("brain.masked_max_q_hint"));
+                            ui.checkbox(&mut self.config.per_type_q_tables, tr("brain.per_type"))
+                                .on_hover_text(tr("brain.per_type_hint"));
+                            ui.checkbox(&mut self.config.near_food_state, tr("brain.near_food"))
+                                .on_hover_text(tr("brain.near_food_hint"));
+
+                            ui.separator();
+                            ui.label(tr("brain.experience_replay"));
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    egui::DragValue::new(&mut self.config.experience_buffer)
+                                        .range(0..=1_000_000)
+                                        .prefix(tr("brain.replay_memory")),
+                                )
+                                .on_hover_text(tr("brain.replay_memory_hint"));
+                                ui.add_enabled(
+                                    self.config.experience_buffer > 0,
+                                    egui::DragValue::new(&mut self.config.experience_batch)
+                                        .range(1..=1024)
+                                        .prefix(tr("brain.replay_batch")),
+                                );
+                            });
+
+                            ui.separator();
+                            ui.label(tr("brain.q_values"));
+                            ui.horizontal(|ui| {
+                                ui.add(
+                                    egui::DragValue::new(&mut self.config.q_initial)
+                                        .speed(1.0)
+                                        .prefix(tr("brain.q_initial")),
+                                )
+                                .on_hover_text(tr("brain.q_initial_hint"));
+                                ui.add(
+                                    egui::DragValue::new(&mut self.config.q_clamp)
+                                        .speed(10.0)
+                                        .range(0.0..=100_000.0)
+                                        .prefix(tr("brain.q_clamp")),
+                                )
+                                .on_hover_text(tr("brain.q_clamp_hint"));
+                            });
+                            ui.add(
+                                egui::Slider::new(&mut self.config.q_display_scale, 1.0..=5000.0)
+                                    .logarithmic(true)
+                                    .text(tr("brain.q_scale")),
+                            );
                         });
                     });
 
                     ui.add_space(10.0);
 
+                    ui.collapsing(tr("controller.title"), |ui| {
+                        self.show_controller(ui, &view, params_enabled);
+                    });
+
+                    ui.add_space(10.0);
+
                     // Afficher les sliders pour configurer les récompenses
-                    ui.collapsing("Récompenses", |ui| {
+                    ui.collapsing(tr("rewards.title"), |ui| {
                         ui.add_enabled_ui(params_enabled, |ui| {
-                            ui.label("Nourriture (+):");
+                            ui.label(tr("rewards.food"));
                             ui.add(egui::Slider::new(
                                 &mut self.config.reward_food,
                                 100.0..=5000.0,
                             ));
 
-                            ui.label("Retour Nid (+):");
-                            ui.add(egui::Slider::new(
-                                &mut self.config.reward_nest,
-                                100.0..=5000.0,
-                            ));
+                            ui.label(tr("rewards.nest"));
+                            ui.add(egui::Slider::new(
+                                &mut self.config.reward_nest,
+                                100.0..=5000.0,
+                            ));
+
+                            ui.checkbox(
+                                &mut self.config.reward_load_scaling,
+                                tr("rewards.load_scaling"),
+                            )
+                            .on_hover_text(tr("rewards.load_scaling_hint"));
+
+                            ui.label(tr("rewards.per_unit"));
+                            ui.add(egui::Slider::new(
+                                &mut self.config.reward_per_unit,
+                                0.0..=50.0,
+                            ));
+
+                            ui.separator();
+
+                            ui.label(tr("rewards.step"));
+                            ui.add(egui::Slider::new(
+                                &mut self.config.reward_default,
+                                -5.0..=0.0,
+                            ));
+
+                            ui.label(tr("rewards.death"));
+                            ui.add(egui::Slider::new(
+                                &mut self.config.reward_death,
+                                -500.0..=-10.0,
+                            ));
+                        });
+                    });
+
+                    ui.add_space(10.0);
+
+                    // Afficher les paramètres de la colonie
+                    ui.collapsing(tr("colony.title"), |ui| {
+                        ui.add_enabled_ui(params_enabled, |ui| {
+                            ui.label(tr("colony.max_per_cell"));
+                            let mut unlimited = self.config.max_ants_per_cell.is_none();
+                            if ui
+                                .checkbox(&mut unlimited, tr("colony.unlimited"))
+                                .changed()
+                            {
+                                self.config.max_ants_per_cell =
+                                    if unlimited { None } else { Some(10) };
+                            }
+                            if let Some(max) = &mut self.config.max_ants_per_cell {
+                                ui.add(egui::Slider::new(max, 1..=50));
+                            }
+                            ui.label(tr("colony.spawns"));
+                            ui.add(
+                                egui::Slider::new(&mut self.config.spawn_rate_per_tick, 1..=20)
+                                    .text(tr("colony.spawn_rate")),
+                            );
+                            ui.add(
+                                egui::Slider::new(&mut self.config.spawn_cooldown, 0..=20)
+                                    .text(tr("colony.spawn_cooldown")),
+                            );
+
+                            ui.separator();
+                            ui.label(tr("colony.min_explorers"));
+                            ui.add(egui::Slider::new(
+                                &mut self.config.min_explorers_active,
+                                0..=50,
+                            ));
+                            ui.label(tr("colony.min_pickers"));
+                            ui.add(egui::Slider::new(
+                                &mut self.config.min_pickers_active,
+                                0..=50,
+                            ));
+
+                            ui.separator();
+                            ui.label(tr("colony.upkeep"));
+                            ui.add(egui::Slider::new(
+                                &mut self.config.food_upkeep_per_ant,
+                                0.0..=1.0,
+                            ));
+                            ui.label(tr("colony.upkeep_interval"));
+                            ui.add(egui::Slider::new(&mut self.config.upkeep_interval, 1..=500));
+                            ui.label(tr("colony.starvation"));
+                            ui.add(egui::Slider::new(
+                                &mut self.config.starvation_ticks,
+                                1..=500,
+                            ));
+
+                            ui.separator();
+                            ui.label(tr("colony.energy"));
+                            ui.add(egui::Slider::new(&mut self.config.ant_energy, 0.0..=1000.0))
+                                .on_hover_text(tr("colony.energy_hint"));
+                            ui.add_enabled_ui(self.config.ant_energy > 0.0, |ui| {
+                                ui.add(
+                                    egui::Slider::new(&mut self.config.energy_drain, 0.0..=10.0)
+                                        .text(tr("colony.energy_drain")),
+                                );
+                                ui.add(
+                                    egui::Slider::new(
+                                        &mut self.config.energy_per_food,
+                                        1.0..=500.0,
+                                    )
+                                    .text(tr("colony.energy_per_food")),
+                                );
+                                ui.add(
+                                    egui::Slider::new(&mut self.config.hunger_threshold, 0.0..=1.0)
+                                        .text(tr("colony.hunger")),
+                                );
+                            });
+
+                            ui.separator();
+                            ui.label(tr("colony.nest_capacity"));
+                            ui.add(egui::Slider::new(&mut self.config.nest_capacity, 1..=500));
+                            let mut own_caps = self.config.nest_type_caps.is_some();
+                            if ui
+                                .checkbox(&mut own_caps, tr("colony.type_caps"))
+                                .on_hover_text(tr("colony.type_caps_hint"))
+                                .changed()
+                            {
+                                self.config.nest_type_caps = own_caps.then_some([10; 3]);
+                            }
+                            if let Some(caps) = &mut self.config.nest_type_caps {
+                                ui.horizontal(|ui| {
+                                    for ant_type in AntsType::all() {
+                                        ui.add(
+                                            egui::DragValue::new(&mut caps[ant_type.index()])
+                                                .range(0..=1000)
+                                                .prefix(format!("{} ", i18n::ant_type(ant_type))),
+                                        );
+                                    }
+                                });
+                            }
+
+                            ui.separator();
+                            ui.label(tr("colony.birth_cost"));
+                            ui.add(egui::Slider::new(&mut self.config.birth_cost, 0..=200))
+                                .on_hover_text(tr("colony.birth_hint"));
+                            ui.add_enabled_ui(self.config.birth_cost > 0, |ui| {
+                                ui.add(
+                                    egui::Slider::new(&mut self.config.birth_interval, 1..=1000)
+                                        .text(tr("colony.birth_interval")),
+                                );
+                                ui.horizontal(|ui| {
+                                    ui.label(tr("common.ratios"));
+                                    for ant_type in AntsType::all() {
+                                        ui.add(
+                                            egui::DragValue::new(
+                                                &mut self.config.birth_ratio[ant_type.index()],
+                                            )
+                                            .range(0..=10)
+                                            .prefix(format!("{} ", i18n::ant_type(ant_type))),
+                                        );
+                                    }
+                                });
+                            });
+                            ui.label(tr("colony.lifespan"))
+                                .on_hover_text(tr("colony.lifespan_hint"));
+                            ui.horizontal(|ui| {
+                                for ant_type in AntsType::all() {
+                                    ui.add(
+                                        egui::DragValue::new(
+                                            &mut self.config.lifespan[ant_type.index()],
+                                        )
+                                        .range(0..=100_000)
+                                        .prefix(format!("{} ", i18n::ant_type(ant_type))),
+                                    );
+                                }
+                            });
+
+                            ui.separator();
+                            ui.label(tr("colony.regrowth"));
+                            ui.add(
+                                egui::Slider::new(&mut self.config.food_regrowth_amount, 0..=100)
+                                    .text(tr("common.units")),
+                            );
+                            ui.add_enabled(
+                                self.config.food_regrowth_amount > 0,
+                                egui::Slider::new(
+                                    &mut self.config.food_regrowth_interval,
+                                    1..=1000,
+                                )
+                                .text(tr("colony.regrowth_interval")),
+                            );
+                            ui.add_enabled(
+                                self.config.food_regrowth_amount > 0,
+                                egui::Slider::new(&mut self.config.food_regrowth_cap, 1..=10_000)
+                                    .text(tr("colony.regrowth_cap")),
+                            );
+
+                            ui.separator();
+                            ui.label(tr("colony.corpses"));
+                            ui.add(
+                                egui::Slider::new(&mut self.config.corpse_food, 0..=50)
+                                    .text(tr("colony.corpse_food")),
+                            )
+                            .on_hover_text(tr("colony.corpse_hint"));
+                            ui.add_enabled(
+                                self.config.corpse_food > 0,
+                                egui::Slider::new(&mut self.config.corpse_decay, 1..=500)
+                                    .text(tr("colony.corpse_decay")),
+                            );
+
+                            ui.separator();
+                            let mut has_target = self.config.target_food.is_some();
+                            if ui.checkbox(&mut has_target, tr("colony.target")).changed() {
+                                self.config.target_food = has_target.then_some(1000);
+                            }
+                            if let Some(target) = &mut self.config.target_food {
+                                ui.add(egui::Slider::new(target, 1..=100_000).logarithmic(true));
+                            }
+
+                            ui.separator();
+                            ui.label(tr("colony.spawn_policy"));
+                            egui::ComboBox::from_id_salt("spawn_policy")
+                                .selected_text(self.config.spawn_policy.label())
+                                .show_ui(ui, |ui| {
+                                    for policy in SpawnPolicyKind::all() {
+                                        ui.selectable_value(
+                                            &mut self.config.spawn_policy,
+                                            policy,
+                                            policy.label(),
+                                        );
+                                    }
+                                });
+                            if self.config.spawn_policy == SpawnPolicyKind::Proportional {
+                                ui.horizontal(|ui| {
+                                    ui.label(tr("common.ratios"));
+                                    for ant_type in AntsType::all() {
+                                        ui.add(
+                                            egui::DragValue::new(
+                                                &mut self.config.spawn_ratio[ant_type.index()],
+                                            )
+                                            .range(0..=10)
+                                            .prefix(format!("{} ", i18n::ant_type(ant_type))),
+                                        );
+                                    }
+                                });
+                            }
+                            if self.config.spawn_policy == SpawnPolicyKind::ExplorerBurst {
+                                ui.label(tr("colony.burst_ticks"));
+                                ui.add(egui::Slider::new(
+                                    &mut self.config.spawn_burst_ticks,
+                                    0..=5000,
+                                ));
+                            }
+                            if self.config.spawn_policy == SpawnPolicyKind::DemandDriven {
+                                ui.label(tr("colony.trail_threshold"));
+                                ui.add(egui::Slider::new(
+                                    &mut self.config.spawn_trail_threshold,
+                                    1.0..=1000.0,
+                                ));
+                                ui.label(tr("colony.stall_ticks"));
+                                ui.add(egui::Slider::new(
+                                    &mut self.config.spawn_stall_ticks,
+                                    10..=2000,
+                                ));
+                            }
+                        });
+                    });
+
+                    ui.add_space(10.0);
+
+                    // Afficher les paramètres des prédateurs
+                    ui.collapsing(tr("predators.title"), |ui| {
+                        ui.add_enabled_ui(params_enabled, |ui| {
+                            ui.label(tr("predators.max"));
+                            ui.add(egui::Slider::new(&mut self.config.max_predators, 0..=20));
+                            ui.label(tr("predators.interval"));
+                            ui.add(egui::Slider::new(
+                                &mut self.config.predator_spawn_interval,
+                                1..=2000,
+                            ));
+                            ui.label(tr("profile.speed"));
+                            ui.add(egui::Slider::new(&mut self.config.predator_speed, 0..=20));
+                            ui.label(tr("predators.fighter_win"));
+                            ui.add(egui::Slider::new(
+                                &mut self.config.fighter_win_chance,
+                                0.0..=1.0,
+                            ));
+                            for (value, label, range) in [
+                                (
+                                    &mut self.config.fighter_health,
+                                    "predators.fighter_health",
+                                    1..=100,
+                                ),
+                                (
+                                    &mut self.config.fighter_attack,
+                                    "predators.fighter_attack",
+                                    0..=50,
+                                ),
+                                (
+                                    &mut self.config.predator_health,
+                                    "predators.health",
+                                    1..=100,
+                                ),
+                                (&mut self.config.predator_attack, "predators.attack", 0..=50),
+                                (&mut self.config.predator_raid, "predators.raid", 0..=100),
+                                (
+                                    &mut self.config.fighter_patrol_range,
+                                    "predators.patrol_range",
+                                    0..=50,
+                                ),
+                            ] {
+                                ui.label(tr(label));
+                                ui.add(egui::Slider::new(value, range));
+                            }
+                        });
+                    });
+
+                    ui.add_space(10.0);
+
+                    // Phéromone d'alarme : poids 0 = pas de carte de peur
+                    ui.collapsing(tr("fear.title"), |ui| {
+                        ui.add_enabled_ui(params_enabled, |ui| {
+                            ui.label(tr("fear.weight"));
+                            ui.add(egui::Slider::new(&mut self.config.fear_weight, 0.0..=10.0));
+                            ui.add_enabled_ui(self.config.uses_fear(), |ui| {
+                                ui.label(tr("fear.deposit"));
+                                ui.add(egui::Slider::new(
+                                    &mut self.config.fear_deposit,
+                                    0.0..=1000.0,
+                                ));
+                                ui.label(tr("fear.evaporation"));
+                                ui.add(egui::Slider::new(
+                                    &mut self.config.fear_evaporation,
+                                    0.0..=1.0,
+                                ));
+                            });
+                        });
+                    });
+
+                    ui.add_space(10.0);
+
+                    // Détection des fourmis bloquées : 0 tick = désactivée
+                    ui.collapsing(tr("stuck.title"), |ui| {
+                        ui.add_enabled_ui(params_enabled, |ui| {
+                            ui.label(tr("stuck.ticks"));
+                            ui.add(egui::Slider::new(&mut self.config.stuck_ticks, 0..=500));
+                            ui.add_enabled_ui(self.config.stuck_ticks > 0, |ui| {
+                                ui.label(tr("stuck.radius"));
+                                ui.add(egui::Slider::new(&mut self.config.stuck_radius, 0..=10));
+                                ui.label(tr("stuck.recovery"));
+                                egui::ComboBox::from_id_salt("stuck_recovery")
+                                    .selected_text(self.config.stuck_recovery.label())
+                                    .show_ui(ui, |ui| {
+                                        for recovery in StuckRecovery::all() {
+                                            ui.selectable_value(
+                                                &mut self.config.stuck_recovery,
+                                                recovery,
+                                                recovery.label(),
+                                            );
+                                        }
+                                    });
+                                if self.config.stuck_recovery != StuckRecovery::Recall {
+                                    ui.label(tr("stuck.recovery_ticks"));
+                                    ui.add(egui::Slider::new(
+                                        &mut self.config.recovery_ticks,
+                                        1..=200,
+                                    ));
+                                }
+                                if self.config.stuck_recovery == StuckRecovery::Explore {
+                                    ui.label(tr("stuck.epsilon"));
+                                    ui.add(egui::Slider::new(
+                                        &mut self.config.stuck_epsilon,
+                                        0.0..=1.0,
+                                    ));
+                                }
+                            });
+                        });
+                    });
+
+                    ui.add_space(10.0);
+
+                    // Cycle jour/nuit : un environnement qui change pour étudier l'adaptation
+                    ui.collapsing(tr("night.title"), |ui| {
+                        if self.simulation_started && view.night {
+                            ui.label(tr("night.now"));
+                        }
+                        ui.add_enabled_ui(params_enabled, |ui| {
+                            ui.label(tr("night.period"));
+                            ui.add(egui::Slider::new(
+                                &mut self.config.day_night_period,
+                                0..=5000,
+                            ));
+                            let enabled = self.config.day_night_period > 0;
+                            ui.add_enabled_ui(enabled, |ui| {
+                                ui.label(tr("night.evaporation"));
+                                ui.add(egui::Slider::new(
+                                    &mut self.config.night_evaporation_factor,
+                                    0.0..=2.0,
+                                ));
+                                ui.label(tr("night.epsilon"));
+                                ui.add(egui::Slider::new(
+                                    &mut self.config.night_epsilon_boost,
+                                    0.0..=1.0,
+                                ));
+                                ui.label(tr("night.slowdown"));
+                                ui.add(egui::Slider::new(&mut self.config.night_slowdown, 1..=10));
+                            });
+                        });
+                    });
+
+                    ui.add_space(10.0);
+                    self.show_comparison_controls(ui, &view);
+
+                    ui.add_space(10.0);
+
+                    // Afficher les options de visualisation
+                    ui.collapsing(tr("view.title"), |ui| {
+                        ui.checkbox(&mut self.show_pheromones_food, tr("view.food_trails"));
+                        ui.checkbox(&mut self.show_pheromones_nest, tr("view.nest_trails"));
+                        if self.config.uses_fear() {
+                            ui.checkbox(&mut self.show_fear, tr("view.fear"))
+                                .on_hover_text(tr("view.fear_hint"));
+                        }
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut self.heat_auto, tr("view.heat_auto"))
+                                .on_hover_text(tr("view.heat_auto_hint"));
+                            ui.checkbox(&mut self.heat_log, tr("view.heat_log"));
+                        });
+                        let [food_range, nest_range] = if self.heat_auto {
+                            self.heat_ranges[0]
+                        } else {
+                            [HeatScale::fixed(self.config.q_display_scale); 2]
+                        };
+                        let palette = theme::palette();
+                        if self.show_pheromones_food {
+                            Self::show_heat_legend(
+                                ui,
+                                tr("view.heat_food"),
+                                food_range,
+                                self.heat_log,
+                                palette.food_trail,
+                            );
+                        }
+                        if self.show_pheromones_nest {
+                            Self::show_heat_legend(
+                                ui,
+                                tr("view.heat_nest"),
+                                nest_range,
+                                self.heat_log,
+                                palette.nest_trail,
+                            );
+                        }
+                        ui.checkbox(&mut self.show_traffic, tr("view.traffic"))
+                            .on_hover_text(tr("view.traffic_hint"));
+                        if self.show_traffic {
+                            Self::show_heat_legend(
+                                ui,
+                                tr("view.heat_traffic"),
+                                HeatScale::fixed(view.traffic.max() as f32),
+                                self.heat_log,
+                                palette.traffic,
+                            );
+                        }
+                        ui.checkbox(&mut self.show_ant_paths, tr("view.ant_paths"));
+                        ui.checkbox(&mut self.show_minimap, tr("view.minimap"))
+                            .on_hover_text(tr("view.minimap_hint"));
+                        egui::ComboBox::from_label(tr("view.policy"))
+                            .selected_text(self.policy_overlay.label())
+                            .show_ui(ui, |ui| {
+                                for overlay in [
+                                    PolicyOverlay::Hidden,
+                                    PolicyOverlay::Food,
+                                    PolicyOverlay::Nest,
+                                ] {
+                                    ui.selectable_value(
+                                        &mut self.policy_overlay,
+                                        overlay,
+                                        overlay.label(),
+                                    );
+                                }
+                            });
+                        // Molette pour zoomer, glisser pour se déplacer sur le plateau
+                        if ui
+                            .add_enabled(
+                                !self.board_camera.is_reset(),
+                                egui::Button::new(tr("common.fit_view")),
+                            )
+                            .clicked()
+                        {
+                            self.board_camera.reset();
+                        }
+                        let colony_count = view.state.colonies.len();
+                        if colony_count > 1 {
+                            egui::ComboBox::from_label(tr("view.shown_colony"))
+                                .selected_text(Colony::label(self.shown_colony).to_string())
+                                .show_ui(ui, |ui| {
+                                    for id in 0..colony_count {
+                                        ui.selectable_value(
+                                            &mut self.shown_colony,
+                                            id,
+                                            Colony::label(id).to_string(),
+                                        );
+                                    }
+                                });
+                        }
+                        if view.state.colonies.iter().any(Colony::has_per_type_maps) {
+                            egui::ComboBox::from_label(tr("view.shown_maps"))
+                                .selected_text(i18n::ant_type(self.shown_caste))
+                                .show_ui(ui, |ui| {
+                                    for ant_type in AntsType::all() {
+                                        ui.selectable_value(
+                                            &mut self.shown_caste,
+                                            ant_type,
+                                            i18n::ant_type(ant_type),
+                                        );
+                                    }
+                                });
+                        }
+                        // Q-tables affichées en CSV et en SVG dans le dossier courant
+                        if ui.button(tr("view.export_brain")).clicked() {
+                            let id = self.shown_colony.min(view.state.colonies.len() - 1);
+                            commands.push(WorkerCommand::ExportTables {
+                                colony: id,
+                                ant_type: self.shown_caste,
+                                prefix: format!("cerveau_{}", Colony::label(id)),
+                            });
+                        }
+                    });
+
+                    ui.add_space(10.0);
+
+                    // Modifier la carte en cours de partie pour tester l'adaptation de la politique
+                    ui.collapsing(tr("god.title"), |ui| {
+                        ui.add_enabled_ui(!view.replaying, |ui| {
+                            ui.checkbox(&mut self.god_mode, tr("god.edit"));
+                        });
+                        if view.replaying {
+                            ui.label(egui::RichText::new(tr("common.unavailable_replay")).small());
+                        }
+                    });
+
+                    ui.add_space(20.0);
+                    ui.separator();
+
+                    if ui.button(tr("game.quit")).clicked() {
+                        self.state = AppState::DimensionInput;
+                        self.tutorial = None;
+                        // Détruire la poignée arrête le thread de simulation
+                        self.worker = None;
+                        self.simulation_started = false;
+                        self.selected_ant = None;
+                        self.selected_tile = None;
+                    }
+                });
+            });
+
+        let tile_inspected = self.god_mode && self.selected_tile.is_some();
+        if tile_inspected || self.selected_ant.is_some() {
+            egui::SidePanel::right("inspector")
+                .resizable(true)
+                .default_width(240.0)
+                .show(ctx, |ui| {
+                    if tile_inspected {
+                        self.show_tile_inspector(ui, &view, &mut commands);
+                    } else {
+                        self.show_ant_inspector(ui, &view);
+                    }
+                });
+        }
+
+        egui::TopBottomPanel::bottom("stats_plots")
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.collapsing(tr("plots.title"), |ui| {
+                    Self::show_stats_plots(ui, &view, &mut commands)
+                });
+                ui.collapsing(tr("type_stats.title"), |ui| {
+                    Self::show_type_stats(ui, &view)
+                });
+                ui.collapsing(tr("log.title"), |ui| self.show_event_log(ui));
+            });
+
+        if self.god_mode && !view.replaying {
+            egui::TopBottomPanel::top("god_toolbar").show(ctx, |ui| self.show_god_toolbar(ui));
+        }
+
+        // Zone de dessin
+        egui::CentralPanel::default().show(ctx, |ui| match &view.rival {
+            // Les deux plateaux partagent le zoom et le déplacement pour rester comparables
+            Some(rival) => {
+                ui.columns(2, |columns| {
+                    columns[0].label(egui::RichText::new("Simulation 1").strong());
+                    self.draw_board(&mut columns[0], &view, true, &mut commands);
+                    columns[1].label(egui::RichText::new("Simulation 2").strong());
+                    self.draw_board(&mut columns[1], rival, false, &mut commands);
+                });
+            }
+            None => self.draw_board(ui, &view, true, &mut commands),
+        });
+
+        // Le worker enregistre le trajet de la fourmi inspectée à chaque tick
+        if self.selected_ant != previous_selection {
+            commands.push(WorkerCommand::Track(self.selected_ant));
+        }
+        if let Some(worker) = &mut self.worker {
+            for command in commands {
+                worker.send(command);
+            }
+        }
+    }
+
+    // Outil appliqué par un clic sur le plateau en mode Dieu, et quantité de nourriture posée
+    fn show_god_toolbar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("god.tool"));
+            for tool in GodTool::ALL {
+                ui.selectable_value(&mut self.god_tool, tool, tool.label());
+            }
+            if self.god_tool == GodTool::Food {
+                ui.separator();
+                ui.label(tr("god.food_amount"));
+                ui.add(egui::DragValue::new(&mut self.god_food_amount).range(1..=10_000));
+            }
+        });
+    }
+
+    // Derniers événements de la simulation, filtrés par type, les plus récents en bas
+    fn show_event_log(&mut self, ui: &mut egui::Ui) {
+        let Some(log) = &self.event_log else {
+            ui.label(tr("log.inactive"));
+            return;
+        };
+        ui.horizontal_wrapped(|ui| {
+            for kind in LogKind::all() {
+                let mut shown = self.shown_log_kinds.contains(&kind);
+                if ui.checkbox(&mut shown, kind.label()).changed() {
+                    if shown {
+                        self.shown_log_kinds.insert(kind);
+                    } else {
+                        self.shown_log_kinds.remove(&kind);
+                    }
+                }
+            }
+            if ui.button(tr("log.clear")).clicked() {
+                log.clear();
+            }
+        });
+        ui.label(egui::RichText::new(trf("log.level", &[&i18n::log_level(log.level())])).small());
+
+        let entries: Vec<String> = log
+            .entries()
+            .iter()
+            .filter(|entry| self.shown_log_kinds.contains(&entry.kind))
+            .map(|entry| entry.to_string())
+            .collect();
+        let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+        egui::ScrollArea::vertical()
+            .max_height(200.0)
+            .auto_shrink([false, true])
+            .stick_to_bottom(true)
+            .show_rows(ui, row_height, entries.len(), |ui, rows| {
+                for line in &entries[rows] {
+                    ui.label(egui::RichText::new(line).monospace());
+                }
+            });
+    }
+
+    // Contenu de la case sélectionnée en mode Dieu, et conversions possibles
+    fn show_tile_inspector(
+        &mut self,
+        ui: &mut egui::Ui,
+        view: &GameView,
+        commands: &mut Vec<WorkerCommand>,
+    ) {
+        ui.horizontal(|ui| {
+            ui.heading(tr("tile.title"));
+            if ui.button("✖").clicked() {
+                self.selected_tile = None;
+            }
+        });
+        let Some(tile) = self
+            .selected_tile
+            .and_then(|position| view.state.grid.get_tile(position))
+        else {
+            return;
+        };
+        let (x, y) = tile.position;
+        ui.label(trf("tile.position", &[&x, &y]));
+        ui.separator();
+
+        match &tile.tile_type {
+            TileType::Default => {
+                ui.label(tr("tile.empty"));
+            }
+            TileType::Wall => {
+                ui.label(tr("tile.wall"));
+            }
+            TileType::DeathZone => {
+                ui.label(tr("tile.death_zone"));
+            }
+            TileType::Mud { slow_factor } => {
+                ui.label(trf("tile.mud_slow", &[slow_factor]));
+            }
+            TileType::Tunnel { exit } => {
+                ui.label(trf("tile.tunnel_to", &[&exit.0, &exit.1]));
+            }
+            TileType::FoodSource { amount, .. } if tile.is_corpse() => {
+                ui.label(trf("tile.corpse", &[amount]));
+            }
+            TileType::FoodSource { amount, value } => {
+                ui.label(trf("tile.food", &[amount]));
+                ui.label(trf("tile.food_value", &[value]));
+            }
+            TileType::Nest {
+                colony,
+                stored_food,
+                explorer_capacity,
+                picker_capacity,
+                fighter_capacity,
+            } => {
+                ui.label(trf("tile.nest", &[&Colony::label(*colony)]));
+                ui.label(trf("tile.stored_food", &[stored_food]));
+                ui.label(trf(
+                    "tile.slots",
+                    &[explorer_capacity, picker_capacity, fighter_capacity],
+                ));
+            }
+        }
+        let ants_here = view
+            .state
+            .ants
+            .iter()
+            .filter(|ant| ant.position == Some((x, y)))
+            .count();
+        ui.label(trf("tile.ants_here", &[&ants_here]));
+
+        ui.separator();
+        if tile.nest_colony().is_some() {
+            ui.label(tr("tile.nest_locked"));
+        } else {
+            ui.label(tr("tile.convert"));
+            ui.horizontal_wrapped(|ui| {
+                let conversions = [
+                    (tr("tile.empty"), TileType::Default),
+                    (tr("tile.wall"), TileType::Wall),
+                    (tr("tile.death_zone"), TileType::DeathZone),
+                    (
+                        tr("tile.mud"),
+                        TileType::Mud {
+                            slow_factor: DEFAULT_MUD_SLOW_FACTOR,
+                        },
+                    ),
+                ];
+                for (label, tile_type) in conversions {
+                    if ui
+                        .add_enabled(tile.tile_type != tile_type, egui::Button::new(label))
+                        .clicked()
+                    {
+                        commands.push(WorkerCommand::EditTile((x, y), tile_type));
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                if ui.button(tr("tile.drop_food")).clicked() {
+                    commands.push(WorkerCommand::EditTile(
+                        (x, y),
+                        TileType::FoodSource {
+                            amount: self.god_food_amount,
+                            value: SUGAR_VALUE,
+                        },
+                    ));
+                }
+                ui.add(egui::DragValue::new(&mut self.god_food_amount).range(1..=10_000));
+            });
+        }
+        if let Some(error) = &view.error {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+    }
+
+    // Méta-contrôleur : réglages avant le lancement, puis courbes d'epsilon et d'alpha
+    // effectifs et dernières décisions
+    fn show_controller(&mut self, ui: &mut egui::Ui, view: &GameView, params_enabled: bool) {
+        ui.add_enabled_ui(params_enabled, |ui| {
+            egui::ComboBox::from_id_salt("meta_controller")
+                .selected_text(self.config.meta_controller.label())
+                .show_ui(ui, |ui| {
+                    for mode in ControllerMode::all() {
+                        ui.selectable_value(&mut self.config.meta_controller, mode, mode.label());
+                    }
+                })
+                .response
+                .on_hover_text(tr("controller.hint"));
+            ui.add_enabled_ui(self.config.meta_controller != ControllerMode::Off, |ui| {
+                ui.label(tr("controller.period"));
+                ui.add(egui::Slider::new(
+                    &mut self.config.controller_period,
+                    50..=5000,
+                ));
+                if self.config.meta_controller == ControllerMode::Stall {
+                    ui.label(tr("controller.step"));
+                    ui.add(egui::Slider::new(
+                        &mut self.config.controller_step,
+                        1.05..=4.0,
+                    ));
+                }
+                ui.label(tr("controller.max_epsilon"));
+                ui.add(egui::Slider::new(
+                    &mut self.config.controller_max_epsilon,
+                    0.0..=1.0,
+                ));
+            });
+        });
+
+        let decisions = &view.controller;
+        if decisions.is_empty() {
+            return;
+        }
+        ui.separator();
+        egui_plot::Plot::new("controller_plot")
+            .height(140.0)
+            .include_y(0.0)
+            .allow_scroll(false)
+            .allow_zoom(false)
+            .allow_drag(false)
+            .x_axis_label("tick")
+            .legend(egui_plot::Legend::default())
+            .show(ui, |plot_ui| {
+                let line = |value: fn(&ControllerDecision) -> f32| {
+                    decisions
+                        .iter()
+                        .map(|decision| [decision.tick as f64, value(decision) as f64])
+                        .collect::<Vec<_>>()
+                };
+                plot_ui.line(egui_plot::Line::new("epsilon", line(|d| d.epsilon)));
+                plot_ui.line(egui_plot::Line::new("alpha", line(|d| d.alpha)));
+            });
+        egui::Grid::new("controller_decisions")
+            .striped(true)
+            .show(ui, |ui| {
+                for header in ["tick", "controller.rate", "ε", "α"] {
+                    ui.strong(tr(header));
+                }
+                ui.end_row();
+                for decision in decisions.iter().rev().take(8) {
+                    ui.label(decision.tick.to_string());
+                    ui.label(format!("{:.3}", decision.rate));
+                    ui.label(format!("x{:.2}", decision.epsilon_scale));
+                    ui.label(format!("x{:.2}", decision.alpha_scale));
+                    ui.end_row();
+                }
+            });
+    }
+
+    // Évolution de la partie : de quoi juger si la colonie apprend
+    fn show_stats_plots(ui: &mut egui::Ui, view: &GameView, commands: &mut Vec<WorkerCommand>) {
+        let plots = StatsSeries::all();
+        ui.columns(plots.len(), |columns| {
+            for (ui, series) in columns.iter_mut().zip(plots) {
+                // L'identifiant du texte sert aussi d'identifiant au graphique, stable d'une langue à l'autre
+                let key = series.key();
+                let title = tr(key);
+                ui.horizontal(|ui| {
+                    ui.label(title);
+                    // Courbe en CSV et en PNG dans le dossier courant, à coller dans un support
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if ui
+                        .small_button(tr("plots.export"))
+                        .on_hover_text(tr("plots.export_hint"))
+                        .clicked()
+                    {
+                        commands.push(WorkerCommand::ExportChart(series));
+                    }
+                });
+                let mut plot = egui_plot::Plot::new(key)
+                    .height(140.0)
+                    .include_y(0.0)
+                    .allow_scroll(false)
+                    .allow_zoom(false)
+                    .allow_drag(false)
+                    .x_axis_label("tick");
+                // En mode comparaison, les deux parties se partagent chaque graphique
+                if view.rival.is_some() {
+                    plot = plot.legend(egui_plot::Legend::default());
+                }
+                plot.show(ui, |plot_ui| match &view.rival {
+                    Some(rival) => {
+                        plot_ui.line(egui_plot::Line::new(
+                            "Simulation 1",
+                            series.points(&view.stats),
+                        ));
+                        plot_ui.line(egui_plot::Line::new(
+                            "Simulation 2",
+                            series.points(&rival.stats),
+                        ));
+                    }
+                    None => plot_ui.line(egui_plot::Line::new(title, series.points(&view.stats))),
+                });
+            }
+        });
+    }
+
+    // Réglages de la seconde partie : ceux de la première, sauf l'apprentissage s'ils diffèrent
+    // Presets : charger un jeu de paramètres (avant le lancement) ou enregistrer le réglage courant
+    fn show_presets(
+        &mut self,
+        ui: &mut egui::Ui,
+        params_enabled: bool,
+        commands: &mut Vec<WorkerCommand>,
+    ) {
+        let selected_label = self
+            .selected_preset
+            .as_ref()
+            .map_or(tr("presets.none"), Preset::label)
+            .to_string();
+        egui::ComboBox::from_id_salt("preset")
+            .selected_text(selected_label)
+            .show_ui(ui, |ui| {
+                for preset in &self.presets {
+                    ui.selectable_value(
+                        &mut self.selected_preset,
+                        Some(preset.clone()),
+                        preset.label(),
+                    );
+                }
+            });
+        ui.horizontal(|ui| {
+            let selected = self.selected_preset.clone();
+            let can_load = params_enabled && selected.is_some();
+            if ui
+                .add_enabled(can_load, egui::Button::new(tr("presets.load")))
+                .clicked()
+            {
+                if let Some(preset) = &selected {
+                    match preset.apply(&self.config) {
+                        Ok(config) => {
+                            self.config = config;
+                            commands.push(WorkerCommand::SetTickRate(self.config.tick_rate));
+                            commands
+                                .push(WorkerCommand::SetTicksPerFrame(self.config.ticks_per_frame));
+                            self.preset_error = None;
+                        }
+                        Err(e) => self.preset_error = Some(e),
+                    }
+                }
+            }
+            let can_delete = matches!(selected, Some(Preset::User(_)));
+            if ui
+                .add_enabled(can_delete, egui::Button::new(tr("presets.delete")))
+                .clicked()
+            {
+                if let Some(preset) = &selected {
+                    match preset.delete() {
+                        Ok(()) => {
+                            self.selected_preset = None;
+                            self.presets = Preset::all();
+                            self.preset_error = None;
+                        }
+                        Err(e) => self.preset_error = Some(e),
+                    }
+                }
+            }
+        });
+        if !params_enabled {
+            ui.label(egui::RichText::new(tr("presets.locked")).small());
+        }
+
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.preset_name)
+                    .hint_text(tr("presets.name"))
+                    .desired_width(140.0),
+            );
+            if ui
+                .add_enabled(
+                    !self.preset_name.trim().is_empty(),
+                    egui::Button::new(tr("presets.save")),
+                )
+                .clicked()
+            {
+                match Preset::save(&self.preset_name, &self.config) {
+                    Ok(preset) => {
+                        self.presets = Preset::all();
+                        self.selected_preset = Some(preset);
+                        self.preset_name.clear();
+                        self.preset_error = None;
+                    }
+                    Err(e) => self.preset_error = Some(e),
+                }
+            }
+        });
+        if let Some(error) = &self.preset_error {
+            ui.colored_label(egui::Color32::RED, error);
+        }
+    }
+
+    fn rival_settings(&self) -> SimulationConfig {
+        if self.comparison_shared {
+            return self.config.clone();
+        }
+        SimulationConfig {
+            alpha: self.rival_config.alpha,
+            gamma: self.rival_config.gamma,
+            epsilon: self.rival_config.epsilon,
+            exploration_policy: self.rival_config.exploration_policy,
+            learning_algorithm: self.rival_config.learning_algorithm,
+            policy: self.rival_config.policy,
+            ..self.config.clone()
+        }
+    }
 
-                            ui.separator();
+    // Mode comparaison : réglé avant le lancement, résumé de la seconde partie ensuite
+    // Compteurs des fourmis additionnés par type, pour comparer leur efficacité
+    fn show_type_stats(ui: &mut egui::Ui, view: &GameView) {
+        let totals = AntTypeStats::by_type(&view.state.ants);
+        egui::Grid::new("type_stats")
+            .striped(true)
+            .num_columns(8)
+            .show(ui, |ui| {
+                for header in [
+                    "type_stats.type",
+                    "type_stats.ants",
+                    "type_stats.trips",
+                    "type_stats.distance",
+                    "type_stats.food",
+                    "type_stats.ticks",
+                    "type_stats.food_rate",
+                    "type_stats.distance_per_trip",
+                ] {
+                    ui.label(egui::RichText::new(tr(header)).strong());
+                }
+                ui.end_row();
+                for ant_type in AntsType::all() {
+                    let stats = &totals[ant_type.index()];
+                    ui.label(i18n::ant_type(ant_type));
+                    ui.label(stats.ants.to_string());
+                    ui.label(stats.trips.to_string());
+                    ui.label(stats.distance.to_string());
+                    ui.label(stats.food_delivered.to_string());
+                    ui.label(stats.ticks_alive.to_string());
+                    ui.label(format!("{:.1}", stats.food_per_kilotick()));
+                    ui.label(
+                        stats
+                            .distance_per_trip()
+                            .map_or("-".to_string(), |d| format!("{:.1}", d)),
+                    );
+                    ui.end_row();
+                }
+            });
+    }
 
-                            ui.label("Coût Déplacement (-):");
-                            ui.add(egui::Slider::new(
-                                &mut self.config.reward_default,
-                                -5.0..=0.0,
-                            ));
+    fn show_comparison_controls(&mut self, ui: &mut egui::Ui, view: &GameView) {
+        ui.collapsing(tr("compare.title"), |ui| {
+            if let Some(rival) = &view.rival {
+                ui.label(trf("compare.rival_tick", &[&rival.state.tick]));
+                for colony in &rival.state.colonies {
+                    ui.label(trf(
+                        "stats.colony_food",
+                        &[&Colony::label(colony.id), &colony.score],
+                    ));
+                }
+                ui.label(format!(
+                    "Alpha {:.4} / Epsilon {:.4}",
+                    rival.alpha, rival.epsilon
+                ));
+                if let Some(reason) = &rival.end_reason {
+                    ui.label(trf("game.over", &[&reason.label()]));
+                }
+                return;
+            }
 
-                            ui.label("Mort (-):");
-                            ui.add(egui::Slider::new(
-                                &mut self.config.reward_death,
-                                -500.0..=-10.0,
-                            ));
-                        });
+            let editable = !self.simulation_started && !view.replaying;
+            ui.add_enabled_ui(editable, |ui| {
+                ui.checkbox(&mut self.comparison, tr("compare.enable"));
+                if !self.comparison {
+                    return;
+                }
+                ui.checkbox(&mut self.comparison_shared, tr("compare.shared"));
+                if self.comparison_shared {
+                    return;
+                }
+                let rival = &mut self.rival_config;
+                egui::ComboBox::from_id_salt("rival_policy")
+                    .selected_text(rival.policy.label())
+                    .show_ui(ui, |ui| {
+                        for policy in PolicyKind::all() {
+                            ui.selectable_value(&mut rival.policy, policy, policy.label());
+                        }
                     });
-
-                    ui.add_space(10.0);
-
-                    // Afficher les options de visualisation
-                    ui.collapsing("Visualisation", |ui| {
-                        ui.checkbox(&mut self.show_pheromones_food, "Pistes Nourriture");
-                        ui.checkbox(&mut self.show_pheromones_nest, "Pistes Retour");
+                ui.add(egui::Slider::new(&mut rival.alpha, 0.0..=1.0).text(tr("compare.alpha")));
+                ui.add(egui::Slider::new(&mut rival.gamma, 0.0..=1.0).text(tr("compare.gamma")));
+                ui.add(
+                    egui::Slider::new(&mut rival.epsilon, 0.0..=1.0).text(tr("compare.epsilon")),
+                );
+                egui::ComboBox::from_id_salt("rival_exploration_policy")
+                    .selected_text(rival.exploration_policy.label())
+                    .show_ui(ui, |ui| {
+                        for policy in ExplorationPolicy::all() {
+                            ui.selectable_value(
+                                &mut rival.exploration_policy,
+                                policy,
+                                policy.label(),
+                            );
+                        }
+                    });
+                egui::ComboBox::from_id_salt("rival_learning_algorithm")
+                    .selected_text(rival.learning_algorithm.label())
+                    .show_ui(ui, |ui| {
+                        for algorithm in LearningAlgorithm::all() {
+                            ui.selectable_value(
+                                &mut rival.learning_algorithm,
+                                algorithm,
+                                algorithm.label(),
+                            );
+                        }
                     });
+                ui.label(egui::RichText::new(tr("compare.note")).small());
+            });
+            if view.replaying {
+                ui.label(egui::RichText::new(tr("common.unavailable_replay")).small());
+            }
+        });
+    }
 
-                    ui.add_space(20.0);
-                    ui.separator();
+    // Détails de la fourmi sélectionnée sur le plateau
+    fn show_ant_inspector(&mut self, ui: &mut egui::Ui, view: &GameView) {
+        ui.horizontal(|ui| {
+            ui.heading(tr("ant.title"));
+            if ui.button("✖").clicked() {
+                self.selected_ant = None;
+            }
+        });
+        let Some(id) = self.selected_ant else {
+            return;
+        };
+        let Some(ant) = view.state.ant(id) else {
+            self.selected_ant = None;
+            return;
+        };
+        ui.separator();
 
-                    if ui.button("Quitter / Reset").clicked() {
-                        self.state = AppState::DimensionInput;
-                        self.ants_game_manager = None;
-                        self.is_running = false;
-                        self.simulation_started = false;
+        egui::Grid::new("ant_details").striped(true).show(ui, |ui| {
+            ui.label(tr("ant.number"));
+            ui.label(format!("#{}", ant.id));
+            ui.end_row();
+            ui.label(tr("ant.type"));
+            ui.label(i18n::ant_type(ant.ant_type));
+            ui.end_row();
+            if view.state.colonies.len() > 1 {
+                ui.label(tr("ant.colony"));
+                ui.label(Colony::label(ant.colony).to_string());
+                ui.end_row();
+            }
+            ui.label(tr("ant.mode"));
+            ui.label(ant.mode.label());
+            ui.end_row();
+            ui.label(tr("ant.load"));
+            ui.label(format!("{} / {}", ant.current_charge, ant.maximal_charge));
+            ui.end_row();
+            if self.config.ant_energy > 0.0 {
+                ui.label(tr("ant.energy"));
+                ui.label(format!(
+                    "{:.0} / {:.0}{}",
+                    ant.energy,
+                    self.config.ant_energy,
+                    if ant.is_hungry(&self.config) {
+                        tr("ant.hungry")
+                    } else {
+                        ""
                     }
-                });
+                ));
+                ui.end_row();
+            }
+            ui.label(tr("ant.age"));
+            ui.label(match self.config.lifespan[ant.ant_type.index()] {
+                0 => format!("{} ticks", ant.age),
+                lifespan => format!("{} / {} ticks", ant.age, lifespan),
             });
-
-        // Sync config vers manager
-        if !self.simulation_started {
-            if let Some(manager) = &mut self.ants_game_manager {
-                manager.config = self.config.clone();
-                manager.rl_params.alpha = self.config.alpha;
-                manager.rl_params.gamma = self.config.gamma;
-                manager.rl_params.epsilon = self.config.epsilon;
+            ui.end_row();
+            ui.label(tr("ant.cooldown"));
+            ui.label(format!(
+                "{} / {} ticks",
+                ant.cooldown, ant.seconds_for_movement
+            ));
+            ui.end_row();
+            ui.label(tr("ant.trips"));
+            ui.label(trf(
+                "ant.trips_value",
+                &[&ant.stats.trips, &ant.stats.food_delivered],
+            ));
+            ui.end_row();
+            ui.label(tr("ant.walked"));
+            ui.label(trf(
+                "ant.walked_value",
+                &[&ant.stats.distance, &ant.stats.ticks_alive],
+            ));
+            ui.end_row();
+            if !ant.trace.is_empty() {
+                ui.label(tr("ant.trace"));
+                ui.label(trf("ant.trace_value", &[&ant.trace.len()]));
+                ui.end_row();
             }
-        }
-
-        // Zone de dessin
-        egui::CentralPanel::default().show(ctx, |ui| {
-            if let Some(game_manager) = &self.ants_game_manager {
-                self.draw_board(ui, game_manager);
+            ui.label(tr("ant.position"));
+            ui.label(match ant.position {
+                Some((x, y)) => format!("({}, {})", x, y),
+                None => tr("ant.off_map").to_string(),
+            });
+            ui.end_row();
+            ui.label(tr("ant.vision"));
+            ui.label(match ant.scope {
+                0 => tr("ant.blind").to_string(),
+                1 => tr("ant.one_cell").to_string(),
+                scope => trf("ant.cells", &[&scope]),
+            });
+            ui.end_row();
+            if let Some(position) = ant.position.filter(|_| ant.scope > 0) {
+                let perception = sensing::sense(&view.state.grid, position, ant.scope, ant.colony);
+                ui.label(tr("ant.in_sight"));
+                let mut seen = Vec::new();
+                if let Some((fx, fy)) = perception.food {
+                    seen.push(trf("ant.seen_food", &[&fx, &fy]));
+                }
+                if let Some((nx, ny)) = perception.nest {
+                    seen.push(trf("ant.seen_nest", &[&nx, &ny]));
+                }
+                if perception.hazards > 0 {
+                    seen.push(trf("ant.seen_hazards", &[&perception.hazards]));
+                }
+                ui.label(if seen.is_empty() {
+                    tr("ant.seen_nothing").to_string()
+                } else {
+                    seen.join(", ")
+                });
+                ui.end_row();
             }
         });
+
+        ui.checkbox(&mut self.follow_selected_ant, tr("ant.follow"));
+
+        // Valeurs Q de la case courante, sur la carte que la fourmi utilise dans son mode
+        let map = view
+            .state
+            .colonies
+            .get(ant.colony)
+            .map(|colony| colony.map(ant.ant_type, ant.mode));
+        if let (Some((x, y)), Some(map)) = (ant.position, map) {
+            ui.separator();
+            ui.label(match ant.mode {
+                AntsMode::FINDING => tr("ant.q_food"),
+                AntsMode::RETURNING => tr("ant.q_nest"),
+            });
+            let best = map.get_best_action(x, y, &view.state.grid);
+            egui::Grid::new("ant_q_values")
+                .striped(true)
+                .show(ui, |ui| {
+                    for action in Action::all() {
+                        let text = tr(match action {
+                            Action::Up => "action.up",
+                            Action::Down => "action.down",
+                            Action::Left => "action.left",
+                            Action::Right => "action.right",
+                            Action::Stay => "action.stay",
+                        });
+                        let value = format!("{:.3}", map.get_q(x, y, action));
+                        if action == best {
+                            ui.strong(text);
+                            ui.strong(value);
+                        } else {
+                            ui.label(text);
+                            ui.label(value);
+                        }
+                        ui.end_row();
+                    }
+                });
+        }
+
+        ui.separator();
+        ui.label(trf("ant.trail", &[&view.trail.len()]));
+        egui::ScrollArea::vertical()
+            .max_height(200.0)
+            .show(ui, |ui| {
+                for (x, y) in view.trail.iter().rev() {
+                    ui.monospace(format!("({}, {})", x, y));
+                }
+            });
     }
 
-    fn draw_board(&self, ui: &mut egui::Ui, game_manager: &AntsGameManager) {
-        let grid = &game_manager.grid;
+    /// Dessine une partie ; seul le plateau `interactive` sélectionne fourmis et cases
+    /// (la seconde partie du mode comparaison est en lecture seule)
+    fn draw_board(
+        &mut self,
+        ui: &mut egui::Ui,
+        view: &GameView,
+        interactive: bool,
+        commands: &mut Vec<WorkerCommand>,
+    ) {
+        let grid = &view.state.grid;
         let available_size = ui.available_size();
 
         if available_size.x <= 0.0 || available_size.y <= 0.0 {
             return;
         }
 
-        let (response, painter) = ui.allocate_painter(available_size, egui::Sense::hover());
+        let (response, painter) =
+            ui.allocate_painter(available_size, egui::Sense::click_and_drag());
+
+        let followed = self
+            .selected_ant
+            .filter(|_| interactive && self.follow_selected_ant)
+            .and_then(|id| view.state.ant(id))
+            .and_then(|ant| ant.position);
+        if let Some(position) = followed {
+            self.board_camera.focus(position);
+        }
 
-        let width = grid.get_width() as f32;
-        let height = grid.get_height() as f32;
-        let cell_size = (available_size.x / width)
-            .min(available_size.y / height)
-            .min(50.0);
+        let layout = self.board_camera.update(
+            ui,
+            &response,
+            (grid.get_width(), grid.get_height()),
+            50.0,
+            true,
+        );
+        let cell_size = layout.cell_size;
+        let (offset_x, offset_y) = (layout.origin.x, layout.origin.y);
 
-        let offset_x = response.rect.min.x + (available_size.x - width * cell_size) / 2.0;
-        let offset_y = response.rect.min.y + (available_size.y - height * cell_size) / 2.0;
+        // Un clic sur une case sélectionne une de ses fourmis, un nouveau clic passe à la suivante
+        // (en mode Dieu, il sélectionne la case elle-même ou lui applique l'outil choisi)
+        let clicked_cell = (interactive && response.clicked())
+            .then(|| response.interact_pointer_pos())
+            .flatten()
+            .and_then(|pos| layout.cell_at(pos));
+        if let Some(cell) = clicked_cell {
+            if self.god_mode {
+                match self.god_tool.tile(self.god_food_amount) {
+                    Some(tile_type) if !view.replaying => {
+                        commands.push(WorkerCommand::EditTile(cell, tile_type))
+                    }
+                    _ => self.selected_tile = Some(cell),
+                }
+            } else {
+                let here: Vec<u32> = view
+                    .state
+                    .ants
+                    .iter()
+                    .filter(|ant| ant.position == Some(cell))
+                    .map(|ant| ant.id)
+                    .collect();
+                let next = self
+                    .selected_ant
+                    .and_then(|current| here.iter().position(|&id| id == current))
+                    .map_or(0, |rank| rank + 1);
+                self.selected_ant = here.get(next % here.len().max(1)).copied();
+            }
+        }
 
-        self.draw_grid_base(&painter, grid, offset_x, offset_y, cell_size);
+        self.static_layers[usize::from(!interactive)].draw(
+            ui.ctx(),
+            &painter,
+            grid,
+            layout.origin,
+            cell_size,
+        );
 
-        if self.show_pheromones_food {
+        let palette = theme::palette();
+        let colonies = &view.state.colonies;
+        let shown = &colonies[self.shown_colony.min(colonies.len() - 1)];
+        let (shown_food, shown_nest) = shown.maps(self.shown_caste);
+        let [food_scale, nest_scale] = self.heat_scales(
+            usize::from(!interactive),
+            [
+                self.show_pheromones_food.then_some(shown_food),
+                self.show_pheromones_nest.then_some(shown_nest),
+            ],
+            grid,
+        );
+        if let Some(trails) = &shown.trails {
+            // ACO classique : les pistes scalaires remplacent les Q-tables, restées vides
+            for (show, mode, color) in [
+                (
+                    self.show_pheromones_food,
+                    AntsMode::FINDING,
+                    palette.food_trail,
+                ),
+                (
+                    self.show_pheromones_nest,
+                    AntsMode::RETURNING,
+                    palette.nest_trail,
+                ),
+            ] {
+                if show {
+                    Self::draw_scent(&painter, trails, mode, offset_x, offset_y, cell_size, color);
+                }
+            }
+        } else if self.show_pheromones_food {
             self.draw_pheromones(
                 &painter,
-                &game_manager.pheromones_food,
+                shown_food,
                 grid,
                 offset_x,
                 offset_y,
                 cell_size,
-                egui::Color32::from_rgb(139, 69, 19),
+                food_scale,
+                palette.food_trail,
             );
         }
         if self.show_pheromones_nest {
             self.draw_pheromones(
                 &painter,
-                &game_manager.pheromones_nest,
+                shown_nest,
                 grid,
                 offset_x,
                 offset_y,
                 cell_size,
-                egui::Color32::from_rgb(255, 105, 180),
+                nest_scale,
+                palette.nest_trail,
+            );
+        }
+        if let Some(fear) = shown.fear.as_ref().filter(|_| self.show_fear) {
+            Self::draw_fear(
+                &painter,
+                fear,
+                self.config.fear_deposit,
+                (offset_x, offset_y),
+                cell_size,
             );
         }
+        if self.show_traffic {
+            self.draw_traffic(&painter, &view.traffic, grid, offset_x, offset_y, cell_size);
+        }
 
+        // La nuit assombrit le plateau sous les objets et les fourmis
+        if view.night {
+            painter.rect_filled(
+                egui::Rect::from_min_size(
+                    layout.origin,
+                    egui::vec2(
+                        grid.get_width() as f32 * cell_size,
+                        grid.get_height() as f32 * cell_size,
+                    ),
+                ),
+                0.0,
+                egui::Color32::from_rgba_unmultiplied(10, 20, 70, 110),
+            );
+        }
         self.draw_grid_objects(&painter, grid, offset_x, offset_y, cell_size);
-        self.draw_ants(&painter, game_manager, offset_x, offset_y, cell_size);
+        let policy_map = match self.policy_overlay {
+            PolicyOverlay::Hidden => None,
+            PolicyOverlay::Food => Some(shown_food),
+            PolicyOverlay::Nest => Some(shown_nest),
+        };
+        if let Some(map) = policy_map {
+            self.draw_policy(&painter, map, grid, offset_x, offset_y, cell_size);
+        }
+        if self.show_ant_paths {
+            Self::draw_ant_paths(&painter, &view.state.ants, offset_x, offset_y, cell_size);
+        }
+        self.draw_ants(&painter, &view.state.ants, offset_x, offset_y, cell_size);
+        self.draw_predators(
+            &painter,
+            &view.state.predators,
+            &view.state.ants,
+            offset_x,
+            offset_y,
+            cell_size,
+        );
+
+        if !interactive {
+            return;
+        }
+
+        // Trajet récent et contour de la fourmi inspectée
+        let cell_center = |(x, y): (u32, u32)| {
+            egui::pos2(
+                offset_x + x as f32 * cell_size + cell_size / 2.0,
+                offset_y + y as f32 * cell_size + cell_size / 2.0,
+            )
+        };
+        let highlight = palette.highlight;
+        painter.add(egui::Shape::line(
+            view.trail.iter().map(|&cell| cell_center(cell)).collect(),
+            egui::Stroke::new(2.0, highlight.gamma_multiply(0.6)),
+        ));
+        let selected = self
+            .selected_ant
+            .and_then(|id| view.state.ant(id))
+            .and_then(|ant| ant.position.map(|position| (position, ant.scope)));
+        if let Some((position, scope)) = selected {
+            // Champ de vision de la fourmi inspectée
+            for cell in sensing::visible_cells(&view.state.grid, position, scope) {
+                painter.rect_filled(
+                    egui::Rect::from_center_size(cell_center(cell), egui::Vec2::splat(cell_size)),
+                    0.0,
+                    highlight.gamma_multiply(0.15),
+                );
+            }
+            painter.circle_stroke(
+                cell_center(position),
+                cell_size * 0.45,
+                egui::Stroke::new(2.5, highlight),
+            );
+        }
+        if let Some(cell) = self.selected_tile.filter(|_| self.god_mode) {
+            painter.rect_stroke(
+                egui::Rect::from_center_size(cell_center(cell), egui::Vec2::splat(cell_size)),
+                0.0,
+                egui::Stroke::new(2.0, egui::Color32::GOLD),
+                egui::StrokeKind::Inside,
+            );
+        }
+
+        if self.show_minimap && !self.board_camera.is_reset() {
+            self.draw_minimap(ui, &painter, response.rect, &layout, view);
+        }
+    }
+
+    // Minicarte en bas à droite du plateau : toute la grille, les fourmis, les prédateurs et
+    // le cadre de la partie visible. Un clic ou un glisser y recentre la vue (et arrête le
+    // suivi de la fourmi inspectée, qui la ramènerait aussitôt)
+    fn draw_minimap(
+        &mut self,
+        ui: &mut egui::Ui,
+        painter: &egui::Painter,
+        board: egui::Rect,
+        layout: &BoardLayout,
+        view: &GameView,
+    ) {
+        let grid = &view.state.grid;
+        let grid_size = egui::vec2(grid.get_width() as f32, grid.get_height() as f32);
+        let scale = (MINIMAP_SIDE / grid_size.max_elem())
+            .min(board.width() / 3.0 / grid_size.x)
+            .min(board.height() / 3.0 / grid_size.y);
+        let size = grid_size * scale;
+        let rect =
+            egui::Rect::from_min_size(board.max - size - egui::Vec2::splat(MINIMAP_MARGIN), size);
+
+        let response = ui.interact(rect, ui.id().with("minimap"), egui::Sense::click_and_drag());
+        let pointer = response
+            .interact_pointer_pos()
+            .filter(|_| response.clicked() || response.dragged());
+        if let Some(pos) = pointer {
+            let cell = ((pos - rect.min) / scale)
+                .clamp(egui::Vec2::ZERO, grid_size - egui::Vec2::splat(1.0));
+            self.follow_selected_ant = false;
+            self.board_camera.focus((cell.x as u32, cell.y as u32));
+        }
+
+        let palette = theme::palette();
+        painter.rect_filled(rect.expand(2.0), 2.0, palette.background);
+        if let Some(texture) = self.static_layers[0].texture_id() {
+            painter.image(
+                texture,
+                rect,
+                egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+                egui::Color32::WHITE,
+            );
+        }
+        let cell_center =
+            |(x, y): (u32, u32)| rect.min + egui::vec2(x as f32 + 0.5, y as f32 + 0.5) * scale;
+        let dot = (scale * 0.5).max(1.0);
+        for ant in &view.state.ants {
+            if let Some(position) = ant.position {
+                painter.circle_filled(cell_center(position), dot, Self::colony_color(ant.colony));
+            }
+        }
+        for predator in &view.state.predators {
+            painter.circle_filled(cell_center(predator.position), dot * 1.5, palette.predator);
+        }
+
+        let visible = layout.visible_cells(board);
+        painter.rect_stroke(
+            egui::Rect::from_min_max(
+                rect.min + visible.min.to_vec2() * scale,
+                rect.min + visible.max.to_vec2() * scale,
+            ),
+            0.0,
+            egui::Stroke::new(1.5, palette.highlight),
+            egui::StrokeKind::Inside,
+        );
+        painter.rect_stroke(
+            rect.expand(2.0),
+            2.0,
+            egui::Stroke::new(1.0, egui::Color32::GRAY),
+            egui::StrokeKind::Inside,
+        );
     }
 
-    fn draw_grid_base(
+    fn draw_predators(
         &self,
         painter: &egui::Painter,
-        grid: &crate::grid::Grid,
+        predators: &[Predator],
+        ants: &[Ant],
         off_x: f32,
         off_y: f32,
         size: f32,
     ) {
-        let grid_w = grid.get_width() as f32 * size;
-        let grid_h = grid.get_height() as f32 * size;
-        painter.rect_filled(
-            egui::Rect::from_min_size(egui::pos2(off_x, off_y), egui::Vec2::new(grid_w, grid_h)),
-            0.0,
-            egui::Color32::from_gray(30),
-        );
+        let cell_center = |(x, y): (u32, u32)| {
+            egui::pos2(
+                off_x + x as f32 * size + size / 2.0,
+                off_y + y as f32 * size + size / 2.0,
+            )
+        };
+        // Affrontements en cours : un trait orange relie chaque combattante à son prédateur
+        for (ant, predator) in predator::clashes(ants, predators) {
+            let Some(position) = ants[ant].position else {
+                continue;
+            };
+            painter.line_segment(
+                [
+                    cell_center(position),
+                    cell_center(predators[predator].position),
+                ],
+                egui::Stroke::new(size * 0.15, egui::Color32::from_rgb(255, 140, 0)),
+            );
+        }
+
+        let color = theme::palette().predator;
+        for predator in predators {
+            let center = cell_center(predator.position);
+            let r = size * 0.4;
 
-        for y in 0..grid.get_height() {
-            for x in 0..grid.get_width() {
+            // Losange (rouge par défaut) pour bien distinguer les prédateurs des fourmis
+            painter.add(egui::Shape::convex_polygon(
+                vec![
+                    egui::pos2(center.x, center.y - r),
+                    egui::pos2(center.x + r, center.y),
+                    egui::pos2(center.x, center.y + r),
+                    egui::pos2(center.x - r, center.y),
+                ],
+                color,
+                egui::Stroke::new(1.0, egui::Color32::BLACK),
+            ));
+            draw_health_bar(painter, center, size, predator.health, predator.max_health);
+        }
+    }
+
+    // Piste de l'ACO classique, plus opaque là où elle est forte (échelle relative à son maximum)
+    fn draw_scent(
+        painter: &egui::Painter,
+        trails: &ScentTrails,
+        mode: AntsMode,
+        off_x: f32,
+        off_y: f32,
+        size: f32,
+        base_color: egui::Color32,
+    ) {
+        let max = trails.max_value(mode);
+        if max <= 0.0 {
+            return;
+        }
+        let (columns, rows) =
+            visible_cells(painter, (off_x, off_y), size, (trails.width, trails.height));
+        let mut mesh = egui::Mesh::default();
+        for y in rows {
+            for x in columns.clone() {
+                let ratio = trails.get(mode, x, y) / max;
+                if ratio < 0.01 {
+                    continue;
+                }
                 let rect = egui::Rect::from_min_size(
                     egui::pos2(off_x + x as f32 * size, off_y + y as f32 * size),
                     egui::Vec2::new(size, size),
-                )
-                .shrink(1.0);
+                );
+                let alpha = (ratio.sqrt() * 200.0) as u8;
+                let color = egui::Color32::from_rgba_unmultiplied(
+                    base_color.r(),
+                    base_color.g(),
+                    base_color.b(),
+                    alpha,
+                );
+                mesh.add_colored_rect(rect, color);
+            }
+        }
+        painter.add(mesh);
+    }
 
-                if let Some(tile) = grid.get_tile((x, y)) {
-                    match tile.tile_type {
-                        crate::tile::TileType::Wall => {
-                            painter.rect_filled(rect, 2.0, egui::Color32::GRAY);
-                        }
-                        crate::tile::TileType::DeathZone => {
-                            painter.rect_filled(rect, 0.0, egui::Color32::from_rgb(50, 0, 0));
-                        }
-                        _ => {}
-                    }
+    // Phéromone d'alarme, à pleine intensité là où vient d'avoir lieu un dépôt `scale`
+    fn draw_fear(
+        painter: &egui::Painter,
+        fear: &FearMap,
+        scale: f32,
+        (off_x, off_y): (f32, f32),
+        size: f32,
+    ) {
+        let (columns, rows) =
+            visible_cells(painter, (off_x, off_y), size, (fear.width, fear.height));
+        let mut mesh = egui::Mesh::default();
+        for y in rows {
+            for x in columns.clone() {
+                let ratio = (fear.get(x, y) / scale.max(f32::EPSILON)).clamp(0.0, 1.0);
+                if ratio < 0.01 {
+                    continue;
                 }
+                let rect = egui::Rect::from_min_size(
+                    egui::pos2(off_x + x as f32 * size, off_y + y as f32 * size),
+                    egui::Vec2::new(size, size),
+                );
+                let [r, g, b, _] = theme::palette().fear.to_array();
+                let color = egui::Color32::from_rgba_unmultiplied(r, g, b, (ratio * 200.0) as u8);
+                mesh.add_colored_rect(rect, color);
             }
         }
+        painter.add(mesh);
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn draw_pheromones(
         &self,
         painter: &egui::Painter,
@@ -633,39 +3815,187 @@ impl Interface {
         off_x: f32,
         off_y: f32,
         size: f32,
+        scale: HeatScale,
         base_color: egui::Color32,
     ) {
-        const MAX_EXPECTED_VALUE: f32 = 50.0;
+        // Seules les cases actives de la carte peuvent porter une piste ; un seul maillage
+        // pour toutes les cases dessinées
+        let (columns, rows) = visible_cells(painter, (off_x, off_y), size, (map.width, map.height));
+        let mut mesh = egui::Mesh::default();
+        for (x, y) in map.active_cells() {
+            if !columns.contains(&x) || !rows.contains(&y) || !grid.is_walkable(x, y) {
+                continue;
+            }
 
-        for y in 0..map.height {
-            for x in 0..map.width {
-                if !grid.is_walkable(x, y) {
-                    continue;
-                }
+            let intensity = scale.intensity(map.get_max_q(x, y, grid), self.heat_log);
+
+            if intensity >= 0.01 {
+                let alpha = (intensity * 200.0) as u8;
 
-                let max_q = map.get_max_q(x, y, grid).max(0.0);
+                let rect = egui::Rect::from_min_size(
+                    egui::pos2(off_x + x as f32 * size, off_y + y as f32 * size),
+                    egui::Vec2::new(size, size),
+                );
 
-                if max_q > 0.1 {
-                    let ratio = (max_q / MAX_EXPECTED_VALUE).clamp(0.0, 1.0);
-                    let visual_intensity = ratio.sqrt();
-                    let alpha = (visual_intensity * 200.0) as u8;
+                let color = egui::Color32::from_rgba_unmultiplied(
+                    base_color.r(),
+                    base_color.g(),
+                    base_color.b(),
+                    alpha,
+                );
 
-                    let rect = egui::Rect::from_min_size(
-                        egui::pos2(off_x + x as f32 * size, off_y + y as f32 * size),
-                        egui::Vec2::new(size, size),
-                    );
+                mesh.add_colored_rect(rect, color);
+            }
+        }
+        painter.add(mesh);
+    }
 
-                    let color = egui::Color32::from_rgba_unmultiplied(
-                        base_color.r(),
-                        base_color.g(),
-                        base_color.b(),
-                        alpha,
-                    );
+    // Passages des fourmis sur chaque case, sur une échelle allant jusqu'à la case la plus
+    // fréquentée
+    fn draw_traffic(
+        &self,
+        painter: &egui::Painter,
+        traffic: &TrafficMap,
+        grid: &crate::grid::Grid,
+        off_x: f32,
+        off_y: f32,
+        size: f32,
+    ) {
+        let scale = HeatScale::fixed(traffic.max() as f32);
+        let traffic_color = theme::palette().traffic;
+        let (width, height) = (grid.get_width(), grid.get_height());
+        let (columns, rows) = visible_cells(painter, (off_x, off_y), size, (width, height));
+        let mut mesh = egui::Mesh::default();
+        for y in rows {
+            for x in columns.clone() {
+                let intensity = scale.intensity(traffic.get(x, y) as f32, self.heat_log);
+                if intensity < 0.01 {
+                    continue;
+                }
+                let rect = egui::Rect::from_min_size(
+                    egui::pos2(off_x + x as f32 * size, off_y + y as f32 * size),
+                    egui::Vec2::new(size, size),
+                );
+                let color = egui::Color32::from_rgba_unmultiplied(
+                    traffic_color.r(),
+                    traffic_color.g(),
+                    traffic_color.b(),
+                    (intensity * 200.0) as u8,
+                );
+                mesh.add_colored_rect(rect, color);
+            }
+        }
+        painter.add(mesh);
+    }
+
+    // Échelles des pistes (nourriture, nid) affichées sur le plateau `board` ; en mode
+    // automatique, la plage de chaque carte montrée suit ses valeurs courantes
+    fn heat_scales(
+        &mut self,
+        board: usize,
+        maps: [Option<&PheromoneMap>; 2],
+        grid: &crate::grid::Grid,
+    ) -> [HeatScale; 2] {
+        if !self.heat_auto {
+            return [HeatScale::fixed(self.config.q_display_scale); 2];
+        }
+        for (range, map) in self.heat_ranges[board].iter_mut().zip(maps) {
+            if let Some(map) = map {
+                range.track(heat_scale::value_range(map, grid));
+            }
+        }
+        self.heat_ranges[board]
+    }
+
+    // Barre de couleur de l'échelle `scale` : du transparent à la couleur pleine, avec
+    // les valeurs des deux extrémités
+    fn show_heat_legend(
+        ui: &mut egui::Ui,
+        label: &str,
+        scale: HeatScale,
+        log: bool,
+        color: egui::Color32,
+    ) {
+        const STEPS: usize = 32;
+        ui.horizontal(|ui| {
+            ui.label(label);
+            ui.label(format!("{:.1}", scale.min));
+            let (rect, _) = ui.allocate_exact_size(egui::vec2(120.0, 12.0), egui::Sense::hover());
+            let painter = ui.painter_at(rect);
+            painter.rect_filled(rect, 0.0, theme::palette().background);
+            let step = rect.width() / STEPS as f32;
+            for i in 0..STEPS {
+                let q = scale.min + (scale.max - scale.min) * (i as f32 + 0.5) / STEPS as f32;
+                let alpha = (scale.intensity(q, log) * 200.0) as u8;
+                painter.rect_filled(
+                    egui::Rect::from_min_size(
+                        rect.min + egui::vec2(i as f32 * step, 0.0),
+                        egui::vec2(step + 0.5, rect.height()),
+                    ),
+                    0.0,
+                    egui::Color32::from_rgba_unmultiplied(color.r(), color.g(), color.b(), alpha),
+                );
+            }
+            ui.label(format!("{:.1}", scale.max));
+        });
+    }
 
-                    painter.rect_filled(rect, 0.0, color);
+    // Flèche vers la meilleure action de chaque case, du bleu (Q faible) au jaune (Q fort)
+    fn draw_policy(
+        &self,
+        painter: &egui::Painter,
+        map: &PheromoneMap,
+        grid: &crate::grid::Grid,
+        off_x: f32,
+        off_y: f32,
+        size: f32,
+    ) {
+        let mut arrows = Vec::new();
+        let mut max_magnitude = 0.0f32;
+        for y in 0..map.height {
+            for x in 0..map.width {
+                if !grid.is_walkable(x, y) {
+                    continue;
+                }
+                let action = map.get_best_action(x, y, grid);
+                let direction = match action {
+                    Action::Up => egui::vec2(0.0, -1.0),
+                    Action::Down => egui::vec2(0.0, 1.0),
+                    Action::Left => egui::vec2(-1.0, 0.0),
+                    Action::Right => egui::vec2(1.0, 0.0),
+                    // Case enfermée : aucune direction à montrer
+                    Action::Stay => continue,
+                };
+                // Case jamais mise à jour : la « meilleure » action n'est qu'un départage
+                let q = map.get_q(x, y, action);
+                if (q - map.bounds().initial).abs() < 1e-3 {
+                    continue;
                 }
+                let magnitude = q.abs();
+                max_magnitude = max_magnitude.max(magnitude);
+                arrows.push((x, y, direction, magnitude));
             }
         }
+
+        let stroke_width = (size * 0.08).clamp(1.0, 3.0);
+        for (x, y, direction, magnitude) in arrows {
+            let ratio = (magnitude / max_magnitude).sqrt();
+            let color = egui::Color32::from_rgb(
+                (60.0 + ratio * 195.0) as u8,
+                (110.0 + ratio * 120.0) as u8,
+                (255.0 - ratio * 255.0) as u8,
+            );
+            let center = egui::pos2(
+                off_x + x as f32 * size + size / 2.0,
+                off_y + y as f32 * size + size / 2.0,
+            );
+            let vec = direction * size * 0.7;
+            painter.arrow(
+                center - vec / 2.0,
+                vec,
+                egui::Stroke::new(stroke_width, color),
+            );
+        }
     }
 
     fn draw_grid_objects(
@@ -676,8 +4006,15 @@ impl Interface {
         off_y: f32,
         size: f32,
     ) {
-        for y in 0..grid.get_height() {
-            for x in 0..grid.get_width() {
+        let (columns, rows) = visible_cells(
+            painter,
+            (off_x, off_y),
+            size,
+            (grid.get_width(), grid.get_height()),
+        );
+        let palette = theme::palette();
+        for y in rows {
+            for x in columns.clone() {
                 let center = egui::pos2(
                     off_x + x as f32 * size + size / 2.0,
                     off_y + y as f32 * size + size / 2.0,
@@ -685,28 +4022,41 @@ impl Interface {
 
                 if let Some(tile) = grid.get_tile((x, y)) {
                     match tile.tile_type {
-                        crate::tile::TileType::Nest { stored_food, .. } => {
+                        crate::tile::TileType::Nest {
+                            colony,
+                            stored_food,
+                            ..
+                        } => {
                             painter.rect_filled(
                                 egui::Rect::from_center_size(center, egui::Vec2::splat(size * 0.5)),
                                 2.0,
-                                egui::Color32::GOLD,
+                                Self::colony_color(colony),
                             );
 
-                            let text = format!("Nid\n{}", stored_food);
+                            let text = trf("board.nest", &[&Colony::label(colony), &stored_food]);
                             painter.text(
                                 center,
                                 egui::Align2::CENTER_CENTER,
                                 text,
                                 egui::FontId::monospace(8.0),
-                                egui::Color32::BLACK,
+                                palette.nest_text,
                             );
                         }
-                        crate::tile::TileType::FoodSource { amount } => {
-                            painter.circle_filled(center, size * 0.35, egui::Color32::GREEN);
+                        crate::tile::TileType::FoodSource { amount, value } => {
+                            // Les cadavres et les sources de plus grande valeur se distinguent
+                            // par leur couleur
+                            let fill = if tile.is_corpse() {
+                                palette.corpse
+                            } else if value > SUGAR_VALUE {
+                                palette.rich_food
+                            } else {
+                                palette.food
+                            };
+                            painter.circle_filled(center, size * 0.35, fill);
                             painter.circle_stroke(
                                 center,
                                 size * 0.35,
-                                egui::Stroke::new(2.0, egui::Color32::WHITE),
+                                egui::Stroke::new(2.0, palette.food_outline),
                             );
 
                             if amount > 0 {
@@ -720,7 +4070,7 @@ impl Interface {
                                     egui::Align2::CENTER_CENTER,
                                     text,
                                     egui::FontId::monospace(7.0),
-                                    egui::Color32::WHITE,
+                                    palette.food_outline,
                                 );
                             } else {
                                 painter.text(
@@ -739,15 +4089,40 @@ impl Interface {
         }
     }
 
-    fn draw_ants(
-        &self,
-        painter: &egui::Painter,
-        manager: &AntsGameManager,
-        off_x: f32,
-        off_y: f32,
-        size: f32,
-    ) {
-        for ant in &manager.ants {
+    fn colony_color(colony: usize) -> egui::Color32 {
+        theme::palette().colonies[colony.min(2)]
+    }
+
+    // Trajet récent de chaque fourmi, de plus en plus opaque en approchant de la fourmi
+    fn draw_ant_paths(painter: &egui::Painter, ants: &[Ant], off_x: f32, off_y: f32, size: f32) {
+        let center = |(x, y): (u32, u32)| {
+            egui::pos2(
+                off_x + x as f32 * size + size / 2.0,
+                off_y + y as f32 * size + size / 2.0,
+            )
+        };
+        let width = (size * 0.15).clamp(1.0, 3.0);
+        for ant in ants.iter().filter(|ant| ant.position.is_some()) {
+            let color = Self::colony_color(ant.colony);
+            let segments = ant.recent_path.len().saturating_sub(1);
+            for (i, (&from, &to)) in ant
+                .recent_path
+                .iter()
+                .zip(ant.recent_path.iter().skip(1))
+                .enumerate()
+            {
+                let fade = (i + 1) as f32 / segments as f32;
+                painter.line_segment(
+                    [center(from), center(to)],
+                    egui::Stroke::new(width, color.gamma_multiply(0.8 * fade)),
+                );
+            }
+        }
+    }
+
+    fn draw_ants(&self, painter: &egui::Painter, ants: &[Ant], off_x: f32, off_y: f32, size: f32) {
+        let palette = theme::palette();
+        for ant in ants {
             if let Some((x, y)) = ant.position {
                 let center = egui::pos2(
                     off_x + x as f32 * size + size / 2.0,
@@ -755,25 +4130,45 @@ impl Interface {
                 );
 
                 let color = match ant.mode {
-                    AntsMode::FINDING => egui::Color32::WHITE,
-                    AntsMode::RETURNING => egui::Color32::YELLOW,
+                    AntsMode::FINDING => palette.ant_finding,
+                    AntsMode::RETURNING => palette.ant_returning,
                 };
 
                 painter.circle_filled(center, size * 0.25, color);
-                painter.circle_stroke(
-                    center,
-                    size * 0.25,
-                    egui::Stroke::new(1.0, egui::Color32::BLACK),
-                );
+                // Le contour indique la colonie (noir pour la colonie A)
+                let outline = if ant.colony == 0 {
+                    palette.ant_outline
+                } else {
+                    Self::colony_color(ant.colony)
+                };
+                painter.circle_stroke(center, size * 0.25, egui::Stroke::new(1.5, outline));
 
                 if ant.current_charge > 0 {
                     painter.circle_filled(
                         egui::pos2(center.x + size * 0.15, center.y - size * 0.15),
                         size * 0.1,
-                        egui::Color32::GREEN,
+                        palette.ant_load,
                     );
                 }
+                draw_health_bar(painter, center, size, ant.health, ant.max_health);
             }
         }
     }
 }
+
+// Barre de vie au-dessus d'une case, seulement pour un combattant blessé
+fn draw_health_bar(painter: &egui::Painter, center: egui::Pos2, size: f32, health: u32, max: u32) {
+    if health >= max || max == 0 {
+        return;
+    }
+    let bar = egui::Rect::from_min_size(
+        egui::pos2(center.x - size * 0.4, center.y - size * 0.5),
+        egui::vec2(size * 0.8, (size * 0.12).max(1.0)),
+    );
+    painter.rect_filled(bar, 0.0, egui::Color32::from_rgb(90, 0, 0));
+    let filled = egui::Rect::from_min_size(
+        bar.min,
+        egui::vec2(bar.width() * health as f32 / max as f32, bar.height()),
+    );
+    painter.rect_filled(filled, 0.0, egui::Color32::from_rgb(0, 200, 0));
+}
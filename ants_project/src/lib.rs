@@ -1,9 +1,98 @@
+//! Simulateur de colonie de fourmis par Q-Learning
+//!
+//! Façade publique stable : les types exportés par [`prelude`] et les méthodes publiques
+//! d'[`ants_game_manager::AntsGameManager`] (constructeurs, `game_step`, accesseurs) suivent
+//! le versionnage sémantique. L'état interne du manager n'est accessible qu'au travers de
+//! ces accesseurs afin de pouvoir faire évoluer la représentation sans casser les projets
+//! qui embarquent le simulateur.
+
+pub mod aco;
 pub mod ant;
+mod ant_decision;
 pub mod ants_game_manager;
+pub mod baseline;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod batch;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod bench;
+mod board_layer;
+pub mod camera;
 pub mod cli_args;
+pub mod colony;
+pub mod coord;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod curriculum;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod ensemble;
+pub mod env;
+pub mod error;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod evaluate;
+pub mod event_editor;
+pub mod event_log;
+pub mod experience;
+pub mod experiment;
+pub mod exploration;
+pub mod fear;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod frame_render;
 pub mod grid;
+pub mod grid_transaction;
+pub mod hazard;
+pub mod heat_scale;
+pub mod html_report;
+pub mod i18n;
 pub mod interface;
+pub mod linear_q;
 pub mod map_editor;
+pub mod map_generator;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod map_preview;
+pub mod meta_controller;
+pub mod metrics;
+pub mod observation;
+pub mod observer;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod optimizer;
+pub mod pathfinding;
+pub mod patrol;
+#[cfg(all(feature = "perf-compare", not(target_arch = "wasm32")))]
+pub mod perf_compare;
 pub mod pheromone;
+pub mod policy;
+pub mod predator;
+pub mod prelude;
+pub mod presets;
+pub mod profiling;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod prometheus;
+#[cfg(feature = "python")]
+mod python;
+pub mod q_backend;
 pub mod q_learning_math;
+pub mod q_table;
+pub mod replay;
+pub mod replay_verify;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod rest_server;
+pub mod reward;
+pub mod runner;
+pub mod scenario;
+pub mod scoring;
+pub mod sensing;
+mod session;
+mod sim_worker;
+pub mod spawn_policy;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod state_stream;
+pub mod stuck;
+#[cfg(not(target_arch = "wasm32"))]
+mod sweep_panel;
+pub mod theme;
 pub mod tile;
+pub mod timeline;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tournament;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tui;
+pub mod tutorial;
@@ -0,0 +1,206 @@
+//! Approximation linéaire des valeurs Q
+//!
+//! Une Q-table garde cinq valeurs par case et n'apprend chaque case que lorsqu'une fourmi y
+//! passe. [`LinearQ`] estime Q(s, a) par une somme pondérée de quelques traits de la case où
+//! mène l'action : sa position grossière (deux pavages décalés de `tile` cases de côté), sa
+//! nature (mur, zone mortelle, nourriture, nid de la colonie) et sa distance au nid le plus
+//! proche. Le nombre de poids ne dépend que du nombre de pavés, et une correction profite
+//! aux cases qui partagent ses traits : un pas qui rapproche du nid vaut la même chose
+//! partout sur la carte.
+//!
+//! Les poids sont propres à chaque combinaison de traits d'état (charge, nourriture en vue),
+//! comme les couches d'une Q-table, et se corrigent par descente de gradient normalisée :
+//! une correction `delta` déplace Q(s, a) d'exactement `delta`.
+
+use crate::exploration::target;
+use crate::grid::Grid;
+use crate::pheromone::{Action, QBounds, ACTION_COUNT};
+use crate::q_backend::QBackend;
+use crate::q_table::StateFeatures;
+use serde::{Deserialize, Serialize};
+
+// Couches de poids : bit 0 = charge, bit 1 = nourriture en vue
+const LAYERS: usize = 4;
+// Pavages de la position, décalés d'un demi-pavé l'un par rapport à l'autre
+const TILINGS: u32 = 2;
+
+// Traits communs à toute la carte, après le biais de chaque action
+const BLOCKED: usize = ACTION_COUNT;
+const LETHAL: usize = ACTION_COUNT + 1;
+const FOOD: usize = ACTION_COUNT + 2;
+const NEST: usize = ACTION_COUNT + 3;
+const NEST_DISTANCE: usize = ACTION_COUNT + 4;
+const NEST_APPROACH: usize = ACTION_COUNT + 5;
+const GLOBAL_FEATURES: usize = ACTION_COUNT + 6;
+
+// Traits actifs d'une paire (état, action) : (indice dans la couche, valeur)
+type Features = Vec<(usize, f32)>;
+
+/// Valeurs Q d'une colonie (ou d'un type de fourmi) approchées par une fonction linéaire
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LinearQ {
+    width: u32,
+    height: u32,
+    tile: u32,
+    colony: usize,
+    // Nids de la colonie, origine des distances
+    nests: Vec<(u32, u32)>,
+    bounds: QBounds,
+    // Poids des couches mises bout à bout
+    weights: Vec<f32>,
+    // Corrections du tick en cours : (couche, traits actifs, delta)
+    #[serde(skip)]
+    pending: Vec<(usize, Features, f32)>,
+}
+
+impl LinearQ {
+    pub fn new(grid: &Grid, colony: usize, tile: u32, bounds: QBounds) -> Self {
+        let mut linear = LinearQ {
+            width: grid.get_width(),
+            height: grid.get_height(),
+            tile: tile.max(1),
+            colony,
+            nests: grid.get_colony_nest_positions(colony),
+            bounds,
+            weights: Vec::new(),
+            pending: Vec::new(),
+        };
+        linear.weights = vec![0.0; linear.layer_len() * LAYERS];
+        linear
+    }
+
+    /// Côté d'un pavé de position, en cases
+    pub fn tile(&self) -> u32 {
+        self.tile
+    }
+
+    /// Nombre de poids appris, toutes couches comprises
+    pub fn weight_count(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn set_bounds(&mut self, bounds: QBounds) {
+        self.bounds = bounds;
+    }
+
+    /// Vrai si le modèle a été construit pour cette grille et ce pavage
+    pub fn fits(&self, grid: &Grid, tile: u32) -> bool {
+        self.width == grid.get_width() && self.height == grid.get_height() && self.tile == tile
+    }
+
+    // Pavés d'un pavage : une colonne et une rangée de plus pour le pavage décalé
+    fn tiling_size(&self) -> (usize, usize) {
+        (
+            (self.width / self.tile + 2) as usize,
+            (self.height / self.tile + 2) as usize,
+        )
+    }
+
+    fn layer_len(&self) -> usize {
+        let (columns, rows) = self.tiling_size();
+        GLOBAL_FEATURES + TILINGS as usize * columns * rows
+    }
+
+    fn layer(state: StateFeatures) -> usize {
+        usize::from(state.carrying) | (usize::from(state.near_food) << 1)
+    }
+
+    fn nest_distance(&self, (x, y): (u32, u32)) -> Option<u32> {
+        self.nests
+            .iter()
+            .map(|&(nx, ny)| x.abs_diff(nx) + y.abs_diff(ny))
+            .min()
+    }
+
+    // Traits actifs de (état, action), passés un à un à `visit` : (indice dans la couche, valeur)
+    fn features(
+        &self,
+        grid: &Grid,
+        state: StateFeatures,
+        action: Action,
+        mut visit: impl FnMut(usize, f32),
+    ) {
+        let from = (state.x, state.y);
+        visit(action.to_usize(), 1.0);
+        // Un pas vers un mur ou hors de la carte laisse la fourmi sur place
+        let (tx, ty) = target(state.x, state.y, action);
+        let to = if action == Action::Stay {
+            from
+        } else if !grid.is_walkable(tx, ty) {
+            visit(BLOCKED, 1.0);
+            from
+        } else {
+            grid.destination(tx, ty)
+        };
+        if grid.is_lethal(to.0, to.1) {
+            visit(LETHAL, 1.0);
+        }
+        if grid.has_food(to.0, to.1) {
+            visit(FOOD, 1.0);
+        }
+        if grid.is_colony_nest(to.0, to.1, self.colony) {
+            visit(NEST, 1.0);
+        }
+        if let (Some(before), Some(after)) = (self.nest_distance(from), self.nest_distance(to)) {
+            visit(
+                NEST_DISTANCE,
+                after as f32 / (self.width + self.height).max(1) as f32,
+            );
+            visit(
+                NEST_APPROACH,
+                (before as f32 - after as f32).clamp(-1.0, 1.0),
+            );
+        }
+        let (columns, rows) = self.tiling_size();
+        for tiling in 0..TILINGS {
+            let offset = tiling * self.tile / TILINGS;
+            let column = ((to.0 + offset) / self.tile) as usize;
+            let row = ((to.1 + offset) / self.tile) as usize;
+            visit(
+                GLOBAL_FEATURES + tiling as usize * columns * rows + row * columns + column,
+                1.0,
+            );
+        }
+    }
+}
+
+impl QBackend for LinearQ {
+    fn get_q(&self, grid: &Grid, state: StateFeatures, action: Action) -> f32 {
+        if state.x >= self.width || state.y >= self.height {
+            return -1000.0; // Hors map, comme la Q-table
+        }
+        let weights = &self.weights[Self::layer(state) * self.layer_len()..];
+        let mut sum = 0.0;
+        self.features(grid, state, action, |i, value| sum += weights[i] * value);
+        let q = self.bounds.initial + sum;
+        if self.bounds.clamp > 0.0 {
+            q.clamp(-self.bounds.clamp, self.bounds.clamp)
+        } else {
+            q
+        }
+    }
+
+    fn queue_update(&mut self, grid: &Grid, state: StateFeatures, action: Action, delta: f32) {
+        if state.x >= self.width || state.y >= self.height {
+            return;
+        }
+        let mut features = Vec::new();
+        self.features(grid, state, action, |i, value| features.push((i, value)));
+        self.pending.push((Self::layer(state), features, delta));
+    }
+
+    fn apply_tick(&mut self, evaporation_rate: f32) {
+        let layer_len = self.layer_len();
+        for (layer, features, delta) in self.pending.drain(..) {
+            let norm: f32 = features.iter().map(|(_, value)| value * value).sum();
+            let weights = &mut self.weights[layer * layer_len..];
+            for (i, value) in features {
+                weights[i] += delta * value / norm;
+            }
+        }
+        // L'évaporation ramène l'estimation vers la valeur de départ, comme sur une carte
+        for weight in &mut self.weights {
+            *weight *= 1.0 - evaporation_rate;
+        }
+    }
+}
@@ -1,12 +1,108 @@
 // On utilise les modules exposés par la lib
-use ants_project::ant::{Ant, AntsType};
-use ants_project::ants_game_manager::AntsGameManager;
-use ants_project::cli_args::SimulationConfig;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::batch;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::bench;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::cli_args::Command;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::curriculum;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::ensemble;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::evaluate;
+use ants_project::event_log;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::frame_render::{FrameRenderer, GifBuffer};
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::html_report;
 use ants_project::interface::Interface;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::map_editor::MapEditor;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::optimizer;
+#[cfg(all(feature = "perf-compare", not(target_arch = "wasm32")))]
+use ants_project::perf_compare;
+use ants_project::prelude::*;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::prometheus::MetricsExporter;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::replay::Replay;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::replay_verify;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::rest_server;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::scenario::Scenario;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::state_stream::StateStreamer;
+#[cfg(not(target_arch = "wasm32"))]
+use ants_project::tournament;
+#[cfg(not(target_arch = "wasm32"))]
+use std::io::Write;
 
+#[cfg(not(target_arch = "wasm32"))]
 fn main() -> Result<(), eframe::Error> {
-    // Parse les arguments de la ligne de commande
-    let config = SimulationConfig::from_args();
+    // Parse la sous-commande et ses options
+    let command = Command::from_args().unwrap_or_else(|e| {
+        eprintln!("Erreur de configuration: {}", e);
+        eprintln!("Utilisez --help pour voir les options disponibles");
+        std::process::exit(1);
+    });
+    let (config, editing) = match command {
+        Command::Optimize(args) => {
+            if let Err(e) = optimizer::run(&args) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Command::Tournament(args) => {
+            if let Err(e) = tournament::run(&args) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Command::Curriculum(args) => {
+            if let Err(e) = curriculum::run(&args) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Command::VerifyReplay(args) => {
+            if let Err(e) = replay_verify::run(&args) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Command::Evaluate(args) => {
+            if let Err(e) = evaluate::run(&args) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Command::Serve(args) => {
+            if let Err(e) = rest_server::run(&args) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        #[cfg(feature = "perf-compare")]
+        Command::PerfCompare(args) => {
+            if let Err(e) = perf_compare::run(&args) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Command::Edit(config) => (config, true),
+        Command::Simulate(config) | Command::Replay(config) => (config, false),
+    };
 
     // Valider la configuration
     if let Err(e) = config.validate() {
@@ -15,45 +111,490 @@ fn main() -> Result<(), eframe::Error> {
         std::process::exit(1);
     }
 
+    // Configuration effective (fichier + options) pour relancer la même simulation
+    if let Some(path) = &config.dump_config {
+        if let Err(e) = config.save(path) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+
+    // Journal d'événements : recopié sur la sortie d'erreur en CLI, panneau Journal en GUI
+    event_log::install(config.log_level, !config.use_gui && !config.use_tui);
+
+    if let Err(e) = batch::limit_threads(config.threads) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+
+    if editing {
+        return run_editor(config);
+    }
+
+    // Partie enregistrée à rejouer à la place d'une nouvelle simulation
+    let replay = config.replay_file.as_ref().map(|path| {
+        Replay::load(path)
+            .and_then(|replay| Ok(AntsGameManager::from_replay(replay)?))
+            .unwrap_or_else(|e| {
+                eprintln!("Erreur de chargement du replay: {}", e);
+                std::process::exit(1);
+            })
+    });
+
+    // Carte du scénario, sinon carte dessinée dans l'éditeur et sauvegardée sur disque
+    let map = match (&config.scenario_file, &config.map_file) {
+        (Some(path), _) => {
+            Scenario::load(path).and_then(|scenario| scenario.map_editor().transpose())
+        }
+        (None, Some(path)) => MapEditor::load_from_file(path).map(Some),
+        _ => Ok(None),
+    }
+    .unwrap_or_else(|e| {
+        eprintln!("Erreur de chargement de la carte: {}", e);
+        std::process::exit(1);
+    });
+
     // Mode GUI ou CLI
     if config.use_gui {
+        // Avec une carte fournie, la partie est créée ici et le plateau s'ouvre sans l'assistant
+        let game = match (&replay, &map) {
+            (None, Some(_)) if config.scenario_file.is_none() => Some(
+                build_manager(config.clone(), map.as_ref()).unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }),
+            ),
+            _ => None,
+        };
         let options = eframe::NativeOptions::default();
         eframe::run_native(
             "Ant Simulator",
             options,
             // On utilise Interface depuis la lib
-            Box::new(move |_cc| Ok(Box::new(Interface::new_with_config(config.clone())))),
+            Box::new(move |cc| {
+                let interface = match (replay, game, map) {
+                    (Some(manager), _, _) => Interface::new_with_replay(manager),
+                    (None, Some(manager), _) => Interface::new_with_game(manager),
+                    // La carte du scénario s'ouvre dans l'éditeur, prête à être lancée
+                    (None, None, Some(editor)) if config.scenario_file.is_some() => {
+                        Interface::new_with_editor(editor, config.clone())
+                    }
+                    _ => Interface::new_with_config(config.clone()),
+                };
+                Ok(Box::new(interface.with_storage(cc.storage)))
+            }),
         )
     } else {
-        println!("Mode CLI actif. Simulation en cours...");
+        // Benchmark : une partie fixe jouée pour mesurer le débit, sans autre sortie
+        if config.bench_ticks > 0 {
+            let report = bench::run_bench(&config).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            });
+            report.print();
+            return Ok(());
+        }
 
-        let mut ants = Vec::new();
-        for _ in 0..config.num_explorers {
-            ants.push(Ant::new(AntsType::EXPLORER));
+        // Un bilan JSON sur la sortie standard y est seul, pour être lu tel quel
+        let quiet = config.summary_file.as_deref() == Some("-");
+        if !quiet {
+            println!("Mode CLI actif. Simulation en cours...");
         }
-        for _ in 0..config.num_pickers {
-            ants.push(Ant::new(AntsType::PICKER));
+
+        if let Some(manager) = replay {
+            let mut runner = SimulationRunner::new(manager).with_max_ticks(config.max_ticks);
+            if config.realtime {
+                runner = runner.with_tick_rate(config.tick_rate);
+            }
+            let animation = report_animation(&mut runner, &config);
+            let summary = if config.use_tui {
+                run_tui(&mut runner);
+                runner.summary()
+            } else {
+                run_headless(&mut runner, config.watch_interval)
+            };
+            if let Some(path) = &config.summary_file {
+                if let Err(e) = runner.report().save(path) {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+            if let Some(path) = &config.report_file {
+                write_report(&mut runner, path, animation);
+            }
+            if !quiet {
+                println!("{}", summary.ticks);
+                if let Some(reason) = summary.end_reason {
+                    println!("Fin de partie: {}", reason.label());
+                }
+            }
+            return Ok(());
         }
-        for _ in 0..config.num_fighters {
-            ants.push(Ant::new(AntsType::FIGHTER));
+
+        // Batch : N parties indépendantes, seules leurs statistiques sont affichées
+        if config.runs > 1 {
+            let report = batch::run_batch(&config, config.runs, |config| {
+                build_manager(config, map.as_ref())
+            })
+            .unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            });
+            report.print();
+            if let Some(path) = &config.runs_output {
+                if let Err(e) = report.save_csv(path) {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+            return Ok(());
         }
 
-        let mut manager = AntsGameManager::new_game_mode_random(
-            config.grid_width,
-            config.grid_height,
-            ants,
-            config.clone(),
-        );
+        // Ensemble : K parties qui mettent leurs Q-tables en commun, le cerveau sauvegardé
+        // est la table commune
+        if config.ensemble > 1 {
+            let outcome =
+                ensemble::run_ensemble(&config, |config| build_manager(config, map.as_ref()))
+                    .unwrap_or_else(|e| {
+                        eprintln!("{}", e);
+                        std::process::exit(1);
+                    });
+            println!(
+                "Ensemble de {} parties, {} mises en commun des Q-tables ({})",
+                config.ensemble,
+                outcome.syncs,
+                config.ensemble_merge.name()
+            );
+            outcome.report.print();
+            if let Some(path) = &config.runs_output {
+                if let Err(e) = outcome.report.save_csv(path) {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                }
+            }
+            if let Some(path) = &config.save_brain {
+                if let Err(e) = outcome.consensus.export_brain(path) {
+                    eprintln!("Erreur de sauvegarde du cerveau: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            if let Some(prefix) = &config.dump_qtable {
+                if let Err(e) = outcome.consensus.dump_qtables(prefix) {
+                    eprintln!("Erreur d'export des Q-tables: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return Ok(());
+        }
+
+        // Partie reprise d'un checkpoint (avec sa configuration), sinon nouvelle partie
+        let mut manager = match &config.resume_from {
+            Some(path) => AntsGameManager::load_checkpoint(path),
+            None => build_manager(config.clone(), map.as_ref()),
+        }
+        .unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        });
+
+        // Métriques Prometheus servies pendant toute la partie
+        if let Some(port) = config.metrics_port {
+            let exporter = MetricsExporter::serve(port).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            });
+            manager.add_observer(Box::new(exporter));
+        }
 
-        let mut tick = 0;
-        while tick < config.max_ticks {
-            manager.game_step();
-            tick += 1;
-            if manager.is_game_finished() {
-                break;
+        if config.record_file.is_some() {
+            if let Err(e) = manager.start_recording() {
+                eprintln!("Erreur d'enregistrement: {}", e);
+                std::process::exit(1);
+            }
+        }
+
+        let mut runner = SimulationRunner::new(manager)
+            .with_max_ticks(config.max_ticks)
+            .with_checkpoints(config.checkpoint_every as u64, &config.checkpoint_file);
+        if config.realtime {
+            runner = runner.with_tick_rate(config.tick_rate);
+        }
+        // Diffusion de l'état aux visualiseurs externes
+        if let Some(port) = config.serve_ws {
+            let mut streamer = StateStreamer::serve(port, config.ws_interval).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            });
+            runner.on_step(move |manager| streamer.publish(manager));
+        }
+        // Images du plateau ; le GIF est terminé quand le runner libère ses hooks
+        if let Some(path) = &config.render_frames {
+            let mut renderer =
+                FrameRenderer::new(path, config.frame_interval).unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    std::process::exit(1);
+                });
+            runner.on_step(move |manager| renderer.capture(manager));
+        }
+        let animation = report_animation(&mut runner, &config);
+        // En mode terminal, la simulation s'arrête quand l'utilisateur quitte
+        let summary = if config.use_tui {
+            run_tui(&mut runner);
+            runner.summary()
+        } else {
+            run_headless(&mut runner, config.watch_interval)
+        };
+        if let Some(path) = &config.summary_file {
+            if let Err(e) = runner.report().save(path) {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        if let Some(path) = &config.report_file {
+            write_report(&mut runner, path, animation);
+        }
+        if let Some(path) = &config.leaderboard_file {
+            record_score(&runner, path, quiet);
+        }
+        if !quiet {
+            println!("{}", summary.ticks);
+            if let Some(reason) = summary.end_reason {
+                println!("Fin de partie: {}", reason.label());
+            }
+            if let Some(winner) = summary.winner {
+                println!("Colonie gagnante: {}", Colony::label(winner));
+            }
+        }
+        if let Some(length) = summary.activity.mean_trip_length().filter(|_| !quiet) {
+            let optimal = summary
+                .optimal_round_trip
+                .map_or_else(|| "?".to_string(), |cells| cells.to_string());
+            println!(
+                "Aller-retour moyen: {:.1} cases (optimal {}, regret {:.1})",
+                length,
+                optimal,
+                summary.activity.trip_regret().unwrap_or_default()
+            );
+        }
+        let mut manager = runner.into_manager();
+
+        if let Err(e) = manager.flush_metrics() {
+            tracing::error!("Erreur d'export des métriques: {}", e);
+        }
+
+        if let Some(path) = &config.record_file {
+            if let Err(e) = manager.save_recording(path) {
+                eprintln!("Erreur de sauvegarde du replay: {}", e);
+                std::process::exit(1);
+            }
+        }
+
+        if let Some(path) = &config.save_brain {
+            if let Err(e) = manager.export_brain(path) {
+                eprintln!("Erreur de sauvegarde du cerveau: {}", e);
+                std::process::exit(1);
+            }
+        }
+
+        if let Some(prefix) = &config.dump_qtable {
+            if let Err(e) = manager.dump_qtables(prefix) {
+                eprintln!("Erreur d'export des Q-tables: {}", e);
+                std::process::exit(1);
             }
         }
-        println!("{}", tick);
         Ok(())
     }
 }
+
+// Éditeur de carte sur `map_file` : chargé s'il existe, sinon carte vierge sauvegardée sous ce nom
+#[cfg(not(target_arch = "wasm32"))]
+fn run_editor(config: SimulationConfig) -> Result<(), eframe::Error> {
+    let editor = match &config.map_file {
+        Some(path) if std::path::Path::new(path).exists() => MapEditor::load_from_file(path)
+            .unwrap_or_else(|e| {
+                eprintln!("Erreur de chargement de la carte: {}", e);
+                std::process::exit(1);
+            }),
+        Some(path) => {
+            let mut editor = MapEditor::new(config.grid_width, config.grid_height);
+            editor.file_path = path.clone();
+            editor
+        }
+        None => MapEditor::new(config.grid_width, config.grid_height),
+    };
+    eframe::run_native(
+        "Ant Simulator",
+        eframe::NativeOptions::default(),
+        Box::new(move |cc| {
+            Ok(Box::new(
+                Interface::new_with_editor(editor, config).with_storage(cc.storage),
+            ))
+        }),
+    )
+}
+
+// Simulation sans affichage ; avec --watch, la grille est redessinée tous les `watch_interval` ticks
+// Nouvelle partie sur la carte chargée (sinon aléatoire), avec le cerveau à reprendre
+#[cfg(not(target_arch = "wasm32"))]
+fn build_manager(
+    config: SimulationConfig,
+    map: Option<&MapEditor>,
+) -> Result<AntsGameManager, String> {
+    let created = match map {
+        Some(editor) => AntsGameManager::new(
+            editor.width,
+            editor.height,
+            editor.to_tiles(),
+            vec![],
+            config.clone(),
+        )
+        .map(|mut manager| {
+            manager.set_ants(Ant::colony_from_config(&config));
+            manager
+        }),
+        None => AntsGameManager::from_config(config.clone()),
+    };
+    let mut manager = created.map_err(|e| format!("Erreur de création de la partie: {}", e))?;
+
+    // Reprendre l'entraînement d'une colonie précédente
+    if let Some(path) = &config.load_brain {
+        manager
+            .import_brain(path)
+            .map_err(|e| format!("Erreur de chargement du cerveau: {}", e))?;
+    }
+    Ok(manager)
+}
+
+// Animation de la partie gardée en mémoire pour le rapport HTML (--report-gif)
+#[cfg(not(target_arch = "wasm32"))]
+fn report_animation(runner: &mut SimulationRunner, config: &SimulationConfig) -> Option<GifBuffer> {
+    if config.report_file.is_none() || !config.report_gif {
+        return None;
+    }
+    let (mut renderer, buffer) =
+        FrameRenderer::in_memory(config.frame_interval).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        });
+    runner.on_step(move |manager| renderer.capture(manager));
+    Some(buffer)
+}
+
+// Rapport HTML de la partie ; les hooks sont libérés d'abord pour terminer l'animation
+#[cfg(not(target_arch = "wasm32"))]
+fn write_report(runner: &mut SimulationRunner, path: &str, animation: Option<GifBuffer>) {
+    runner.clear_step_hooks();
+    let animation = animation.map(|buffer| buffer.bytes());
+    if let Err(e) = html_report::write_report(
+        path,
+        &runner.report(),
+        runner.manager(),
+        animation.as_deref(),
+    ) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+// Inscrit la partie au classement des meilleurs scores de sa carte (--leaderboard)
+#[cfg(not(target_arch = "wasm32"))]
+fn record_score(runner: &SimulationRunner, path: &str, quiet: bool) {
+    let scores = RunScores::of(runner.manager(), runner.report().end_reason);
+    let rule = ScoreRule::from_config(runner.manager().config());
+    let mut leaderboard = Leaderboard::load(path).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    });
+    let rank = leaderboard.record(&scores, &rule);
+    if let Err(e) = leaderboard.save(path) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+    if !quiet {
+        let entries = leaderboard.map(scores.map).map_or(0, |map| map.runs.len());
+        println!(
+            "Score: {:.1} (rang {} sur {} pour cette carte)",
+            rule.score(&scores.run),
+            rank,
+            entries
+        );
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn run_headless(runner: &mut SimulationRunner, watch_interval: u32) -> SimulationSummary {
+    if watch_interval == 0 {
+        return runner.run_to_completion();
+    }
+
+    print!("\x1b[2J");
+    while !runner.is_done() {
+        runner.run_ticks(watch_interval as u64);
+        let manager = runner.manager();
+        let metrics = manager.metrics();
+        let pheromones = (manager.pheromones_food(), manager.config().q_display_scale);
+        let frame = manager
+            .grid()
+            .render_ascii(manager.ants(), Some(pheromones), true);
+        // Revenir en haut à gauche plutôt qu'effacer l'écran évite le scintillement
+        print!("\x1b[H{}", frame);
+        println!(
+            "Tick {} | livrées {} | morts {} | fourmis actives {}\x1b[K",
+            manager.current_tick_index(),
+            metrics.total_food_delivered,
+            metrics.total_deaths,
+            manager
+                .ants()
+                .iter()
+                .filter(|a| a.position.is_some())
+                .count()
+        );
+        let _ = std::io::stdout().flush();
+    }
+    runner.summary()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn run_tui(runner: &mut SimulationRunner) {
+    if let Err(e) = ants_project::tui::run(runner) {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
+
+// Point d'entrée web (trunk) : l'interface est rendue dans le canvas `ants_canvas` d'index.html.
+// Pas d'arguments ni de fichiers dans le navigateur : on part de la configuration par défaut.
+#[cfg(target_arch = "wasm32")]
+fn main() {
+    use eframe::wasm_bindgen::JsCast;
+
+    let config = SimulationConfig::default();
+    event_log::install(config.log_level, false);
+
+    wasm_bindgen_futures::spawn_local(async move {
+        let canvas = eframe::web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.get_element_by_id("ants_canvas"))
+            .and_then(|element| {
+                element
+                    .dyn_into::<eframe::web_sys::HtmlCanvasElement>()
+                    .ok()
+            })
+            .expect("Canvas #ants_canvas introuvable dans la page");
+
+        let result = eframe::WebRunner::new()
+            .start(
+                canvas,
+                eframe::WebOptions::default(),
+                Box::new(move |cc| {
+                    Ok(Box::new(
+                        Interface::new_with_config(config).with_storage(cc.storage),
+                    ))
+                }),
+            )
+            .await;
+        if let Err(e) = result {
+            eframe::web_sys::console::error_1(&e);
+        }
+    });
+}
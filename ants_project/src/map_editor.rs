@@ -1,48 +1,163 @@
-use crate::tile::{Tile, TileType};
+use crate::camera::Camera;
+use crate::i18n::{tr, trf};
+use crate::map_generator::{self, Generator, GeneratorSettings};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::map_preview::{MapPreview, PreviewLayer, PREVIEW_TICKS};
+use crate::theme;
+use crate::tile::{
+    default_food_value, FoodRegrowth, Tile, TileType, DEFAULT_MUD_SLOW_FACTOR, SUGAR_VALUE,
+};
 use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use web_time::Duration;
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub enum MapEditorTileType {
     Default,
     Wall,
     Nest,
+    // Nid de la colonie adverse (mode multi-colonies)
+    NestB,
     FoodSource,
     DeathZone,
+    Mud,
+    // Entrée de tunnel, sa sortie se choisit dans les réglages de la case
+    Tunnel,
+}
+
+/// Réglages d'une case de nourriture, de nid, de boue ou de tunnel, modifiables case par
+/// case dans l'éditeur
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TileProperties {
+    Food {
+        amount: u32,
+        // Valeur de chaque unité (1 = sucre, 5 = protéines)
+        #[serde(default = "default_food_value")]
+        value: u32,
+        // Repousse propre à la source (None = celle de la configuration)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        regrowth: Option<FoodRegrowth>,
+    },
+    Nest {
+        explorer_capacity: u32,
+        picker_capacity: u32,
+        fighter_capacity: u32,
+    },
+    Mud {
+        slow_factor: u32,
+    },
+    Tunnel {
+        // None tant que la sortie n'a pas été choisie (carte invalide)
+        exit: Option<(u32, u32)>,
+    },
 }
 
 impl MapEditorTileType {
+    /// Tuile de jeu avec les réglages par défaut de la palette
     pub fn to_tile_type(&self) -> TileType {
+        self.to_tile_type_with(self.default_properties())
+    }
+
+    /// Réglages d'une case fraîchement peinte (None pour les cases sans réglage)
+    pub fn default_properties(&self) -> Option<TileProperties> {
         match self {
-            MapEditorTileType::Default => TileType::Default,
-            MapEditorTileType::Wall => TileType::Wall,
-            MapEditorTileType::Nest => TileType::Nest {
-                stored_food: 0,
+            MapEditorTileType::Nest | MapEditorTileType::NestB => Some(TileProperties::Nest {
                 explorer_capacity: 10,
                 picker_capacity: 10,
                 fighter_capacity: 10,
+            }),
+            MapEditorTileType::FoodSource => Some(TileProperties::Food {
+                amount: 1000,
+                value: SUGAR_VALUE,
+                regrowth: None,
+            }),
+            MapEditorTileType::Mud => Some(TileProperties::Mud {
+                slow_factor: DEFAULT_MUD_SLOW_FACTOR,
+            }),
+            MapEditorTileType::Tunnel => Some(TileProperties::Tunnel { exit: None }),
+            _ => None,
+        }
+    }
+
+    /// Tuile de jeu avec les réglages donnés ; des réglages d'un autre genre de case sont ignorés
+    pub fn to_tile_type_with(&self, properties: Option<TileProperties>) -> TileType {
+        let properties = match (self.default_properties(), properties) {
+            (Some(default), Some(own))
+                if std::mem::discriminant(&default) == std::mem::discriminant(&own) =>
+            {
+                Some(own)
+            }
+            (default, _) => default,
+        };
+        match (self, properties) {
+            (
+                MapEditorTileType::Nest | MapEditorTileType::NestB,
+                Some(TileProperties::Nest {
+                    explorer_capacity,
+                    picker_capacity,
+                    fighter_capacity,
+                }),
+            ) => TileType::Nest {
+                colony: usize::from(*self == MapEditorTileType::NestB),
+                stored_food: 0,
+                explorer_capacity,
+                picker_capacity,
+                fighter_capacity,
             },
-            MapEditorTileType::FoodSource => TileType::FoodSource { amount: 1000 },
-            MapEditorTileType::DeathZone => TileType::DeathZone,
+            (MapEditorTileType::FoodSource, Some(TileProperties::Food { amount, value, .. })) => {
+                TileType::FoodSource { amount, value }
+            }
+            (MapEditorTileType::Mud, Some(TileProperties::Mud { slow_factor })) => {
+                TileType::Mud { slow_factor }
+            }
+            // Un tunnel sans sortie reste une case vide
+            (MapEditorTileType::Tunnel, Some(TileProperties::Tunnel { exit: Some(exit) })) => {
+                TileType::Tunnel { exit }
+            }
+            (MapEditorTileType::Wall, _) => TileType::Wall,
+            (MapEditorTileType::DeathZone, _) => TileType::DeathZone,
+            _ => TileType::Default,
+        }
+    }
+
+    /// Type posé sur l'image miroir d'une case : un nid devient celui de l'autre colonie,
+    /// pour que chaque colonie ait la même carte vue de son nid
+    pub fn mirrored(&self) -> MapEditorTileType {
+        match self {
+            MapEditorTileType::Nest => MapEditorTileType::NestB,
+            MapEditorTileType::NestB => MapEditorTileType::Nest,
+            other => *other,
         }
     }
 
     pub fn label(&self) -> &'static str {
         match self {
-            MapEditorTileType::Default => "Vide",
-            MapEditorTileType::Wall => "Mur",
-            MapEditorTileType::Nest => "Nid",
-            MapEditorTileType::FoodSource => "Nourriture",
-            MapEditorTileType::DeathZone => "Danger",
+            MapEditorTileType::Default => tr("editor.tile_default"),
+            MapEditorTileType::Wall => tr("tile.wall"),
+            MapEditorTileType::Nest => tr("editor.tile_nest_a"),
+            MapEditorTileType::NestB => tr("editor.tile_nest_b"),
+            MapEditorTileType::FoodSource => tr("editor.tile_food"),
+            MapEditorTileType::DeathZone => tr("editor.tile_danger"),
+            MapEditorTileType::Mud => tr("tile.mud"),
+            MapEditorTileType::Tunnel => tr("editor.tile_tunnel"),
         }
     }
 
+    /// Couleur de la case dans le thème courant
     pub fn color(&self) -> egui::Color32 {
+        let palette = theme::palette();
         match self {
-            MapEditorTileType::Default => egui::Color32::from_gray(40), // Un peu plus foncé
-            MapEditorTileType::Wall => egui::Color32::GRAY,
-            MapEditorTileType::Nest => egui::Color32::GOLD,
-            MapEditorTileType::FoodSource => egui::Color32::GREEN,
-            MapEditorTileType::DeathZone => egui::Color32::from_rgb(139, 0, 0),
+            MapEditorTileType::Default => palette.editor_empty,
+            MapEditorTileType::Wall => palette.wall,
+            MapEditorTileType::Nest => palette.colonies[0],
+            MapEditorTileType::NestB => palette.colonies[1],
+            MapEditorTileType::FoodSource => palette.food,
+            MapEditorTileType::DeathZone => palette.editor_death_zone,
+            MapEditorTileType::Mud => palette.editor_mud,
+            MapEditorTileType::Tunnel => palette.editor_tunnel,
         }
     }
 
@@ -51,20 +166,183 @@ impl MapEditorTileType {
             MapEditorTileType::Default,
             MapEditorTileType::Wall,
             MapEditorTileType::Nest,
+            MapEditorTileType::NestB,
             MapEditorTileType::FoodSource,
             MapEditorTileType::DeathZone,
+            MapEditorTileType::Mud,
+            MapEditorTileType::Tunnel,
         ]
         .iter()
         .copied()
     }
 }
 
+/// Outil de dessin de l'éditeur
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BrushTool {
+    /// Pinceau carré de `brush_size` cases, au clic ou en glissant
+    Pencil,
+    /// Rectangle plein entre le début et la fin du glisser
+    Rectangle,
+    /// Segment du début à la fin du glisser, de l'épaisseur du pinceau
+    Line,
+    /// Remplit la zone de même type autour de la case cliquée
+    Fill,
+}
+
+impl BrushTool {
+    pub fn label(&self) -> &'static str {
+        match self {
+            BrushTool::Pencil => tr("editor.pencil"),
+            BrushTool::Rectangle => tr("editor.rectangle"),
+            BrushTool::Line => tr("editor.line"),
+            BrushTool::Fill => tr("editor.fill"),
+        }
+    }
+
+    pub fn all() -> [BrushTool; 4] {
+        [
+            BrushTool::Pencil,
+            BrushTool::Rectangle,
+            BrushTool::Line,
+            BrushTool::Fill,
+        ]
+    }
+}
+
+/// Symétries appliquées automatiquement à chaque case peinte
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Symmetry {
+    /// Miroir gauche / droite (axe vertical au milieu de la carte)
+    pub horizontal: bool,
+    /// Miroir haut / bas (axe horizontal au milieu de la carte)
+    pub vertical: bool,
+    /// Rotation d'un demi-tour autour du centre
+    pub rotational: bool,
+}
+
+/// Plus grand côté proposé par le redimensionnement
+pub const MAX_MAP_SIDE: u32 = 500;
+
+/// Taille maximale du pinceau (côté du carré, en cases)
+pub const MAX_BRUSH_SIZE: u32 = 9;
+
+// Nombre de modifications gardées pour l'annulation
+const MAX_UNDO: usize = 200;
+
+// Rafraîchissement de la barre de progression pendant un aperçu
+#[cfg(not(target_arch = "wasm32"))]
+const PREVIEW_REFRESH: Duration = Duration::from_millis(250);
+
+// Contenu d'une case : son type et ses réglages s'ils diffèrent de ceux de la palette
+type CellState = (MapEditorTileType, Option<TileProperties>);
+
+// Changement d'une case, suffisant pour l'annuler comme pour le refaire
+#[derive(Clone, Copy, Debug)]
+struct TileChange {
+    x: u32,
+    y: u32,
+    before: CellState,
+    after: CellState,
+}
+
+// Carte entière, gardée par l'historique quand ses dimensions changent
+#[derive(Clone)]
+struct MapLayout {
+    width: u32,
+    height: u32,
+    tiles: Vec<Vec<MapEditorTileType>>,
+    properties: HashMap<(u32, u32), TileProperties>,
+}
+
+// Entrée de l'historique : les cases changées, ou la carte avant et après une rotation,
+// un miroir ou un redimensionnement (les coordonnées des cases n'y survivent pas)
+enum Edit {
+    Tiles(Vec<TileChange>),
+    Reshape {
+        before: Box<MapLayout>,
+        after: Box<MapLayout>,
+    },
+}
+
+/// Transformations de la carte entière (barre d'outils)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Reshape {
+    /// Quart de tour dans le sens des aiguilles d'une montre
+    Rotate90,
+    /// Gauche et droite échangées
+    MirrorHorizontal,
+    /// Haut et bas échangés
+    MirrorVertical,
+}
+
 pub struct MapEditor {
     pub width: u32,
     pub height: u32,
     pub tiles: Vec<Vec<MapEditorTileType>>,
     pub selected_tile_type: MapEditorTileType,
+    pub tool: BrushTool,
+    pub brush_size: u32,
+    // Réglages du menu "Générer"
+    pub generator: GeneratorSettings,
+    pub symmetry: Symmetry,
+    // Réglages du menu "Taille"
+    pub resize_to: (u32, u32),
+    pub resize_anchor: egui::Align2,
+    // Réglages des cases (nourriture, nid, boue, tunnel) qui ne gardent pas ceux de la palette
+    properties: HashMap<(u32, u32), TileProperties>,
+    // Case dont les réglages sont affichés (clic droit)
+    pub selected_cell: Option<(u32, u32)>,
+    // Le prochain clic sur la carte choisit la sortie du tunnel sélectionné
+    pub picking_exit: bool,
+    // Assombrir les cases qu'aucun nid ne peut atteindre
+    pub show_unreachable: bool,
     pub nest_count: u32,
+    // Nid B facultatif : sa présence active le mode multi-colonies
+    pub nest_b_count: u32,
+    // Mode multi-nids : chaque colonie peut avoir plusieurs cases de nid
+    pub multi_nest: bool,
+    // Chemin utilisé par les boutons Sauvegarder / Charger
+    pub file_path: String,
+    pub file_status: Option<String>,
+    // Zoom et déplacement du canevas
+    pub camera: Camera,
+    // Modifications annulables : un coup de pinceau ou un remplissage forme une seule entrée
+    undo_stack: Vec<Edit>,
+    redo_stack: Vec<Edit>,
+    // Cases changées depuis le début de la modification en cours
+    pending: Vec<TileChange>,
+    // Case de départ et case courante du glisser en cours (rectangle, ligne, pinceau)
+    drag: Option<((u32, u32), (u32, u32))>,
+    // Partie d'aperçu en cours ou jouée, et son calque superposé à la grille
+    #[cfg(not(target_arch = "wasm32"))]
+    pub preview: Option<MapPreview>,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub preview_layer: PreviewLayer,
+    #[cfg(not(target_arch = "wasm32"))]
+    pub show_preview: bool,
+}
+
+/// Carte sauvegardée (fichier JSON de l'éditeur, ou section `[map]` d'un scénario)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MapFile {
+    width: u32,
+    height: u32,
+    tiles: Vec<Vec<MapEditorTileType>>,
+    // Réglages propres à certaines cases (absent des cartes plus anciennes)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    properties: Vec<CellProperties>,
+    // Plusieurs nids par colonie autorisés (absent des cartes à un nid par colonie)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    multi_nest: bool,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct CellProperties {
+    x: u32,
+    y: u32,
+    #[serde(flatten)]
+    properties: TileProperties,
 }
 
 impl MapEditor {
@@ -75,11 +353,409 @@ impl MapEditor {
             height,
             tiles,
             selected_tile_type: MapEditorTileType::Wall, // Wall par défaut, plus pratique
+            tool: BrushTool::Pencil,
+            brush_size: 1,
+            generator: GeneratorSettings::default(),
+            symmetry: Symmetry::default(),
+            resize_to: (width, height),
+            resize_anchor: egui::Align2::LEFT_TOP,
+            properties: HashMap::new(),
+            selected_cell: None,
+            picking_exit: false,
+            show_unreachable: false,
             nest_count: 0,
+            nest_b_count: 0,
+            multi_nest: false,
+            file_path: "map.json".to_string(),
+            file_status: None,
+            camera: Camera::default(),
+            undo_stack: Vec::new(),
+            redo_stack: Vec::new(),
+            pending: Vec::new(),
+            drag: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            preview: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            preview_layer: PreviewLayer::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            show_preview: true,
         }
     }
 
+    pub fn save_to_file(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.to_json()?).map_err(|e| format!("Impossible d'écrire {}: {}", path, e))
+    }
+
+    pub fn load_from_file(path: &str) -> Result<Self, String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Impossible de lire {}: {}", path, e))?;
+        let mut editor =
+            Self::from_json(&content).map_err(|e| format!("Carte invalide {}: {}", path, e))?;
+        editor.file_path = path.to_string();
+        Ok(editor)
+    }
+
+    // Carte au format JSON, sans passer par le disque (utilisé tel quel sur le web)
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(&self.to_map_file())
+            .map_err(|e| format!("Impossible de sérialiser la carte: {}", e))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let map: MapFile = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        Self::from_map_file(map)
+    }
+
+    pub fn to_map_file(&self) -> MapFile {
+        MapFile {
+            width: self.width,
+            height: self.height,
+            tiles: self.tiles.clone(),
+            properties: self.cell_properties(),
+            multi_nest: self.multi_nest,
+        }
+    }
+
+    pub fn from_map_file(map: MapFile) -> Result<Self, String> {
+        // Vérifier que les dimensions annoncées correspondent aux tuiles
+        if map.tiles.len() != map.height as usize
+            || map.tiles.iter().any(|row| row.len() != map.width as usize)
+        {
+            return Err("dimensions incohérentes".to_string());
+        }
+
+        // Une carte chargée part d'un historique vide
+        let mut editor = MapEditor::new(map.width, map.height);
+        editor.multi_nest = map.multi_nest;
+        for (y, row) in map.tiles.iter().enumerate() {
+            for (x, &tile_type) in row.iter().enumerate() {
+                editor.write_tile(x as u32, y as u32, tile_type);
+            }
+        }
+        for cell in map.properties {
+            if cell.x >= map.width || cell.y >= map.height {
+                return Err(format!(
+                    "réglages hors de la carte ({}, {})",
+                    cell.x, cell.y
+                ));
+            }
+            editor.properties.insert((cell.x, cell.y), cell.properties);
+        }
+        Ok(editor)
+    }
+
+    // Réglages propres aux cases, dans l'ordre de lecture de la carte
+    fn cell_properties(&self) -> Vec<CellProperties> {
+        let mut cells: Vec<CellProperties> = self
+            .properties
+            .iter()
+            .map(|(&(x, y), &properties)| CellProperties { x, y, properties })
+            .collect();
+        cells.sort_by_key(|cell| (cell.y, cell.x));
+        cells
+    }
+
+    /// Réglages effectifs d'une case : les siens, sinon ceux de la palette pour son type
+    pub fn properties(&self, x: u32, y: u32) -> Option<TileProperties> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let tile_type = self.tiles[y as usize][x as usize];
+        self.properties
+            .get(&(x, y))
+            .copied()
+            .or(tile_type.default_properties())
+    }
+
+    /// Change les réglages d'une case de nourriture, de nid, de boue ou de tunnel. Les retouches successives
+    /// d'une même case forment une seule modification pour l'annulation
+    pub fn set_properties(&mut self, x: u32, y: u32, properties: TileProperties) {
+        let Some(current) = self.properties(x, y) else {
+            return;
+        };
+        // Des réglages d'un autre genre de case n'auraient aucun effet
+        if current == properties
+            || std::mem::discriminant(&current) != std::mem::discriminant(&properties)
+        {
+            return;
+        }
+        let tile_type = self.tiles[y as usize][x as usize];
+        // Revenir aux réglages de la palette efface simplement ceux de la case
+        let stored = Some(properties).filter(|&p| Some(p) != tile_type.default_properties());
+        let after = (tile_type, stored);
+
+        // Retouche précédente de la même case, prolongée plutôt que doublée
+        let previous = match self.undo_stack.last_mut() {
+            Some(Edit::Tiles(changes)) => changes.as_mut_slice(),
+            _ => &mut [],
+        };
+        let previous = match previous {
+            [last]
+                if self.pending.is_empty()
+                    && self.redo_stack.is_empty()
+                    && (last.x, last.y) == (x, y)
+                    && last.before.0 == tile_type
+                    && last.after.0 == tile_type =>
+            {
+                Some(last)
+            }
+            _ => None,
+        };
+        match previous {
+            Some(last) => last.after = after,
+            None => self.pending.push(TileChange {
+                x,
+                y,
+                before: (tile_type, self.properties.get(&(x, y)).copied()),
+                after,
+            }),
+        }
+        self.write_cell(x, y, after);
+    }
+
+    /// Change une case et ses images par les symétries actives ; les changements rejoignent
+    /// la modification en cours (voir [`Self::commit_edit`])
     pub fn set_tile(&mut self, x: u32, y: u32, tile_type: MapEditorTileType) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        for (mx, my) in self.symmetric_cells((x, y)) {
+            if (mx, my) != (x, y) {
+                self.set_single_tile(mx, my, tile_type.mirrored());
+            }
+        }
+        self.set_single_tile(x, y, tile_type);
+    }
+
+    /// Case et ses images par les symétries actives (sans doublon)
+    pub fn symmetric_cells(&self, cell: (u32, u32)) -> Vec<(u32, u32)> {
+        let (w, h) = (self.width - 1, self.height - 1);
+        let mut cells = vec![cell];
+        // (symétrie active, retourne x, retourne y)
+        let images = [
+            (self.symmetry.horizontal, true, false),
+            (self.symmetry.vertical, false, true),
+            (self.symmetry.rotational, true, true),
+        ];
+        for (active, flip_x, flip_y) in images {
+            if !active {
+                continue;
+            }
+            for i in 0..cells.len() {
+                let (x, y) = cells[i];
+                let image = (
+                    if flip_x { w - x } else { x },
+                    if flip_y { h - y } else { y },
+                );
+                if !cells.contains(&image) {
+                    cells.push(image);
+                }
+            }
+        }
+        cells
+    }
+
+    fn set_single_tile(&mut self, x: u32, y: u32, tile_type: MapEditorTileType) {
+        let before = self.tiles[y as usize][x as usize];
+        if before == tile_type {
+            return;
+        }
+        // Une case repeinte reprend les réglages de la palette
+        let after = (tile_type, None);
+        self.pending.push(TileChange {
+            x,
+            y,
+            before: (before, self.properties.get(&(x, y)).copied()),
+            after,
+        });
+        self.write_cell(x, y, after);
+    }
+
+    /// Termine la modification en cours : elle sera annulée d'un bloc par [`Self::undo`]
+    pub fn commit_edit(&mut self) {
+        if self.pending.is_empty() {
+            return;
+        }
+        let changes = std::mem::take(&mut self.pending);
+        self.push_edit(Edit::Tiles(changes));
+    }
+
+    fn push_edit(&mut self, edit: Edit) {
+        if self.undo_stack.len() == MAX_UNDO {
+            self.undo_stack.remove(0);
+        }
+        self.undo_stack.push(edit);
+        self.redo_stack.clear();
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.pending.is_empty() || !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.pending.is_empty() && !self.redo_stack.is_empty()
+    }
+
+    /// Annule la dernière modification (y compris un coup de pinceau encore en cours)
+    pub fn undo(&mut self) {
+        self.commit_edit();
+        if let Some(edit) = self.undo_stack.pop() {
+            match &edit {
+                Edit::Tiles(changes) => {
+                    for change in changes.iter().rev() {
+                        self.write_cell(change.x, change.y, change.before);
+                    }
+                }
+                Edit::Reshape { before, .. } => self.set_layout(before),
+            }
+            self.redo_stack.push(edit);
+        }
+    }
+
+    /// Refait la dernière modification annulée
+    pub fn redo(&mut self) {
+        self.commit_edit();
+        if let Some(edit) = self.redo_stack.pop() {
+            match &edit {
+                Edit::Tiles(changes) => {
+                    for change in changes {
+                        self.write_cell(change.x, change.y, change.after);
+                    }
+                }
+                Edit::Reshape { after, .. } => self.set_layout(after),
+            }
+            self.undo_stack.push(edit);
+        }
+    }
+
+    /// Tourne ou retourne toute la carte, nids, nourriture et réglages compris
+    pub fn reshape(&mut self, reshape: Reshape) {
+        let (w, h) = (self.width, self.height);
+        match reshape {
+            // La case (x, y) de la nouvelle carte vient de l'ancienne (y, h - 1 - x)
+            Reshape::Rotate90 => self.remap(h, w, |x, y| Some((y, h - 1 - x))),
+            Reshape::MirrorHorizontal => self.remap(w, h, |x, y| Some((w - 1 - x, y))),
+            Reshape::MirrorVertical => self.remap(w, h, |x, y| Some((x, h - 1 - y))),
+        }
+    }
+
+    /// Tourne toute la carte d'un quart de tour dans le sens des aiguilles d'une montre
+    pub fn rotate_90(&mut self) {
+        self.reshape(Reshape::Rotate90);
+    }
+
+    /// Échange la gauche et la droite de la carte
+    pub fn mirror_horizontal(&mut self) {
+        self.reshape(Reshape::MirrorHorizontal);
+    }
+
+    /// Échange le haut et le bas de la carte
+    pub fn mirror_vertical(&mut self) {
+        self.reshape(Reshape::MirrorVertical);
+    }
+
+    /// Change les dimensions de la carte. `anchor` désigne le bord (ou le centre) qui reste
+    /// en place ; les cases qui sortent de la nouvelle carte sont perdues, les nouvelles
+    /// sont vides
+    pub fn resize(&mut self, width: u32, height: u32, anchor: egui::Align2) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        let offset = |old: u32, new: u32, align: egui::Align| -> i64 {
+            match align {
+                egui::Align::Min => 0,
+                egui::Align::Center => (new as i64 - old as i64) / 2,
+                egui::Align::Max => new as i64 - old as i64,
+            }
+        };
+        let dx = offset(self.width, width, anchor.x());
+        let dy = offset(self.height, height, anchor.y());
+        let (old_w, old_h) = (self.width as i64, self.height as i64);
+        self.remap(width, height, |x, y| {
+            let (ox, oy) = (x as i64 - dx, y as i64 - dy);
+            (ox >= 0 && oy >= 0 && ox < old_w && oy < old_h).then_some((ox as u32, oy as u32))
+        });
+    }
+
+    // Construit une carte `width` x `height` dont chaque case reprend celle que `source`
+    // désigne dans la carte actuelle (vide si None), et l'enregistre dans l'historique
+    fn remap(&mut self, width: u32, height: u32, source: impl Fn(u32, u32) -> Option<(u32, u32)>) {
+        self.commit_edit();
+        let before = self.layout();
+        let mut after = MapLayout {
+            width,
+            height,
+            tiles: vec![vec![MapEditorTileType::Default; width as usize]; height as usize],
+            properties: HashMap::new(),
+        };
+        // Nouvelle position de chaque case conservée, pour déplacer les sorties de tunnel
+        let mut moved = HashMap::new();
+        for y in 0..height {
+            for x in 0..width {
+                if let Some((ox, oy)) = source(x, y) {
+                    after.tiles[y as usize][x as usize] = before.tiles[oy as usize][ox as usize];
+                    if let Some(&properties) = before.properties.get(&(ox, oy)) {
+                        after.properties.insert((x, y), properties);
+                    }
+                    moved.insert((ox, oy), (x, y));
+                }
+            }
+        }
+        for properties in after.properties.values_mut() {
+            if let TileProperties::Tunnel { exit } = properties {
+                *exit = exit.and_then(|old| moved.get(&old).copied());
+            }
+        }
+        self.set_layout(&after);
+        self.push_edit(Edit::Reshape {
+            before: Box::new(before),
+            after: Box::new(after),
+        });
+    }
+
+    fn layout(&self) -> MapLayout {
+        MapLayout {
+            width: self.width,
+            height: self.height,
+            tiles: self.tiles.clone(),
+            properties: self.properties.clone(),
+        }
+    }
+
+    // Remplace toute la carte ; la sélection et la vue, liées aux anciennes dimensions,
+    // sont réinitialisées
+    fn set_layout(&mut self, layout: &MapLayout) {
+        self.width = layout.width;
+        self.height = layout.height;
+        self.tiles = layout.tiles.clone();
+        self.properties = layout.properties.clone();
+        let count = |tile_type| {
+            self.tiles
+                .iter()
+                .flatten()
+                .filter(|&&t| t == tile_type)
+                .count()
+        };
+        self.nest_count = count(MapEditorTileType::Nest) as u32;
+        self.nest_b_count = count(MapEditorTileType::NestB) as u32;
+        self.resize_to = (self.width, self.height);
+        self.selected_cell = None;
+        self.picking_exit = false;
+        self.drag = None;
+        self.camera.reset();
+    }
+
+    // Écrit une case et ses réglages sans passer par l'historique
+    fn write_cell(&mut self, x: u32, y: u32, (tile_type, properties): CellState) {
+        self.write_tile(x, y, tile_type);
+        match properties {
+            Some(properties) => self.properties.insert((x, y), properties),
+            None => self.properties.remove(&(x, y)),
+        };
+    }
+
+    // Écrit une case sans passer par l'historique, en tenant les compteurs de nids à jour
+    fn write_tile(&mut self, x: u32, y: u32, tile_type: MapEditorTileType) {
         if x < self.width && y < self.height {
             let current = self.tiles[y as usize][x as usize];
 
@@ -90,35 +766,177 @@ impl MapEditor {
             if current != MapEditorTileType::Nest && tile_type == MapEditorTileType::Nest {
                 self.nest_count += 1;
             }
+            if current == MapEditorTileType::NestB && tile_type != MapEditorTileType::NestB {
+                self.nest_b_count = self.nest_b_count.saturating_sub(1);
+            }
+            if current != MapEditorTileType::NestB && tile_type == MapEditorTileType::NestB {
+                self.nest_b_count += 1;
+            }
 
             self.tiles[y as usize][x as usize] = tile_type;
         }
     }
 
+    /// Peint une liste de cases (hors grille ignorées)
+    pub fn paint(
+        &mut self,
+        cells: impl IntoIterator<Item = (u32, u32)>,
+        tile_type: MapEditorTileType,
+    ) {
+        for (x, y) in cells {
+            self.set_tile(x, y, tile_type);
+        }
+    }
+
+    /// Cases couvertes par le pinceau centré sur `center`, coupées au bord de la grille
+    pub fn brush_cells(&self, (cx, cy): (u32, u32)) -> Vec<(u32, u32)> {
+        let size = self.brush_size.clamp(1, MAX_BRUSH_SIZE);
+        let (x0, y0) = (
+            cx.saturating_sub((size - 1) / 2),
+            cy.saturating_sub((size - 1) / 2),
+        );
+        let (x1, y1) = (
+            (cx + size / 2).min(self.width.saturating_sub(1)),
+            (cy + size / 2).min(self.height.saturating_sub(1)),
+        );
+        Self::rect_cells((x0, y0), (x1, y1))
+    }
+
+    /// Rectangle plein de coins opposés `a` et `b`
+    pub fn rect_cells(a: (u32, u32), b: (u32, u32)) -> Vec<(u32, u32)> {
+        let (x0, x1) = (a.0.min(b.0), a.0.max(b.0));
+        let (y0, y1) = (a.1.min(b.1), a.1.max(b.1));
+        (y0..=y1)
+            .flat_map(|y| (x0..=x1).map(move |x| (x, y)))
+            .collect()
+    }
+
+    /// Cases traversées par le segment `from` -> `to` (algorithme de Bresenham)
+    pub fn line_cells(from: (u32, u32), to: (u32, u32)) -> Vec<(u32, u32)> {
+        let (mut x, mut y) = (from.0 as i64, from.1 as i64);
+        let (tx, ty) = (to.0 as i64, to.1 as i64);
+        let (dx, dy) = ((tx - x).abs(), -(ty - y).abs());
+        let (sx, sy) = ((tx - x).signum(), (ty - y).signum());
+        let mut err = dx + dy;
+        let mut cells = vec![(x as u32, y as u32)];
+        while (x, y) != (tx, ty) {
+            let e2 = 2 * err;
+            if e2 >= dy {
+                err += dy;
+                x += sx;
+            }
+            if e2 <= dx {
+                err += dx;
+                y += sy;
+            }
+            cells.push((x as u32, y as u32));
+        }
+        cells
+    }
+
+    /// Segment épaissi par le pinceau
+    pub fn stroke_cells(&self, from: (u32, u32), to: (u32, u32)) -> Vec<(u32, u32)> {
+        let mut cells: Vec<(u32, u32)> = Self::line_cells(from, to)
+            .into_iter()
+            .flat_map(|cell| self.brush_cells(cell))
+            .collect();
+        cells.sort_unstable();
+        cells.dedup();
+        cells
+    }
+
+    /// Remplace par `tile_type` la zone connexe (4 voisins) de même type que la case de départ
+    pub fn flood_fill(&mut self, x: u32, y: u32, tile_type: MapEditorTileType) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+        let target = self.tiles[y as usize][x as usize];
+        if target == tile_type {
+            return;
+        }
+        let mut queue = VecDeque::from([(x, y)]);
+        self.set_tile(x, y, tile_type);
+        while let Some((x, y)) = queue.pop_front() {
+            let neighbours = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            for (nx, ny) in neighbours {
+                if nx < self.width
+                    && ny < self.height
+                    && self.tiles[ny as usize][nx as usize] == target
+                {
+                    self.set_tile(nx, ny, tile_type);
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+    }
+
     pub fn to_tiles(&self) -> Vec<Tile> {
         let mut tiles = Vec::new();
         for y in 0..self.height {
             for x in 0..self.width {
-                let tile_type = self.tiles[y as usize][x as usize].to_tile_type();
-                tiles.push(Tile::new(x, y, tile_type, None));
+                let properties = self.properties.get(&(x, y)).copied();
+                let tile_type = self.tiles[y as usize][x as usize].to_tile_type_with(properties);
+                let mut tile = Tile::new(x, y, tile_type, None);
+                if let Some(TileProperties::Food { regrowth, .. }) = properties {
+                    tile.regrowth = regrowth.filter(|_| tile.food_amount().is_some());
+                }
+                tiles.push(tile);
             }
         }
         tiles
     }
 
     pub fn fill_all(&mut self, tile_type: MapEditorTileType) {
+        // Le remplissage s'annule d'un bloc, séparément du coup de pinceau précédent
+        self.commit_edit();
         self.nest_count = 0;
+        self.nest_b_count = 0;
         for y in 0..self.height {
             for x in 0..self.width {
-                if tile_type == MapEditorTileType::Nest {
+                if tile_type == MapEditorTileType::Nest && !self.multi_nest {
                     if self.nest_count == 0 {
                         self.set_tile(x, y, tile_type);
                     }
+                } else if tile_type == MapEditorTileType::NestB && !self.multi_nest {
+                    if self.nest_b_count == 0 {
+                        self.set_tile(x, y, tile_type);
+                    }
                 } else {
                     self.set_tile(x, y, tile_type);
                 }
             }
         }
+        self.commit_edit();
+    }
+
+    /// Remplace les murs et les cases vides par ceux du générateur choisi ; nids, nourriture
+    /// et zones mortelles restent en place. Le tout s'annule d'un bloc
+    pub fn generate(&mut self) {
+        self.commit_edit();
+        let walls = map_generator::generate(self.width, self.height, &self.generator);
+        for (y, row) in walls.iter().enumerate() {
+            for (x, &wall) in row.iter().enumerate() {
+                let (x, y) = (x as u32, y as u32);
+                let current = self.tiles[y as usize][x as usize];
+                if matches!(
+                    current,
+                    MapEditorTileType::Default | MapEditorTileType::Wall
+                ) {
+                    let tile_type = if wall {
+                        MapEditorTileType::Wall
+                    } else {
+                        MapEditorTileType::Default
+                    };
+                    self.set_tile(x, y, tile_type);
+                }
+            }
+        }
+        self.commit_edit();
     }
 
     pub fn clear(&mut self) {
@@ -126,52 +944,596 @@ impl MapEditor {
     }
 
     pub fn is_valid(&self) -> bool {
-        if self.nest_count != 1 {
-            return false;
+        self.get_validation_error().is_none()
+    }
+
+    // Case praticable pour une fourmi : ni mur, ni zone mortelle (elle y mourrait)
+    fn is_walkable(&self, x: u32, y: u32) -> bool {
+        !matches!(
+            self.tiles[y as usize][x as usize],
+            MapEditorTileType::Wall | MapEditorTileType::DeathZone
+        )
+    }
+
+    // Sortie d'un tunnel dont elle a été choisie, comme dans le jeu
+    fn tunnel_exit(&self, x: u32, y: u32) -> Option<(u32, u32)> {
+        if self.tiles[y as usize][x as usize] != MapEditorTileType::Tunnel {
+            return None;
+        }
+        match self.properties(x, y) {
+            Some(TileProperties::Tunnel { exit }) => exit,
+            _ => None,
         }
-        let has_food = self
-            .tiles
-            .iter()
-            .any(|row| row.iter().any(|&t| t == MapEditorTileType::FoodSource));
-        has_food
+    }
+
+    /// Cases atteignables depuis `start` en passant par des cases praticables (parcours
+    /// en largeur avec les mêmes déplacements que les fourmis), indexées `[y][x]`
+    pub fn reachable_from(&self, start: (u32, u32)) -> Vec<Vec<bool>> {
+        let mut reached = vec![vec![false; self.width as usize]; self.height as usize];
+        let mut queue = VecDeque::from([start]);
+        reached[start.1 as usize][start.0 as usize] = true;
+        while let Some((x, y)) = queue.pop_front() {
+            let neighbours = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            for (nx, ny) in neighbours {
+                if nx >= self.width || ny >= self.height {
+                    continue;
+                }
+                // Entrer dans un tunnel fait arriver directement à sa sortie
+                let (nx, ny) = self.tunnel_exit(nx, ny).unwrap_or((nx, ny));
+                if !reached[ny as usize][nx as usize] && self.is_walkable(nx, ny) {
+                    reached[ny as usize][nx as usize] = true;
+                    queue.push_back((nx, ny));
+                }
+            }
+        }
+        reached
+    }
+
+    /// Cases atteignables depuis au moins un nid
+    pub fn reachable_cells(&self) -> Vec<Vec<bool>> {
+        let mut reached = vec![vec![false; self.width as usize]; self.height as usize];
+        for nest in self.cells_of(&[MapEditorTileType::Nest, MapEditorTileType::NestB]) {
+            for (row, nest_row) in reached.iter_mut().zip(self.reachable_from(nest)) {
+                for (cell, from_nest) in row.iter_mut().zip(nest_row) {
+                    *cell |= from_nest;
+                }
+            }
+        }
+        reached
+    }
+
+    // Positions des cases de l'un des types donnés, dans l'ordre de lecture
+    fn cells_of(&self, types: &[MapEditorTileType]) -> Vec<(u32, u32)> {
+        let mut cells = Vec::new();
+        for (y, row) in self.tiles.iter().enumerate() {
+            for (x, tile_type) in row.iter().enumerate() {
+                if types.contains(tile_type) {
+                    cells.push((x as u32, y as u32));
+                }
+            }
+        }
+        cells
+    }
+
+    // Première source de nourriture qu'aucune fourmi ne peut atteindre, ou premier nid qui
+    // n'atteint aucune nourriture
+    fn connectivity_error(&self) -> Option<String> {
+        let food = self.cells_of(&[MapEditorTileType::FoodSource]);
+        for (nest_type, name) in [
+            (MapEditorTileType::Nest, "A"),
+            (MapEditorTileType::NestB, "B"),
+        ] {
+            for nest in self.cells_of(&[nest_type]) {
+                let reached = self.reachable_from(nest);
+                if !food.iter().any(|&(x, y)| reached[y as usize][x as usize]) {
+                    return Some(trf("editor.nest_no_food", &[&name]));
+                }
+            }
+        }
+        let reached = self.reachable_cells();
+        food.into_iter()
+            .find(|&(x, y)| !reached[y as usize][x as usize])
+            .map(|(x, y)| trf("editor.food_unreachable", &[&x, &y]))
     }
 
     pub fn get_validation_error(&self) -> Option<String> {
         if self.nest_count == 0 {
-            return Some("❌ Placez 1 NID (case jaune)".to_string());
+            return Some(tr("editor.place_nest").to_string());
         }
-        if self.nest_count > 1 {
-            return Some(format!("❌ Trop de NIDS ({}/1)", self.nest_count));
+        // En mode multi-nids, une colonie peut avoir autant de nids que voulu
+        if self.nest_count > 1 && !self.multi_nest {
+            return Some(trf("editor.too_many_nests", &[&self.nest_count]));
+        }
+        if self.nest_b_count > 1 && !self.multi_nest {
+            return Some(trf("editor.too_many_nests_b", &[&self.nest_b_count]));
         }
 
         let has_food = self
             .tiles
             .iter()
-            .any(|row| row.iter().any(|&t| t == MapEditorTileType::FoodSource));
+            .any(|row| row.contains(&MapEditorTileType::FoodSource));
         if !has_food {
-            return Some("❌ Placez de la NOURRITURE (case verte)".to_string());
+            return Some(tr("editor.place_food").to_string());
+        }
+
+        for (x, y) in self.cells_of(&[MapEditorTileType::Tunnel]) {
+            match self.tunnel_exit(x, y) {
+                None => return Some(trf("editor.tunnel_no_exit", &[&x, &y])),
+                Some((ex, ey))
+                    if self.tiles[ey as usize][ex as usize] == MapEditorTileType::Wall =>
+                {
+                    return Some(trf("editor.tunnel_into_wall", &[&x, &y]))
+                }
+                _ => {}
+            }
         }
 
-        None
+        self.connectivity_error()
     }
 }
 
+// Sauvegarde et chargement de la carte dans un fichier
+#[cfg(not(target_arch = "wasm32"))]
+fn show_file_bar(ui: &mut egui::Ui, editor: &mut MapEditor) {
+    ui.horizontal(|ui_inner| {
+        ui_inner.label(tr("editor.file"));
+        ui_inner.text_edit_singleline(&mut editor.file_path);
+        if ui_inner.button(tr("editor.save")).clicked() {
+            editor.file_status = Some(match editor.save_to_file(&editor.file_path) {
+                Ok(()) => trf("editor.saved", &[&editor.file_path]),
+                Err(e) => e,
+            });
+        }
+        if ui_inner.button(tr("editor.load")).clicked() {
+            match MapEditor::load_from_file(&editor.file_path) {
+                Ok(loaded) => {
+                    let path = editor.file_path.clone();
+                    *editor = loaded;
+                    editor.file_status = Some(trf("editor.loaded_from", &[&path]));
+                }
+                Err(e) => editor.file_status = Some(e),
+            }
+        }
+    });
+}
+
+// Pas de système de fichiers dans le navigateur : la carte passe par le presse-papiers,
+// et le champ texte reçoit le JSON collé
+#[cfg(target_arch = "wasm32")]
+fn show_file_bar(ui: &mut egui::Ui, editor: &mut MapEditor) {
+    ui.horizontal(|ui_inner| {
+        ui_inner.label(tr("editor.json"));
+        ui_inner.text_edit_singleline(&mut editor.file_path);
+        if ui_inner.button(tr("editor.copy")).clicked() {
+            editor.file_status = Some(match editor.to_json() {
+                Ok(json) => {
+                    ui_inner.ctx().copy_text(json);
+                    tr("editor.copied").to_string()
+                }
+                Err(e) => e,
+            });
+        }
+        if ui_inner.button(tr("editor.paste")).clicked() {
+            match MapEditor::from_json(&editor.file_path) {
+                Ok(loaded) => {
+                    *editor = loaded;
+                    editor.file_status = Some(tr("editor.loaded").to_string());
+                }
+                Err(e) => editor.file_status = Some(trf("editor.invalid", &[&e])),
+            }
+        }
+    });
+}
+
+// Aperçu de la carte : courte partie jouée en fond, dont un calque recouvre ensuite la grille
+#[cfg(not(target_arch = "wasm32"))]
+fn show_preview_bar(ui: &mut egui::Ui, editor: &mut MapEditor) {
+    ui.horizontal_wrapped(|ui_inner| {
+        let running = editor
+            .preview
+            .as_mut()
+            .is_some_and(|preview| preview.outcome().is_none());
+        if running {
+            let done = editor.preview.as_ref().map_or(0, MapPreview::progress);
+            ui_inner.add(
+                egui::ProgressBar::new(done as f32 / PREVIEW_TICKS as f32)
+                    .desired_width(160.0)
+                    .text(format!("{} / {}", done, PREVIEW_TICKS)),
+            );
+            if ui_inner.button(tr("preview.stop")).clicked() {
+                editor.preview = None;
+            }
+            ui_inner.ctx().request_repaint_after(PREVIEW_REFRESH);
+            return;
+        }
+
+        let button = ui_inner.add_enabled(
+            editor.is_valid(),
+            egui::Button::new(trf("preview.start", &[&PREVIEW_TICKS])),
+        );
+        let button = match editor.get_validation_error() {
+            Some(error) => button.on_disabled_hover_text(error),
+            None => button.on_hover_text(tr("preview.start_hint")),
+        };
+        if button.clicked() {
+            editor.preview = Some(MapPreview::start(
+                editor.width,
+                editor.height,
+                editor.to_tiles(),
+            ));
+            editor.show_preview = true;
+            return;
+        }
+
+        let tiles = editor.to_tiles();
+        let Some(preview) = editor.preview.as_mut() else {
+            return;
+        };
+        let stale = preview.is_stale(&tiles);
+        match preview.outcome() {
+            Some(Ok(result)) => {
+                ui_inner.checkbox(&mut editor.show_preview, tr("preview.show"));
+                for layer in PreviewLayer::all() {
+                    ui_inner.selectable_value(&mut editor.preview_layer, layer, layer.label());
+                }
+                ui_inner.label(trf(
+                    "preview.summary",
+                    &[&result.food_delivered, &result.deaths, &result.ticks],
+                ));
+                if stale {
+                    ui_inner.label(egui::RichText::new(tr("preview.stale")).italics());
+                }
+            }
+            Some(Err(error)) => {
+                ui_inner.colored_label(egui::Color32::RED, error.as_str());
+            }
+            None => {}
+        }
+    });
+}
+
+// Calque de l'aperçu par-dessus les cases, atténué quand la carte a changé depuis
+#[cfg(not(target_arch = "wasm32"))]
+fn draw_preview(
+    painter: &egui::Painter,
+    editor: &mut MapEditor,
+    cell_rect: impl Fn((u32, u32)) -> egui::Rect,
+) {
+    if !editor.show_preview {
+        return;
+    }
+    let tiles = editor.to_tiles();
+    let (layer, width, height) = (editor.preview_layer, editor.width, editor.height);
+    let Some(preview) = editor.preview.as_mut() else {
+        return;
+    };
+    let fade = if preview.is_stale(&tiles) { 0.4 } else { 1.0 };
+    let Some(Ok(result)) = preview.outcome() else {
+        return;
+    };
+    let color = layer.color();
+    let mut mesh = egui::Mesh::default();
+    for y in 0..height {
+        for x in 0..width {
+            let intensity = result.intensity(layer, x, y);
+            if intensity < 0.01 {
+                continue;
+            }
+            let alpha = (intensity * 200.0 * fade) as u8;
+            mesh.add_colored_rect(
+                cell_rect((x, y)),
+                egui::Color32::from_rgba_unmultiplied(color.r(), color.g(), color.b(), alpha),
+            );
+        }
+    }
+    painter.add(mesh);
+}
+
+// Menu "Générer" : murs d'un labyrinthe, de grottes ou d'un bruit, retouchables ensuite
+fn show_generator_menu(ui: &mut egui::Ui, editor: &mut MapEditor) {
+    let settings = &mut editor.generator;
+    for generator in Generator::all() {
+        ui.radio_value(&mut settings.generator, generator, generator.label());
+    }
+    ui.separator();
+    ui.horizontal(|ui_inner| {
+        ui_inner.label(tr("editor.seed"));
+        ui_inner.add(egui::DragValue::new(&mut settings.seed));
+        if ui_inner
+            .button("🎲")
+            .on_hover_text(tr("editor.random_seed"))
+            .clicked()
+        {
+            settings.seed = rand::random();
+        }
+    });
+    ui.add(
+        egui::Slider::new(&mut settings.density, 0.0..=1.0)
+            .text(settings.generator.density_label()),
+    );
+    ui.label(egui::RichText::new(tr("editor.generate_note")).small());
+    if ui.button(tr("editor.generate")).clicked() {
+        editor.generate();
+        ui.close();
+    }
+}
+
+// Menu "Taille" : nouvelles dimensions et bord qui reste en place
+fn show_resize_menu(ui: &mut egui::Ui, editor: &mut MapEditor) {
+    ui.horizontal(|ui_inner| {
+        ui_inner.add(
+            egui::DragValue::new(&mut editor.resize_to.0)
+                .range(1..=MAX_MAP_SIDE)
+                .prefix(tr("editor.width")),
+        );
+        ui_inner.add(
+            egui::DragValue::new(&mut editor.resize_to.1)
+                .range(1..=MAX_MAP_SIDE)
+                .prefix(tr("editor.height")),
+        );
+    });
+    ui.label(tr("editor.anchor"));
+    egui::Grid::new("resize_anchor").show(ui, |ui_grid| {
+        let aligns = [egui::Align::Min, egui::Align::Center, egui::Align::Max];
+        for vertical in aligns {
+            for horizontal in aligns {
+                let anchor = egui::Align2([horizontal, vertical]);
+                ui_grid.radio_value(&mut editor.resize_anchor, anchor, "");
+            }
+            ui_grid.end_row();
+        }
+    });
+    if ui.button(tr("editor.apply")).clicked() {
+        let (width, height) = editor.resize_to;
+        editor.resize(width, height, editor.resize_anchor);
+        ui.close();
+    }
+}
+
+// Réglages de la case choisie au clic droit : quantité de nourriture, capacités du nid,
+// ralentissement de la boue ou sortie du tunnel
+fn show_cell_properties(ui: &mut egui::Ui, editor: &mut MapEditor) {
+    let Some((x, y)) = editor.selected_cell else {
+        ui.label(egui::RichText::new(tr("editor.cell_hint")).small());
+        return;
+    };
+    let tile_type = editor.tiles[y as usize][x as usize];
+    ui.horizontal_wrapped(|ui_inner| {
+        ui_inner.label(trf("editor.cell", &[&x, &y, &tile_type.label()]));
+        match editor.properties(x, y) {
+            Some(TileProperties::Food {
+                mut amount,
+                mut value,
+                mut regrowth,
+            }) => {
+                let mut changed = ui_inner
+                    .add(
+                        egui::DragValue::new(&mut amount)
+                            .range(1..=100_000)
+                            .prefix(tr("editor.food_amount")),
+                    )
+                    .changed();
+                changed |= ui_inner
+                    .add(
+                        egui::DragValue::new(&mut value)
+                            .range(1..=100)
+                            .prefix(tr("editor.food_value")),
+                    )
+                    .on_hover_text(tr("editor.food_value_hint"))
+                    .changed();
+                // Sans repousse propre, la source suit celle de la configuration
+                let mut own_regrowth = regrowth.is_some();
+                if ui_inner
+                    .checkbox(&mut own_regrowth, tr("editor.own_regrowth"))
+                    .changed()
+                {
+                    regrowth = own_regrowth.then_some(FoodRegrowth {
+                        amount: 10,
+                        interval: 100,
+                        cap: amount,
+                    });
+                    changed = true;
+                }
+                if let Some(rule) = &mut regrowth {
+                    for (value, range, prefix, suffix) in [
+                        (&mut rule.amount, 0..=10_000, "+", tr("editor.units")),
+                        (
+                            &mut rule.interval,
+                            1..=100_000,
+                            tr("editor.every"),
+                            " ticks",
+                        ),
+                        (&mut rule.cap, 1..=100_000, tr("editor.up_to"), ""),
+                    ] {
+                        let value = egui::DragValue::new(value)
+                            .range(range)
+                            .prefix(prefix)
+                            .suffix(suffix);
+                        changed |= ui_inner.add(value).changed();
+                    }
+                }
+                if changed {
+                    editor.set_properties(
+                        x,
+                        y,
+                        TileProperties::Food {
+                            amount,
+                            value,
+                            regrowth,
+                        },
+                    );
+                }
+            }
+            Some(TileProperties::Nest {
+                mut explorer_capacity,
+                mut picker_capacity,
+                mut fighter_capacity,
+            }) => {
+                let mut changed = false;
+                for (value, prefix) in [
+                    (&mut explorer_capacity, tr("editor.explorers")),
+                    (&mut picker_capacity, tr("editor.pickers")),
+                    (&mut fighter_capacity, tr("editor.fighters")),
+                ] {
+                    changed |= ui_inner
+                        .add(egui::DragValue::new(value).range(0..=1000).prefix(prefix))
+                        .changed();
+                }
+                if changed {
+                    let properties = TileProperties::Nest {
+                        explorer_capacity,
+                        picker_capacity,
+                        fighter_capacity,
+                    };
+                    editor.set_properties(x, y, properties);
+                }
+            }
+            Some(TileProperties::Mud { mut slow_factor }) => {
+                let value = egui::DragValue::new(&mut slow_factor)
+                    .range(2..=20)
+                    .prefix(tr("editor.slowdown"));
+                if ui_inner.add(value).changed() {
+                    editor.set_properties(x, y, TileProperties::Mud { slow_factor });
+                }
+            }
+            Some(TileProperties::Tunnel { exit }) => {
+                ui_inner.label(match exit {
+                    Some((ex, ey)) => trf("editor.exit", &[&ex, &ey]),
+                    None => tr("editor.no_exit").to_string(),
+                });
+                ui_inner
+                    .toggle_value(&mut editor.picking_exit, tr("editor.pick_exit"))
+                    .on_hover_text(tr("editor.pick_exit_hint"));
+            }
+            None => {
+                ui_inner.label(tr("editor.no_settings"));
+            }
+        }
+        if let Some(default) = tile_type.default_properties() {
+            if ui_inner
+                .add_enabled(
+                    editor.properties(x, y) != Some(default),
+                    egui::Button::new(tr("editor.defaults")),
+                )
+                .clicked()
+            {
+                editor.set_properties(x, y, default);
+            }
+        }
+        if ui_inner.button("✖").clicked() {
+            editor.selected_cell = None;
+            editor.picking_exit = false;
+        }
+    });
+}
+
 pub fn show_map_editor(ui: &mut egui::Ui, editor: &mut MapEditor, _base_cell_size: f32) -> bool {
     let mut launch_clicked = false;
 
     // 1. BARRE D'OUTILS EN HAUT
-    ui.horizontal(|ui_inner| {
-        ui_inner.label("Outils :");
-        if ui_inner.button("🗑️ Tout effacer").clicked() {
+    ui.horizontal_wrapped(|ui_inner| {
+        ui_inner.label(tr("editor.tools"));
+        if ui_inner.button(tr("editor.clear")).clicked() {
             editor.clear();
         }
-        if ui_inner.button("⬜ Remplir vide").clicked() {
+        if ui_inner.button(tr("editor.fill_empty")).clicked() {
             editor.fill_all(MapEditorTileType::Default);
         }
+        if ui_inner
+            .add_enabled(editor.can_undo(), egui::Button::new(tr("editor.undo")))
+            .on_hover_text("Ctrl+Z")
+            .clicked()
+        {
+            editor.undo();
+        }
+        if ui_inner
+            .add_enabled(editor.can_redo(), egui::Button::new(tr("editor.redo")))
+            .on_hover_text("Ctrl+Y")
+            .clicked()
+        {
+            editor.redo();
+        }
+        ui_inner.menu_button(tr("editor.generate_menu"), |ui_menu| {
+            show_generator_menu(ui_menu, editor)
+        });
+        for (reshape, label, hover) in [
+            (Reshape::Rotate90, "⟳ 90°", tr("editor.rotate")),
+            (
+                Reshape::MirrorHorizontal,
+                "⇆",
+                tr("editor.mirror_horizontal"),
+            ),
+            (Reshape::MirrorVertical, "⇅", tr("editor.mirror_vertical")),
+        ] {
+            if ui_inner.button(label).on_hover_text(hover).clicked() {
+                editor.reshape(reshape);
+            }
+        }
+        ui_inner.menu_button(tr("editor.size_menu"), |ui_menu| {
+            show_resize_menu(ui_menu, editor)
+        });
+        // Clic droit ou molette enfoncée pour déplacer la vue, molette pour zoomer
+        if ui_inner
+            .add_enabled(
+                !editor.camera.is_reset(),
+                egui::Button::new(tr("common.fit_view")),
+            )
+            .clicked()
+        {
+            editor.camera.reset();
+        }
     });
+    // Raccourcis clavier, sauf quand un champ texte (chemin du fichier) les utilise déjà
+    if !ui.ctx().wants_keyboard_input() {
+        let (undo, redo) = ui.input_mut(|i| {
+            let redo = i.consume_key(egui::Modifiers::COMMAND, egui::Key::Y)
+                || i.consume_key(
+                    egui::Modifiers::COMMAND | egui::Modifiers::SHIFT,
+                    egui::Key::Z,
+                );
+            (i.consume_key(egui::Modifiers::COMMAND, egui::Key::Z), redo)
+        });
+        if undo {
+            editor.undo();
+        }
+        if redo {
+            editor.redo();
+        }
+    }
+    show_file_bar(ui, editor);
+    #[cfg(not(target_arch = "wasm32"))]
+    show_preview_bar(ui, editor);
+    ui.checkbox(&mut editor.show_unreachable, tr("editor.show_unreachable"));
+    ui.checkbox(&mut editor.multi_nest, tr("editor.multi_nest"))
+        .on_hover_text(tr("editor.multi_nest_hint"));
+    if let Some(status) = &editor.file_status {
+        ui.label(status);
+    }
     ui.separator();
 
-    // 2. SÉLECTION DU TYPE DE TUILE
+    // 2. OUTIL DE DESSIN ET SÉLECTION DU TYPE DE TUILE
+    ui.horizontal_wrapped(|ui_inner| {
+        ui_inner.label(tr("editor.tool"));
+        for tool in BrushTool::all() {
+            ui_inner.selectable_value(&mut editor.tool, tool, tool.label());
+        }
+        ui_inner.add_enabled(
+            matches!(editor.tool, BrushTool::Pencil | BrushTool::Line),
+            egui::Slider::new(&mut editor.brush_size, 1..=MAX_BRUSH_SIZE)
+                .text(tr("editor.brush_size")),
+        );
+        ui_inner.separator();
+        ui_inner.label(tr("editor.symmetry"));
+        let symmetry = &mut editor.symmetry;
+        ui_inner.checkbox(&mut symmetry.horizontal, tr("editor.symmetry_horizontal"));
+        ui_inner.checkbox(&mut symmetry.vertical, tr("editor.symmetry_vertical"));
+        ui_inner.checkbox(&mut symmetry.rotational, tr("editor.symmetry_rotational"));
+    });
     ui.horizontal_wrapped(|ui_inner| {
         for tile_type in MapEditorTileType::all() {
             let is_selected = editor.selected_tile_type == tile_type;
@@ -188,6 +1550,7 @@ pub fn show_map_editor(ui: &mut egui::Ui, editor: &mut MapEditor, _base_cell_siz
             }
         }
     });
+    show_cell_properties(ui, editor);
     ui.separator();
 
     // 3. ZONE BASSE (BOUTON LANCER + VALIDATION)
@@ -201,13 +1564,10 @@ pub fn show_map_editor(ui: &mut egui::Ui, editor: &mut MapEditor, _base_cell_siz
         // --- BOUTON LANCER (Dessiné tout en bas) ---
         if editor.is_valid() {
             // Gros bouton vert
-            let btn = egui::Button::new(
-                egui::RichText::new("🚀 LANCER LA PARTIE")
-                    .size(20.0)
-                    .strong(),
-            )
-            .fill(egui::Color32::from_rgb(0, 100, 0))
-            .min_size(egui::vec2(200.0, 40.0));
+            let btn =
+                egui::Button::new(egui::RichText::new(tr("editor.launch")).size(20.0).strong())
+                    .fill(egui::Color32::from_rgb(0, 100, 0))
+                    .min_size(egui::vec2(200.0, 40.0));
 
             if ui_bottom.add(btn).clicked() {
                 launch_clicked = true;
@@ -224,7 +1584,12 @@ pub fn show_map_editor(ui: &mut egui::Ui, editor: &mut MapEditor, _base_cell_siz
         ui_bottom.add_space(10.0);
 
         // Stats juste au-dessus du bouton
-        ui_bottom.label(format!("Nids: {}/1", editor.nest_count));
+        let nests_key = if editor.multi_nest {
+            "editor.nests_multi"
+        } else {
+            "editor.nests"
+        };
+        ui_bottom.label(trf(nests_key, &[&editor.nest_count, &editor.nest_b_count]));
         ui_bottom.separator();
 
         // 4. GRILLE CENTRALE (Prend tout l'espace restant au-dessus du bouton)
@@ -242,19 +1607,19 @@ pub fn show_map_editor(ui: &mut egui::Ui, editor: &mut MapEditor, _base_cell_siz
             ui_bottom.allocate_painter(available_size, egui::Sense::click_and_drag());
 
         // --- CALCUL DU CENTRAGE ---
-        let width_f = editor.width as f32;
-        let height_f = editor.height as f32;
-
-        // Taille des cases auto-adaptative pour tout faire tenir
-        let cell_size = (available_size.x / width_f)
-            .min(available_size.y / height_f)
-            .min(40.0); // Max 40px pour pas que ce soit énorme
-
-        // Calcul des offsets pour centrer
-        let grid_w_px = width_f * cell_size;
-        let grid_h_px = height_f * cell_size;
-        let offset_x = response.rect.min.x + (available_size.x - grid_w_px) / 2.0;
-        let offset_y = response.rect.min.y + (available_size.y - grid_h_px) / 2.0;
+        // Taille des cases auto-adaptative pour tout faire tenir (max 40px sans zoom),
+        // le bouton principal reste réservé au pinceau
+        let layout = editor.camera.update(
+            ui_bottom,
+            &response,
+            (editor.width, editor.height),
+            40.0,
+            false,
+        );
+        let cell_size = layout.cell_size;
+        let grid_w_px = editor.width as f32 * cell_size;
+        let grid_h_px = editor.height as f32 * cell_size;
+        let (offset_x, offset_y) = (layout.origin.x, layout.origin.y);
 
         // Dessin du fond de la grille
         painter.rect_filled(
@@ -283,26 +1648,153 @@ pub fn show_map_editor(ui: &mut egui::Ui, editor: &mut MapEditor, _base_cell_siz
             }
         }
 
+        // Axes de symétrie actifs
+        let axis = egui::Stroke::new(1.0, egui::Color32::from_white_alpha(90));
+        let center = egui::pos2(offset_x + grid_w_px / 2.0, offset_y + grid_h_px / 2.0);
+        if editor.symmetry.horizontal {
+            painter.vline(center.x, offset_y..=offset_y + grid_h_px, axis);
+        }
+        if editor.symmetry.vertical {
+            painter.hline(offset_x..=offset_x + grid_w_px, center.y, axis);
+        }
+        if editor.symmetry.rotational {
+            painter.circle_stroke(center, cell_size.max(4.0), axis);
+        }
+
+        // Cases praticables qu'aucune fourmi ne peut atteindre : hachurées en rouge
+        if editor.show_unreachable && editor.nest_count + editor.nest_b_count > 0 {
+            let reached = editor.reachable_cells();
+            let stroke = egui::Stroke::new(1.5, egui::Color32::from_rgb(220, 40, 40));
+            for y in 0..editor.height {
+                for x in 0..editor.width {
+                    if reached[y as usize][x as usize] || !editor.is_walkable(x, y) {
+                        continue;
+                    }
+                    let rect = egui::Rect::from_min_size(
+                        egui::pos2(
+                            offset_x + x as f32 * cell_size,
+                            offset_y + y as f32 * cell_size,
+                        ),
+                        egui::Vec2::splat(cell_size),
+                    );
+                    painter.rect_filled(rect, 0.0, egui::Color32::from_black_alpha(120));
+                    painter.line_segment([rect.left_bottom(), rect.right_top()], stroke);
+                }
+            }
+        }
+
+        // Les cases aux réglages personnalisés portent un point blanc, la case choisie un contour
+        let cell_rect = |(x, y): (u32, u32)| {
+            egui::Rect::from_min_size(
+                egui::pos2(
+                    offset_x + x as f32 * cell_size,
+                    offset_y + y as f32 * cell_size,
+                ),
+                egui::Vec2::splat(cell_size),
+            )
+        };
+        #[cfg(not(target_arch = "wasm32"))]
+        draw_preview(&painter, editor, cell_rect);
+        // Chaque tunnel est relié à sa sortie par une flèche
+        for (&(x, y), properties) in &editor.properties {
+            if let TileProperties::Tunnel { exit: Some(exit) } = properties {
+                let from = cell_rect((x, y)).center();
+                painter.arrow(
+                    from,
+                    cell_rect(*exit).center() - from,
+                    egui::Stroke::new(2.0, MapEditorTileType::Tunnel.color()),
+                );
+            }
+        }
+        for &cell in editor.properties.keys() {
+            let corner =
+                cell_rect(cell).right_top() + egui::vec2(-cell_size * 0.2, cell_size * 0.2);
+            painter.circle_filled(corner, cell_size * 0.1, egui::Color32::WHITE);
+        }
+        if let Some(cell) = editor.selected_cell {
+            painter.rect_stroke(
+                cell_rect(cell),
+                0.0,
+                egui::Stroke::new(2.0, egui::Color32::WHITE),
+                egui::StrokeKind::Inside,
+            );
+        }
+
         // --- GESTION DES CLICS / DESSIN ---
-        // On permet de cliquer OU de glisser
-        if response.clicked() || (response.dragged() && response.is_pointer_button_down_on()) {
-            if let Some(pos) = response.interact_pointer_pos() {
-                // On inverse la logique pour trouver la case
-                let rel_x = pos.x - offset_x;
-                let rel_y = pos.y - offset_y;
-
-                if rel_x >= 0.0 && rel_y >= 0.0 {
-                    let grid_x = (rel_x / cell_size).floor() as u32;
-                    let grid_y = (rel_y / cell_size).floor() as u32;
-
-                    // Sécurité bornes
-                    if grid_x < editor.width && grid_y < editor.height {
-                        editor.set_tile(grid_x, grid_y, editor.selected_tile_type);
-                        response.mark_changed(); // Indique à egui de redessiner vite
+        // On inverse la logique pour trouver la case (None hors de la grille)
+        let pointer_cell = response
+            .interact_pointer_pos()
+            .and_then(|pos| layout.cell_at(pos));
+        if response.secondary_clicked() {
+            editor.selected_cell = pointer_cell;
+            editor.picking_exit = false;
+        }
+        let tile_type = editor.selected_tile_type;
+        if response.drag_started_by(egui::PointerButton::Primary) && !editor.picking_exit {
+            // Le glisser part de la case où le bouton a été enfoncé
+            let origin = ui_bottom.input(|i| i.pointer.press_origin());
+            editor.drag = origin
+                .and_then(|pos| layout.cell_at(pos))
+                .map(|cell| (cell, cell));
+        }
+        let dragging = response.dragged_by(egui::PointerButton::Primary);
+        if let (Some((start, last)), Some(cell)) = (editor.drag, pointer_cell) {
+            if dragging {
+                // Le pinceau relie les cases survolées pour ne pas laisser de trous
+                if editor.tool == BrushTool::Pencil {
+                    let cells = editor.stroke_cells(last, cell);
+                    editor.paint(cells, tile_type);
+                    response.mark_changed(); // Indique à egui de redessiner vite
+                }
+                editor.drag = Some((start, cell));
+            }
+        }
+        if response.clicked() && editor.picking_exit {
+            // Le clic désigne la sortie du tunnel sélectionné au lieu de peindre
+            if let (Some((x, y)), Some(exit)) = (editor.selected_cell, pointer_cell) {
+                editor.set_properties(x, y, TileProperties::Tunnel { exit: Some(exit) });
+            }
+            editor.picking_exit = false;
+        } else if response.clicked() {
+            if let Some(cell) = pointer_cell {
+                match editor.tool {
+                    BrushTool::Fill => editor.flood_fill(cell.0, cell.1, tile_type),
+                    _ => {
+                        let cells = editor.brush_cells(cell);
+                        editor.paint(cells, tile_type);
                     }
                 }
             }
         }
+
+        // Rectangle et ligne : aperçu pendant le glisser, tracé au relâchement
+        if let Some((start, end)) = editor.drag {
+            let cells = match editor.tool {
+                BrushTool::Rectangle => MapEditor::rect_cells(start, end),
+                BrushTool::Line => editor.stroke_cells(start, end),
+                BrushTool::Pencil | BrushTool::Fill => Vec::new(),
+            };
+            if dragging {
+                for (x, y) in cells {
+                    let rect = egui::Rect::from_min_size(
+                        egui::pos2(
+                            offset_x + x as f32 * cell_size,
+                            offset_y + y as f32 * cell_size,
+                        ),
+                        egui::Vec2::splat(cell_size),
+                    );
+                    painter.rect_filled(rect, 0.0, tile_type.color().gamma_multiply(0.6));
+                }
+            } else {
+                editor.paint(cells, tile_type);
+                editor.drag = None;
+            }
+        }
+
+        // Tout le glisser forme une seule modification, enregistrée au relâchement
+        if !dragging {
+            editor.commit_edit();
+        }
     });
 
     launch_clicked
@@ -0,0 +1,197 @@
+//! Générateurs de murs pour l'éditeur de carte
+//!
+//! Chaque générateur renvoie une grille de booléens (`true` = mur) indexée `[y][x]` comme
+//! [`crate::map_editor::MapEditor::tiles`] ; l'éditeur la recopie ensuite case par case
+//! pour que le résultat reste retouchable et annulable.
+
+use crate::i18n::tr;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+// Nombre de passes de lissage des grottes
+const CAVE_SMOOTHING_STEPS: usize = 5;
+// Taille en cases d'une maille du bruit de Perlin
+const NOISE_SCALE: f32 = 8.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Generator {
+    /// Labyrinthe parfait creusé par backtracking récursif
+    Maze,
+    /// Grottes par automate cellulaire
+    Caves,
+    /// Murs là où un bruit de Perlin dépasse un seuil
+    Noise,
+}
+
+impl Generator {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Generator::Maze => tr("editor.maze"),
+            Generator::Caves => tr("editor.caves"),
+            Generator::Noise => tr("editor.noise"),
+        }
+    }
+
+    /// Sens du curseur de densité pour ce générateur
+    pub fn density_label(&self) -> &'static str {
+        match self {
+            Generator::Maze => tr("editor.maze_density"),
+            Generator::Caves => tr("editor.caves_density"),
+            Generator::Noise => tr("editor.noise_density"),
+        }
+    }
+
+    pub fn all() -> [Generator; 3] {
+        [Generator::Maze, Generator::Caves, Generator::Noise]
+    }
+}
+
+/// Réglages du menu "Générer" de l'éditeur
+#[derive(Clone, Copy, Debug)]
+pub struct GeneratorSettings {
+    pub generator: Generator,
+    pub seed: u64,
+    /// Entre 0 et 1, interprétée selon le générateur (voir [`Generator::density_label`])
+    pub density: f32,
+}
+
+impl Default for GeneratorSettings {
+    fn default() -> Self {
+        GeneratorSettings {
+            generator: Generator::Maze,
+            seed: 0,
+            density: 0.45,
+        }
+    }
+}
+
+/// Murs d'une carte `width` x `height` ; même réglages = même carte
+pub fn generate(width: u32, height: u32, settings: &GeneratorSettings) -> Vec<Vec<bool>> {
+    let (width, height) = (width as usize, height as usize);
+    let mut rng = StdRng::seed_from_u64(settings.seed);
+    let density = settings.density.clamp(0.0, 1.0);
+    match settings.generator {
+        Generator::Maze => maze(width, height, density, &mut rng),
+        Generator::Caves => caves(width, height, density, &mut rng),
+        Generator::Noise => noise(width, height, density, &mut rng),
+    }
+}
+
+// Les couloirs passent par les cases de coordonnées paires, les murs entre elles ;
+// `keep` est la part des murs intérieurs gardés (en dessous de 1, le labyrinthe a des boucles)
+fn maze(width: usize, height: usize, keep: f32, rng: &mut StdRng) -> Vec<Vec<bool>> {
+    let mut walls = vec![vec![true; width]; height];
+    if width == 0 || height == 0 {
+        return walls;
+    }
+    let mut stack: Vec<(usize, usize)> = vec![(0, 0)];
+    walls[0][0] = false;
+    while let Some(&(x, y)) = stack.last() {
+        let mut next: Vec<(usize, usize)> = [(2, 0), (0, 2), (-2, 0), (0, -2)]
+            .iter()
+            .filter_map(|&(dx, dy)| {
+                let nx = x.checked_add_signed(dx)?;
+                let ny = y.checked_add_signed(dy)?;
+                (nx < width && ny < height && walls[ny][nx]).then_some((nx, ny))
+            })
+            .collect();
+        next.shuffle(rng);
+        match next.first() {
+            Some(&(nx, ny)) => {
+                walls[(y + ny) / 2][(x + nx) / 2] = false;
+                walls[ny][nx] = false;
+                stack.push((nx, ny));
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+
+    // Ouvrir des murs entre deux couloirs pour créer des raccourcis
+    for (y, row) in walls.iter_mut().enumerate() {
+        for (x, wall) in row.iter_mut().enumerate() {
+            let between_rows = y % 2 == 1 && x % 2 == 0 && y + 1 < height;
+            let between_columns = x % 2 == 1 && y % 2 == 0 && x + 1 < width;
+            if *wall && (between_rows || between_columns) && rng.gen::<f32>() >= keep {
+                *wall = false;
+            }
+        }
+    }
+    walls
+}
+
+// Remplissage aléatoire puis lissage : une case devient mur si au moins 5 cases de son
+// voisinage 3x3 en sont (le bord de la carte compte comme mur)
+fn caves(width: usize, height: usize, fill: f32, rng: &mut StdRng) -> Vec<Vec<bool>> {
+    let mut walls: Vec<Vec<bool>> = (0..height)
+        .map(|_| (0..width).map(|_| rng.gen::<f32>() < fill).collect())
+        .collect();
+    for _ in 0..CAVE_SMOOTHING_STEPS {
+        walls = (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| {
+                        let mut count = 0;
+                        for ny in y as isize - 1..=y as isize + 1 {
+                            for nx in x as isize - 1..=x as isize + 1 {
+                                let inside = nx >= 0
+                                    && ny >= 0
+                                    && (nx as usize) < width
+                                    && (ny as usize) < height;
+                                if !inside || walls[ny as usize][nx as usize] {
+                                    count += 1;
+                                }
+                            }
+                        }
+                        count >= 5
+                    })
+                    .collect()
+            })
+            .collect();
+    }
+    walls
+}
+
+// Bruit de Perlin 2D ; le seuil est choisi pour que `share` des cases soient des murs
+fn noise(width: usize, height: usize, share: f32, rng: &mut StdRng) -> Vec<Vec<bool>> {
+    let lattice_w = (width as f32 / NOISE_SCALE) as usize + 2;
+    let lattice_h = (height as f32 / NOISE_SCALE) as usize + 2;
+    let gradients: Vec<(f32, f32)> = (0..lattice_w * lattice_h)
+        .map(|_| {
+            let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+            (angle.cos(), angle.sin())
+        })
+        .collect();
+
+    let fade = |t: f32| t * t * t * (t * (t * 6.0 - 15.0) + 10.0);
+    let value = |x: usize, y: usize| {
+        let (px, py) = (x as f32 / NOISE_SCALE, y as f32 / NOISE_SCALE);
+        let (cx, cy) = (px as usize, py as usize);
+        let (fx, fy) = (px - cx as f32, py - cy as f32);
+        let corner = |dx: usize, dy: usize| {
+            let (gx, gy) = gradients[(cy + dy) * lattice_w + cx + dx];
+            gx * (fx - dx as f32) + gy * (fy - dy as f32)
+        };
+        let (u, v) = (fade(fx), fade(fy));
+        let top = corner(0, 0) + u * (corner(1, 0) - corner(0, 0));
+        let bottom = corner(0, 1) + u * (corner(1, 1) - corner(0, 1));
+        top + v * (bottom - top)
+    };
+
+    let values: Vec<Vec<f32>> = (0..height)
+        .map(|y| (0..width).map(|x| value(x, y)).collect())
+        .collect();
+    let mut sorted: Vec<f32> = values.iter().flatten().copied().collect();
+    sorted.sort_by(|a, b| b.total_cmp(a));
+    let wall_count = (sorted.len() as f32 * share).round() as usize;
+    let threshold = match wall_count {
+        0 => f32::INFINITY,
+        n => sorted[n - 1],
+    };
+    values
+        .into_iter()
+        .map(|row| row.into_iter().map(|v| v >= threshold).collect())
+        .collect()
+}
@@ -0,0 +1,231 @@
+//! Aperçu d'une carte en cours d'édition : une courte partie sans affichage, jouée en fond
+//!
+//! Le bouton « Prévisualiser 1000 ticks » de l'éditeur lance sur un thread une partie de
+//! [`PREVIEW_TICKS`] ticks sur la carte telle qu'elle est dessinée, avec la configuration par
+//! défaut (fourmis comprises) et une graine fixe. Une fois la partie jouée, l'éditeur
+//! superpose à sa grille un de ses calques ([`PreviewLayer`]) : les passages des fourmis de
+//! toutes les colonies, ou les pistes apprises par la colonie A. Un aperçu décrit la carte
+//! de son lancement ; l'éditeur le montre atténué dès que la carte change.
+
+use crate::ant::{Ant, AntsType};
+use crate::ants_game_manager::{AntsGameManager, EndReason};
+use crate::cli_args::SimulationConfig;
+use crate::heat_scale::{self, HeatScale};
+use crate::i18n::tr;
+use crate::pheromone::PheromoneMap;
+use crate::theme;
+use crate::tile::Tile;
+use eframe::egui;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Ticks joués par l'aperçu (moins si la partie se termine avant)
+pub const PREVIEW_TICKS: usize = 1000;
+// Graine fixe : deux aperçus de la même carte donnent le même résultat
+const PREVIEW_SEED: u64 = 1;
+
+/// Calque de l'aperçu superposé à la grille de l'éditeur
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum PreviewLayer {
+    /// Passages des fourmis sur chaque case
+    #[default]
+    Traffic,
+    /// Piste vers la nourriture de la colonie A
+    FoodTrail,
+    /// Piste du retour au nid de la colonie A
+    NestTrail,
+}
+
+impl PreviewLayer {
+    pub fn all() -> [PreviewLayer; 3] {
+        [
+            PreviewLayer::Traffic,
+            PreviewLayer::FoodTrail,
+            PreviewLayer::NestTrail,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PreviewLayer::Traffic => tr("preview.traffic"),
+            PreviewLayer::FoodTrail => tr("preview.food_trail"),
+            PreviewLayer::NestTrail => tr("preview.nest_trail"),
+        }
+    }
+
+    /// Couleur du calque, celle du plateau de jeu
+    pub fn color(&self) -> egui::Color32 {
+        let palette = theme::palette();
+        match self {
+            PreviewLayer::Traffic => palette.traffic,
+            PreviewLayer::FoodTrail => palette.food_trail,
+            PreviewLayer::NestTrail => palette.nest_trail,
+        }
+    }
+}
+
+/// Bilan de la partie d'aperçu
+pub struct PreviewResult {
+    width: u32,
+    height: u32,
+    // Intensité dans [0, 1] de chaque calque, case par case (rang y * width + x)
+    traffic: Vec<f32>,
+    food_trail: Vec<f32>,
+    nest_trail: Vec<f32>,
+    pub ticks: usize,
+    pub food_delivered: u64,
+    pub deaths: u64,
+    pub end_reason: Option<EndReason>,
+}
+
+impl PreviewResult {
+    fn of(manager: &AntsGameManager) -> Self {
+        let grid = manager.grid();
+        let cells =
+            || (0..grid.get_height()).flat_map(|y| (0..grid.get_width()).map(move |x| (x, y)));
+        let traffic = manager.traffic();
+        let traffic_scale = HeatScale::fixed(traffic.max() as f32);
+        // Même échelle que le plateau en mode automatique : de zéro à la plus forte valeur
+        let trail = |map: &PheromoneMap| {
+            let scale = heat_scale::value_range(map, grid)
+                .map_or_else(HeatScale::default, |(_, high)| HeatScale::fixed(high));
+            cells()
+                .map(|(x, y)| {
+                    if grid.is_walkable(x, y) {
+                        scale.intensity(map.get_max_q(x, y, grid).max(0.0), false)
+                    } else {
+                        0.0
+                    }
+                })
+                .collect()
+        };
+        let (food_trail, nest_trail) = manager
+            .colonies()
+            .first()
+            .map(|colony| {
+                let (food, nest) = colony.maps(AntsType::EXPLORER);
+                (trail(food), trail(nest))
+            })
+            .unwrap_or_default();
+        PreviewResult {
+            width: grid.get_width(),
+            height: grid.get_height(),
+            traffic: cells()
+                .map(|(x, y)| traffic_scale.intensity(traffic.get(x, y) as f32, false))
+                .collect(),
+            food_trail,
+            nest_trail,
+            ticks: manager.current_tick_index(),
+            food_delivered: manager.colonies().iter().map(|colony| colony.score).sum(),
+            deaths: manager.colonies().iter().map(|colony| colony.deaths).sum(),
+            end_reason: manager.end_reason(),
+        }
+    }
+
+    /// Intensité dans [0, 1] du calque sur (x, y), 0 hors de la carte de l'aperçu
+    pub fn intensity(&self, layer: PreviewLayer, x: u32, y: u32) -> f32 {
+        if x >= self.width || y >= self.height {
+            return 0.0;
+        }
+        let values = match layer {
+            PreviewLayer::Traffic => &self.traffic,
+            PreviewLayer::FoodTrail => &self.food_trail,
+            PreviewLayer::NestTrail => &self.nest_trail,
+        };
+        let index = y as usize * self.width as usize + x as usize;
+        values.get(index).copied().unwrap_or(0.0)
+    }
+}
+
+/// Partie d'aperçu jouée sur un thread ; la lâcher l'interrompt
+pub struct MapPreview {
+    // Carte au lancement, pour savoir si l'aperçu décrit encore la carte dessinée
+    tiles: Vec<Tile>,
+    ticks_done: Arc<AtomicUsize>,
+    cancelled: Arc<AtomicBool>,
+    // Déposé par le thread à la fin de la partie, puis repris par `outcome`
+    shared: Arc<Mutex<Option<Result<PreviewResult, String>>>>,
+    outcome: Option<Result<PreviewResult, String>>,
+}
+
+impl MapPreview {
+    /// Lance l'aperçu de la carte `tiles` (au format de `MapEditor::to_tiles`)
+    pub fn start(width: u32, height: u32, tiles: Vec<Tile>) -> Self {
+        let preview = MapPreview {
+            tiles: tiles.clone(),
+            ticks_done: Arc::new(AtomicUsize::new(0)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            shared: Arc::new(Mutex::new(None)),
+            outcome: None,
+        };
+        let ticks_done = Arc::clone(&preview.ticks_done);
+        let cancelled = Arc::clone(&preview.cancelled);
+        let shared = Arc::clone(&preview.shared);
+        thread::spawn(move || {
+            // Les événements de l'aperçu n'ont pas leur place dans le journal de la GUI
+            let outcome = tracing::subscriber::with_default(
+                tracing::subscriber::NoSubscriber::default(),
+                || run(width, height, tiles, &ticks_done, &cancelled),
+            );
+            if let (Some(outcome), Ok(mut shared)) = (outcome, shared.lock()) {
+                *shared = Some(outcome);
+            }
+        });
+        preview
+    }
+
+    /// Ticks déjà joués
+    pub fn progress(&self) -> usize {
+        self.ticks_done.load(Ordering::Relaxed)
+    }
+
+    /// Bilan de la partie, ou l'erreur qui l'a empêchée ; None tant qu'elle se joue
+    pub fn outcome(&mut self) -> Option<&Result<PreviewResult, String>> {
+        if self.outcome.is_none() {
+            self.outcome = self.shared.lock().ok().and_then(|mut shared| shared.take());
+        }
+        self.outcome.as_ref()
+    }
+
+    /// La carte a changé depuis le lancement de l'aperçu
+    pub fn is_stale(&self, tiles: &[Tile]) -> bool {
+        self.tiles != tiles
+    }
+}
+
+impl Drop for MapPreview {
+    fn drop(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+// Joue la partie d'aperçu ; None si elle a été interrompue
+fn run(
+    width: u32,
+    height: u32,
+    tiles: Vec<Tile>,
+    ticks_done: &AtomicUsize,
+    cancelled: &AtomicBool,
+) -> Option<Result<PreviewResult, String>> {
+    let config = SimulationConfig {
+        grid_width: width,
+        grid_height: height,
+        seed: Some(PREVIEW_SEED),
+        snapshot_interval: 0,
+        ..SimulationConfig::default()
+    };
+    let mut manager = match AntsGameManager::new(width, height, tiles, vec![], config.clone()) {
+        Ok(manager) => manager,
+        Err(e) => return Some(Err(e.to_string())),
+    };
+    manager.set_ants(Ant::colony_from_config(&config));
+    while manager.current_tick_index() < PREVIEW_TICKS && !manager.is_game_finished() {
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+        manager.game_step();
+        ticks_done.store(manager.current_tick_index(), Ordering::Relaxed);
+    }
+    Some(Ok(PreviewResult::of(&manager)))
+}
@@ -0,0 +1,243 @@
+//! Méta-contrôleur : réglage d'epsilon et d'alpha pendant la partie
+//!
+//! Pour qui ne peut pas se payer un balayage hors ligne (`ants optimize`), le méta-contrôleur
+//! surveille la nourriture livrée aux nids et décide tous les `controller_period` ticks de
+//! facteurs appliqués à epsilon et à alpha, par-dessus leurs calendriers de décroissance :
+//!
+//! - `stall` : quand rien n'a été livré pendant la période, ou que le débit de livraison a
+//!   reculé de plus de 10 % depuis la période précédente, les deux facteurs sont multipliés
+//!   par `controller_step` ; quand il se maintient ou progresse, ils redescendent vers 1 ;
+//! - `bandit` : chaque période joue un bras parmi quelques couples (facteur d'epsilon,
+//!   facteur d'alpha), choisi par UCB1 d'après le débit obtenu par chaque bras.
+//!
+//! Epsilon reste sous `controller_max_epsilon` et alpha sous 1. Les décisions ne dépendent
+//! que des livraisons, sans tirage aléatoire : une partie rejouée prend les mêmes. Elles
+//! sont gardées dans les checkpoints et oubliées au-delà du tick où l'on rembobine.
+
+use crate::ants_game_manager::QLearningParams;
+use crate::cli_args::SimulationConfig;
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+
+/// Bras du bandit : (facteur d'epsilon, facteur d'alpha). Le premier garde les réglages de
+/// la configuration.
+pub const BANDIT_ARMS: [(f32, f32); 6] = [
+    (1.0, 1.0),
+    (2.0, 1.0),
+    (4.0, 1.0),
+    (0.5, 1.0),
+    (1.0, 2.0),
+    (1.0, 0.5),
+];
+
+// Facteur maximal atteint par le mode stall à force de stagnation
+const MAX_SCALE: f32 = 10.0;
+// Part du débit précédent en deçà de laquelle la progression est jugée en recul
+const STALL_TOLERANCE: f32 = 0.9;
+// Poids du bonus d'exploration d'UCB1, les débits étant ramenés au meilleur débit moyen
+const UCB_C: f32 = 1.0;
+
+/// Règle du méta-contrôleur
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControllerMode {
+    // Epsilon et alpha suivent seulement leurs calendriers de décroissance
+    #[default]
+    Off,
+    // Hausse d'epsilon et d'alpha quand les livraisons stagnent
+    Stall,
+    // Bandit UCB1 sur les couples de facteurs de `BANDIT_ARMS`
+    Bandit,
+}
+
+impl ControllerMode {
+    pub fn all() -> impl Iterator<Item = ControllerMode> {
+        [
+            ControllerMode::Off,
+            ControllerMode::Stall,
+            ControllerMode::Bandit,
+        ]
+        .iter()
+        .copied()
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "off" => Some(ControllerMode::Off),
+            "stall" => Some(ControllerMode::Stall),
+            "bandit" => Some(ControllerMode::Bandit),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ControllerMode::Off => tr("controller.off"),
+            ControllerMode::Stall => tr("controller.stall"),
+            ControllerMode::Bandit => tr("controller.bandit"),
+        }
+    }
+}
+
+/// Décision prise à la fin d'une période
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ControllerDecision {
+    pub tick: u64,
+    /// Nourriture livrée aux nids depuis le début de la partie
+    pub delivered: u64,
+    /// Nourriture livrée par tick pendant la période écoulée
+    pub rate: f32,
+    /// Facteurs appliqués à epsilon et alpha jusqu'à la décision suivante
+    pub epsilon_scale: f32,
+    pub alpha_scale: f32,
+    /// Bras joué jusqu'à la décision suivante (mode bandit)
+    pub arm: Option<usize>,
+    /// Epsilon et alpha de la première colonie une fois les facteurs appliqués
+    pub epsilon: f32,
+    pub alpha: f32,
+}
+
+/// Décisions du méta-contrôleur depuis le début de la partie
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MetaController {
+    decisions: Vec<ControllerDecision>,
+}
+
+impl MetaController {
+    pub fn decisions(&self) -> &[ControllerDecision] {
+        &self.decisions
+    }
+
+    /// Facteurs (epsilon, alpha) en vigueur
+    pub fn scales(&self) -> (f32, f32) {
+        self.decisions.last().map_or((1.0, 1.0), |decision| {
+            (decision.epsilon_scale, decision.alpha_scale)
+        })
+    }
+
+    /// Oublie les décisions prises après `tick` (rembobinage)
+    pub fn rewind(&mut self, tick: u64) {
+        self.decisions.retain(|decision| decision.tick <= tick);
+    }
+
+    /// Applique les facteurs en vigueur à des paramètres déjà décrus
+    pub fn apply(&self, config: &SimulationConfig, params: &mut QLearningParams) {
+        (params.epsilon, params.alpha) =
+            Self::scaled(config, self.scales(), (params.epsilon, params.alpha));
+    }
+
+    /// Décide de nouveaux facteurs si `tick` termine une période. `delivered` est la
+    /// nourriture livrée aux nids jusque-là, `params` les paramètres décrus de la première
+    /// colonie, qui servent seulement à noter les valeurs effectives.
+    pub fn update(
+        &mut self,
+        config: &SimulationConfig,
+        tick: u64,
+        delivered: u64,
+        params: &QLearningParams,
+    ) -> Option<ControllerDecision> {
+        let period = u64::from(config.controller_period.max(1));
+        if config.meta_controller == ControllerMode::Off
+            || tick == 0
+            || !tick.is_multiple_of(period)
+        {
+            return None;
+        }
+        let previous = self.decisions.last().copied();
+        // Décision déjà prise pour ce tick (on vient de rembobiner jusqu'à lui)
+        if previous.is_some_and(|decision| decision.tick >= tick) {
+            return None;
+        }
+        let (since, before) =
+            previous.map_or((0, 0), |decision| (decision.tick, decision.delivered));
+        let rate = delivered.saturating_sub(before) as f32 / (tick - since) as f32;
+
+        let (epsilon_scale, alpha_scale, arm) = match config.meta_controller {
+            ControllerMode::Off => return None,
+            ControllerMode::Stall => {
+                let step = config.controller_step;
+                let (epsilon_scale, alpha_scale) = self.scales();
+                let stalled = rate == 0.0
+                    || previous.is_some_and(|decision| rate < decision.rate * STALL_TOLERANCE);
+                let adjust = |scale: f32| {
+                    if stalled {
+                        (scale * step).min(MAX_SCALE)
+                    } else {
+                        (scale / step).max(1.0)
+                    }
+                };
+                (adjust(epsilon_scale), adjust(alpha_scale), None)
+            }
+            ControllerMode::Bandit => {
+                let arm = self.next_arm(rate);
+                let (epsilon_scale, alpha_scale) = BANDIT_ARMS[arm];
+                (epsilon_scale, alpha_scale, Some(arm))
+            }
+        };
+
+        let (epsilon, alpha) = Self::scaled(
+            config,
+            (epsilon_scale, alpha_scale),
+            (params.epsilon, params.alpha),
+        );
+        let decision = ControllerDecision {
+            tick,
+            delivered,
+            rate,
+            epsilon_scale,
+            alpha_scale,
+            arm,
+            epsilon,
+            alpha,
+        };
+        self.decisions.push(decision);
+        Some(decision)
+    }
+
+    // Epsilon et alpha une fois les facteurs appliqués ; un epsilon configuré au-dessus du
+    // plafond n'est jamais augmenté, mais peut être réduit
+    fn scaled(
+        config: &SimulationConfig,
+        (epsilon_scale, alpha_scale): (f32, f32),
+        (epsilon, alpha): (f32, f32),
+    ) -> (f32, f32) {
+        let ceiling = config.controller_max_epsilon.max(epsilon);
+        (
+            (epsilon * epsilon_scale).min(ceiling),
+            (alpha * alpha_scale).min(1.0),
+        )
+    }
+
+    // Bras de la période suivante : chaque bras est d'abord essayé une fois, dans l'ordre,
+    // puis UCB1 sur le débit moyen de chacun. `rate` est le débit de la période qui
+    // s'achève, obtenu par le bras de la dernière décision (le premier avant toute décision).
+    fn next_arm(&self, rate: f32) -> usize {
+        let mut totals = [0.0f32; BANDIT_ARMS.len()];
+        let mut counts = [0u32; BANDIT_ARMS.len()];
+        let mut played = 0;
+        for decision in &self.decisions {
+            totals[played] += decision.rate;
+            counts[played] += 1;
+            played = decision.arm.unwrap_or(0);
+        }
+        totals[played] += rate;
+        counts[played] += 1;
+
+        if let Some(untried) = counts.iter().position(|&count| count == 0) {
+            return untried;
+        }
+        let means: Vec<f32> = totals
+            .iter()
+            .zip(counts)
+            .map(|(total, count)| total / count as f32)
+            .collect();
+        let best = means.iter().copied().fold(0.0, f32::max);
+        let scale = if best > 0.0 { best } else { 1.0 };
+        let plays: u32 = counts.iter().sum();
+        let score = |arm: usize| {
+            means[arm] / scale + UCB_C * (2.0 * (plays as f32).ln() / counts[arm] as f32).sqrt()
+        };
+        (0..BANDIT_ARMS.len())
+            .max_by(|&a, &b| score(a).total_cmp(&score(b)).then(b.cmp(&a)))
+            .unwrap_or(0)
+    }
+}
@@ -0,0 +1,477 @@
+use crate::ant::Ant;
+use crate::coord::{Coord, GridShape};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+
+// Nombre de ticks gardés en mémoire avant d'écrire sur disque
+const FLUSH_EVERY: usize = 1000;
+
+// Points conservés au maximum par l'historique des courbes de la GUI
+const STATS_CAPACITY: usize = 2048;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MetricsFormat {
+    Csv,
+    Ndjson,
+}
+
+impl MetricsFormat {
+    // Le format est déduit de l'extension du fichier (.json / .ndjson => NDJSON, sinon CSV)
+    pub fn from_path(path: &str) -> Self {
+        if path.ends_with(".json") || path.ends_with(".ndjson") {
+            MetricsFormat::Ndjson
+        } else {
+            MetricsFormat::Csv
+        }
+    }
+}
+
+// Mesures relevées à la fin d'un tick
+#[derive(Clone, Debug, Default, Serialize)]
+pub struct TickMetrics {
+    pub tick: usize,
+    pub food_delivered: u32,
+    pub active_ants: u32,
+    pub deaths: u32,
+    pub ants_spawned: u32,
+    pub avg_q_magnitude: f32,
+    pub births: u32,
+    // Nourriture stockée dans l'ensemble des nids à la fin du tick
+    pub nest_food: u32,
+    // Âge moyen en ticks des fourmis des colonies, sur la carte ou au nid
+    pub mean_age: f32,
+    // Compteurs cumulés des fourmis de chaque type
+    pub explorers: AntTypeStats,
+    pub pickers: AntTypeStats,
+    pub fighters: AntTypeStats,
+    // Longueur moyenne des allers-retours terminés et écart moyen au plus court, toutes
+    // fourmis confondues (None tant qu'aucun n'est terminé)
+    pub mean_trip_length: Option<f64>,
+    pub trip_regret: Option<f64>,
+}
+
+impl TickMetrics {
+    const CSV_HEADER: &'static str =
+        "tick,food_delivered,active_ants,deaths,ants_spawned,avg_q_magnitude,births,nest_food,\
+         explorer_trips,explorer_distance,explorer_food_delivered,explorer_ticks_alive,\
+         picker_trips,picker_distance,picker_food_delivered,picker_ticks_alive,\
+         fighter_trips,fighter_distance,fighter_food_delivered,fighter_ticks_alive,\
+         mean_trip_length,trip_regret,mean_age";
+
+    fn to_csv(&self) -> String {
+        let mut line = format!(
+            "{},{},{},{},{},{},{},{}",
+            self.tick,
+            self.food_delivered,
+            self.active_ants,
+            self.deaths,
+            self.ants_spawned,
+            self.avg_q_magnitude,
+            self.births,
+            self.nest_food
+        );
+        for stats in [&self.explorers, &self.pickers, &self.fighters] {
+            line.push_str(&format!(
+                ",{},{},{},{}",
+                stats.trips, stats.distance, stats.food_delivered, stats.ticks_alive
+            ));
+        }
+        // Colonnes vides tant qu'aucun aller-retour n'est terminé
+        for value in [self.mean_trip_length, self.trip_regret] {
+            line.push(',');
+            if let Some(value) = value {
+                line.push_str(&value.to_string());
+            }
+        }
+        line.push_str(&format!(",{}", self.mean_age));
+        line
+    }
+}
+
+/// Compteurs d'activité additionnés sur toutes les fourmis d'un type
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct AntTypeStats {
+    pub ants: u32,
+    pub trips: u64,
+    pub distance: u64,
+    pub food_delivered: u64,
+    pub ticks_alive: u64,
+    // Cases parcourues pendant les allers-retours terminés, et somme des plus courts
+    pub trip_distance: u64,
+    pub optimal_distance: u64,
+}
+
+impl AntTypeStats {
+    /// Totaux de chaque type, dans l'ordre de `AntsType::all()`
+    pub fn by_type(ants: &[Ant]) -> [AntTypeStats; 3] {
+        let mut totals = [AntTypeStats::default(); 3];
+        for ant in ants {
+            let total = &mut totals[ant.ant_type.index()];
+            total.ants += 1;
+            total.trips += ant.stats.trips as u64;
+            total.distance += ant.stats.distance as u64;
+            total.food_delivered += ant.stats.food_delivered as u64;
+            total.ticks_alive += ant.stats.ticks_alive as u64;
+            total.trip_distance += ant.stats.trip_distance as u64;
+            total.optimal_distance += ant.stats.optimal_distance as u64;
+        }
+        totals
+    }
+
+    /// Somme des compteurs de plusieurs types
+    pub fn combined(stats: &[AntTypeStats]) -> AntTypeStats {
+        stats
+            .iter()
+            .fold(AntTypeStats::default(), |total, s| AntTypeStats {
+                ants: total.ants + s.ants,
+                trips: total.trips + s.trips,
+                distance: total.distance + s.distance,
+                food_delivered: total.food_delivered + s.food_delivered,
+                ticks_alive: total.ticks_alive + s.ticks_alive,
+                trip_distance: total.trip_distance + s.trip_distance,
+                optimal_distance: total.optimal_distance + s.optimal_distance,
+            })
+    }
+
+    /// Nourriture livrée pour 1000 ticks passés sur la carte : l'efficacité du type à
+    /// rapporter de la nourriture, quel que soit le nombre de fourmis
+    pub fn food_per_kilotick(&self) -> f64 {
+        if self.ticks_alive == 0 {
+            return 0.0;
+        }
+        self.food_delivered as f64 * 1000.0 / self.ticks_alive as f64
+    }
+
+    /// Cases parcourues en moyenne pour un aller-retour
+    pub fn distance_per_trip(&self) -> Option<f64> {
+        (self.trips > 0).then(|| self.distance as f64 / self.trips as f64)
+    }
+
+    /// Longueur moyenne d'un aller-retour nid-nourriture-nid terminé, errance comprise
+    pub fn mean_trip_length(&self) -> Option<f64> {
+        (self.trips > 0).then(|| self.trip_distance as f64 / self.trips as f64)
+    }
+
+    /// Cases parcourues en trop par aller-retour par rapport au plus court aller-retour de
+    /// la colonie (le « regret » : 0 pour une colonie qui ne prend que des raccourcis)
+    pub fn trip_regret(&self) -> Option<f64> {
+        (self.trips > 0)
+            .then(|| (self.trip_distance as f64 - self.optimal_distance as f64) / self.trips as f64)
+    }
+}
+
+// Collecteur de métriques alimenté par game_step
+#[derive(Default)]
+pub struct MetricsCollector {
+    path: Option<String>,
+    pending: Vec<TickMetrics>,
+    file_started: bool,
+    current: TickMetrics,
+    last: Option<TickMetrics>,
+    pub total_food_delivered: u64,
+    pub total_deaths: u64,
+    pub total_births: u64,
+}
+
+impl MetricsCollector {
+    pub fn new(path: Option<String>) -> Self {
+        MetricsCollector {
+            path,
+            ..Default::default()
+        }
+    }
+
+    pub fn record_delivery(&mut self, amount: u32) {
+        self.current.food_delivered += amount;
+        self.total_food_delivered += amount as u64;
+    }
+
+    pub fn record_death(&mut self) {
+        self.current.deaths += 1;
+        self.total_deaths += 1;
+    }
+
+    pub fn record_spawn(&mut self) {
+        self.current.ants_spawned += 1;
+    }
+
+    pub fn record_birth(&mut self) {
+        self.current.births += 1;
+        self.total_births += 1;
+    }
+
+    // Clôturer le tick courant et préparer le suivant
+    pub fn end_tick(
+        &mut self,
+        tick: usize,
+        active_ants: u32,
+        nest_food: u32,
+        avg_q_magnitude: f32,
+        mean_age: f32,
+        per_type: [AntTypeStats; 3],
+    ) {
+        let mut metrics = std::mem::take(&mut self.current);
+        metrics.tick = tick;
+        metrics.active_ants = active_ants;
+        metrics.nest_food = nest_food;
+        metrics.avg_q_magnitude = avg_q_magnitude;
+        metrics.mean_age = mean_age;
+        [metrics.explorers, metrics.pickers, metrics.fighters] = per_type;
+        let all = AntTypeStats::combined(&per_type);
+        metrics.mean_trip_length = all.mean_trip_length();
+        metrics.trip_regret = all.trip_regret();
+        self.last = Some(metrics.clone());
+
+        if self.path.is_none() {
+            return;
+        }
+        self.pending.push(metrics);
+        if self.pending.len() >= FLUSH_EVERY {
+            if let Err(e) = self.flush() {
+                // On arrête l'export plutôt que de spammer la console à chaque tick
+                tracing::error!(kind = "system", tick, "{}", e);
+                self.path = None;
+            }
+        }
+    }
+
+    pub fn last(&self) -> Option<&TickMetrics> {
+        self.last.as_ref()
+    }
+
+    // Écrire les métriques en attente dans le fichier de sortie
+    pub fn flush(&mut self) -> Result<(), String> {
+        let path = match &self.path {
+            Some(path) => path.clone(),
+            None => return Ok(()),
+        };
+
+        // Le premier flush remplace le fichier, les suivants écrivent à la suite
+        let file = if self.file_started {
+            OpenOptions::new().append(true).open(&path)
+        } else {
+            File::create(&path)
+        }
+        .map_err(|e| format!("Impossible d'ouvrir {}: {}", path, e))?;
+        let mut writer = BufWriter::new(file);
+        let write_err = |e: std::io::Error| format!("Impossible d'écrire {}: {}", path, e);
+
+        let format = MetricsFormat::from_path(&path);
+        if !self.file_started && format == MetricsFormat::Csv {
+            writeln!(writer, "{}", TickMetrics::CSV_HEADER).map_err(write_err)?;
+        }
+        self.file_started = true;
+
+        for metrics in self.pending.drain(..) {
+            let line = match format {
+                MetricsFormat::Csv => metrics.to_csv(),
+                MetricsFormat::Ndjson => serde_json::to_string(&metrics)
+                    .map_err(|e| format!("Impossible de sérialiser les métriques: {}", e))?,
+            };
+            writeln!(writer, "{}", line).map_err(write_err)?;
+        }
+        writer.flush().map_err(write_err)
+    }
+}
+
+/// Passages des fourmis sur chaque case depuis le début de la partie : là où elles
+/// marchent vraiment, indépendamment des valeurs Q
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TrafficMap {
+    width: u32,
+    counts: Vec<u32>,
+}
+
+impl TrafficMap {
+    pub fn new(width: u32, height: u32) -> Self {
+        TrafficMap {
+            width,
+            counts: vec![0; GridShape::new(width, height).len()],
+        }
+    }
+
+    // Rang de la case, None hors de la carte (la hauteur se déduit du nombre de cases)
+    fn index(&self, x: u32, y: u32) -> Option<usize> {
+        let height = self.counts.len() / self.width.max(1) as usize;
+        GridShape::new(self.width, height as u32)
+            .index(Coord::new(x, y))
+            .map(|index| index.as_usize())
+    }
+
+    /// Une fourmi vient d'arriver sur (x, y)
+    pub fn record(&mut self, x: u32, y: u32) {
+        if let Some(i) = self.index(x, y) {
+            self.counts[i] = self.counts[i].saturating_add(1);
+        }
+    }
+
+    /// Passages sur (x, y), 0 hors de la carte
+    pub fn get(&self, x: u32, y: u32) -> u32 {
+        self.index(x, y).map_or(0, |i| self.counts[i])
+    }
+
+    /// Plus grand nombre de passages sur une case
+    pub fn max(&self) -> u32 {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+}
+
+/// Point des courbes de suivi de l'apprentissage
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize)]
+pub struct StatsPoint {
+    pub tick: usize,
+    // Nourriture stockée dans l'ensemble des nids
+    pub nest_food: u32,
+    pub active_ants: u32,
+    // Morts cumulées depuis le début de la partie
+    pub total_deaths: u64,
+    pub avg_q_magnitude: f32,
+    // Fourmis appartenant aux colonies, sur la carte ou au nid
+    pub population: u32,
+    // Âge moyen de ces fourmis, en ticks
+    #[serde(default)]
+    pub mean_age: f32,
+}
+
+/// Historique des courbes, de taille bornée : une fois plein, un point sur deux est oublié
+/// et l'écart entre deux points double, de sorte que toute la partie reste visible
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StatsHistory {
+    points: Vec<StatsPoint>,
+    stride: usize,
+}
+
+impl Default for StatsHistory {
+    fn default() -> Self {
+        StatsHistory {
+            points: Vec::new(),
+            stride: 1,
+        }
+    }
+}
+
+impl StatsHistory {
+    // Ce tick fait partie des points échantillonnés (évite de calculer les autres)
+    pub fn wants(&self, tick: usize) -> bool {
+        tick.is_multiple_of(self.stride)
+    }
+
+    pub fn record(&mut self, point: StatsPoint) {
+        if !self.wants(point.tick) {
+            return;
+        }
+        // La partie repart d'un tick passé : l'ancien futur n'a plus cours
+        while self.points.last().is_some_and(|p| p.tick >= point.tick) {
+            self.points.pop();
+        }
+        if self.points.len() >= STATS_CAPACITY {
+            self.stride *= 2;
+            let stride = self.stride;
+            self.points.retain(|p| p.tick.is_multiple_of(stride));
+            if !self.wants(point.tick) {
+                return;
+            }
+        }
+        self.points.push(point);
+    }
+
+    pub fn points(&self) -> &[StatsPoint] {
+        &self.points
+    }
+}
+
+/// Courbes tracées par le panneau de statistiques de la GUI
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatsSeries {
+    NestFood,
+    ActiveAnts,
+    Population,
+    MeanAge,
+    Deaths,
+    MeanQ,
+}
+
+impl StatsSeries {
+    pub fn all() -> [StatsSeries; 6] {
+        [
+            StatsSeries::NestFood,
+            StatsSeries::ActiveAnts,
+            StatsSeries::Population,
+            StatsSeries::MeanAge,
+            StatsSeries::Deaths,
+            StatsSeries::MeanQ,
+        ]
+    }
+
+    /// Clé du titre traduit, qui sert aussi d'identifiant au graphique
+    pub fn key(&self) -> &'static str {
+        match self {
+            StatsSeries::NestFood => "plots.nest_food",
+            StatsSeries::ActiveAnts => "plots.active_ants",
+            StatsSeries::Population => "plots.population",
+            StatsSeries::MeanAge => "plots.mean_age",
+            StatsSeries::Deaths => "plots.deaths",
+            StatsSeries::MeanQ => "plots.mean_q",
+        }
+    }
+
+    /// Nom stable, indépendant de la langue (fichiers exportés)
+    pub fn name(&self) -> &'static str {
+        match self {
+            StatsSeries::NestFood => "nest_food",
+            StatsSeries::ActiveAnts => "active_ants",
+            StatsSeries::Population => "population",
+            StatsSeries::MeanAge => "mean_age",
+            StatsSeries::Deaths => "deaths",
+            StatsSeries::MeanQ => "mean_q",
+        }
+    }
+
+    pub fn value(&self, point: &StatsPoint) -> f64 {
+        match self {
+            StatsSeries::NestFood => point.nest_food as f64,
+            StatsSeries::ActiveAnts => point.active_ants as f64,
+            StatsSeries::Population => point.population as f64,
+            StatsSeries::MeanAge => point.mean_age as f64,
+            StatsSeries::Deaths => point.total_deaths as f64,
+            StatsSeries::MeanQ => point.avg_q_magnitude as f64,
+        }
+    }
+
+    /// Points (tick, valeur) de la courbe d'une partie
+    pub fn points(&self, stats: &[StatsPoint]) -> Vec<[f64; 2]> {
+        stats
+            .iter()
+            .map(|point| [point.tick as f64, self.value(point)])
+            .collect()
+    }
+
+    /// CSV de la courbe de chaque partie (une colonne par partie, en mode comparaison) ;
+    /// une case reste vide quand une partie n'a pas de point à ce tick
+    pub fn to_csv(&self, runs: &[&[StatsPoint]]) -> String {
+        let mut rows: BTreeMap<usize, Vec<Option<f64>>> = BTreeMap::new();
+        for (run, stats) in runs.iter().enumerate() {
+            for point in stats.iter() {
+                rows.entry(point.tick)
+                    .or_insert_with(|| vec![None; runs.len()])[run] = Some(self.value(point));
+            }
+        }
+        let mut header = vec!["tick".to_string()];
+        if runs.len() == 1 {
+            header.push(self.name().to_string());
+        } else {
+            header.extend((1..=runs.len()).map(|run| format!("simulation_{}", run)));
+        }
+        let mut csv = header.join(",") + "\n";
+        for (tick, values) in rows {
+            let cells: Vec<String> = values
+                .iter()
+                .map(|value| value.map_or_else(String::new, |v| v.to_string()))
+                .collect();
+            csv += &format!("{},{}\n", tick, cells.join(","));
+        }
+        csv
+    }
+}
@@ -0,0 +1,255 @@
+//! Observations encodées pour les agents externes
+//!
+//! [`AntsEnv`](crate::env::AntsEnv) décrit chaque fourmi par une structure Rust ; un
+//! apprenant externe (réseau de neurones, bibliothèque de RL) attend plutôt des nombres dans
+//! un ordre fixe. [`ObservationBuilder`] produit, selon `--observation` :
+//!
+//! - `features` : un vecteur de [`FEATURE_LEN`] valeurs par fourmi, dans l'ordre de
+//!   `Observation::ants` : position normalisée, présence sur la carte, type et mode
+//!   (one-hot), charge, puis la fenêtre 3x3 des cases autour d'elle ([`WINDOW_CHANNELS`]
+//!   indicateurs par case, ligne après ligne du coin haut gauche au coin bas droit) et enfin
+//!   les valeurs Q des cinq actions (ordre de `Action::all`) apprises par sa colonie. Une
+//!   fourmi hors de la carte n'a que des zéros ;
+//! - `grid` : un tenseur `channels x height x width` de toute la carte, rangé canal après
+//!   canal puis ligne après ligne : les [`TILE_CHANNELS`] canaux des cases, puis
+//!   [`COLONY_CHANNELS`] canaux par colonie (voir [`grid_channels`]).
+//!
+//! Les indices de chaque trait sont fixés par les constantes de ce module et ne changent
+//! qu'avec une nouvelle version mineure.
+
+use crate::ant::{Ant, AntsMode, AntsType};
+use crate::ants_game_manager::AntsGameManager;
+use crate::coord::Coord;
+use crate::grid::Grid;
+use crate::pheromone::{Action, ACTION_COUNT};
+use crate::q_table::StateFeatures;
+use crate::tile::TileType;
+use serde::{Deserialize, Serialize};
+
+/// Indicateurs de chaque case de la fenêtre 3x3 d'une fourmi : bloquée (mur ou hors de la
+/// carte), nourriture, nid de sa colonie, nid d'une autre colonie, zone mortelle, boue, tunnel
+pub const WINDOW_CHANNELS: usize = 7;
+/// Traits propres à la fourmi, avant la fenêtre : x, y, sur la carte, prête à jouer, type
+/// (3), mode (2), chargée
+pub const ANT_FEATURES: usize = 10;
+/// Longueur du vecteur d'une fourmi
+pub const FEATURE_LEN: usize = ANT_FEATURES + 9 * WINDOW_CHANNELS + ACTION_COUNT;
+
+/// Canaux des cases dans le tenseur de la carte : mur, zone mortelle, boue, tunnel, nourriture
+pub const TILE_CHANNELS: usize = 5;
+/// Canaux de chaque colonie : nid, fourmis, fourmis chargées, Q max de la piste vers la
+/// nourriture, Q max de la piste vers le nid
+pub const COLONY_CHANNELS: usize = 5;
+
+/// Encodage des observations renvoyées par `AntsEnv`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ObservationFormat {
+    /// Aucun : seule la description structurée des fourmis est fournie
+    #[default]
+    None,
+    /// Un vecteur de traits par fourmi
+    Features,
+    /// Un tenseur de toute la carte
+    Grid,
+}
+
+impl ObservationFormat {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "none" => Some(ObservationFormat::None),
+            "features" => Some(ObservationFormat::Features),
+            "grid" => Some(ObservationFormat::Grid),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ObservationFormat::None => "none",
+            ObservationFormat::Features => "features",
+            ObservationFormat::Grid => "grid",
+        }
+    }
+}
+
+/// Observation encodée d'un tick
+#[derive(Clone, Debug, PartialEq)]
+pub enum EncodedObservation {
+    /// `FEATURE_LEN` valeurs par fourmi, dans l'ordre des fourmis du manager
+    Features(Vec<Vec<f32>>),
+    /// Tenseur de toute la carte
+    Grid(GridTensor),
+}
+
+/// Tenseur `channels x height x width` d'une carte : la valeur du canal c en (x, y) est au
+/// rang `c * height * width + y * width + x` de `data`
+#[derive(Clone, Debug, PartialEq)]
+pub struct GridTensor {
+    pub channels: usize,
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<f32>,
+}
+
+/// Nombre de canaux du tenseur d'une carte à `colonies` colonies
+pub fn grid_channels(colonies: usize) -> usize {
+    TILE_CHANNELS + COLONY_CHANNELS * colonies
+}
+
+/// Construit l'observation encodée d'une partie dans le format choisi
+#[derive(Clone, Copy, Debug)]
+pub struct ObservationBuilder {
+    format: ObservationFormat,
+}
+
+impl ObservationBuilder {
+    pub fn new(format: ObservationFormat) -> Self {
+        ObservationBuilder { format }
+    }
+
+    pub fn format(&self) -> ObservationFormat {
+        self.format
+    }
+
+    /// Observation du tick courant, None avec le format `none`
+    pub fn build(&self, manager: &AntsGameManager) -> Option<EncodedObservation> {
+        match self.format {
+            ObservationFormat::None => None,
+            ObservationFormat::Features => Some(EncodedObservation::Features(
+                manager
+                    .ants()
+                    .iter()
+                    .map(|ant| ant_features(manager, ant))
+                    .collect(),
+            )),
+            ObservationFormat::Grid => Some(EncodedObservation::Grid(grid_tensor(manager))),
+        }
+    }
+}
+
+/// Vecteur de `FEATURE_LEN` traits d'une fourmi (zéros si elle n'est pas sur la carte)
+pub fn ant_features(manager: &AntsGameManager, ant: &Ant) -> Vec<f32> {
+    let mut features = vec![0.0; FEATURE_LEN];
+    let Some(coord) = ant.coord() else {
+        return features;
+    };
+    let grid = manager.grid();
+    let flag = |on: bool| if on { 1.0 } else { 0.0 };
+    features[0] = coord.x as f32 / grid.get_width().max(1) as f32;
+    features[1] = coord.y as f32 / grid.get_height().max(1) as f32;
+    features[2] = 1.0;
+    features[3] = flag(ant.cooldown == 0);
+    features[4 + ant.ant_type.index()] = 1.0;
+    features[7] = flag(ant.mode == AntsMode::FINDING);
+    features[8] = flag(ant.mode == AntsMode::RETURNING);
+    features[9] = flag(ant.current_charge > 0);
+
+    let window = &mut features[ANT_FEATURES..ANT_FEATURES + 9 * WINDOW_CHANNELS];
+    for (cell, channels) in window.chunks_mut(WINDOW_CHANNELS).enumerate() {
+        let dx = cell as u32 % 3;
+        let dy = cell as u32 / 3;
+        // Un pas au-delà du bord haut ou gauche sort de la carte, comme `Coord::step`
+        let target = Coord::new(
+            (coord.x + dx).wrapping_sub(1),
+            (coord.y + dy).wrapping_sub(1),
+        );
+        window_cell(grid, target, ant.colony, channels);
+    }
+
+    let colony = &manager.colonies()[ant.colony];
+    let state = StateFeatures::observe(
+        grid,
+        coord.into(),
+        ant.mode,
+        ant.scope,
+        colony.table(ant.ant_type).layout(),
+    );
+    let backend = colony.backend(ant.ant_type, state);
+    for (value, action) in features[FEATURE_LEN - ACTION_COUNT..]
+        .iter_mut()
+        .zip(Action::all())
+    {
+        *value = backend.get_q(grid, state, action);
+    }
+    features
+}
+
+// Indicateurs d'une case de la fenêtre d'une fourmi de la colonie `colony`
+fn window_cell(grid: &Grid, coord: Coord, colony: usize, channels: &mut [f32]) {
+    let Some(tile) = grid.get_tile(coord.into()) else {
+        channels[0] = 1.0;
+        return;
+    };
+    let channel = match tile.tile_type {
+        TileType::Default => return,
+        TileType::Wall => 0,
+        TileType::FoodSource { .. } => 1,
+        TileType::Nest { colony: owner, .. } if owner == colony => 2,
+        TileType::Nest { .. } => 3,
+        TileType::DeathZone => 4,
+        TileType::Mud { .. } => 5,
+        TileType::Tunnel { .. } => 6,
+    };
+    channels[channel] = 1.0;
+}
+
+/// Tenseur de toute la carte (voir le format `grid`)
+pub fn grid_tensor(manager: &AntsGameManager) -> GridTensor {
+    let grid = manager.grid();
+    let shape = grid.shape();
+    let cells = shape.len();
+    let colonies = manager.colonies();
+    let channels = grid_channels(colonies.len());
+    let mut data = vec![0.0; channels * cells];
+
+    for coord in shape.coords() {
+        let cell = shape.index_of(coord).as_usize();
+        let Some(tile) = grid.get_tile(coord.into()) else {
+            continue;
+        };
+        let channel = match tile.tile_type {
+            TileType::Wall => Some(0),
+            TileType::DeathZone => Some(1),
+            TileType::Mud { .. } => Some(2),
+            TileType::Tunnel { .. } => Some(3),
+            TileType::FoodSource { .. } => Some(4),
+            TileType::Nest { colony, .. } => Some(TILE_CHANNELS + colony * COLONY_CHANNELS),
+            TileType::Default => None,
+        };
+        if let Some(channel) = channel.filter(|&channel| channel < channels) {
+            data[channel * cells + cell] = 1.0;
+        }
+    }
+
+    for ant in manager.ants() {
+        let Some(index) = ant.coord().and_then(|coord| shape.index(coord)) else {
+            continue;
+        };
+        let base = TILE_CHANNELS + ant.colony * COLONY_CHANNELS;
+        data[(base + 1) * cells + index.as_usize()] += 1.0;
+        if ant.current_charge > 0 {
+            data[(base + 2) * cells + index.as_usize()] += 1.0;
+        }
+    }
+
+    for (colony, state) in colonies.iter().enumerate() {
+        let base = TILE_CHANNELS + colony * COLONY_CHANNELS;
+        // Comme à l'affichage, les pistes montrées sont celles des exploratrices
+        let (food, nest) = state.maps(AntsType::EXPLORER);
+        for coord in shape.coords() {
+            let cell = shape.index_of(coord).as_usize();
+            if !grid.is_walkable(coord.x, coord.y) {
+                continue;
+            }
+            data[(base + 3) * cells + cell] = food.get_max_q(coord.x, coord.y, grid);
+            data[(base + 4) * cells + cell] = nest.get_max_q(coord.x, coord.y, grid);
+        }
+    }
+
+    GridTensor {
+        channels,
+        width: shape.width,
+        height: shape.height,
+        data,
+    }
+}
@@ -0,0 +1,159 @@
+use crate::ant::AntsType;
+use crate::metrics::TickMetrics;
+
+// Cause de la mort d'une fourmi
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DeathCause {
+    DeathZone,
+    Predator,
+    Combat,
+    Starvation,
+    OldAge,
+}
+
+impl DeathCause {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DeathCause::DeathZone => "zone mortelle",
+            DeathCause::Predator => "prédateur",
+            DeathCause::Combat => "combat",
+            DeathCause::Starvation => "faim",
+            DeathCause::OldAge => "vieillesse",
+        }
+    }
+}
+
+// Nourriture ramassée sur une source ou déposée au nid
+#[derive(Clone, Debug)]
+pub struct FoodEvent {
+    // Index dans `ants()` au moment de l'événement, et identifiant stable de la fourmi
+    pub ant_index: usize,
+    pub ant_id: u32,
+    pub colony: usize,
+    pub position: (u32, u32),
+    pub amount: u32,
+}
+
+#[derive(Clone, Debug)]
+pub struct DeathEvent {
+    // Index dans `ants()` au moment de l'événement, et identifiant stable de la fourmi
+    pub ant_index: usize,
+    pub ant_id: u32,
+    pub colony: usize,
+    pub position: (u32, u32),
+    pub cause: DeathCause,
+}
+
+#[derive(Clone, Debug)]
+pub struct SpawnEvent {
+    // Index dans `ants()` au moment de l'événement, et identifiant stable de la fourmi
+    pub ant_index: usize,
+    pub ant_id: u32,
+    pub colony: usize,
+    pub ant_type: AntsType,
+    pub position: (u32, u32),
+}
+
+/// Reçoit les événements de la simulation ; chaque méthode ne fait rien par défaut
+pub trait SimulationObserver: Send {
+    fn on_tick(&mut self, _metrics: &TickMetrics) {}
+    fn on_food_pickup(&mut self, _event: &FoodEvent) {}
+    fn on_food_delivered(&mut self, _event: &FoodEvent) {}
+    fn on_ant_death(&mut self, _event: &DeathEvent) {}
+    fn on_spawn(&mut self, _event: &SpawnEvent) {}
+}
+
+// Adaptateurs pour enregistrer une simple closure sur un seul type d'événement
+struct OnTick<F>(F);
+struct OnFoodPickup<F>(F);
+struct OnFoodDelivered<F>(F);
+struct OnAntDeath<F>(F);
+struct OnSpawn<F>(F);
+
+impl<F: FnMut(&TickMetrics) + Send> SimulationObserver for OnTick<F> {
+    fn on_tick(&mut self, metrics: &TickMetrics) {
+        (self.0)(metrics)
+    }
+}
+
+impl<F: FnMut(&FoodEvent) + Send> SimulationObserver for OnFoodPickup<F> {
+    fn on_food_pickup(&mut self, event: &FoodEvent) {
+        (self.0)(event)
+    }
+}
+
+impl<F: FnMut(&FoodEvent) + Send> SimulationObserver for OnFoodDelivered<F> {
+    fn on_food_delivered(&mut self, event: &FoodEvent) {
+        (self.0)(event)
+    }
+}
+
+impl<F: FnMut(&DeathEvent) + Send> SimulationObserver for OnAntDeath<F> {
+    fn on_ant_death(&mut self, event: &DeathEvent) {
+        (self.0)(event)
+    }
+}
+
+impl<F: FnMut(&SpawnEvent) + Send> SimulationObserver for OnSpawn<F> {
+    fn on_spawn(&mut self, event: &SpawnEvent) {
+        (self.0)(event)
+    }
+}
+
+// Liste des observateurs enregistrés sur un manager
+#[derive(Default)]
+pub struct Observers {
+    observers: Vec<Box<dyn SimulationObserver>>,
+}
+
+impl Observers {
+    pub fn add(&mut self, observer: Box<dyn SimulationObserver>) {
+        self.observers.push(observer);
+    }
+
+    pub fn add_on_tick(&mut self, f: impl FnMut(&TickMetrics) + Send + 'static) {
+        self.add(Box::new(OnTick(f)));
+    }
+
+    pub fn add_on_food_pickup(&mut self, f: impl FnMut(&FoodEvent) + Send + 'static) {
+        self.add(Box::new(OnFoodPickup(f)));
+    }
+
+    pub fn add_on_food_delivered(&mut self, f: impl FnMut(&FoodEvent) + Send + 'static) {
+        self.add(Box::new(OnFoodDelivered(f)));
+    }
+
+    pub fn add_on_ant_death(&mut self, f: impl FnMut(&DeathEvent) + Send + 'static) {
+        self.add(Box::new(OnAntDeath(f)));
+    }
+
+    pub fn add_on_spawn(&mut self, f: impl FnMut(&SpawnEvent) + Send + 'static) {
+        self.add(Box::new(OnSpawn(f)));
+    }
+
+    pub fn tick(&mut self, metrics: &TickMetrics) {
+        self.observers.iter_mut().for_each(|o| o.on_tick(metrics));
+    }
+
+    pub fn food_pickup(&mut self, event: FoodEvent) {
+        self.observers
+            .iter_mut()
+            .for_each(|o| o.on_food_pickup(&event));
+    }
+
+    pub fn food_delivered(&mut self, event: FoodEvent) {
+        self.observers
+            .iter_mut()
+            .for_each(|o| o.on_food_delivered(&event));
+    }
+
+    pub fn ant_death(&mut self, event: DeathEvent) {
+        self.observers
+            .iter_mut()
+            .for_each(|o| o.on_ant_death(&event));
+    }
+
+    pub fn spawn(&mut self, event: SpawnEvent) {
+        self.observers.iter_mut().for_each(|o| o.on_spawn(&event));
+    }
+}
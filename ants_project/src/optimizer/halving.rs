@@ -0,0 +1,165 @@
+// Élimination successive (successive halving, façon Hyperband) : toutes les combinaisons
+// jouent un petit budget de ticks, seule la meilleure fraction continue avec un budget
+// multiplié par `eta`, jusqu'à la limite de ticks. Les simulations des survivantes sont
+// prolongées là où elles s'étaient arrêtées plutôt que relancées depuis le début.
+use super::objective::{Goal, RunAverages, RunMetrics};
+use super::progress::Progress;
+use super::results::SimulationResult;
+use super::stats::SampleStats;
+use crate::prelude::SimulationRunner;
+use rayon::prelude::*;
+use std::time::Instant;
+
+// Un tour : budget de ticks atteint par chaque simulation et nombre de combinaisons en lice
+#[derive(Debug, Clone, Copy)]
+pub struct Round {
+    pub budget: u64,
+    pub candidates: usize,
+}
+
+// Tours successifs : le budget est multiplié par `eta` et le nombre de combinaisons divisé
+// par `eta`. La dernière survivante va directement jusqu'à `max_ticks`.
+pub fn schedule(candidates: usize, min_ticks: u64, max_ticks: u64, eta: u32) -> Vec<Round> {
+    let mut rounds = vec![Round {
+        budget: min_ticks.min(max_ticks),
+        candidates,
+    }];
+    loop {
+        let last = rounds[rounds.len() - 1];
+        if last.budget >= max_ticks {
+            return rounds;
+        }
+        let candidates = last.candidates.div_ceil(eta as usize).max(1);
+        let budget = if candidates == 1 {
+            max_ticks
+        } else {
+            last.budget.saturating_mul(eta as u64).min(max_ticks)
+        };
+        rounds.push(Round { budget, candidates });
+    }
+}
+
+// Nombre total de simulations (tour × combinaison × répétition), pour la progression
+pub fn simulation_count(rounds: &[Round], repeats: u32) -> usize {
+    rounds.iter().map(|r| r.candidates * repeats as usize).sum()
+}
+
+struct Candidate {
+    params: Vec<f32>,
+    // Une simulation par répétition, conservée d'un tour à l'autre
+    runners: Vec<SimulationRunner>,
+    result: Option<SimulationResult>,
+}
+
+// Exécute le calendrier et renvoie les résultats déjà classés : d'abord les combinaisons
+// allées le plus loin, chaque groupe trié par score. Une combinaison éliminée garde le
+// résultat du dernier budget qu'elle a joué, transmis à `on_result` au moment de
+// l'élimination ; le booléen indique si ce résultat porte sur le budget complet.
+#[allow(clippy::too_many_arguments)]
+pub fn run(
+    combinations: Vec<Vec<f32>>,
+    rounds: &[Round],
+    repeats: u32,
+    goal: Goal,
+    new_runner: &(dyn Fn(&[f32], u64) -> SimulationRunner + Sync),
+    label: &(dyn Fn(&[f32]) -> String + Sync),
+    progress: &Progress,
+    on_result: &(dyn Fn(&SimulationResult, bool) + Sync),
+) -> Vec<SimulationResult> {
+    let objective = goal.objective;
+    let mut candidates: Vec<Candidate> = combinations
+        .into_iter()
+        .map(|params| Candidate {
+            runners: (0..repeats as u64)
+                .map(|seed| new_runner(&params, seed))
+                .collect(),
+            params,
+            result: None,
+        })
+        .collect();
+    // Groupes d'éliminées, du premier tour au dernier
+    let mut eliminated: Vec<Vec<SimulationResult>> = Vec::new();
+
+    for (index, round) in rounds.iter().enumerate() {
+        candidates.par_iter_mut().for_each(|candidate| {
+            let name = label(&candidate.params);
+            let runs: Vec<RunMetrics> = candidate
+                .runners
+                .par_iter_mut()
+                .enumerate()
+                .map(|(seed, runner)| {
+                    let start = Instant::now();
+                    let already_timed_out = runner.timed_out();
+                    runner.run_ticks(round.budget.saturating_sub(runner.tick_count()));
+                    let run = RunMetrics::from_runner(runner);
+                    // Une simulation abandonnée le reste aux tours suivants
+                    let outcome = if run.timed_out {
+                        if !already_timed_out {
+                            progress.simulation_timed_out();
+                        }
+                        "DNF".to_string()
+                    } else {
+                        format!(
+                            "{} à {} ticks",
+                            objective.format(objective.value(&run)),
+                            round.budget
+                        )
+                    };
+                    progress.simulation_done(&name, seed as u64, &outcome, start.elapsed());
+                    run
+                })
+                .collect();
+            let values: Vec<f64> = runs.iter().map(|run| objective.value(run)).collect();
+            candidate.result = Some(SimulationResult {
+                params: candidate.params.clone(),
+                stats: SampleStats::from_samples(&values),
+                averages: RunAverages::from_runs(&runs),
+            });
+        });
+
+        candidates.sort_by(|a, b| {
+            let (a, b) = (a.result.as_ref().unwrap(), b.result.as_ref().unwrap());
+            compare(goal, a, b)
+        });
+        let keep = rounds
+            .get(index + 1)
+            .map_or(candidates.len(), |next| next.candidates);
+        let dropped: Vec<SimulationResult> = candidates
+            .drain(keep.min(candidates.len())..)
+            .filter_map(|candidate| candidate.result)
+            .collect();
+        let complete = round.budget >= rounds[rounds.len() - 1].budget;
+        for result in &dropped {
+            on_result(result, complete);
+        }
+        eliminated.push(dropped);
+    }
+
+    let mut ranked: Vec<SimulationResult> = Vec::new();
+    for candidate in candidates {
+        if let Some(result) = candidate.result {
+            on_result(&result, true);
+            ranked.push(result);
+        }
+    }
+    for group in eliminated.into_iter().rev() {
+        ranked.extend(group);
+    }
+    ranked
+}
+
+// Médiane, puis moyenne, puis nourriture livrée : avant la limite de ticks, les parties non
+// terminées sont toutes à égalité sur l'objectif `ticks`, la nourriture les départage
+fn compare(goal: Goal, a: &SimulationResult, b: &SimulationResult) -> std::cmp::Ordering {
+    goal.score(a.stats.median)
+        .total_cmp(&goal.score(b.stats.median))
+        .then(
+            goal.score(a.stats.mean)
+                .total_cmp(&goal.score(b.stats.mean)),
+        )
+        .then(
+            b.averages
+                .food_delivered
+                .total_cmp(&a.averages.food_delivered),
+        )
+}
@@ -0,0 +1,64 @@
+// Cartes communes à toutes les combinaisons : générées une fois au démarrage (ou chargées
+// depuis l'éditeur), pour que les écarts de score viennent des paramètres et non de la carte
+use crate::cli_args::SimulationConfig;
+use crate::grid::Grid;
+use crate::map_editor::MapEditor;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+// Cartes jouées par les simulations
+pub enum MapSet {
+    // Construites au démarrage, la répétition `r` joue sur la carte `r % N`
+    Fixed(Vec<Grid>),
+    // Générées pour chaque simulation avec les proportions de la combinaison (paramètres
+    // map_* de l'espace de recherche) : la répétition `r` utilise la graine `seed + r % count`
+    Generated {
+        count: u32,
+        seed: u64,
+        width: u32,
+        height: u32,
+    },
+}
+
+impl MapSet {
+    // Carte de la répétition `run` pour une combinaison appliquée sur `config`
+    pub fn map(&self, config: &SimulationConfig, run: u64) -> Grid {
+        match self {
+            MapSet::Fixed(maps) => maps[run as usize % maps.len()].clone(),
+            MapSet::Generated {
+                count,
+                seed,
+                width,
+                height,
+            } => {
+                let mut rng = StdRng::seed_from_u64(seed + run % *count as u64);
+                Grid::new_random_with_params(*width, *height, &config.map_gen, &mut rng)
+            }
+        }
+    }
+}
+
+// `count` cartes aléatoires, de graines `seed`, `seed + 1`, ...
+pub fn generate(count: u32, seed: u64, width: u32, height: u32) -> Vec<Grid> {
+    (0..count as u64)
+        .map(|i| {
+            let mut rng = StdRng::seed_from_u64(seed + i);
+            Grid::new_random_with_rng(width, height, &mut rng)
+        })
+        .collect()
+}
+
+// Carte dessinée dans l'éditeur et sauvegardée au format JSON
+pub fn load(path: &str) -> Result<Grid, String> {
+    let editor = MapEditor::load_from_file(path)
+        .map_err(|e| format!("Erreur de chargement de la carte {}: {}", path, e))?;
+    let grid = Grid::new_with_tiles(editor.width, editor.height, editor.to_tiles())
+        .map_err(|e| format!("Carte invalide {}: {}", path, e))?;
+    if grid.get_nest_position().is_none() {
+        return Err(format!("La carte {} n'a pas de nid", path));
+    }
+    if !grid.is_food_remaining() {
+        return Err(format!("La carte {} n'a pas de nourriture", path));
+    }
+    Ok(grid)
+}
@@ -0,0 +1,677 @@
+mod halving;
+mod maps;
+mod objective;
+mod plot;
+mod progress;
+mod results;
+mod search_space;
+mod stats;
+mod strategy;
+mod sweep;
+
+use crate::cli_args::{next_arg, next_int, next_parsed};
+use crate::experiment::manifest_path;
+use crate::prelude::*;
+use maps::MapSet;
+pub use objective::Objective;
+use objective::{Goal, RunAverages, RunMetrics, OBJECTIVE_NAMES};
+use progress::Progress;
+// Import magique pour le parallélisme
+use rayon::prelude::*;
+use results::{ResultsLog, SimulationResult};
+use search_space::SearchSpace;
+pub use stats::SampleStats;
+use std::collections::HashMap;
+use std::str::FromStr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use strategy::{GridSearch, RandomSearch, SearchStrategy, TpeSearch};
+pub use sweep::{Sweep, SweepPoint, SweepRange, SweepSettings};
+
+// Nombre de configurations détaillées en fin d'analyse
+const TOP_RESULTS: usize = 5;
+// Dimensions des cartes générées
+const MAP_WIDTH: u32 = 30;
+const MAP_HEIGHT: u32 = 30;
+
+struct OptimizerArgs {
+    search_space: Option<String>,
+    // Nombre de simulations (graines différentes) par combinaison
+    repeats: u32,
+    strategy: String,
+    // Combinaisons évaluées par les stratégies aléatoire et bayésienne
+    samples: usize,
+    // Graine des tirages de la stratégie (distincte des graines des simulations)
+    search_seed: u64,
+    // Fichier de résultats écrit au fil de l'eau (CSV, ou JSON Lines si .json/.jsonl)
+    output: Option<String>,
+    // Fichier d'un balayage interrompu : ses combinaisons ne sont pas réévaluées
+    resume: Option<String>,
+    // Une ligne par simulation terminée
+    verbose: bool,
+    objective: Objective,
+    // Valeur visée de l'objectif : le classement se fait alors sur l'écart à cette valeur
+    target: Option<f64>,
+    // Limite de ticks de chaque simulation (budget fixe pour les objectifs food et efficiency)
+    max_ticks: u64,
+    // Nombre de cartes aléatoires partagées par toutes les combinaisons, et graine de la première
+    maps: u32,
+    map_seed: u64,
+    // Carte dessinée dans l'éditeur, utilisée à la place des cartes aléatoires
+    map: Option<String>,
+    // Élimination successive : budget du premier tour et facteur de réduction
+    halving: bool,
+    min_ticks: u64,
+    eta: u32,
+    // Dossier des cartes de chaleur écrites en fin de balayage
+    plot: Option<String>,
+    // Threads de calcul (0 = un par coeur)
+    threads: usize,
+    // Temps de calcul maximal d'une simulation, au-delà duquel elle est abandonnée (DNF)
+    run_timeout: Option<Duration>,
+}
+
+// Point d'entrée de `ants optimize` : `args` ne contient que les options de la sous-commande
+pub fn run(args: &[String]) -> Result<(), String> {
+    let Some(args) = parse_args(args)? else {
+        return Ok(());
+    };
+    crate::batch::limit_threads(args.threads)?;
+    let start_total = Instant::now();
+    let started_at = SystemTime::now();
+    println!("🚀 Démarrage du Méta-Optimiseur (Mode TURBO - In-Memory)");
+
+    // 1. Définition des hyperparamètres à tester (fichier TOML ou grille par défaut)
+    let search_space = match &args.search_space {
+        Some(path) => SearchSpace::load(path)?,
+        None => SearchSpace::default(),
+    };
+    for param in &search_space.params {
+        println!("   {} : {:?}", param.name, param.domain);
+    }
+
+    let mut strategy: Box<dyn SearchStrategy> = match args.strategy.as_str() {
+        "grid" => Box::new(GridSearch::new(&search_space)),
+        "random" => Box::new(RandomSearch::new(
+            &search_space,
+            args.samples,
+            args.search_seed,
+        )),
+        // Un lot par tour de threads : les propositions suivantes profitent des résultats
+        "bayes" => Box::new(TpeSearch::new(
+            &search_space,
+            args.samples,
+            rayon::current_num_threads(),
+            args.search_seed,
+        )),
+        other => {
+            return Err(format!(
+                "Stratégie inconnue: {} (attendu: grid, random, bayes)",
+                other
+            ))
+        }
+    };
+    let goal = Goal {
+        objective: args.objective,
+        target: args.target,
+    };
+    println!(
+        "⚡ Recherche {} avec {} répétition(s) par combinaison, objectif {} ({})...",
+        strategy.name(),
+        args.repeats,
+        args.objective.name(),
+        goal.direction()
+    );
+
+    // Les cartes sont construites une seule fois : chaque simulation part d'une copie. Quand
+    // l'espace de recherche règle la génération des cartes, chaque combinaison génère les
+    // siennes, avec les mêmes graines que les autres.
+    let maps = match &args.map {
+        Some(_) if search_space.shapes_maps() => {
+            return Err(
+                "--map fournit une carte toute faite : l'espace de recherche ne peut pas régler la génération des cartes (map_*)"
+                    .to_string(),
+            )
+        }
+        Some(path) => {
+            let map = maps::load(path)?;
+            println!(
+                "🗺️  Carte {}x{} chargée depuis {}",
+                map.get_width(),
+                map.get_height(),
+                path
+            );
+            MapSet::Fixed(vec![map])
+        }
+        None if search_space.shapes_maps() => {
+            println!(
+                "🗺️  {} carte(s) {}x{} générée(s) pour chaque combinaison (graines {}..{})",
+                args.maps,
+                MAP_WIDTH,
+                MAP_HEIGHT,
+                args.map_seed,
+                args.map_seed + args.maps as u64
+            );
+            MapSet::Generated {
+                count: args.maps,
+                seed: args.map_seed,
+                width: MAP_WIDTH,
+                height: MAP_HEIGHT,
+            }
+        }
+        None => {
+            println!(
+                "🗺️  {} carte(s) {}x{} générée(s) (graines {}..{})",
+                args.maps,
+                MAP_WIDTH,
+                MAP_HEIGHT,
+                args.map_seed,
+                args.map_seed + args.maps as u64
+            );
+            MapSet::Fixed(maps::generate(
+                args.maps,
+                args.map_seed,
+                MAP_WIDTH,
+                MAP_HEIGHT,
+            ))
+        }
+    };
+
+    let names: Vec<String> = search_space.params.iter().map(|p| p.name.clone()).collect();
+    let opened = match (&args.resume, &args.output) {
+        (Some(path), _) => ResultsLog::resume(path, names, args.objective, args.repeats)
+            .map(|(log, previous)| (Some(log), previous)),
+        (None, Some(path)) => ResultsLog::create(path, names, args.objective, args.repeats)
+            .map(|log| (Some(log), Vec::new())),
+        (None, None) => Ok((None, Vec::new())),
+    };
+    let (log, previous) = opened?;
+    // Configuration commune à toutes les combinaisons ; la répétition n d'une combinaison
+    // est jouée avec la graine n
+    if let Some(path) = args.resume.as_ref().or(args.output.as_ref()) {
+        let config = SimulationConfig {
+            seed: None,
+            ..base_config(0, args.max_ticks)
+        };
+        Manifest::new(
+            ArtifactKind::OptimizerResults,
+            vec![path.clone()],
+            &config,
+            started_at,
+        )
+        .write(&manifest_path(path))?;
+    }
+    // Résultats déjà connus, indexés par la représentation exacte des valeurs
+    let mut known: HashMap<Vec<u32>, SimulationResult> = previous
+        .into_iter()
+        .map(|result| (param_key(&result.params), result))
+        .collect();
+    if !known.is_empty() {
+        println!(
+            "♻️  {} combinaison(s) reprise(s) depuis le fichier de résultats",
+            known.len()
+        );
+    }
+    let log = log.map(Mutex::new);
+
+    // En élimination successive, toutes les combinaisons (grille ou tirage aléatoire) sont
+    // connues dès le départ
+    let halving_plan = args.halving.then(|| {
+        let combinations = strategy.propose();
+        let rounds =
+            halving::schedule(combinations.len(), args.min_ticks, args.max_ticks, args.eta);
+        for round in &rounds {
+            println!(
+                "   ✂️  {} combinaison(s) jusqu'à {} ticks",
+                round.candidates, round.budget
+            );
+        }
+        (combinations, rounds)
+    });
+    let total = match &halving_plan {
+        Some((_, rounds)) => halving::simulation_count(rounds, args.repeats),
+        None => strategy.remaining() * args.repeats as usize,
+    };
+    let progress = Progress::new(total, args.verbose);
+    let save = |result: &SimulationResult| {
+        if let Some(log) = &log {
+            if let Err(e) = log.lock().unwrap().append(result) {
+                eprintln!("{}", e);
+            }
+        }
+    };
+    // `complete` : résultat sur le budget de ticks complet, seul comparable au meilleur
+    let record = |result: &SimulationResult, complete: bool| {
+        if complete {
+            progress.combination_done(
+                &format_params(&search_space, &result.params),
+                goal.score(result.stats.median),
+                args.objective.format(result.stats.median),
+            );
+        }
+        save(result);
+    };
+
+    let objective = args.objective;
+    let results = match halving_plan {
+        Some((combinations, rounds)) => {
+            let new_runner = |params: &[f32], seed: u64| {
+                new_runner(
+                    &search_space,
+                    params,
+                    &maps,
+                    seed,
+                    args.max_ticks,
+                    args.run_timeout,
+                )
+            };
+            let label = |params: &[f32]| format_params(&search_space, params);
+            // Résultats déjà classés : les survivantes du dernier tour en tête
+            halving::run(
+                combinations,
+                &rounds,
+                args.repeats,
+                goal,
+                &new_runner,
+                &label,
+                &progress,
+                &record,
+            )
+        }
+        None => {
+            // 2. EXÉCUTION PARALLÈLE (C'est ici que la magie opère), lot par lot : la stratégie
+            // reçoit les résultats d'un lot avant de proposer le suivant. Les combinaisons déjà
+            // présentes dans le fichier repris sont rendues sans être simulées à nouveau : avec
+            // la même graine de recherche, la stratégie repropose exactement les mêmes lots.
+            let mut results: Vec<SimulationResult> = Vec::new();
+            loop {
+                let batch = strategy.propose();
+                if batch.is_empty() {
+                    break;
+                }
+                let pending: Vec<Vec<f32>> = batch
+                    .iter()
+                    .filter(|params| !known.contains_key(&param_key(params)))
+                    .cloned()
+                    .collect();
+                progress.skip((batch.len() - pending.len()) * args.repeats as usize);
+                let record = |result: &SimulationResult| record(result, true);
+                let batch_results =
+                    evaluate_batch(&search_space, pending, &maps, &args, &progress, &record);
+                for result in batch_results {
+                    known.insert(param_key(&result.params), result);
+                }
+
+                for params in &batch {
+                    let result = known[&param_key(params)].clone();
+                    strategy.observe(&result.params, goal.score(result.stats.median));
+                    results.push(result);
+                }
+            }
+
+            // Classement par médiane, peu sensible à une répétition aberrante, puis par moyenne
+            results.sort_by(|a, b| {
+                goal.score(a.stats.median)
+                    .total_cmp(&goal.score(b.stats.median))
+                    .then(
+                        goal.score(a.stats.mean)
+                            .total_cmp(&goal.score(b.stats.mean)),
+                    )
+            });
+            results
+        }
+    };
+    progress.finish();
+
+    let duration = start_total.elapsed();
+    println!(
+        "✅ {} combinaisons évaluées en {:.2?}",
+        results.len(),
+        duration
+    );
+    if progress.timeouts() > 0 {
+        println!(
+            "⏱️  {} simulation(s) abandonnée(s) par --run-timeout (DNF), comptée(s) comme le pire résultat",
+            progress.timeouts()
+        );
+    }
+
+    if let Some(best) = results.first() {
+        println!("\n🏆 MEILLEURE CONFIGURATION :");
+        for (param, value) in search_space.params.iter().zip(&best.params) {
+            println!("   {:<22}: {}", param.name, value);
+        }
+        let stats = &best.stats;
+        println!(
+            "   {:<22}: {}",
+            format!("{} (médiane)", objective.label()),
+            objective.format(stats.median)
+        );
+        println!(
+            "   {:<22}: {} ± {} (min {}, max {})",
+            format!("{} (moyenne)", objective.label()),
+            objective.format(stats.mean),
+            objective.format(stats.stddev),
+            objective.format(stats.min),
+            objective.format(stats.max)
+        );
+        println!("   Moyennes : {}", format_averages(&best.averages));
+    }
+
+    println!(
+        "\n📊 {} MEILLEURES CONFIGURATIONS :",
+        TOP_RESULTS.min(results.len())
+    );
+    for result in results.iter().take(TOP_RESULTS) {
+        println!(
+            "   médiane {:>16} | moyenne {:>16} ± {:<16} | {}",
+            objective.format(result.stats.median),
+            objective.format(result.stats.mean),
+            objective.format(result.stats.stddev),
+            format_params(&search_space, &result.params)
+        );
+    }
+
+    if let Some(dir) = &args.plot {
+        let written = plot::write_heatmaps(dir, &search_space, &results, goal)?;
+        println!("\n🖼️  {} carte(s) de chaleur écrite(s) :", written.len());
+        for path in written {
+            println!("   {}", path.display());
+        }
+    }
+    Ok(())
+}
+
+// "alpha=0.1 gamma=0.9 ..." dans l'ordre de l'espace de recherche
+fn format_params(search_space: &SearchSpace, params: &[f32]) -> String {
+    let fields: Vec<String> = search_space
+        .params
+        .iter()
+        .zip(params)
+        .map(|(param, value)| format!("{}={}", param.name, value))
+        .collect();
+    fields.join(" ")
+}
+
+fn format_averages(averages: &RunAverages) -> String {
+    format!(
+        "{:.0} ticks, {:.0} livrées, {:.1}% de survie, {:.4} livrée(s)/tick, regret {:.1} cases",
+        averages.ticks,
+        averages.food_delivered,
+        averages.survival_rate * 100.0,
+        averages.food_per_tick,
+        averages.trip_regret
+    )
+}
+
+// Clé de recherche d'une combinaison : les bits des f32, comparés exactement
+fn param_key(params: &[f32]) -> Vec<u32> {
+    params.iter().map(|v| v.to_bits()).collect()
+}
+
+// Chaque combinaison est répétée avec les graines 0..repeats, la répétition `r` se jouant sur
+// la carte `r % N` : toutes les combinaisons voient exactement les mêmes parties (ou, si elles
+// règlent la génération des cartes, des cartes tirées avec les mêmes graines).
+// `on_result` est appelé depuis les threads de travail dès qu'une combinaison a terminé
+// toutes ses répétitions.
+fn evaluate_batch(
+    search_space: &SearchSpace,
+    combinations: Vec<Vec<f32>>,
+    maps: &MapSet,
+    args: &OptimizerArgs,
+    progress: &Progress,
+    on_result: &(dyn Fn(&SimulationResult) + Sync),
+) -> Vec<SimulationResult> {
+    // .par_iter() remplace .iter() et distribue le travail sur tous les coeurs CPU ;
+    // les répétitions d'une combinaison sont elles aussi réparties entre les threads
+    combinations
+        .into_par_iter()
+        .map(|params| {
+            let runs: Vec<RunMetrics> = (0..args.repeats as u64)
+                .into_par_iter()
+                .map(|seed| {
+                    let start = Instant::now();
+                    let run = run_single_simulation(
+                        search_space,
+                        &params,
+                        maps,
+                        seed,
+                        args.max_ticks,
+                        args.run_timeout,
+                    );
+                    let outcome = if run.timed_out {
+                        progress.simulation_timed_out();
+                        "DNF".to_string()
+                    } else {
+                        args.objective.format(args.objective.value(&run))
+                    };
+                    progress.simulation_done(
+                        &format_params(search_space, &params),
+                        seed,
+                        &outcome,
+                        start.elapsed(),
+                    );
+                    run
+                })
+                .collect();
+            let values: Vec<f64> = runs.iter().map(|run| args.objective.value(run)).collect();
+            let result = SimulationResult {
+                params,
+                stats: SampleStats::from_samples(&values),
+                averages: RunAverages::from_runs(&runs),
+            };
+            on_result(&result);
+            result
+        })
+        .collect()
+}
+
+// Renvoie None quand l'aide a été affichée
+fn parse_args(args: &[String]) -> Result<Option<OptimizerArgs>, String> {
+    let mut parsed = OptimizerArgs {
+        search_space: None,
+        repeats: 1,
+        strategy: "grid".to_string(),
+        samples: 50,
+        search_seed: 0,
+        output: None,
+        resume: None,
+        verbose: false,
+        objective: Objective::Ticks,
+        target: None,
+        max_ticks: 100_000,
+        halving: false,
+        min_ticks: 1_000,
+        eta: 3,
+        maps: 1,
+        map_seed: 0,
+        map: None,
+        plot: None,
+        threads: 0,
+        run_timeout: None,
+    };
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            // Espace de recherche au format TOML
+            "--search-space" => parsed.search_space = Some(text(args, &mut i)?),
+            "--repeats" => parsed.repeats = int(args, &mut i)?,
+            "--strategy" => parsed.strategy = text(args, &mut i)?,
+            "--samples" => parsed.samples = int(args, &mut i)?,
+            "--search-seed" => parsed.search_seed = int(args, &mut i)?,
+            "--output" => parsed.output = Some(text(args, &mut i)?),
+            "--resume" => parsed.resume = Some(text(args, &mut i)?),
+            "--objective" => parsed.objective = Objective::parse(&text(args, &mut i)?)?,
+            "--target" => {
+                parsed.target =
+                    Some(next_parsed(args, &mut i, "un nombre").map_err(|e| e.to_string())?)
+            }
+            "--ticks" => parsed.max_ticks = int(args, &mut i)?,
+            "--maps" => parsed.maps = int(args, &mut i)?,
+            "--map-seed" => parsed.map_seed = int(args, &mut i)?,
+            "--map" => parsed.map = Some(text(args, &mut i)?),
+            "--plot" => parsed.plot = Some(text(args, &mut i)?),
+            "--threads" => parsed.threads = int(args, &mut i)?,
+            "--run-timeout" => parsed.run_timeout = Some(Duration::from_secs(int(args, &mut i)?)),
+            "--halving" => parsed.halving = true,
+            "--min-ticks" => parsed.min_ticks = int(args, &mut i)?,
+            "--eta" => parsed.eta = int(args, &mut i)?,
+            "--verbose" | "-v" => parsed.verbose = true,
+            "--help" | "-h" => {
+                println!("Usage: ants optimize [OPTIONS]");
+                println!("  --search-space <FICHIER> Espace de recherche TOML (défaut: alpha/gamma/epsilon)");
+                println!("  --repeats <K>          Simulations par combinaison, graines 0..K (défaut: 1)");
+                println!("  --strategy <S>         grid, random ou bayes (TPE) (défaut: grid)");
+                println!(
+                    "  --samples <N>          Combinaisons évaluées en random/bayes (défaut: 50)"
+                );
+                println!("  --search-seed <N>      Graine des tirages de random/bayes (défaut: 0)");
+                println!("  --output <FICHIER>     Résultats écrits au fil de l'eau (.csv, ou .json/.jsonl)");
+                println!("  --resume <FICHIER>     Reprend un balayage interrompu et complète ce fichier");
+                println!(
+                    "  --objective <O>        Objectif : {} (défaut: ticks)",
+                    OBJECTIVE_NAMES
+                );
+                println!("  --target <V>           Valeur visée de l'objectif : classement par écart à V");
+                println!(
+                    "  --ticks <N>            Limite de ticks par simulation (défaut: 100000)"
+                );
+                println!("  --maps <N>             Cartes aléatoires communes à toutes les combinaisons (défaut: 1)");
+                println!(
+                    "  --map-seed <N>         Graine de la première carte générée (défaut: 0)"
+                );
+                println!("  --map <FICHIER>        Carte de l'éditeur (JSON) à la place des cartes aléatoires");
+                println!(
+                    "  --plot <DOSSIER>       Cartes de chaleur PNG des deux premiers paramètres"
+                );
+                println!("  --halving              Élimination successive : seule la meilleure fraction continue");
+                println!(
+                    "  --min-ticks <N>        Budget du premier tour d'élimination (défaut: 1000)"
+                );
+                println!(
+                    "  --eta <N>              Facteur de réduction entre deux tours (défaut: 3)"
+                );
+                println!("  --threads <N>          Threads de calcul (défaut: un par coeur)");
+                println!("  --run-timeout <SECS>   Abandonner (DNF) une simulation après SECS secondes de calcul");
+                println!("  --verbose, -v          Une ligne par simulation terminée");
+                return Ok(None);
+            }
+            arg => return Err(format!("Argument inconnu: {}", arg)),
+        }
+        i += 1;
+    }
+    if parsed.repeats == 0 {
+        return Err("--repeats doit être > 0".to_string());
+    }
+    if parsed.maps == 0 {
+        return Err("--maps doit être > 0".to_string());
+    }
+    if parsed.map.is_some() && parsed.maps != 1 {
+        return Err("--map et --maps sont exclusifs : --map fournit l'unique carte".to_string());
+    }
+    if parsed.halving {
+        if parsed.eta < 2 || parsed.min_ticks == 0 {
+            return Err("--halving demande --eta >= 2 et --min-ticks > 0".to_string());
+        }
+        // Les combinaisons doivent toutes être connues d'avance, et une simulation
+        // interrompue ne peut pas être reprise depuis un fichier de résultats
+        if parsed.strategy == "bayes" || parsed.resume.is_some() {
+            return Err("--halving est incompatible avec --strategy bayes et --resume".to_string());
+        }
+        // Les combinaisons éliminées n'ont pas joué le budget complet
+        if parsed.plot.is_some() {
+            return Err("--halving et --plot sont exclusifs".to_string());
+        }
+    }
+    if parsed.max_ticks == 0 {
+        return Err("--ticks doit être > 0".to_string());
+    }
+    if parsed.run_timeout == Some(Duration::ZERO) {
+        return Err("--run-timeout doit être > 0".to_string());
+    }
+    if parsed.output.is_some() && parsed.resume.is_some() {
+        return Err(
+            "--output et --resume sont exclusifs : --resume complète déjà son fichier".to_string(),
+        );
+    }
+    Ok(Some(parsed))
+}
+
+// Valeur entière de l'option args[*i], refusée si elle manque ou est invalide
+fn int<T: FromStr>(args: &[String], i: &mut usize) -> Result<T, String> {
+    next_int(args, i).map_err(|e| e.to_string())
+}
+
+// Valeur texte de l'option args[*i], refusée si elle manque
+fn text(args: &[String], i: &mut usize) -> Result<String, String> {
+    next_arg(args, i)
+        .map(str::to_string)
+        .map_err(|e| e.to_string())
+}
+
+// Cette fonction exécute une simulation complète SANS affichage, purement mathématique
+fn run_single_simulation(
+    search_space: &SearchSpace,
+    params: &[f32],
+    maps: &MapSet,
+    seed: u64,
+    max_ticks: u64,
+    run_timeout: Option<Duration>,
+) -> RunMetrics {
+    // BOUCLE DE SIMULATION PURE : pas de sleep, pas d'affichage, juste du calcul CPU brut
+    let mut runner = new_runner(search_space, params, maps, seed, max_ticks, run_timeout);
+    runner.run_to_completion();
+    RunMetrics::from_runner(&runner)
+}
+
+// Simulation prête à démarrer : fourmis générées en mémoire depuis la configuration, sur une
+// copie de la carte commune ou sur une carte générée avec les proportions de la combinaison,
+// abandonnée après `run_timeout` de calcul
+fn new_runner(
+    search_space: &SearchSpace,
+    params: &[f32],
+    maps: &MapSet,
+    seed: u64,
+    max_ticks: u64,
+    run_timeout: Option<Duration>,
+) -> SimulationRunner {
+    let mut config = base_config(seed, max_ticks);
+    search_space.apply(&mut config, params);
+    let map = maps.map(&config, seed);
+    config.grid_width = map.get_width();
+    config.grid_height = map.get_height();
+
+    let ants = Ant::colony_from_config(&config);
+    // Les bornes de l'espace de recherche sont validées à son chargement, et chaque carte a un nid
+    let manager = AntsGameManager::with_grid(map, ants, config)
+        .expect("configuration vérifiée avant le balayage");
+    let runner = SimulationRunner::new(manager);
+    match run_timeout {
+        Some(limit) => runner.with_time_limit(limit),
+        None => runner,
+    }
+}
+
+// Configuration optimisée pour le test, avant l'application des paramètres d'une combinaison
+fn base_config(seed: u64, max_ticks: u64) -> SimulationConfig {
+    SimulationConfig {
+        num_explorers: 10,
+        num_pickers: 20,
+        num_fighters: 0,
+        max_ticks,                      // Sécurité anti-boucle infinie, ou budget fixe
+        tick_rate: TickRate::Unlimited, // Inutile ici mais requis par la struct
+        // Paramètres standards
+        reward_food: 1000.0,
+        reward_nest: 1000.0,
+        reward_death: -100.0,
+        reward_default: -1.0,
+        nest_capacity: 100,
+        pheromone_evaporation: 0.999,
+        use_gui: false,
+        output_file: None,
+        snapshot_interval: 0, // Pas de timeline : inutile sans affichage
+        seed: Some(seed),
+        // Caractéristiques des fourmis par défaut
+        ..SimulationConfig::default()
+    }
+}
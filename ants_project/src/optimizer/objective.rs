@@ -0,0 +1,213 @@
+// Grandeur optimisée (--objective) et mesures relevées à la fin de chaque simulation
+use crate::prelude::{EndReason, SimulationRunner};
+
+pub const OBJECTIVE_NAMES: &str = "ticks, food, survival, efficiency, regret";
+
+// Bilan d'une simulation, de quoi calculer tous les objectifs
+#[derive(Debug, Clone, Copy)]
+pub struct RunMetrics {
+    pub ticks: u64,
+    pub food_delivered: u64,
+    pub deaths: u64,
+    // Sorties du nid : toutes les fourmis au départ, puis chaque fourmi morte renvoyée
+    pub deployments: u64,
+    // Cases parcourues en trop par aller-retour face au plus court (None sans aller-retour)
+    pub trip_regret: Option<f64>,
+    // Simulation abandonnée par --run-timeout (DNF)
+    pub timed_out: bool,
+}
+
+impl RunMetrics {
+    pub fn from_runner(runner: &SimulationRunner) -> Self {
+        let summary = runner.summary();
+        // Une simulation abandonnée compte comme la pire possible pour tous les objectifs :
+        // toute la limite de ticks, rien de livré, aucune survie ni aller-retour
+        if summary.end_reason == Some(EndReason::Timeout) {
+            return RunMetrics {
+                ticks: runner.max_ticks(),
+                food_delivered: 0,
+                deaths: 0,
+                deployments: 0,
+                trip_regret: None,
+                timed_out: true,
+            };
+        }
+        let manager = runner.manager();
+        let spawns = manager.spawn_stats();
+        let respawns = spawns.explorers_spawned + spawns.pickers_spawned + spawns.fighters_spawned;
+        RunMetrics {
+            ticks: summary.ticks,
+            food_delivered: summary.food_delivered,
+            deaths: summary.deaths,
+            deployments: manager.ants().len() as u64 + respawns as u64,
+            trip_regret: summary.activity.trip_regret(),
+            timed_out: false,
+        }
+    }
+
+    // Part des sorties qui ne se sont pas terminées par une mort (entre 0 et 1). Les fourmis
+    // mortes étant renvoyées au nid, on rapporte les morts aux sorties et non à la population.
+    pub fn survival_rate(&self) -> f64 {
+        if self.deployments == 0 {
+            return 0.0;
+        }
+        1.0 - (self.deaths.min(self.deployments) as f64 / self.deployments as f64)
+    }
+
+    pub fn food_per_tick(&self) -> f64 {
+        if self.ticks == 0 {
+            return 0.0;
+        }
+        self.food_delivered as f64 / self.ticks as f64
+    }
+
+    // Regret des allers-retours ; une partie sans aucun aller-retour terminé compte pour
+    // toute sa durée, pire que n'importe quel trajet effectivement bouclé
+    pub fn regret(&self) -> f64 {
+        self.trip_regret.unwrap_or(self.ticks as f64)
+    }
+}
+
+// Moyennes de toutes les mesures sur les répétitions d'une combinaison, affichées et
+// enregistrées quel que soit l'objectif choisi
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RunAverages {
+    pub ticks: f64,
+    pub food_delivered: f64,
+    pub survival_rate: f64,
+    pub food_per_tick: f64,
+    pub trip_regret: f64,
+}
+
+impl RunAverages {
+    // `runs` ne doit pas être vide
+    pub fn from_runs(runs: &[RunMetrics]) -> Self {
+        let n = runs.len() as f64;
+        let mean = |f: &dyn Fn(&RunMetrics) -> f64| runs.iter().map(f).sum::<f64>() / n;
+        RunAverages {
+            ticks: mean(&|r| r.ticks as f64),
+            food_delivered: mean(&|r| r.food_delivered as f64),
+            survival_rate: mean(&RunMetrics::survival_rate),
+            food_per_tick: mean(&RunMetrics::food_per_tick),
+            trip_regret: mean(&RunMetrics::regret),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Objective {
+    // Durée de la partie jusqu'à épuisement de la nourriture (à minimiser)
+    Ticks,
+    // Nourriture livrée dans le budget de ticks (à maximiser)
+    Food,
+    // Taux de survie des fourmis (à maximiser)
+    Survival,
+    // Nourriture livrée par tick (à maximiser)
+    Efficiency,
+    // Cases parcourues en trop par aller-retour nid-nourriture (à minimiser)
+    Regret,
+}
+
+impl Objective {
+    pub const ALL: [Objective; 5] = [
+        Objective::Ticks,
+        Objective::Food,
+        Objective::Survival,
+        Objective::Efficiency,
+        Objective::Regret,
+    ];
+
+    pub fn parse(name: &str) -> Result<Self, String> {
+        match name {
+            "ticks" => Ok(Objective::Ticks),
+            "food" => Ok(Objective::Food),
+            "survival" => Ok(Objective::Survival),
+            "efficiency" => Ok(Objective::Efficiency),
+            "regret" => Ok(Objective::Regret),
+            other => Err(format!(
+                "Objectif inconnu: {} (attendu: {})",
+                other, OBJECTIVE_NAMES
+            )),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Objective::Ticks => "ticks",
+            Objective::Food => "food",
+            Objective::Survival => "survival",
+            Objective::Efficiency => "efficiency",
+            Objective::Regret => "regret",
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Objective::Ticks => "Temps",
+            Objective::Food => "Nourriture livrée",
+            Objective::Survival => "Survie",
+            Objective::Efficiency => "Efficacité",
+            Objective::Regret => "Regret des trajets",
+        }
+    }
+
+    pub fn maximize(self) -> bool {
+        !matches!(self, Objective::Ticks | Objective::Regret)
+    }
+
+    pub fn value(self, run: &RunMetrics) -> f64 {
+        match self {
+            Objective::Ticks => run.ticks as f64,
+            Objective::Food => run.food_delivered as f64,
+            Objective::Survival => run.survival_rate(),
+            Objective::Efficiency => run.food_per_tick(),
+            Objective::Regret => run.regret(),
+        }
+    }
+
+    // Score à minimiser, utilisé par les stratégies de recherche et pour le classement
+    pub fn score(self, value: f64) -> f64 {
+        if self.maximize() {
+            -value
+        } else {
+            value
+        }
+    }
+
+    pub fn format(self, value: f64) -> String {
+        match self {
+            Objective::Ticks => format!("{:.0} ticks", value),
+            Objective::Food => format!("{:.0} livrées", value),
+            Objective::Survival => format!("{:.1}%", value * 100.0),
+            Objective::Efficiency => format!("{:.4}/tick", value),
+            Objective::Regret => format!("{:.1} cases", value),
+        }
+    }
+}
+
+// Objectif et valeur visée (--target) : avec une cible, le score est l'écart à la cible, pour
+// trouver par exemple les cartes qu'une colonie vide en 5000 ticks environ
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Goal {
+    pub objective: Objective,
+    pub target: Option<f64>,
+}
+
+impl Goal {
+    // Score à minimiser, utilisé par les stratégies de recherche et pour le classement
+    pub fn score(self, value: f64) -> f64 {
+        match self.target {
+            Some(target) => (value - target).abs(),
+            None => self.objective.score(value),
+        }
+    }
+
+    // "à minimiser", "à maximiser" ou "au plus près de 5000 ticks"
+    pub fn direction(self) -> String {
+        match self.target {
+            Some(target) => format!("au plus près de {}", self.objective.format(target)),
+            None if self.objective.maximize() => "à maximiser".to_string(),
+            None => "à minimiser".to_string(),
+        }
+    }
+}
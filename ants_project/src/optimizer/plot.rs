@@ -0,0 +1,109 @@
+// Cartes de chaleur d'un balayage (--plot) : les deux premiers paramètres de l'espace de
+// recherche forment les axes, et chaque combinaison des suivants a son image. Les cases sont
+// colorées par le score de l'objectif et portent sa médiane.
+use super::objective::Goal;
+use super::results::SimulationResult;
+use super::search_space::SearchSpace;
+use crate::frame_render::{self, Heatmap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Écrit une image par combinaison des paramètres au-delà des deux premiers dans `dir`
+/// (créé au besoin) et renvoie les chemins écrits
+pub fn write_heatmaps(
+    dir: &str,
+    search_space: &SearchSpace,
+    results: &[SimulationResult],
+    goal: Goal,
+) -> Result<Vec<PathBuf>, String> {
+    let names: Vec<&str> = search_space
+        .params
+        .iter()
+        .map(|p| p.name.as_str())
+        .collect();
+    if names.len() < 2 {
+        return Err(
+            "--plot demande au moins deux paramètres dans l'espace de recherche".to_string(),
+        );
+    }
+    fs::create_dir_all(dir)
+        .map_err(|e| format!("Impossible de créer le dossier {}: {}", dir, e))?;
+
+    // Mêmes axes pour toutes les images
+    let columns = distinct(results.iter().map(|result| result.params[0]));
+    let rows = distinct(results.iter().map(|result| result.params[1]));
+    let slices = distinct_slices(results);
+
+    let mut written = Vec::new();
+    for slice in slices {
+        let mut cells = vec![None; columns.len() * rows.len()];
+        for result in results
+            .iter()
+            .filter(|result| result.params[2..] == slice[..])
+        {
+            let column = position(&columns, result.params[0]);
+            let row = position(&rows, result.params[1]);
+            let cell = (result.stats.median, goal.score(result.stats.median));
+            // Plusieurs résultats dans une case (tirages aléatoires) : le meilleur l'emporte
+            let slot: &mut Option<(f64, f64)> = &mut cells[row * columns.len() + column];
+            if slot.is_none_or(|(_, score)| cell.1 < score) {
+                *slot = Some(cell);
+            }
+        }
+        let heatmap = Heatmap {
+            columns: columns.clone(),
+            rows: rows.clone(),
+            cells,
+        };
+        let path = Path::new(dir).join(file_name(&names, &slice));
+        frame_render::render_heatmap(&heatmap)
+            .save(&path)
+            .map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+// Valeurs distinctes, triées
+fn distinct(values: impl Iterator<Item = f32>) -> Vec<f32> {
+    let mut values: Vec<f32> = values.collect();
+    values.sort_by(f32::total_cmp);
+    values.dedup();
+    values
+}
+
+// Combinaisons distinctes des paramètres au-delà des deux premiers, dans l'ordre croissant
+fn distinct_slices(results: &[SimulationResult]) -> Vec<Vec<f32>> {
+    let mut slices: Vec<Vec<f32>> = results
+        .iter()
+        .map(|result| result.params[2..].to_vec())
+        .collect();
+    slices.sort_by(|a, b| {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| a.total_cmp(b))
+            .find(|order| order.is_ne())
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    slices.dedup();
+    slices
+}
+
+fn position(values: &[f32], value: f32) -> usize {
+    values
+        .iter()
+        .position(|v| *v == value)
+        .expect("valeur prise dans les résultats")
+}
+
+// "alpha_gamma.png", ou "alpha_gamma_epsilon=0.05.png" avec les paramètres suivants
+fn file_name(names: &[&str], slice: &[f32]) -> String {
+    let mut parts = vec![names[0].to_string(), names[1].to_string()];
+    parts.extend(
+        names[2..]
+            .iter()
+            .zip(slice)
+            .map(|(name, value)| format!("{}={}", name, value)),
+    );
+    format!("{}.png", parts.join("_"))
+}
@@ -0,0 +1,154 @@
+// Suivi en direct du balayage : barre de progression sur stderr, alimentée par les threads
+// de travail à chaque simulation terminée
+use std::io::{self, IsTerminal, Write};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+// Largeur de la barre en caractères
+const BAR_WIDTH: usize = 30;
+// Longueur maximale de la description du meilleur résultat, pour tenir sur une ligne
+const BEST_LABEL_WIDTH: usize = 60;
+// Intervalle minimal entre deux rafraîchissements de la barre
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+struct ProgressState {
+    done: usize,
+    total: usize,
+    // Simulations abandonnées par --run-timeout
+    timeouts: usize,
+    // Meilleur score connu (à minimiser), sa valeur affichée et sa combinaison
+    best: Option<(f64, String, String)>,
+    last_draw: Option<Instant>,
+}
+
+pub struct Progress {
+    state: Mutex<ProgressState>,
+    start: Instant,
+    verbose: bool,
+    // La barre n'est dessinée que dans un terminal, pas dans un fichier de log
+    draw_bar: bool,
+}
+
+impl Progress {
+    // `total` : nombre de simulations prévues (combinaisons × répétitions)
+    pub fn new(total: usize, verbose: bool) -> Self {
+        Progress {
+            state: Mutex::new(ProgressState {
+                done: 0,
+                total,
+                timeouts: 0,
+                best: None,
+                last_draw: None,
+            }),
+            start: Instant::now(),
+            verbose,
+            draw_bar: io::stderr().is_terminal(),
+        }
+    }
+
+    // Simulations qui n'auront pas lieu (combinaisons reprises depuis un fichier de résultats)
+    pub fn skip(&self, simulations: usize) {
+        let mut state = self.state.lock().unwrap();
+        state.total = state.total.saturating_sub(simulations);
+    }
+
+    // Une simulation vient de se terminer ; `label` décrit sa combinaison
+    pub fn simulation_done(&self, label: &str, seed: u64, outcome: &str, elapsed: Duration) {
+        let mut state = self.state.lock().unwrap();
+        state.done += 1;
+        if self.verbose {
+            self.clear_bar();
+            eprintln!(
+                "   [{}/{}] {} graine={} : {} en {:.2?}",
+                state.done, state.total, label, seed, outcome, elapsed
+            );
+        }
+        // Après une ligne détaillée, la barre est redessinée sans attendre
+        let due = state
+            .last_draw
+            .is_none_or(|last| last.elapsed() >= REDRAW_INTERVAL);
+        if self.verbose || due || state.done == state.total {
+            self.draw(&mut state);
+        }
+    }
+
+    // Une simulation vient d'être abandonnée par --run-timeout (avant `simulation_done`)
+    pub fn simulation_timed_out(&self) {
+        self.state.lock().unwrap().timeouts += 1;
+    }
+
+    pub fn timeouts(&self) -> usize {
+        self.state.lock().unwrap().timeouts
+    }
+
+    // Une combinaison a terminé toutes ses répétitions ; `value` est le résultat affiché
+    pub fn combination_done(&self, label: &str, score: f64, value: String) {
+        let mut state = self.state.lock().unwrap();
+        if state.best.as_ref().is_none_or(|(best, _, _)| score < *best) {
+            state.best = Some((score, value, label.to_string()));
+            self.draw(&mut state);
+        }
+    }
+
+    // Laisse la dernière barre affichée et passe à la ligne
+    pub fn finish(&self) {
+        if self.draw_bar {
+            let mut state = self.state.lock().unwrap();
+            self.draw(&mut state);
+            eprintln!();
+        }
+    }
+
+    fn clear_bar(&self) {
+        if self.draw_bar {
+            eprint!("\r\x1b[2K");
+        }
+    }
+
+    fn draw(&self, state: &mut ProgressState) {
+        if !self.draw_bar {
+            return;
+        }
+        state.last_draw = Some(Instant::now());
+
+        let ratio = if state.total == 0 {
+            1.0
+        } else {
+            (state.done as f64 / state.total as f64).min(1.0)
+        };
+        let filled = (ratio * BAR_WIDTH as f64).round() as usize;
+        let elapsed = self.start.elapsed();
+        // Estimation au rythme moyen depuis le début
+        let eta = if state.done > 0 && state.done < state.total {
+            let remaining = (state.total - state.done) as f64 / state.done as f64;
+            format!("reste ~{:.0?}", elapsed.mul_f64(remaining))
+        } else if state.done >= state.total {
+            "terminé".to_string()
+        } else {
+            "reste ?".to_string()
+        };
+        let best = match &state.best {
+            Some((_, value, label)) if label.chars().count() > BEST_LABEL_WIDTH => {
+                let short: String = label.chars().take(BEST_LABEL_WIDTH - 1).collect();
+                format!(" | meilleur {} ({}…)", value, short)
+            }
+            Some((_, value, label)) => format!(" | meilleur {} ({})", value, label),
+            None => String::new(),
+        };
+
+        let mut stderr = io::stderr().lock();
+        let _ = write!(
+            stderr,
+            "\r\x1b[2K[{}{}] {}/{} ({:.0}%) {:.0?}, {}{}",
+            "#".repeat(filled),
+            "-".repeat(BAR_WIDTH - filled),
+            state.done,
+            state.total,
+            ratio * 100.0,
+            elapsed,
+            eta,
+            best
+        );
+        let _ = stderr.flush();
+    }
+}
@@ -0,0 +1,333 @@
+// Journal des résultats : chaque combinaison évaluée est ajoutée au fichier dès qu'elle est
+// terminée, pour conserver les résultats et reprendre un balayage interrompu (--resume)
+use super::objective::{Objective, RunAverages};
+use super::stats::SampleStats;
+use serde_json::{json, Map, Value};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+
+#[derive(Debug, Clone)]
+pub struct SimulationResult {
+    // Valeurs des paramètres balayés, dans l'ordre de l'espace de recherche
+    pub params: Vec<f32>,
+    // Statistiques de la valeur de l'objectif
+    pub stats: SampleStats,
+    pub averages: RunAverages,
+}
+
+// CSV (une colonne par paramètre), ou JSON Lines (un objet par ligne) si l'extension est
+// .json ou .jsonl
+#[derive(Clone, Copy, PartialEq)]
+enum Format {
+    Csv,
+    JsonLines,
+}
+
+impl Format {
+    fn from_path(path: &str) -> Self {
+        match Path::new(path).extension().and_then(|e| e.to_str()) {
+            Some("json") | Some("jsonl") => Format::JsonLines,
+            _ => Format::Csv,
+        }
+    }
+}
+
+// Colonnes numériques écrites après les paramètres et le nom de l'objectif : statistiques de
+// l'objectif sur les répétitions, puis moyennes de toutes les mesures
+const VALUE_COLUMNS: [&str; 11] = [
+    "repeats",
+    "median",
+    "mean",
+    "stddev",
+    "min",
+    "max",
+    "avg_ticks",
+    "avg_food",
+    "avg_survival",
+    "avg_food_per_tick",
+    "avg_trip_regret",
+];
+
+impl SimulationResult {
+    fn values(&self, repeats: u32) -> [f64; 11] {
+        let (stats, averages) = (&self.stats, &self.averages);
+        [
+            repeats as f64,
+            stats.median,
+            stats.mean,
+            stats.stddev,
+            stats.min,
+            stats.max,
+            averages.ticks,
+            averages.food_delivered,
+            averages.survival_rate,
+            averages.food_per_tick,
+            averages.trip_regret,
+        ]
+    }
+
+    fn from_values(params: Vec<f32>, values: &[f64]) -> Self {
+        SimulationResult {
+            params,
+            stats: SampleStats {
+                median: values[1],
+                mean: values[2],
+                stddev: values[3],
+                min: values[4],
+                max: values[5],
+            },
+            averages: RunAverages {
+                ticks: values[6],
+                food_delivered: values[7],
+                survival_rate: values[8],
+                food_per_tick: values[9],
+                trip_regret: values[10],
+            },
+        }
+    }
+}
+
+pub struct ResultsLog {
+    file: File,
+    path: String,
+    format: Format,
+    names: Vec<String>,
+    objective: Objective,
+    repeats: u32,
+}
+
+impl ResultsLog {
+    // Nouveau fichier (écrase un éventuel fichier existant)
+    pub fn create(
+        path: &str,
+        names: Vec<String>,
+        objective: Objective,
+        repeats: u32,
+    ) -> Result<Self, String> {
+        let file =
+            File::create(path).map_err(|e| format!("Impossible de créer {}: {}", path, e))?;
+        let mut log = ResultsLog {
+            file,
+            path: path.to_string(),
+            format: Format::from_path(path),
+            names,
+            objective,
+            repeats,
+        };
+        if log.format == Format::Csv {
+            let header = csv_header(&log.names);
+            log.write_line(&header.join(","))?;
+        }
+        Ok(log)
+    }
+
+    // Relit les résultats d'un balayage précédent puis continue d'écrire à la suite.
+    // Un fichier absent démarre simplement un nouveau balayage.
+    pub fn resume(
+        path: &str,
+        names: Vec<String>,
+        objective: Objective,
+        repeats: u32,
+    ) -> Result<(Self, Vec<SimulationResult>), String> {
+        if !Path::new(path).exists() {
+            let log = Self::create(path, names, objective, repeats)?;
+            return Ok((log, Vec::new()));
+        }
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Impossible de lire {}: {}", path, e))?;
+        let format = Format::from_path(path);
+        let rows = match format {
+            Format::Csv => parse_csv(&content, &names),
+            Format::JsonLines => parse_json_lines(&content, &names),
+        };
+        let results = rows
+            .and_then(|rows| {
+                rows.into_iter()
+                    .map(|row| row.into_result(objective, repeats))
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .map_err(|e| format!("Reprise impossible depuis {}: {}", path, e))?;
+
+        let file = OpenOptions::new()
+            .append(true)
+            .open(path)
+            .map_err(|e| format!("Impossible d'ouvrir {}: {}", path, e))?;
+        let mut log = ResultsLog {
+            file,
+            path: path.to_string(),
+            format,
+            names,
+            objective,
+            repeats,
+        };
+        // Termine une éventuelle ligne tronquée pour ne pas y coller la suivante
+        if !content.is_empty() && !content.ends_with('\n') {
+            log.write_line("")?;
+        }
+        Ok((log, results))
+    }
+
+    pub fn append(&mut self, result: &SimulationResult) -> Result<(), String> {
+        let values = result.values(self.repeats);
+        let line = match self.format {
+            Format::Csv => {
+                let mut fields: Vec<String> = result.params.iter().map(f32::to_string).collect();
+                fields.push(self.objective.name().to_string());
+                fields.extend(values.iter().map(f64::to_string));
+                fields.join(",")
+            }
+            Format::JsonLines => {
+                let params: Map<String, Value> = self
+                    .names
+                    .iter()
+                    .zip(&result.params)
+                    // Passage par l'affichage du f32 pour écrire 0.1 et non 0.10000000149011612
+                    .map(|(name, value)| {
+                        (name.clone(), json!(value.to_string().parse::<f64>().ok()))
+                    })
+                    .collect();
+                let mut object = Map::new();
+                object.insert("params".to_string(), Value::Object(params));
+                object.insert("objective".to_string(), json!(self.objective.name()));
+                for (column, value) in VALUE_COLUMNS.iter().zip(values) {
+                    object.insert(column.to_string(), json!(value));
+                }
+                Value::Object(object).to_string()
+            }
+        };
+        self.write_line(&line)
+    }
+
+    // Écriture immédiate : une interruption ne perd au plus que les simulations en cours
+    fn write_line(&mut self, line: &str) -> Result<(), String> {
+        writeln!(self.file, "{}", line)
+            .and_then(|_| self.file.flush())
+            .map_err(|e| format!("Impossible d'écrire dans {}: {}", self.path, e))
+    }
+}
+
+fn csv_header(names: &[String]) -> Vec<&str> {
+    names
+        .iter()
+        .map(String::as_str)
+        .chain(["objective"])
+        .chain(VALUE_COLUMNS)
+        .collect()
+}
+
+// Ligne relue d'un fichier de résultats, avant vérification de sa compatibilité
+struct Row {
+    line: usize,
+    params: Vec<f32>,
+    objective: String,
+    values: Vec<f64>,
+}
+
+impl Row {
+    fn into_result(self, objective: Objective, repeats: u32) -> Result<SimulationResult, String> {
+        if self.objective != objective.name() {
+            return Err(format!(
+                "ligne {}: objectif {} au lieu de {} (relancer avec le même --objective)",
+                self.line,
+                self.objective,
+                objective.name()
+            ));
+        }
+        if self.values[0] != repeats as f64 {
+            return Err(format!(
+                "ligne {}: {} répétition(s) au lieu de {} (relancer avec le même --repeats)",
+                self.line, self.values[0], repeats
+            ));
+        }
+        Ok(SimulationResult::from_values(self.params, &self.values))
+    }
+}
+
+fn parse_csv(content: &str, names: &[String]) -> Result<Vec<Row>, String> {
+    let mut lines = content.lines().enumerate();
+    let expected = csv_header(names);
+    match lines.next() {
+        Some((_, header)) if header.split(',').eq(expected.iter().copied()) => {}
+        _ => {
+            return Err(format!(
+                "en-tête attendu: {} (même espace de recherche que le balayage interrompu)",
+                expected.join(",")
+            ))
+        }
+    }
+
+    let mut rows = Vec::new();
+    for (index, line) in lines {
+        let line_number = index + 1;
+        // Une ligne incomplète peut rester si le processus a été tué pendant l'écriture
+        let fields: Vec<&str> = line.split(',').collect();
+        if line.is_empty() || fields.len() != expected.len() {
+            continue;
+        }
+        let invalid = |e: &dyn std::fmt::Display| format!("ligne {}: {}", line_number, e);
+        let params = fields[..names.len()]
+            .iter()
+            .map(|f| f.parse::<f32>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| invalid(&e))?;
+        let values = fields[names.len() + 1..]
+            .iter()
+            .map(|f| f.parse::<f64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| invalid(&e))?;
+        rows.push(Row {
+            line: line_number,
+            params,
+            objective: fields[names.len()].to_string(),
+            values,
+        });
+    }
+    Ok(rows)
+}
+
+fn parse_json_lines(content: &str, names: &[String]) -> Result<Vec<Row>, String> {
+    let mut rows = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line_number = index + 1;
+        // Une ligne tronquée (processus tué pendant l'écriture) est ignorée
+        let Ok(value) = serde_json::from_str::<Value>(line) else {
+            continue;
+        };
+        let params_obj = value["params"]
+            .as_object()
+            .ok_or_else(|| format!("ligne {}: champ params manquant", line_number))?;
+        if params_obj.len() != names.len() {
+            return Err(format!(
+                "ligne {}: paramètres différents de l'espace de recherche ({})",
+                line_number,
+                names.join(", ")
+            ));
+        }
+        let params = names
+            .iter()
+            .map(|name| {
+                params_obj
+                    .get(name)
+                    .and_then(Value::as_f64)
+                    .map(|v| v as f32)
+                    .ok_or_else(|| format!("ligne {}: paramètre {} manquant", line_number, name))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let values = VALUE_COLUMNS
+            .iter()
+            .map(|column| {
+                value[column]
+                    .as_f64()
+                    .ok_or_else(|| format!("ligne {}: champ {} manquant", line_number, column))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        rows.push(Row {
+            line: line_number,
+            params,
+            objective: value["objective"].as_str().unwrap_or_default().to_string(),
+            values,
+        });
+    }
+    Ok(rows)
+}
@@ -0,0 +1,237 @@
+use crate::prelude::SimulationConfig;
+use rand::Rng;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+
+// Paramètres de SimulationConfig qu'il est possible de balayer, dans l'ordre d'affichage.
+// Les paramètres map_* règlent la génération des cartes aléatoires.
+const PARAMETERS: [&str; 13] = [
+    "alpha",
+    "gamma",
+    "epsilon",
+    "reward_food",
+    "reward_nest",
+    "reward_death",
+    "reward_default",
+    "pheromone_evaporation",
+    "map_wall_density",
+    "map_death_density",
+    "map_food_sources",
+    "map_food_max",
+    "map_protein_share",
+];
+
+// Valeurs d'un paramètre : liste explicite (`values`), ou plage [min, max] découpée en
+// `steps` valeurs. Bornes en f64 pour retomber sur des valeurs rondes (0.3 et non 0.29999998).
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ParamValues {
+    values: Option<Vec<f32>>,
+    min: Option<f64>,
+    max: Option<f64>,
+    steps: Option<u32>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SearchSpaceFile {
+    params: BTreeMap<String, ParamValues>,
+}
+
+// Domaine d'un paramètre : valeurs discrètes, ou intervalle continu découpé en `steps`
+// valeurs pour la recherche en grille
+#[derive(Clone, Debug)]
+pub enum Domain {
+    Values(Vec<f32>),
+    Range { min: f64, max: f64, steps: u32 },
+}
+
+impl Domain {
+    // Valeurs essayées par la recherche en grille
+    pub fn grid_values(&self) -> Vec<f32> {
+        match self {
+            Domain::Values(values) => values.clone(),
+            Domain::Range { min, steps: 1, .. } => vec![*min as f32],
+            Domain::Range { min, max, steps } => {
+                let step = (max - min) / (steps - 1) as f64;
+                (0..*steps)
+                    .map(|i| (min + step * i as f64) as f32)
+                    .collect()
+            }
+        }
+    }
+
+    // Tirage uniforme dans le domaine (recherche aléatoire)
+    pub fn sample<R: Rng>(&self, rng: &mut R) -> f32 {
+        match self {
+            Domain::Values(values) => values[rng.gen_range(0..values.len())],
+            Domain::Range { min, max, .. } if min == max => *min as f32,
+            Domain::Range { min, max, .. } => rng.gen_range(*min..=*max) as f32,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Param {
+    pub name: String,
+    pub domain: Domain,
+}
+
+// Espace de recherche : chaque paramètre balayé avec son domaine
+pub struct SearchSpace {
+    pub params: Vec<Param>,
+}
+
+impl Default for SearchSpace {
+    // Grille historique de l'optimiseur : alpha, gamma et epsilon
+    fn default() -> Self {
+        let param = |name: &str, values: Vec<f32>| Param {
+            name: name.to_string(),
+            domain: Domain::Values(values),
+        };
+        SearchSpace {
+            params: vec![
+                param("alpha", vec![0.1, 0.3, 0.5, 0.7, 0.9]),
+                param("gamma", vec![0.8, 0.9, 0.95, 0.99]),
+                param("epsilon", vec![0.01, 0.05, 0.1, 0.2]),
+            ],
+        }
+    }
+}
+
+impl SearchSpace {
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Impossible de lire {}: {}", path, e))?;
+        Self::parse(&content).map_err(|e| format!("Espace de recherche invalide {}: {}", path, e))
+    }
+
+    fn parse(content: &str) -> Result<Self, String> {
+        let file: SearchSpaceFile = toml::from_str(content).map_err(|e| e.to_string())?;
+
+        let mut params = Vec::new();
+        for (name, values) in file.params {
+            if !PARAMETERS.contains(&name.as_str()) {
+                return Err(format!(
+                    "paramètre inconnu {} (possibles: {})",
+                    name,
+                    PARAMETERS.join(", ")
+                ));
+            }
+            let domain = match values {
+                ParamValues {
+                    values: Some(values),
+                    min: None,
+                    max: None,
+                    steps: None,
+                } => {
+                    if values.is_empty() {
+                        return Err(format!("{}: aucune valeur", name));
+                    }
+                    Domain::Values(values)
+                }
+                ParamValues {
+                    values: None,
+                    min: Some(min),
+                    max: Some(max),
+                    steps: Some(steps),
+                } => {
+                    if steps == 0 || min > max {
+                        return Err(format!("{}: plage vide (min > max ou steps = 0)", name));
+                    }
+                    Domain::Range { min, max, steps }
+                }
+                _ => {
+                    return Err(format!(
+                        "{}: attendu soit `values`, soit `min`, `max` et `steps`",
+                        name
+                    ))
+                }
+            };
+            // Les validations de SimulationConfig portent sur des intervalles : des bornes
+            // valides garantissent que toutes les valeurs tirées ou balayées le sont aussi
+            let bounds = match &domain {
+                Domain::Values(values) => values.clone(),
+                Domain::Range { min, max, .. } => vec![*min as f32, *max as f32],
+            };
+            let param = Param { name, domain };
+            let single = SearchSpace {
+                params: vec![param.clone()],
+            };
+            for value in bounds {
+                let mut config = SimulationConfig::default();
+                single.apply(&mut config, &[value]);
+                config
+                    .validate()
+                    .map_err(|e| format!("{} = {}: {}", param.name, value, e))?;
+            }
+            params.push(param);
+        }
+        if params.is_empty() {
+            return Err("aucun paramètre à balayer".to_string());
+        }
+
+        params.sort_by_key(|param| PARAMETERS.iter().position(|p| *p == param.name));
+        Ok(SearchSpace { params })
+    }
+
+    // Produit cartésien des valeurs de grille, une combinaison par simulation
+    pub fn combinations(&self) -> Vec<Vec<f32>> {
+        self.params
+            .iter()
+            .fold(vec![Vec::new()], |combinations, param| {
+                let values = param.domain.grid_values();
+                combinations
+                    .iter()
+                    .flat_map(|prefix| {
+                        values.iter().map(move |&v| {
+                            let mut combination = prefix.clone();
+                            combination.push(v);
+                            combination
+                        })
+                    })
+                    .collect()
+            })
+    }
+
+    // Au moins un paramètre règle la génération des cartes : chaque combinaison a les siennes
+    pub fn shapes_maps(&self) -> bool {
+        self.params
+            .iter()
+            .any(|param| param.name.starts_with("map_"))
+    }
+
+    // Applique une combinaison (dans l'ordre de `params`) sur une configuration de base ; les
+    // paramètres entiers sont arrondis
+    pub fn apply(&self, config: &mut SimulationConfig, combination: &[f32]) {
+        for (param, &value) in self.params.iter().zip(combination) {
+            let name = &param.name;
+            let count = match name.as_str() {
+                "map_food_sources" => Some(&mut config.map_gen.food_sources),
+                "map_food_max" => Some(&mut config.map_gen.food_max),
+                _ => None,
+            };
+            if let Some(count) = count {
+                *count = value.round() as u32;
+                continue;
+            }
+            let field = match name.as_str() {
+                "alpha" => &mut config.alpha,
+                "gamma" => &mut config.gamma,
+                "epsilon" => &mut config.epsilon,
+                "reward_food" => &mut config.reward_food,
+                "reward_nest" => &mut config.reward_nest,
+                "reward_death" => &mut config.reward_death,
+                "reward_default" => &mut config.reward_default,
+                "pheromone_evaporation" => &mut config.pheromone_evaporation,
+                "map_wall_density" => &mut config.map_gen.wall_density,
+                "map_death_density" => &mut config.map_gen.death_density,
+                "map_protein_share" => &mut config.map_gen.protein_share,
+                // Les noms sont validés au chargement
+                _ => unreachable!("paramètre inconnu {}", name),
+            };
+            *field = value;
+        }
+    }
+}
@@ -0,0 +1,40 @@
+// Statistiques sur les valeurs de l'objectif pour les répétitions d'une même configuration
+#[derive(Debug, Clone)]
+pub struct SampleStats {
+    pub mean: f64,
+    pub median: f64,
+    pub stddev: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl SampleStats {
+    // `samples` ne doit pas être vide
+    pub fn from_samples(samples: &[f64]) -> Self {
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable_by(f64::total_cmp);
+        let n = sorted.len();
+
+        let mean = sorted.iter().sum::<f64>() / n as f64;
+        let median = if n.is_multiple_of(2) {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2.0
+        } else {
+            sorted[n / 2]
+        };
+        // Écart-type de l'échantillon (n - 1), nul pour une seule répétition
+        let stddev = if n > 1 {
+            let variance = sorted.iter().map(|&v| (v - mean).powi(2)).sum::<f64>() / (n - 1) as f64;
+            variance.sqrt()
+        } else {
+            0.0
+        };
+
+        SampleStats {
+            mean,
+            median,
+            stddev,
+            min: sorted[0],
+            max: sorted[n - 1],
+        }
+    }
+}
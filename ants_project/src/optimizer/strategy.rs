@@ -0,0 +1,217 @@
+use super::search_space::{Domain, SearchSpace};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+// Fraction des meilleurs résultats qui forme la "bonne" population du TPE
+const TPE_GOOD_FRACTION: f64 = 0.25;
+// Candidats tirés dans la bonne population pour chaque proposition du TPE
+const TPE_CANDIDATES: usize = 24;
+
+/// Manière de parcourir l'espace de recherche. Le score observé est à minimiser.
+pub trait SearchStrategy {
+    fn name(&self) -> &'static str;
+
+    /// Prochain lot de combinaisons à évaluer, vide quand la recherche est terminée
+    fn propose(&mut self) -> Vec<Vec<f32>>;
+
+    /// Résultat d'une combinaison proposée
+    fn observe(&mut self, _params: &[f32], _score: f64) {}
+
+    /// Nombre de combinaisons qui restent à proposer (barre de progression)
+    fn remaining(&self) -> usize;
+}
+
+// Toutes les combinaisons de la grille, en un seul lot
+pub struct GridSearch {
+    combinations: Option<Vec<Vec<f32>>>,
+}
+
+impl GridSearch {
+    pub fn new(space: &SearchSpace) -> Self {
+        GridSearch {
+            combinations: Some(space.combinations()),
+        }
+    }
+}
+
+impl SearchStrategy for GridSearch {
+    fn name(&self) -> &'static str {
+        "grille"
+    }
+
+    fn propose(&mut self) -> Vec<Vec<f32>> {
+        self.combinations.take().unwrap_or_default()
+    }
+
+    fn remaining(&self) -> usize {
+        self.combinations.as_ref().map_or(0, Vec::len)
+    }
+}
+
+// `samples` points tirés uniformément dans les domaines, en un seul lot
+pub struct RandomSearch {
+    domains: Vec<Domain>,
+    samples: usize,
+    rng: StdRng,
+}
+
+impl RandomSearch {
+    pub fn new(space: &SearchSpace, samples: usize, seed: u64) -> Self {
+        RandomSearch {
+            domains: space.params.iter().map(|p| p.domain.clone()).collect(),
+            samples,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl SearchStrategy for RandomSearch {
+    fn name(&self) -> &'static str {
+        "aléatoire"
+    }
+
+    fn propose(&mut self) -> Vec<Vec<f32>> {
+        let samples = std::mem::take(&mut self.samples);
+        (0..samples)
+            .map(|_| sample_uniform(&self.domains, &mut self.rng))
+            .collect()
+    }
+
+    fn remaining(&self) -> usize {
+        self.samples
+    }
+}
+
+// Optimisation bayésienne façon TPE (Tree-structured Parzen Estimator) : après une phase
+// aléatoire, chaque proposition maximise l(x) / g(x), où l et g sont des estimateurs de densité
+// construits sur les meilleurs et les moins bons résultats déjà observés (paramètre par paramètre)
+pub struct TpeSearch {
+    domains: Vec<Domain>,
+    budget: usize,
+    startup: usize,
+    batch: usize,
+    proposed: usize,
+    history: Vec<(Vec<f32>, f64)>,
+    rng: StdRng,
+}
+
+impl TpeSearch {
+    // `batch` : propositions par lot (au moins le nombre de threads pour occuper les coeurs)
+    pub fn new(space: &SearchSpace, budget: usize, batch: usize, seed: u64) -> Self {
+        TpeSearch {
+            domains: space.params.iter().map(|p| p.domain.clone()).collect(),
+            budget,
+            startup: (budget / 4).clamp(batch.min(budget), budget),
+            batch: batch.max(1),
+            proposed: 0,
+            history: Vec::new(),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    fn propose_one(&mut self) -> Vec<f32> {
+        let mut sorted: Vec<&(Vec<f32>, f64)> = self.history.iter().collect();
+        sorted.sort_by(|a, b| a.1.total_cmp(&b.1));
+        let n_good = ((sorted.len() as f64 * TPE_GOOD_FRACTION).ceil() as usize).max(1);
+        let (good, bad) = sorted.split_at(n_good.min(sorted.len()));
+
+        (0..self.domains.len())
+            .map(|d| {
+                let good: Vec<f32> = good.iter().map(|(params, _)| params[d]).collect();
+                let bad: Vec<f32> = bad.iter().map(|(params, _)| params[d]).collect();
+                let domain = &self.domains[d];
+                let mut best = (f64::NEG_INFINITY, domain.sample(&mut self.rng));
+                for _ in 0..TPE_CANDIDATES {
+                    let candidate = parzen_sample(domain, &good, &mut self.rng);
+                    let ratio = parzen_density(domain, &good, candidate)
+                        / parzen_density(domain, &bad, candidate);
+                    if ratio > best.0 {
+                        best = (ratio, candidate);
+                    }
+                }
+                best.1
+            })
+            .collect()
+    }
+}
+
+impl SearchStrategy for TpeSearch {
+    fn name(&self) -> &'static str {
+        "bayésienne (TPE)"
+    }
+
+    fn propose(&mut self) -> Vec<Vec<f32>> {
+        let remaining = self.budget - self.proposed;
+        let batch = if self.proposed < self.startup {
+            // Phase d'initialisation : tirages uniformes
+            let n = self.startup - self.proposed;
+            (0..n)
+                .map(|_| sample_uniform(&self.domains, &mut self.rng))
+                .collect()
+        } else {
+            (0..self.batch.min(remaining))
+                .map(|_| self.propose_one())
+                .collect::<Vec<_>>()
+        };
+        self.proposed += batch.len();
+        batch
+    }
+
+    fn observe(&mut self, params: &[f32], score: f64) {
+        self.history.push((params.to_vec(), score));
+    }
+
+    fn remaining(&self) -> usize {
+        self.budget - self.proposed
+    }
+}
+
+fn sample_uniform(domains: &[Domain], rng: &mut StdRng) -> Vec<f32> {
+    domains.iter().map(|d| d.sample(rng)).collect()
+}
+
+// Largeur des noyaux gaussiens : se resserre quand les observations s'accumulent
+fn bandwidth(min: f64, max: f64, n: usize) -> f64 {
+    ((max - min) / (n as f64 + 1.0).sqrt()).max((max - min) * 0.02)
+}
+
+// Tirage dans l'estimateur de Parzen : un point observé (ou le domaine entier, a priori) + bruit
+fn parzen_sample(domain: &Domain, observed: &[f32], rng: &mut StdRng) -> f32 {
+    let pick = rng.gen_range(0..=observed.len());
+    if pick == observed.len() {
+        return domain.sample(rng);
+    }
+    match domain {
+        Domain::Values(_) => observed[pick],
+        Domain::Range { min, max, .. } => {
+            // Box-Muller
+            let (u1, u2): (f64, f64) = (rng.gen::<f64>().max(f64::MIN_POSITIVE), rng.gen());
+            let noise = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+            let sigma = bandwidth(*min, *max, observed.len());
+            (observed[pick] as f64 + noise * sigma).clamp(*min, *max) as f32
+        }
+    }
+}
+
+// Densité de l'estimateur de Parzen en x, mélangée avec un a priori uniforme
+fn parzen_density(domain: &Domain, observed: &[f32], x: f32) -> f64 {
+    let weight = 1.0 / (observed.len() as f64 + 1.0);
+    match domain {
+        Domain::Values(values) => {
+            let count = observed.iter().filter(|&&v| v == x).count() as f64;
+            (count + 1.0) / (observed.len() as f64 + values.len() as f64)
+        }
+        Domain::Range { min, max, .. } if max == min => 1.0,
+        Domain::Range { min, max, .. } => {
+            let sigma = bandwidth(*min, *max, observed.len());
+            let kernels: f64 = observed
+                .iter()
+                .map(|&v| {
+                    let z = (x as f64 - v as f64) / sigma;
+                    (-0.5 * z * z).exp() / (sigma * (2.0 * std::f64::consts::PI).sqrt())
+                })
+                .sum();
+            weight * (kernels + 1.0 / (max - min))
+        }
+    }
+}
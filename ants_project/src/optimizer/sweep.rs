@@ -0,0 +1,225 @@
+// Balayage alpha/gamma/epsilon lancé depuis la GUI : même principe que `ants optimize` en
+// grille, mais sur la carte et la configuration de la partie affichée, dans un thread de
+// fond dont l'interface lit les résultats au fil de l'eau
+use super::objective::{Objective, RunMetrics};
+use super::search_space::{Domain, Param, SearchSpace};
+use super::stats::SampleStats;
+use crate::prelude::*;
+use rayon::prelude::*;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+// Ticks joués entre deux vérifications de l'annulation
+const CANCEL_CHECK_TICKS: u64 = 500;
+
+/// Plage d'un paramètre découpée en `steps` valeurs, bornes comprises
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SweepRange {
+    pub min: f32,
+    pub max: f32,
+    pub steps: u32,
+}
+
+/// Réglages d'un balayage : plages des trois paramètres, répétitions et objectif
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SweepSettings {
+    pub alpha: SweepRange,
+    pub gamma: SweepRange,
+    pub epsilon: SweepRange,
+    // Simulations par combinaison, graines 0..repeats
+    pub repeats: u32,
+    pub max_ticks: u64,
+    pub objective: Objective,
+}
+
+impl Default for SweepSettings {
+    // Bornes proches de la grille par défaut de `ants optimize`, en moins de points
+    fn default() -> Self {
+        SweepSettings {
+            alpha: SweepRange {
+                min: 0.1,
+                max: 0.9,
+                steps: 5,
+            },
+            gamma: SweepRange {
+                min: 0.8,
+                max: 0.98,
+                steps: 4,
+            },
+            epsilon: SweepRange {
+                min: 0.05,
+                max: 0.2,
+                steps: 2,
+            },
+            repeats: 1,
+            max_ticks: 5000,
+            objective: Objective::Food,
+        }
+    }
+}
+
+impl SweepSettings {
+    fn search_space(&self) -> SearchSpace {
+        let param = |name: &str, range: SweepRange| Param {
+            name: name.to_string(),
+            domain: Domain::Range {
+                min: range.min as f64,
+                max: range.max.max(range.min) as f64,
+                steps: range.steps.max(1),
+            },
+        };
+        SearchSpace {
+            params: vec![
+                param("alpha", self.alpha),
+                param("gamma", self.gamma),
+                param("epsilon", self.epsilon),
+            ],
+        }
+    }
+
+    /// Nombre de simulations du balayage
+    pub fn simulation_count(&self) -> usize {
+        self.search_space().combinations().len() * self.repeats.max(1) as usize
+    }
+}
+
+/// Résultat d'une combinaison : médiane de l'objectif sur ses répétitions
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SweepPoint {
+    pub alpha: f32,
+    pub gamma: f32,
+    pub epsilon: f32,
+    pub median: f64,
+}
+
+/// Balayage en cours dans un thread de fond ; l'abandonner l'annule
+pub struct Sweep {
+    objective: Objective,
+    total: usize,
+    points: Arc<Mutex<Vec<SweepPoint>>>,
+    simulations_done: Arc<AtomicUsize>,
+    cancelled: Arc<AtomicBool>,
+    finished: Arc<AtomicBool>,
+}
+
+impl Sweep {
+    /// Lance le balayage sur des copies de `grid`, chaque simulation reprenant `base` avec
+    /// les valeurs de sa combinaison
+    pub fn start(grid: Grid, base: SimulationConfig, settings: &SweepSettings) -> Self {
+        let search_space = settings.search_space();
+        let repeats = settings.repeats.max(1) as u64;
+        let sweep = Sweep {
+            objective: settings.objective,
+            total: settings.simulation_count(),
+            points: Arc::new(Mutex::new(Vec::new())),
+            simulations_done: Arc::new(AtomicUsize::new(0)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+            finished: Arc::new(AtomicBool::new(false)),
+        };
+        let config = SimulationConfig {
+            grid_width: grid.get_width(),
+            grid_height: grid.get_height(),
+            max_ticks: settings.max_ticks,
+            tick_rate: TickRate::Unlimited,
+            use_gui: false,
+            output_file: None,
+            snapshot_interval: 0,
+            ..base
+        };
+        let objective = settings.objective;
+        let points = Arc::clone(&sweep.points);
+        let done = Arc::clone(&sweep.simulations_done);
+        let cancelled = Arc::clone(&sweep.cancelled);
+        let finished = Arc::clone(&sweep.finished);
+        thread::spawn(move || {
+            search_space
+                .combinations()
+                .into_par_iter()
+                .for_each(|params| {
+                    let mut config = config.clone();
+                    search_space.apply(&mut config, &params);
+                    let values: Vec<f64> = (0..repeats)
+                        .into_par_iter()
+                        .filter_map(|seed| {
+                            let value = run(&grid, &config, seed, &cancelled)?;
+                            done.fetch_add(1, Ordering::Relaxed);
+                            Some(objective.value(&value))
+                        })
+                        .collect();
+                    if values.len() as u64 == repeats {
+                        points.lock().unwrap().push(SweepPoint {
+                            alpha: params[0],
+                            gamma: params[1],
+                            epsilon: params[2],
+                            median: SampleStats::from_samples(&values).median,
+                        });
+                    }
+                });
+            finished.store(true, Ordering::Relaxed);
+        });
+        sweep
+    }
+
+    pub fn objective(&self) -> Objective {
+        self.objective
+    }
+
+    /// Combinaisons terminées, dans l'ordre où elles ont fini
+    pub fn points(&self) -> Vec<SweepPoint> {
+        self.points.lock().unwrap().clone()
+    }
+
+    /// Simulations terminées et nombre total de simulations
+    pub fn progress(&self) -> (usize, usize) {
+        (self.simulations_done.load(Ordering::Relaxed), self.total)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished.load(Ordering::Relaxed)
+    }
+
+    /// Meilleure combinaison terminée selon l'objectif
+    pub fn best(&self) -> Option<SweepPoint> {
+        self.points().into_iter().min_by(|a, b| {
+            self.objective
+                .score(a.median)
+                .total_cmp(&self.objective.score(b.median))
+        })
+    }
+
+    /// Arrête le balayage : les simulations en cours s'interrompent, les combinaisons
+    /// déjà terminées restent disponibles
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+impl Drop for Sweep {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
+
+// Une simulation complète, None si le balayage a été annulé avant sa fin
+fn run(
+    grid: &Grid,
+    config: &SimulationConfig,
+    seed: u64,
+    cancelled: &AtomicBool,
+) -> Option<RunMetrics> {
+    let config = SimulationConfig {
+        seed: Some(seed),
+        ..config.clone()
+    };
+    let ants = Ant::colony_from_config(&config);
+    let manager = AntsGameManager::with_grid(grid.clone(), ants, config).ok()?;
+    let mut runner = SimulationRunner::new(manager);
+    while !runner.is_done() {
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+        runner.run_ticks(CANCEL_CHECK_TICKS);
+    }
+    Some(RunMetrics::from_runner(&runner))
+}
@@ -0,0 +1,180 @@
+//! Plus courts chemins sur la grille
+//!
+//! Les déplacements sont ceux des fourmis : vers les quatre voisins praticables, un tunnel
+//! mène directement à sa sortie et les zones mortelles ne sont jamais traversées. Chaque pas
+//! compte pour un, boue comprise (`baseline::astar_step` tient compte de la boue).
+//!
+//! [`PathCache`] garde les parcours déjà calculés tant que la grille ne change pas de
+//! version (voir `Grid::revision`).
+
+use crate::coord::GridShape;
+use crate::exploration::{target, MOVING_ACTIONS};
+use crate::grid::Grid;
+use std::collections::{HashMap, VecDeque};
+
+/// Parcours en largeur depuis une case : distance en pas de chaque case et case précédente
+/// sur un plus court chemin
+#[derive(Clone, Debug, PartialEq)]
+pub struct DistanceMap {
+    width: u32,
+    height: u32,
+    from: (u32, u32),
+    distances: Vec<Option<u32>>,
+    previous: Vec<Option<(u32, u32)>>,
+}
+
+impl DistanceMap {
+    /// Case de départ du parcours
+    pub fn origin(&self) -> (u32, u32) {
+        self.from
+    }
+
+    fn shape(&self) -> GridShape {
+        GridShape::new(self.width, self.height)
+    }
+
+    fn index(&self, cell: (u32, u32)) -> Option<usize> {
+        self.shape()
+            .index(cell.into())
+            .map(|index| index.as_usize())
+    }
+
+    /// Nombre de pas jusqu'à `to`, None si la case est inatteignable ou hors de la carte
+    pub fn distance(&self, to: (u32, u32)) -> Option<u32> {
+        self.index(to).and_then(|i| self.distances[i])
+    }
+
+    pub fn is_reachable(&self, to: (u32, u32)) -> bool {
+        self.distance(to).is_some()
+    }
+
+    /// Cases du plus court chemin du départ à `to`, extrémités comprises (une sortie de
+    /// tunnel suit directement la case d'avant l'entrée)
+    pub fn path_to(&self, to: (u32, u32)) -> Option<Vec<(u32, u32)>> {
+        self.distance(to)?;
+        let mut path = vec![to];
+        let mut cell = to;
+        while let Some(previous) = self.index(cell).and_then(|i| self.previous[i]) {
+            path.push(previous);
+            cell = previous;
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    /// Toutes les cases atteignables, dans l'ordre de lecture
+    pub fn reachable_cells(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        let shape = self.shape();
+        self.distances
+            .iter()
+            .enumerate()
+            .filter(|(_, d)| d.is_some())
+            .map(move |(i, _)| shape.coord_at(i).into())
+    }
+}
+
+/// Distances en pas depuis `from` vers toutes les cases de la grille
+pub fn bfs_distances(grid: &Grid, from: (u32, u32)) -> DistanceMap {
+    let shape = grid.shape();
+    let cells = shape.len();
+    let mut map = DistanceMap {
+        width: shape.width,
+        height: shape.height,
+        from,
+        distances: vec![None; cells],
+        previous: vec![None; cells],
+    };
+    let Some(start) = map.index(from) else {
+        return map;
+    };
+    map.distances[start] = Some(0);
+    let mut queue = VecDeque::from([(from, 0)]);
+    while let Some(((x, y), distance)) = queue.pop_front() {
+        for action in MOVING_ACTIONS {
+            let (nx, ny) = target(x, y, action);
+            if !grid.is_walkable(nx, ny) {
+                continue;
+            }
+            let next = grid.destination(nx, ny);
+            if grid.is_lethal(next.0, next.1) {
+                continue;
+            }
+            let Some(i) = map.index(next) else {
+                continue;
+            };
+            if map.distances[i].is_none() {
+                map.distances[i] = Some(distance + 1);
+                map.previous[i] = Some((x, y));
+                queue.push_back((next, distance + 1));
+            }
+        }
+    }
+    map
+}
+
+/// Plus court chemin de `from` à `to`, extrémités comprises ; None s'il n'y en a pas
+pub fn shortest_path(grid: &Grid, from: (u32, u32), to: (u32, u32)) -> Option<Vec<(u32, u32)>> {
+    bfs_distances(grid, from).path_to(to)
+}
+
+/// Une fourmi partie de `from` peut atteindre `to`
+pub fn is_reachable(grid: &Grid, from: (u32, u32), to: (u32, u32)) -> bool {
+    bfs_distances(grid, from).is_reachable(to)
+}
+
+/// Plus court aller-retour entre l'un des `nests` et la source de nourriture la plus proche
+/// de ce nid : aller jusqu'à elle, puis revenir au nid le plus proche (une livraison compte
+/// dans n'importe quel nid de la colonie). None si aucun nid n'atteint de nourriture.
+pub fn shortest_round_trip(grid: &Grid, nests: &[(u32, u32)]) -> Option<u32> {
+    nests
+        .iter()
+        .filter_map(|&nest| {
+            let outward = bfs_distances(grid, nest);
+            let (food, there) = outward
+                .reachable_cells()
+                .filter(|&(x, y)| grid.has_food(x, y))
+                .filter_map(|cell| Some((cell, outward.distance(cell)?)))
+                .min_by_key(|&(_, distance)| distance)?;
+            let back = bfs_distances(grid, food);
+            let home = nests.iter().filter_map(|&n| back.distance(n)).min()?;
+            Some(there + home)
+        })
+        .min()
+}
+
+/// Parcours déjà calculés sur une grille, oubliés dès qu'elle change de version
+#[derive(Clone, Debug, Default)]
+pub struct PathCache {
+    revision: u64,
+    maps: HashMap<(u32, u32), DistanceMap>,
+}
+
+impl PathCache {
+    /// Distances depuis `from`, recalculées seulement si la grille a changé
+    pub fn bfs_distances(&mut self, grid: &Grid, from: (u32, u32)) -> &DistanceMap {
+        if grid.revision() != self.revision {
+            self.maps.clear();
+            self.revision = grid.revision();
+        }
+        self.maps
+            .entry(from)
+            .or_insert_with(|| bfs_distances(grid, from))
+    }
+
+    pub fn shortest_path(
+        &mut self,
+        grid: &Grid,
+        from: (u32, u32),
+        to: (u32, u32),
+    ) -> Option<Vec<(u32, u32)>> {
+        self.bfs_distances(grid, from).path_to(to)
+    }
+
+    pub fn distance(&mut self, grid: &Grid, from: (u32, u32), to: (u32, u32)) -> Option<u32> {
+        self.bfs_distances(grid, from).distance(to)
+    }
+
+    pub fn is_reachable(&mut self, grid: &Grid, from: (u32, u32), to: (u32, u32)) -> bool {
+        self.bfs_distances(grid, from).is_reachable(to)
+    }
+}
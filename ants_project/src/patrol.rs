@@ -0,0 +1,64 @@
+//! Patrouille des combattantes
+//!
+//! Quand des prédateurs rôdent, une combattante ne fourrage plus au hasard : elle fonce sur
+//! le prédateur le plus proche dans son rayon de patrouille, et à défaut veille sur les pistes
+//! de nourriture les plus fortes de sa colonie sans s'éloigner du nid de plus de ce rayon.
+
+use crate::baseline;
+use crate::exploration::{target, MOVING_ACTIONS};
+use crate::grid::Grid;
+use crate::pheromone::{Action, PheromoneMap};
+
+fn distance((x, y): (u32, u32), (ox, oy): (u32, u32)) -> u32 {
+    x.abs_diff(ox) + y.abs_diff(oy)
+}
+
+/// Premier pas du plus court chemin vers le prédateur le plus proche à moins de `range`
+/// cases (rester sur place au contact) ; None s'il n'y en a aucun ou qu'il est hors d'atteinte
+pub fn intercept(
+    grid: &Grid,
+    position: (u32, u32),
+    predators: &[(u32, u32)],
+    range: u32,
+) -> Option<Action> {
+    let closest = predators
+        .iter()
+        .copied()
+        .filter(|&p| distance(position, p) <= range)
+        .min_by_key(|&p| distance(position, p))?;
+    if distance(position, closest) <= 1 {
+        return Some(Action::Stay);
+    }
+    baseline::astar_step(grid, position, &[closest], |x, y| {
+        distance((x, y), closest) <= 1
+    })
+}
+
+/// Pas de ronde : retour vers `nest` par le plus court chemin au-delà de `range` cases,
+/// sinon la case voisine sûre où la piste `trail` est la plus forte, sans sortir du rayon
+pub fn patrol(
+    grid: &Grid,
+    trail: &PheromoneMap,
+    position: (u32, u32),
+    nest: Option<(u32, u32)>,
+    range: u32,
+) -> Option<Action> {
+    if let Some(nest) = nest.filter(|&nest| distance(position, nest) > range) {
+        return baseline::astar_step(grid, position, &[nest], |x, y| (x, y) == nest);
+    }
+    let (x, y) = position;
+    MOVING_ACTIONS
+        .iter()
+        .filter_map(|&action| {
+            let (nx, ny) = target(x, y, action);
+            if !grid.is_walkable(nx, ny) {
+                return None;
+            }
+            let arrival = grid.destination(nx, ny);
+            let inside = nest.is_none_or(|nest| distance(arrival, nest) <= range);
+            (inside && !grid.is_lethal(arrival.0, arrival.1))
+                .then(|| (action, trail.get_max_q(arrival.0, arrival.1, grid)))
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(action, _)| action)
+}
@@ -0,0 +1,162 @@
+//! Comparaison des stockages de Q-tables (`--features perf-compare`)
+//!
+//! `ants perf-compare` joue la charge fixe de `--bench` une fois par stockage : l'ancienne
+//! disposition en `Vec` imbriqués, le tableau plat et les blocs alloués à la demande. Chaque
+//! partie part de la même graine ; les empreintes finales doivent donc être identiques, et
+//! seul le temps change. Le tableau donne le débit, le temps de la phase des phéromones
+//! (évaporation comprise) et le gain par rapport à l'ancienne disposition.
+
+use crate::ants_game_manager::AntsGameManager;
+use crate::bench::{self, BenchReport, BENCH_GRID_SIZE};
+use crate::cli_args::{SimulationConfig, DEFAULT_BENCH_TICKS};
+use crate::pheromone::{self, QLayout};
+use crate::replay_verify::StateHashes;
+use std::time::Instant;
+
+struct PerfCompareArgs {
+    ticks: u64,
+    // Côté de la grille, pour voir l'effet de la taille de la carte sur chaque stockage
+    size: u32,
+}
+
+/// Mesures de la charge fixe avec un stockage imposé
+pub struct LayoutReport {
+    pub layout: QLayout,
+    pub report: BenchReport,
+    pub state: StateHashes,
+}
+
+/// Joue `ticks` ticks de la charge fixe sur une grille `size` x `size` avec chaque stockage
+pub fn compare_layouts(ticks: u64, size: u32) -> Result<Vec<LayoutReport>, String> {
+    let config = SimulationConfig {
+        bench_ticks: ticks,
+        use_gui: false,
+        ..SimulationConfig::default()
+    };
+    let reports = QLayout::all()
+        .into_iter()
+        .map(|layout| {
+            pheromone::force_layout(Some(layout));
+            let mut manager = AntsGameManager::from_config(bench::workload(&config, size))
+                .map_err(|e| format!("Erreur de création de la partie: {}", e))?;
+            manager.enable_profiling();
+            let start = Instant::now();
+            for _ in 0..ticks {
+                manager.game_step();
+            }
+            let elapsed = start.elapsed();
+            Ok(LayoutReport {
+                layout,
+                report: BenchReport {
+                    ticks,
+                    elapsed,
+                    timings: manager.phase_timings().cloned().unwrap_or_default(),
+                },
+                state: StateHashes::of(&manager),
+            })
+        })
+        .collect();
+    pheromone::force_layout(None);
+    reports
+}
+
+// Point d'entrée de `ants perf-compare` : `args` ne contient que les options de la sous-commande
+pub fn run(args: &[String]) -> Result<(), String> {
+    let Some(args) = parse_args(args)? else {
+        return Ok(());
+    };
+    println!(
+        "Comparaison des stockages : {} ticks, grille {}x{}",
+        args.ticks, args.size, args.size
+    );
+    let reports = compare_layouts(args.ticks, args.size)?;
+    let legacy = &reports[0];
+    println!(
+        "{:<10} {:>12} {:>12} {:>18} {:>8}",
+        "stockage", "ticks/s", "µs/tick", "phéromones µs/tick", "gain"
+    );
+    let ticks = args.ticks.max(1) as f64;
+    for entry in &reports {
+        let report = &entry.report;
+        println!(
+            "{:<10} {:>12.1} {:>12.2} {:>18.2} {:>7.2}x",
+            entry.layout.name(),
+            report.ticks_per_second(),
+            report.elapsed.as_secs_f64() * 1e6 / ticks,
+            report.timings.pheromones.as_secs_f64() * 1e6 / ticks,
+            report.ticks_per_second() / legacy.report.ticks_per_second().max(f64::EPSILON)
+        );
+    }
+    // Le stockage ne doit rien changer à la partie
+    let diverging: Vec<&str> = reports
+        .iter()
+        .filter(|entry| entry.state != legacy.state)
+        .map(|entry| entry.layout.name())
+        .collect();
+    if !diverging.is_empty() {
+        return Err(format!(
+            "État final différent de {} avec : {}",
+            legacy.layout.name(),
+            diverging.join(", ")
+        ));
+    }
+    println!("État final identique ({})", legacy.state);
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<Option<PerfCompareArgs>, String> {
+    let mut parsed = PerfCompareArgs {
+        ticks: DEFAULT_BENCH_TICKS,
+        size: BENCH_GRID_SIZE,
+    };
+    let mut i = 0;
+    while i < args.len() {
+        let flag = args[i].as_str();
+        if flag == "--help" || flag == "-h" {
+            print_help();
+            return Ok(None);
+        }
+        let value = args
+            .get(i + 1)
+            .ok_or_else(|| format!("{} attend une valeur", flag))?;
+        let number =
+            |expected: &str| format!("Valeur invalide pour {}: {} ({})", flag, value, expected);
+        match flag {
+            "--ticks" => {
+                parsed.ticks = value
+                    .parse()
+                    .ok()
+                    .filter(|&ticks| ticks > 0)
+                    .ok_or_else(|| number("entier > 0"))?
+            }
+            "--size" => {
+                parsed.size = value
+                    .parse()
+                    .ok()
+                    .filter(|&size| size >= 10)
+                    .ok_or_else(|| number("entier >= 10"))?
+            }
+            other => return Err(format!("Argument inconnu: {}", other)),
+        }
+        i += 2;
+    }
+    Ok(Some(parsed))
+}
+
+fn print_help() {
+    println!("Usage: ants perf-compare [OPTIONS]");
+    println!();
+    println!("Joue la charge fixe de --bench avec chaque stockage des Q-tables (nested : ancienne");
+    println!("disposition en Vec imbriqués, dense : tableau plat, chunked : blocs alloués à la");
+    println!("demande) et compare leurs débits. Les parties doivent finir dans le même état.");
+    println!();
+    println!("OPTIONS:");
+    println!(
+        "  --ticks <N>            Ticks joués avec chaque stockage (défaut: {})",
+        DEFAULT_BENCH_TICKS
+    );
+    println!(
+        "  --size <N>             Côté de la grille (défaut: {})",
+        BENCH_GRID_SIZE
+    );
+}
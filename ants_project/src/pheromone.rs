@@ -1,9 +1,15 @@
 // src/pheromones.rs
+use crate::coord::{Coord, GridIndex, GridShape};
+use crate::exploration;
 use crate::grid::Grid;
-use std::collections::HashMap;
+use crate::q_table::StateFeatures;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs;
 
 // Énumération des cinq actions possibles pour une fourmi
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Action {
     Up = 0,
     Down = 1,
@@ -31,55 +37,743 @@ impl Action {
     }
 }
 
-#[derive(Clone)]
+pub(crate) const ACTION_COUNT: usize = 5;
+
+/// Adaptation d'une Q-table à une grille d'une autre taille (transfert entre deux cartes)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Resample {
+    /// Étirement au plus proche voisin : chaque case reprend les valeurs de la case qui
+    /// occupe la même position relative dans l'ancienne carte
+    #[default]
+    Scale,
+    /// Recadrage depuis le coin haut gauche : les cases communes gardent leurs valeurs, les
+    /// nouvelles partent de zéro et celles qui dépassent sont perdues
+    Crop,
+    /// Interpolation bilinéaire des valeurs Q des quatre cases les plus proches : plus
+    /// lisse que `Scale`, mais les pistes étroites s'étalent sur les cases voisines
+    Bilinear,
+}
+
+impl Resample {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "scale" => Some(Resample::Scale),
+            "crop" => Some(Resample::Crop),
+            "bilinear" => Some(Resample::Bilinear),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Resample::Scale => "scale",
+            Resample::Crop => "crop",
+            Resample::Bilinear => "bilinear",
+        }
+    }
+}
+
+/// Mise en commun des Q-tables des colonies d'un ensemble
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QMerge {
+    /// Moyenne de chaque valeur Q sur toutes les tables
+    #[default]
+    Mean,
+    /// Vote case par case : chaque table qui a appris quelque chose sur la case vote pour sa
+    /// meilleure action, et la case reprend la moyenne des tables de l'action majoritaire
+    Vote,
+}
+
+impl QMerge {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "mean" => Some(QMerge::Mean),
+            "vote" => Some(QMerge::Vote),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            QMerge::Mean => "mean",
+            QMerge::Vote => "vote",
+        }
+    }
+
+    // Valeurs communes d'une case à partir de celles de chaque table (écarts à la valeur
+    // de départ, comme dans le stockage)
+    fn cell(&self, cells: &[&[f32]]) -> [f32; ACTION_COUNT] {
+        let voters: Vec<&[f32]> = match self {
+            QMerge::Mean => cells.to_vec(),
+            QMerge::Vote => {
+                // Une table qui n'a jamais touché la case ne vote pas
+                let ballots: Vec<(&[f32], usize)> = cells
+                    .iter()
+                    .filter(|values| values.iter().any(|&q| q != 0.0))
+                    .map(|&values| (values, best_index(values)))
+                    .collect();
+                let mut votes = [0; ACTION_COUNT];
+                for &(_, action) in &ballots {
+                    votes[action] += 1;
+                }
+                // À égalité, l'action la plus tôt dans l'ordre de `Action::all` l'emporte
+                let winner = (0..ACTION_COUNT)
+                    .max_by_key(|&action| (votes[action], std::cmp::Reverse(action)))
+                    .unwrap_or(0);
+                ballots
+                    .into_iter()
+                    .filter(|&(_, action)| action == winner)
+                    .map(|(values, _)| values)
+                    .collect()
+            }
+        };
+        let mut merged = [0.0; ACTION_COUNT];
+        if voters.is_empty() {
+            return merged;
+        }
+        for values in &voters {
+            for (sum, q) in merged.iter_mut().zip(values.iter()) {
+                *sum += q;
+            }
+        }
+        merged.map(|sum| sum / voters.len() as f32)
+    }
+}
+
+// Indice de la plus forte valeur (la première en cas d'égalité)
+fn best_index(values: &[f32]) -> usize {
+    values
+        .iter()
+        .enumerate()
+        .fold((0, f32::NEG_INFINITY), |best, (i, &q)| {
+            if q > best.1 {
+                (i, q)
+            } else {
+                best
+            }
+        })
+        .0
+}
+
+/// Au-delà de ce nombre de cases, une Q-table range ses valeurs par blocs alloués à la
+/// demande plutôt que dans un tableau couvrant toute la carte
+pub const SPARSE_MIN_CELLS: usize = 250_000;
+
+// Côté d'un bloc du stockage par blocs, en cases
+const CHUNK_SIDE: u32 = 16;
+const CHUNK_LEN: usize = (CHUNK_SIDE * CHUNK_SIDE) as usize * ACTION_COUNT;
+
+// Valeurs d'une case jamais mise à jour
+const ZERO_CELL: [f32; ACTION_COUNT] = [0.0; ACTION_COUNT];
+
+/// Stockage des Q-values, imposé à toutes les tables créées par `force_layout` pour comparer
+/// leurs performances sur une même partie (`ants perf-compare`)
+#[cfg(feature = "perf-compare")]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QLayout {
+    /// Disposition d'origine : un `Vec` de colonnes, toute la carte évaporée à chaque tick
+    Nested,
+    /// Tableau plat, évaporation des seules cases actives (défaut des petites cartes)
+    Dense,
+    /// Blocs alloués à la demande (défaut au-delà de `SPARSE_MIN_CELLS` cases)
+    Chunked,
+}
+
+#[cfg(feature = "perf-compare")]
+impl QLayout {
+    pub fn all() -> [QLayout; 3] {
+        [QLayout::Nested, QLayout::Dense, QLayout::Chunked]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            QLayout::Nested => "nested",
+            QLayout::Dense => "dense",
+            QLayout::Chunked => "chunked",
+        }
+    }
+}
+
+// Stockage imposé : 0 pour le choix selon la taille, sinon 1 + indice dans QLayout::all()
+#[cfg(feature = "perf-compare")]
+static FORCED_LAYOUT: std::sync::atomic::AtomicU8 = std::sync::atomic::AtomicU8::new(0);
+
+/// Impose `layout` aux tables créées ensuite, dans tout le processus ; `None` rétablit le
+/// choix selon la taille de la carte
+#[cfg(feature = "perf-compare")]
+pub fn force_layout(layout: Option<QLayout>) {
+    let code = layout.map_or(0, |layout| {
+        1 + QLayout::all().iter().position(|&l| l == layout).unwrap() as u8
+    });
+    FORCED_LAYOUT.store(code, std::sync::atomic::Ordering::Relaxed);
+}
+
+#[cfg(feature = "perf-compare")]
+fn forced_layout() -> Option<QLayout> {
+    match FORCED_LAYOUT.load(std::sync::atomic::Ordering::Relaxed) {
+        0 => None,
+        code => Some(QLayout::all()[code as usize - 1]),
+    }
+}
+
+/// Valeur de départ des Q-values et borne de leur amplitude
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct QBounds {
+    /// Valeur d'une paire (case, action) jamais mise à jour ; positive, elle rend l'inconnu
+    /// attirant (initialisation optimiste)
+    pub initial: f32,
+    /// Amplitude maximale |Q| après chaque mise à jour, 0 = sans borne
+    pub clamp: f32,
+}
+
+impl QBounds {
+    // Écart à la valeur de départ ramené dans la borne, pour une valeur stockée `stored`
+    fn clamped(&self, stored: f32) -> f32 {
+        if self.clamp <= 0.0 {
+            return stored;
+        }
+        (self.initial + stored).clamp(-self.clamp, self.clamp) - self.initial
+    }
+}
+
+// Q-table d'une carte : la valeur de (x, y, action) a l'indice (y * width + x) * 5 + action,
+// quel que soit le stockage. En Double Q-Learning, une seconde table de même forme est
+// apprise en alternance ; les valeurs lues par les fourmis sont alors la moyenne des deux.
+// Le stockage garde l'écart à `bounds.initial` : une case jamais visitée ne coûte rien même
+// avec une valeur de départ non nulle, et l'évaporation ramène vers cette valeur.
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(try_from = "PheromoneMapFile", into = "PheromoneMapFile")]
 pub struct PheromoneMap {
     pub(crate) width: u32,
     pub(crate) height: u32,
-    data: Vec<Vec<[f32; 5]>>,
-    pending_updates: HashMap<(u32, u32, usize), f32>,
+    values: QValues,
+    // Mises à jour du tick en cours : (indice de l'entrée, delta)
+    pending_updates: Vec<(usize, f32)>,
+    // Seconde table du Double Q-Learning (sans table jumelle elle-même)
+    twin: Option<Box<PheromoneMap>>,
+    bounds: QBounds,
+}
+
+// Valeurs d'une case (et de la table jumelle), gardées par la timeline de rembobinage
+#[derive(Clone, Serialize, Deserialize)]
+pub(crate) struct CellValues {
+    cell: usize,
+    values: [f32; ACTION_COUNT],
+    twin: Option<[f32; ACTION_COUNT]>,
+}
+
+// Stockage des Q-values, choisi à la création selon la taille de la carte
+#[derive(Clone)]
+enum QValues {
+    // Tableau plat de toute la carte ; seules les cases non nulles subissent l'évaporation
+    Dense {
+        data: Vec<f32>,
+        active_cells: Vec<usize>,
+        is_active: Vec<bool>,
+    },
+    // Blocs de CHUNK_SIDE x CHUNK_SIDE cases, absents tant que toutes leurs valeurs sont
+    // nulles et libérés quand elles y retombent : seuls les blocs présents s'évaporent
+    Chunked {
+        shape: GridShape,
+        chunks_x: u32,
+        chunks: Vec<Option<Box<[f32]>>>,
+        active_chunks: Vec<usize>,
+    },
+    // Disposition d'origine, indexée par [x][y], sans suivi des cases actives
+    #[cfg(feature = "perf-compare")]
+    Nested {
+        width: u32,
+        columns: Vec<Vec<[f32; ACTION_COUNT]>>,
+    },
+}
+
+impl QValues {
+    fn new(width: u32, height: u32) -> Self {
+        let cells = GridShape::new(width, height).len();
+        #[cfg(feature = "perf-compare")]
+        match forced_layout() {
+            Some(QLayout::Nested) => {
+                return QValues::Nested {
+                    width,
+                    columns: vec![vec![ZERO_CELL; height as usize]; width as usize],
+                }
+            }
+            Some(QLayout::Dense) => return Self::dense(cells),
+            Some(QLayout::Chunked) => return Self::chunked(width, height),
+            None => {}
+        }
+        if cells < SPARSE_MIN_CELLS {
+            Self::dense(cells)
+        } else {
+            Self::chunked(width, height)
+        }
+    }
+
+    fn dense(cells: usize) -> Self {
+        QValues::Dense {
+            data: vec![0.0; cells * ACTION_COUNT],
+            active_cells: Vec::new(),
+            is_active: vec![false; cells],
+        }
+    }
+
+    fn chunked(width: u32, height: u32) -> Self {
+        let chunks_x = width.div_ceil(CHUNK_SIDE);
+        let chunks_y = height.div_ceil(CHUNK_SIDE);
+        QValues::Chunked {
+            shape: GridShape::new(width, height),
+            chunks_x,
+            chunks: vec![None; (chunks_x * chunks_y) as usize],
+            active_chunks: Vec::new(),
+        }
+    }
+
+    // Bloc contenant la case, et indice de sa première valeur dans le bloc
+    fn locate(shape: GridShape, chunks_x: u32, cell: usize) -> (usize, usize) {
+        let Coord { x, y } = shape.coord_at(cell);
+        let chunk = (y / CHUNK_SIDE * chunks_x + x / CHUNK_SIDE) as usize;
+        let offset = (y % CHUNK_SIDE * CHUNK_SIDE + x % CHUNK_SIDE) as usize * ACTION_COUNT;
+        (chunk, offset)
+    }
+
+    fn cell(&self, cell: usize) -> &[f32] {
+        match self {
+            QValues::Dense { data, .. } => &data[cell * ACTION_COUNT..(cell + 1) * ACTION_COUNT],
+            QValues::Chunked {
+                shape,
+                chunks_x,
+                chunks,
+                ..
+            } => {
+                let (chunk, offset) = Self::locate(*shape, *chunks_x, cell);
+                match &chunks[chunk] {
+                    Some(values) => &values[offset..offset + ACTION_COUNT],
+                    None => &ZERO_CELL,
+                }
+            }
+            #[cfg(feature = "perf-compare")]
+            QValues::Nested { width, columns } => {
+                &columns[cell % *width as usize][cell / *width as usize]
+            }
+        }
+    }
+
+    fn get(&self, idx: usize) -> f32 {
+        self.cell(idx / ACTION_COUNT)[idx % ACTION_COUNT]
+    }
+
+    // Valeurs modifiables de la case, qui devient active (son bloc est alloué au besoin)
+    fn cell_mut(&mut self, cell: usize) -> &mut [f32] {
+        match self {
+            QValues::Dense {
+                data,
+                active_cells,
+                is_active,
+            } => {
+                if !is_active[cell] {
+                    is_active[cell] = true;
+                    active_cells.push(cell);
+                }
+                &mut data[cell * ACTION_COUNT..(cell + 1) * ACTION_COUNT]
+            }
+            QValues::Chunked {
+                shape,
+                chunks_x,
+                chunks,
+                active_chunks,
+            } => {
+                let (chunk, offset) = Self::locate(*shape, *chunks_x, cell);
+                let values = chunks[chunk].get_or_insert_with(|| {
+                    active_chunks.push(chunk);
+                    vec![0.0; CHUNK_LEN].into_boxed_slice()
+                });
+                &mut values[offset..offset + ACTION_COUNT]
+            }
+            #[cfg(feature = "perf-compare")]
+            QValues::Nested { width, columns } => {
+                &mut columns[cell % *width as usize][cell / *width as usize]
+            }
+        }
+    }
+
+    // Valeurs des cases actives ; dans l'ordre des positions si `ordered`, pour qu'une somme
+    // ne dépende pas de l'ordre d'activation
+    fn active_values(&self, ordered: bool) -> Box<dyn Iterator<Item = &f32> + '_> {
+        match self {
+            QValues::Dense {
+                data, is_active, ..
+            } if ordered => Box::new(
+                (0..is_active.len())
+                    .filter(|&cell| is_active[cell])
+                    .flat_map(|cell| &data[cell * ACTION_COUNT..(cell + 1) * ACTION_COUNT]),
+            ),
+            QValues::Dense {
+                data, active_cells, ..
+            } => Box::new(
+                active_cells
+                    .iter()
+                    .flat_map(|&cell| &data[cell * ACTION_COUNT..(cell + 1) * ACTION_COUNT]),
+            ),
+            QValues::Chunked {
+                chunks,
+                active_chunks,
+                ..
+            } => {
+                let mut order = active_chunks.clone();
+                if ordered {
+                    order.sort_unstable();
+                }
+                Box::new(
+                    order
+                        .into_iter()
+                        .flat_map(|chunk| chunks[chunk].iter().flat_map(|values| values.iter())),
+                )
+            }
+            // Toutes les cases comptent comme actives, toujours dans l'ordre des positions
+            #[cfg(feature = "perf-compare")]
+            QValues::Nested { columns, .. } => {
+                let height = columns.first().map_or(0, Vec::len);
+                Box::new(
+                    (0..height)
+                        .flat_map(move |y| columns.iter().flat_map(move |column| &column[y])),
+                )
+            }
+        }
+    }
+
+    // Cases de la carte pouvant porter une valeur non nulle (toutes celles des blocs présents),
+    // sans ordre
+    fn active_cells(&self) -> Vec<usize> {
+        match self {
+            QValues::Dense { active_cells, .. } => active_cells.clone(),
+            QValues::Chunked {
+                shape,
+                chunks_x,
+                active_chunks,
+                ..
+            } => active_chunks
+                .iter()
+                .flat_map(|&chunk| {
+                    let left = chunk as u32 % chunks_x * CHUNK_SIDE;
+                    let top = chunk as u32 / chunks_x * CHUNK_SIDE;
+                    (top..top + CHUNK_SIDE).flat_map(move |y| {
+                        (left..left + CHUNK_SIDE)
+                            .filter_map(move |x| shape.index(Coord::new(x, y)))
+                            .map(GridIndex::as_usize)
+                    })
+                })
+                .collect(),
+            #[cfg(feature = "perf-compare")]
+            QValues::Nested { columns, .. } => {
+                (0..columns.len() * columns.first().map_or(0, Vec::len)).collect()
+            }
+        }
+    }
+
+    // Remplace les valeurs d'une case ; une case nulle qui le reste n'est pas activée
+    fn set_cell(&mut self, cell: usize, values: &[f32]) {
+        if values.iter().all(|&q| q == 0.0) && self.cell(cell).iter().all(|&q| q == 0.0) {
+            return;
+        }
+        self.cell_mut(cell).copy_from_slice(values);
+    }
+
+    // Évaporation des seules cases (ou blocs) actives ; celles retombées à zéro sont oubliées
+    fn evaporate(&mut self, evaporation_rate: f32) {
+        match self {
+            QValues::Dense {
+                data,
+                active_cells,
+                is_active,
+            } => active_cells.retain(|&cell| {
+                let values = &mut data[cell * ACTION_COUNT..(cell + 1) * ACTION_COUNT];
+                let still_active = evaporate_values(values, evaporation_rate);
+                is_active[cell] = still_active;
+                still_active
+            }),
+            QValues::Chunked {
+                chunks,
+                active_chunks,
+                ..
+            } => active_chunks.retain(|&chunk| {
+                let Some(values) = &mut chunks[chunk] else {
+                    return false;
+                };
+                let still_active = evaporate_values(values, evaporation_rate);
+                if !still_active {
+                    chunks[chunk] = None;
+                }
+                still_active
+            }),
+            #[cfg(feature = "perf-compare")]
+            QValues::Nested { columns, .. } => {
+                for values in columns.iter_mut().flat_map(|column| column.iter_mut()) {
+                    evaporate_values(values, evaporation_rate);
+                }
+            }
+        }
+    }
+}
+
+// Évapore des valeurs Q et indique s'il en reste une non nulle
+fn evaporate_values(values: &mut [f32], evaporation_rate: f32) -> bool {
+    for val in values.iter_mut() {
+        // Si le taux est 0.01 (1%), on multiplie par 0.99 (99% restant)
+        *val *= 1.0 - evaporation_rate;
+
+        if val.abs() < 0.001 {
+            *val = 0.0;
+        }
+    }
+    values.iter().any(|&q| q != 0.0)
+}
+
+// Forme sérialisée : tableau plat quel que soit le stockage, les buffers de travail se
+// reconstruisent au chargement
+#[derive(Serialize, Deserialize)]
+struct PheromoneMapFile {
+    width: u32,
+    height: u32,
+    data: Vec<f32>,
+    #[serde(default)]
+    twin: Option<Vec<f32>>,
+    #[serde(default)]
+    bounds: QBounds,
+}
+
+impl From<PheromoneMap> for PheromoneMapFile {
+    fn from(map: PheromoneMap) -> Self {
+        PheromoneMapFile {
+            width: map.width,
+            height: map.height,
+            data: map.dense_values(),
+            twin: map.twin.map(|twin| twin.dense_values()),
+            bounds: map.bounds,
+        }
+    }
+}
+
+impl TryFrom<PheromoneMapFile> for PheromoneMap {
+    type Error = String;
+
+    fn try_from(file: PheromoneMapFile) -> Result<Self, String> {
+        let mut map = PheromoneMap::from_data(file.width, file.height, file.data)?;
+        if let Some(twin) = file.twin {
+            map.twin = Some(Box::new(PheromoneMap::from_data(
+                file.width,
+                file.height,
+                twin,
+            )?));
+        }
+        map.set_bounds(file.bounds);
+        Ok(map)
+    }
 }
 
 impl PheromoneMap {
     pub fn new(width: u32, height: u32) -> Self {
+        PheromoneMap::with_bounds(width, height, QBounds::default())
+    }
+
+    /// Carte dont toutes les paires valent `bounds.initial`, bornée par `bounds.clamp`
+    pub fn with_bounds(width: u32, height: u32, bounds: QBounds) -> Self {
         PheromoneMap {
             width,
             height,
-            data: vec![vec![[0.0; 5]; height as usize]; width as usize],
-            pending_updates: HashMap::new(),
+            values: QValues::new(width, height),
+            pending_updates: Vec::new(),
+            twin: None,
+            bounds,
+        }
+    }
+
+    /// Change la valeur de départ et la borne (de la table jumelle aussi). Les écarts déjà
+    /// appris sont gardés : changer la valeur de départ décale toute la table.
+    pub fn set_bounds(&mut self, bounds: QBounds) {
+        self.bounds = bounds;
+        if let Some(twin) = &mut self.twin {
+            twin.set_bounds(bounds);
+        }
+    }
+
+    pub fn bounds(&self) -> QBounds {
+        self.bounds
+    }
+
+    fn from_data(width: u32, height: u32, data: Vec<f32>) -> Result<Self, String> {
+        let expected = GridShape::new(width, height).len() * ACTION_COUNT;
+        if data.len() != expected {
+            return Err(format!(
+                "{} valeurs Q pour une carte {}x{} ({} attendues)",
+                data.len(),
+                width,
+                height,
+                expected
+            ));
+        }
+        let mut map = PheromoneMap::new(width, height);
+        for (cell, values) in data.chunks(ACTION_COUNT).enumerate() {
+            if values.iter().any(|&q| q != 0.0) {
+                map.values.cell_mut(cell).copy_from_slice(values);
+            }
+        }
+        Ok(map)
+    }
+
+    // Toutes les valeurs de la carte dans un tableau plat, cases jamais visitées comprises
+    fn dense_values(&self) -> Vec<f32> {
+        match &self.values {
+            QValues::Dense { data, .. } => data.clone(),
+            _ => (0..self.shape().len())
+                .flat_map(|cell| self.cell(cell).iter().copied())
+                .collect(),
+        }
+    }
+
+    /// Vrai si les valeurs sont rangées par blocs alloués à la demande (grandes cartes)
+    pub fn is_sparse(&self) -> bool {
+        matches!(self.values, QValues::Chunked { .. })
+    }
+
+    /// Copie de la Q-table (et de sa seconde table) adaptée à une grille `width` x `height`
+    pub fn resampled(&self, width: u32, height: u32, mode: Resample) -> PheromoneMap {
+        let mut map = PheromoneMap::with_bounds(width, height, self.bounds);
+        for y in 0..height {
+            for x in 0..width {
+                let values = self.sample(x, y, width, height, mode);
+                if values.iter().any(|&q| q != 0.0) {
+                    let to = map.cell_index(x, y);
+                    map.values.cell_mut(to).copy_from_slice(&values);
+                }
+            }
+        }
+        map.twin = self
+            .twin
+            .as_ref()
+            .map(|twin| Box::new(twin.resampled(width, height, mode)));
+        map
+    }
+
+    // Valeurs Q de la case (x, y) d'une grille `width` x `height` tirées de cette table
+    fn sample(
+        &self,
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+        mode: Resample,
+    ) -> [f32; ACTION_COUNT] {
+        let mut values = [0.0; ACTION_COUNT];
+        match mode {
+            // Centre de la case cible ramené dans cette carte
+            Resample::Scale => {
+                let sx = (2 * x as u64 + 1) * self.width as u64 / (2 * width as u64);
+                let sy = (2 * y as u64 + 1) * self.height as u64 / (2 * height as u64);
+                values.copy_from_slice(self.cell(self.cell_index(sx as u32, sy as u32)));
+            }
+            Resample::Crop => {
+                if x < self.width && y < self.height {
+                    values.copy_from_slice(self.cell(self.cell_index(x, y)));
+                }
+            }
+            Resample::Bilinear => {
+                // Même centre que Scale, en coordonnées continues entre les centres des cases
+                let axis = |pos: u32, target: u32, source: u32| {
+                    let at = ((pos as f32 + 0.5) * source as f32 / target as f32 - 0.5)
+                        .clamp(0.0, (source - 1) as f32);
+                    let low = at.floor() as u32;
+                    (low, (low + 1).min(source - 1), at - low as f32)
+                };
+                let (x0, x1, fx) = axis(x, width, self.width);
+                let (y0, y1, fy) = axis(y, height, self.height);
+                for (cx, cy, weight) in [
+                    (x0, y0, (1.0 - fx) * (1.0 - fy)),
+                    (x1, y0, fx * (1.0 - fy)),
+                    (x0, y1, (1.0 - fx) * fy),
+                    (x1, y1, fx * fy),
+                ] {
+                    for (value, q) in values.iter_mut().zip(self.cell(self.cell_index(cx, cy))) {
+                        *value += weight * q;
+                    }
+                }
+            }
         }
+        values
+    }
+
+    /// Ajoute (ou retire) la seconde table du Double Q-Learning ; elle part des valeurs déjà
+    /// apprises
+    pub fn set_double(&mut self, enabled: bool) {
+        if !enabled {
+            self.twin = None;
+        } else if self.twin.is_none() {
+            let mut twin = self.clone();
+            twin.pending_updates.clear();
+            self.twin = Some(Box::new(twin));
+        }
+    }
+
+    pub fn is_double(&self) -> bool {
+        self.twin.is_some()
+    }
+
+    /// Table mise à jour : la seconde (si elle existe) ou celle-ci
+    pub fn table_mut(&mut self, second: bool) -> &mut PheromoneMap {
+        if second && self.twin.is_some() {
+            self.twin.as_deref_mut().unwrap()
+        } else {
+            self
+        }
+    }
+
+    /// Valeurs Q brutes des cinq actions en (x, y) dans l'une des deux tables
+    pub fn table_values(&self, second: bool, x: u32, y: u32) -> [f32; ACTION_COUNT] {
+        let table = match &self.twin {
+            Some(twin) if second => twin,
+            _ => self,
+        };
+        let mut values = [self.bounds.initial; ACTION_COUNT];
+        if x < self.width && y < self.height {
+            for (value, q) in values.iter_mut().zip(table.cell(table.cell_index(x, y))) {
+                *value += q;
+            }
+        }
+        values
+    }
+
+    /// Dimensions de la carte ; la case (x, y) a le rang `shape().index_of((x, y))`
+    pub fn shape(&self) -> GridShape {
+        GridShape::new(self.width, self.height)
+    }
+
+    fn cell_index(&self, x: u32, y: u32) -> usize {
+        self.shape().index_of(Coord::new(x, y)).as_usize()
+    }
+
+    fn cell(&self, cell: usize) -> &[f32] {
+        self.values.cell(cell)
     }
 
     pub fn get_q(&self, x: u32, y: u32, action: Action) -> f32 {
         if x >= self.width || y >= self.height {
             return -1000.0; // Hors map
         }
-        self.data[x as usize][y as usize][action.to_usize()]
+        let idx = self.cell_index(x, y) * ACTION_COUNT + action.to_usize();
+        self.bounds.initial
+            + match &self.twin {
+                Some(twin) => (self.values.get(idx) + twin.values.get(idx)) / 2.0,
+                None => self.values.get(idx),
+            }
     }
-
     // Trouver la meilleure action en évitant les murs et en exploitation de la connaissance
     pub fn get_best_action(&self, x: u32, y: u32, grid: &Grid) -> Action {
         let mut best_action = Action::Stay; // Fallback si bloquée
         let mut max_val = -f32::INFINITY;
 
-        // MODIFICATION : On définit manuellement les actions de mouvement uniquement
-        let moving_actions = [Action::Up, Action::Down, Action::Left, Action::Right];
-
-        // On itère sur moving_actions au lieu de Action::all()
-        for &action in moving_actions.iter() {
-            // Simuler la position de destination pour cette action
-            let (nx, ny) = match action {
-                Action::Up => (x, y.saturating_sub(1)),
-                Action::Down => (x, y + 1),
-                Action::Left => (x.saturating_sub(1), y),
-                Action::Right => (x + 1, y),
-                _ => (x, y), // Cas impossible ici
-            };
-
-            // Ignorer si hors map ou mur
-            if nx >= self.width || ny >= self.height || !grid.is_walkable(nx, ny) {
-                continue;
-            }
-
+        // Mêmes déplacements que ceux que l'exploration peut tirer
+        for action in exploration::valid_actions(x, y, grid, 0) {
             let val = self.get_q(x, y, action);
 
             // Ici on prend strictement supérieur, donc la première action (Up) gagne en cas d'égalité 0
@@ -89,22 +783,18 @@ impl PheromoneMap {
             }
         }
 
-        // Si max_val est toujours -Infini, c'est qu'elle est enfermée par des murs
-        if max_val == -f32::INFINITY {
-            return Action::Stay;
-        }
-
         best_action
     }
-    // Obtenir la valeur Q maximale de l'état suivant
+    // Obtenir la valeur Q maximale de l'état suivant, sur les 5 actions murs compris
     pub fn get_max_q(&self, x: u32, y: u32, _grid: &Grid) -> f32 {
         if x >= self.width || y >= self.height {
             return 0.0;
         }
         let mut max_val = -f32::INFINITY;
-        for q in self.data[x as usize][y as usize].iter() {
-            if *q > max_val {
-                max_val = *q;
+        for action in Action::all() {
+            let q = self.get_q(x, y, action);
+            if q > max_val {
+                max_val = q;
             }
         }
         if max_val == -f32::INFINITY {
@@ -114,31 +804,423 @@ impl PheromoneMap {
         }
     }
 
+    /// Valeur Q maximale de l'état suivant parmi les déplacements que la fourmi peut y
+    /// jouer, ceux de `get_best_action` (rester sur place si elle est emmurée) : les pas
+    /// vers un mur, jamais joués, ne gonflent plus la cible de Bellman
+    pub fn get_max_valid_q(&self, x: u32, y: u32, grid: &Grid) -> f32 {
+        if x >= self.width || y >= self.height {
+            return 0.0;
+        }
+        let actions = exploration::valid_actions(x, y, grid, 0);
+        if actions.is_empty() {
+            return self.get_q(x, y, Action::Stay);
+        }
+        actions
+            .into_iter()
+            .map(|action| self.get_q(x, y, action))
+            .fold(f32::NEG_INFINITY, f32::max)
+    }
+
+    /// Cases dont une valeur Q peut être non nulle, dans l'ordre des positions ; les autres
+    /// valent zéro (sert à ne dessiner que les pistes existantes)
+    pub fn active_cells(&self) -> Vec<(u32, u32)> {
+        self.active_cell_indices()
+            .into_iter()
+            .map(|cell| self.shape().coord_at(cell).into())
+            .collect()
+    }
+
+    // Indices triés des cases actives de la table ou de sa jumelle
+    fn active_cell_indices(&self) -> Vec<usize> {
+        let mut cells = self.values.active_cells();
+        if let Some(twin) = &self.twin {
+            cells.extend(twin.values.active_cells());
+        }
+        let total = self.shape().len();
+        cells.retain(|&cell| cell < total);
+        cells.sort_unstable();
+        cells.dedup();
+        cells
+    }
+
+    // Même taille et même nombre de tables : les cases de l'une s'écrivent dans l'autre
+    pub(crate) fn same_shape(&self, other: &PheromoneMap) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.is_double() == other.is_double()
+    }
+
+    // Cases dont les valeurs (ou celles de la table jumelle) diffèrent de `old`, de même forme
+    pub(crate) fn changed_cells(&self, old: &PheromoneMap) -> Vec<CellValues> {
+        let mut cells = self.active_cell_indices();
+        cells.extend(old.active_cell_indices());
+        cells.sort_unstable();
+        cells.dedup();
+        cells
+            .into_iter()
+            .filter(|&cell| {
+                self.values.cell(cell) != old.values.cell(cell)
+                    || self
+                        .twin
+                        .as_ref()
+                        .zip(old.twin.as_ref())
+                        .is_some_and(|(twin, old)| twin.values.cell(cell) != old.values.cell(cell))
+            })
+            .map(|cell| CellValues {
+                cell,
+                values: self.values.cell(cell).try_into().unwrap(),
+                twin: self
+                    .twin
+                    .as_ref()
+                    .map(|twin| twin.values.cell(cell).try_into().unwrap()),
+            })
+            .collect()
+    }
+
+    // Réécrit une case relevée par `changed_cells`
+    pub(crate) fn set_cell(&mut self, cell: &CellValues) {
+        self.values.set_cell(cell.cell, &cell.values);
+        if let (Some(twin), Some(values)) = (&mut self.twin, &cell.twin) {
+            twin.values.set_cell(cell.cell, values);
+        }
+    }
+
+    /// Table commune à plusieurs tables de même forme (colonies d'un ensemble), fusionnées
+    /// case par case selon `merge` ; les tables jumelles du Double Q-Learning sont fusionnées
+    /// entre elles. Les mises à jour en attente ne sont pas reprises.
+    pub fn merged(maps: &[&PheromoneMap], merge: QMerge) -> PheromoneMap {
+        let first = maps[0];
+        let mut merged = PheromoneMap::with_bounds(first.width, first.height, first.bounds);
+        let mut cells: Vec<usize> = maps
+            .iter()
+            .flat_map(|map| map.values.active_cells())
+            .collect();
+        cells.sort_unstable();
+        cells.dedup();
+        let total = first.shape().len();
+        for cell in cells.into_iter().filter(|&cell| cell < total) {
+            let values: Vec<&[f32]> = maps.iter().map(|map| map.values.cell(cell)).collect();
+            merged.values.set_cell(cell, &merge.cell(&values));
+        }
+        let twins: Option<Vec<&PheromoneMap>> =
+            maps.iter().map(|map| map.twin.as_deref()).collect();
+        merged.twin = twins.map(|twins| Box::new(PheromoneMap::merged(&twins, merge)));
+        merged
+    }
+
+    // Valeur Q la plus forte de toute la carte (intensité de la meilleure piste)
+    pub fn max_value(&self) -> f32 {
+        let own = self
+            .values
+            .active_values(false)
+            .fold(0.0, |acc: f32, &q| acc.max(self.bounds.initial + q));
+        self.twin
+            .as_ref()
+            .map_or(own, |twin| own.max(twin.max_value()))
+    }
+
+    // Moyenne des valeurs Q absolues (mesure globale de l'apprentissage)
+    pub fn mean_abs_q(&self) -> f32 {
+        let entries = self.shape().len() * ACTION_COUNT;
+        if entries == 0 {
+            return 0.0;
+        }
+        // Somme dans l'ordre des cases (et non d'activation) : une carte rechargée depuis un
+        // fichier retrouve exactement la même valeur
+        let initial = self.bounds.initial;
+        let (sum, active) = self
+            .values
+            .active_values(true)
+            .fold((0.0f32, 0), |(sum, n), q| {
+                (sum + (initial + q).abs(), n + 1)
+            });
+        // Les paires jamais allouées valent la valeur de départ
+        let own = (sum + (entries - active) as f32 * initial.abs()) / entries as f32;
+        self.twin
+            .as_ref()
+            .map_or(own, |twin| (own + twin.mean_abs_q()) / 2.0)
+    }
+
+    // Indice de (x, y, action) dans le tableau de Q-values, None hors de la carte
+    fn entry_index(&self, x: u32, y: u32, action: Action) -> Option<usize> {
+        self.shape()
+            .index(Coord::new(x, y))
+            .map(|cell| cell.as_usize() * ACTION_COUNT + action.to_usize())
+    }
+
     // Ajouter une modification au buffer sans toucher la grille immédiatement
     pub fn queue_update(&mut self, x: u32, y: u32, action: Action, delta: f32) {
-        let key = (x, y, action.to_usize());
-        *self.pending_updates.entry(key).or_insert(0.0) += delta;
+        if let Some(idx) = self.entry_index(x, y, action) {
+            self.pending_updates.push((idx, delta));
+        }
     }
 
-    // Appliquer tous les changements en attente et appliquer l'évaporation
+    // Q(λ) : répartir la correction sur toutes les paires de la trace, au prorata de leur
+    // éligibilité
+    pub fn queue_trace_update(&mut self, trace: &EligibilityTrace, delta: f32) {
+        self.pending_updates
+            .extend(trace.entries.iter().map(|&(idx, e)| (idx, delta * e)));
+    }
+
+    // Appliquer tous les changements en attente (dans la borne) et appliquer l'évaporation
     pub fn apply_tick(&mut self, evaporation_rate: f32) {
+        if let Some(twin) = &mut self.twin {
+            twin.apply_tick(evaporation_rate);
+        }
         // Appliquer les mises à jour en attente au tableau de Q-values
-        for ((x, y, act_idx), val) in self.pending_updates.drain() {
-            self.data[x as usize][y as usize][act_idx] += val;
+        for i in 0..self.pending_updates.len() {
+            let (idx, val) = self.pending_updates[i];
+            let q = &mut self.values.cell_mut(idx / ACTION_COUNT)[idx % ACTION_COUNT];
+            *q = self.bounds.clamped(*q + val);
         }
+        // clear() garde l'allocation du buffer pour le tick suivant
+        self.pending_updates.clear();
 
-        // Appliquer l'évaporation à toutes les phéromones
-        for col in self.data.iter_mut() {
-            for row in col.iter_mut() {
-                for val in row.iter_mut() {
-                    // Si le taux est 0.01 (1%), on multiplie par 0.99 (99% restant)
-                    *val *= 1.0 - evaporation_rate;
+        // Appliquer l'évaporation aux seules cases actives
+        self.values.evaporate(evaporation_rate);
+    }
 
-                    if val.abs() < 0.001 {
-                        *val = 0.0;
-                    }
+    /// Écrit la Q-table en CSV, une ligne par case : `x,y,up,down,left,right,stay,max_q`
+    pub fn export_csv(&self, path: &str) -> Result<(), String> {
+        let mut content = String::from("x,y,up,down,left,right,stay,max_q\n");
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let values: Vec<f32> = Action::all().map(|a| self.get_q(x, y, a)).collect();
+                let max_q = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+                let _ = write!(content, "{},{}", x, y);
+                for q in values {
+                    let _ = write!(content, ",{}", q);
                 }
+                let _ = writeln!(content, ",{}", max_q);
             }
         }
+        fs::write(path, content).map_err(|e| format!("Impossible d'écrire {}: {}", path, e))
+    }
+
+    /// Écrit une carte de chaleur SVG de la Q-table : intensité du Q maximal de chaque case
+    /// (relative au maximum de la carte) et flèche vers la meilleure action, murs en gris
+    pub fn export_svg(&self, path: &str, grid: &Grid) -> Result<(), String> {
+        fs::write(path, self.to_svg(grid))
+            .map_err(|e| format!("Impossible d'écrire {}: {}", path, e))
+    }
+
+    /// Carte de chaleur d'`export_svg`, sans passer par le disque
+    pub fn to_svg(&self, grid: &Grid) -> String {
+        const CELL: u32 = 20;
+        let max = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| grid.is_walkable(x, y))
+            .map(|(x, y)| self.get_max_q(x, y, grid))
+            .fold(0.0f32, f32::max);
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w}" height="{h}" viewBox="0 0 {w} {h}">"#,
+            w = self.width * CELL,
+            h = self.height * CELL
+        );
+        svg.push_str(
+            r##"<defs><marker id="head" viewBox="0 0 10 10" refX="8" refY="5" markerWidth="4" markerHeight="4" orient="auto"><path d="M0,0 L10,5 L0,10 z" fill="#3c6eff"/></marker></defs>"##,
+        );
+        svg.push('\n');
+        let _ = writeln!(
+            svg,
+            r##"<rect width="100%" height="100%" fill="#1e1e1e"/>"##
+        );
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let (px, py) = (x * CELL, y * CELL);
+                if !grid.is_walkable(x, y) {
+                    let _ = writeln!(
+                        svg,
+                        r##"<rect x="{}" y="{}" width="{c}" height="{c}" fill="#a0a0a0"/>"##,
+                        px,
+                        py,
+                        c = CELL
+                    );
+                    continue;
+                }
+                let max_q = self.get_max_q(x, y, grid);
+                // Racine du ratio, comme dans la GUI, pour faire ressortir les faibles valeurs
+                let ratio = if max > 0.0 {
+                    (max_q.max(0.0) / max).sqrt()
+                } else {
+                    0.0
+                };
+                let _ = writeln!(
+                    svg,
+                    r##"<rect x="{}" y="{}" width="{c}" height="{c}" fill="#ffaa00" fill-opacity="{:.3}"><title>({}, {}) max Q = {}</title></rect>"##,
+                    px,
+                    py,
+                    ratio,
+                    x,
+                    y,
+                    max_q,
+                    c = CELL
+                );
+
+                let action = self.get_best_action(x, y, grid);
+                let (dx, dy) = match action {
+                    Action::Up => (0.0, -1.0),
+                    Action::Down => (0.0, 1.0),
+                    Action::Left => (-1.0, 0.0),
+                    Action::Right => (1.0, 0.0),
+                    Action::Stay => continue,
+                };
+                // Case jamais mise à jour : la « meilleure » action n'est qu'un départage
+                if (self.get_q(x, y, action) - self.bounds.initial).abs() < 1e-3 {
+                    continue;
+                }
+                let (cx, cy) = (px as f32 + CELL as f32 / 2.0, py as f32 + CELL as f32 / 2.0);
+                let half = CELL as f32 * 0.35;
+                let _ = writeln!(
+                    svg,
+                    r##"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="#3c6eff" stroke-width="1.5" marker-end="url(#head)"/>"##,
+                    cx - dx * half,
+                    cy - dy * half,
+                    cx + dx * half,
+                    cy + dy * half
+                );
+            }
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+// Traces d'éligibilité d'une fourmi pour Q(λ) de Watkins : (indice dans la carte, éligibilité).
+// Les indices désignent une couche de la Q-table (celle du mode courant de la fourmi et de
+// ses traits d'état), la trace est vidée quand elle change de couche.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct EligibilityTrace {
+    entries: Vec<(usize, f32)>,
+    // Couche de la Q-table où pointent les indices
+    #[serde(default)]
+    layer: usize,
+}
+
+impl EligibilityTrace {
+    // Trace remplaçante : la paire jouée repart à une éligibilité de 1
+    pub fn visit(&mut self, map: &PheromoneMap, layer: usize, x: u32, y: u32, action: Action) {
+        if layer != self.layer {
+            self.entries.clear();
+            self.layer = layer;
+        }
+        let Some(idx) = map.entry_index(x, y, action) else {
+            return;
+        };
+        match self.entries.iter_mut().find(|(i, _)| *i == idx) {
+            Some(entry) => entry.1 = 1.0,
+            None => self.entries.push((idx, 1.0)),
+        }
+    }
+
+    // Action exploratoire, mort ou changement de mode : le crédit ne remonte plus au-delà
+    pub fn reset(&mut self) {
+        self.entries.clear();
+    }
+
+    // Atténuer toutes les éligibilités de gamma * lambda après une mise à jour
+    pub fn apply_tick(&mut self, decay: f32) {
+        self.entries.retain_mut(|(_, e)| {
+            *e *= decay;
+            *e >= 0.001
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Pas d'une fourmi en attente de sa cible à n pas
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NStep {
+    pub state: StateFeatures,
+    pub action: Action,
+    pub reward: f32,
+    // Double Q-Learning : la correction porte sur la seconde table
+    pub second_table: bool,
+}
+
+// Derniers pas d'une fourmi pour le Q-Learning à n pas, du plus ancien au plus récent
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct NStepBuffer {
+    steps: VecDeque<NStep>,
+}
+
+impl NStepBuffer {
+    pub fn push(&mut self, step: NStep) {
+        self.steps.push_back(step);
+    }
+
+    // Retire le plus ancien pas avec sa cible : les récompenses de tous les pas en mémoire,
+    // actualisées, plus gamma^k * `bootstrap` (valeur de l'état qui suit le dernier des k pas)
+    pub fn pop_target(&mut self, gamma: f32, bootstrap: f32) -> Option<(NStep, f32)> {
+        let mut target = 0.0;
+        let mut discount = 1.0;
+        for step in &self.steps {
+            target += discount * step.reward;
+            discount *= gamma;
+        }
+        let oldest = self.steps.pop_front()?;
+        Some((oldest, target + discount * bootstrap))
+    }
+
+    // Mort ou sortie du nid : les pas en attente ne recevront pas de cible
+    pub fn clear(&mut self) {
+        self.steps.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+}
+
+// Nombre de fois où chaque action a été jouée depuis chaque case (utilisé par UCB)
+#[derive(Clone, Serialize, Deserialize)]
+pub struct VisitCounts {
+    width: u32,
+    height: u32,
+    data: Vec<[u32; 5]>,
+}
+
+impl VisitCounts {
+    pub fn new(width: u32, height: u32) -> Self {
+        VisitCounts {
+            width,
+            height,
+            data: vec![[0; 5]; GridShape::new(width, height).len()],
+        }
+    }
+
+    // Compteurs de la case, None hors de la carte
+    fn cell(&self, x: u32, y: u32) -> Option<&[u32; 5]> {
+        let index = GridShape::new(self.width, self.height).index(Coord::new(x, y))?;
+        Some(&self.data[index.as_usize()])
+    }
+
+    pub fn record(&mut self, x: u32, y: u32, action: Action) {
+        if let Some(index) = GridShape::new(self.width, self.height).index(Coord::new(x, y)) {
+            self.data[index.as_usize()][action.to_usize()] += 1;
+        }
+    }
+
+    pub fn count(&self, x: u32, y: u32, action: Action) -> u32 {
+        self.cell(x, y)
+            .map_or(0, |counts| counts[action.to_usize()])
+    }
+
+    pub fn state_count(&self, x: u32, y: u32) -> u32 {
+        self.cell(x, y).map_or(0, |counts| counts.iter().sum())
     }
 }
@@ -0,0 +1,57 @@
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+
+// Façon dont les fourmis choisissent leurs déplacements pendant une partie
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PolicyKind {
+    // Apprentissage par renforcement sur les Q-tables (algorithme et exploration configurables)
+    QLearning,
+    // Optimisation par colonie de fourmis classique : phéromone scalaire, pas de Bellman
+    ClassicAco,
+    // Référence basse : déplacements uniformes parmi les cases praticables
+    RandomWalk,
+    // Référence haute : plus court chemin (A*) vers la nourriture ou le nid, carte connue
+    AStar,
+}
+
+impl PolicyKind {
+    pub fn all() -> impl Iterator<Item = PolicyKind> {
+        [
+            PolicyKind::QLearning,
+            PolicyKind::ClassicAco,
+            PolicyKind::RandomWalk,
+            PolicyKind::AStar,
+        ]
+        .iter()
+        .copied()
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "qlearning" => Some(PolicyKind::QLearning),
+            "aco" => Some(PolicyKind::ClassicAco),
+            "random" => Some(PolicyKind::RandomWalk),
+            "astar" => Some(PolicyKind::AStar),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PolicyKind::QLearning => "Q-Learning",
+            PolicyKind::ClassicAco => tr("policy_kind.aco"),
+            PolicyKind::RandomWalk => tr("policy_kind.random"),
+            PolicyKind::AStar => tr("policy_kind.astar"),
+        }
+    }
+
+    /// Politique de référence (marche aléatoire, A*) : la vision des fourmis n'est pas utilisée
+    pub fn is_baseline(&self) -> bool {
+        matches!(self, PolicyKind::RandomWalk | PolicyKind::AStar)
+    }
+
+    /// Les fourmis mettent à jour les Q-tables (sinon elles restent à zéro)
+    pub fn learns(&self) -> bool {
+        matches!(self, PolicyKind::QLearning)
+    }
+}
@@ -0,0 +1,109 @@
+use crate::ant::{Ant, AntsType};
+use crate::grid::Grid;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+// Prédateur qui chasse les fourmis ouvrières sur la carte et pille les nids sans défense
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Predator {
+    pub position: (u32, u32),
+    pub cooldown: u32,
+    // Points de vie restants et de départ, entamés par les coups des combattantes
+    #[serde(default = "default_health")]
+    pub health: u32,
+    #[serde(default = "default_health")]
+    pub max_health: u32,
+}
+
+// Points de vie des prédateurs enregistrés avant l'apparition des combats
+fn default_health() -> u32 {
+    1
+}
+
+// Bilan des affrontements entre prédateurs et colonie
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct PredatorStats {
+    pub predators_spawned: u32,
+    pub predators_killed: u32,
+    pub ants_killed: u32,
+    pub fighters_lost: u32,
+    // Nourriture volée dans les nids sans combattante
+    #[serde(default)]
+    pub food_raided: u32,
+}
+
+impl Predator {
+    pub fn new(position: (u32, u32), health: u32) -> Self {
+        Predator {
+            position,
+            cooldown: 0,
+            health,
+            max_health: health,
+        }
+    }
+
+    // Choisir une case libre (ni mur, ni zone mortelle, ni nid) pour faire apparaître un prédateur
+    pub fn find_spawn_position<R: Rng>(grid: &Grid, rng: &mut R) -> Option<(u32, u32)> {
+        let (width, height) = (grid.get_width(), grid.get_height());
+        if width == 0 || height == 0 {
+            return None;
+        }
+        // Limite de tentatives pour ne pas boucler sur une carte saturée
+        for _ in 0..100 {
+            let (x, y) = (rng.gen_range(0..width), rng.gen_range(0..height));
+            if grid.is_walkable(x, y) && !grid.is_lethal(x, y) && !grid.is_nest(x, y) {
+                return Some((x, y));
+            }
+        }
+        None
+    }
+
+    // Avancer d'une case vers la cible la plus proche (ouvrière ou nid sans défense), ou
+    // errer s'il n'y en a aucune
+    pub fn hunt<R: Rng>(&mut self, grid: &Grid, targets: &[(u32, u32)], rng: &mut R) {
+        let (x, y) = self.position;
+        let prey = targets
+            .iter()
+            .copied()
+            .min_by_key(|&(ax, ay)| ax.abs_diff(x) + ay.abs_diff(y));
+
+        let mut candidates: Vec<(u32, u32)> = [
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+        ]
+        .into_iter()
+        .filter(|&(nx, ny)| grid.is_walkable(nx, ny) && !grid.is_lethal(nx, ny))
+        .collect();
+
+        if let Some((px, py)) = prey {
+            let current = px.abs_diff(x) + py.abs_diff(y);
+            candidates.retain(|&(nx, ny)| px.abs_diff(nx) + py.abs_diff(ny) < current);
+        }
+
+        if !candidates.is_empty() {
+            self.position = candidates[rng.gen_range(0..candidates.len())];
+        }
+    }
+
+    pub fn is_adjacent_to(&self, pos: (u32, u32)) -> bool {
+        self.position.0.abs_diff(pos.0) + self.position.1.abs_diff(pos.1) <= 1
+    }
+}
+
+/// Affrontements en cours : (fourmi, prédateur) pour chaque combattante au contact d'un
+/// prédateur, dans l'ordre des prédateurs puis des fourmis
+pub fn clashes(ants: &[Ant], predators: &[Predator]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    for (p, predator) in predators.iter().enumerate() {
+        for (a, ant) in ants.iter().enumerate() {
+            if ant.ant_type == AntsType::FIGHTER
+                && ant.position.is_some_and(|pos| predator.is_adjacent_to(pos))
+            {
+                pairs.push((a, p));
+            }
+        }
+    }
+    pairs
+}
@@ -0,0 +1,38 @@
+//! Imports courants pour embarquer le simulateur dans un autre projet Rust
+//!
+//! `use ants_project::prelude::*;` donne accès aux types nécessaires pour configurer,
+//! lancer et observer une simulation sans dépendre de l'organisation interne des modules.
+
+pub use crate::ant::{Ant, AntStats, AntsMode, AntsType};
+pub use crate::ants_game_manager::{
+    AntsGameManager, EndReason, GameStateSnapshot, QLearningParams,
+};
+pub use crate::cli_args::SimulationConfig;
+pub use crate::colony::Colony;
+pub use crate::coord::{Coord, GridIndex, GridShape};
+pub use crate::env::{AntObservation, AntsEnv, Observation};
+pub use crate::error::AntsError;
+pub use crate::event_log::{EventLog, LogEntry, LogKind, LogLevel};
+pub use crate::experiment::{ArtifactKind, Manifest};
+pub use crate::exploration::ExplorationPolicy;
+pub use crate::grid::Grid;
+pub use crate::grid_transaction::{GridTransaction, TransactionReport};
+pub use crate::linear_q::LinearQ;
+pub use crate::metrics::{AntTypeStats, MetricsCollector, TickMetrics};
+pub use crate::observation::{
+    EncodedObservation, GridTensor, ObservationBuilder, ObservationFormat,
+};
+pub use crate::observer::{DeathCause, DeathEvent, FoodEvent, SimulationObserver, SpawnEvent};
+pub use crate::pathfinding::{DistanceMap, PathCache};
+pub use crate::pheromone::{Action, PheromoneMap, Resample};
+pub use crate::predator::{Predator, PredatorStats};
+pub use crate::profiling::PhaseTimings;
+pub use crate::q_backend::{QBackend, QBackendKind};
+pub use crate::q_learning_math::LearningAlgorithm;
+pub use crate::q_table::{QTable, StateFeatures, StateLayout};
+pub use crate::reward::{DefaultReward, RewardModel};
+pub use crate::runner::{SimulationRunner, SimulationSummary, TickRate};
+pub use crate::scoring::{Leaderboard, RunResult, RunScores, ScoreRule};
+pub use crate::spawn_policy::{ColonyState, SpawnPolicy, SpawnPolicyKind, SpawnReason, SpawnStats};
+pub use crate::tile::{Tile, TileType};
+pub use crate::timeline::Timeline;
@@ -0,0 +1,211 @@
+//! Jeux de paramètres nommés (presets) choisis depuis la GUI
+//!
+//! Un preset est une configuration TOML au format de `--config`, sans les champs propres
+//! à la partie en cours (taille de la grille, fourmis, graine, fichiers...). Les champs
+//! absents reprennent leur valeur par défaut. Les presets de l'utilisateur sont enregistrés
+//! dans `presets/` de son dossier de configuration (`~/.config/ants/presets/` sous Linux),
+//! et se rechargent aussi en ligne de commande avec `--config`.
+
+use crate::cli_args::SimulationConfig;
+use crate::i18n::tr;
+use std::fs;
+use std::path::PathBuf;
+
+// Champs fixés par la partie ou par le mode d'exécution, jamais remplacés par un preset
+const GAME_KEYS: &[&str] = &[
+    "grid_width",
+    "grid_height",
+    "num_explorers",
+    "num_fighters",
+    "num_pickers",
+    "explorer_capacity_load",
+    "explorer_speed",
+    "explorer_scope",
+    "picker_capacity_load",
+    "picker_speed",
+    "picker_scope",
+    "fighter_capacity_load",
+    "fighter_speed",
+    "fighter_scope",
+    "seed",
+    "hazards",
+    "events",
+    "use_gui",
+    "use_tui",
+    "watch_interval",
+    "log_level",
+    "output_file",
+    "report_file",
+    "report_gif",
+    "leaderboard_file",
+    "metrics_port",
+    "serve_ws",
+    "ws_interval",
+    "render_frames",
+    "frame_interval",
+    "runs",
+    "runs_output",
+    "bench_ticks",
+    "map_file",
+    "save_brain",
+    "dump_qtable",
+    "load_brain",
+    "brain_resample",
+    "record_file",
+    "replay_file",
+    "checkpoint_every",
+    "checkpoint_file",
+];
+
+// Presets fournis avec le programme : (clé de traduction du nom, paramètres TOML)
+const BUILTIN_PRESETS: &[(&str, &str)] = &[
+    (
+        "preset.fast_learner",
+        r#"
+alpha = 0.5
+gamma = 0.95
+lambda = 0.8
+epsilon = 0.3
+experience_buffer = 5000
+experience_batch = 32
+epsilon_decay = { schedule = "Exponential", rate = 0.999, min = 0.02, step_every = 1000 }
+"#,
+    ),
+    (
+        "preset.cautious_explorer",
+        r#"
+alpha = 0.1
+gamma = 0.99
+exploration_policy = "Ucb"
+ucb_c = 200.0
+reward_death = -1000.0
+near_food_state = true
+"#,
+    ),
+];
+
+/// Preset fourni avec le programme ou enregistré par l'utilisateur
+#[derive(Clone, Debug, PartialEq)]
+pub enum Preset {
+    Builtin(&'static str),
+    User(String),
+}
+
+impl Preset {
+    pub fn label(&self) -> &str {
+        match self {
+            Preset::Builtin(key) => tr(key),
+            Preset::User(name) => name,
+        }
+    }
+
+    /// Presets intégrés, puis ceux de l'utilisateur par ordre alphabétique
+    pub fn all() -> Vec<Preset> {
+        let mut users: Vec<String> = presets_dir()
+            .and_then(|dir| fs::read_dir(dir).ok())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter_map(|entry| {
+                let path = entry.path();
+                if path.extension()? != "toml" {
+                    return None;
+                }
+                Some(path.file_stem()?.to_str()?.to_string())
+            })
+            .collect();
+        users.sort();
+        BUILTIN_PRESETS
+            .iter()
+            .map(|&(key, _)| Preset::Builtin(key))
+            .chain(users.into_iter().map(Preset::User))
+            .collect()
+    }
+
+    /// Paramètres du preset appliqués à `base`, dont seuls les champs de la partie sont gardés
+    pub fn apply(&self, base: &SimulationConfig) -> Result<SimulationConfig, String> {
+        let content = match self {
+            Preset::Builtin(key) => BUILTIN_PRESETS
+                .iter()
+                .find(|(builtin, _)| builtin == key)
+                .map(|(_, content)| content.to_string())
+                .ok_or_else(|| format!("Preset inconnu : {}", key))?,
+            Preset::User(name) => {
+                let path = preset_path(name)?;
+                fs::read_to_string(&path)
+                    .map_err(|e| format!("Impossible de lire {}: {}", path.display(), e))?
+            }
+        };
+        let mut table: toml::Table = content
+            .parse()
+            .map_err(|e| format!("Preset invalide « {} » : {}", self.label(), e))?;
+        let base_table = toml::Table::try_from(base)
+            .map_err(|e| format!("Impossible de sérialiser la configuration: {}", e))?;
+        for key in GAME_KEYS {
+            table.remove(*key);
+            if let Some(value) = base_table.get(*key) {
+                table.insert(key.to_string(), value.clone());
+            }
+        }
+        let config: SimulationConfig = table
+            .try_into()
+            .map_err(|e| format!("Preset invalide « {} » : {}", self.label(), e))?;
+        config.validate().map_err(|e| e.to_string())?;
+        Ok(SimulationConfig {
+            resume_from: base.resume_from.clone(),
+            scenario_file: base.scenario_file.clone(),
+            dump_config: base.dump_config.clone(),
+            ..config
+        })
+    }
+
+    /// Enregistre les paramètres de `config` sous `name` (remplace un preset du même nom)
+    pub fn save(name: &str, config: &SimulationConfig) -> Result<Preset, String> {
+        let path = preset_path(name)?;
+        let mut table = toml::Table::try_from(config)
+            .map_err(|e| format!("Impossible de sérialiser la configuration: {}", e))?;
+        for key in GAME_KEYS {
+            table.remove(*key);
+        }
+        let content = toml::to_string_pretty(&table)
+            .map_err(|e| format!("Impossible de sérialiser la configuration: {}", e))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .map_err(|e| format!("Impossible de créer {}: {}", dir.display(), e))?;
+        }
+        fs::write(&path, content)
+            .map_err(|e| format!("Impossible d'écrire {}: {}", path.display(), e))?;
+        Ok(Preset::User(name.trim().to_string()))
+    }
+
+    /// Supprime un preset de l'utilisateur (les presets intégrés ne se suppriment pas)
+    pub fn delete(&self) -> Result<(), String> {
+        let Preset::User(name) = self else {
+            return Err(format!("Le preset « {} » est intégré", self.label()));
+        };
+        let path = preset_path(name)?;
+        fs::remove_file(&path)
+            .map_err(|e| format!("Impossible de supprimer {}: {}", path.display(), e))
+    }
+}
+
+// Fichier d'un preset de l'utilisateur ; le nom sert de nom de fichier
+fn preset_path(name: &str) -> Result<PathBuf, String> {
+    let name = name.trim();
+    if name.is_empty() || name.starts_with('.') || name.contains(['/', '\\', ':']) {
+        return Err(format!("Nom de preset invalide : « {} »", name));
+    }
+    let dir = presets_dir().ok_or("Aucun dossier de configuration utilisateur")?;
+    Ok(dir.join(format!("{}.toml", name)))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn presets_dir() -> Option<PathBuf> {
+    directories::ProjectDirs::from("", "", "ants").map(|dirs| dirs.config_dir().join("presets"))
+}
+
+// Pas de système de fichiers dans le navigateur : seuls les presets intégrés existent
+#[cfg(target_arch = "wasm32")]
+fn presets_dir() -> Option<PathBuf> {
+    None
+}
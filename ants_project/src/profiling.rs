@@ -0,0 +1,62 @@
+//! Mesure du temps passé dans chaque phase d'un tick
+//!
+//! Désactivée par défaut : `AntsGameManager::enable_profiling` l'active, puis
+//! `phase_timings` donne les durées cumulées depuis. Sert au benchmark (`--bench`).
+
+use web_time::{Duration, Instant};
+
+/// Durées cumulées des phases de `game_step`, dans leur ordre d'exécution
+#[derive(Clone, Debug, Default)]
+pub struct PhaseTimings {
+    pub ticks: u64,
+    /// Décroissances, événements, densité, sortie du nid et tirages aléatoires
+    pub setup: Duration,
+    /// Choix des actions et corrections Q (phase parallèle)
+    pub decisions: Duration,
+    /// Résolution des conflits, déplacements et dépôts de phéromones
+    pub movement: Duration,
+    /// Experience replay, prédateurs, famine, naissances, repousse et cadavres
+    pub world: Duration,
+    /// Évaporation et application des mises à jour de phéromones en attente
+    pub pheromones: Duration,
+    /// Snapshot, métriques et observateurs
+    pub bookkeeping: Duration,
+}
+
+impl PhaseTimings {
+    /// (nom, durée cumulée) de chaque phase
+    pub fn phases(&self) -> [(&'static str, Duration); 6] {
+        [
+            ("préparation", self.setup),
+            ("choix des actions", self.decisions),
+            ("déplacements", self.movement),
+            ("monde", self.world),
+            ("phéromones", self.pheromones),
+            ("bilan", self.bookkeeping),
+        ]
+    }
+
+    pub fn total(&self) -> Duration {
+        self.phases().iter().map(|(_, duration)| *duration).sum()
+    }
+}
+
+// Chronomètre d'un tick, inerte (et sans appel à l'horloge) quand le profilage est désactivé
+pub(crate) struct PhaseClock(Option<Instant>);
+
+impl PhaseClock {
+    pub(crate) fn start(enabled: bool) -> Self {
+        PhaseClock(enabled.then(Instant::now))
+    }
+
+    // Temps écoulé depuis le tour précédent
+    pub(crate) fn lap(&mut self) -> Duration {
+        let Some(last) = &mut self.0 else {
+            return Duration::ZERO;
+        };
+        let now = Instant::now();
+        let elapsed = now - *last;
+        *last = now;
+        elapsed
+    }
+}
@@ -0,0 +1,193 @@
+//! Export des métriques au format Prometheus / OpenMetrics
+//!
+//! `--metrics-port` ouvre un petit serveur HTTP dans un thread à part : `GET /metrics`
+//! renvoie les compteurs et jauges de la partie en cours (ticks, ticks par seconde,
+//! fourmis actives, nourriture au nid, morts, Q moyen), mis à jour à chaque tick par un
+//! observateur de la simulation. Il n'y a qu'une page et aucune dépendance HTTP : de quoi
+//! suivre une longue partie headless dans Grafana.
+
+use crate::metrics::TickMetrics;
+use crate::observer::SimulationObserver;
+use std::fmt::Write as _;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// Fenêtre sur laquelle est mesurée la vitesse de la simulation
+const RATE_WINDOW: Duration = Duration::from_secs(1);
+
+// Valeurs exposées, mises à jour par la simulation et lues par le serveur
+#[derive(Default)]
+struct Exported {
+    tick: u64,
+    food_delivered: u64,
+    deaths: u64,
+    births: u64,
+    ants_spawned: u64,
+    active_ants: u32,
+    nest_food: u32,
+    mean_q: f32,
+    ticks_per_second: f64,
+    // Début de la fenêtre de mesure de la vitesse : instant et tick atteint
+    window_start: Option<(Instant, u64)>,
+}
+
+/// Observateur qui publie les métriques de la partie sur `http://<hôte>:<port>/metrics`
+pub struct MetricsExporter {
+    exported: Arc<Mutex<Exported>>,
+}
+
+impl MetricsExporter {
+    /// Écoute sur `port` (toutes les interfaces) ; les requêtes sont servies par un thread
+    /// qui vit jusqu'à la fin du programme
+    pub fn serve(port: u16) -> Result<Self, String> {
+        let listener = TcpListener::bind(("0.0.0.0", port))
+            .map_err(|e| format!("Impossible d'écouter sur le port {}: {}", port, e))?;
+        let exported = Arc::new(Mutex::new(Exported::default()));
+        let shared = Arc::clone(&exported);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                // Un client qui se déconnecte en cours de route n'arrête pas le serveur
+                let _ = respond(stream, &shared);
+            }
+        });
+        Ok(MetricsExporter { exported })
+    }
+}
+
+impl SimulationObserver for MetricsExporter {
+    fn on_tick(&mut self, metrics: &TickMetrics) {
+        let mut exported = lock(&self.exported);
+        let tick = metrics.tick as u64;
+        exported.tick = tick;
+        exported.food_delivered += metrics.food_delivered as u64;
+        exported.deaths += metrics.deaths as u64;
+        exported.births += metrics.births as u64;
+        exported.ants_spawned += metrics.ants_spawned as u64;
+        exported.active_ants = metrics.active_ants;
+        exported.nest_food = metrics.nest_food;
+        exported.mean_q = metrics.avg_q_magnitude;
+
+        let now = Instant::now();
+        match exported.window_start {
+            Some((start, start_tick)) if now.duration_since(start) >= RATE_WINDOW => {
+                let elapsed = now.duration_since(start).as_secs_f64();
+                exported.ticks_per_second = tick.saturating_sub(start_tick) as f64 / elapsed;
+                exported.window_start = Some((now, tick));
+            }
+            Some(_) => {}
+            None => exported.window_start = Some((now, tick)),
+        }
+    }
+}
+
+fn lock(exported: &Mutex<Exported>) -> std::sync::MutexGuard<'_, Exported> {
+    exported.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// Lit la ligne de requête (les en-têtes sont ignorés) et renvoie la page des métriques
+fn respond(stream: TcpStream, exported: &Mutex<Exported>) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(&stream);
+    let mut request = String::new();
+    reader.read_line(&mut request)?;
+    // Consommer les en-têtes jusqu'à la ligne vide
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        header.clear();
+    }
+
+    let path = request.split_whitespace().nth(1).unwrap_or("");
+    let (status, content_type, body) = if path == "/metrics" || path.starts_with("/metrics?") {
+        (
+            "200 OK",
+            "text/plain; version=0.0.4; charset=utf-8",
+            render(&lock(exported)),
+        )
+    } else {
+        (
+            "404 Not Found",
+            "text/plain; charset=utf-8",
+            "Seule la page /metrics existe\n".to_string(),
+        )
+    };
+    let mut stream = &stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )?;
+    stream.flush()
+}
+
+// Format texte de Prometheus : HELP et TYPE puis la valeur de chaque métrique
+fn render(exported: &Exported) -> String {
+    let metrics: [(&str, &str, &str, String); 9] = [
+        (
+            "ants_ticks_total",
+            "counter",
+            "Ticks simulés",
+            exported.tick.to_string(),
+        ),
+        (
+            "ants_ticks_per_second",
+            "gauge",
+            "Vitesse de la simulation sur la dernière seconde",
+            format!("{:.1}", exported.ticks_per_second),
+        ),
+        (
+            "ants_active_ants",
+            "gauge",
+            "Fourmis sur la carte",
+            exported.active_ants.to_string(),
+        ),
+        (
+            "ants_nest_food",
+            "gauge",
+            "Nourriture stockée dans l'ensemble des nids",
+            exported.nest_food.to_string(),
+        ),
+        (
+            "ants_food_delivered_total",
+            "counter",
+            "Nourriture livrée aux nids",
+            exported.food_delivered.to_string(),
+        ),
+        (
+            "ants_deaths_total",
+            "counter",
+            "Fourmis mortes",
+            exported.deaths.to_string(),
+        ),
+        (
+            "ants_births_total",
+            "counter",
+            "Fourmis nées au nid",
+            exported.births.to_string(),
+        ),
+        (
+            "ants_spawned_total",
+            "counter",
+            "Sorties du nid",
+            exported.ants_spawned.to_string(),
+        ),
+        (
+            "ants_mean_q",
+            "gauge",
+            "Moyenne des valeurs Q absolues",
+            exported.mean_q.to_string(),
+        ),
+    ];
+    let mut page = String::new();
+    for (name, kind, help, value) in metrics {
+        let _ = writeln!(page, "# HELP {} {}", name, help);
+        let _ = writeln!(page, "# TYPE {} {}", name, kind);
+        let _ = writeln!(page, "{} {}", name, value);
+    }
+    page
+}
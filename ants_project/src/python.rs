@@ -0,0 +1,327 @@
+// Bindings Python (feature `python`) : configuration, boucle de simulation, inspection de la
+// grille et export des Q-tables pour piloter des expériences depuis Python/Jupyter
+use crate::ant::{AntsMode, AntsType};
+use crate::ants_game_manager::AntsGameManager;
+use crate::cli_args::SimulationConfig;
+use crate::observation;
+use crate::pheromone::{Action, PheromoneMap};
+use crate::tile::TileType;
+use pyo3::exceptions::{PyIOError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyModule};
+
+/// Configuration d'une simulation. Les arguments nommés reprennent les champs de
+/// `SimulationConfig` (ex. `Config(alpha=0.3, grid_width=40, seed=1)`).
+#[pyclass(name = "Config")]
+#[derive(Clone)]
+pub struct PyConfig {
+    inner: SimulationConfig,
+}
+
+#[pymethods]
+impl PyConfig {
+    #[new]
+    #[pyo3(signature = (**kwargs))]
+    fn new(py: Python<'_>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<Self> {
+        let mut inner = PyConfig {
+            inner: SimulationConfig {
+                use_gui: false,
+                ..SimulationConfig::default()
+            },
+        };
+        if let Some(kwargs) = kwargs {
+            inner.update(py, kwargs)?;
+        }
+        Ok(inner)
+    }
+
+    /// Modifie plusieurs champs d'un coup, avec les mêmes noms que le constructeur
+    #[pyo3(signature = (**kwargs))]
+    fn set(&mut self, py: Python<'_>, kwargs: Option<&Bound<'_, PyDict>>) -> PyResult<()> {
+        match kwargs {
+            Some(kwargs) => self.update(py, kwargs),
+            None => Ok(()),
+        }
+    }
+
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        let inner = serde_json::from_str(json)
+            .map_err(|e| PyValueError::new_err(format!("Configuration invalide: {}", e)))?;
+        Ok(PyConfig { inner })
+    }
+
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.inner).map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    fn validate(&self) -> PyResult<()> {
+        self.inner
+            .validate()
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Config(grid={}x{}, alpha={}, gamma={}, epsilon={}, max_ticks={})",
+            self.inner.grid_width,
+            self.inner.grid_height,
+            self.inner.alpha,
+            self.inner.gamma,
+            self.inner.epsilon,
+            self.inner.max_ticks
+        )
+    }
+}
+
+impl PyConfig {
+    // Les valeurs Python passent par JSON pour réutiliser la désérialisation serde
+    fn update(&mut self, py: Python<'_>, kwargs: &Bound<'_, PyDict>) -> PyResult<()> {
+        let json: String = py
+            .import("json")?
+            .call_method1("dumps", (kwargs,))?
+            .extract()?;
+        let changes: serde_json::Value =
+            serde_json::from_str(&json).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        let mut merged =
+            serde_json::to_value(&self.inner).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        if let (Some(target), Some(changes)) = (merged.as_object_mut(), changes.as_object()) {
+            for (key, value) in changes {
+                if !target.contains_key(key) {
+                    return Err(PyValueError::new_err(format!("Paramètre inconnu: {}", key)));
+                }
+                target.insert(key.clone(), value.clone());
+            }
+        }
+        self.inner = serde_json::from_value(merged)
+            .map_err(|e| PyValueError::new_err(format!("Configuration invalide: {}", e)))?;
+        Ok(())
+    }
+}
+
+/// Une partie en cours, pilotée tick par tick depuis Python
+#[pyclass(name = "Simulation", unsendable)]
+pub struct PySimulation {
+    manager: AntsGameManager,
+}
+
+#[pymethods]
+impl PySimulation {
+    #[new]
+    #[pyo3(signature = (config=None))]
+    fn new(config: Option<PyConfig>) -> PyResult<Self> {
+        let config = config.map(|c| c.inner).unwrap_or_default();
+        let manager = AntsGameManager::from_config(config)
+            .map_err(|e| PyValueError::new_err(e.to_string()))?;
+        Ok(PySimulation { manager })
+    }
+
+    fn game_step(&mut self) {
+        self.manager.game_step();
+    }
+
+    /// Joue au plus `ticks` ticks (s'arrête si la partie se termine), renvoie le nombre joué
+    fn run(&mut self, ticks: u64) -> u64 {
+        let mut played = 0;
+        while played < ticks && !self.manager.is_game_finished() {
+            self.manager.game_step();
+            played += 1;
+        }
+        played
+    }
+
+    #[getter]
+    fn tick(&self) -> usize {
+        self.manager.current_tick_index()
+    }
+
+    #[getter]
+    fn width(&self) -> u32 {
+        self.manager.grid().get_width()
+    }
+
+    #[getter]
+    fn height(&self) -> u32 {
+        self.manager.grid().get_height()
+    }
+
+    fn is_finished(&self) -> bool {
+        self.manager.is_game_finished()
+    }
+
+    fn scores(&self) -> Vec<u64> {
+        self.manager.colonies().iter().map(|c| c.score).collect()
+    }
+
+    /// Type de la case (x, y) : "default", "wall", "nest", "food", "death_zone",
+    /// "mud" ou "tunnel"
+    fn tile(&self, x: u32, y: u32) -> PyResult<&'static str> {
+        self.manager
+            .grid()
+            .get_tile((x, y))
+            .map(|tile| tile_name(&tile.tile_type))
+            .ok_or_else(|| PyValueError::new_err(format!("Case ({}, {}) hors de la grille", x, y)))
+    }
+
+    /// Grille complète, ligne par ligne, avec les mêmes noms que `tile`
+    fn grid(&self) -> Vec<Vec<&'static str>> {
+        let grid = self.manager.grid();
+        (0..grid.get_height())
+            .map(|y| {
+                (0..grid.get_width())
+                    .map(|x| {
+                        grid.get_tile((x, y))
+                            .map_or("default", |tile| tile_name(&tile.tile_type))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Quantité de nourriture par case (0 hors des sources), ligne par ligne
+    fn food(&self) -> Vec<Vec<u32>> {
+        let grid = self.manager.grid();
+        (0..grid.get_height())
+            .map(|y| {
+                (0..grid.get_width())
+                    .map(|x| match grid.get_tile((x, y)).map(|t| &t.tile_type) {
+                        Some(TileType::FoodSource { amount, .. }) => *amount,
+                        _ => 0,
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Une entrée par fourmi : identifiant stable, position (None si morte ou pas encore
+    /// sortie), colonie, type, mode et charge transportée
+    fn ants<'py>(&self, py: Python<'py>) -> PyResult<Vec<Bound<'py, PyDict>>> {
+        self.manager
+            .ants()
+            .iter()
+            .map(|ant| {
+                let dict = PyDict::new(py);
+                dict.set_item("id", ant.id)?;
+                dict.set_item("position", ant.position)?;
+                dict.set_item("colony", ant.colony)?;
+                dict.set_item("type", format!("{:?}", ant.ant_type))?;
+                dict.set_item("mode", format!("{:?}", ant.mode))?;
+                dict.set_item("carrying", ant.current_charge)?;
+                Ok(dict)
+            })
+            .collect()
+    }
+
+    /// Q-table d'une colonie sous forme [y][x][action] (actions : haut, bas, gauche, droite,
+    /// rester). `mode` vaut "food" (recherche) ou "nest" (retour au nid). `ant_type`
+    /// ("explorer", "picker" ou "fighter") ne compte qu'avec des cartes par type de fourmi.
+    #[pyo3(signature = (colony=0, mode="food", ant_type="explorer"))]
+    fn q_table(&self, colony: usize, mode: &str, ant_type: &str) -> PyResult<Vec<Vec<Vec<f32>>>> {
+        let mode = match mode {
+            "food" => AntsMode::FINDING,
+            "nest" => AntsMode::RETURNING,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "Mode inconnu: {} (attendu: food, nest)",
+                    other
+                )))
+            }
+        };
+        let ant_type = match ant_type {
+            "explorer" => AntsType::EXPLORER,
+            "picker" => AntsType::PICKER,
+            "fighter" => AntsType::FIGHTER,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "Type de fourmi inconnu: {} (attendu: explorer, picker, fighter)",
+                    other
+                )))
+            }
+        };
+        let colony = self
+            .manager
+            .colonies()
+            .get(colony)
+            .ok_or_else(|| PyValueError::new_err(format!("Colonie {} inexistante", colony)))?;
+        let map = colony.map(ant_type, mode);
+        Ok(q_values(map, self.width(), self.height()))
+    }
+
+    /// Vecteur de traits de chaque fourmi (format `features` de `--observation`), dans
+    /// l'ordre des fourmis de la partie
+    fn ant_features(&self) -> Vec<Vec<f32>> {
+        self.manager
+            .ants()
+            .iter()
+            .map(|ant| observation::ant_features(&self.manager, ant))
+            .collect()
+    }
+
+    /// Tenseur de la carte (format `grid` de `--observation`), indexé [canal][y][x]
+    fn grid_tensor(&self) -> Vec<Vec<Vec<f32>>> {
+        let tensor = observation::grid_tensor(&self.manager);
+        let width = tensor.width.max(1) as usize;
+        tensor
+            .data
+            .chunks(width * tensor.height.max(1) as usize)
+            .map(|channel| channel.chunks(width).map(<[f32]>::to_vec).collect())
+            .collect()
+    }
+
+    /// Métriques du dernier tick joué, plus les totaux depuis le début de la partie
+    fn metrics<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let metrics = self.manager.metrics();
+        let dict = PyDict::new(py);
+        dict.set_item("total_food_delivered", metrics.total_food_delivered)?;
+        dict.set_item("total_deaths", metrics.total_deaths)?;
+        dict.set_item("total_births", metrics.total_births)?;
+        if let Some(m) = metrics.last() {
+            dict.set_item("tick", m.tick)?;
+            dict.set_item("food_delivered", m.food_delivered)?;
+            dict.set_item("active_ants", m.active_ants)?;
+            dict.set_item("deaths", m.deaths)?;
+            dict.set_item("ants_spawned", m.ants_spawned)?;
+            dict.set_item("avg_q_magnitude", m.avg_q_magnitude)?;
+            dict.set_item("births", m.births)?;
+            dict.set_item("nest_food", m.nest_food)?;
+        }
+        Ok(dict)
+    }
+
+    fn export_brain(&self, path: &str) -> PyResult<()> {
+        self.manager.export_brain(path).map_err(PyIOError::new_err)
+    }
+
+    fn import_brain(&mut self, path: &str) -> PyResult<()> {
+        self.manager.import_brain(path).map_err(PyIOError::new_err)
+    }
+}
+
+fn tile_name(tile_type: &TileType) -> &'static str {
+    match tile_type {
+        TileType::Default => "default",
+        TileType::Wall => "wall",
+        TileType::Nest { .. } => "nest",
+        TileType::FoodSource { .. } => "food",
+        TileType::DeathZone => "death_zone",
+        TileType::Mud { .. } => "mud",
+        TileType::Tunnel { .. } => "tunnel",
+    }
+}
+
+fn q_values(map: &PheromoneMap, width: u32, height: u32) -> Vec<Vec<Vec<f32>>> {
+    (0..height)
+        .map(|y| {
+            (0..width)
+                .map(|x| Action::all().map(|a| map.get_q(x, y, a)).collect())
+                .collect()
+        })
+        .collect()
+}
+
+#[pymodule]
+fn ants_project(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyConfig>()?;
+    m.add_class::<PySimulation>()?;
+    Ok(())
+}
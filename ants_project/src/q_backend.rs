@@ -0,0 +1,123 @@
+//! Représentation des valeurs Q apprises par une colonie
+//!
+//! La décision et l'apprentissage des fourmis ne lisent et ne corrigent les valeurs Q qu'au
+//! travers de [`QBackend`]. La carte tabulaire ([`PheromoneMap`], cinq valeurs par case) et
+//! l'approximation linéaire ([`crate::linear_q::LinearQ`], quelques poids par trait de case)
+//! sont interchangeables, selon `--q-backend`. Les affichages, exports et outils d'analyse
+//! restent propres aux cartes tabulaires.
+
+use crate::exploration;
+use crate::grid::Grid;
+use crate::i18n::tr;
+use crate::pheromone::{Action, PheromoneMap};
+use crate::q_table::StateFeatures;
+use serde::{Deserialize, Serialize};
+
+/// Représentation des valeurs Q choisie par la configuration
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QBackendKind {
+    // Une valeur par case, par action et par couche d'état
+    Tabular,
+    // Somme pondérée des traits de la case visée, partagée entre les cases
+    Linear,
+}
+
+impl QBackendKind {
+    pub fn all() -> impl Iterator<Item = QBackendKind> {
+        [QBackendKind::Tabular, QBackendKind::Linear]
+            .iter()
+            .copied()
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "tabular" => Some(QBackendKind::Tabular),
+            "linear" => Some(QBackendKind::Linear),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            QBackendKind::Tabular => tr("backend.tabular"),
+            QBackendKind::Linear => tr("backend.linear"),
+        }
+    }
+}
+
+/// Valeurs Q consultées et corrigées par les fourmis
+pub trait QBackend {
+    /// Valeur de l'action `action` dans l'état `state` (très négative hors de la carte)
+    fn get_q(&self, grid: &Grid, state: StateFeatures, action: Action) -> f32;
+
+    /// Ajoute une correction, appliquée avec les autres à la fin du tick
+    fn queue_update(&mut self, grid: &Grid, state: StateFeatures, action: Action, delta: f32);
+
+    /// Applique les corrections en attente puis l'évaporation
+    fn apply_tick(&mut self, evaporation_rate: f32);
+
+    /// Meilleure valeur de l'état sur les cinq actions, murs compris (0 hors de la carte)
+    fn get_max_q(&self, grid: &Grid, state: StateFeatures) -> f32 {
+        if state.x >= grid.get_width() || state.y >= grid.get_height() {
+            return 0.0;
+        }
+        Action::all()
+            .map(|action| self.get_q(grid, state, action))
+            .fold(f32::NEG_INFINITY, f32::max)
+    }
+
+    /// Meilleure valeur de l'état parmi les déplacements jouables (rester sur place si la
+    /// case est emmurée)
+    fn get_max_valid_q(&self, grid: &Grid, state: StateFeatures) -> f32 {
+        if state.x >= grid.get_width() || state.y >= grid.get_height() {
+            return 0.0;
+        }
+        let actions = exploration::valid_actions(state.x, state.y, grid, 0);
+        if actions.is_empty() {
+            return self.get_q(grid, state, Action::Stay);
+        }
+        actions
+            .into_iter()
+            .map(|action| self.get_q(grid, state, action))
+            .fold(f32::NEG_INFINITY, f32::max)
+    }
+
+    /// Meilleur déplacement jouable depuis l'état, le premier à égalité (`Stay` si emmurée)
+    fn get_best_action(&self, grid: &Grid, state: StateFeatures) -> Action {
+        let mut best = (Action::Stay, f32::NEG_INFINITY);
+        for action in exploration::valid_actions(state.x, state.y, grid, 0) {
+            let value = self.get_q(grid, state, action);
+            if value > best.1 {
+                best = (action, value);
+            }
+        }
+        best.0
+    }
+}
+
+// La couche tabulaire de l'état a déjà été choisie : seule la case compte ici
+impl QBackend for PheromoneMap {
+    fn get_q(&self, _grid: &Grid, state: StateFeatures, action: Action) -> f32 {
+        PheromoneMap::get_q(self, state.x, state.y, action)
+    }
+
+    fn queue_update(&mut self, _grid: &Grid, state: StateFeatures, action: Action, delta: f32) {
+        PheromoneMap::queue_update(self, state.x, state.y, action, delta);
+    }
+
+    fn apply_tick(&mut self, evaporation_rate: f32) {
+        PheromoneMap::apply_tick(self, evaporation_rate);
+    }
+
+    fn get_max_q(&self, grid: &Grid, state: StateFeatures) -> f32 {
+        PheromoneMap::get_max_q(self, state.x, state.y, grid)
+    }
+
+    fn get_max_valid_q(&self, grid: &Grid, state: StateFeatures) -> f32 {
+        PheromoneMap::get_max_valid_q(self, state.x, state.y, grid)
+    }
+
+    fn get_best_action(&self, grid: &Grid, state: StateFeatures) -> Action {
+        PheromoneMap::get_best_action(self, state.x, state.y, grid)
+    }
+}
@@ -1,3 +1,131 @@
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+
+// Algorithme utilisé pour estimer la valeur de l'état suivant dans la mise à jour de Bellman
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LearningAlgorithm {
+    // Hors politique : valeur de la meilleure action suivante
+    QLearning,
+    // Sur politique : valeur de l'action suivante réellement choisie
+    Sarsa,
+    // Espérance de la valeur suivante sous la politique epsilon-greedy
+    ExpectedSarsa,
+    // Deux tables par mode : l'une choisit la meilleure action suivante, l'autre l'évalue
+    DoubleQLearning,
+}
+
+impl LearningAlgorithm {
+    pub fn all() -> impl Iterator<Item = LearningAlgorithm> {
+        [
+            LearningAlgorithm::QLearning,
+            LearningAlgorithm::Sarsa,
+            LearningAlgorithm::ExpectedSarsa,
+            LearningAlgorithm::DoubleQLearning,
+        ]
+        .iter()
+        .copied()
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "qlearning" => Some(LearningAlgorithm::QLearning),
+            "sarsa" => Some(LearningAlgorithm::Sarsa),
+            "expected-sarsa" => Some(LearningAlgorithm::ExpectedSarsa),
+            "double-qlearning" => Some(LearningAlgorithm::DoubleQLearning),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LearningAlgorithm::QLearning => "Q-Learning",
+            LearningAlgorithm::Sarsa => "SARSA",
+            LearningAlgorithm::ExpectedSarsa => "Expected SARSA",
+            LearningAlgorithm::DoubleQLearning => "Double Q-Learning",
+        }
+    }
+}
+
+// Forme de la décroissance d'un hyperparamètre au fil des ticks
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DecaySchedule {
+    Constant,
+    // valeur = initiale - taux * tick
+    Linear,
+    // valeur = initiale * taux^tick
+    Exponential,
+    // valeur = initiale * taux^(tick / palier)
+    Step,
+}
+
+impl DecaySchedule {
+    pub fn all() -> impl Iterator<Item = DecaySchedule> {
+        [
+            DecaySchedule::Constant,
+            DecaySchedule::Linear,
+            DecaySchedule::Exponential,
+            DecaySchedule::Step,
+        ]
+        .iter()
+        .copied()
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "constant" => Some(DecaySchedule::Constant),
+            "linear" => Some(DecaySchedule::Linear),
+            "exponential" => Some(DecaySchedule::Exponential),
+            "step" => Some(DecaySchedule::Step),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DecaySchedule::Constant => tr("decay.constant"),
+            DecaySchedule::Linear => tr("decay.linear"),
+            DecaySchedule::Exponential => tr("decay.exponential"),
+            DecaySchedule::Step => tr("decay.step"),
+        }
+    }
+}
+
+// Paramètres d'une décroissance (epsilon ou alpha)
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DecayConfig {
+    pub schedule: DecaySchedule,
+    pub rate: f32, // Retrait par tick (linéaire) ou facteur multiplicatif (exponentielle, paliers)
+    pub min: f32,  // Valeur plancher
+    pub step_every: u32, // Taille d'un palier en ticks
+}
+
+impl Default for DecayConfig {
+    fn default() -> Self {
+        DecayConfig {
+            schedule: DecaySchedule::Constant,
+            rate: 1.0,
+            min: 0.0,
+            step_every: 1000,
+        }
+    }
+}
+
+impl DecayConfig {
+    // Valeur au tick donné, calculée depuis la valeur initiale (compatible avec le rewind)
+    pub fn value_at(&self, initial: f32, tick: u64) -> f32 {
+        let value = match self.schedule {
+            DecaySchedule::Constant => return initial,
+            DecaySchedule::Linear => initial - self.rate * tick as f32,
+            DecaySchedule::Exponential => initial * self.rate.powf(tick as f32),
+            DecaySchedule::Step => {
+                let steps = tick / self.step_every.max(1) as u64;
+                initial * self.rate.powf(steps as f32)
+            }
+        };
+        value.max(self.min.min(initial))
+    }
+}
+
 pub struct QLearningMath {
     pub alpha: f32,   // Learning rate
     pub gamma: f32,   // Discount factor
@@ -13,8 +141,28 @@ impl QLearningMath {
         }
     }
 
-    // Delta = Alpha * (Reward + Gamma * MaxNext - Current)
-    pub fn compute_delta(&self, current_q: f32, reward: f32, max_next_q: f32) -> f32 {
-        self.alpha * (reward + self.gamma * max_next_q - current_q)
+    // Delta = Alpha * (Reward + Gamma * NextValue - Current)
+    // NextValue dépend de l'algorithme : max (Q-Learning), action suivante (SARSA) ou espérance
+    pub fn compute_delta(&self, current_q: f32, reward: f32, next_value: f32) -> f32 {
+        self.alpha * (reward + self.gamma * next_value - current_q)
+    }
+
+    // Espérance de la valeur suivante : somme des (probabilité, Q) de la politique
+    pub fn expected_value(&self, weighted_qs: &[(f32, f32)]) -> f32 {
+        weighted_qs.iter().map(|(p, q)| p * q).sum()
+    }
+
+    // Double Q-Learning : valeur, dans `evaluator`, de la meilleure action selon `chooser`.
+    // Découpler choix et évaluation évite de surestimer Q quand les valeurs sont bruitées.
+    pub fn double_q_value(&self, chooser: &[f32], evaluator: &[f32]) -> f32 {
+        let best = chooser
+            .iter()
+            .enumerate()
+            .fold(None, |best: Option<(usize, f32)>, (i, &q)| match best {
+                Some((_, best_q)) if best_q >= q => best,
+                _ => Some((i, q)),
+            });
+        best.and_then(|(i, _)| evaluator.get(i).copied())
+            .unwrap_or(0.0)
     }
 }
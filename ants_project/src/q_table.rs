@@ -0,0 +1,253 @@
+//! Q-table indexée par l'état complet d'une fourmi
+//!
+//! L'état vu par le Q-Learning ne se résume pas à la case : une fourmi qui rapporte de la
+//! nourriture ne doit pas apprendre dans la même table qu'une fourmi qui en cherche.
+//! [`StateFeatures`] réunit la case et ces traits ; chaque combinaison de traits a sa couche,
+//! une [`PheromoneMap`] de la taille de la grille. Ajouter un trait revient à ajouter un
+//! champ à `StateFeatures` et un bit à [`StateLayout`], sans nouvelle carte à câbler.
+
+use crate::ant::AntsMode;
+use crate::cli_args::SimulationConfig;
+use crate::grid::Grid;
+use crate::linear_q::LinearQ;
+use crate::pheromone::{PheromoneMap, Resample};
+use crate::q_backend::QBackend;
+use crate::sensing;
+use serde::{Deserialize, Serialize};
+
+/// État d'une fourmi pour le Q-Learning
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StateFeatures {
+    pub x: u32,
+    pub y: u32,
+    /// La fourmi rapporte de la nourriture au nid (mode RETURNING)
+    pub carrying: bool,
+    /// De la nourriture est sur sa case ou en vue ; ignoré si la Q-table ne distingue pas ce
+    /// trait
+    #[serde(default)]
+    pub near_food: bool,
+}
+
+impl StateFeatures {
+    /// État d'une fourmi dans ce mode en (x, y), sans les traits optionnels
+    pub fn new(x: u32, y: u32, mode: AntsMode) -> Self {
+        StateFeatures {
+            x,
+            y,
+            carrying: mode == AntsMode::RETURNING,
+            near_food: false,
+        }
+    }
+
+    /// État d'une fourmi qui voit à `scope` cases en (x, y) : seuls les traits que `layout`
+    /// distingue sont relevés
+    pub fn observe(
+        grid: &Grid,
+        (x, y): (u32, u32),
+        mode: AntsMode,
+        scope: u32,
+        layout: StateLayout,
+    ) -> Self {
+        let near_food = layout.near_food
+            && (grid.has_food(x, y)
+                || sensing::visible_cells(grid, (x, y), scope.max(1))
+                    .into_iter()
+                    .any(|(cx, cy)| grid.has_food(cx, cy)));
+        StateFeatures {
+            near_food,
+            ..StateFeatures::new(x, y, mode)
+        }
+    }
+
+    pub fn mode(&self) -> AntsMode {
+        if self.carrying {
+            AntsMode::RETURNING
+        } else {
+            AntsMode::FINDING
+        }
+    }
+}
+
+/// Traits optionnels distingués par une Q-table (le mode l'est toujours)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateLayout {
+    /// Nourriture en vue (`--near-food-state`)
+    #[serde(default)]
+    pub near_food: bool,
+}
+
+impl StateLayout {
+    pub fn from_config(config: &SimulationConfig) -> Self {
+        StateLayout {
+            near_food: config.near_food_state,
+        }
+    }
+
+    pub fn layer_count(&self) -> usize {
+        2 << usize::from(self.near_food)
+    }
+
+    // Couche d'un état : bit 0 = charge, bit 1 = nourriture en vue
+    fn layer_index(&self, state: StateFeatures) -> usize {
+        usize::from(state.carrying) | (usize::from(self.near_food && state.near_food) << 1)
+    }
+}
+
+/// Q-table d'une colonie (ou d'un type de fourmi) : une couche par combinaison de traits
+#[derive(Clone, Serialize, Deserialize)]
+pub struct QTable {
+    layout: StateLayout,
+    layers: Vec<PheromoneMap>,
+    // Approximation linéaire consultée à la place des couches (`--q-backend linear`) ; les
+    // couches restent alors à leur valeur de départ
+    #[serde(default)]
+    linear: Option<LinearQ>,
+}
+
+impl QTable {
+    pub fn new(width: u32, height: u32, layout: StateLayout) -> Self {
+        QTable {
+            layout,
+            layers: vec![PheromoneMap::new(width, height); layout.layer_count()],
+            linear: None,
+        }
+    }
+
+    /// Table à deux couches (recherche, retour), comme les cerveaux écrits avant les couches
+    pub fn from_mode_maps(food: PheromoneMap, nest: PheromoneMap) -> Self {
+        QTable {
+            layout: StateLayout::default(),
+            layers: vec![food, nest],
+            linear: None,
+        }
+    }
+
+    pub fn width(&self) -> u32 {
+        self.layers.first().map_or(0, |map| map.width)
+    }
+
+    pub fn height(&self) -> u32 {
+        self.layers.first().map_or(0, |map| map.height)
+    }
+
+    pub fn layout(&self) -> StateLayout {
+        self.layout
+    }
+
+    /// Change les traits distingués ; une nouvelle couche part des valeurs de la couche de
+    /// même mode qui ne distinguait pas encore ce trait
+    pub fn set_layout(&mut self, layout: StateLayout) {
+        if layout == self.layout {
+            return;
+        }
+        let base: Vec<PheromoneMap> = self.layers.drain(..2).collect();
+        self.layers = (0..layout.layer_count())
+            .map(|layer| base[layer & 1].clone())
+            .collect();
+        self.layout = layout;
+    }
+
+    /// Indice de la couche d'un état, stable tant que la disposition ne change pas
+    pub fn layer_index(&self, state: StateFeatures) -> usize {
+        self.layout.layer_index(state)
+    }
+
+    pub fn layer(&self, state: StateFeatures) -> &PheromoneMap {
+        &self.layers[self.layer_index(state)]
+    }
+
+    pub fn layer_mut(&mut self, state: StateFeatures) -> &mut PheromoneMap {
+        let layer = self.layer_index(state);
+        &mut self.layers[layer]
+    }
+
+    /// Valeurs Q consultées dans cet état : l'approximation linéaire si la table en a une,
+    /// la couche de l'état sinon
+    pub fn backend(&self, state: StateFeatures) -> &dyn QBackend {
+        match &self.linear {
+            Some(linear) => linear,
+            None => self.layer(state),
+        }
+    }
+
+    /// Valeurs Q corrigées dans cet état ; `second` choisit la seconde table d'une couche en
+    /// Double Q-Learning
+    pub fn backend_mut(&mut self, state: StateFeatures, second: bool) -> &mut dyn QBackend {
+        let layer = self.layout.layer_index(state);
+        match &mut self.linear {
+            Some(linear) => linear,
+            None => self.layers[layer].table_mut(second),
+        }
+    }
+
+    pub fn linear(&self) -> Option<&LinearQ> {
+        self.linear.as_ref()
+    }
+
+    pub fn linear_mut(&mut self) -> Option<&mut LinearQ> {
+        self.linear.as_mut()
+    }
+
+    /// Remplace (ou retire) l'approximation linéaire
+    pub fn set_linear(&mut self, linear: Option<LinearQ>) {
+        self.linear = linear;
+    }
+
+    /// Couche d'un mode quand aucun trait optionnel n'est présent (celle qu'on affiche)
+    pub fn mode_map(&self, mode: AntsMode) -> &PheromoneMap {
+        &self.layers[usize::from(mode == AntsMode::RETURNING)]
+    }
+
+    pub fn layers(&self) -> &[PheromoneMap] {
+        &self.layers
+    }
+
+    pub fn layers_mut(&mut self) -> &mut [PheromoneMap] {
+        &mut self.layers
+    }
+
+    /// Nom d'une couche dans les fichiers exportés : `food`, `nest`, `food_near_food`, ...
+    pub fn layer_name(&self, layer: usize) -> String {
+        let mut name = String::from(if layer & 1 == 0 { "food" } else { "nest" });
+        if layer & 2 != 0 {
+            name.push_str("_near_food");
+        }
+        name
+    }
+
+    /// Vérifie qu'une table lue sur disque a ses couches et la taille attendue
+    pub fn check(&self, width: u32, height: u32) -> Result<(), String> {
+        if self.layers.len() != self.layout.layer_count() {
+            return Err(format!(
+                "{} couche(s) pour {} attendue(s)",
+                self.layers.len(),
+                self.layout.layer_count()
+            ));
+        }
+        match self
+            .layers
+            .iter()
+            .find(|map| map.width != width || map.height != height)
+        {
+            Some(map) => Err(format!(
+                "cartes {}x{} incompatibles avec la grille {}x{}",
+                map.width, map.height, width, height
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Copie de la table adaptée à une grille `width` x `height` (voir `PheromoneMap::resampled`)
+    pub fn resampled(&self, width: u32, height: u32, mode: Resample) -> Self {
+        QTable {
+            layout: self.layout,
+            layers: self
+                .layers
+                .iter()
+                .map(|map| map.resampled(width, height, mode))
+                .collect(),
+            // Les pavés de position d'une autre grille ne se transposent pas
+            linear: None,
+        }
+    }
+}
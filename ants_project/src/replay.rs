@@ -0,0 +1,91 @@
+use crate::ants_game_manager::GameStateSnapshot;
+use crate::cli_args::SimulationConfig;
+use crate::pheromone::Action;
+use crate::replay_verify::StateHashes;
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+
+// Version du format, à incrémenter à chaque changement incompatible
+const REPLAY_VERSION: u32 = 22;
+
+// Actions jouées pendant un tick : (index de la fourmi, action choisie)
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ReplayFrame {
+    pub tick: u64,
+    pub actions: Vec<(u32, Action)>,
+}
+
+// Enregistrement compact d'une partie : état initial, graine et actions horodatées.
+// La graine rejoue les tirages qui ne sont pas des actions (prédateurs, combats).
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Replay {
+    version: u32,
+    pub seed: u64,
+    pub config: SimulationConfig,
+    pub initial_state: GameStateSnapshot,
+    // Seuls les ticks où au moins une fourmi a agi sont stockés
+    pub frames: Vec<ReplayFrame>,
+    // Nombre de ticks simulés pendant l'enregistrement
+    pub ticks: u64,
+    // Empreintes de l'état à la fin de l'enregistrement, vérifiées par `replay_verify`
+    pub final_state: Option<StateHashes>,
+}
+
+impl Replay {
+    pub fn new(seed: u64, config: SimulationConfig, initial_state: GameStateSnapshot) -> Self {
+        Replay {
+            version: REPLAY_VERSION,
+            seed,
+            config,
+            initial_state,
+            frames: Vec::new(),
+            ticks: 0,
+            final_state: None,
+        }
+    }
+
+    pub fn record(&mut self, tick: u64, ant_index: usize, action: Action) {
+        match self.frames.last_mut() {
+            Some(frame) if frame.tick == tick => frame.actions.push((ant_index as u32, action)),
+            _ => self.frames.push(ReplayFrame {
+                tick,
+                actions: vec![(ant_index as u32, action)],
+            }),
+        }
+    }
+
+    // Action enregistrée pour une fourmi à un tick donné
+    pub fn action_for(&self, tick: u64, ant_index: usize) -> Option<Action> {
+        let frame = self
+            .frames
+            .binary_search_by_key(&tick, |f| f.tick)
+            .ok()
+            .map(|idx| &self.frames[idx])?;
+        frame
+            .actions
+            .iter()
+            .find(|(ant, _)| *ant as usize == ant_index)
+            .map(|&(_, action)| action)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let file =
+            File::create(path).map_err(|e| format!("Impossible de créer {}: {}", path, e))?;
+        bincode::serialize_into(BufWriter::new(file), self)
+            .map_err(|e| format!("Impossible d'écrire le replay {}: {}", path, e))
+    }
+
+    pub fn load(path: &str) -> Result<Self, String> {
+        let file = File::open(path).map_err(|e| format!("Impossible de lire {}: {}", path, e))?;
+        let replay: Replay = bincode::deserialize_from(BufReader::new(file))
+            .map_err(|e| format!("Replay invalide {}: {}", path, e))?;
+        if replay.version != REPLAY_VERSION {
+            return Err(format!(
+                "Replay {} en version {}, version {} attendue",
+                path, replay.version, REPLAY_VERSION
+            ));
+        }
+        Ok(replay)
+    }
+}
@@ -0,0 +1,194 @@
+//! Vérification des replays de référence
+//!
+//! Un replay enregistré garde les empreintes de l'état final de la partie : carte, fourmis
+//! et valeurs Q. [`replay_verify`] rejoue la partie avec la graine enregistrée et compare
+//! les empreintes obtenues, ce qui permet de valider une réécriture interne (stockage des
+//! Q-tables, ordre des phases du tick...) contre des parties canoniques enregistrées avant
+//! elle. `ants verify-replay <FILE>...` fait la même chose depuis la ligne de commande.
+//!
+//! Les empreintes de la carte et des fourmis portent sur leur sérialisation, celle des
+//! valeurs Q sur les valeurs elles-mêmes, case par case : elle ne dépend pas de la façon
+//! dont les tables sont stockées.
+
+use crate::ant::Ant;
+use crate::ants_game_manager::AntsGameManager;
+use crate::colony::Colony;
+use crate::grid::Grid;
+use crate::pheromone::PheromoneMap;
+use crate::replay::Replay;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Empreintes (FNV-1a 64 bits) de l'état d'une partie à un tick donné
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StateHashes {
+    pub tick: u64,
+    pub grid: u64,
+    pub ants: u64,
+    pub q_values: u64,
+}
+
+impl StateHashes {
+    /// Empreintes de l'état courant de la partie
+    pub fn of(manager: &AntsGameManager) -> Self {
+        Self::compute(
+            manager.current_tick_index() as u64,
+            manager.grid(),
+            manager.ants(),
+            manager.colonies(),
+        )
+    }
+
+    pub fn compute(tick: u64, grid: &Grid, ants: &[Ant], colonies: &[Colony]) -> Self {
+        let mut q_values = Fnv::new();
+        for colony in colonies {
+            for map in colony.active_maps() {
+                hash_q_values(&mut q_values, map);
+            }
+        }
+        StateHashes {
+            tick,
+            grid: Fnv::of_serialized(grid),
+            ants: Fnv::of_serialized(ants),
+            q_values: q_values.finish(),
+        }
+    }
+
+    // Parties de l'état dont l'empreinte diffère de `expected`
+    fn differences(&self, expected: &StateHashes) -> Vec<&'static str> {
+        [
+            ("tick", self.tick == expected.tick),
+            ("carte", self.grid == expected.grid),
+            ("fourmis", self.ants == expected.ants),
+            ("valeurs Q", self.q_values == expected.q_values),
+        ]
+        .into_iter()
+        .filter(|&(_, same)| !same)
+        .map(|(part, _)| part)
+        .collect()
+    }
+}
+
+impl fmt::Display for StateHashes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "tick {} carte {:016x} fourmis {:016x} Q {:016x}",
+            self.tick, self.grid, self.ants, self.q_values
+        )
+    }
+}
+
+// Toutes les valeurs de la carte (et de sa table jumelle), dans l'ordre des cases
+fn hash_q_values(hasher: &mut Fnv, map: &PheromoneMap) {
+    let tables: &[bool] = if map.is_double() {
+        &[false, true]
+    } else {
+        &[false]
+    };
+    for &second in tables {
+        for y in 0..map.height {
+            for x in 0..map.width {
+                for value in map.table_values(second, x, y) {
+                    hasher.write(&value.to_bits().to_le_bytes());
+                }
+            }
+        }
+    }
+}
+
+// FNV-1a : empreinte stable d'une version du compilateur à l'autre, contrairement au
+// hachage de la bibliothèque standard
+pub(crate) struct Fnv(u64);
+
+impl Fnv {
+    fn new() -> Self {
+        Fnv(0xcbf2_9ce4_8422_2325)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 ^= byte as u64;
+            self.0 = self.0.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    pub(crate) fn of_serialized(value: &(impl Serialize + ?Sized)) -> u64 {
+        let mut hasher = Fnv::new();
+        // La sérialisation en mémoire d'un état valide n'échoue pas
+        if let Ok(bytes) = bincode::serialize(value) {
+            hasher.write(&bytes);
+        }
+        hasher.finish()
+    }
+}
+
+/// Rejoue `replay` jusqu'au dernier tick enregistré et vérifie que l'état final a les
+/// empreintes enregistrées. Renvoie ces empreintes, ou une erreur qui nomme les parties de
+/// l'état qui ont divergé.
+pub fn replay_verify(replay: Replay) -> Result<StateHashes, String> {
+    let Some(expected) = replay.final_state else {
+        return Err("le replay ne contient pas d'empreintes de l'état final".to_string());
+    };
+    let mut manager = AntsGameManager::from_replay(replay).map_err(|e| e.to_string())?;
+    while !manager.replay_finished() {
+        manager.game_step();
+    }
+    let actual = StateHashes::of(&manager);
+    let differences = actual.differences(&expected);
+    if differences.is_empty() {
+        Ok(actual)
+    } else {
+        Err(format!(
+            "état final différent ({})\n  attendu : {}\n  obtenu  : {}",
+            differences.join(", "),
+            expected,
+            actual
+        ))
+    }
+}
+
+/// Charge le replay `path` et le vérifie avec [`replay_verify`]
+pub fn verify_file(path: &str) -> Result<StateHashes, String> {
+    replay_verify(Replay::load(path)?).map_err(|e| format!("{}: {}", path, e))
+}
+
+/// Sous-commande `verify-replay` : vérifie chaque fichier et échoue si l'un d'eux diverge
+pub fn run(args: &[String]) -> Result<(), String> {
+    if args.is_empty() || args.iter().any(|arg| arg == "--help" || arg == "-h") {
+        print_help();
+        return Ok(());
+    }
+    if let Some(flag) = args.iter().find(|arg| arg.starts_with('-')) {
+        return Err(format!("Argument inconnu: {}", flag));
+    }
+    let mut failures = 0;
+    for path in args {
+        match verify_file(path) {
+            Ok(hashes) => println!("OK {} ({})", path, hashes),
+            Err(e) => {
+                failures += 1;
+                println!("ÉCHEC {}", e);
+            }
+        }
+    }
+    if failures > 0 {
+        return Err(format!(
+            "{} replay(s) sur {} divergent",
+            failures,
+            args.len()
+        ));
+    }
+    Ok(())
+}
+
+fn print_help() {
+    println!("Usage: ants verify-replay <FILE>...");
+    println!();
+    println!("Rejoue chaque replay (.ants) avec sa graine et vérifie que la carte, les fourmis");
+    println!("et les valeurs Q finales ont les empreintes enregistrées avec lui.");
+}
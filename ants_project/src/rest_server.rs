@@ -0,0 +1,513 @@
+//! Serveur REST d'évaluation
+//!
+//! `ants serve --port 8080` pilote des simulations headless par HTTP, sans lier la crate :
+//! un tableau de bord web ou un job de CI crée une partie, la fait avancer et relève ses
+//! résultats. Les corps sont en JSON, les erreurs aussi (`{"error": "..."}`).
+//!
+//! | Méthode et chemin                    | Effet                                               |
+//! |--------------------------------------|-----------------------------------------------------|
+//! | `GET /simulations`                   | Parties en cours : identifiant, tick, fin           |
+//! | `POST /simulations`                  | Crée une partie (`{"config": {...}, "map": {...}}`) |
+//! | `POST /simulations/<id>/step?ticks=N`| Joue N ticks (1 par défaut), renvoie l'avancement   |
+//! | `GET /simulations/<id>/state`        | Carte et fourmis au tick courant                    |
+//! | `GET /simulations/<id>/stats`        | Bilan de la partie, celui de `--summary`            |
+//! | `GET /simulations/<id>/qtable`       | Cerveau de la colonie A (format de `--save-brain`)  |
+//! | `DELETE /simulations/<id>`           | Abandonne la partie                                 |
+//!
+//! `config` reprend les champs d'un fichier `--config` (les absents gardent leur valeur par
+//! défaut) et `map` une carte de l'éditeur ; sans carte, la grille est générée. Les chemins
+//! de fichiers de la configuration sont ignorés : le serveur ne lit ni n'écrit rien sur le
+//! disque pour le compte d'un client. Les requêtes sont servies une à une, dans l'ordre :
+//! une requête ne joue pas plus de `MAX_STEP_TICKS` ticks et une carte ne dépasse pas
+//! `MAX_CELLS` cases.
+
+use crate::ant::{Ant, AntsMode};
+use crate::ants_game_manager::AntsGameManager;
+use crate::cli_args::SimulationConfig;
+use crate::map_editor::MapEditor;
+use crate::runner::SimulationRunner;
+use crate::tile::TileType;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Duration;
+
+// Corps de requête accepté au plus (une carte de l'éditeur tient largement dedans)
+const MAX_BODY: usize = 16 * 1024 * 1024;
+// Un client muet ne bloque pas les autres plus longtemps que ça
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+// Les requêtes étant servies une à une, une seule ne doit pas occuper le serveur trop
+// longtemps : ticks joués par requête et cases d'une carte, au plus
+const MAX_STEP_TICKS: u64 = 100_000;
+const MAX_CELLS: u64 = 1_000_000;
+
+struct ServeArgs {
+    bind: String,
+    port: u16,
+    // Parties gardées en mémoire en même temps
+    max_simulations: usize,
+}
+
+// Partie à créer : configuration et carte facultatives
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct CreateRequest {
+    config: Option<SimulationConfig>,
+    map: Option<serde_json::Value>,
+}
+
+#[derive(Serialize)]
+struct Created {
+    id: u64,
+    width: u32,
+    height: u32,
+    seed: u64,
+}
+
+#[derive(Serialize)]
+struct Progress {
+    id: u64,
+    tick: u64,
+    // Ticks effectivement joués par cette requête (moins que demandé si la partie a fini)
+    played: u64,
+    done: bool,
+    end_reason: Option<&'static str>,
+    food_delivered: u64,
+    deaths: u64,
+}
+
+// (id, x, y, type, porte de la nourriture, colonie), comme la diffusion WebSocket
+type AntRecord = (u32, u32, u32, usize, bool, usize);
+
+#[derive(Serialize)]
+struct State<'a> {
+    tick: u64,
+    width: u32,
+    height: u32,
+    // Cases ligne après ligne : la case (x, y) au rang y * width + x
+    tiles: Vec<&'a TileType>,
+    ants: Vec<AntRecord>,
+}
+
+// Réponse HTTP : statut et corps JSON
+struct Response {
+    status: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn json(status: &'static str, value: &impl Serialize) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Response { status, body },
+            Err(e) => Self::error("500 Internal Server Error", e.to_string()),
+        }
+    }
+
+    fn error(status: &'static str, message: impl Into<String>) -> Self {
+        Response {
+            status,
+            body: serde_json::json!({ "error": message.into() }).to_string(),
+        }
+    }
+
+    fn not_found(id: u64) -> Self {
+        Self::error("404 Not Found", format!("Aucune partie {}", id))
+    }
+}
+
+// Requête lue sur la connexion
+struct Request {
+    method: String,
+    path: String,
+    query: BTreeMap<String, String>,
+    body: Vec<u8>,
+}
+
+// Parties en cours, par identifiant
+struct Server {
+    simulations: BTreeMap<u64, SimulationRunner>,
+    next_id: u64,
+    max_simulations: usize,
+}
+
+// Point d'entrée de `ants serve` : `args` ne contient que les options de la sous-commande
+pub fn run(args: &[String]) -> Result<(), String> {
+    let Some(args) = parse_args(args)? else {
+        return Ok(());
+    };
+    let listener = TcpListener::bind((args.bind.as_str(), args.port)).map_err(|e| {
+        format!(
+            "Impossible d'écouter sur {}:{}: {}",
+            args.bind, args.port, e
+        )
+    })?;
+    println!(
+        "Serveur REST sur http://{}:{}/simulations",
+        args.bind, args.port
+    );
+    let mut server = Server {
+        simulations: BTreeMap::new(),
+        next_id: 1,
+        max_simulations: args.max_simulations,
+    };
+    for stream in listener.incoming().flatten() {
+        // Un client qui se déconnecte en cours de route n'arrête pas le serveur
+        let _ = server.respond(stream);
+    }
+    Ok(())
+}
+
+impl Server {
+    fn respond(&mut self, stream: TcpStream) -> std::io::Result<()> {
+        stream.set_read_timeout(Some(READ_TIMEOUT))?;
+        let response = match read_request(&stream)? {
+            Ok(request) => self.handle(&request),
+            Err(response) => response,
+        };
+        let mut stream = &stream;
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            response.status,
+            response.body.len(),
+            response.body
+        )?;
+        stream.flush()
+    }
+
+    fn handle(&mut self, request: &Request) -> Response {
+        let segments: Vec<&str> = request.path.split('/').filter(|s| !s.is_empty()).collect();
+        let id = match segments.get(1).map(|id| id.parse::<u64>()) {
+            None => None,
+            Some(Ok(id)) => Some(id),
+            Some(Err(_)) => return Response::error("404 Not Found", "Identifiant invalide"),
+        };
+        match (request.method.as_str(), segments.as_slice(), id) {
+            ("GET", ["simulations"], _) => self.list(),
+            ("POST", ["simulations"], _) => self.create(&request.body),
+            ("DELETE", ["simulations", _], Some(id)) => match self.simulations.remove(&id) {
+                Some(_) => Response::json("200 OK", &serde_json::json!({ "deleted": id })),
+                None => Response::not_found(id),
+            },
+            ("POST", ["simulations", _, "step"], Some(id)) => self.step(id, &request.query),
+            ("GET", ["simulations", _, "state"], Some(id)) => self.with(id, state),
+            ("GET", ["simulations", _, "stats"], Some(id)) => {
+                self.with(id, |runner| Response::json("200 OK", &runner.report()))
+            }
+            ("GET", ["simulations", _, "qtable"], Some(id)) => {
+                self.with(id, |runner| match runner.manager().brain_json() {
+                    Ok(body) => Response {
+                        status: "200 OK",
+                        body,
+                    },
+                    Err(e) => Response::error("500 Internal Server Error", e),
+                })
+            }
+            (_, ["simulations"], _)
+            | (_, ["simulations", _], _)
+            | (_, ["simulations", _, "step" | "state" | "stats" | "qtable"], _) => {
+                Response::error("405 Method Not Allowed", "Méthode non prise en charge")
+            }
+            _ => Response::error("404 Not Found", "Chemin inconnu (voir /simulations)"),
+        }
+    }
+
+    fn with(&self, id: u64, f: impl FnOnce(&SimulationRunner) -> Response) -> Response {
+        self.simulations
+            .get(&id)
+            .map_or_else(|| Response::not_found(id), f)
+    }
+
+    fn list(&self) -> Response {
+        let simulations: Vec<serde_json::Value> = self
+            .simulations
+            .iter()
+            .map(|(id, runner)| {
+                serde_json::json!({
+                    "id": id,
+                    "tick": runner.tick_count(),
+                    "done": runner.is_done(),
+                })
+            })
+            .collect();
+        Response::json("200 OK", &simulations)
+    }
+
+    fn create(&mut self, body: &[u8]) -> Response {
+        if self.simulations.len() >= self.max_simulations {
+            return Response::error(
+                "503 Service Unavailable",
+                format!(
+                    "{} parties en cours, supprimez-en une (DELETE /simulations/<id>)",
+                    self.simulations.len()
+                ),
+            );
+        }
+        let request: CreateRequest = if body.iter().all(u8::is_ascii_whitespace) {
+            CreateRequest::default()
+        } else {
+            match serde_json::from_slice(body) {
+                Ok(request) => request,
+                Err(e) => return Response::error("400 Bad Request", e.to_string()),
+            }
+        };
+        let manager = match build_game(request) {
+            Ok(manager) => manager,
+            Err(e) => return Response::error("400 Bad Request", e),
+        };
+        let id = self.next_id;
+        self.next_id += 1;
+        let created = Created {
+            id,
+            width: manager.grid().get_width(),
+            height: manager.grid().get_height(),
+            seed: manager.seed(),
+        };
+        self.simulations.insert(id, SimulationRunner::new(manager));
+        Response::json("201 Created", &created)
+    }
+
+    fn step(&mut self, id: u64, query: &BTreeMap<String, String>) -> Response {
+        let ticks = match query.get("ticks").map(|ticks| ticks.parse::<u64>()) {
+            None => 1,
+            Some(Ok(ticks)) if ticks <= MAX_STEP_TICKS => ticks,
+            Some(Ok(_)) => {
+                return Response::error(
+                    "400 Bad Request",
+                    format!("ticks limité à {} par requête", MAX_STEP_TICKS),
+                )
+            }
+            Some(Err(_)) => {
+                return Response::error("400 Bad Request", "ticks attend un entier positif")
+            }
+        };
+        let Some(runner) = self.simulations.get_mut(&id) else {
+            return Response::not_found(id);
+        };
+        let played = runner.run_ticks(ticks);
+        let metrics = runner.manager().metrics();
+        Response::json(
+            "200 OK",
+            &Progress {
+                id,
+                tick: runner.tick_count(),
+                played,
+                done: runner.is_done(),
+                end_reason: runner.end_reason().map(|reason| reason.name()),
+                food_delivered: metrics.total_food_delivered,
+                deaths: metrics.total_deaths,
+            },
+        )
+    }
+}
+
+fn state(runner: &SimulationRunner) -> Response {
+    let manager = runner.manager();
+    let grid = manager.grid();
+    let shape = grid.shape();
+    let tiles = shape
+        .coords()
+        .filter_map(|coord| grid.get_tile(coord.into()).map(|tile| &tile.tile_type))
+        .collect();
+    let ants = manager
+        .ants()
+        .iter()
+        .filter_map(|ant| {
+            ant.position.map(|(x, y)| {
+                let carrying = ant.mode == AntsMode::RETURNING;
+                (ant.id, x, y, ant.ant_type.index(), carrying, ant.colony)
+            })
+        })
+        .collect();
+    Response::json(
+        "200 OK",
+        &State {
+            tick: runner.tick_count(),
+            width: shape.width,
+            height: shape.height,
+            tiles,
+            ants,
+        },
+    )
+}
+
+// Partie headless de la configuration reçue, sur la carte reçue sinon sur une carte générée
+fn build_game(request: CreateRequest) -> Result<AntsGameManager, String> {
+    let config = request.config.unwrap_or_default();
+    // Rien n'est lu ni écrit sur le disque, et aucun autre serveur n'est ouvert
+    let config = SimulationConfig {
+        use_gui: false,
+        use_tui: false,
+        realtime: false,
+        watch_interval: 0,
+        output_file: None,
+        summary_file: None,
+        report_file: None,
+        report_gif: false,
+        leaderboard_file: None,
+        metrics_port: None,
+        serve_ws: None,
+        render_frames: None,
+        runs_output: None,
+        map_file: None,
+        save_brain: None,
+        dump_qtable: None,
+        load_brain: None,
+        record_file: None,
+        replay_file: None,
+        checkpoint_every: 0,
+        resume_from: None,
+        scenario_file: None,
+        dump_config: None,
+        ..config
+    };
+    config
+        .validate()
+        .map_err(|e| format!("Configuration invalide: {}", e))?;
+    let created = match request.map {
+        Some(map) => {
+            let map = MapEditor::from_json(&map.to_string())
+                .map_err(|e| format!("Carte invalide: {}", e))?;
+            check_size(map.width, map.height)?;
+            AntsGameManager::new(
+                map.width,
+                map.height,
+                map.to_tiles(),
+                vec![],
+                config.clone(),
+            )
+            .map(|mut manager| {
+                manager.set_ants(Ant::colony_from_config(&config));
+                manager
+            })
+        }
+        None => {
+            check_size(config.grid_width, config.grid_height)?;
+            AntsGameManager::from_config(config)
+        }
+    };
+    created.map_err(|e| format!("Erreur de création de la partie: {}", e))
+}
+
+fn check_size(width: u32, height: u32) -> Result<(), String> {
+    if u64::from(width) * u64::from(height) > MAX_CELLS {
+        return Err(format!(
+            "Carte {}x{} trop grande : {} cases au plus",
+            width, height, MAX_CELLS
+        ));
+    }
+    Ok(())
+}
+
+// Ligne de requête, en-têtes (seul Content-Length compte) puis corps. Une requête
+// illisible donne directement la réponse d'erreur à renvoyer.
+fn read_request(stream: &TcpStream) -> std::io::Result<Result<Request, Response>> {
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(Err(Response::error(
+            "400 Bad Request",
+            "Requête HTTP invalide",
+        )));
+    };
+    let (method, target) = (method.to_string(), target.to_string());
+
+    let mut length = 0;
+    let mut header = String::new();
+    while reader.read_line(&mut header)? > 2 {
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                match value.trim().parse() {
+                    Ok(value) => length = value,
+                    Err(_) => {
+                        return Ok(Err(Response::error(
+                            "400 Bad Request",
+                            "Content-Length invalide",
+                        )))
+                    }
+                }
+            }
+        }
+        header.clear();
+    }
+    if length > MAX_BODY {
+        return Ok(Err(Response::error(
+            "413 Payload Too Large",
+            format!("Corps limité à {} octets", MAX_BODY),
+        )));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+    let query = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (key.to_string(), value.to_string())
+        })
+        .collect();
+    Ok(Ok(Request {
+        method,
+        path: path.to_string(),
+        query,
+        body,
+    }))
+}
+
+fn parse_args(args: &[String]) -> Result<Option<ServeArgs>, String> {
+    let mut parsed = ServeArgs {
+        bind: "127.0.0.1".to_string(),
+        port: 8080,
+        max_simulations: 16,
+    };
+    let mut i = 0;
+    while i < args.len() {
+        let flag = args[i].as_str();
+        if flag == "--help" || flag == "-h" {
+            print_help();
+            return Ok(None);
+        }
+        let value = args
+            .get(i + 1)
+            .ok_or_else(|| format!("{} attend une valeur", flag))?;
+        let number =
+            |expected: &str| format!("Valeur invalide pour {}: {} ({})", flag, value, expected);
+        match flag {
+            "--port" => parsed.port = value.parse().map_err(|_| number("port 0-65535"))?,
+            "--bind" => parsed.bind = value.clone(),
+            "--max-simulations" => {
+                parsed.max_simulations = value
+                    .parse()
+                    .ok()
+                    .filter(|&count| count > 0)
+                    .ok_or_else(|| number("entier > 0"))?
+            }
+            other => return Err(format!("Argument inconnu: {}", other)),
+        }
+        i += 2;
+    }
+    Ok(Some(parsed))
+}
+
+fn print_help() {
+    println!("Usage: ants serve [OPTIONS]");
+    println!();
+    println!("Pilote des simulations headless par une API REST en JSON :");
+    println!("  GET    /simulations                 Parties en cours");
+    println!("  POST   /simulations                 Créer une partie ({{\"config\": ..., \"map\": ...}})");
+    println!("  POST   /simulations/<id>/step?ticks=N  Jouer N ticks");
+    println!("  GET    /simulations/<id>/state      Carte et fourmis");
+    println!("  GET    /simulations/<id>/stats      Bilan (format de --summary)");
+    println!("  GET    /simulations/<id>/qtable     Cerveau (format de --save-brain)");
+    println!("  DELETE /simulations/<id>            Supprimer une partie");
+    println!();
+    println!("OPTIONS:");
+    println!("  --port <N>             Port d'écoute (défaut: 8080)");
+    println!("  --bind <ADDR>          Adresse d'écoute (défaut: 127.0.0.1, 0.0.0.0 pour toutes)");
+    println!("  --max-simulations <N>  Parties gardées en mémoire en même temps (défaut: 16)");
+}
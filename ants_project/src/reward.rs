@@ -0,0 +1,66 @@
+use crate::ant::{Ant, AntsMode};
+use crate::cli_args::SimulationConfig;
+use crate::grid::Grid;
+use crate::tile::SUGAR_VALUE;
+
+/// Récompense d'un pas de fourmi, apprise par sa Q-table. `target` est la case où elle
+/// arrive (la sortie si elle emprunte un tunnel) ; elle peut être hors de la carte. La fourmi
+/// est encore dans l'état d'avant le pas : position, mode et charge de départ.
+///
+/// Le calcul a lieu pendant la phase de décision parallèle, d'où `Sync`.
+pub trait RewardModel: Send + Sync {
+    fn reward(&self, ant: &Ant, target: (u32, u32), grid: &Grid, config: &SimulationConfig) -> f32;
+}
+
+// Part de sa charge maximale qu'une fourmi remplit en ramassant `taken` unités, ou 1 sans
+// `reward_load_scaling`
+pub(crate) fn load_fraction(ant: &Ant, taken: u32, config: &SimulationConfig) -> f32 {
+    if !config.reward_load_scaling || ant.maximal_charge == 0 {
+        return 1.0;
+    }
+    (taken as f32 / ant.maximal_charge as f32).min(1.0)
+}
+
+/// Récompenses de la configuration : `reward_death` dans une zone mortelle, `reward_food`
+/// (multipliée par la valeur d'une unité) sur une source en cherchant, `reward_nest` au nid
+/// de sa colonie en rentrant, `reward_default` partout ailleurs (modèle par défaut).
+///
+/// `reward_load_scaling` multiplie en plus `reward_food` par la part de la charge que la
+/// fourmi remplira sur la source, et `reward_per_unit` s'ajoute au nid pour chaque unité
+/// livrée : sans eux, une ramasseuse qui repart avec une unité est aussi bien récompensée
+/// qu'une ramasseuse pleine.
+pub struct DefaultReward;
+
+impl RewardModel for DefaultReward {
+    fn reward(
+        &self,
+        ant: &Ant,
+        (nx, ny): (u32, u32),
+        grid: &Grid,
+        config: &SimulationConfig,
+    ) -> f32 {
+        if grid.is_lethal(nx, ny) {
+            return config.reward_death;
+        }
+
+        match ant.mode {
+            // Une source vaut d'autant plus que chacune de ses unités est nourrissante
+            AntsMode::FINDING if grid.has_food(nx, ny) => {
+                let tile = grid.get_tile((nx, ny));
+                let value = tile.and_then(|tile| tile.food_value());
+                // Ce que la fourmi prendra, estimé sur la source avant les ramassages du tick
+                let taken = tile
+                    .and_then(|tile| tile.food_amount())
+                    .unwrap_or(0)
+                    .min(ant.maximal_charge.saturating_sub(ant.current_charge));
+                config.reward_food
+                    * value.unwrap_or(SUGAR_VALUE) as f32
+                    * load_fraction(ant, taken, config)
+            }
+            AntsMode::RETURNING if grid.is_colony_nest(nx, ny, ant.colony) => {
+                config.reward_nest + config.reward_per_unit * ant.current_charge as f32
+            }
+            _ => config.reward_default,
+        }
+    }
+}
@@ -0,0 +1,373 @@
+use crate::ant::AntsType;
+use crate::ants_game_manager::{AntsGameManager, EndReason};
+use crate::cli_args::SimulationConfig;
+use crate::colony::Colony;
+use crate::error::AntsError;
+use crate::meta_controller::ControllerDecision;
+use crate::metrics::AntTypeStats;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
+use tracing::{error, info};
+
+/// Rythme des ticks : celui de la GUI, et du CLI avec `--realtime`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TickRate {
+    /// Aussi vite que possible
+    Unlimited,
+    /// Nombre de ticks par seconde
+    TicksPerSecond(u32),
+    /// Délai entre deux ticks, en millisecondes
+    MsPerTick(u64),
+}
+
+impl Default for TickRate {
+    fn default() -> Self {
+        TickRate::MsPerTick(100)
+    }
+}
+
+impl TickRate {
+    /// `unlimited`, `<N>tps` (ticks par seconde) ou `<N>ms` (délai entre deux ticks), N > 0
+    pub fn from_name(name: &str) -> Option<Self> {
+        if name == "unlimited" {
+            return Some(TickRate::Unlimited);
+        }
+        let rate = if let Some(tps) = name.strip_suffix("tps") {
+            TickRate::TicksPerSecond(tps.parse().ok()?)
+        } else {
+            TickRate::MsPerTick(name.strip_suffix("ms")?.parse().ok()?)
+        };
+        rate.is_valid().then_some(rate)
+    }
+
+    /// Un rythme de 0 tick par seconde ou de 0 ms par tick n'a pas de sens : `Unlimited`
+    /// remplace l'ancienne valeur sentinelle 0
+    pub fn is_valid(&self) -> bool {
+        !matches!(self, TickRate::TicksPerSecond(0) | TickRate::MsPerTick(0))
+    }
+
+    /// Attente entre deux ticks, None sans limite
+    pub fn interval(&self) -> Option<Duration> {
+        match *self {
+            TickRate::Unlimited => None,
+            TickRate::TicksPerSecond(tps) => Some(Duration::from_secs_f64(1.0 / tps.max(1) as f64)),
+            TickRate::MsPerTick(ms) => Some(Duration::from_millis(ms)),
+        }
+    }
+
+    /// Deux fois plus de ticks par seconde ; au-delà de `max_tps` ou sous 1 ms par tick, plus
+    /// de limite
+    pub fn faster(&self, max_tps: u32) -> Self {
+        match *self {
+            TickRate::Unlimited => TickRate::Unlimited,
+            TickRate::TicksPerSecond(tps) if tps >= max_tps => TickRate::Unlimited,
+            TickRate::TicksPerSecond(tps) => TickRate::TicksPerSecond((tps * 2).min(max_tps)),
+            TickRate::MsPerTick(ms) if ms <= 1 => TickRate::Unlimited,
+            TickRate::MsPerTick(ms) => TickRate::MsPerTick(ms / 2),
+        }
+    }
+
+    /// Deux fois moins de ticks par seconde, sans passer sous 1 tick par seconde ni au-delà
+    /// de `max_ms` par tick
+    pub fn slower(&self, max_ms: u64) -> Self {
+        match *self {
+            TickRate::Unlimited => TickRate::MsPerTick(1),
+            TickRate::TicksPerSecond(tps) => TickRate::TicksPerSecond((tps / 2).max(1)),
+            TickRate::MsPerTick(ms) => TickRate::MsPerTick((ms * 2).clamp(1, max_ms)),
+        }
+    }
+}
+
+impl fmt::Display for TickRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TickRate::Unlimited => write!(f, "unlimited"),
+            TickRate::TicksPerSecond(tps) => write!(f, "{}tps", tps),
+            TickRate::MsPerTick(ms) => write!(f, "{}ms", ms),
+        }
+    }
+}
+
+// Bilan d'une simulation lancée via SimulationRunner
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SimulationSummary {
+    pub ticks: u64,
+    pub food_delivered: u64,
+    pub deaths: u64,
+    // Vrai si la partie s'est terminée d'elle-même (et non par la limite de ticks)
+    pub finished: bool,
+    // Condition d'arrêt (None si la simulation a été interrompue avant sa fin)
+    pub end_reason: Option<EndReason>,
+    pub winner: Option<usize>,
+    // Compteurs d'activité de toutes les fourmis, allers-retours compris
+    pub activity: AntTypeStats,
+    // Plus court aller-retour nid-nourriture de la première colonie au départ
+    pub optimal_round_trip: Option<u32>,
+}
+
+/// Bilan d'une partie CLI au format JSON (`--summary`), lisible par des scripts : les
+/// compteurs de `SimulationSummary`, ceux de chaque type de fourmi, la graine et la
+/// configuration jouée
+#[derive(Serialize)]
+pub struct RunReport<'a> {
+    pub seed: u64,
+    pub ticks: u64,
+    pub finished: bool,
+    /// Nom stable de la condition d'arrêt (voir `EndReason::name`)
+    pub end_reason: Option<&'static str>,
+    /// Lettre de la colonie gagnante
+    pub winner: Option<char>,
+    pub food_delivered: u64,
+    pub deaths: u64,
+    /// Blocages détectés (`--stuck-ticks`)
+    pub stuck_ants: u32,
+    pub mean_trip_length: Option<f64>,
+    pub optimal_round_trip: Option<u32>,
+    pub trip_regret: Option<f64>,
+    pub explorers: AntTypeStats,
+    pub pickers: AntTypeStats,
+    pub fighters: AntTypeStats,
+    /// Décisions du méta-contrôleur (`--meta-controller`), vide s'il est désactivé
+    pub controller: &'a [ControllerDecision],
+    pub config: &'a SimulationConfig,
+}
+
+impl RunReport<'_> {
+    /// Écrit le bilan dans `path`, ou sur la sortie standard si `path` vaut `-`
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Impossible de sérialiser le bilan: {}", e))?;
+        if path == "-" {
+            println!("{}", json);
+            return Ok(());
+        }
+        fs::write(path, json + "\n").map_err(|e| format!("Impossible d'écrire {}: {}", path, e))
+    }
+}
+
+// Fonction appelée après chaque tick avec la partie
+type StepHook = Box<dyn FnMut(&AntsGameManager) + Send>;
+
+/// Boucle de simulation sans affichage, partagée par le CLI et les outils externes
+pub struct SimulationRunner {
+    manager: AntsGameManager,
+    ticks: u64,
+    max_ticks: u64,
+    // Checkpoint réécrit tous les `every` ticks (désactivé après un échec d'écriture)
+    checkpoint: Option<(u64, String)>,
+    // Appelés après chaque tick avec la partie (diffusion de l'état, ...)
+    step_hooks: Vec<StepHook>,
+    // Attente entre deux ticks (`--realtime`) et moment où le prochain tick est dû
+    tick_interval: Option<Duration>,
+    next_tick: Option<Instant>,
+    // Temps de calcul accordé à la partie (`--run-timeout`) et temps déjà passé à la jouer
+    time_limit: Option<Duration>,
+    busy: Duration,
+}
+
+impl SimulationRunner {
+    /// La limite de ticks est celle de la configuration du manager ; une partie reprise
+    /// d'un checkpoint compte aussi les ticks joués avant lui
+    pub fn new(manager: AntsGameManager) -> Self {
+        let max_ticks = manager.config().max_ticks;
+        SimulationRunner {
+            ticks: manager.current_tick_index() as u64,
+            manager,
+            max_ticks,
+            checkpoint: None,
+            step_hooks: Vec::new(),
+            tick_interval: None,
+            next_tick: None,
+            time_limit: None,
+            busy: Duration::ZERO,
+        }
+    }
+
+    pub fn from_config(config: SimulationConfig) -> Result<Self, AntsError> {
+        AntsGameManager::from_config(config).map(Self::new)
+    }
+
+    pub fn with_max_ticks(mut self, max_ticks: u64) -> Self {
+        self.max_ticks = max_ticks;
+        self
+    }
+
+    /// Sauvegarde la partie dans `path` tous les `every` ticks (0 = jamais)
+    pub fn with_checkpoints(mut self, every: u64, path: &str) -> Self {
+        self.checkpoint = (every > 0).then(|| (every, path.to_string()));
+        self
+    }
+
+    /// Abandonne la partie une fois `limit` de temps de calcul passé à la jouer : seul le
+    /// temps passé dans les ticks compte, pas celui où la partie attend son tour
+    pub fn with_time_limit(mut self, limit: Duration) -> Self {
+        self.time_limit = Some(limit);
+        self
+    }
+
+    /// Joue les ticks au rythme `rate` plutôt qu'aussi vite que possible
+    pub fn with_tick_rate(mut self, rate: TickRate) -> Self {
+        self.tick_interval = rate.interval();
+        self
+    }
+
+    /// Appelle `f` après chaque tick joué par ce runner
+    pub fn on_step(&mut self, f: impl FnMut(&AntsGameManager) + Send + 'static) {
+        self.step_hooks.push(Box::new(f));
+    }
+
+    /// Libère les fonctions d'`on_step`, ce qui termine les sorties qu'elles tiennent
+    /// (animation GIF des images rendues, ...)
+    pub fn clear_step_hooks(&mut self) {
+        self.step_hooks.clear();
+    }
+
+    pub fn manager(&self) -> &AntsGameManager {
+        &self.manager
+    }
+
+    pub fn manager_mut(&mut self) -> &mut AntsGameManager {
+        &mut self.manager
+    }
+
+    pub fn into_manager(self) -> AntsGameManager {
+        self.manager
+    }
+
+    /// Nombre de ticks joués par la partie
+    pub fn tick_count(&self) -> u64 {
+        self.ticks
+    }
+
+    /// Limite de ticks de la partie
+    pub fn max_ticks(&self) -> u64 {
+        self.max_ticks
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.ticks >= self.max_ticks || self.is_finished() || self.timed_out()
+    }
+
+    /// Vrai si la partie a été abandonnée faute de temps de calcul (DNF)
+    pub fn timed_out(&self) -> bool {
+        self.time_limit.is_some_and(|limit| self.busy >= limit)
+    }
+
+    // Fin naturelle : objectif atteint, plus de nourriture ou de fourmis, ou replay entièrement rejoué
+    fn is_finished(&self) -> bool {
+        self.manager.is_game_finished() || self.manager.replay_finished()
+    }
+
+    /// Condition d'arrêt de la partie, y compris la limite de ticks du runner
+    pub fn end_reason(&self) -> Option<EndReason> {
+        self.manager
+            .end_reason()
+            .or_else(|| (self.ticks >= self.max_ticks).then_some(EndReason::TickLimit))
+            .or_else(|| self.timed_out().then_some(EndReason::Timeout))
+    }
+
+    /// Avance d'au plus `n` ticks, renvoie le nombre de ticks réellement joués
+    pub fn run_ticks(&mut self, n: u64) -> u64 {
+        let start = self.ticks;
+        while self.ticks - start < n && !self.is_done() {
+            self.step();
+        }
+        self.ticks - start
+    }
+
+    /// Joue jusqu'à la fin de la partie ou jusqu'à la limite de ticks
+    pub fn run_to_completion(&mut self) -> SimulationSummary {
+        while !self.is_done() {
+            self.step();
+        }
+        self.summary()
+    }
+
+    fn step(&mut self) {
+        self.wait_for_tick();
+        // Horloge lue seulement avec une limite de temps (pas d'horloge système en wasm)
+        let start = self.time_limit.map(|_| Instant::now());
+        self.manager.game_step();
+        if let Some(start) = start {
+            self.busy += start.elapsed();
+        }
+        self.ticks += 1;
+        for hook in &mut self.step_hooks {
+            hook(&self.manager);
+        }
+        if let Some((every, path)) = &self.checkpoint {
+            if self.ticks.is_multiple_of(*every) {
+                match self.manager.save_checkpoint(path) {
+                    Ok(()) => info!(
+                        kind = "checkpoint",
+                        tick = self.ticks,
+                        "écrit dans {}",
+                        path
+                    ),
+                    Err(e) => {
+                        // On arrête les checkpoints plutôt que de répéter l'erreur à chaque fois
+                        error!(kind = "checkpoint", tick = self.ticks, "{}", e);
+                        self.checkpoint = None;
+                    }
+                }
+            }
+        }
+    }
+
+    // Attend que le prochain tick soit dû ; un retard n'est pas rattrapé par une rafale
+    fn wait_for_tick(&mut self) {
+        let Some(interval) = self.tick_interval else {
+            return;
+        };
+        let now = Instant::now();
+        let due = self.next_tick.unwrap_or(now);
+        if due > now {
+            thread::sleep(due - now);
+        }
+        self.next_tick = Some(due.max(now) + interval);
+    }
+
+    /// Bilan JSON de la partie dans son état actuel
+    pub fn report(&self) -> RunReport<'_> {
+        let summary = self.summary();
+        let by_type = AntTypeStats::by_type(self.manager.ants());
+        let [explorers, pickers, fighters] =
+            AntsType::all().map(|ant_type| by_type[ant_type.index()]);
+        RunReport {
+            seed: self.manager.seed(),
+            ticks: summary.ticks,
+            finished: summary.finished,
+            end_reason: summary.end_reason.map(|reason| reason.name()),
+            winner: summary.winner.map(Colony::label),
+            food_delivered: summary.food_delivered,
+            deaths: summary.deaths,
+            stuck_ants: self.manager.stuck_ants(),
+            mean_trip_length: summary.activity.mean_trip_length(),
+            optimal_round_trip: summary.optimal_round_trip,
+            trip_regret: summary.activity.trip_regret(),
+            explorers,
+            pickers,
+            fighters,
+            controller: self.manager.controller_decisions(),
+            config: self.manager.config(),
+        }
+    }
+
+    pub fn summary(&self) -> SimulationSummary {
+        let metrics = self.manager.metrics();
+        SimulationSummary {
+            ticks: self.ticks,
+            food_delivered: metrics.total_food_delivered,
+            deaths: metrics.total_deaths,
+            finished: self.is_finished(),
+            end_reason: self.end_reason(),
+            winner: self.manager.winner(),
+            activity: AntTypeStats::combined(&AntTypeStats::by_type(self.manager.ants())),
+            optimal_round_trip: self.manager.optimal_round_trip(0),
+        }
+    }
+}
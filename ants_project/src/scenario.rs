@@ -0,0 +1,126 @@
+//! Scénarios : une expérience complète dans un seul fichier TOML
+//!
+//! Un fichier `.scenario` réunit la configuration (graine, nombre de fourmis, zones
+//! scriptées comprises), la carte de l'éditeur et une chronologie d'événements joués à un
+//! tick donné. Chargé avec `--scenario`, il rejoue exactement la même expérience.
+//!
+//! ```toml
+//! [config]
+//! seed = 42
+//! num_pickers = 5
+//!
+//! [map]
+//! width = 3
+//! height = 1
+//! tiles = [["Nest", "Default", "FoodSource"]]
+//!
+//! [[events]]
+//! tick = 500
+//! kind = "food"
+//! x = 1
+//! y = 0
+//! amount = 200
+//!
+//! [[events]]
+//! tick = 800
+//! kind = "predator_wave"
+//! count = 3
+//!
+//! [goal]
+//! food = 150
+//! max_deaths = 20
+//! transfer = "scale"
+//! ```
+//!
+//! La section `[goal]` ne sert qu'à `ants curriculum` : c'est le critère de réussite de
+//! l'étape (nourriture à livrer avant `max_ticks`, morts tolérées) et la façon d'adapter à
+//! cette carte le cerveau appris à l'étape précédente.
+
+use crate::cli_args::SimulationConfig;
+use crate::map_editor::{MapEditor, MapFile};
+use crate::pheromone::Resample;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+/// Changement de la carte joué au début d'un tick
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum EventKind {
+    /// Ajoute `amount` unités de nourriture sur une case vide ou une source existante
+    Food { x: u32, y: u32, amount: u32 },
+    /// Rend une case vide mortelle (`lethal = true`) ou retire une zone mortelle
+    Hazard { x: u32, y: u32, lethal: bool },
+    /// Fait apparaître `count` prédateurs sur des cases libres, au-delà de `max_predators`
+    PredatorWave { count: u32 },
+}
+
+/// Événement de la chronologie d'un scénario
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScenarioEvent {
+    pub tick: usize,
+    #[serde(flatten)]
+    pub kind: EventKind,
+}
+
+/// Critère de réussite d'une étape de curriculum
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StageGoal {
+    /// Nourriture à livrer au nid A avant la limite de ticks de l'étape
+    pub food: u64,
+    /// Morts tolérées pendant l'étape (sans limite si absent)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_deaths: Option<u64>,
+    /// Adaptation du cerveau de l'étape précédente à cette carte
+    #[serde(default)]
+    pub transfer: Resample,
+}
+
+/// Contenu d'un fichier `.scenario`
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Scenario {
+    #[serde(default)]
+    pub config: SimulationConfig,
+    // Carte dessinée dans l'éditeur (None = carte aléatoire de la taille configurée)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub map: Option<MapFile>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<ScenarioEvent>,
+    // Objectif de l'étape quand le scénario fait partie d'un curriculum
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub goal: Option<StageGoal>,
+}
+
+impl Scenario {
+    /// Lit un scénario ; ses événements rejoignent ceux de sa configuration
+    pub fn load(path: &str) -> Result<Self, String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Impossible de lire {}: {}", path, e))?;
+        Self::from_toml(&content).map_err(|e| format!("{}: {}", path, e))
+    }
+
+    /// Lit un scénario depuis son texte TOML (voir `load`)
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let mut scenario: Scenario =
+            toml::from_str(content).map_err(|e| format!("Scénario invalide: {}", e))?;
+        let events = std::mem::take(&mut scenario.events);
+        scenario.config.events.extend(events);
+        scenario.config.events.sort_by_key(|event| event.tick);
+        Ok(scenario)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        fs::write(path, self.to_toml()?).map_err(|e| format!("Impossible d'écrire {}: {}", path, e))
+    }
+
+    pub fn to_toml(&self) -> Result<String, String> {
+        toml::to_string_pretty(self)
+            .map_err(|e| format!("Impossible de sérialiser le scénario: {}", e))
+    }
+
+    /// Éditeur ouvert sur la carte du scénario, s'il en a une
+    pub fn map_editor(&self) -> Option<Result<MapEditor, String>> {
+        self.map
+            .clone()
+            .map(|map| MapEditor::from_map_file(map).map_err(|e| format!("Carte invalide: {}", e)))
+    }
+}
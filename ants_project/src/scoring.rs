@@ -0,0 +1,246 @@
+//! Scores et meilleurs scores : un objectif chiffré pour régler les paramètres à la main
+//!
+//! Le score d'une partie combine la nourriture livrée, les fourmis mortes et les ticks joués
+//! selon la formule de la configuration ([`ScoreRule`]) :
+//! `score_food × nourriture − score_death × morts − score_tick × ticks`.
+//!
+//! Les parties terminées sont inscrites par carte dans un classement JSON local
+//! ([`Leaderboard`], `meilleurs_scores.json` par défaut) : une entrée pour la partie entière
+//! et, quand plusieurs colonies s'affrontent, une entrée par colonie. Une carte est reconnue
+//! à l'empreinte de son état de départ ([`map_fingerprint`]), d'où qu'elle vienne. Les
+//! entrées gardent la nourriture, les morts et les ticks plutôt que le score : changer la
+//! formule reclasse aussitôt toutes les parties déjà inscrites.
+
+use crate::ants_game_manager::AntsGameManager;
+use crate::cli_args::SimulationConfig;
+use crate::experiment::rfc3339;
+use crate::grid::Grid;
+use crate::replay_verify::Fnv;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use web_time::SystemTime;
+
+/// Classement proposé par défaut, dans le dossier courant
+pub const DEFAULT_LEADERBOARD_FILE: &str = "meilleurs_scores.json";
+
+// Parties gardées par carte dans chaque classement ; au-delà, les moins bonnes sont oubliées
+const MAX_ENTRIES: usize = 100;
+
+/// Formule du score : points par unité de nourriture livrée, retirés par fourmi morte et
+/// par tick joué
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ScoreRule {
+    pub food: f32,
+    pub death: f32,
+    pub tick: f32,
+}
+
+impl ScoreRule {
+    pub fn from_config(config: &SimulationConfig) -> Self {
+        ScoreRule {
+            food: config.score_food,
+            death: config.score_death,
+            tick: config.score_tick,
+        }
+    }
+
+    pub fn score(&self, result: &RunResult) -> f64 {
+        f64::from(self.food) * result.food as f64
+            - f64::from(self.death) * result.deaths as f64
+            - f64::from(self.tick) * result.ticks as f64
+    }
+}
+
+/// Empreinte de la carte `grid` (FNV-1a de sa forme sérialisée)
+pub fn map_fingerprint(grid: &Grid) -> u64 {
+    Fnv::of_serialized(grid)
+}
+
+/// Résultat d'une partie, ou d'une de ses colonies, et les réglages qui l'ont produit
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RunResult {
+    /// Colonie classée, None pour la partie entière (toutes colonies confondues)
+    pub colony: Option<usize>,
+    /// Nourriture livrée aux nids (unités multipliées par leur valeur)
+    pub food: u64,
+    pub deaths: u64,
+    pub ticks: u64,
+    /// Condition d'arrêt (voir `EndReason::name`)
+    pub end_reason: Option<String>,
+    pub seed: u64,
+    pub alpha: f32,
+    pub gamma: f32,
+    pub epsilon: f32,
+}
+
+/// Résultats d'une partie terminée, prêts à être inscrits au classement de sa carte
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunScores {
+    /// Empreinte de la carte de départ et ses dimensions
+    pub map: u64,
+    pub width: u32,
+    pub height: u32,
+    /// La partie entière
+    pub run: RunResult,
+    /// Chaque colonie, vide s'il n'y en a qu'une
+    pub colonies: Vec<RunResult>,
+}
+
+impl RunScores {
+    /// Résultats de la partie de `manager` au tick courant, arrêtée pour `end_reason`
+    pub fn of(manager: &AntsGameManager, end_reason: Option<&str>) -> Self {
+        let config = manager.config();
+        let result = |colony, food, deaths| RunResult {
+            colony,
+            food,
+            deaths,
+            ticks: manager.current_tick_index() as u64,
+            end_reason: end_reason.map(str::to_string),
+            seed: manager.seed(),
+            alpha: config.alpha,
+            gamma: config.gamma,
+            epsilon: config.epsilon,
+        };
+        let colonies = manager.colonies();
+        let run = result(
+            None,
+            colonies.iter().map(|colony| colony.score).sum(),
+            colonies.iter().map(|colony| colony.deaths).sum(),
+        );
+        let colonies = if colonies.len() > 1 {
+            colonies
+                .iter()
+                .map(|colony| result(Some(colony.id), colony.score, colony.deaths))
+                .collect()
+        } else {
+            Vec::new()
+        };
+        RunScores {
+            map: manager.map_fingerprint(),
+            width: manager.grid().get_width(),
+            height: manager.grid().get_height(),
+            run,
+            colonies,
+        }
+    }
+}
+
+/// Partie inscrite au classement
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    #[serde(flatten)]
+    pub result: RunResult,
+    /// Date UTC de l'inscription, au format RFC 3339
+    pub recorded_at: String,
+}
+
+/// Classements d'une carte
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct MapScores {
+    pub width: u32,
+    pub height: u32,
+    /// Parties entières
+    #[serde(default)]
+    pub runs: Vec<ScoreEntry>,
+    /// Colonies des parties à plusieurs colonies
+    #[serde(default)]
+    pub colonies: Vec<ScoreEntry>,
+}
+
+impl MapScores {
+    /// Parties entières, de la meilleure à la moins bonne selon `rule`
+    pub fn ranked_runs(&self, rule: &ScoreRule) -> Vec<&ScoreEntry> {
+        ranked(&self.runs, rule)
+    }
+
+    /// Colonies, de la meilleure à la moins bonne selon `rule`
+    pub fn ranked_colonies(&self, rule: &ScoreRule) -> Vec<&ScoreEntry> {
+        ranked(&self.colonies, rule)
+    }
+}
+
+/// Meilleurs scores de chaque carte, indexés par l'empreinte de la carte en hexadécimal
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Leaderboard {
+    #[serde(default)]
+    pub maps: BTreeMap<String, MapScores>,
+}
+
+impl Leaderboard {
+    /// Relit le classement de `path` ; un fichier absent donne un classement vide
+    pub fn load(path: &str) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| format!("Classement invalide {}: {}", path, e)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Leaderboard::default()),
+            Err(e) => Err(format!("Impossible de lire {}: {}", path, e)),
+        }
+    }
+
+    /// Écrit le classement dans `path` (écrit à côté puis renommé)
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Impossible de sérialiser le classement: {}", e))?;
+        let partial = format!("{}.tmp", path);
+        fs::write(&partial, json).map_err(|e| format!("Impossible d'écrire {}: {}", partial, e))?;
+        fs::rename(&partial, path).map_err(|e| format!("Impossible d'écrire {}: {}", path, e))
+    }
+
+    /// Classements de la carte d'empreinte `map`
+    pub fn map(&self, map: u64) -> Option<&MapScores> {
+        self.maps.get(&map_key(map))
+    }
+
+    /// Inscrit la partie et ses colonies au classement de sa carte, et renvoie le rang de la
+    /// partie selon `rule` (1 pour la meilleure). Un résultat déjà inscrit (même graine,
+    /// mêmes paramètres, même issue) ne l'est pas une seconde fois.
+    pub fn record(&mut self, scores: &RunScores, rule: &ScoreRule) -> usize {
+        let recorded_at = rfc3339(SystemTime::now());
+        let map = self.maps.entry(map_key(scores.map)).or_default();
+        map.width = scores.width;
+        map.height = scores.height;
+        insert(&mut map.runs, &scores.run, &recorded_at, rule);
+        for colony in &scores.colonies {
+            insert(&mut map.colonies, colony, &recorded_at, rule);
+        }
+        rank(&map.runs, &scores.run, rule)
+    }
+}
+
+/// Rang de `result` parmi `entries` selon `rule` : 1 + le nombre d'entrées au score
+/// strictement meilleur
+pub fn rank(entries: &[ScoreEntry], result: &RunResult, rule: &ScoreRule) -> usize {
+    let score = rule.score(result);
+    1 + entries
+        .iter()
+        .filter(|entry| rule.score(&entry.result) > score)
+        .count()
+}
+
+fn map_key(map: u64) -> String {
+    format!("{:016x}", map)
+}
+
+// Entrées triées par score décroissant ; à égalité, la plus ancienne d'abord
+fn ranked<'a>(entries: &'a [ScoreEntry], rule: &ScoreRule) -> Vec<&'a ScoreEntry> {
+    let mut ranked: Vec<&ScoreEntry> = entries.iter().collect();
+    ranked.sort_by(|a, b| rule.score(&b.result).total_cmp(&rule.score(&a.result)));
+    ranked
+}
+
+// Ajoute `result` s'il n'y est pas déjà, puis oublie les moins bonnes entrées au-delà de
+// `MAX_ENTRIES`
+fn insert(entries: &mut Vec<ScoreEntry>, result: &RunResult, recorded_at: &str, rule: &ScoreRule) {
+    if entries.iter().any(|entry| entry.result == *result) {
+        return;
+    }
+    entries.push(ScoreEntry {
+        result: result.clone(),
+        recorded_at: recorded_at.to_string(),
+    });
+    if entries.len() > MAX_ENTRIES {
+        entries.sort_by(|a, b| rule.score(&b.result).total_cmp(&rule.score(&a.result)));
+        entries.truncate(MAX_ENTRIES);
+    }
+}
@@ -0,0 +1,118 @@
+//! Perception locale des fourmis
+//!
+//! Une fourmi voit les cases à une distance de Manhattan d'au plus `scope` (les murs ne
+//! cachent rien). Ce qu'elle voit modifie son choix d'action : les déplacements vers une
+//! zone mortelle sont retirés du tirage, et une cible visible (nourriture en recherche, nid
+//! de sa colonie au retour) impose un pas glouton vers elle.
+
+use crate::ant::AntsMode;
+use crate::exploration::{target, MOVING_ACTIONS};
+use crate::grid::Grid;
+use crate::pheromone::Action;
+
+/// Ce qu'une fourmi perçoit depuis sa case
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Perception {
+    // Source de nourriture visible la plus proche
+    pub food: Option<(u32, u32)>,
+    // Case du nid de la colonie visible la plus proche
+    pub nest: Option<(u32, u32)>,
+    // Nombre de zones mortelles dans le champ de vision
+    pub hazards: u32,
+}
+
+impl Perception {
+    /// Cible à rejoindre dans ce mode, si elle est en vue
+    pub fn target(&self, mode: AntsMode) -> Option<(u32, u32)> {
+        match mode {
+            AntsMode::FINDING => self.food,
+            AntsMode::RETURNING => self.nest,
+        }
+    }
+}
+
+/// Cases à une distance de Manhattan comprise entre 1 et `scope` de (x, y), sur la carte
+pub fn visible_cells(grid: &Grid, (x, y): (u32, u32), scope: u32) -> Vec<(u32, u32)> {
+    let r = scope as i64;
+    let mut cells = Vec::new();
+    for dy in -r..=r {
+        for dx in -r..=r {
+            if (dx, dy) == (0, 0) || dx.abs() + dy.abs() > r {
+                continue;
+            }
+            let (cx, cy) = (x as i64 + dx, y as i64 + dy);
+            if cx < 0 || cy < 0 || cx >= grid.get_width() as i64 || cy >= grid.get_height() as i64 {
+                continue;
+            }
+            cells.push((cx as u32, cy as u32));
+        }
+    }
+    cells
+}
+
+/// Relève la nourriture, le nid de `colony` et les dangers dans un rayon `scope`
+pub fn sense(grid: &Grid, position: (u32, u32), scope: u32, colony: usize) -> Perception {
+    let mut perception = Perception::default();
+    let distance = |(cx, cy): (u32, u32)| cx.abs_diff(position.0) + cy.abs_diff(position.1);
+    let closer =
+        |current: Option<(u32, u32)>, cell| current.is_none_or(|c| distance(cell) < distance(c));
+    for cell in visible_cells(grid, position, scope) {
+        let (cx, cy) = cell;
+        if grid.is_lethal(cx, cy) {
+            perception.hazards += 1;
+        } else if grid.has_food(cx, cy) && closer(perception.food, cell) {
+            perception.food = Some(cell);
+        } else if grid.is_colony_nest(cx, cy, colony) && closer(perception.nest, cell) {
+            perception.nest = Some(cell);
+        }
+    }
+    perception
+}
+
+/// Pas qui rapproche le plus de `goal` sans mener dans une zone mortelle (un tunnel compte
+/// pour sa sortie) ; None si un mur barre toutes les approches
+pub fn step_toward(grid: &Grid, (x, y): (u32, u32), goal: (u32, u32)) -> Option<Action> {
+    let distance = |(cx, cy): (u32, u32)| cx.abs_diff(goal.0) + cy.abs_diff(goal.1);
+    let current = distance((x, y));
+    MOVING_ACTIONS
+        .iter()
+        .filter_map(|&action| {
+            let (nx, ny) = target(x, y, action);
+            if !grid.is_walkable(nx, ny) {
+                return None;
+            }
+            let arrival = grid.destination(nx, ny);
+            (!grid.is_lethal(arrival.0, arrival.1) && distance(arrival) < current)
+                .then_some((action, distance(arrival)))
+        })
+        .min_by_key(|&(_, d)| d)
+        .map(|(action, _)| action)
+}
+
+/// Retire d'une distribution les déplacements qui mènent dans une zone mortelle et
+/// renormalise ; la distribution est gardée telle quelle si tout est dangereux
+pub fn mask_hazards(
+    grid: &Grid,
+    (x, y): (u32, u32),
+    dist: Vec<(Action, f32)>,
+) -> Vec<(Action, f32)> {
+    let safe: Vec<(Action, f32)> = dist
+        .iter()
+        .copied()
+        .filter(|&(action, _)| {
+            let (nx, ny) = target(x, y, action);
+            if nx >= grid.get_width() || ny >= grid.get_height() {
+                return true;
+            }
+            let (dx, dy) = grid.destination(nx, ny);
+            !grid.is_lethal(dx, dy)
+        })
+        .collect();
+    let total: f32 = safe.iter().map(|(_, p)| p).sum();
+    if safe.is_empty() || total <= 0.0 {
+        return dist;
+    }
+    safe.into_iter()
+        .map(|(action, p)| (action, p / total))
+        .collect()
+}
@@ -0,0 +1,103 @@
+//! Sessions de la GUI : reprendre une démonstration exactement là où elle s'est arrêtée
+//!
+//! Une session rassemble dans un seul fichier la partie en cours (l'état d'un checkpoint et
+//! la timeline de rembobinage, position courante comprise), la seconde partie du mode
+//! comparaison et les réglages de l'interface : paramètres, options d'affichage, caméra et
+//! fourmi inspectée. Elle se rouvre après un redémarrage du programme, la partie en pause.
+//! Comme pour les checkpoints, la relecture d'un replay et l'enregistrement en cours n'en
+//! font pas partie.
+
+use crate::ants_game_manager::{AntsGameManager, Checkpoint};
+use crate::interface::InterfaceState;
+use crate::timeline::Timeline;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+
+// Version du format des sessions, à incrémenter à chaque changement incompatible
+const SESSION_VERSION: u32 = 4;
+
+/// Fichier proposé par défaut dans le menu Session, dans le dossier courant
+pub const DEFAULT_SESSION_FILE: &str = "session.antsession";
+
+#[derive(Serialize, Deserialize)]
+struct SessionFile {
+    version: u32,
+    interface: InterfaceState,
+    game: SavedGame,
+    rival: Option<SavedGame>,
+}
+
+// Une partie et sa timeline, que le checkpoint seul ne garde pas
+#[derive(Serialize, Deserialize)]
+struct SavedGame {
+    checkpoint: Checkpoint,
+    timeline: Timeline,
+}
+
+impl SavedGame {
+    fn capture(manager: &AntsGameManager) -> Result<Self, String> {
+        Ok(SavedGame {
+            checkpoint: manager.checkpoint()?,
+            timeline: manager.history().clone(),
+        })
+    }
+
+    fn resume(self) -> Result<AntsGameManager, String> {
+        let mut manager = AntsGameManager::from_checkpoint(self.checkpoint)?;
+        manager.history = self.timeline;
+        Ok(manager)
+    }
+}
+
+/// Session relue depuis un fichier, prête à être confiée à un nouveau worker
+pub(crate) struct Session {
+    pub interface: InterfaceState,
+    pub manager: AntsGameManager,
+    pub rival: Option<AntsGameManager>,
+}
+
+/// Enregistre la session dans `path` (écrit à côté puis renommé, comme les checkpoints)
+pub(crate) fn save(
+    path: &str,
+    interface: InterfaceState,
+    manager: &AntsGameManager,
+    rival: Option<&AntsGameManager>,
+) -> Result<(), String> {
+    let session = SessionFile {
+        version: SESSION_VERSION,
+        interface,
+        game: SavedGame::capture(manager)?,
+        rival: rival.map(SavedGame::capture).transpose()?,
+    };
+    let partial = format!("{}.tmp", path);
+    let file =
+        File::create(&partial).map_err(|e| format!("Impossible de créer {}: {}", partial, e))?;
+    bincode::serialize_into(BufWriter::new(file), &session)
+        .map_err(|e| format!("Impossible d'écrire la session {}: {}", partial, e))?;
+    fs::rename(&partial, path)
+        .map_err(|e| format!("Impossible d'écrire la session {}: {}", path, e))
+}
+
+/// Relit une session enregistrée par [`save`]
+pub(crate) fn load(path: &str) -> Result<Session, String> {
+    let file = File::open(path).map_err(|e| format!("Impossible de lire {}: {}", path, e))?;
+    let session: SessionFile = bincode::deserialize_from(BufReader::new(file))
+        .map_err(|e| format!("Session invalide {}: {}", path, e))?;
+    if session.version != SESSION_VERSION {
+        return Err(format!(
+            "Session {} en version {}, version {} attendue",
+            path, session.version, SESSION_VERSION
+        ));
+    }
+    let invalid = |e: String| format!("Session invalide {}: {}", path, e);
+    Ok(Session {
+        interface: session.interface,
+        manager: session.game.resume().map_err(invalid)?,
+        rival: session
+            .rival
+            .map(SavedGame::resume)
+            .transpose()
+            .map_err(invalid)?,
+    })
+}
@@ -0,0 +1,642 @@
+//! Simulation de la GUI hors du thread d'affichage
+//!
+//! L'interface confie son [`AntsGameManager`] à un worker et ne fait plus que dessiner la
+//! dernière image publiée : la simulation peut enchaîner des milliers de ticks par seconde
+//! sans faire chuter le rafraîchissement de la fenêtre. En natif le worker tourne sur son
+//! propre thread ; sur wasm32, faute de threads, la même boucle avance à chaque image.
+
+use crate::ant::{Ant, AntsType};
+use crate::ants_game_manager::{AntsGameManager, EndReason, GameStateSnapshot};
+use crate::cli_args::SimulationConfig;
+use crate::error::AntsError;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::experiment::{manifest_path, ArtifactKind};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::frame_render;
+use crate::interface::InterfaceState;
+use crate::meta_controller::ControllerDecision;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::metrics::StatsSeries;
+use crate::metrics::{StatsPoint, TrafficMap};
+use crate::predator::PredatorStats;
+use crate::runner::TickRate;
+use crate::scoring::RunScores;
+use crate::session;
+use crate::spawn_policy::SpawnStats;
+use crate::tile::TileType;
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::Arc;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Mutex;
+use web_time::{Duration, Instant};
+
+// Temps de calcul maximal entre deux publications (~60 images par seconde)
+#[cfg(not(target_arch = "wasm32"))]
+const FRAME_BUDGET: Duration = Duration::from_millis(16);
+// Sur wasm la boucle partage le thread de l'affichage, on lui laisse moins de temps
+#[cfg(target_arch = "wasm32")]
+const FRAME_BUDGET: Duration = Duration::from_millis(8);
+
+// Positions conservées dans l'historique de la fourmi suivie
+const TRAIL_LENGTH: usize = 64;
+
+/// Relecture automatique de la timeline, indépendante de la simulation en direct
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HistoryPlayback {
+    pub snapshots_per_second: u32,
+    /// Reprendre au premier snapshot une fois le dernier atteint
+    pub looping: bool,
+}
+
+/// Ordres envoyés par l'interface au worker
+pub enum WorkerCommand {
+    Start,
+    Pause,
+    /// Rythme des ticks
+    SetTickRate(TickRate),
+    /// Ticks joués à chaque pas (mode turbo)
+    SetTicksPerFrame(u32),
+    /// Jouer immédiatement ce nombre de ticks, sans attendre le délai ni le lancement
+    FastForward(u64),
+    /// Jouer quelques ticks d'un bloc, avant de publier l'image suivante (pas à pas)
+    Step(u32),
+    /// Réglages modifiés avant le lancement de la partie
+    SetConfig(Box<SimulationConfig>),
+    /// Revenir au snapshot le plus proche de ce tick (met la partie en pause)
+    Restore(usize),
+    /// Rejouer la timeline comme un film (met la simulation en pause)
+    PlayHistory(HistoryPlayback),
+    StopHistory,
+    /// Fourmi dont on enregistre les positions à chaque tick (`Ant::id`)
+    Track(Option<u32>),
+    /// Remplacer une case de la carte en cours de partie (mode Dieu), dans les deux parties
+    /// en mode comparaison
+    EditTile((u32, u32), TileType),
+    /// Figer la politique apprise en cours de partie, ou la libérer, dans les deux parties
+    /// en mode comparaison
+    SetFrozen(bool),
+    /// Seconde partie sur la même carte et avec la même graine, jouée tick pour tick avec
+    /// la première pour comparer deux réglages (`None` pour la retirer)
+    SetRival(Option<Box<SimulationConfig>>),
+    /// Seconde partie reprise d'une session enregistrée
+    ResumeRival(Box<AntsGameManager>),
+    /// Enregistrer la session dans ce fichier, avec les réglages de l'interface
+    SaveSession(String, Box<InterfaceState>),
+    /// Exporter les cartes d'un type de fourmi d'une colonie sous ce préfixe, avec leur
+    /// manifeste d'expérience
+    ExportTables {
+        colony: usize,
+        ant_type: AntsType,
+        prefix: String,
+    },
+    /// Exporter une courbe du panneau de statistiques en CSV et en PNG (les deux parties en
+    /// mode comparaison)
+    #[cfg(not(target_arch = "wasm32"))]
+    ExportChart(StatsSeries),
+}
+
+/// Image de la partie publiée par le worker : tout ce que l'interface dessine et affiche
+pub struct GameView {
+    pub state: GameStateSnapshot,
+    pub spawn_stats: SpawnStats,
+    pub predator_stats: PredatorStats,
+    pub starved_ants: u32,
+    pub stuck_ants: u32,
+    pub alpha: f32,
+    /// Epsilon en vigueur, relevé la nuit
+    pub epsilon: f32,
+    /// Le tick à jouer tombe la nuit du cycle jour/nuit
+    pub night: bool,
+    /// Politique figée : actions gloutonnes sans apprentissage
+    pub frozen: bool,
+    pub end_reason: Option<EndReason>,
+    pub winner: Option<usize>,
+    /// Empreinte de la carte de départ, qui désigne son classement de meilleurs scores
+    pub map: u64,
+    /// Résultats à inscrire aux meilleurs scores, une fois la partie terminée
+    pub scores: Option<RunScores>,
+    pub replaying: bool,
+    pub replay_finished: bool,
+    /// Plus ancien et plus récent tick de la timeline, `None` si elle est vide
+    pub timeline: Option<(usize, usize)>,
+    pub running: bool,
+    /// Relecture de la timeline en cours
+    pub history_playback: Option<HistoryPlayback>,
+    /// Ticks d'avance rapide restant à jouer
+    pub fast_forward: u64,
+    /// Dernières cases visitées par la fourmi suivie, de la plus ancienne à la plus récente
+    pub trail: Vec<(u32, u32)>,
+    /// Dernier ordre refusé par la simulation (modification de la carte, comparaison)
+    pub error: Option<String>,
+    /// Résultat du dernier enregistrement de session : fichier écrit, ou erreur
+    pub session_saved: Option<Result<String, String>>,
+    /// Courbes de suivi de l'apprentissage
+    pub stats: Vec<StatsPoint>,
+    /// Passages des fourmis sur chaque case
+    pub traffic: TrafficMap,
+    /// Décisions du méta-contrôleur
+    pub controller: Vec<ControllerDecision>,
+    /// Image de la seconde partie en mode comparaison
+    pub rival: Option<Box<GameView>>,
+}
+
+// État du worker, partagé par la version thread et la version wasm
+struct WorkerLoop {
+    manager: AntsGameManager,
+    rival: Option<AntsGameManager>,
+    running: bool,
+    tick_delay: Duration,
+    ticks_per_frame: u32,
+    fast_forward: u64,
+    history_playback: Option<HistoryPlayback>,
+    last_tick: Instant,
+    tracked: Option<u32>,
+    trail: VecDeque<(u32, u32)>,
+    error: Option<String>,
+    session_saved: Option<Result<String, String>>,
+}
+
+impl WorkerLoop {
+    fn new(manager: AntsGameManager) -> Self {
+        let tick_delay = Self::tick_delay(manager.config().tick_rate);
+        let ticks_per_frame = manager.config().ticks_per_frame;
+        WorkerLoop {
+            manager,
+            rival: None,
+            running: false,
+            tick_delay,
+            ticks_per_frame,
+            fast_forward: 0,
+            history_playback: None,
+            last_tick: Instant::now(),
+            tracked: None,
+            trail: VecDeque::new(),
+            error: None,
+            session_saved: None,
+        }
+    }
+
+    // Un délai nul calcule les ticks aussi vite que possible
+    fn tick_delay(rate: TickRate) -> Duration {
+        rate.interval().unwrap_or(Duration::ZERO)
+    }
+
+    fn apply(&mut self, command: WorkerCommand) {
+        match command {
+            WorkerCommand::Start => {
+                self.history_playback = None;
+                self.running = !self.finished();
+            }
+            WorkerCommand::Pause => self.running = false,
+            WorkerCommand::SetTickRate(rate) => self.tick_delay = Self::tick_delay(rate),
+            WorkerCommand::SetTicksPerFrame(ticks) => self.ticks_per_frame = ticks.max(1),
+            WorkerCommand::FastForward(ticks) => {
+                if !self.finished() {
+                    self.fast_forward += ticks;
+                }
+            }
+            WorkerCommand::SetConfig(config) => self.manager.set_config(*config),
+            WorkerCommand::Step(ticks) => {
+                for _ in 0..ticks {
+                    if self.finished() {
+                        break;
+                    }
+                    self.step();
+                }
+            }
+            WorkerCommand::Restore(tick) => {
+                self.running = false;
+                self.fast_forward = 0;
+                self.restore(tick);
+            }
+            WorkerCommand::PlayHistory(playback) => {
+                self.running = false;
+                self.fast_forward = 0;
+                // Depuis la fin de la timeline, la relecture repart du début
+                let current = self.manager.current_tick_index();
+                let first = self.manager.history().first_tick();
+                if self.history_playback.is_none()
+                    && self
+                        .manager
+                        .history()
+                        .last_tick()
+                        .is_none_or(|tick| tick <= current)
+                {
+                    if let Some(tick) = first {
+                        self.restore(tick);
+                    }
+                }
+                self.history_playback = Some(playback);
+            }
+            WorkerCommand::StopHistory => self.history_playback = None,
+            WorkerCommand::Track(ant) => {
+                self.tracked = ant;
+                self.trail.clear();
+                self.record_trail();
+            }
+            WorkerCommand::EditTile(position, tile_type) => {
+                let rival_edit = self
+                    .rival
+                    .as_mut()
+                    .map_or(Ok(()), |rival| rival.edit_tile(position, tile_type.clone()));
+                self.error = self
+                    .manager
+                    .edit_tile(position, tile_type)
+                    .and(rival_edit)
+                    .err()
+                    .map(|e| e.to_string());
+            }
+            WorkerCommand::SetFrozen(frozen) => {
+                let rival_frozen = self
+                    .rival
+                    .as_mut()
+                    .map_or(Ok(()), |rival| rival.set_frozen(frozen));
+                self.error = self
+                    .manager
+                    .set_frozen(frozen)
+                    .and(rival_frozen)
+                    .err()
+                    .map(|e| e.to_string());
+            }
+            WorkerCommand::SetRival(config) => {
+                self.rival = None;
+                if let Some(config) = config {
+                    match self.build_rival(*config) {
+                        Ok(rival) => self.rival = Some(rival),
+                        Err(e) => self.error = Some(e.to_string()),
+                    }
+                }
+            }
+            WorkerCommand::ResumeRival(rival) => self.rival = Some(*rival),
+            WorkerCommand::SaveSession(path, interface) => {
+                let saved = session::save(&path, *interface, &self.manager, self.rival.as_ref());
+                self.session_saved = Some(saved.map(|()| path));
+            }
+            WorkerCommand::ExportTables {
+                colony,
+                ant_type,
+                prefix,
+            } => match self.manager.export_tables(colony, ant_type, &prefix) {
+                Ok(files) => tracing::info!(
+                    tick = self.manager.current_tick_index() as u64,
+                    "Cerveau exporté : {}",
+                    files.join(", ")
+                ),
+                Err(e) => self.error = Some(e),
+            },
+            #[cfg(not(target_arch = "wasm32"))]
+            WorkerCommand::ExportChart(series) => match self.export_chart(series) {
+                Ok(files) => tracing::info!(
+                    tick = self.manager.current_tick_index() as u64,
+                    "Courbe exportée : {}",
+                    files.join(", ")
+                ),
+                Err(e) => self.error = Some(e),
+            },
+        }
+    }
+
+    // Écrit `courbe_<nom>.csv` et `courbe_<nom>.png` dans le dossier courant, avec leur
+    // manifeste, et renvoie les fichiers écrits
+    #[cfg(not(target_arch = "wasm32"))]
+    fn export_chart(&self, series: StatsSeries) -> Result<Vec<String>, String> {
+        let mut runs = vec![self.manager.stats_history()];
+        runs.extend(self.rival.as_ref().map(AntsGameManager::stats_history));
+        let prefix = format!("courbe_{}", series.name());
+
+        let csv = format!("{}.csv", prefix);
+        std::fs::write(&csv, series.to_csv(&runs))
+            .map_err(|e| format!("Impossible d'écrire {}: {}", csv, e))?;
+        let png = format!("{}.png", prefix);
+        let lines: Vec<Vec<[f64; 2]>> = runs.iter().map(|stats| series.points(stats)).collect();
+        frame_render::render_chart(&lines)
+            .save(&png)
+            .map_err(|e| format!("Impossible d'écrire {}: {}", png, e))?;
+
+        let files = vec![csv, png];
+        self.manager
+            .manifest(ArtifactKind::Chart, files.clone())
+            .write(&manifest_path(&prefix))?;
+        Ok(files)
+    }
+
+    // Même carte, même population de départ et même graine que la partie principale :
+    // seuls les réglages diffèrent
+    fn build_rival(&self, config: SimulationConfig) -> Result<AntsGameManager, AntsError> {
+        let config = SimulationConfig {
+            seed: Some(self.manager.seed()),
+            output_file: None,
+            save_brain: None,
+            record_file: None,
+            ..config
+        };
+        let ants = Ant::colony_from_config(&config);
+        AntsGameManager::with_grid(self.manager.grid().clone(), ants, config)
+    }
+
+    fn game_over(manager: &AntsGameManager) -> bool {
+        manager.replay_finished() || manager.is_game_finished()
+    }
+
+    // En mode comparaison, on continue tant qu'une des deux parties n'est pas terminée
+    fn finished(&self) -> bool {
+        Self::game_over(&self.manager) && self.rival.as_ref().is_none_or(Self::game_over)
+    }
+
+    // Ramène les deux parties au snapshot le plus proche de `tick`
+    fn restore(&mut self, tick: usize) {
+        self.manager.restore_snapshot(tick);
+        if let Some(rival) = &mut self.rival {
+            rival.restore_snapshot(tick);
+        }
+        // Le passé de la fourmi ne correspond plus à la timeline restaurée
+        self.trail.clear();
+        self.record_trail();
+    }
+
+    // Il reste des ticks (ou des snapshots) à jouer sans attendre d'ordre
+    fn busy(&self) -> bool {
+        self.running || self.fast_forward > 0 || self.history_playback.is_some()
+    }
+
+    // Passe au snapshot suivant de la timeline, ou arrête la relecture au dernier
+    fn show_next_snapshot(&mut self, looping: bool) {
+        let current = self.manager.current_tick_index();
+        let history = self.manager.history();
+        let next = history
+            .ticks()
+            .find(|&tick| tick > current)
+            .or(history.first_tick().filter(|_| looping));
+        match next {
+            Some(tick) => self.restore(tick),
+            None => self.history_playback = None,
+        }
+    }
+
+    // Ajoute la case de la fourmi suivie si elle a bougé
+    fn record_trail(&mut self) {
+        let position = self
+            .tracked
+            .and_then(|id| self.manager.ant_by_id(id))
+            .and_then(|ant| ant.position);
+        if let Some(position) = position {
+            if self.trail.back() != Some(&position) {
+                if self.trail.len() == TRAIL_LENGTH {
+                    self.trail.pop_front();
+                }
+                self.trail.push_back(position);
+            }
+        }
+    }
+
+    fn step(&mut self) {
+        if !Self::game_over(&self.manager) {
+            self.manager.game_step();
+        }
+        if let Some(rival) = self.rival.as_mut().filter(|rival| !Self::game_over(rival)) {
+            rival.game_step();
+        }
+        self.record_trail();
+        if self.finished() {
+            self.running = false;
+            self.fast_forward = 0;
+        }
+    }
+
+    // Joue des ticks pendant au plus `budget`, renvoie le temps à attendre avant le suivant
+    fn advance(&mut self, budget: Duration) -> Duration {
+        let start = Instant::now();
+        while self.busy() && start.elapsed() < budget {
+            // La relecture de la timeline montre un snapshot par image, à son propre rythme
+            if let Some(playback) = self.history_playback {
+                let now = Instant::now();
+                let next_frame = self.last_tick
+                    + Duration::from_secs_f64(1.0 / playback.snapshots_per_second.max(1) as f64);
+                if now < next_frame {
+                    return next_frame - now;
+                }
+                self.last_tick = now;
+                self.show_next_snapshot(playback.looping);
+                continue;
+            }
+            // L'avance rapide passe avant le rythme normal et ignore le délai
+            if self.fast_forward > 0 {
+                self.fast_forward -= 1;
+                self.step();
+                continue;
+            }
+            let now = Instant::now();
+            let next_tick = self.last_tick + self.tick_delay;
+            if now < next_tick {
+                return next_tick - now;
+            }
+            for _ in 0..self.ticks_per_frame {
+                if !self.running {
+                    break;
+                }
+                self.step();
+            }
+            self.last_tick = now;
+        }
+        Duration::ZERO
+    }
+
+    fn view(&self) -> GameView {
+        GameView {
+            running: self.running,
+            history_playback: self.history_playback,
+            fast_forward: self.fast_forward,
+            trail: self.trail.iter().copied().collect(),
+            error: self.error.clone(),
+            session_saved: self.session_saved.clone(),
+            rival: self
+                .rival
+                .as_ref()
+                .map(|rival| Box::new(Self::manager_view(rival))),
+            ..Self::manager_view(&self.manager)
+        }
+    }
+
+    // Ce que l'on sait dessiner d'une partie, sans l'état propre au worker
+    fn manager_view(manager: &AntsGameManager) -> GameView {
+        let history = manager.history();
+        let current = manager.current_tick_index();
+        GameView {
+            state: GameStateSnapshot {
+                tick: current,
+                grid: manager.grid().clone(),
+                ants: manager.ants().to_vec(),
+                colonies: manager.colonies().to_vec(),
+                predators: manager.predators().to_vec(),
+            },
+            spawn_stats: manager.spawn_stats().clone(),
+            predator_stats: manager.predator_stats().clone(),
+            starved_ants: manager.starved_ants(),
+            stuck_ants: manager.stuck_ants(),
+            alpha: manager.rl_params().alpha,
+            epsilon: manager
+                .config()
+                .epsilon_at(manager.rl_params().epsilon, current),
+            night: manager.is_night(),
+            frozen: manager.config().freeze,
+            end_reason: manager.end_reason(),
+            winner: manager.winner(),
+            map: manager.map_fingerprint(),
+            scores: manager
+                .end_reason()
+                .map(|reason| RunScores::of(manager, Some(reason.name()))),
+            replaying: manager.is_replaying(),
+            replay_finished: manager.replay_finished(),
+            timeline: history
+                .first_tick()
+                .zip(history.last_tick())
+                .map(|(first, last)| (first, current.max(last))),
+            running: false,
+            history_playback: None,
+            fast_forward: 0,
+            trail: Vec::new(),
+            error: None,
+            session_saved: None,
+            stats: manager.stats_history().to_vec(),
+            traffic: manager.traffic().clone(),
+            controller: manager.controller_decisions().to_vec(),
+            rival: None,
+        }
+    }
+}
+
+/// Poignée de l'interface sur la simulation en cours
+pub struct SimulationWorker {
+    #[cfg(not(target_arch = "wasm32"))]
+    // Fermer le canal (à la destruction) arrête le thread
+    commands: Option<std::sync::mpsc::Sender<WorkerCommand>>,
+    // Dernière image publiée par le thread et pas encore reprise : une image plus récente
+    // remplace la précédente, la mémoire ne croît pas quand l'interface ne dessine plus
+    #[cfg(not(target_arch = "wasm32"))]
+    updates: Arc<Mutex<Option<GameView>>>,
+    #[cfg(not(target_arch = "wasm32"))]
+    thread: Option<std::thread::JoinHandle<()>>,
+
+    #[cfg(target_arch = "wasm32")]
+    inner: WorkerLoop,
+    #[cfg(target_arch = "wasm32")]
+    ctx: egui::Context,
+
+    view: Arc<GameView>,
+}
+
+impl SimulationWorker {
+    /// Prend en charge la partie, en pause jusqu'à l'ordre [`WorkerCommand::Start`]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spawn(manager: AntsGameManager, ctx: egui::Context) -> Self {
+        use std::sync::mpsc;
+
+        let inner = WorkerLoop::new(manager);
+        let view = Arc::new(inner.view());
+        let (commands, command_rx) = mpsc::channel();
+        let updates = Arc::new(Mutex::new(None));
+        let published = Arc::clone(&updates);
+        let thread = std::thread::Builder::new()
+            .name("simulation".to_string())
+            .spawn(move || run(inner, command_rx, published, ctx))
+            .expect("impossible de démarrer le thread de simulation");
+        SimulationWorker {
+            commands: Some(commands),
+            updates,
+            thread: Some(thread),
+            view,
+        }
+    }
+
+    /// Prend en charge la partie, en pause jusqu'à l'ordre [`WorkerCommand::Start`]
+    #[cfg(target_arch = "wasm32")]
+    pub fn spawn(manager: AntsGameManager, ctx: egui::Context) -> Self {
+        let inner = WorkerLoop::new(manager);
+        let view = Arc::new(inner.view());
+        SimulationWorker { inner, ctx, view }
+    }
+
+    pub fn send(&mut self, command: WorkerCommand) {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Some(commands) = &self.commands {
+                // Le thread ne s'arrête qu'à la destruction de la poignée
+                let _ = commands.send(command);
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.inner.apply(command);
+            self.view = Arc::new(self.inner.view());
+        }
+    }
+
+    /// Dernière image publiée par la simulation
+    pub fn view(&mut self) -> Arc<GameView> {
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            if let Some(view) = self.updates.lock().ok().and_then(|mut slot| slot.take()) {
+                self.view = Arc::new(view);
+            }
+        }
+        #[cfg(target_arch = "wasm32")]
+        {
+            if self.inner.busy() {
+                let wait = self.inner.advance(FRAME_BUDGET);
+                self.view = Arc::new(self.inner.view());
+                self.ctx.request_repaint_after(wait);
+            }
+        }
+        Arc::clone(&self.view)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Drop for SimulationWorker {
+    fn drop(&mut self) {
+        self.commands = None;
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+// Boucle du thread : calcule, publie une image, puis attend un ordre ou le prochain tick
+#[cfg(not(target_arch = "wasm32"))]
+fn run(
+    mut worker: WorkerLoop,
+    commands: std::sync::mpsc::Receiver<WorkerCommand>,
+    updates: Arc<Mutex<Option<GameView>>>,
+    ctx: egui::Context,
+) {
+    use std::sync::mpsc::RecvTimeoutError;
+
+    loop {
+        let wait = worker.advance(FRAME_BUDGET);
+        let view = worker.view();
+        match updates.lock() {
+            Ok(mut slot) => *slot = Some(view),
+            Err(_) => return,
+        }
+        ctx.request_repaint();
+
+        // En pause, rien à calculer : on dort jusqu'au prochain ordre
+        let received = if worker.busy() {
+            match commands.recv_timeout(wait) {
+                Ok(command) => Some(command),
+                Err(RecvTimeoutError::Timeout) => None,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        } else {
+            match commands.recv() {
+                Ok(command) => Some(command),
+                Err(_) => return,
+            }
+        };
+        for command in received.into_iter().chain(commands.try_iter()) {
+            worker.apply(command);
+        }
+    }
+}
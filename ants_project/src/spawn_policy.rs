@@ -0,0 +1,213 @@
+use crate::ant::AntsType;
+use crate::cli_args::SimulationConfig;
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+
+/// Règle de choix du type de fourmi que le nid fait sortir. Les minimums d'explorateurs et
+/// de récolteuses de la configuration passent avant toute règle (voir `choose_type`).
+pub trait SpawnPolicy: Send {
+    /// Type à déployer (None = n'importe quelle fourmi en attente) et raison du choix
+    fn choose(
+        &self,
+        state: &ColonyState,
+        config: &SimulationConfig,
+    ) -> (Option<AntsType>, SpawnReason);
+}
+
+/// Applique les minimums configurés, puis la règle `policy`
+pub fn choose_type(
+    policy: &dyn SpawnPolicy,
+    state: &ColonyState,
+    config: &SimulationConfig,
+) -> (Option<AntsType>, SpawnReason) {
+    if state.active_explorers < config.min_explorers_active {
+        return (Some(AntsType::EXPLORER), SpawnReason::MinimumExplorers);
+    }
+    if state.active_pickers < config.min_pickers_active {
+        return (Some(AntsType::PICKER), SpawnReason::MinimumPickers);
+    }
+    policy.choose(state, config)
+}
+
+/// Pas de préférence : la première fourmi en attente sort (règle par défaut)
+pub struct PriorityPolicy;
+
+impl SpawnPolicy for PriorityPolicy {
+    fn choose(&self, _: &ColonyState, _: &SimulationConfig) -> (Option<AntsType>, SpawnReason) {
+        (None, SpawnReason::Any)
+    }
+}
+
+/// Garde les fourmis déployées dans les proportions de `spawn_ratio` : le type le plus en
+/// retard sur sa part sort en premier
+pub struct ProportionalPolicy;
+
+impl SpawnPolicy for ProportionalPolicy {
+    fn choose(
+        &self,
+        state: &ColonyState,
+        config: &SimulationConfig,
+    ) -> (Option<AntsType>, SpawnReason) {
+        let behind = AntsType::all()
+            .into_iter()
+            .filter(|ant_type| config.spawn_ratio[ant_type.index()] > 0)
+            .min_by(|a, b| {
+                let share =
+                    |t: &AntsType| state.active(*t) as f32 / config.spawn_ratio[t.index()] as f32;
+                share(a).total_cmp(&share(b))
+            });
+        (behind, SpawnReason::Proportion)
+    }
+}
+
+/// Envoie des explorateurs pendant les `spawn_burst_ticks` premiers ticks, puis des récolteuses
+pub struct ExplorerBurstPolicy;
+
+impl SpawnPolicy for ExplorerBurstPolicy {
+    fn choose(
+        &self,
+        state: &ColonyState,
+        config: &SimulationConfig,
+    ) -> (Option<AntsType>, SpawnReason) {
+        if state.tick < config.spawn_burst_ticks as usize {
+            (Some(AntsType::EXPLORER), SpawnReason::Burst)
+        } else {
+            (Some(AntsType::PICKER), SpawnReason::Burst)
+        }
+    }
+}
+
+/// Décide selon l'état de la colonie : piste de nourriture forte => récolteuses,
+/// plus de livraisons => explorateurs
+pub struct DemandDrivenPolicy;
+
+impl SpawnPolicy for DemandDrivenPolicy {
+    fn choose(
+        &self,
+        state: &ColonyState,
+        config: &SimulationConfig,
+    ) -> (Option<AntsType>, SpawnReason) {
+        if state.ticks_since_delivery >= config.spawn_stall_ticks {
+            // Plus aucune livraison : il faut trouver de nouvelles sources
+            (Some(AntsType::EXPLORER), SpawnReason::Stalled)
+        } else if state.food_trail_strength >= config.spawn_trail_threshold {
+            // Une piste solide existe : il faut des bras pour l'exploiter
+            (Some(AntsType::PICKER), SpawnReason::StrongTrail)
+        } else {
+            (None, SpawnReason::Any)
+        }
+    }
+}
+
+// Stratégie intégrée choisie dans la configuration
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SpawnPolicyKind {
+    // Respecter les minimums d'explorateurs puis de récolteuses, sinon l'ordre de la liste
+    Priority,
+    // Proportions fixes entre types parmi les fourmis déployées
+    Proportional,
+    // Vague d'explorateurs au début de la partie, puis des récolteuses
+    ExplorerBurst,
+    // Décider selon l'état de la colonie (pistes fortes => récolteuses, stagnation => explorateurs)
+    DemandDriven,
+}
+
+impl SpawnPolicyKind {
+    pub fn all() -> impl Iterator<Item = SpawnPolicyKind> {
+        [
+            SpawnPolicyKind::Priority,
+            SpawnPolicyKind::Proportional,
+            SpawnPolicyKind::ExplorerBurst,
+            SpawnPolicyKind::DemandDriven,
+        ]
+        .iter()
+        .copied()
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "priority" => Some(SpawnPolicyKind::Priority),
+            "proportional" => Some(SpawnPolicyKind::Proportional),
+            "burst" => Some(SpawnPolicyKind::ExplorerBurst),
+            "demand" => Some(SpawnPolicyKind::DemandDriven),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SpawnPolicyKind::Priority => tr("spawn_policy.priority"),
+            SpawnPolicyKind::Proportional => tr("spawn_policy.proportional"),
+            SpawnPolicyKind::ExplorerBurst => tr("spawn_policy.burst"),
+            SpawnPolicyKind::DemandDriven => tr("spawn_policy.demand"),
+        }
+    }
+
+    /// Règle correspondante
+    pub fn policy(&self) -> &'static dyn SpawnPolicy {
+        match self {
+            SpawnPolicyKind::Priority => &PriorityPolicy,
+            SpawnPolicyKind::Proportional => &ProportionalPolicy,
+            SpawnPolicyKind::ExplorerBurst => &ExplorerBurstPolicy,
+            SpawnPolicyKind::DemandDriven => &DemandDrivenPolicy,
+        }
+    }
+}
+
+// Raison ayant motivé le choix du type de fourmi déployée
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpawnReason {
+    MinimumExplorers,
+    MinimumPickers,
+    StrongTrail,
+    Stalled,
+    Proportion,
+    Burst,
+    Any,
+}
+
+/// Vue de l'état de la colonie utilisée pour décider du prochain déploiement
+pub struct ColonyState {
+    pub tick: usize,
+    pub active_explorers: u32,
+    pub active_pickers: u32,
+    pub active_fighters: u32,
+    // Plus forte valeur des cartes de nourriture de la colonie
+    pub food_trail_strength: f32,
+    pub ticks_since_delivery: u32,
+}
+
+impl ColonyState {
+    pub fn active(&self, ant_type: AntsType) -> u32 {
+        match ant_type {
+            AntsType::EXPLORER => self.active_explorers,
+            AntsType::PICKER => self.active_pickers,
+            AntsType::FIGHTER => self.active_fighters,
+        }
+    }
+}
+
+// Compteurs de déploiements pour les statistiques
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct SpawnStats {
+    pub explorers_spawned: u32,
+    pub pickers_spawned: u32,
+    pub fighters_spawned: u32,
+    pub trail_driven: u32,
+    pub stall_driven: u32,
+}
+
+impl SpawnStats {
+    pub fn record(&mut self, ant_type: AntsType, reason: SpawnReason) {
+        match ant_type {
+            AntsType::EXPLORER => self.explorers_spawned += 1,
+            AntsType::PICKER => self.pickers_spawned += 1,
+            AntsType::FIGHTER => self.fighters_spawned += 1,
+        }
+        match reason {
+            SpawnReason::StrongTrail => self.trail_driven += 1,
+            SpawnReason::Stalled => self.stall_driven += 1,
+            _ => {}
+        }
+    }
+}
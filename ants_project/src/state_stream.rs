@@ -0,0 +1,372 @@
+//! Diffusion de l'état de la simulation par WebSocket
+//!
+//! `--serve-ws <PORT>` accepte des clients WebSocket (`ws://<hôte>:<PORT>/`) et leur envoie
+//! tous les `--ws-interval` ticks un message texte JSON ne contenant que ce qui a changé
+//! depuis le message précédent. Un client qui se connecte reçoit l'état complet
+//! (`"full": true`) au premier message qui suit sa connexion, puis les diffs comme les
+//! autres. Les messages des clients sont ignorés.
+//!
+//! ```json
+//! {"tick": 120, "full": false, "width": 20, "height": 20,
+//!  "ants": [[3, 5, 7, 1, true, 0]],
+//!  "gone": [4],
+//!  "tiles": [{"x": 5, "y": 8, "tile": {"FoodSource": {"amount": 12, "value": 1}}}],
+//!  "pheromones": [{"colony": 0, "max_food": 0.8, "max_nest": 1.2, "mean_q": 0.01}]}
+//! ```
+//!
+//! `ants` liste les fourmis qui ont bougé ou changé de mode : `[id, x, y, type, porte de
+//! la nourriture, colonie]`, avec le type 0 = exploratrice, 1 = récolteuse, 2 = combattante.
+//! `gone` liste celles qui ont quitté la carte (mortes ou rentrées au nid). Les fourmis sont
+//! désignées par leur identifiant (`Ant::id`), qui ne change pas quand d'autres meurent.
+
+use crate::ant::AntsMode;
+use crate::ants_game_manager::AntsGameManager;
+use crate::coord::Coord;
+use crate::tile::TileType;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+// Suffixe fixé par la RFC 6455 pour calculer la réponse à la poignée de main
+const WS_GUID: &str = "258EAFA5-E914-47DA-95CA-C5AB0DC85B11";
+
+// Un client trop lent est abandonné plutôt que de ralentir la simulation
+const WRITE_TIMEOUT: Duration = Duration::from_millis(200);
+
+// (id, x, y, type, porte de la nourriture, colonie)
+type AntRecord = (u32, u32, u32, usize, bool, usize);
+
+#[derive(Serialize)]
+struct TileChange<'a> {
+    x: u32,
+    y: u32,
+    tile: &'a TileType,
+}
+
+#[derive(Clone, Serialize)]
+struct PheromoneSummary {
+    colony: usize,
+    max_food: f32,
+    max_nest: f32,
+    mean_q: f32,
+}
+
+#[derive(Serialize)]
+struct StateMessage<'a> {
+    tick: usize,
+    full: bool,
+    width: u32,
+    height: u32,
+    ants: Vec<AntRecord>,
+    gone: Vec<u32>,
+    tiles: Vec<TileChange<'a>>,
+    pheromones: Vec<PheromoneSummary>,
+}
+
+/// Serveur WebSocket qui diffuse l'état de la partie à chaque appel de `publish`
+pub struct StateStreamer {
+    interval: usize,
+    // Clients dont la poignée de main est faite mais qui n'ont pas encore reçu l'état complet ;
+    // il ne leur est envoyé qu'avec un message, pour qu'il serve de base au diff suivant comme
+    // pour les autres clients
+    pending: Arc<Mutex<Vec<TcpStream>>>,
+    clients: Vec<TcpStream>,
+    // État envoyé au dernier message, base du prochain diff : fourmis sur la carte par id
+    last_ants: HashMap<u32, AntRecord>,
+    last_tiles: Vec<TileType>,
+}
+
+impl StateStreamer {
+    /// Écoute sur `port` (toutes les interfaces) ; `interval` ticks séparent deux messages
+    pub fn serve(port: u16, interval: u32) -> Result<Self, String> {
+        let listener = TcpListener::bind(("0.0.0.0", port))
+            .map_err(|e| format!("Impossible d'écouter sur le port {}: {}", port, e))?;
+        let pending = Arc::new(Mutex::new(Vec::new()));
+        let accepted = Arc::clone(&pending);
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                // Une poignée de main ratée ne concerne que ce client
+                if let Ok(stream) = handshake(stream) {
+                    lock(&accepted).push(stream);
+                }
+            }
+        });
+        Ok(StateStreamer {
+            interval: interval.max(1) as usize,
+            pending,
+            clients: Vec::new(),
+            last_ants: HashMap::new(),
+            last_tiles: Vec::new(),
+        })
+    }
+
+    /// Envoie le diff depuis le dernier message si le tick courant tombe sur l'intervalle, et
+    /// l'état complet aux clients arrivés depuis
+    pub fn publish(&mut self, manager: &AntsGameManager) {
+        let tick = manager.current_tick_index();
+        if !tick.is_multiple_of(self.interval) {
+            return;
+        }
+        let newcomers: Vec<TcpStream> = std::mem::take(&mut *lock(&self.pending));
+
+        let grid = manager.grid();
+        let shape = grid.shape();
+        let (width, height) = (shape.width, shape.height);
+        let ants: Vec<AntRecord> = manager
+            .ants()
+            .iter()
+            .filter_map(|ant| {
+                ant.position.map(|(x, y)| {
+                    let carrying = ant.mode == AntsMode::RETURNING;
+                    (ant.id, x, y, ant.ant_type.index(), carrying, ant.colony)
+                })
+            })
+            .collect();
+        let on_map: HashMap<u32, AntRecord> =
+            ants.iter().map(|record| (record.0, *record)).collect();
+        let tiles: Vec<TileType> = shape
+            .coords()
+            .filter_map(|coord| {
+                grid.get_tile(coord.into())
+                    .map(|tile| tile.tile_type.clone())
+            })
+            .collect();
+        let pheromones: Vec<PheromoneSummary> = manager
+            .colonies()
+            .iter()
+            .enumerate()
+            .map(|(colony, c)| PheromoneSummary {
+                colony,
+                max_food: c.q_table.mode_map(AntsMode::FINDING).max_value(),
+                max_nest: c.q_table.mode_map(AntsMode::RETURNING).max_value(),
+                mean_q: (c.q_table.mode_map(AntsMode::FINDING).mean_abs_q()
+                    + c.q_table.mode_map(AntsMode::RETURNING).mean_abs_q())
+                    / 2.0,
+            })
+            .collect();
+
+        let message = |full: bool, pheromones: Vec<PheromoneSummary>| {
+            let changed_ants: Vec<AntRecord> = ants
+                .iter()
+                .filter(|record| full || self.last_ants.get(&record.0) != Some(*record))
+                .copied()
+                .collect();
+            let mut gone: Vec<u32> = if full {
+                Vec::new()
+            } else {
+                self.last_ants
+                    .keys()
+                    .filter(|id| !on_map.contains_key(id))
+                    .copied()
+                    .collect()
+            };
+            gone.sort_unstable();
+            let tiles = tiles
+                .iter()
+                .enumerate()
+                .filter(|&(i, tile)| full || self.last_tiles.get(i) != Some(tile))
+                .map(|(i, tile)| {
+                    let Coord { x, y } = shape.coord_at(i);
+                    TileChange { x, y, tile }
+                })
+                .collect();
+            serde_json::to_string(&StateMessage {
+                tick,
+                full,
+                width,
+                height,
+                ants: changed_ants,
+                gone,
+                tiles,
+                pheromones,
+            })
+            .unwrap_or_default()
+        };
+
+        let diff = message(false, pheromones.clone());
+        // Un client qui ne suit plus (déconnecté ou trop lent) est retiré
+        self.clients
+            .retain_mut(|client| send_text(client, &diff).is_ok());
+        if !newcomers.is_empty() {
+            let full = message(true, pheromones);
+            self.clients.extend(
+                newcomers
+                    .into_iter()
+                    .filter_map(|mut client| send_text(&mut client, &full).ok().map(|_| client)),
+            );
+        }
+        self.last_ants = on_map;
+        self.last_tiles = tiles;
+    }
+}
+
+fn lock(pending: &Mutex<Vec<TcpStream>>) -> std::sync::MutexGuard<'_, Vec<TcpStream>> {
+    pending.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+// Poignée de main HTTP : répond à `Sec-WebSocket-Key` et passe la connexion en WebSocket
+fn handshake(stream: TcpStream) -> std::io::Result<TcpStream> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+    let mut reader = BufReader::new(&stream);
+    let mut key = None;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? <= 2 {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("sec-websocket-key") {
+                key = Some(value.trim().to_string());
+            }
+        }
+    }
+    let mut stream = stream;
+    let Some(key) = key else {
+        stream.write_all(b"HTTP/1.1 400 Bad Request\r\nConnection: close\r\n\r\n")?;
+        return Err(std::io::Error::other("requête sans Sec-WebSocket-Key"));
+    };
+    let accept = base64(&sha1(format!("{}{}", key, WS_GUID).as_bytes()));
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\n\
+         Sec-WebSocket-Accept: {}\r\n\r\n",
+        accept
+    )?;
+    stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    Ok(stream)
+}
+
+// Trame texte non masquée (le serveur ne masque jamais ses trames)
+fn send_text(stream: &mut TcpStream, text: &str) -> std::io::Result<()> {
+    let payload = text.as_bytes();
+    let mut frame = vec![0x81];
+    match payload.len() {
+        len if len < 126 => frame.push(len as u8),
+        len if len <= u16::MAX as usize => {
+            frame.push(126);
+            frame.extend_from_slice(&(len as u16).to_be_bytes());
+        }
+        len => {
+            frame.push(127);
+            frame.extend_from_slice(&(len as u64).to_be_bytes());
+        }
+    }
+    frame.extend_from_slice(payload);
+    stream.write_all(&frame)
+}
+
+// SHA-1, utilisé seulement par la poignée de main WebSocket
+fn sha1(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0];
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_be_bytes());
+
+    for block in message.chunks(64) {
+        let mut w = [0u32; 80];
+        for (i, word) in block.chunks(4).enumerate() {
+            w[i] = u32::from_be_bytes([word[0], word[1], word[2], word[3]]);
+        }
+        for i in 16..80 {
+            w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+        }
+        let [mut a, mut b, mut c, mut d, mut e] = h;
+        for (i, &word) in w.iter().enumerate() {
+            let (f, k) = match i {
+                0..=19 => ((b & c) | (!b & d), 0x5A827999),
+                20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+                40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+                _ => (b ^ c ^ d, 0xCA62C1D6),
+            };
+            let temp = a
+                .rotate_left(5)
+                .wrapping_add(f)
+                .wrapping_add(e)
+                .wrapping_add(k)
+                .wrapping_add(word);
+            e = d;
+            d = c;
+            c = b.rotate_left(30);
+            b = a;
+            a = temp;
+        }
+        for (value, add) in h.iter_mut().zip([a, b, c, d, e]) {
+            *value = value.wrapping_add(add);
+        }
+    }
+
+    let mut digest = [0u8; 20];
+    for (chunk, value) in digest.chunks_mut(4).zip(h) {
+        chunk.copy_from_slice(&value.to_be_bytes());
+    }
+    digest
+}
+
+fn base64(data: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in data.chunks(3) {
+        let bytes = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = u32::from_be_bytes([0, bytes[0], bytes[1], bytes[2]]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha1_matches_known_digests() {
+        let hex = |digest: [u8; 20]| -> String {
+            digest.iter().map(|byte| format!("{:02x}", byte)).collect()
+        };
+        assert_eq!(hex(sha1(b"")), "da39a3ee5e6b4b0d3255bfef95601890afd80709");
+        assert_eq!(
+            hex(sha1(b"abc")),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        // Deux blocs : le bourrage déborde du premier
+        assert_eq!(
+            hex(sha1(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+        );
+    }
+
+    #[test]
+    fn base64_pads_partial_groups() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn accept_key_matches_rfc_6455_sample() {
+        let key = "dGhlIHNhbXBsZSBub25jZQ==";
+        let accept = base64(&sha1(format!("{}{}", key, WS_GUID).as_bytes()));
+        assert_eq!(accept, "s3pPLMBiTxaQ9kYGzzhZRbK+xOo=");
+    }
+}
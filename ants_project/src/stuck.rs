@@ -0,0 +1,80 @@
+//! Détection des fourmis bloquées
+//!
+//! Une fourmi qui ne s'éloigne pas de plus de `stuck_radius` cases d'une case de référence
+//! pendant `stuck_ticks` ticks est bloquée : elle campe sur une case saturée, fait des
+//! allers-retours entre deux cases ou tourne en rond sur un petit cycle. Elle suit alors
+//! pendant `recovery_ticks` ticks la reprise choisie dans la configuration.
+
+use crate::i18n::tr;
+use serde::{Deserialize, Serialize};
+
+/// Ce que fait une fourmi bloquée pour se dégager
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StuckRecovery {
+    // Exploration epsilon-greedy avec au moins `stuck_epsilon`, pour cette fourmi seulement
+    Explore,
+    // Marche au hasard parmi les cases voisines praticables, sans consulter la Q-table
+    RandomWalk,
+    // Retour immédiat sur le nid le plus proche de sa colonie
+    Recall,
+}
+
+impl StuckRecovery {
+    pub fn all() -> impl Iterator<Item = StuckRecovery> {
+        [
+            StuckRecovery::Explore,
+            StuckRecovery::RandomWalk,
+            StuckRecovery::Recall,
+        ]
+        .iter()
+        .copied()
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "explore" => Some(StuckRecovery::Explore),
+            "random" => Some(StuckRecovery::RandomWalk),
+            "recall" => Some(StuckRecovery::Recall),
+            _ => None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StuckRecovery::Explore => tr("stuck.explore"),
+            StuckRecovery::RandomWalk => tr("stuck.random"),
+            StuckRecovery::Recall => tr("stuck.recall"),
+        }
+    }
+}
+
+/// Surveillance d'une fourmi : case de référence et ticks passés sans s'en éloigner
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StuckWatch {
+    anchor: Option<(u32, u32)>,
+    ticks: u32,
+}
+
+impl StuckWatch {
+    /// Note la position de la fourmi à la fin d'un tick. Vrai si elle est restée `limit`
+    /// ticks à `radius` cases au plus (distance de Manhattan) de sa case de référence ;
+    /// la surveillance repart alors de sa position.
+    pub fn observe(&mut self, (x, y): (u32, u32), radius: u32, limit: u32) -> bool {
+        let near = self
+            .anchor
+            .is_some_and(|(ax, ay)| x.abs_diff(ax) + y.abs_diff(ay) <= radius);
+        if near {
+            self.ticks += 1;
+            if self.ticks < limit {
+                return false;
+            }
+        }
+        self.anchor = Some((x, y));
+        self.ticks = 0;
+        near
+    }
+
+    pub fn reset(&mut self) {
+        *self = StuckWatch::default();
+    }
+}
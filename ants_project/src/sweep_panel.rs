@@ -0,0 +1,190 @@
+//! Méta-optimiseur de la GUI : balayage alpha/gamma/epsilon sur la carte affichée
+//!
+//! Le panneau règle les plages des trois paramètres, lance un [`Sweep`] en fond (simulations
+//! sans affichage, en parallèle) et dessine ses résultats au fil de l'eau : un point par
+//! couple (alpha, gamma), coloré par le meilleur score obtenu sur les valeurs d'epsilon. La
+//! meilleure combinaison peut ensuite être reprise dans les réglages de la partie.
+
+use crate::cli_args::SimulationConfig;
+use crate::grid::Grid;
+use crate::i18n::{tr, trf};
+use crate::optimizer::{Objective, Sweep, SweepPoint, SweepRange, SweepSettings};
+use eframe::egui;
+use web_time::Duration;
+
+// Rafraîchissement du panneau pendant un balayage
+const REFRESH: Duration = Duration::from_millis(250);
+
+/// État du panneau : réglages du prochain balayage et balayage en cours ou terminé
+#[derive(Default)]
+pub(crate) struct SweepPanel {
+    settings: SweepSettings,
+    sweep: Option<Sweep>,
+}
+
+impl SweepPanel {
+    /// `can_apply` : la partie n'est pas encore lancée et ses réglages sont modifiables
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        grid: &Grid,
+        config: &mut SimulationConfig,
+        can_apply: bool,
+    ) {
+        let running = self
+            .sweep
+            .as_ref()
+            .is_some_and(|sweep| !sweep.is_finished());
+        ui.add_enabled_ui(!running, |ui| self.show_settings(ui));
+
+        ui.horizontal(|ui| {
+            if running {
+                if ui.button(tr("sweep.stop")).clicked() {
+                    if let Some(sweep) = &self.sweep {
+                        sweep.cancel();
+                    }
+                }
+            } else if ui.button(tr("sweep.start")).clicked() {
+                self.sweep = Some(Sweep::start(grid.clone(), config.clone(), &self.settings));
+            }
+            if !running {
+                ui.label(trf(
+                    "sweep.simulations",
+                    &[&self.settings.simulation_count()],
+                ));
+            }
+        });
+
+        let Some(sweep) = &self.sweep else {
+            return;
+        };
+        let (done, total) = sweep.progress();
+        ui.add(
+            egui::ProgressBar::new(done as f32 / total.max(1) as f32)
+                .text(format!("{} / {}", done, total)),
+        );
+        if running {
+            ui.ctx().request_repaint_after(REFRESH);
+        }
+
+        let points = sweep.points();
+        Self::show_plot(ui, &points, sweep.objective());
+        if let Some(best) = sweep.best() {
+            ui.label(trf(
+                "sweep.best",
+                &[
+                    &best.alpha,
+                    &best.gamma,
+                    &best.epsilon,
+                    &sweep.objective().format(best.median),
+                ],
+            ));
+            if ui
+                .add_enabled(can_apply, egui::Button::new(tr("sweep.apply")))
+                .on_disabled_hover_text(tr("sweep.apply_locked"))
+                .clicked()
+            {
+                config.alpha = best.alpha;
+                config.gamma = best.gamma;
+                config.epsilon = best.epsilon;
+            }
+        }
+    }
+
+    fn show_settings(&mut self, ui: &mut egui::Ui) {
+        let settings = &mut self.settings;
+        egui::Grid::new("sweep_ranges").show(ui, |ui| {
+            ui.label("");
+            ui.label(tr("sweep.min"));
+            ui.label(tr("sweep.max"));
+            ui.label(tr("sweep.steps"));
+            ui.end_row();
+            for (name, range) in [
+                ("alpha", &mut settings.alpha),
+                ("gamma", &mut settings.gamma),
+                ("epsilon", &mut settings.epsilon),
+            ] {
+                Self::show_range(ui, name, range);
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("sweep.repeats"));
+            ui.add(egui::DragValue::new(&mut settings.repeats).range(1..=10));
+            ui.label(tr("sweep.ticks"));
+            ui.add(
+                egui::DragValue::new(&mut settings.max_ticks)
+                    .range(100..=100_000)
+                    .speed(100),
+            );
+        });
+        egui::ComboBox::from_id_salt("sweep_objective")
+            .selected_text(settings.objective.label())
+            .show_ui(ui, |ui| {
+                for objective in Objective::ALL {
+                    ui.selectable_value(&mut settings.objective, objective, objective.label());
+                }
+            });
+    }
+
+    fn show_range(ui: &mut egui::Ui, name: &str, range: &mut SweepRange) {
+        ui.label(name);
+        ui.add(
+            egui::DragValue::new(&mut range.min)
+                .range(0.0..=1.0)
+                .speed(0.01),
+        );
+        ui.add(
+            egui::DragValue::new(&mut range.max)
+                .range(range.min..=1.0)
+                .speed(0.01),
+        );
+        ui.add(egui::DragValue::new(&mut range.steps).range(1..=10));
+        ui.end_row();
+    }
+
+    // Un point par couple (alpha, gamma), du bleu (pire score) au jaune (meilleur)
+    fn show_plot(ui: &mut egui::Ui, points: &[SweepPoint], objective: Objective) {
+        let mut cells: Vec<SweepPoint> = Vec::new();
+        for point in points {
+            let same_cell = cells
+                .iter_mut()
+                .find(|cell| cell.alpha == point.alpha && cell.gamma == point.gamma);
+            match same_cell {
+                Some(cell) if objective.score(point.median) < objective.score(cell.median) => {
+                    *cell = *point
+                }
+                Some(_) => {}
+                None => cells.push(*point),
+            }
+        }
+        let scores = cells.iter().map(|cell| objective.score(cell.median));
+        let best = scores.clone().fold(f64::INFINITY, f64::min);
+        let worst = scores.fold(f64::NEG_INFINITY, f64::max);
+
+        egui_plot::Plot::new("sweep_plot")
+            .height(200.0)
+            .x_axis_label("alpha")
+            .y_axis_label("gamma")
+            .allow_scroll(false)
+            .show(ui, |plot_ui| {
+                for cell in &cells {
+                    let ratio = if worst > best {
+                        ((worst - objective.score(cell.median)) / (worst - best)) as f32
+                    } else {
+                        1.0
+                    };
+                    let color = egui::Color32::from_rgb(
+                        (60.0 + ratio * 195.0) as u8,
+                        (110.0 + ratio * 120.0) as u8,
+                        (255.0 - ratio * 255.0) as u8,
+                    );
+                    let name = format!("ε={} : {}", cell.epsilon, objective.format(cell.median));
+                    plot_ui.points(
+                        egui_plot::Points::new(name, vec![[cell.alpha as f64, cell.gamma as f64]])
+                            .radius(6.0)
+                            .color(color),
+                    );
+                }
+            });
+    }
+}
@@ -0,0 +1,247 @@
+//! Thèmes de rendu du plateau et de l'éditeur de carte
+//!
+//! Les couleurs du plateau (cases, pistes, fourmis, prédateurs), de la palette de l'éditeur
+//! et des légendes viennent toutes de la [`Palette`] du thème courant. En plus du thème
+//! d'origine : une palette sûre pour les daltoniens (couleurs d'Okabe et Ito, pistes
+//! bleue et orange au lieu de marron et rose), un contraste renforcé pour les projecteurs
+//! et des niveaux de gris pour l'impression. Comme la langue, le thème est global : il se
+//! change dans le menu « Réglages », qui le garde d'une session à l'autre.
+
+use eframe::egui::Color32;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// Thème de rendu de la GUI
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RenderTheme {
+    #[default]
+    Default,
+    ColorblindSafe,
+    HighContrast,
+    Grayscale,
+}
+
+impl RenderTheme {
+    pub fn all() -> [RenderTheme; 4] {
+        [
+            RenderTheme::Default,
+            RenderTheme::ColorblindSafe,
+            RenderTheme::HighContrast,
+            RenderTheme::Grayscale,
+        ]
+    }
+
+    /// Clé de traduction du nom du thème
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            RenderTheme::Default => "theme.default",
+            RenderTheme::ColorblindSafe => "theme.colorblind",
+            RenderTheme::HighContrast => "theme.high_contrast",
+            RenderTheme::Grayscale => "theme.grayscale",
+        }
+    }
+
+    pub fn palette(&self) -> &'static Palette {
+        match self {
+            RenderTheme::Default => &DEFAULT,
+            RenderTheme::ColorblindSafe => &COLORBLIND_SAFE,
+            RenderTheme::HighContrast => &HIGH_CONTRAST,
+            RenderTheme::Grayscale => &GRAYSCALE,
+        }
+    }
+}
+
+/// Couleurs d'un thème
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Palette {
+    /// Fond du plateau et cases vides
+    pub background: Color32,
+    pub wall: Color32,
+    pub death_zone: Color32,
+    pub mud: Color32,
+    /// Entrée d'un tunnel et flèche vers sa sortie
+    pub tunnel: Color32,
+    pub food: Color32,
+    /// Source plus nourrissante que le sucre (protéine)
+    pub rich_food: Color32,
+    pub corpse: Color32,
+    /// Contour des sources et texte de leur quantité
+    pub food_outline: Color32,
+    /// Piste vers la nourriture (fourmis en recherche) et piste vers le nid
+    pub food_trail: Color32,
+    pub nest_trail: Color32,
+    pub traffic: Color32,
+    pub fear: Color32,
+    /// Fourmi qui cherche, fourmi qui rentre, et pastille de sa charge
+    pub ant_finding: Color32,
+    pub ant_returning: Color32,
+    pub ant_load: Color32,
+    /// Contour des fourmis de la colonie A (les autres prennent la couleur de leur colonie)
+    pub ant_outline: Color32,
+    /// Nids et contours des colonies A, B, puis suivantes
+    pub colonies: [Color32; 3],
+    /// Texte des réserves posé sur les nids
+    pub nest_text: Color32,
+    pub predator: Color32,
+    /// Fourmi inspectée, son trajet et son champ de vision
+    pub highlight: Color32,
+    /// Cases de l'éditeur qui se distinguent du plateau pour rester lisibles en petit
+    pub editor_empty: Color32,
+    pub editor_death_zone: Color32,
+    pub editor_mud: Color32,
+    pub editor_tunnel: Color32,
+}
+
+const DEFAULT: Palette = Palette {
+    background: Color32::from_gray(30),
+    wall: Color32::GRAY,
+    death_zone: Color32::from_rgb(50, 0, 0),
+    mud: Color32::from_rgb(80, 55, 30),
+    tunnel: Color32::from_rgb(170, 90, 220),
+    food: Color32::GREEN,
+    rich_food: Color32::from_rgb(230, 140, 40),
+    corpse: Color32::from_rgb(120, 80, 50),
+    food_outline: Color32::WHITE,
+    food_trail: Color32::from_rgb(139, 69, 19),
+    nest_trail: Color32::from_rgb(255, 105, 180),
+    traffic: Color32::from_rgb(0, 200, 255),
+    // Plus sombre que le violet des tunnels
+    fear: Color32::from_rgb(148, 0, 211),
+    ant_finding: Color32::WHITE,
+    ant_returning: Color32::YELLOW,
+    ant_load: Color32::GREEN,
+    ant_outline: Color32::BLACK,
+    colonies: [
+        Color32::GOLD,
+        Color32::from_rgb(255, 140, 0),
+        Color32::from_rgb(100, 149, 237),
+    ],
+    nest_text: Color32::BLACK,
+    predator: Color32::RED,
+    highlight: Color32::from_rgb(0, 200, 255),
+    editor_empty: Color32::from_gray(40),
+    editor_death_zone: Color32::from_rgb(139, 0, 0),
+    editor_mud: Color32::from_rgb(110, 75, 40),
+    editor_tunnel: Color32::from_rgb(150, 80, 200),
+};
+
+// Couleurs d'Okabe et Ito : aucune paire n'est confondue par les protanopes, deutéranopes
+// ou tritanopes. Pistes bleue et orange, fourmis blanches (recherche) ou bleu ciel (retour).
+const COLORBLIND_SAFE: Palette = Palette {
+    background: Color32::from_gray(30),
+    wall: Color32::from_gray(150),
+    death_zone: Color32::from_rgb(90, 30, 65),
+    mud: Color32::from_rgb(85, 70, 45),
+    tunnel: Color32::from_rgb(204, 121, 167),
+    food: Color32::from_rgb(0, 158, 115),
+    rich_food: Color32::from_rgb(240, 228, 66),
+    corpse: Color32::from_rgb(120, 95, 70),
+    food_outline: Color32::WHITE,
+    food_trail: Color32::from_rgb(0, 114, 178),
+    nest_trail: Color32::from_rgb(230, 159, 0),
+    traffic: Color32::from_rgb(240, 228, 66),
+    fear: Color32::from_rgb(204, 121, 167),
+    ant_finding: Color32::WHITE,
+    ant_returning: Color32::from_rgb(86, 180, 233),
+    ant_load: Color32::from_rgb(0, 158, 115),
+    ant_outline: Color32::BLACK,
+    colonies: [
+        Color32::from_rgb(240, 228, 66),
+        Color32::from_rgb(213, 94, 0),
+        Color32::from_rgb(86, 180, 233),
+    ],
+    nest_text: Color32::BLACK,
+    predator: Color32::from_rgb(213, 94, 0),
+    highlight: Color32::from_rgb(86, 180, 233),
+    editor_empty: Color32::from_gray(40),
+    editor_death_zone: Color32::from_rgb(136, 46, 114),
+    editor_mud: Color32::from_rgb(120, 100, 60),
+    editor_tunnel: Color32::from_rgb(204, 121, 167),
+};
+
+// Fond noir et couleurs saturées, pour un projecteur délavé ou une salle éclairée
+const HIGH_CONTRAST: Palette = Palette {
+    background: Color32::BLACK,
+    wall: Color32::WHITE,
+    death_zone: Color32::from_rgb(140, 0, 0),
+    mud: Color32::from_rgb(130, 90, 0),
+    tunnel: Color32::from_rgb(255, 0, 255),
+    food: Color32::from_rgb(0, 255, 0),
+    rich_food: Color32::from_rgb(255, 160, 0),
+    corpse: Color32::from_rgb(170, 110, 60),
+    food_outline: Color32::WHITE,
+    food_trail: Color32::from_rgb(255, 120, 0),
+    nest_trail: Color32::from_rgb(0, 220, 255),
+    traffic: Color32::from_rgb(255, 255, 0),
+    fear: Color32::from_rgb(255, 0, 255),
+    ant_finding: Color32::WHITE,
+    ant_returning: Color32::from_rgb(255, 0, 255),
+    ant_load: Color32::from_rgb(0, 255, 0),
+    ant_outline: Color32::BLACK,
+    colonies: [
+        Color32::from_rgb(255, 255, 0),
+        Color32::from_rgb(255, 80, 0),
+        Color32::from_rgb(0, 200, 255),
+    ],
+    nest_text: Color32::BLACK,
+    predator: Color32::RED,
+    highlight: Color32::from_rgb(0, 255, 255),
+    editor_empty: Color32::from_gray(15),
+    editor_death_zone: Color32::from_rgb(200, 0, 0),
+    editor_mud: Color32::from_rgb(150, 100, 0),
+    editor_tunnel: Color32::from_rgb(255, 0, 255),
+};
+
+// Fond clair et niveaux de gris, lisibles sur une capture imprimée en noir et blanc : les
+// deux pistes se distinguent par leur intensité
+const GRAYSCALE: Palette = Palette {
+    background: Color32::from_gray(235),
+    wall: Color32::from_gray(90),
+    death_zone: Color32::from_gray(20),
+    mud: Color32::from_gray(175),
+    tunnel: Color32::from_gray(120),
+    food: Color32::from_gray(60),
+    rich_food: Color32::from_gray(110),
+    corpse: Color32::from_gray(150),
+    food_outline: Color32::WHITE,
+    food_trail: Color32::BLACK,
+    nest_trail: Color32::from_gray(140),
+    traffic: Color32::from_gray(70),
+    fear: Color32::from_gray(100),
+    ant_finding: Color32::WHITE,
+    ant_returning: Color32::from_gray(60),
+    ant_load: Color32::from_gray(160),
+    ant_outline: Color32::BLACK,
+    colonies: [
+        Color32::from_gray(200),
+        Color32::from_gray(150),
+        Color32::from_gray(110),
+    ],
+    nest_text: Color32::BLACK,
+    predator: Color32::BLACK,
+    highlight: Color32::BLACK,
+    editor_empty: Color32::from_gray(225),
+    editor_death_zone: Color32::from_gray(20),
+    editor_mud: Color32::from_gray(175),
+    editor_tunnel: Color32::from_gray(120),
+};
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// Thème courant de la GUI
+pub fn theme() -> RenderTheme {
+    RenderTheme::all()
+        .get(CURRENT.load(Ordering::Relaxed) as usize)
+        .copied()
+        .unwrap_or_default()
+}
+
+pub fn set_theme(theme: RenderTheme) {
+    CURRENT.store(theme as u8, Ordering::Relaxed);
+}
+
+/// Couleurs du thème courant
+pub fn palette() -> &'static Palette {
+    theme().palette()
+}
@@ -1,8 +1,26 @@
-#[derive(Clone, Debug, PartialEq)]
+use crate::ant::AntsType;
+use serde::{Deserialize, Serialize};
+
+// Ralentissement de la boue posée sans réglage particulier (éditeur, mode Dieu)
+pub const DEFAULT_MUD_SLOW_FACTOR: u32 = 3;
+
+/// Valeur par unité d'une source de sucre, la qualité ordinaire
+pub const SUGAR_VALUE: u32 = 1;
+/// Valeur par unité d'une source de protéines, plus rare et plus nourrissante
+pub const PROTEIN_VALUE: u32 = 5;
+
+// Valeur des sources (et des charges) enregistrées avant l'apparition des qualités
+pub(crate) fn default_food_value() -> u32 {
+    SUGAR_VALUE
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub enum TileType {
     Default,
     Wall,
     Nest {
+        // Colonie propriétaire du nid (0 = A, 1 = B, ...)
+        colony: usize,
         stored_food: u32,
         explorer_capacity: u32,
         picker_capacity: u32,
@@ -10,14 +28,41 @@ pub enum TileType {
     },
     FoodSource {
         amount: u32,
+        // Valeur de chaque unité ramassée : récompense et réserves du nid en sont multipliées
+        #[serde(default = "default_food_value")]
+        value: u32,
     },
     DeathZone,
+    /// Boue : une fourmi qui y entre attend `slow_factor` fois plus longtemps avant son
+    /// prochain déplacement
+    Mud {
+        slow_factor: u32,
+    },
+    /// Entrée de tunnel à sens unique : la fourmi qui y entre ressort directement en `exit`
+    Tunnel {
+        exit: (u32, u32),
+    },
 }
 
-#[derive(Clone, Debug, PartialEq)]
+/// Repousse d'une source de nourriture : `amount` unités tous les `interval` ticks,
+/// sans dépasser `cap`
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FoodRegrowth {
+    pub amount: u32,
+    pub interval: u32,
+    pub cap: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Tile {
     pub position: (u32, u32),
     pub tile_type: TileType,
+    // Repousse propre à cette source, à la place de celle de la configuration
+    #[serde(default)]
+    pub regrowth: Option<FoodRegrowth>,
+    // Cadavre de fourmi : la source perd une unité tous les `decay` ticks puis disparaît
+    #[serde(default)]
+    pub decay: Option<u32>,
 }
 
 impl Tile {
@@ -28,7 +73,10 @@ impl Tile {
                 position: (x, y),
                 tile_type: TileType::FoodSource {
                     amount: forced_amount,
+                    value: SUGAR_VALUE,
                 },
+                regrowth: None,
+                decay: None,
             };
         }
 
@@ -37,17 +85,95 @@ impl Tile {
         Tile {
             position: (x, y),
             tile_type,
+            regrowth: None,
+            decay: None,
+        }
+    }
+
+    /// Repousse effective de la case : la sienne, sinon `default` ; None hors des sources
+    /// de nourriture ou si la repousse est désactivée
+    pub fn food_regrowth(&self, default: Option<FoodRegrowth>) -> Option<FoodRegrowth> {
+        if !matches!(self.tile_type, TileType::FoodSource { .. }) || self.is_corpse() {
+            return None;
         }
+        self.regrowth
+            .or(default)
+            .filter(|rule| rule.amount > 0 && rule.interval > 0)
+    }
+
+    /// Source de nourriture temporaire laissée par une fourmi morte
+    pub fn is_corpse(&self) -> bool {
+        self.decay.is_some()
     }
 
     pub fn food_amount(&self) -> Option<u32> {
-        if let TileType::FoodSource { amount } = self.tile_type {
+        if let TileType::FoodSource { amount, .. } = self.tile_type {
             Some(amount)
         } else {
             None
         }
     }
 
+    /// Valeur par unité de la source de nourriture, None ailleurs
+    pub fn food_value(&self) -> Option<u32> {
+        if let TileType::FoodSource { value, .. } = self.tile_type {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    pub fn stored_food(&self) -> Option<u32> {
+        if let TileType::Nest { stored_food, .. } = self.tile_type {
+            Some(stored_food)
+        } else {
+            None
+        }
+    }
+
+    /// Nombre maximal de fourmis de ce type qu'un nid peut abriter
+    pub fn nest_capacity(&self, ant_type: AntsType) -> Option<u32> {
+        if let TileType::Nest {
+            explorer_capacity,
+            picker_capacity,
+            fighter_capacity,
+            ..
+        } = self.tile_type
+        {
+            Some(match ant_type {
+                AntsType::EXPLORER => explorer_capacity,
+                AntsType::PICKER => picker_capacity,
+                AntsType::FIGHTER => fighter_capacity,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn nest_colony(&self) -> Option<usize> {
+        if let TileType::Nest { colony, .. } = self.tile_type {
+            Some(colony)
+        } else {
+            None
+        }
+    }
+
+    pub fn slow_factor(&self) -> Option<u32> {
+        if let TileType::Mud { slow_factor } = self.tile_type {
+            Some(slow_factor)
+        } else {
+            None
+        }
+    }
+
+    pub fn tunnel_exit(&self) -> Option<(u32, u32)> {
+        if let TileType::Tunnel { exit } = self.tile_type {
+            Some(exit)
+        } else {
+            None
+        }
+    }
+
     pub fn is_walkable(&self) -> bool {
         !matches!(self.tile_type, TileType::Wall)
     }
@@ -57,7 +183,7 @@ impl Tile {
     }
 
     pub fn has_food(&self) -> bool {
-        matches!(self.tile_type, TileType::FoodSource { amount } if amount > 0)
+        matches!(self.tile_type, TileType::FoodSource { amount, .. } if amount > 0)
     }
 
     pub fn is_nest(&self) -> bool {
@@ -69,4 +195,15 @@ impl Tile {
             *stored_food += amount;
         }
     }
+
+    // Retirer jusqu'à `amount` unités des réserves du nid, renvoie la quantité réellement retirée
+    pub fn take_food_from_nest(&mut self, amount: u32) -> u32 {
+        if let TileType::Nest { stored_food, .. } = &mut self.tile_type {
+            let taken = amount.min(*stored_food);
+            *stored_food -= taken;
+            taken
+        } else {
+            0
+        }
+    }
 }
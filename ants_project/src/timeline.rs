@@ -0,0 +1,495 @@
+//! Timeline de rembobinage : états passés de la partie, compressés
+//!
+//! Copier tout l'état à chaque snapshot (grille, fourmis et toutes les Q-tables) rend le
+//! rembobinage impossible sur les longues parties. La timeline ne garde un état complet (image
+//! clé) que tous les `keyframe_interval` snapshots ; entre deux, chaque snapshot ne stocke
+//! que ce qui a changé depuis le précédent : cases et fourmis modifiées, et cases de Q-table
+//! dont la valeur diffère de la simple évaporation. Un état passé se reconstruit en rejouant
+//! ces différences depuis l'image clé qui le précède.
+
+use crate::ant::Ant;
+use crate::ants_game_manager::GameStateSnapshot;
+use crate::cli_args::SimulationConfig;
+use crate::colony::Colony;
+use crate::grid::Grid;
+use crate::linear_q::LinearQ;
+use crate::pheromone::{CellValues, PheromoneMap};
+use crate::predator::Predator;
+use crate::q_table::QTable;
+use crate::tile::Tile;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+
+// Ticks d'évaporation rejoués au plus entre deux snapshots ; au-delà, les cases qui ont
+// changé sont simplement toutes enregistrées
+const MAX_EVAPORATION_TICKS: usize = 64;
+
+/// État courant de la partie, emprunté au manager le temps de l'enregistrer
+pub(crate) struct StateRef<'a> {
+    pub tick: usize,
+    pub grid: &'a Grid,
+    pub ants: &'a [Ant],
+    pub colonies: &'a [Colony],
+    pub predators: &'a [Predator],
+}
+
+impl StateRef<'_> {
+    fn to_snapshot(&self) -> GameStateSnapshot {
+        GameStateSnapshot {
+            tick: self.tick,
+            grid: self.grid.clone(),
+            ants: self.ants.to_vec(),
+            colonies: self.colonies.to_vec(),
+            predators: self.predators.to_vec(),
+        }
+    }
+}
+
+/// Snapshots de la partie triés par tick, espacés et limités selon la configuration
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct Timeline {
+    // Le premier snapshot est toujours une image clé
+    entries: VecDeque<Entry>,
+    // État complet du dernier snapshot, base de la différence suivante
+    last: Option<GameStateSnapshot>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+struct Entry {
+    tick: usize,
+    frame: Frame,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+enum Frame {
+    Key(Box<GameStateSnapshot>),
+    Delta(Box<StateDelta>),
+}
+
+// Passage d'un snapshot au suivant : évaporation des cartes, puis changements enregistrés
+#[derive(Clone, Serialize, Deserialize)]
+struct StateDelta {
+    tick: usize,
+    evaporation: f32,
+    evaporation_ticks: usize,
+    grid: GridDelta,
+    ant_count: usize,
+    // Fourmis modifiées ou nées depuis le snapshot précédent, par indice croissant
+    ants: Vec<(usize, Ant)>,
+    // Une entrée par colonie
+    colonies: Vec<ColonyDelta>,
+    predators: Vec<Predator>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+enum GridDelta {
+    Tiles(Vec<Tile>),
+    // Grille d'une autre taille
+    Replaced(Box<Grid>),
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+enum ColonyDelta {
+    // Colonie nouvelle, ou dont les tables ont changé de forme
+    Replaced(Box<Colony>),
+    Changed {
+        score: u64,
+        ticks_since_delivery: u32,
+        upkeep_debt: f32,
+        starving_ticks: u32,
+        // (rang de la carte dans `maps`, case)
+        cells: Vec<(usize, CellValues)>,
+        scent: Vec<(usize, f32, f32)>,
+        fear: Vec<(usize, f32)>,
+        // Approximations linéaires modifiées, entières : (rang de la table dans `tables`)
+        #[serde(default)]
+        linear: Vec<(usize, LinearQ)>,
+    },
+}
+
+impl Timeline {
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Ticks des snapshots, du plus ancien au plus récent
+    pub fn ticks(&self) -> impl Iterator<Item = usize> + '_ {
+        self.entries.iter().map(|entry| entry.tick)
+    }
+
+    pub fn first_tick(&self) -> Option<usize> {
+        self.entries.front().map(|entry| entry.tick)
+    }
+
+    pub fn last_tick(&self) -> Option<usize> {
+        self.entries.back().map(|entry| entry.tick)
+    }
+
+    /// Nombre d'images clés (états complets) gardées
+    pub fn keyframe_count(&self) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| matches!(entry.frame, Frame::Key(_)))
+            .count()
+    }
+
+    /// État reconstruit du snapshot le plus proche de `tick` (le plus ancien en cas d'égalité)
+    pub fn nearest(&self, tick: usize) -> Option<GameStateSnapshot> {
+        let index = (0..self.entries.len())
+            .min_by_key(|&i| (self.entries[i].tick.abs_diff(tick), self.entries[i].tick))?;
+        Some(self.state_at(index))
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+        self.last = None;
+    }
+
+    /// Enregistre l'état du tick courant si la politique de snapshots le demande ; les
+    /// snapshots de ce tick et des suivants (un futur abandonné) sont d'abord oubliés
+    pub(crate) fn record(&mut self, state: StateRef, config: &SimulationConfig) {
+        self.truncate_from(state.tick);
+
+        let interval = config.snapshot_interval as usize;
+        if interval == 0 || !state.tick.is_multiple_of(interval) {
+            return;
+        }
+        let since_keyframe = self
+            .entries
+            .iter()
+            .rev()
+            .take_while(|entry| matches!(entry.frame, Frame::Delta(_)))
+            .count();
+        let frame = match &mut self.last {
+            Some(last) if since_keyframe + 1 < config.keyframe_interval as usize => {
+                Frame::Delta(Box::new(StateDelta::record(
+                    last,
+                    &state,
+                    config.evaporation_at(state.tick.saturating_sub(1)),
+                )))
+            }
+            _ => {
+                let snapshot = state.to_snapshot();
+                self.last = Some(snapshot.clone());
+                Frame::Key(Box::new(snapshot))
+            }
+        };
+        self.entries.push_back(Entry {
+            tick: state.tick,
+            frame,
+        });
+
+        // Tampon circulaire : on oublie les snapshots les plus anciens
+        if let Some(max) = config.max_snapshots {
+            while self.entries.len() > max {
+                self.pop_front();
+            }
+        }
+    }
+
+    // Oublie les snapshots de `tick` et des suivants
+    fn truncate_from(&mut self, tick: usize) {
+        let kept = self.entries.iter().take_while(|e| e.tick < tick).count();
+        if kept == self.entries.len() {
+            return;
+        }
+        self.entries.truncate(kept);
+        self.last = None;
+        self.last = kept.checked_sub(1).map(|index| self.state_at(index));
+    }
+
+    // Oublie le plus ancien snapshot ; le suivant devient une image clé s'il n'en est pas une
+    fn pop_front(&mut self) {
+        let Some(Entry {
+            frame: Frame::Key(mut state),
+            ..
+        }) = self.entries.pop_front()
+        else {
+            return;
+        };
+        if let Some(front) = self.entries.front_mut() {
+            if let Frame::Delta(delta) = &front.frame {
+                delta.apply(&mut state);
+                front.frame = Frame::Key(state);
+            }
+        }
+    }
+
+    // État complet du snapshot d'indice `index`, depuis l'image clé qui le précède
+    fn state_at(&self, index: usize) -> GameStateSnapshot {
+        if index + 1 == self.entries.len() {
+            if let Some(last) = &self.last {
+                return last.clone();
+            }
+        }
+        let key = (0..=index)
+            .rev()
+            .find(|&i| matches!(self.entries[i].frame, Frame::Key(_)))
+            .expect("le premier snapshot est une image clé");
+        let Frame::Key(keyframe) = &self.entries[key].frame else {
+            unreachable!()
+        };
+        let mut state = GameStateSnapshot::clone(keyframe);
+        for entry in self.entries.range(key + 1..=index) {
+            if let Frame::Delta(delta) = &entry.frame {
+                delta.apply(&mut state);
+            }
+        }
+        state
+    }
+}
+
+impl StateDelta {
+    // Différence entre `base` et `state` ; `base` devient égal à `state`
+    fn record(base: &mut GameStateSnapshot, state: &StateRef, evaporation: f32) -> StateDelta {
+        let evaporation_ticks = state
+            .tick
+            .saturating_sub(base.tick)
+            .min(MAX_EVAPORATION_TICKS);
+        evaporate(&mut base.colonies, evaporation, evaporation_ticks);
+
+        let grid = if (base.grid.get_width(), base.grid.get_height())
+            == (state.grid.get_width(), state.grid.get_height())
+        {
+            GridDelta::Tiles(changed_tiles(&base.grid, state.grid))
+        } else {
+            GridDelta::Replaced(Box::new(state.grid.clone()))
+        };
+        let ants = state
+            .ants
+            .iter()
+            .enumerate()
+            .filter(|&(i, ant)| base.ants.get(i) != Some(ant))
+            .map(|(i, ant)| (i, ant.clone()))
+            .collect();
+        let colonies = state
+            .colonies
+            .iter()
+            .enumerate()
+            .map(|(i, colony)| match base.colonies.get(i) {
+                Some(old) if same_structure(old, colony) => ColonyDelta::Changed {
+                    score: colony.score,
+                    ticks_since_delivery: colony.ticks_since_delivery,
+                    upkeep_debt: colony.upkeep_debt,
+                    starving_ticks: colony.starving_ticks,
+                    cells: maps(colony)
+                        .zip(maps(old))
+                        .enumerate()
+                        .flat_map(|(rank, (map, old))| {
+                            map.changed_cells(old)
+                                .into_iter()
+                                .map(move |cell| (rank, cell))
+                        })
+                        .collect(),
+                    scent: colony
+                        .trails
+                        .as_ref()
+                        .zip(old.trails.as_ref())
+                        .map(|(trails, old)| trails.changed_cells(old))
+                        .unwrap_or_default(),
+                    // La peur s'évapore à son propre taux : ses cases changées sont toutes notées
+                    fear: colony
+                        .fear
+                        .as_ref()
+                        .zip(old.fear.as_ref())
+                        .map(|(fear, old)| fear.changed_cells(old))
+                        .unwrap_or_default(),
+                    linear: tables(colony)
+                        .zip(tables(old))
+                        .enumerate()
+                        .filter_map(|(rank, (table, old))| {
+                            let linear = table.linear()?;
+                            (Some(linear) != old.linear()).then(|| (rank, linear.clone()))
+                        })
+                        .collect(),
+                },
+                _ => ColonyDelta::Replaced(Box::new(colony.clone())),
+            })
+            .collect();
+
+        let delta = StateDelta {
+            tick: state.tick,
+            evaporation,
+            evaporation_ticks,
+            grid,
+            ant_count: state.ants.len(),
+            ants,
+            colonies,
+            predators: state.predators.to_vec(),
+        };
+        delta.apply_changes(base);
+        delta
+    }
+
+    // Passe de l'état du snapshot précédent à celui-ci
+    fn apply(&self, state: &mut GameStateSnapshot) {
+        evaporate(
+            &mut state.colonies,
+            self.evaporation,
+            self.evaporation_ticks,
+        );
+        self.apply_changes(state);
+    }
+
+    fn apply_changes(&self, state: &mut GameStateSnapshot) {
+        state.tick = self.tick;
+        match &self.grid {
+            GridDelta::Tiles(tiles) => {
+                for tile in tiles {
+                    if let Some(cell) = state.grid.get_mut_tile(tile.position) {
+                        *cell = tile.clone();
+                    }
+                }
+            }
+            GridDelta::Replaced(grid) => state.grid = Grid::clone(grid),
+        }
+
+        state.ants.truncate(self.ant_count);
+        for (i, ant) in &self.ants {
+            match state.ants.get_mut(*i) {
+                Some(old) => *old = ant.clone(),
+                None => state.ants.push(ant.clone()),
+            }
+        }
+
+        state.colonies.truncate(self.colonies.len());
+        for (i, delta) in self.colonies.iter().enumerate() {
+            match delta {
+                ColonyDelta::Replaced(colony) if i < state.colonies.len() => {
+                    state.colonies[i] = Colony::clone(colony)
+                }
+                ColonyDelta::Replaced(colony) => state.colonies.push(Colony::clone(colony)),
+                ColonyDelta::Changed {
+                    score,
+                    ticks_since_delivery,
+                    upkeep_debt,
+                    starving_ticks,
+                    cells,
+                    scent,
+                    fear,
+                    linear,
+                } => {
+                    let colony = &mut state.colonies[i];
+                    colony.score = *score;
+                    colony.ticks_since_delivery = *ticks_since_delivery;
+                    colony.upkeep_debt = *upkeep_debt;
+                    colony.starving_ticks = *starving_ticks;
+                    let mut maps = maps_mut(colony);
+                    for (rank, cell) in cells {
+                        maps[*rank].set_cell(cell);
+                    }
+                    if let Some(trails) = &mut colony.trails {
+                        for &cell in scent {
+                            trails.set_cell(cell);
+                        }
+                    }
+                    if let Some(map) = &mut colony.fear {
+                        for &cell in fear {
+                            map.set_cell(cell);
+                        }
+                    }
+                    let mut tables = tables_mut(colony);
+                    for (rank, model) in linear {
+                        tables[*rank].set_linear(Some(model.clone()));
+                    }
+                }
+            }
+        }
+
+        state.predators.clone_from(&self.predators);
+    }
+}
+
+// Ce que deviennent les cartes après `ticks` ticks sans aucun dépôt, comme dans `game_step`
+fn evaporate(colonies: &mut [Colony], rate: f32, ticks: usize) {
+    for _ in 0..ticks {
+        for colony in colonies.iter_mut() {
+            for map in colony.active_maps_mut() {
+                map.apply_tick(rate);
+            }
+            if let Some(trails) = &mut colony.trails {
+                trails.evaporate(rate);
+            }
+        }
+    }
+}
+
+// Cases de `new` différentes de celles de `old`, de même taille
+fn changed_tiles(old: &Grid, new: &Grid) -> Vec<Tile> {
+    let mut tiles = Vec::new();
+    for y in 0..new.get_height() {
+        for x in 0..new.get_width() {
+            if let Some(tile) = new
+                .get_tile((x, y))
+                .filter(|&t| old.get_tile((x, y)) != Some(t))
+            {
+                tiles.push(tile.clone());
+            }
+        }
+    }
+    tiles
+}
+
+// Toutes les cartes d'une colonie, tables par type comprises, dans un ordre fixe
+fn tables(colony: &Colony) -> impl Iterator<Item = &QTable> {
+    std::iter::once(&colony.q_table).chain(&colony.caste_tables)
+}
+
+fn tables_mut(colony: &mut Colony) -> Vec<&mut QTable> {
+    std::iter::once(&mut colony.q_table)
+        .chain(&mut colony.caste_tables)
+        .collect()
+}
+
+fn maps(colony: &Colony) -> impl Iterator<Item = &PheromoneMap> {
+    colony
+        .q_table
+        .layers()
+        .iter()
+        .chain(colony.caste_tables.iter().flat_map(|table| table.layers()))
+}
+
+fn maps_mut(colony: &mut Colony) -> Vec<&mut PheromoneMap> {
+    colony
+        .q_table
+        .layers_mut()
+        .iter_mut()
+        .chain(
+            colony
+                .caste_tables
+                .iter_mut()
+                .flat_map(|table| table.layers_mut()),
+        )
+        .collect()
+}
+
+// Mêmes tables, de mêmes formes : les cases de l'une s'écrivent dans l'autre
+fn same_structure(old: &Colony, new: &Colony) -> bool {
+    let layouts = |colony: &Colony| {
+        std::iter::once(colony.q_table.layout())
+            .chain(colony.caste_tables.iter().map(|table| table.layout()))
+            .collect::<Vec<_>>()
+    };
+    let trails_size = |colony: &Colony| {
+        colony
+            .trails
+            .as_ref()
+            .map(|trails| (trails.width, trails.height))
+    };
+    let fear_size = |colony: &Colony| colony.fear.as_ref().map(|fear| (fear.width, fear.height));
+    let linear = |colony: &Colony| {
+        tables(colony)
+            .map(|table| table.linear().map(LinearQ::weight_count))
+            .collect::<Vec<_>>()
+    };
+    layouts(old) == layouts(new)
+        && maps(old).count() == maps(new).count()
+        && maps(old).zip(maps(new)).all(|(a, b)| a.same_shape(b))
+        && trails_size(old) == trails_size(new)
+        && fear_size(old) == fear_size(new)
+        && linear(old) == linear(new)
+}
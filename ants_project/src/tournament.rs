@@ -0,0 +1,286 @@
+//! Tournoi : deux réglages de Q-Learning s'affrontent sur la même carte
+//!
+//! `ants tournament --config-a a.toml --config-b b.toml --map m.json --rounds 10` joue
+//! plusieurs manches sur une carte à deux nids. Les deux colonies partent de la même
+//! population (celle de `--config-a`) et se disputent les mêmes sources de nourriture :
+//! seuls alpha, gamma, epsilon et leurs décroissances diffèrent. Les camps sont échangés à
+//! chaque manche pour qu'aucun réglage ne profite d'un nid mieux placé.
+
+use crate::ant::Ant;
+use crate::ants_game_manager::AntsGameManager;
+use crate::cli_args::SimulationConfig;
+use crate::colony::Colony;
+use crate::map_editor::MapEditor;
+use crate::runner::SimulationRunner;
+use rayon::prelude::*;
+use std::fs;
+
+struct TournamentArgs {
+    config_a: String,
+    config_b: String,
+    map: String,
+    rounds: u32,
+    // Durée maximale d'une manche (défaut : max_ticks de --config-a)
+    max_ticks: Option<u64>,
+    // Graine de la première manche, les suivantes utilisent seed + 1, seed + 2, ...
+    seed: u64,
+    // Résultats de chaque manche (CSV)
+    output: Option<String>,
+}
+
+/// Réglage vainqueur d'une manche
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RoundWinner {
+    A,
+    B,
+    Draw,
+}
+
+impl RoundWinner {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RoundWinner::A => "A",
+            RoundWinner::B => "B",
+            RoundWinner::Draw => "égalité",
+        }
+    }
+}
+
+/// Résultat d'une manche
+#[derive(Clone, Debug)]
+pub struct RoundResult {
+    pub round: u32,
+    pub seed: u64,
+    // Colonie jouée par le réglage A (B joue l'autre)
+    pub colony_a: usize,
+    pub ticks: u64,
+    pub food_a: u64,
+    pub food_b: u64,
+    pub winner: RoundWinner,
+}
+
+impl RoundResult {
+    /// Nourriture livrée par A moins celle livrée par B
+    pub fn differential(&self) -> i64 {
+        self.food_a as i64 - self.food_b as i64
+    }
+}
+
+// Point d'entrée de `ants tournament` : `args` ne contient que les options de la sous-commande
+pub fn run(args: &[String]) -> Result<(), String> {
+    let Some(args) = parse_args(args)? else {
+        return Ok(());
+    };
+    let config_a = SimulationConfig::load(&args.config_a)?;
+    let config_b = SimulationConfig::load(&args.config_b)?;
+    for (path, config) in [(&args.config_a, &config_a), (&args.config_b, &config_b)] {
+        config
+            .validate()
+            .map_err(|e| format!("Configuration invalide {}: {}", path, e))?;
+    }
+    let map = MapEditor::load_from_file(&args.map)
+        .map_err(|e| format!("Erreur de chargement de la carte {}: {}", args.map, e))?;
+
+    println!(
+        "Tournoi en {} manche(s) sur {} : A = {}, B = {}",
+        args.rounds, args.map, args.config_a, args.config_b
+    );
+    let results = (0..args.rounds)
+        .into_par_iter()
+        .map(|round| {
+            let seed = args.seed.wrapping_add(round as u64);
+            play_round(&config_a, &config_b, &map, round, seed, args.max_ticks)
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    print_report(&results);
+    if let Some(path) = &args.output {
+        save_csv(&results, path)?;
+    }
+    Ok(())
+}
+
+// Une manche : A joue la colonie `round % 2`, B l'autre
+fn play_round(
+    config_a: &SimulationConfig,
+    config_b: &SimulationConfig,
+    map: &MapEditor,
+    round: u32,
+    seed: u64,
+    max_ticks: Option<u64>,
+) -> Result<RoundResult, String> {
+    let config = SimulationConfig {
+        seed: Some(seed),
+        max_ticks: max_ticks.unwrap_or(config_a.max_ticks),
+        use_gui: false,
+        output_file: None,
+        // Manches jouées en parallèle : pas de timeline de rembobinage par manche
+        snapshot_interval: 0,
+        ..config_a.clone()
+    };
+    let mut manager = AntsGameManager::new(
+        map.width,
+        map.height,
+        map.to_tiles(),
+        vec![],
+        config.clone(),
+    )
+    .map_err(|e| format!("Erreur de création de la partie: {}", e))?;
+    if manager.colonies().len() != 2 {
+        return Err(format!(
+            "Un tournoi se joue sur une carte à deux nids (A et B), celle-ci en a {}",
+            manager.colonies().len()
+        ));
+    }
+    manager.set_ants(Ant::colony_from_config(&config));
+
+    let colony_a = (round % 2) as usize;
+    manager.set_colony_learning(colony_a, config_a)?;
+    manager.set_colony_learning(1 - colony_a, config_b)?;
+
+    let mut runner = SimulationRunner::new(manager).with_max_ticks(config.max_ticks);
+    let summary = runner.run_to_completion();
+    let colonies = runner.manager().colonies();
+    let (food_a, food_b) = (colonies[colony_a].score, colonies[1 - colony_a].score);
+    // Sans objectif de nourriture atteint, la manche revient à qui a le plus livré
+    let winner = match summary.winner {
+        Some(colony) if colony == colony_a => RoundWinner::A,
+        Some(_) => RoundWinner::B,
+        None if food_a > food_b => RoundWinner::A,
+        None if food_b > food_a => RoundWinner::B,
+        None => RoundWinner::Draw,
+    };
+    Ok(RoundResult {
+        round,
+        seed,
+        colony_a,
+        ticks: summary.ticks,
+        food_a,
+        food_b,
+        winner,
+    })
+}
+
+fn print_report(results: &[RoundResult]) {
+    println!(
+        "{:<8} {:>8} {:>8} {:>10} {:>10} {:>8}  vainqueur",
+        "manche", "nid A", "ticks", "livré A", "livré B", "écart"
+    );
+    for result in results {
+        println!(
+            "{:<8} {:>8} {:>8} {:>10} {:>10} {:>+8}  {}",
+            result.round,
+            Colony::label(result.colony_a),
+            result.ticks,
+            result.food_a,
+            result.food_b,
+            result.differential(),
+            result.winner.label()
+        );
+    }
+    let count = |winner: RoundWinner| results.iter().filter(|r| r.winner == winner).count();
+    let mean_differential =
+        results.iter().map(|r| r.differential() as f64).sum::<f64>() / results.len().max(1) as f64;
+    println!(
+        "Victoires : A {} - B {} ({} égalité(s)), écart moyen {:+.1} en faveur de {}",
+        count(RoundWinner::A),
+        count(RoundWinner::B),
+        count(RoundWinner::Draw),
+        mean_differential,
+        if mean_differential >= 0.0 { "A" } else { "B" }
+    );
+}
+
+// Une ligne par manche : graine, nid joué par A, nourriture livrée par chaque réglage
+fn save_csv(results: &[RoundResult], path: &str) -> Result<(), String> {
+    let mut content = String::from("round,seed,nest_a,ticks,food_a,food_b,differential,winner\n");
+    for result in results {
+        content.push_str(&format!(
+            "{},{},{},{},{},{},{},{}\n",
+            result.round,
+            result.seed,
+            Colony::label(result.colony_a),
+            result.ticks,
+            result.food_a,
+            result.food_b,
+            result.differential(),
+            match result.winner {
+                RoundWinner::A => "A",
+                RoundWinner::B => "B",
+                RoundWinner::Draw => "draw",
+            }
+        ));
+    }
+    fs::write(path, content).map_err(|e| format!("Impossible d'écrire {}: {}", path, e))
+}
+
+fn parse_args(args: &[String]) -> Result<Option<TournamentArgs>, String> {
+    let mut config_a = None;
+    let mut config_b = None;
+    let mut map = None;
+    let mut parsed = TournamentArgs {
+        config_a: String::new(),
+        config_b: String::new(),
+        map: String::new(),
+        rounds: 10,
+        max_ticks: None,
+        seed: 0,
+        output: None,
+    };
+    let mut i = 0;
+    while i < args.len() {
+        let flag = args[i].as_str();
+        if flag == "--help" || flag == "-h" {
+            print_help();
+            return Ok(None);
+        }
+        let value = args
+            .get(i + 1)
+            .ok_or_else(|| format!("{} attend une valeur", flag))?;
+        let number =
+            |expected: &str| format!("Valeur invalide pour {}: {} ({})", flag, value, expected);
+        match flag {
+            "--config-a" => config_a = Some(value.clone()),
+            "--config-b" => config_b = Some(value.clone()),
+            "--map" => map = Some(value.clone()),
+            "--rounds" => {
+                parsed.rounds = value
+                    .parse()
+                    .ok()
+                    .filter(|&rounds| rounds > 0)
+                    .ok_or_else(|| number("entier > 0"))?
+            }
+            "--max-ticks" => parsed.max_ticks = Some(value.parse().map_err(|_| number("entier"))?),
+            "--seed" => parsed.seed = value.parse().map_err(|_| number("entier"))?,
+            "--output" => parsed.output = Some(value.clone()),
+            other => return Err(format!("Argument inconnu: {}", other)),
+        }
+        i += 2;
+    }
+    let missing = |flag: &str| format!("ants tournament demande {} (voir --help)", flag);
+    parsed.config_a = config_a.ok_or_else(|| missing("--config-a"))?;
+    parsed.config_b = config_b.ok_or_else(|| missing("--config-b"))?;
+    parsed.map = map.ok_or_else(|| missing("--map"))?;
+    Ok(Some(parsed))
+}
+
+fn print_help() {
+    println!("Usage: ants tournament --config-a <FILE> --config-b <FILE> --map <FILE> [OPTIONS]");
+    println!();
+    println!("Deux colonies aux réglages de Q-Learning différents (alpha, gamma, epsilon et");
+    println!("leurs décroissances) se disputent la nourriture d'une carte à deux nids. Le reste");
+    println!("de la partie (population, récompenses, ...) vient de --config-a.");
+    println!();
+    println!("OPTIONS:");
+    println!("  --config-a <FILE>      Configuration TOML du réglage A");
+    println!("  --config-b <FILE>      Configuration TOML du réglage B");
+    println!("  --map <FILE>           Carte JSON de l'éditeur, avec les nids A et B");
+    println!(
+        "  --rounds <N>           Nombre de manches, camps échangés à chaque manche (défaut: 10)"
+    );
+    println!(
+        "  --max-ticks <N>        Durée maximale d'une manche (défaut: max_ticks de --config-a)"
+    );
+    println!("  --seed <N>             Graine de la première manche, puis N+1, N+2... (défaut: 0)");
+    println!("  --output <FILE>        Résultats de chaque manche (CSV)");
+}
@@ -0,0 +1,271 @@
+// Visualisation dans le terminal (--tui) pour les serveurs sans affichage graphique
+use crate::ant::{AntsMode, AntsType};
+use crate::ants_game_manager::AntsGameManager;
+use crate::colony::Colony;
+use crate::coord::Coord;
+use crate::runner::SimulationRunner;
+use crate::tile::TileType;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+use std::time::Duration;
+
+// Délai entre deux rafraîchissements de l'écran
+const FRAME_DELAY: Duration = Duration::from_millis(50);
+// Ticks joués par rafraîchissement, sélectionnés avec + et -
+const SPEEDS: [u64; 7] = [1, 2, 5, 10, 50, 100, 1000];
+
+#[derive(Clone, Copy, PartialEq)]
+enum PheromoneView {
+    Food,
+    Nest,
+    Hidden,
+}
+
+struct TuiState {
+    paused: bool,
+    speed: usize,
+    pheromones: PheromoneView,
+    shown_colony: usize,
+}
+
+/// Fait tourner la simulation dans le terminal jusqu'à ce que l'utilisateur quitte.
+/// Touches : espace pause, n un tick, +/- vitesse, p phéromones, c colonie, q quitter.
+pub fn run(runner: &mut SimulationRunner) -> Result<(), String> {
+    let mut terminal = ratatui::init();
+    let result = run_loop(&mut terminal, runner);
+    ratatui::restore();
+    result.map_err(|e| format!("Erreur du terminal: {}", e))
+}
+
+fn run_loop(terminal: &mut DefaultTerminal, runner: &mut SimulationRunner) -> std::io::Result<()> {
+    let mut state = TuiState {
+        paused: false,
+        speed: 0,
+        pheromones: PheromoneView::Food,
+        shown_colony: 0,
+    };
+
+    loop {
+        terminal.draw(|frame| draw(frame, runner, &state))?;
+
+        if event::poll(FRAME_DELAY)? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind != KeyEventKind::Press {
+                    continue;
+                }
+                match key.code {
+                    KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                    KeyCode::Char(' ') => state.paused = !state.paused,
+                    KeyCode::Char('n') | KeyCode::Right if state.paused => {
+                        runner.run_ticks(1);
+                    }
+                    KeyCode::Char('+') => state.speed = (state.speed + 1).min(SPEEDS.len() - 1),
+                    KeyCode::Char('-') => state.speed = state.speed.saturating_sub(1),
+                    KeyCode::Char('p') => {
+                        state.pheromones = match state.pheromones {
+                            PheromoneView::Food => PheromoneView::Nest,
+                            PheromoneView::Nest => PheromoneView::Hidden,
+                            PheromoneView::Hidden => PheromoneView::Food,
+                        }
+                    }
+                    KeyCode::Char('c') => {
+                        let count = runner.manager().colonies().len();
+                        state.shown_colony = (state.shown_colony + 1) % count;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if !state.paused {
+            runner.run_ticks(SPEEDS[state.speed]);
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, runner: &SimulationRunner, state: &TuiState) {
+    let [grid_area, stats_area] =
+        Layout::horizontal([Constraint::Min(10), Constraint::Length(34)]).areas(frame.area());
+    draw_grid(frame, grid_area, runner.manager(), state);
+    draw_stats(frame, stats_area, runner, state);
+}
+
+// Chaque case occupe deux caractères pour garder des proportions proches du carré.
+// Si la grille dépasse du terminal, seul le coin supérieur gauche est affiché.
+fn draw_grid(frame: &mut Frame, area: Rect, manager: &AntsGameManager, state: &TuiState) {
+    let grid = manager.grid();
+    let colony = &manager.colonies()[state.shown_colony.min(manager.colonies().len() - 1)];
+    // Avec des cartes par type de fourmi, on montre celles des exploratrices
+    let (food, nest) = colony.maps(AntsType::EXPLORER);
+    let pheromones = match state.pheromones {
+        PheromoneView::Food => Some((food, (139, 69, 19))),
+        PheromoneView::Nest => Some((nest, (255, 105, 180))),
+        PheromoneView::Hidden => None,
+    };
+
+    let (width, height) = (grid.get_width() as usize, grid.get_height() as usize);
+    let mut cells: Vec<Span> = Vec::with_capacity(width * height);
+    for y in 0..height as u32 {
+        for x in 0..width as u32 {
+            let mut style = Style::default().bg(Color::Rgb(30, 30, 30));
+            if let Some((map, base)) = pheromones {
+                if grid.is_walkable(x, y) {
+                    let q = map.get_max_q(x, y, grid);
+                    style = style.bg(pheromone_color(q, manager.config().q_display_scale, base));
+                }
+            }
+            let tile = grid.get_tile((x, y));
+            let (text, fg) = match tile.map(|t| &t.tile_type) {
+                Some(TileType::FoodSource { .. }) if tile.is_some_and(|t| t.is_corpse()) => {
+                    ("%%".to_string(), Color::Rgb(120, 80, 50))
+                }
+                Some(TileType::Wall) => ("██".to_string(), Color::Gray),
+                Some(TileType::DeathZone) => ("xx".to_string(), Color::Red),
+                Some(TileType::FoodSource { .. }) => ("**".to_string(), Color::Green),
+                Some(TileType::Mud { .. }) => ("~~".to_string(), Color::Rgb(139, 90, 43)),
+                Some(TileType::Tunnel { .. }) => ("()".to_string(), Color::Magenta),
+                Some(TileType::Nest { colony, .. }) => (
+                    format!("N{}", Colony::label(*colony)),
+                    colony_color(*colony),
+                ),
+                _ => ("  ".to_string(), Color::Reset),
+            };
+            cells.push(Span::styled(text, style.fg(fg)));
+        }
+    }
+
+    let shape = grid.shape();
+    for predator in manager.predators() {
+        let Some(index) = shape.index(Coord::from(predator.position)) else {
+            continue;
+        };
+        cells[index.as_usize()] = Span::styled(
+            "<>",
+            Style::default()
+                .fg(Color::Red)
+                .bg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+        );
+    }
+    // Les fourmis sont dessinées en dernier ; la lettre indique le type, la couleur la colonie
+    // et le mode (gras en retour au nid), un point signale une charge transportée
+    for ant in manager.ants() {
+        if let Some(index) = ant.coord().and_then(|coord| shape.index(coord)) {
+            let text = match (ant.ant_type, ant.current_charge > 0) {
+                (AntsType::EXPLORER, false) => "e ",
+                (AntsType::EXPLORER, true) => "e.",
+                (AntsType::PICKER, false) => "p ",
+                (AntsType::PICKER, true) => "p.",
+                (AntsType::FIGHTER, false) => "f ",
+                (AntsType::FIGHTER, true) => "f.",
+            };
+            let mut style = cells[index.as_usize()].style.fg(colony_color(ant.colony));
+            if ant.mode == AntsMode::RETURNING {
+                style = style.add_modifier(Modifier::BOLD);
+            }
+            cells[index.as_usize()] = Span::styled(text, style);
+        }
+    }
+
+    let mut lines: Vec<Line> = Vec::with_capacity(height);
+    let mut cells = cells.into_iter();
+    for _ in 0..height {
+        lines.push(Line::from(cells.by_ref().take(width).collect::<Vec<_>>()));
+    }
+
+    let title = format!(" Grille {}x{} ", width, height);
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(title)),
+        area,
+    );
+}
+
+fn draw_stats(frame: &mut Frame, area: Rect, runner: &SimulationRunner, state: &TuiState) {
+    let manager = runner.manager();
+    let metrics = manager.metrics();
+    let active = manager
+        .ants()
+        .iter()
+        .filter(|a| a.position.is_some())
+        .count();
+    let status = if runner.is_done() {
+        "Terminé"
+    } else if state.paused {
+        "En pause"
+    } else {
+        "En cours"
+    };
+    let pheromones = match state.pheromones {
+        PheromoneView::Food => "nourriture",
+        PheromoneView::Nest => "nid",
+        PheromoneView::Hidden => "masquées",
+    };
+
+    let mut lines = vec![
+        Line::from(format!("État      : {}", status)),
+        Line::from(format!("Tick      : {}", manager.current_tick_index())),
+        Line::from(format!("Vitesse   : {} tick(s)/image", SPEEDS[state.speed])),
+        Line::from(""),
+        Line::from(format!("Fourmis   : {}/{}", active, manager.ants().len())),
+        Line::from(format!("Livraisons: {}", metrics.total_food_delivered)),
+        Line::from(format!("Morts     : {}", metrics.total_deaths)),
+        Line::from(format!("Naissances: {}", metrics.total_births)),
+        Line::from(format!("Prédateurs: {}", manager.predators().len())),
+        Line::from(format!("Alpha     : {:.3}", manager.rl_params().alpha)),
+        Line::from(format!("Epsilon   : {:.3}", manager.rl_params().epsilon)),
+        Line::from(""),
+    ];
+    for colony in manager.colonies() {
+        lines.push(Line::styled(
+            format!("Colonie {} : {}", Colony::label(colony.id), colony.score),
+            Style::default().fg(colony_color(colony.id)),
+        ));
+    }
+    if let Some(winner) = manager.winner() {
+        lines.push(Line::from(format!("Gagnante  : {}", Colony::label(winner))));
+    }
+    if let Some(reason) = runner.end_reason() {
+        lines.push(Line::from(format!("Fin       : {}", reason.label())));
+    }
+    lines.extend([
+        Line::from(""),
+        Line::from(format!(
+            "Phéromones: {} ({})",
+            pheromones,
+            Colony::label(state.shown_colony)
+        )),
+        Line::from(""),
+        Line::from("espace  pause / reprise"),
+        Line::from("n       un tick (en pause)"),
+        Line::from("+ / -   vitesse"),
+        Line::from("p       phéromones affichées"),
+        Line::from("c       colonie affichée"),
+        Line::from("q       quitter"),
+    ]);
+
+    frame.render_widget(
+        Paragraph::new(lines).block(Block::bordered().title(" Statistiques ")),
+        area,
+    );
+}
+
+// `scale` : valeur Q affichée avec la couleur la plus intense (même échelle que la GUI)
+fn pheromone_color(max_q: f32, scale: f32, (r, g, b): (u8, u8, u8)) -> Color {
+    let intensity = (max_q.max(0.0) / scale).clamp(0.0, 1.0).sqrt();
+    // Mélange entre le fond (gris 30) et la couleur de la piste
+    let mix = |c: u8| (30.0 + (c as f32 - 30.0) * intensity * 0.8) as u8;
+    Color::Rgb(mix(r), mix(g), mix(b))
+}
+
+// Mêmes teintes que la GUI
+fn colony_color(colony: usize) -> Color {
+    match colony {
+        0 => Color::Rgb(255, 215, 0),
+        1 => Color::Rgb(255, 140, 0),
+        _ => Color::Rgb(100, 149, 237),
+    }
+}
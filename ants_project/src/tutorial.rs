@@ -0,0 +1,275 @@
+//! Tutoriel : une partie guidée pour découvrir le simulateur
+//!
+//! L'entrée « Tutoriel » du premier écran enchaîne les étapes de [`STEPS`] dans une fenêtre
+//! affichée par-dessus le parcours habituel de l'interface. Une étape peut charger un des
+//! scénarios livrés avec le programme ([`TUTORIAL_SCENARIOS`], au format de `--scenario`),
+//! mettre en avant un calque du plateau, puis attendre une action : un clic sur
+//! « Suivant », le lancement de la partie ou un tick donné.
+
+use crate::scenario::Scenario;
+
+/// Scénarios du tutoriel : (nom, contenu TOML). Des cartes minuscules et peu de fourmis,
+/// pour que l'apprentissage se voie en quelques centaines de ticks.
+pub const TUTORIAL_SCENARIOS: &[(&str, &str)] = &[
+    (
+        "premiers_pas",
+        r#"
+[config]
+seed = 1
+num_explorers = 4
+num_pickers = 1
+num_fighters = 0
+explorer_speed = 1
+picker_speed = 2
+epsilon = 0.2
+tick_rate = { ticks_per_second = 30 }
+
+[map]
+width = 8
+height = 5
+tiles = [
+    ["Nest", "Default", "Default", "Default", "Default", "Default", "Default", "Default"],
+    ["Default", "Default", "Wall", "Wall", "Default", "Default", "Default", "Default"],
+    ["Default", "Default", "Default", "Wall", "Default", "Default", "Default", "Default"],
+    ["Default", "Default", "Default", "Wall", "Wall", "Default", "Default", "Default"],
+    ["Default", "Default", "Default", "Default", "Default", "Default", "Default", "FoodSource"],
+]
+
+[[map.properties]]
+x = 7
+y = 4
+Food = { amount = 300 }
+"#,
+    ),
+    (
+        "zone_mortelle",
+        r#"
+[config]
+seed = 2
+num_explorers = 6
+num_pickers = 2
+num_fighters = 0
+explorer_speed = 1
+picker_speed = 2
+epsilon = 0.2
+tick_rate = { ticks_per_second = 30 }
+
+[map]
+width = 8
+height = 5
+tiles = [
+    ["Nest", "Default", "Default", "Default", "Default", "Default", "Default", "Default"],
+    ["Default", "Default", "Default", "DeathZone", "Default", "Default", "Default", "Default"],
+    ["Default", "Default", "Default", "Default", "Default", "Default", "Default", "Default"],
+    ["Default", "Default", "Default", "DeathZone", "Default", "Default", "Default", "Default"],
+    ["Default", "Default", "Default", "DeathZone", "Default", "Default", "Default", "FoodSource"],
+]
+
+[[map.properties]]
+x = 7
+y = 4
+Food = { amount = 500 }
+
+# Le passage du milieu se ferme : la colonie doit réapprendre le détour par le haut
+[[events]]
+tick = 600
+kind = "hazard"
+x = 3
+y = 2
+lethal = true
+"#,
+    ),
+];
+
+/// Calque du plateau mis en avant par une étape
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Highlight {
+    /// Réglages d'affichage laissés tels quels
+    None,
+    /// Piste vers la nourriture seule
+    FoodTrail,
+    /// Piste du retour au nid seule
+    NestTrail,
+    /// Flèches de la politique apprise
+    Policy,
+}
+
+/// Condition qui fait passer à l'étape suivante sans cliquer sur « Suivant »
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Advance {
+    /// Seulement le bouton « Suivant »
+    Next,
+    /// Le lancement de la partie
+    Started,
+    /// La partie a atteint ce tick
+    Tick(usize),
+}
+
+/// Étape du tutoriel ; le titre et le texte sont des clés de traduction
+#[derive(Clone, Copy, Debug)]
+pub struct TutorialStep {
+    pub title: &'static str,
+    pub text: &'static str,
+    /// Scénario de `TUTORIAL_SCENARIOS` chargé en arrivant sur l'étape
+    pub scenario: Option<usize>,
+    pub highlight: Highlight,
+    pub advance: Advance,
+}
+
+const fn step(
+    title: &'static str,
+    text: &'static str,
+    scenario: Option<usize>,
+    highlight: Highlight,
+    advance: Advance,
+) -> TutorialStep {
+    TutorialStep {
+        title,
+        text,
+        scenario,
+        highlight,
+        advance,
+    }
+}
+
+/// Étapes du tutoriel, dans l'ordre
+pub const STEPS: &[TutorialStep] = &[
+    step(
+        "tutorial.welcome.title",
+        "tutorial.welcome.text",
+        Some(0),
+        Highlight::None,
+        Advance::Next,
+    ),
+    step(
+        "tutorial.start.title",
+        "tutorial.start.text",
+        None,
+        Highlight::None,
+        Advance::Started,
+    ),
+    step(
+        "tutorial.explore.title",
+        "tutorial.explore.text",
+        None,
+        Highlight::None,
+        Advance::Tick(400),
+    ),
+    step(
+        "tutorial.food_trail.title",
+        "tutorial.food_trail.text",
+        None,
+        Highlight::FoodTrail,
+        Advance::Next,
+    ),
+    step(
+        "tutorial.nest_trail.title",
+        "tutorial.nest_trail.text",
+        None,
+        Highlight::NestTrail,
+        Advance::Next,
+    ),
+    step(
+        "tutorial.policy.title",
+        "tutorial.policy.text",
+        None,
+        Highlight::Policy,
+        Advance::Next,
+    ),
+    step(
+        "tutorial.sliders.title",
+        "tutorial.sliders.text",
+        None,
+        Highlight::None,
+        Advance::Next,
+    ),
+    step(
+        "tutorial.danger.title",
+        "tutorial.danger.text",
+        Some(1),
+        Highlight::FoodTrail,
+        Advance::Started,
+    ),
+    step(
+        "tutorial.hazard.title",
+        "tutorial.hazard.text",
+        None,
+        Highlight::FoodTrail,
+        Advance::Tick(900),
+    ),
+    step(
+        "tutorial.done.title",
+        "tutorial.done.text",
+        None,
+        Highlight::None,
+        Advance::Next,
+    ),
+];
+
+/// Scénario `index` de `TUTORIAL_SCENARIOS`
+pub fn scenario(index: usize) -> Result<Scenario, String> {
+    let (name, content) = TUTORIAL_SCENARIOS
+        .get(index)
+        .ok_or_else(|| format!("Scénario de tutoriel inconnu : {}", index))?;
+    Scenario::from_toml(content).map_err(|e| format!("{}: {}", name, e))
+}
+
+/// Avancement dans les étapes du tutoriel
+#[derive(Clone, Debug, Default)]
+pub struct Tutorial {
+    index: usize,
+    // Revenu en arrière : les conditions déjà remplies ne font pas repartir aussitôt
+    went_back: bool,
+}
+
+impl Tutorial {
+    pub fn new() -> Self {
+        Tutorial::default()
+    }
+
+    pub fn step(&self) -> &'static TutorialStep {
+        &STEPS[self.index]
+    }
+
+    /// Numéro de l'étape courante, à partir de 0
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn is_last(&self) -> bool {
+        self.index + 1 == STEPS.len()
+    }
+
+    /// Passe à l'étape suivante ; renvoie false sur la dernière
+    pub fn forward(&mut self) -> bool {
+        if self.is_last() {
+            return false;
+        }
+        self.index += 1;
+        self.went_back = false;
+        true
+    }
+
+    /// Revient à l'étape précédente ; renvoie false sur la première
+    pub fn back(&mut self) -> bool {
+        if self.index == 0 {
+            return false;
+        }
+        self.index -= 1;
+        self.went_back = true;
+        true
+    }
+
+    /// La condition de l'étape courante est remplie par la partie (lancée ou non, au tick
+    /// `tick`) ; jamais après un retour en arrière
+    pub fn ready(&self, started: bool, tick: usize) -> bool {
+        if self.went_back {
+            return false;
+        }
+        match self.step().advance {
+            Advance::Next => false,
+            Advance::Started => started,
+            Advance::Tick(target) => started && tick >= target,
+        }
+    }
+}